@@ -93,6 +93,14 @@ pub async fn logout(
     Err(StatusCode::BAD_REQUEST)
 }
 
+/// Issue a short-lived token for opening a log stream with `EventSource`
+pub async fn stream_token(
+    user: AuthUser,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<crate::auth::StreamTokenResponse>, crate::auth::AuthError> {
+    state.auth.issue_stream_token(&user.claims).map(Json)
+}
+
 #[derive(Serialize)]
 pub struct LogoutResponse {
     pub message: String,
@@ -166,16 +174,27 @@ pub async fn create_user(
 pub struct AuditLogsQuery {
     #[serde(default = "default_limit")]
     pub limit: usize,
+    /// Earliest timestamp (RFC 3339), inclusive
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Latest timestamp (RFC 3339), inclusive
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    /// User ID or username
+    pub actor: Option<String>,
+    pub action: Option<String>,
 }
 
 fn default_limit() -> usize {
     100
 }
 
+/// Most audit log entries returned by one request
+const MAX_AUDIT_LOGS_LIMIT: usize = 1000;
+
 #[derive(Serialize)]
 pub struct AuditLogsResponse {
     pub logs: Vec<crate::audit::AuditLogEntry>,
-    pub total: usize,
+    /// Whether older matching entries were left out by the limit
+    pub has_more: bool,
 }
 
 pub async fn get_audit_logs(
@@ -188,9 +207,21 @@ pub async fn get_audit_logs(
         return Err(StatusCode::FORBIDDEN);
     }
     
-    let all_logs = state.audit.get_logs();
-    let total = all_logs.len();
-    let logs = state.audit.get_recent_logs(query.limit);
+    let filter = crate::audit::AuditFilter {
+        since: query.since,
+        until: query.until,
+        actor: query.actor,
+        action: query.action,
+    };
+    let limit = query.limit.min(MAX_AUDIT_LOGS_LIMIT);
+    // Most recent matches, plus one to tell whether any were left out
+    let audit = state.audit.clone();
+    let mut logs = tokio::task::spawn_blocking(move || audit.query(&filter, limit + 1))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let excess = logs.len().saturating_sub(limit);
+    logs.drain(..excess);
+    let has_more = excess > 0;
     
     state.audit.log_success(
         user.claims.sub.clone(),
@@ -200,5 +231,5 @@ pub async fn get_audit_logs(
         Some(format!("Retrieved {} logs", logs.len())),
     );
     
-    Ok(Json(AuditLogsResponse { logs, total }))
+    Ok(Json(AuditLogsResponse { logs, has_more }))
 }
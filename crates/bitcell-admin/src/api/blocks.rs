@@ -9,6 +9,7 @@ use serde::Serialize;
 use std::sync::Arc;
 
 use crate::AppState;
+use bitcell_ca::replay::{self, CellRun};
 use bitcell_ca::{Battle, BattleOutcome, Glider, GliderPattern, Position};
 
 #[derive(Debug, Serialize)]
@@ -51,7 +52,10 @@ pub struct TransactionInfo {
 #[derive(Debug, Serialize)]
 pub struct BlockBattleFrame {
     pub step: usize,
-    pub grid: Vec<Vec<u8>>,
+    /// Side length of the downsampled grid
+    pub size: usize,
+    /// Live cells of the downsampled grid, run-length encoded row-major
+    pub cells: Vec<CellRun>,
     pub energy_a: u64,
     pub energy_b: u64,
 }
@@ -88,7 +92,7 @@ pub async fn list_blocks(
     let endpoints: Vec<(String, String)> = nodes
         .iter()
         .map(|n| {
-            let metrics_port = n.port + 1;
+            let metrics_port = bitcell_node::config::metrics_port_for(n.port);
             (n.id.clone(), format!("http://127.0.0.1:{}/metrics", metrics_port))
         })
         .collect();
@@ -235,7 +239,8 @@ pub async fn get_block_battles(
             
             frames.push(BlockBattleFrame {
                 step,
-                grid: downsampled,
+                size: downsample_size,
+                cells: replay::encode_cells(&downsampled.concat()),
                 energy_a,
                 energy_b,
             });
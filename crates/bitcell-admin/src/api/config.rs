@@ -67,15 +67,46 @@ pub async fn get_config(
 }
 
 /// Update configuration
+///
+/// Running nodes are told about the settings they can apply live
+/// (currently `max_peers`); the rest takes effect on their next start.
 pub async fn update_config(
     State(state): State<Arc<AppState>>,
     Json(config): Json<Config>,
 ) -> Result<Json<Config>, (StatusCode, Json<String>)> {
+    let max_peers = config.network.max_peers;
+    if !(1..=bitcell_node::config::MAX_PEERS_LIMIT).contains(&max_peers) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(format!(
+                "max_peers must be between 1 and {}",
+                bitcell_node::config::MAX_PEERS_LIMIT
+            )),
+        ));
+    }
+
     match state.config.update_config(config.clone()) {
-        Ok(_) => Ok(Json(config)),
+        Ok(_) => {
+            notify_running_nodes(&state, serde_json::json!({ "max_peers": max_peers })).await;
+            Ok(Json(config))
+        }
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(format!("Failed to update config: {}", e)),
         )),
     }
 }
+
+/// Push live-reloadable settings to every running node
+async fn notify_running_nodes(state: &AppState, changes: serde_json::Value) {
+    for id in state.process.running_node_ids() {
+        let result = match state.process.admin_rpc_url(&id) {
+            Ok(rpc_url) => super::nodes::send_reload(&rpc_url, changes.clone()).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(reload) => tracing::info!("Pushed config to node '{}': {}", id, reload),
+            Err(e) => tracing::warn!("Failed to push config to node '{}': {}", id, e),
+        }
+    }
+}
@@ -1,11 +1,12 @@
 //! Faucet API endpoints
 
 use axum::{
-    extract::{State, Json},
+    extract::{ConnectInfo, State, Json},
     response::IntoResponse,
-    http::StatusCode,
+    http::{header, StatusCode},
 };
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
 use std::sync::Arc;
 use crate::{AppState, faucet::{FaucetError, FaucetRequest as ServiceRequest}};
 
@@ -25,6 +26,8 @@ pub struct FaucetResponse {
     pub message: String,
     pub tx_hash: Option<String>,
     pub amount: Option<u64>,
+    /// Seconds until a rate-limited request would be accepted
+    pub retry_after_seconds: Option<u64>,
 }
 
 /// Faucet info response
@@ -40,6 +43,7 @@ pub struct FaucetInfoResponse {
 /// Request testnet tokens
 pub async fn request_tokens(
     State(state): State<Arc<AppState>>,
+    client: Option<ConnectInfo<SocketAddr>>,
     Json(req): Json<FaucetRequest>,
 ) -> impl IntoResponse {
     let faucet = match &state.faucet {
@@ -51,13 +55,22 @@ pub async fn request_tokens(
                 message: "Faucet not enabled".to_string(),
                 tx_hash: None,
                 amount: None,
+                retry_after_seconds: None,
             })
         ).into_response(),
     };
 
+    let client_ip = client.map(|ConnectInfo(addr)| addr.ip());
+
+    // Reject rate-limited callers before touching the node
+    if let Err(FaucetError::RateLimited(seconds)) = faucet.check_eligibility(&req.address, client_ip) {
+        return rate_limited(seconds);
+    }
+
     match faucet.process_request(
         &req.address,
         req.captcha_response.as_deref(),
+        client_ip,
     ).await {
         Ok(request) => {
             Json(FaucetResponse {
@@ -68,14 +81,12 @@ pub async fn request_tokens(
                 ),
                 tx_hash: Some(request.tx_hash),
                 amount: Some(request.amount),
+                retry_after_seconds: None,
             }).into_response()
         }
+        Err(FaucetError::RateLimited(seconds)) => rate_limited(seconds),
         Err(e) => {
             let (status, message) = match e {
-                FaucetError::RateLimited(seconds) => (
-                    StatusCode::TOO_MANY_REQUESTS,
-                    format!("Rate limit exceeded. Try again in {} seconds", seconds)
-                ),
                 FaucetError::InvalidAddress(msg) => (
                     StatusCode::BAD_REQUEST,
                     msg
@@ -101,12 +112,28 @@ pub async fn request_tokens(
                     message,
                     tx_hash: None,
                     amount: None,
+                    retry_after_seconds: None,
                 })
             ).into_response()
         }
     }
 }
 
+/// 429 response carrying the wait both as a header and in the body
+fn rate_limited(seconds: u64) -> axum::response::Response {
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(header::RETRY_AFTER, seconds.to_string())],
+        Json(FaucetResponse {
+            success: false,
+            message: format!("Rate limit exceeded. Try again in {} seconds", seconds),
+            tx_hash: None,
+            amount: None,
+            retry_after_seconds: Some(seconds),
+        })
+    ).into_response()
+}
+
 /// Get faucet information
 pub async fn get_info(
     State(state): State<Arc<AppState>>,
@@ -176,6 +203,7 @@ pub struct CheckEligibilityResponse {
 
 pub async fn check_eligibility(
     State(state): State<Arc<AppState>>,
+    client: Option<ConnectInfo<SocketAddr>>,
     Json(req): Json<CheckEligibilityRequest>,
 ) -> impl IntoResponse {
     let faucet = match &state.faucet {
@@ -183,7 +211,9 @@ pub async fn check_eligibility(
         None => return (StatusCode::NOT_FOUND, "Faucet not enabled").into_response(),
     };
 
-    match faucet.check_rate_limit(&req.address) {
+    let client_ip = client.map(|ConnectInfo(addr)| addr.ip());
+
+    match faucet.check_eligibility(&req.address, client_ip) {
         Ok(_) => Json(CheckEligibilityResponse {
             eligible: true,
             message: "Address is eligible for faucet request".to_string(),
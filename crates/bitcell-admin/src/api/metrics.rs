@@ -81,10 +81,7 @@ pub async fn get_metrics(
     // Get endpoints for metrics fetching (try all nodes)
     let endpoints: Vec<(String, String)> = all_nodes
         .iter()
-        .map(|n| {
-            let metrics_port = n.port + 1; // Metrics port is node port + 1
-            (n.id.clone(), format!("http://127.0.0.1:{}/metrics", metrics_port))
-        })
+        .map(|n| (n.id.clone(), crate::metrics_client::metrics_endpoint(n)))
         .collect();
 
     if endpoints.is_empty() {
@@ -136,6 +133,21 @@ pub async fn get_metrics(
     Ok(Json(response))
 }
 
+/// Get a cluster-wide rollup across all registered nodes
+pub async fn cluster_metrics(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<crate::metrics_client::ClusterMetrics>, (StatusCode, Json<String>)> {
+    let nodes = state.process.list_nodes();
+    if nodes.is_empty() {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json("No nodes configured. Please deploy nodes first.".to_string()),
+        ));
+    }
+
+    Ok(Json(state.metrics_client.aggregate(&nodes).await))
+}
+
 /// Get chain-specific metrics
 pub async fn chain_metrics(
     State(state): State<Arc<AppState>>,
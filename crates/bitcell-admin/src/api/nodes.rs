@@ -3,10 +3,17 @@
 use axum::{
     extract::{Path, State},
     http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
     Json,
 };
+use futures::Stream;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
 
 use crate::{AppState, auth::AuthUser};
 use super::NodeInfo;
@@ -224,6 +231,79 @@ pub async fn delete_node(
     }
 }
 
+/// Push config changes to a running node without restarting it
+///
+/// The body is a partial node config, e.g. `{"tx_pool_size": 5000}`. The
+/// node applies its reloadable fields live and lists the rest under
+/// `restart_required`.
+pub async fn reload_node_config(
+    user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(changes): Json<serde_json::Value>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    validate_node_id(&id)?;
+
+    let result = match state.process.admin_rpc_url(&id) {
+        Ok(rpc_url) => send_reload(&rpc_url, changes).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(reload) => {
+            tracing::info!("Reloaded config of node '{}': {}", id, reload);
+            state.audit.log_success(
+                user.claims.sub,
+                user.claims.username,
+                "reload_node_config".to_string(),
+                id.clone(),
+                Some(reload.to_string()),
+            );
+            Ok(Json(reload))
+        }
+        Err(e) => {
+            state.audit.log_failure(
+                user.claims.sub,
+                user.claims.username,
+                "reload_node_config".to_string(),
+                id.clone(),
+                e.clone(),
+            );
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Failed to reload config of node '{}': {}", id, e),
+                }),
+            ))
+        }
+    }
+}
+
+/// Call `bitcell_reloadConfig` on a node's admin RPC listener
+pub(crate) async fn send_reload(rpc_url: &str, changes: serde_json::Value) -> Result<serde_json::Value, String> {
+    let rpc_req = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "bitcell_reloadConfig",
+        "params": [changes],
+        "id": 1
+    });
+
+    let response: serde_json::Value = reqwest::Client::new()
+        .post(rpc_url)
+        .json(&rpc_req)
+        .send()
+        .await
+        .map_err(|e| format!("RPC request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Invalid RPC response: {}", e))?;
+
+    if let Some(error) = response.get("error").filter(|e| !e.is_null()) {
+        return Err(error["message"].as_str().unwrap_or("Unknown RPC error").to_string());
+    }
+    Ok(response["result"].clone())
+}
+
 #[derive(Debug, Deserialize)]
 pub struct LogParams {
     #[serde(default = "default_lines")]
@@ -273,3 +353,182 @@ pub async fn get_node_logs(
         }
     }
 }
+
+/// Interval between keep-alive comments on a log stream
+const LOG_STREAM_HEARTBEAT: Duration = Duration::from_secs(15);
+
+/// How often a streamed log file is checked for new lines
+const LOG_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Most bytes read from a streamed log file per poll
+const LOG_READ_CHUNK: u64 = 1024 * 1024;
+
+/// Longest line sent on a log stream; longer runs without a newline are split
+const MAX_LOG_LINE: usize = 64 * 1024;
+
+/// Stream a node's log over Server-Sent Events
+///
+/// Each line appended to the log after the client connects is sent as one
+/// event; a `: heartbeat` comment goes out every 15 seconds. Browsers
+/// authenticate with a token from `/api/auth/stream-token` in `?token=`.
+pub async fn stream_node_logs(
+    user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, String)> {
+    validate_node_id(&id).map_err(|e| (e.0, e.1.error.clone()))?;
+
+    let log_path = state.process.get_log_path(&id)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Node '{}' not found", id)))?;
+
+    state.audit.log_success(
+        user.claims.sub,
+        user.claims.username,
+        "stream_node_logs".to_string(),
+        id,
+        None,
+    );
+
+    Ok(Sse::new(tail_log(PathBuf::from(log_path)))
+        .keep_alive(KeepAlive::new().interval(LOG_STREAM_HEARTBEAT).text("heartbeat")))
+}
+
+/// Follow `path` from its current end, yielding each new line as an event
+///
+/// A missing file is followed from its start once created, and a file that
+/// shrinks (truncated or rotated) is re-read from the beginning.
+fn tail_log(path: PathBuf) -> impl Stream<Item = Result<Event, Infallible>> {
+    let offset = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    let tail = LogTail {
+        path,
+        offset,
+        partial: Vec::new(),
+        lines: VecDeque::new(),
+    };
+
+    futures::stream::unfold(tail, |mut tail| async move {
+        let line = tail.next_line().await;
+        Some((Ok(Event::default().data(line)), tail))
+    })
+}
+
+/// Read position in a followed log file
+struct LogTail {
+    path: PathBuf,
+    offset: u64,
+    /// Bytes after the last newline, completed by a later write
+    partial: Vec<u8>,
+    lines: VecDeque<String>,
+}
+
+impl LogTail {
+    async fn next_line(&mut self) -> String {
+        loop {
+            if let Some(line) = self.lines.pop_front() {
+                return line;
+            }
+            tokio::time::sleep(LOG_POLL_INTERVAL).await;
+            // Unreadable files are retried on the next poll
+            let _ = self.poll().await;
+        }
+    }
+
+    async fn poll(&mut self) -> std::io::Result<()> {
+        let mut file = tokio::fs::File::open(&self.path).await?;
+        let len = file.metadata().await?.len();
+        if len < self.offset {
+            self.offset = 0;
+            self.partial.clear();
+        }
+        if len == self.offset {
+            return Ok(());
+        }
+
+        // A large backlog is drained over several polls
+        file.seek(std::io::SeekFrom::Start(self.offset)).await?;
+        let read = file.take(LOG_READ_CHUNK).read_to_end(&mut self.partial).await?;
+        self.offset += read as u64;
+
+        loop {
+            let end = match self.partial.iter().position(|&b| b == b'\n') {
+                Some(end) if end < MAX_LOG_LINE => end + 1,
+                _ if self.partial.len() >= MAX_LOG_LINE => MAX_LOG_LINE,
+                _ => break,
+            };
+            let line: Vec<u8> = self.partial.drain(..end).collect();
+            let line = String::from_utf8_lossy(&line);
+            self.lines.push_back(line.trim_end_matches(['\r', '\n']).to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::IntoResponse;
+    use futures::StreamExt;
+    use std::io::Write;
+
+    #[tokio::test]
+    async fn test_log_stream_sends_new_lines() {
+        let path = std::env::temp_dir().join(format!("bitcell-log-stream-{}.log", std::process::id()));
+        std::fs::write(&path, "already written\n").unwrap();
+
+        let response = Sse::new(tail_log(path.clone())).into_response();
+        let mut body = response.into_body().into_data_stream();
+
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "block 1 produced").unwrap();
+        write!(file, "block 2 ").unwrap();
+        file.flush().unwrap();
+
+        let mut received = String::new();
+        while !received.contains("data: block 1 produced") {
+            let chunk = tokio::time::timeout(Duration::from_secs(5), body.next())
+                .await
+                .expect("no event within timeout")
+                .unwrap()
+                .unwrap();
+            received.push_str(&String::from_utf8_lossy(&chunk));
+        }
+        assert!(!received.contains("already written"));
+
+        // A partial line is held back until its newline arrives
+        writeln!(file, "produced").unwrap();
+        file.flush().unwrap();
+        let chunk = tokio::time::timeout(Duration::from_secs(5), body.next())
+            .await
+            .expect("no event within timeout")
+            .unwrap()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&chunk), "data: block 2 produced\n\n");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_log_tail_bounds_reads_and_lines() {
+        let path = std::env::temp_dir().join(format!("bitcell-log-bounds-{}.log", std::process::id()));
+        let mut contents = vec![b'x'; MAX_LOG_LINE + 10];
+        contents.push(b'\n');
+        contents.resize(3 * LOG_READ_CHUNK as usize, b'y');
+        std::fs::write(&path, &contents).unwrap();
+
+        let mut tail = LogTail {
+            path: path.clone(),
+            offset: 0,
+            partial: Vec::new(),
+            lines: VecDeque::new(),
+        };
+        tail.poll().await.unwrap();
+
+        // One chunk per poll, and over-long lines are split
+        assert_eq!(tail.offset, LOG_READ_CHUNK);
+        assert_eq!(tail.lines[0].len(), MAX_LOG_LINE);
+        assert_eq!(tail.lines[1].len(), 10);
+        assert!(tail.partial.len() < MAX_LOG_LINE);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
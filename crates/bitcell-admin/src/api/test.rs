@@ -11,6 +11,7 @@ use std::sync::Arc;
 use crate::AppState;
 
 // Import BitCell types
+use bitcell_ca::replay::{self, CellRun};
 use bitcell_ca::{Battle, Glider, GliderPattern, Position, BattleOutcome};
 
 #[derive(Debug, Deserialize)]
@@ -52,7 +53,10 @@ pub struct BattleVisualizationResponse {
 #[derive(Debug, Serialize)]
 pub struct BattleFrame {
     pub step: usize,
-    pub grid: Vec<Vec<u8>>,
+    /// Side length of the downsampled grid
+    pub size: usize,
+    /// Live cells of the downsampled grid, run-length encoded row-major
+    pub cells: Vec<CellRun>,
     pub energy_a: u64,
     pub energy_b: u64,
 }
@@ -234,7 +238,8 @@ pub async fn run_battle_visualization(
 
             frames.push(BattleFrame {
                 step,
-                grid: downsampled,
+                size: downsample_size,
+                cells: replay::encode_cells(&downsampled.concat()),
                 energy_a,
                 energy_b,
             });
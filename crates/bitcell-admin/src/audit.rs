@@ -1,15 +1,31 @@
 //! Audit logging for admin console actions
 //!
 //! Tracks all administrative actions for security and compliance.
+//!
+//! Entries are kept in memory and, with [`AuditLogger::with_storage`],
+//! appended as JSON lines to `audit.jsonl`. Files are rotated by size to
+//! `audit.jsonl.1`, `audit.jsonl.2`, ... and the oldest are deleted.
 
 use chrono::{DateTime, Utc};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 
 /// Maximum number of audit log entries to keep in memory
 const MAX_AUDIT_LOGS: usize = 10_000;
 
+/// Name of the active audit log file
+pub const AUDIT_LOG_FILE: &str = "audit.jsonl";
+
+/// Size at which the active audit log file is rotated (10 MB)
+pub const DEFAULT_MAX_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Rotated audit log files kept alongside the active one
+pub const MAX_ROTATED_FILES: usize = 5;
+
 /// Audit log entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditLogEntry {
@@ -25,19 +41,159 @@ pub struct AuditLogEntry {
     pub error_message: Option<String>,
 }
 
+/// Audit log query; unset fields match everything
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AuditFilter {
+    /// Earliest timestamp, inclusive
+    pub since: Option<DateTime<Utc>>,
+    /// Latest timestamp, inclusive
+    pub until: Option<DateTime<Utc>>,
+    /// User ID or username
+    pub actor: Option<String>,
+    pub action: Option<String>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, entry: &AuditLogEntry) -> bool {
+        self.since.is_none_or(|since| entry.timestamp >= since)
+            && self.until.is_none_or(|until| entry.timestamp <= until)
+            && self.actor.as_ref().is_none_or(|actor| entry.user_id == *actor || entry.username == *actor)
+            && self.action.as_ref().is_none_or(|action| entry.action == *action)
+    }
+}
+
+/// Append-only JSON lines storage
+struct AuditStorage {
+    dir: PathBuf,
+    file: File,
+    size: u64,
+    max_file_bytes: u64,
+}
+
+impl AuditStorage {
+    fn open(dir: &Path, max_file_bytes: u64) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        let file = OpenOptions::new().create(true).append(true).open(dir.join(AUDIT_LOG_FILE))?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            file,
+            size,
+            max_file_bytes,
+        })
+    }
+
+    /// Audit log files, oldest first
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = (1..=MAX_ROTATED_FILES)
+            .rev()
+            .map(|i| self.dir.join(format!("{}.{}", AUDIT_LOG_FILE, i)))
+            .collect();
+        files.push(self.dir.join(AUDIT_LOG_FILE));
+        files.retain(|path| path.exists());
+        files
+    }
+
+    fn append(&mut self, entry: &AuditLogEntry) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        if self.size > 0 && self.size + line.len() as u64 > self.max_file_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(&line)?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    /// Shift `audit.jsonl.N` to `.N+1`, dropping the oldest, and start a new file
+    fn rotate(&mut self) -> std::io::Result<()> {
+        let rotated = |i: usize| self.dir.join(format!("{}.{}", AUDIT_LOG_FILE, i));
+        let oldest = rotated(MAX_ROTATED_FILES);
+        if oldest.exists() {
+            std::fs::remove_file(oldest)?;
+        }
+        for i in (1..MAX_ROTATED_FILES).rev() {
+            if rotated(i).exists() {
+                std::fs::rename(rotated(i), rotated(i + 1))?;
+            }
+        }
+        std::fs::rename(self.dir.join(AUDIT_LOG_FILE), rotated(1))?;
+
+        self.file = OpenOptions::new().create(true).append(true).open(self.dir.join(AUDIT_LOG_FILE))?;
+        self.size = 0;
+        Ok(())
+    }
+
+    /// Open every audit log file, oldest first
+    ///
+    /// Open handles stay readable if a later append rotates or deletes the
+    /// files, so they can be scanned without holding the storage lock.
+    fn open_files(&self) -> std::io::Result<Vec<File>> {
+        self.files().into_iter().map(File::open).collect()
+    }
+}
+
+/// Read the newest `limit` entries matching `filter` from `files` (oldest
+/// first), returned oldest first
+///
+/// Files are scanned newest first and older files are skipped once `limit`
+/// matches are found. Lines that fail to parse (e.g. a torn final write) are
+/// skipped.
+fn read_entries(files: Vec<File>, filter: &AuditFilter, limit: usize) -> std::io::Result<Vec<AuditLogEntry>> {
+    let mut newest_first: Vec<AuditLogEntry> = Vec::new();
+    for file in files.into_iter().rev() {
+        if newest_first.len() >= limit {
+            break;
+        }
+        let mut matched = Vec::new();
+        for line in BufReader::new(file).lines() {
+            if let Ok(entry) = serde_json::from_str::<AuditLogEntry>(&line?) {
+                if filter.matches(&entry) {
+                    matched.push(entry);
+                }
+            }
+        }
+        newest_first.extend(matched.into_iter().rev());
+    }
+    newest_first.truncate(limit);
+    newest_first.reverse();
+    Ok(newest_first)
+}
+
 /// Audit logger
 pub struct AuditLogger {
     logs: RwLock<VecDeque<AuditLogEntry>>,
+    storage: Option<Mutex<AuditStorage>>,
 }
 
 impl AuditLogger {
-    /// Create a new audit logger
+    /// Create a new in-memory audit logger
     pub fn new() -> Self {
         Self {
             logs: RwLock::new(VecDeque::with_capacity(MAX_AUDIT_LOGS)),
+            storage: None,
         }
     }
 
+    /// Create an audit logger persisting to `dir`, rotating at 10 MB
+    ///
+    /// The most recent stored entries are loaded back into memory.
+    pub fn with_storage(dir: impl AsRef<Path>) -> std::io::Result<Self> {
+        Self::with_storage_limit(dir, DEFAULT_MAX_FILE_BYTES)
+    }
+
+    /// Create an audit logger persisting to `dir`, rotating at `max_file_bytes`
+    pub fn with_storage_limit(dir: impl AsRef<Path>, max_file_bytes: u64) -> std::io::Result<Self> {
+        let storage = AuditStorage::open(dir.as_ref(), max_file_bytes)?;
+        let logs: VecDeque<AuditLogEntry> =
+            read_entries(storage.open_files()?, &AuditFilter::default(), MAX_AUDIT_LOGS)?.into();
+
+        Ok(Self {
+            logs: RwLock::new(logs),
+            storage: Some(Mutex::new(storage)),
+        })
+    }
+
     /// Log an action
     pub fn log(
         &self,
@@ -62,6 +218,12 @@ impl AuditLogger {
             error_message: error_message.clone(),
         };
 
+        if let Some(storage) = &self.storage {
+            if let Err(e) = storage.lock().append(&entry) {
+                tracing::error!("Failed to persist audit log entry: {}", e);
+            }
+        }
+
         let mut logs = self.logs.write();
         
         // Remove oldest entry if at capacity
@@ -155,6 +317,33 @@ impl AuditLogger {
             .collect()
     }
 
+    /// Get the newest `limit` logs matching `filter`, oldest first
+    ///
+    /// With storage this covers everything still on disk, including rotated
+    /// files; otherwise only the entries held in memory. Reading storage is
+    /// blocking IO, so async callers should run it with `spawn_blocking`.
+    pub fn query(&self, filter: &AuditFilter, limit: usize) -> Vec<AuditLogEntry> {
+        if let Some(storage) = &self.storage {
+            // Only opening the files needs the lock; appends continue during the scan
+            let files = storage.lock().open_files();
+            match files.and_then(|files| read_entries(files, filter, limit)) {
+                Ok(entries) => return entries,
+                Err(e) => tracing::error!("Failed to read audit log storage: {}", e),
+            }
+        }
+
+        let logs = self.logs.read();
+        let mut entries: Vec<AuditLogEntry> = logs
+            .iter()
+            .rev()
+            .filter(|log| filter.matches(log))
+            .take(limit)
+            .cloned()
+            .collect();
+        entries.reverse();
+        entries
+    }
+
     /// Get recent logs (last N entries)
     pub fn get_recent_logs(&self, count: usize) -> Vec<AuditLogEntry> {
         let logs = self.logs.read();
@@ -162,7 +351,7 @@ impl AuditLogger {
         logs.iter().skip(start).cloned().collect()
     }
 
-    /// Clear all logs (admin only)
+    /// Clear all in-memory logs (admin only); stored files are kept
     pub fn clear_logs(&self) {
         self.logs.write().clear();
         tracing::warn!("Audit logs cleared");
@@ -358,4 +547,99 @@ mod tests {
         logger.clear_logs();
         assert_eq!(logger.count(), 0);
     }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bitcell-audit-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn log_as(logger: &AuditLogger, user: &str, action: &str) {
+        logger.log_success(
+            format!("{}-id", user),
+            user.to_string(),
+            action.to_string(),
+            "node1".to_string(),
+            None,
+        );
+    }
+
+    #[test]
+    fn test_query_persisted_by_actor_and_time() {
+        let dir = temp_dir("query");
+        let logger = AuditLogger::with_storage(&dir).unwrap();
+
+        log_as(&logger, "alice", "start_node");
+        log_as(&logger, "bob", "stop_node");
+        let between = Utc::now();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        log_as(&logger, "alice", "stop_node");
+        log_as(&logger, "bob", "deploy_node");
+        drop(logger);
+
+        // A restarted logger sees the stored entries
+        let logger = AuditLogger::with_storage(&dir).unwrap();
+        assert_eq!(logger.count(), 4);
+
+        let alice = logger.query(&AuditFilter {
+            actor: Some("alice".to_string()),
+            ..Default::default()
+        }, usize::MAX);
+        assert_eq!(alice.len(), 2);
+        assert!(alice.iter().all(|e| e.username == "alice"));
+
+        // The actor may also be given by user ID
+        let by_id = logger.query(&AuditFilter {
+            actor: Some("bob-id".to_string()),
+            action: Some("deploy_node".to_string()),
+            ..Default::default()
+        }, usize::MAX);
+        assert_eq!(by_id.len(), 1);
+
+        let recent = logger.query(&AuditFilter {
+            since: Some(between),
+            ..Default::default()
+        }, usize::MAX);
+        assert_eq!(
+            recent.iter().map(|e| e.action.as_str()).collect::<Vec<_>>(),
+            vec!["stop_node", "deploy_node"]
+        );
+
+        let earlier = logger.query(&AuditFilter {
+            until: Some(between),
+            ..Default::default()
+        }, usize::MAX);
+        assert_eq!(earlier.len(), 2);
+
+        // A limit keeps the newest matches
+        let latest = logger.query(&AuditFilter::default(), 3);
+        assert_eq!(
+            latest.iter().map(|e| e.action.as_str()).collect::<Vec<_>>(),
+            vec!["stop_node", "stop_node", "deploy_node"]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_storage_rotates_by_size() {
+        let dir = temp_dir("rotate");
+        let logger = AuditLogger::with_storage_limit(&dir, 1024).unwrap();
+
+        for i in 0..100 {
+            log_as(&logger, "alice", &format!("action{}", i));
+        }
+
+        assert!(dir.join(AUDIT_LOG_FILE).metadata().unwrap().len() <= 1024);
+        assert!(dir.join(format!("{}.{}", AUDIT_LOG_FILE, MAX_ROTATED_FILES)).exists());
+        assert!(!dir.join(format!("{}.{}", AUDIT_LOG_FILE, MAX_ROTATED_FILES + 1)).exists());
+
+        // Only the newest files survive, in order
+        let stored = logger.query(&AuditFilter::default(), usize::MAX);
+        assert!(stored.len() < 100);
+        assert_eq!(stored.last().unwrap().action, "action99");
+        assert!(stored.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -60,6 +60,32 @@ pub struct Claims {
     pub exp: i64,         // Expiry time
     pub iat: i64,         // Issued at
     pub jti: String,      // JWT ID (for token revocation)
+    /// Restricts the token to one use (e.g. `STREAM_TOKEN_SCOPE`); unscoped
+    /// tokens are ordinary access tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+/// Scope of the short-lived tokens accepted in the query string of log streams
+///
+/// `EventSource` cannot send an Authorization header, so the dashboard trades
+/// its access token for one of these and passes it as `?token=`.
+pub const STREAM_TOKEN_SCOPE: &str = "log_stream";
+
+/// Lifetime of a stream token in seconds; it only has to outlive the connect
+pub const STREAM_TOKEN_TTL_SECS: i64 = 60;
+
+/// Query string carrying a stream token
+#[derive(Debug, Deserialize)]
+struct StreamTokenQuery {
+    token: String,
+}
+
+/// Stream token response
+#[derive(Debug, Serialize)]
+pub struct StreamTokenResponse {
+    pub token: String,
+    pub expires_in: i64,
 }
 
 /// Authentication request
@@ -161,6 +187,16 @@ impl AuthManager {
 
     /// Generate JWT token
     fn generate_token(&self, user: &User, expires_in: i64) -> Result<String, AuthError> {
+        self.generate_scoped_token(user, expires_in, None)
+    }
+
+    /// Generate JWT token limited to `scope`
+    fn generate_scoped_token(
+        &self,
+        user: &User,
+        expires_in: i64,
+        scope: Option<&str>,
+    ) -> Result<String, AuthError> {
         let now = Utc::now();
         let claims = Claims {
             sub: user.id.clone(),
@@ -169,6 +205,7 @@ impl AuthManager {
             exp: (now + Duration::seconds(expires_in)).timestamp(),
             iat: now.timestamp(),
             jti: Uuid::new_v4().to_string(),
+            scope: scope.map(str::to_string),
         };
 
         encode(&Header::new(Algorithm::HS256), &claims, &self.jwt_secret)
@@ -193,6 +230,9 @@ impl AuthManager {
     /// Refresh access token using refresh token
     pub fn refresh(&self, req: RefreshRequest) -> Result<AuthResponse, AuthError> {
         let claims = self.validate_token(&req.refresh_token)?;
+        if claims.scope.is_some() {
+            return Err(AuthError::InvalidToken);
+        }
         
         let users = self.users.read();
         let user = users
@@ -220,6 +260,50 @@ impl AuthManager {
         })
     }
 
+    /// Issue a short-lived token that only authorizes log streams
+    pub fn issue_stream_token(&self, claims: &Claims) -> Result<StreamTokenResponse, AuthError> {
+        let users = self.users.read();
+        let user = users
+            .iter()
+            .find(|u| u.id == claims.sub)
+            .ok_or(AuthError::UserNotFound)?;
+
+        Ok(StreamTokenResponse {
+            token: self.generate_scoped_token(user, STREAM_TOKEN_TTL_SECS, Some(STREAM_TOKEN_SCOPE))?,
+            expires_in: STREAM_TOKEN_TTL_SECS,
+        })
+    }
+
+    /// Authenticate a request from its Bearer header, or from a stream token
+    /// in the query string on log stream routes
+    pub fn authenticate_request(&self, request: &Request) -> Result<Claims, AuthError> {
+        let bearer = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|h| h.strip_prefix("Bearer "));
+
+        if let Some(token) = bearer {
+            let claims = self.validate_token(token)?;
+            // Scoped tokens travel in URLs and must not unlock the full API
+            if claims.scope.is_some() {
+                return Err(AuthError::InvalidToken);
+            }
+            return Ok(claims);
+        }
+
+        if !request.uri().path().ends_with("/logs/stream") {
+            return Err(AuthError::InvalidToken);
+        }
+        let query = axum::extract::Query::<StreamTokenQuery>::try_from_uri(request.uri())
+            .map_err(|_| AuthError::InvalidToken)?;
+        let claims = self.validate_token(&query.token)?;
+        if claims.scope.as_deref() != Some(STREAM_TOKEN_SCOPE) {
+            return Err(AuthError::InvalidToken);
+        }
+        Ok(claims)
+    }
+
     /// Revoke a token (for logout)
     pub fn revoke_token(&self, token: String) {
         self.revoked_tokens.write().insert(token);
@@ -316,20 +400,8 @@ pub async fn auth_middleware(
     mut request: Request,
     next: Next,
 ) -> Result<Response, AuthError> {
-    // Get the Authorization header
-    let auth_header = request
-        .headers()
-        .get(header::AUTHORIZATION)
-        .and_then(|h| h.to_str().ok())
-        .ok_or(AuthError::InvalidToken)?;
-
-    // Extract the token from "Bearer <token>"
-    let token = auth_header
-        .strip_prefix("Bearer ")
-        .ok_or(AuthError::InvalidToken)?;
-
-    // Validate the token
-    let claims = auth.validate_token(token)?;
+    // Validate the Bearer token (or a log stream's query token)
+    let claims = auth.authenticate_request(&request)?;
 
     // Insert claims into request extensions
     request.extensions_mut().insert(claims);
@@ -337,6 +409,28 @@ pub async fn auth_middleware(
     Ok(next.run(request).await)
 }
 
+/// Middleware rejecting tokens whose role cannot act as `required`
+///
+/// Runs inside `auth_middleware`, which puts the token's claims in the
+/// request extensions. Use it as a route layer:
+/// `middleware::from_fn(|req, next| require_role(Role::Operator, req, next))`.
+pub async fn require_role(
+    required: Role,
+    request: Request,
+    next: Next,
+) -> Result<Response, AuthError> {
+    let claims = request
+        .extensions()
+        .get::<Claims>()
+        .ok_or(AuthError::InvalidToken)?;
+
+    if !claims.role.can_perform(required) {
+        return Err(AuthError::InsufficientPermissions);
+    }
+
+    Ok(next.run(request).await)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -447,4 +541,40 @@ mod tests {
         );
         assert!(result.is_err());
     }
+
+    fn request(uri: &str, bearer: Option<&str>) -> Request {
+        let mut builder = axum::http::Request::builder().uri(uri);
+        if let Some(token) = bearer {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        builder.body(axum::body::Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn test_stream_token_only_opens_log_streams() {
+        let auth = AuthManager::new("test-secret");
+        let response = auth.login(LoginRequest {
+            username: "admin".to_string(),
+            password: "admin".to_string(),
+        }).unwrap();
+        let claims = auth.validate_token(&response.access_token).unwrap();
+        let stream = auth.issue_stream_token(&claims).unwrap();
+        assert_eq!(stream.expires_in, STREAM_TOKEN_TTL_SECS);
+
+        let uri = format!("/api/nodes/node1/logs/stream?token={}", stream.token);
+        let streamed = auth.authenticate_request(&request(&uri, None)).unwrap();
+        assert_eq!(streamed.sub, claims.sub);
+        assert_eq!(streamed.scope.as_deref(), Some(STREAM_TOKEN_SCOPE));
+
+        // Not accepted as a query token elsewhere, as a Bearer token, or for refresh
+        let uri = format!("/api/nodes/node1/logs?token={}", stream.token);
+        assert!(auth.authenticate_request(&request(&uri, None)).is_err());
+        assert!(auth.authenticate_request(&request("/api/nodes", Some(&stream.token))).is_err());
+        assert!(auth.refresh(RefreshRequest { refresh_token: stream.token.clone() }).is_err());
+
+        // Access tokens still work as Bearer tokens but not in the query string
+        assert!(auth.authenticate_request(&request("/api/nodes", Some(&response.access_token))).is_ok());
+        let uri = format!("/api/nodes/node1/logs/stream?token={}", response.access_token);
+        assert!(auth.authenticate_request(&request(&uri, None)).is_err());
+    }
 }
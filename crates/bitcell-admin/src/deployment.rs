@@ -6,6 +6,39 @@ use crate::api::NodeType;
 use crate::process::{ProcessManager, NodeConfig};
 use crate::setup::{SetupManager, NodeEndpoint};
 
+/// Offset of the public RPC port range above the P2P base port
+const RPC_PORT_OFFSET: u16 = 1000;
+
+/// Offset of the admin RPC port range above the P2P base port
+const ADMIN_RPC_PORT_OFFSET: u16 = 2000;
+
+/// Ports handed to one deployed node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct NodePorts {
+    p2p: u16,
+    rpc: u16,
+    admin_rpc: u16,
+}
+
+/// Distance between consecutive nodes' P2P ports, leaving room for the
+/// metrics port the node binary opens above its P2P port
+const P2P_PORT_STRIDE: u16 = 3;
+
+/// Ports for `count` nodes starting at `base_port`
+///
+/// P2P ports step by `P2P_PORT_STRIDE`; RPC and admin RPC ports come from
+/// their own consecutive ranges so no node's listener lands on another
+/// node's port.
+fn allocate_ports(base_port: u16, count: usize) -> Vec<NodePorts> {
+    (0..count as u16)
+        .map(|i| NodePorts {
+            p2p: base_port + i * P2P_PORT_STRIDE,
+            rpc: base_port + RPC_PORT_OFFSET + i,
+            admin_rpc: base_port + ADMIN_RPC_PORT_OFFSET + i,
+        })
+        .collect()
+}
+
 pub struct DeploymentManager {
     process: Arc<ProcessManager>,
     setup: Arc<SetupManager>,
@@ -41,8 +74,8 @@ impl DeploymentManager {
         let existing_nodes = self.process.list_nodes();
         for node in &existing_nodes {
             if node.port >= base_port {
-                // We use port (P2P) and port+1 (Metrics), so next available is port+2
-                base_port = std::cmp::max(base_port, node.port + 2);
+                // The P2P port is followed by the node's metrics port
+                base_port = std::cmp::max(base_port, node.port + P2P_PORT_STRIDE);
             }
         }
         
@@ -54,30 +87,28 @@ impl DeploymentManager {
             if let Some(port_str) = node.metrics_endpoint.split(':').last() {
                 if let Some(port_part) = port_str.split('/').next() {
                     if let Ok(metrics_port) = port_part.parse::<u16>() {
-                        // metrics_port is port + 1, so P2P port is metrics_port - 1
-                        let p2p_port = metrics_port.saturating_sub(1);
-                        if p2p_port >= base_port {
-                            base_port = std::cmp::max(base_port, p2p_port + 2);
+                        // The metrics port is the last port a node uses
+                        if metrics_port >= base_port {
+                            base_port = std::cmp::max(base_port, metrics_port + 1);
                         }
                     }
                 }
             }
         }
 
-        let base_rpc_port = base_port + 1000;
         let mut deployed_nodes = Vec::new();
 
-        for i in 0..count {
+        for (i, ports) in allocate_ports(base_port, count).into_iter().enumerate() {
             let node_id = format!("{:?}-{}-{}", node_type, deployment_id, i);
-            // Increment by 2 to allow space for metrics port (port + 1)
-            let port = base_port + (i * 2) as u16;
-            let rpc_port = base_rpc_port + i as u16;
+            let port = ports.p2p;
+            let rpc_port = ports.rpc;
             
             let config = NodeConfig {
                 node_type,
                 data_dir: format!("/tmp/bitcell/{}", node_id),
                 port,
                 rpc_port,
+                admin_rpc_port: ports.admin_rpc,
                 log_level: "info".to_string(),
                 network: "testnet".to_string(),
                 enable_dht,
@@ -92,7 +123,10 @@ impl DeploymentManager {
             let endpoint = NodeEndpoint {
                 id: node_id.clone(),
                 node_type: format!("{:?}", node_type).to_lowercase(),
-                metrics_endpoint: format!("http://127.0.0.1:{}/metrics", port + 1),
+                metrics_endpoint: format!(
+                    "http://127.0.0.1:{}/metrics",
+                    bitcell_node::config::metrics_port_for(port)
+                ),
                 rpc_endpoint: format!("http://127.0.0.1:{}", rpc_port),
             };
             self.setup.add_node(endpoint);
@@ -128,3 +162,24 @@ impl DeploymentManager {
         deployed_nodes
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_allocated_ports_are_distinct() {
+        let ports = allocate_ports(19000, 10);
+        assert_eq!(ports.len(), 10);
+
+        // Include the metrics port each node binds above its P2P port
+        let mut seen = HashSet::new();
+        for node in &ports {
+            let metrics = bitcell_node::config::metrics_port_for(node.p2p);
+            for port in [node.p2p, metrics, node.rpc, node.admin_rpc] {
+                assert!(seen.insert(port), "port {} allocated twice", port);
+            }
+        }
+    }
+}
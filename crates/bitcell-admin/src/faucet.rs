@@ -1,12 +1,14 @@
 //! Testnet Faucet Service
 //!
 //! Provides token distribution for testnet with:
-//! - Rate limiting per address
+//! - Rate limiting per address and per client IP, persisted across restarts
 //! - Request tracking and audit logging
 //! - CAPTCHA verification support
 //! - Secure wallet management
 
 use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use parking_lot::RwLock;
@@ -42,6 +44,12 @@ pub struct FaucetConfig {
     pub rate_limit_seconds: u64,
     /// Maximum requests per address per day
     pub max_requests_per_day: usize,
+    /// Maximum requests from one client IP per day
+    #[serde(default = "default_max_requests_per_ip_per_day")]
+    pub max_requests_per_ip_per_day: usize,
+    /// JSON file persisting grant timestamps, so a restart keeps the limits
+    #[serde(default)]
+    pub rate_limit_state_path: Option<PathBuf>,
     /// Faucet private key (hex string)
     pub private_key: String,
     /// Node RPC host
@@ -60,6 +68,8 @@ impl Default for FaucetConfig {
             amount_per_request: 1_000_000_000, // 1 CELL in smallest units
             rate_limit_seconds: 3600,           // 1 hour
             max_requests_per_day: 5,
+            max_requests_per_ip_per_day: default_max_requests_per_ip_per_day(),
+            rate_limit_state_path: None,
             private_key: String::new(),
             node_rpc_host: "127.0.0.1".to_string(),
             node_rpc_port: 8545,
@@ -69,6 +79,10 @@ impl Default for FaucetConfig {
     }
 }
 
+fn default_max_requests_per_ip_per_day() -> usize {
+    20
+}
+
 impl FaucetConfig {
     /// Validate the configuration fields
     pub fn validate(&self) -> Result<(), FaucetError> {
@@ -87,6 +101,11 @@ impl FaucetConfig {
                 "max_requests_per_day must be greater than 0".to_string()
             ));
         }
+        if self.max_requests_per_ip_per_day == 0 {
+            return Err(FaucetError::ConfigError(
+                "max_requests_per_ip_per_day must be greater than 0".to_string()
+            ));
+        }
         if self.private_key.is_empty() {
             return Err(FaucetError::ConfigError(
                 "private_key must be set".to_string()
@@ -126,35 +145,59 @@ pub enum RequestStatus {
     Failed,
 }
 
-/// Rate limit tracking
-#[derive(Debug, Clone)]
+/// Rate limit tracking for one address
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct RateLimitInfo {
     last_request: u64,
     requests_today: Vec<u64>,
 }
 
+/// Grant timestamps by address and by client IP
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RateLimits {
+    addresses: HashMap<String, RateLimitInfo>,
+    ips: HashMap<String, Vec<u64>>,
+}
+
 /// Maximum number of requests to keep in history
 const MAX_HISTORY_SIZE: usize = 10_000;
 
 /// Threshold for cleaning up old rate limit entries (30 days)
 const CLEANUP_THRESHOLD_SECONDS: u64 = 30 * 86400;
 
+/// Start of the UTC day containing `timestamp`
+fn day_start(timestamp: u64) -> u64 {
+    timestamp - timestamp % 86400
+}
+
 /// Faucet service
 pub struct FaucetService {
     config: Arc<RwLock<FaucetConfig>>,
-    rate_limits: Arc<RwLock<HashMap<String, RateLimitInfo>>>,
+    rate_limits: Arc<RwLock<RateLimits>>,
     request_history: Arc<RwLock<Vec<FaucetRequest>>>,
 }
 
 impl FaucetService {
     /// Create a new faucet service
+    ///
+    /// Restores grant timestamps from `rate_limit_state_path` if it exists.
     pub fn new(config: FaucetConfig) -> Result<Self, FaucetError> {
         // Validate configuration
         config.validate()?;
+
+        let rate_limits = match &config.rate_limit_state_path {
+            Some(path) if path.exists() => {
+                let content = std::fs::read_to_string(path)
+                    .map_err(|e| FaucetError::ConfigError(format!("Failed to read rate limit state: {}", e)))?;
+                serde_json::from_str(&content)
+                    .map_err(|e| FaucetError::ConfigError(format!("Failed to parse rate limit state: {}", e)))?
+            }
+            _ => RateLimits::default(),
+        };
         
         Ok(Self {
             config: Arc::new(RwLock::new(config)),
-            rate_limits: Arc::new(RwLock::new(HashMap::new())),
+            rate_limits: Arc::new(RwLock::new(rate_limits)),
             request_history: Arc::new(RwLock::new(Vec::new())),
         })
     }
@@ -172,93 +215,119 @@ impl FaucetService {
         Ok(())
     }
 
-    /// Check if address can request tokens (with atomic check-and-set)
-    fn check_and_record_rate_limit(&self, address: &str, timestamp: u64) -> Result<(), FaucetError> {
-        let config = self.config.read();
-        let mut rate_limits = self.rate_limits.write();
-        
-        // Get or create rate limit info
-        let info = rate_limits.entry(address.to_string()).or_insert_with(|| {
-            RateLimitInfo {
-                last_request: 0,
-                requests_today: Vec::new(),
+    /// Check the cooldown and daily caps at `now`
+    ///
+    /// Returns `RateLimited` with the seconds until the earliest limit lifts.
+    fn check_limits(
+        config: &FaucetConfig,
+        limits: &RateLimits,
+        address: &str,
+        ip: Option<IpAddr>,
+        now: u64,
+    ) -> Result<(), FaucetError> {
+        let today_start = day_start(now);
+        let until_tomorrow = today_start + 86400 - now;
+
+        if let Some(info) = limits.addresses.get(address) {
+            // Check time-based rate limit
+            let elapsed = now.saturating_sub(info.last_request);
+            if elapsed < config.rate_limit_seconds && info.last_request > 0 {
+                return Err(FaucetError::RateLimited(config.rate_limit_seconds - elapsed));
+            }
+
+            // Check daily request limit
+            let requests_today = info.requests_today.iter().filter(|&&t| t >= today_start).count();
+            if requests_today >= config.max_requests_per_day {
+                return Err(FaucetError::RateLimited(until_tomorrow));
             }
-        });
-        
-        // Check time-based rate limit
-        let elapsed = timestamp.saturating_sub(info.last_request);
-        if elapsed < config.rate_limit_seconds && info.last_request > 0 {
-            let remaining = config.rate_limit_seconds - elapsed;
-            return Err(FaucetError::RateLimited(remaining));
         }
 
-        // Check daily request limit
-        let today_start = timestamp.checked_div(86400)
-            .and_then(|d| d.checked_mul(86400))
-            .unwrap_or(timestamp);
-        
-        let requests_today: Vec<_> = info.requests_today
-            .iter()
-            .filter(|&&t| t >= today_start)
-            .copied()
-            .collect();
-        
-        if requests_today.len() >= config.max_requests_per_day {
-            let next_day = today_start + 86400;
-            let remaining = next_day - timestamp;
-            return Err(FaucetError::RateLimited(remaining));
+        // Check the per-IP daily cap
+        if let Some(grants) = ip.and_then(|ip| limits.ips.get(&ip.to_string())) {
+            let requests_today = grants.iter().filter(|&&t| t >= today_start).count();
+            if requests_today >= config.max_requests_per_ip_per_day {
+                return Err(FaucetError::RateLimited(until_tomorrow));
+            }
         }
-        
+
+        Ok(())
+    }
+
+    /// Check if address can request tokens (with atomic check-and-set)
+    fn check_and_record_rate_limit(
+        &self,
+        address: &str,
+        ip: Option<IpAddr>,
+        timestamp: u64,
+    ) -> Result<(), FaucetError> {
+        let config = self.config.read();
+        let mut rate_limits = self.rate_limits.write();
+        Self::check_limits(&config, &rate_limits, address, ip, timestamp)?;
+
         // Record the request atomically
+        let today_start = day_start(timestamp);
+        let info = rate_limits.addresses.entry(address.to_string()).or_default();
         info.last_request = timestamp;
         info.requests_today.retain(|&t| t >= today_start);
         info.requests_today.push(timestamp);
+
+        if let Some(ip) = ip {
+            let grants = rate_limits.ips.entry(ip.to_string()).or_default();
+            grants.retain(|&t| t >= today_start);
+            grants.push(timestamp);
+        }
         
         // Cleanup old entries periodically (every 100 requests)
-        if rate_limits.len() % 100 == 0 {
-            rate_limits.retain(|_, info| {
+        if rate_limits.addresses.len() % 100 == 0 {
+            rate_limits.addresses.retain(|_, info| {
                 timestamp.saturating_sub(info.last_request) < CLEANUP_THRESHOLD_SECONDS
             });
+            rate_limits.ips.retain(|_, grants| grants.iter().any(|&t| t >= today_start));
+        }
+
+        if let Some(path) = &config.rate_limit_state_path {
+            if let Err(e) = Self::save_rate_limits(path, &rate_limits) {
+                tracing::warn!("Failed to persist faucet rate limits: {}", e);
+            }
         }
 
         Ok(())
     }
 
+    fn save_rate_limits(path: &Path, rate_limits: &RateLimits) -> Result<(), String> {
+        let content = serde_json::to_string(rate_limits)
+            .map_err(|e| format!("Failed to serialize rate limits: {}", e))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create rate limit directory: {}", e))?;
+        }
+
+        // Write to a sibling temp file and rename it over the real one so a
+        // crash mid-write never leaves a truncated state file behind.
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, content)
+            .map_err(|e| format!("Failed to write rate limit file: {}", e))?;
+        std::fs::rename(&tmp, path)
+            .map_err(|e| format!("Failed to replace rate limit file: {}", e))
+    }
+
     /// Check if address can request tokens (read-only check)
     pub fn check_rate_limit(&self, address: &str) -> Result<(), FaucetError> {
-        let config = self.config.read();
+        self.check_eligibility(address, None)
+    }
+
+    /// Check if `address`, requesting from `ip`, can receive tokens now
+    ///
+    /// Read-only; a rejection carries the seconds until the request would
+    /// be accepted.
+    pub fn check_eligibility(&self, address: &str, ip: Option<IpAddr>) -> Result<(), FaucetError> {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
 
-        let rate_limits = self.rate_limits.read();
-        
-        if let Some(info) = rate_limits.get(address) {
-            // Check time-based rate limit
-            let elapsed = now.saturating_sub(info.last_request);
-            if elapsed < config.rate_limit_seconds {
-                let remaining = config.rate_limit_seconds - elapsed;
-                return Err(FaucetError::RateLimited(remaining));
-            }
-
-            // Check daily request limit
-            let today_start = now.checked_div(86400)
-                .and_then(|d| d.checked_mul(86400))
-                .unwrap_or(now);
-            let requests_today: Vec<_> = info.requests_today
-                .iter()
-                .filter(|&&t| t >= today_start)
-                .collect();
-            
-            if requests_today.len() >= config.max_requests_per_day {
-                let next_day = today_start + 86400;
-                let remaining = next_day - now;
-                return Err(FaucetError::RateLimited(remaining));
-            }
-        }
-
-        Ok(())
+        Self::check_limits(&self.config.read(), &self.rate_limits.read(), address, ip, now)
     }
 
     /// Get faucet balance
@@ -345,6 +414,7 @@ impl FaucetService {
         &self,
         address: &str,
         captcha_response: Option<&str>,
+        client_ip: Option<IpAddr>,
     ) -> Result<FaucetRequest, FaucetError> {
         // Validate address format
         self.validate_address(address)?;
@@ -364,7 +434,7 @@ impl FaucetService {
             .as_secs();
 
         // Atomically check and record rate limit (prevents TOCTOU race condition)
-        self.check_and_record_rate_limit(address, timestamp)?;
+        self.check_and_record_rate_limit(address, client_ip, timestamp)?;
 
         // Check recipient balance if configured
         if let Some(max_balance) = config.max_recipient_balance {
@@ -657,7 +727,7 @@ mod tests {
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        assert!(service.check_and_record_rate_limit(address, timestamp).is_ok());
+        assert!(service.check_and_record_rate_limit(address, None, timestamp).is_ok());
 
         // Second immediate request should be rate limited
         assert!(matches!(
@@ -681,8 +751,8 @@ mod tests {
             .as_secs();
 
         // Record 2 requests
-        assert!(service.check_and_record_rate_limit(address, base_time).is_ok());
-        assert!(service.check_and_record_rate_limit(address, base_time + 2).is_ok());
+        assert!(service.check_and_record_rate_limit(address, None, base_time).is_ok());
+        assert!(service.check_and_record_rate_limit(address, None, base_time + 2).is_ok());
 
         // Third request should exceed daily limit
         assert!(matches!(
@@ -691,6 +761,80 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_cooldown_window() {
+        let mut config = create_test_config();
+        config.rate_limit_seconds = 86400;
+
+        let service = FaucetService::new(config).expect("Failed to create service");
+        let address = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0";
+        let start = day_start(1_700_000_000);
+
+        assert!(service.check_and_record_rate_limit(address, None, start).is_ok());
+
+        // A second request inside the cooldown reports the remaining wait
+        assert!(matches!(
+            service.check_and_record_rate_limit(address, None, start + 3600),
+            Err(FaucetError::RateLimited(82800))
+        ));
+
+        // Allowed once the window has passed
+        assert!(service.check_and_record_rate_limit(address, None, start + 86400).is_ok());
+    }
+
+    #[test]
+    fn test_per_ip_daily_cap() {
+        let mut config = create_test_config();
+        config.max_requests_per_ip_per_day = 2;
+
+        let service = FaucetService::new(config).expect("Failed to create service");
+        let ip: IpAddr = "203.0.113.7".parse().unwrap();
+        let start = day_start(1_700_000_000);
+        let addresses = [
+            "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0",
+            "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb1",
+            "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb2",
+        ];
+
+        assert!(service.check_and_record_rate_limit(addresses[0], Some(ip), start).is_ok());
+        assert!(service.check_and_record_rate_limit(addresses[1], Some(ip), start + 10).is_ok());
+
+        // A fresh address from the same IP waits until the next day
+        assert!(matches!(
+            service.check_and_record_rate_limit(addresses[2], Some(ip), start + 100),
+            Err(FaucetError::RateLimited(86300))
+        ));
+        assert!(service.check_and_record_rate_limit(addresses[2], None, start + 100).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limits_survive_restart() {
+        let path = std::env::temp_dir().join(format!("bitcell-faucet-limits-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut config = create_test_config();
+        config.rate_limit_state_path = Some(path.clone());
+        let address = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0";
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let service = FaucetService::new(config.clone()).expect("Failed to create service");
+        assert!(service.check_and_record_rate_limit(address, None, now).is_ok());
+        assert!(path.exists());
+        assert!(!path.with_extension("tmp").exists());
+        drop(service);
+
+        let restarted = FaucetService::new(config).expect("Failed to create service");
+        assert!(matches!(
+            restarted.check_eligibility(address, None),
+            Err(FaucetError::RateLimited(_))
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn test_get_stats() {
         let config = create_test_config();
@@ -0,0 +1,313 @@
+//! PKCS#11 Backend
+//!
+//! This module provides integration with local PKCS#11 tokens such as
+//! YubiHSM 2 or SoftHSM for secure key management and signing.
+//!
+//! # Features
+//! - secp256k1 key generation on the token (private keys are non-extractable)
+//! - ECDSA signing with low-S normalization
+//! - Keys addressed by their `CKA_LABEL`
+//!
+//! # Example
+//! ```ignore
+//! use bitcell_admin::hsm::{HsmConfig, HsmClient};
+//!
+//! let config = HsmConfig::pkcs11("/usr/lib/softhsm/libsofthsm2.so", 0, "1234", "bitcell-key");
+//! let hsm = HsmClient::connect(config).await?;
+//! let signature = hsm.sign(&hash).await?;
+//! ```
+
+use async_trait::async_trait;
+use bitcell_crypto::{Hash256, PublicKey, Signature};
+use cryptoki::context::{CInitializeArgs, Pkcs11};
+use cryptoki::mechanism::Mechanism;
+use cryptoki::object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle};
+use cryptoki::session::{Session, UserType};
+use cryptoki::types::AuthPin;
+use std::sync::Mutex;
+
+use crate::hsm::{HsmBackend, HsmConfig, HsmError, HsmProvider, HsmResult};
+
+/// DER-encoded OID of secp256k1 (1.3.132.0.10), used as `CKA_EC_PARAMS`
+const SECP256K1_EC_PARAMS: [u8; 7] = [0x06, 0x05, 0x2B, 0x81, 0x04, 0x00, 0x0A];
+
+/// PKCS#11 token backend
+pub struct Pkcs11Backend {
+    // Sessions are not `Sync`; operations on one session are serialized
+    session: Mutex<Session>,
+}
+
+impl Pkcs11Backend {
+    /// Load the PKCS#11 module at `config.endpoint`, open a session on
+    /// `config.slot` and log in with the PIN in `credentials.token`
+    pub fn connect(config: &HsmConfig) -> HsmResult<Self> {
+        let slot_id = config
+            .slot
+            .ok_or_else(|| HsmError::InvalidConfig("PKCS#11 slot required".into()))?;
+
+        let pin = config
+            .credentials
+            .token
+            .as_ref()
+            .ok_or_else(|| HsmError::InvalidConfig("PKCS#11 user PIN required".into()))?;
+
+        let context = Pkcs11::new(&config.endpoint)
+            .map_err(|e| HsmError::ConnectionFailed(format!("Failed to load PKCS#11 module {}: {}", config.endpoint, e)))?;
+        context
+            .initialize(CInitializeArgs::OsThreads)
+            .map_err(|e| HsmError::ConnectionFailed(format!("Failed to initialize PKCS#11 module: {}", e)))?;
+
+        let slot = context
+            .get_slots_with_token()
+            .map_err(|e| HsmError::ConnectionFailed(format!("Failed to list PKCS#11 slots: {}", e)))?
+            .into_iter()
+            .find(|slot| slot.id() == slot_id)
+            .ok_or_else(|| HsmError::ConnectionFailed(format!("No token in PKCS#11 slot {}", slot_id)))?;
+
+        let session = context
+            .open_rw_session(slot)
+            .map_err(|e| HsmError::ConnectionFailed(format!("Failed to open PKCS#11 session: {}", e)))?;
+        session
+            .login(UserType::User, Some(&AuthPin::new(pin.clone())))
+            .map_err(|e| HsmError::AuthenticationFailed(format!("PKCS#11 login failed: {}", e)))?;
+
+        Ok(Self {
+            session: Mutex::new(session),
+        })
+    }
+
+    fn session(&self) -> HsmResult<std::sync::MutexGuard<'_, Session>> {
+        self.session
+            .lock()
+            .map_err(|_| HsmError::InternalError("PKCS#11 session lock poisoned".into()))
+    }
+
+    /// Find the object of `class` labelled `key_name`
+    fn find_key(session: &Session, class: ObjectClass, key_name: &str) -> HsmResult<ObjectHandle> {
+        session
+            .find_objects(&[
+                Attribute::Class(class),
+                Attribute::KeyType(KeyType::EC),
+                Attribute::Label(key_name.as_bytes().to_vec()),
+            ])
+            .map_err(|e| HsmError::InternalError(format!("Failed to search keys: {}", e)))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| HsmError::KeyNotFound(key_name.to_string()))
+    }
+
+    /// Read the compressed public key of the public key object `handle`
+    fn read_public_key(session: &Session, handle: ObjectHandle) -> HsmResult<PublicKey> {
+        let attributes = session
+            .get_attributes(handle, &[AttributeType::EcPoint])
+            .map_err(|e| HsmError::InternalError(format!("Failed to read public key: {}", e)))?;
+
+        match attributes.into_iter().next() {
+            Some(Attribute::EcPoint(point)) => compress_ec_point(&point),
+            _ => Err(HsmError::InternalError("Public key has no EC point".into())),
+        }
+    }
+}
+
+/// Convert a token's `CKA_EC_POINT` to a compressed BitCell public key
+///
+/// The value is a DER OCTET STRING around the SEC1 point, though some
+/// modules return the bare point.
+fn compress_ec_point(point: &[u8]) -> HsmResult<PublicKey> {
+    let sec1 = match point {
+        [0x04, len, rest @ ..] if *len as usize == rest.len() && rest.first() == Some(&0x04) => rest,
+        _ => point,
+    };
+
+    let key = k256::ecdsa::VerifyingKey::from_sec1_bytes(sec1)
+        .map_err(|e| HsmError::InternalError(format!("Invalid EC point: {}", e)))?;
+    let bytes: [u8; 33] = key
+        .to_encoded_point(true)
+        .as_bytes()
+        .try_into()
+        .map_err(|_| HsmError::InternalError("Unexpected compressed key length".into()))?;
+
+    PublicKey::from_bytes(bytes)
+        .map_err(|e| HsmError::InternalError(format!("Failed to parse public key: {}", e)))
+}
+
+/// Convert a raw `r || s` CKM_ECDSA signature to BitCell's low-S form
+fn normalize_signature(raw: &[u8]) -> HsmResult<Signature> {
+    let signature = k256::ecdsa::Signature::from_slice(raw)
+        .map_err(|e| HsmError::SigningFailed(format!("Invalid signature: {}", e)))?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+
+    Ok(Signature::from_bytes(signature.to_bytes().into()))
+}
+
+#[async_trait]
+impl HsmBackend for Pkcs11Backend {
+    fn provider(&self) -> HsmProvider {
+        HsmProvider::Pkcs11
+    }
+
+    async fn is_available(&self) -> bool {
+        self.session().is_ok_and(|session| session.get_session_info().is_ok())
+    }
+
+    async fn get_public_key(&self, key_name: &str) -> HsmResult<PublicKey> {
+        let session = self.session()?;
+        let handle = Self::find_key(&session, ObjectClass::PUBLIC_KEY, key_name)?;
+        Self::read_public_key(&session, handle)
+    }
+
+    async fn sign(&self, key_name: &str, hash: &Hash256) -> HsmResult<Signature> {
+        let session = self.session()?;
+        let handle = Self::find_key(&session, ObjectClass::PRIVATE_KEY, key_name)?;
+
+        // BitCell signatures are ECDSA over SHA-256 of the message, and
+        // CKM_ECDSA signs a precomputed digest
+        let digest = Hash256::hash(hash.as_bytes());
+        let raw = session
+            .sign(&Mechanism::Ecdsa, handle, digest.as_bytes())
+            .map_err(|e| HsmError::SigningFailed(format!("PKCS#11 signing failed: {}", e)))?;
+
+        normalize_signature(&raw)
+    }
+
+    async fn generate_key(&self, key_name: &str) -> HsmResult<PublicKey> {
+        let session = self.session()?;
+
+        // Check if key already exists
+        if Self::find_key(&session, ObjectClass::PRIVATE_KEY, key_name).is_ok() {
+            return Err(HsmError::InternalError(format!(
+                "Key '{}' already exists",
+                key_name
+            )));
+        }
+
+        let label = key_name.as_bytes().to_vec();
+        let public_template = [
+            Attribute::Token(true),
+            Attribute::Private(false),
+            Attribute::KeyType(KeyType::EC),
+            Attribute::EcParams(SECP256K1_EC_PARAMS.to_vec()),
+            Attribute::Verify(true),
+            Attribute::Label(label.clone()),
+        ];
+        let private_template = [
+            Attribute::Token(true),
+            Attribute::Private(true),
+            Attribute::Sensitive(true),
+            Attribute::Extractable(false),
+            Attribute::Sign(true),
+            Attribute::Label(label),
+        ];
+
+        let (public_key, _) = session
+            .generate_key_pair(&Mechanism::EccKeyPairGen, &public_template, &private_template)
+            .map_err(|e| HsmError::InternalError(format!("Failed to generate key: {}", e)))?;
+
+        Self::read_public_key(&session, public_key)
+    }
+
+    async fn list_keys(&self) -> HsmResult<Vec<String>> {
+        let session = self.session()?;
+        let handles = session
+            .find_objects(&[
+                Attribute::Class(ObjectClass::PRIVATE_KEY),
+                Attribute::KeyType(KeyType::EC),
+            ])
+            .map_err(|e| HsmError::InternalError(format!("Failed to list keys: {}", e)))?;
+
+        let mut key_names = Vec::new();
+        for handle in handles {
+            let attributes = session
+                .get_attributes(handle, &[AttributeType::Label])
+                .map_err(|e| HsmError::InternalError(format!("Failed to read key label: {}", e)))?;
+            if let Some(Attribute::Label(label)) = attributes.into_iter().next() {
+                key_names.push(String::from_utf8_lossy(&label).into_owned());
+            }
+        }
+
+        Ok(key_names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SoftHSM settings from the environment, if a token is available
+    ///
+    /// Set `BITCELL_PKCS11_MODULE`, `BITCELL_PKCS11_SLOT` and
+    /// `BITCELL_PKCS11_PIN` to run the token tests.
+    fn softhsm_config(key_name: &str) -> Option<HsmConfig> {
+        let module = std::env::var("BITCELL_PKCS11_MODULE").ok()?;
+        let slot = std::env::var("BITCELL_PKCS11_SLOT").ok()?.parse().ok()?;
+        let pin = std::env::var("BITCELL_PKCS11_PIN").ok()?;
+        Some(HsmConfig::pkcs11(&module, slot, &pin, key_name))
+    }
+
+    #[test]
+    fn test_compress_ec_point() {
+        let sk = bitcell_crypto::SecretKey::generate();
+        let uncompressed = k256::ecdsa::VerifyingKey::from_sec1_bytes(sk.public_key().as_bytes())
+            .unwrap()
+            .to_encoded_point(false);
+
+        // Bare SEC1 point and DER OCTET STRING both decode
+        let bare = uncompressed.as_bytes().to_vec();
+        let mut der = vec![0x04, bare.len() as u8];
+        der.extend_from_slice(&bare);
+
+        assert_eq!(compress_ec_point(&bare).unwrap(), sk.public_key());
+        assert_eq!(compress_ec_point(&der).unwrap(), sk.public_key());
+        assert!(compress_ec_point(&[0x04, 0x01, 0x02]).is_err());
+    }
+
+    #[test]
+    fn test_normalize_signature() {
+        let sk = bitcell_crypto::SecretKey::generate();
+        let message = b"normalize";
+        let signature = sk.sign(message);
+
+        // Flip s to its high form, as a token may return it
+        let low = k256::ecdsa::Signature::from_slice(signature.as_bytes()).unwrap();
+        let (r, s) = low.split_scalars();
+        let high = k256::ecdsa::Signature::from_scalars(r, -s).unwrap();
+
+        let normalized = normalize_signature(&high.to_bytes()).unwrap();
+        assert_eq!(normalized, signature);
+        assert!(normalized.verify(&sk.public_key(), message).is_ok());
+    }
+
+    #[test]
+    fn test_missing_slot_or_pin() {
+        let mut config = HsmConfig::pkcs11("/nonexistent/libpkcs11.so", 0, "1234", "test-key");
+        config.slot = None;
+        assert!(matches!(Pkcs11Backend::connect(&config), Err(HsmError::InvalidConfig(_))));
+
+        let mut config = HsmConfig::pkcs11("/nonexistent/libpkcs11.so", 0, "1234", "test-key");
+        config.credentials.token = None;
+        assert!(matches!(Pkcs11Backend::connect(&config), Err(HsmError::InvalidConfig(_))));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires a SoftHSM token - see softhsm_config
+    async fn test_softhsm_generate_sign_list() {
+        let key_name = format!("bitcell-test-{}", std::process::id());
+        let config = softhsm_config(&key_name).expect("SoftHSM environment not configured");
+        let backend = Pkcs11Backend::connect(&config).unwrap();
+        assert!(backend.is_available().await);
+
+        let pk = backend.generate_key(&key_name).await.unwrap();
+        assert_eq!(backend.get_public_key(&key_name).await.unwrap(), pk);
+        assert!(backend.generate_key(&key_name).await.is_err());
+        assert!(backend.list_keys().await.unwrap().contains(&key_name));
+
+        let hash = Hash256::hash(b"softhsm");
+        let signature = backend.sign(&key_name, &hash).await.unwrap();
+        assert!(signature.verify(&pk, hash.as_bytes()).is_ok());
+
+        assert!(matches!(
+            backend.sign("missing-key", &hash).await,
+            Err(HsmError::KeyNotFound(_))
+        ));
+    }
+}
@@ -56,23 +56,33 @@ pub struct AdminConsole {
 }
 
 impl AdminConsole {
+    /// Create a new admin console in development mode
+    ///
+    /// Falls back to an insecure default JWT secret when
+    /// `BITCELL_JWT_SECRET` is unset; use [`AdminConsole::try_new`] in
+    /// production. Fails if the audit log directory cannot be opened.
+    pub fn new(addr: SocketAddr) -> Result<Self, String> {
+        Self::try_new(addr, false)
+    }
+
     /// Create a new admin console
-    pub fn new(addr: SocketAddr) -> Self {
+    ///
+    /// In production mode a missing or weak `BITCELL_JWT_SECRET` is an error
+    /// instead of a warning.
+    pub fn try_new(addr: SocketAddr, production: bool) -> Result<Self, String> {
+        let jwt_secret = load_jwt_secret(std::env::var(JWT_SECRET_ENV).ok(), production)?;
+
         let process = Arc::new(ProcessManager::new());
         let setup = Arc::new(setup::SetupManager::new());
         let deployment = Arc::new(DeploymentManager::new(process.clone(), setup.clone()));
         let system_metrics = Arc::new(system_metrics::SystemMetricsCollector::new());
-        
-        // Initialize auth with a secret key
-        // TODO: SECURITY: Load JWT secret from environment variable or secure config
-        // Current hardcoded secret is for development only and MUST be changed for production
-        let jwt_secret = std::env::var("BITCELL_JWT_SECRET")
-            .unwrap_or_else(|_| {
-                tracing::warn!("BITCELL_JWT_SECRET not set, using default (INSECURE for production!)");
-                "bitcell-admin-jwt-secret-change-in-production".to_string()
-            });
+
         let auth = Arc::new(auth::AuthManager::new(&jwt_secret));
-        let audit = Arc::new(audit::AuditLogger::new());
+        let audit = Arc::new(match audit_log_dir(std::env::var(AUDIT_DIR_ENV).ok(), production) {
+            Some(dir) => audit::AuditLogger::with_storage(&dir)
+                .map_err(|e| format!("Failed to open audit log in {}: {}", dir.display(), e))?,
+            None => audit::AuditLogger::new(),
+        });
 
         // Try to load setup state from default location
         let setup_path = std::path::PathBuf::from(SETUP_FILE_PATH);
@@ -80,7 +90,7 @@ impl AdminConsole {
             tracing::warn!("Failed to load setup state: {}", e);
         }
 
-        Self {
+        Ok(Self {
             addr,
             api: Arc::new(AdminApi::new()),
             deployment,
@@ -92,7 +102,7 @@ impl AdminConsole {
             faucet: None,
             auth,
             audit,
-        }
+        })
     }
 
     /// Enable faucet with configuration
@@ -130,20 +140,22 @@ impl AdminConsole {
             .route("/api/faucet/info", get(api::faucet::get_info))
             .route("/api/faucet/check", post(api::faucet::check_eligibility));
 
-        // Protected routes requiring authentication
-        let protected_routes = Router::new()
-            // Dashboard (viewer role required)
+        // Read-only routes (viewer role)
+        let viewer_routes = Router::new()
+            // Dashboard
             .route("/", get(web::dashboard::index))
             .route("/dashboard", get(web::dashboard::index))
 
-            // Read-only API endpoints (viewer role)
+            // Read-only API endpoints
             .route("/api/nodes", get(api::nodes::list_nodes))
             .route("/api/nodes/:id", get(api::nodes::get_node))
             .route("/api/nodes/:id/logs", get(api::nodes::get_node_logs))
+            .route("/api/nodes/:id/logs/stream", get(api::nodes::stream_node_logs))
             .route("/api/metrics", get(api::metrics::get_metrics))
             .route("/api/metrics/chain", get(api::metrics::chain_metrics))
             .route("/api/metrics/network", get(api::metrics::network_metrics))
             .route("/api/metrics/system", get(api::metrics::system_metrics))
+            .route("/api/metrics/cluster", get(api::metrics::cluster_metrics))
             .route("/api/deployment/status", get(api::deployment::deployment_status))
             .route("/api/config", get(api::config::get_config))
             .route("/api/setup/status", get(api::setup::get_setup_status))
@@ -151,11 +163,16 @@ impl AdminConsole {
             .route("/api/blocks/:height", get(api::blocks::get_block))
             .route("/api/blocks/:height/battles", get(api::blocks::get_block_battles))
             .route("/api/audit/logs", get(api::auth::get_audit_logs))
-            // Faucet history and stats require authentication (contain operational data)
             .route("/api/faucet/history", get(api::faucet::get_history))
             .route("/api/faucet/stats", get(api::faucet::get_stats))
-            
-            // Operator routes (can start/stop nodes, deploy)
+            .route("/api/auth/logout", post(api::auth::logout))
+            .route("/api/auth/stream-token", post(api::auth::stream_token))
+            .route_layer(middleware::from_fn(|req: axum::extract::Request, next: middleware::Next| {
+                auth::require_role(auth::Role::Viewer, req, next)
+            }));
+
+        // Operator routes (can start/stop nodes, deploy)
+        let operator_routes = Router::new()
             .route("/api/nodes/:id/start", post(api::nodes::start_node))
             .route("/api/nodes/:id/stop", post(api::nodes::stop_node))
             .route("/api/deployment/deploy", post(api::deployment::deploy_node))
@@ -166,17 +183,29 @@ impl AdminConsole {
             .route("/api/setup/config-path", post(api::setup::set_config_path))
             .route("/api/setup/data-dir", post(api::setup::set_data_dir))
             .route("/api/setup/complete", post(api::setup::complete_setup))
-            
-            // Admin routes (can delete nodes, update config)
+
+            // Wallet API (can submit transactions)
+            .nest("/api/wallet", api::wallet::router().with_state(self.config.clone()))
+            .route_layer(middleware::from_fn(|req: axum::extract::Request, next: middleware::Next| {
+                auth::require_role(auth::Role::Operator, req, next)
+            }));
+
+        // Admin routes (can delete nodes, update config, manage users)
+        let admin_routes = Router::new()
             .route("/api/nodes/:id", delete(api::nodes::delete_node))
+            .route("/api/nodes/:id/reload-config", post(api::nodes::reload_node_config))
             .route("/api/config", post(api::config::update_config))
             .route("/api/auth/users", post(api::auth::create_user))
-            .route("/api/auth/logout", post(api::auth::logout))
-            
-            // Wallet API
-            .nest("/api/wallet", api::wallet::router().with_state(self.config.clone()))
-            
-            // Apply auth middleware to all protected routes
+            .route_layer(middleware::from_fn(|req: axum::extract::Request, next: middleware::Next| {
+                auth::require_role(auth::Role::Admin, req, next)
+            }));
+
+        // Protected routes requiring authentication; the token is checked
+        // before each tier's role layer
+        let protected_routes = Router::new()
+            .merge(viewer_routes)
+            .merge(operator_routes)
+            .merge(admin_routes)
             .layer(middleware::from_fn_with_state(
                 self.auth.clone(),
                 auth::auth_middleware,
@@ -216,7 +245,8 @@ impl AdminConsole {
         let app = self.build_router();
 
         let listener = tokio::net::TcpListener::bind(self.addr).await?;
-        axum::serve(listener, app).await?;
+        // Connection info lets the faucet rate-limit by client IP
+        axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
 
         Ok(())
     }
@@ -237,14 +267,141 @@ pub struct AppState {
     pub audit: Arc<audit::AuditLogger>,
 }
 
+/// Environment variable holding the JWT signing secret
+pub const JWT_SECRET_ENV: &str = "BITCELL_JWT_SECRET";
+
+/// Environment variable enabling production mode in the binary
+pub const PRODUCTION_ENV: &str = "BITCELL_ADMIN_PRODUCTION";
+
+/// Shortest JWT secret accepted in production mode
+pub const MIN_JWT_SECRET_LEN: usize = 32;
+
+/// Development-only JWT secret
+const DEV_JWT_SECRET: &str = "bitcell-admin-jwt-secret-change-in-production";
+
+/// Environment variable naming the audit log directory
+pub const AUDIT_DIR_ENV: &str = "BITCELL_AUDIT_DIR";
+
+/// Audit log directory used in production mode when `BITCELL_AUDIT_DIR` is unset
+pub const DEFAULT_AUDIT_DIR: &str = ".bitcell/audit";
+
+/// Where to persist the audit log, if anywhere
+///
+/// `configured` is the value of `BITCELL_AUDIT_DIR`. Development mode keeps
+/// the log in memory unless it is set.
+fn audit_log_dir(configured: Option<String>, production: bool) -> Option<std::path::PathBuf> {
+    configured
+        .or_else(|| production.then(|| DEFAULT_AUDIT_DIR.to_string()))
+        .map(std::path::PathBuf::from)
+}
+
+/// Choose the JWT secret from the value of `BITCELL_JWT_SECRET`
+///
+/// Production mode rejects a missing secret, the development default and
+/// anything shorter than `MIN_JWT_SECRET_LEN` bytes.
+fn load_jwt_secret(configured: Option<String>, production: bool) -> Result<String, String> {
+    match configured {
+        Some(secret) if !production => Ok(secret),
+        Some(secret) if secret == DEV_JWT_SECRET || secret.len() < MIN_JWT_SECRET_LEN => Err(format!(
+            "{} must be at least {} bytes and not the development default",
+            JWT_SECRET_ENV, MIN_JWT_SECRET_LEN
+        )),
+        Some(secret) => Ok(secret),
+        None if production => Err(format!("{} must be set in production mode", JWT_SECRET_ENV)),
+        None => {
+            tracing::warn!("{} not set, using default (INSECURE for production!)", JWT_SECRET_ENV);
+            Ok(DEV_JWT_SECRET.to_string())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use auth::{LoginRequest, Role};
+    use axum::body::Body;
+    use axum::http::{header, Method, Request, StatusCode};
+    use tower::ServiceExt;
 
     #[test]
     fn test_admin_console_creation() {
         let addr = "127.0.0.1:8080".parse().unwrap();
-        let console = AdminConsole::new(addr);
+        let console = AdminConsole::new(addr).unwrap();
         assert_eq!(console.addr, addr);
     }
+
+    #[test]
+    fn test_production_jwt_secret() {
+        assert!(load_jwt_secret(None, true).is_err());
+        assert_eq!(load_jwt_secret(None, false).unwrap(), DEV_JWT_SECRET);
+
+        assert!(load_jwt_secret(Some("too-short".to_string()), true).is_err());
+        assert!(load_jwt_secret(Some(DEV_JWT_SECRET.to_string()), true).is_err());
+        assert_eq!(load_jwt_secret(Some("too-short".to_string()), false).unwrap(), "too-short");
+
+        let strong = "a".repeat(MIN_JWT_SECRET_LEN);
+        assert_eq!(load_jwt_secret(Some(strong.clone()), true).unwrap(), strong);
+    }
+
+    #[test]
+    fn test_production_persists_audit_log() {
+        assert_eq!(audit_log_dir(None, false), None);
+        assert_eq!(audit_log_dir(None, true), Some(std::path::PathBuf::from(DEFAULT_AUDIT_DIR)));
+        assert_eq!(
+            audit_log_dir(Some("/var/log/bitcell".to_string()), false),
+            Some(std::path::PathBuf::from("/var/log/bitcell"))
+        );
+    }
+
+    fn token(console: &AdminConsole, username: &str, role: Role) -> String {
+        console.auth.add_user(username.to_string(), "secret".to_string(), role).unwrap();
+        console.auth.login(LoginRequest {
+            username: username.to_string(),
+            password: "secret".to_string(),
+        }).unwrap().access_token
+    }
+
+    async fn status(console: &AdminConsole, method: Method, uri: &str, token: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        console.build_router()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_role_tiers() {
+        let console = AdminConsole::new("127.0.0.1:0".parse().unwrap()).unwrap();
+        let viewer = token(&console, "viewer", Role::Viewer);
+        let operator = token(&console, "operator", Role::Operator);
+        let admin = token(&console, "root", Role::Admin);
+
+        // No token at all
+        assert_eq!(status(&console, Method::GET, "/api/nodes", None).await, StatusCode::UNAUTHORIZED);
+
+        // Viewer tier
+        assert_eq!(status(&console, Method::GET, "/api/nodes", Some(&viewer)).await, StatusCode::OK);
+
+        // Operator tier
+        let start = "/api/nodes/node1/start";
+        assert_eq!(status(&console, Method::POST, start, Some(&viewer)).await, StatusCode::FORBIDDEN);
+        assert_ne!(status(&console, Method::POST, start, Some(&operator)).await, StatusCode::FORBIDDEN);
+        assert_ne!(status(&console, Method::POST, start, Some(&admin)).await, StatusCode::FORBIDDEN);
+
+        // Admin tier, sharing a path with a viewer route
+        let node = "/api/nodes/node1";
+        assert_ne!(status(&console, Method::GET, node, Some(&viewer)).await, StatusCode::FORBIDDEN);
+        assert_eq!(status(&console, Method::DELETE, node, Some(&viewer)).await, StatusCode::FORBIDDEN);
+        assert_eq!(status(&console, Method::DELETE, node, Some(&operator)).await, StatusCode::FORBIDDEN);
+        assert_ne!(status(&console, Method::DELETE, node, Some(&admin)).await, StatusCode::FORBIDDEN);
+
+        for uri in ["/api/config", "/api/auth/users"] {
+            assert_eq!(status(&console, Method::POST, uri, Some(&operator)).await, StatusCode::FORBIDDEN);
+            assert_ne!(status(&console, Method::POST, uri, Some(&admin)).await, StatusCode::FORBIDDEN);
+        }
+    }
 }
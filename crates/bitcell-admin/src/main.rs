@@ -22,7 +22,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .unwrap_or_else(|| "127.0.0.1:8080".to_string())
         .parse()?;
 
-    let console = AdminConsole::new(addr);
+    // Production mode refuses to start without a strong JWT secret
+    let production = std::env::var(bitcell_admin::PRODUCTION_ENV)
+        .is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"));
+    let console = AdminConsole::try_new(addr, production)?;
 
     tracing::info!("Admin console ready");
     tracing::info!("Dashboard available at http://{}", addr);
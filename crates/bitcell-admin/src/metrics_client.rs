@@ -1,8 +1,11 @@
 //! Metrics client for fetching real data from running nodes
 
+use crate::api::NodeInfo;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NodeMetrics {
@@ -30,6 +33,17 @@ pub struct NodeMetrics {
 #[derive(Clone)]
 pub struct MetricsClient {
     client: reqwest::Client,
+    /// Last `total_txs_processed` seen per node, for throughput
+    tx_samples: Arc<Mutex<HashMap<String, (Instant, u64)>>>,
+}
+
+/// Prometheus endpoint of a node, on the port the node binary serves metrics
+pub fn metrics_endpoint(node: &NodeInfo) -> String {
+    format!(
+        "http://{}:{}/metrics",
+        node.address,
+        bitcell_node::config::metrics_port_for(node.port)
+    )
 }
 
 impl MetricsClient {
@@ -39,6 +53,7 @@ impl MetricsClient {
                 .timeout(Duration::from_secs(5))
                 .build()
                 .expect("Failed to build HTTP client for metrics"),
+            tx_samples: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -183,6 +198,77 @@ impl MetricsClient {
     }
 }
 
+impl MetricsClient {
+    /// Roll up metrics from every node in `nodes`
+    ///
+    /// Chain height is the highest reported, peers are summed, trust is
+    /// averaged weighted by each node's active miners, and throughput is
+    /// the summed rate of processed transactions since the previous call.
+    /// Unreachable nodes are listed in `errors` and left out of the rollup.
+    pub async fn aggregate(&self, nodes: &[NodeInfo]) -> ClusterMetrics {
+        let results = futures::future::join_all(
+            nodes.iter().map(|node| self.fetch_node_metrics(&node.id, &metrics_endpoint(node))),
+        )
+        .await;
+
+        let mut node_metrics = Vec::new();
+        let mut errors = Vec::new();
+        for (node, result) in nodes.iter().zip(results) {
+            match result {
+                Ok(metrics) => node_metrics.push(metrics),
+                Err(e) => errors.push(format!("{}: {}", node.id, e)),
+            }
+        }
+
+        let now = Instant::now();
+        let mut samples = self.tx_samples.lock();
+        let tx_throughput = node_metrics
+            .iter()
+            .map(|m| {
+                let previous = samples.insert(m.node_id.clone(), (now, m.total_txs_processed));
+                tx_rate(previous, now, m.total_txs_processed)
+            })
+            .sum();
+
+        ClusterMetrics {
+            total_nodes: nodes.len(),
+            reporting_nodes: node_metrics.len(),
+            chain_height: node_metrics.iter().map(|m| m.chain_height).max().unwrap_or(0),
+            total_peers: node_metrics.iter().map(|m| m.peer_count).sum(),
+            average_trust_score: weighted_trust(&node_metrics),
+            tx_throughput,
+            errors,
+        }
+    }
+}
+
+/// Transactions per second between a previous sample and now
+fn tx_rate(previous: Option<(Instant, u64)>, now: Instant, total: u64) -> f64 {
+    match previous {
+        Some((at, count)) if now > at => {
+            total.saturating_sub(count) as f64 / now.duration_since(at).as_secs_f64()
+        }
+        _ => 0.0,
+    }
+}
+
+/// Trust averaged over all active miners, or a plain mean of the nodes'
+/// scores when none report miners
+fn weighted_trust(metrics: &[NodeMetrics]) -> f64 {
+    let miners: usize = metrics.iter().map(|m| m.active_miners).sum();
+    if miners > 0 {
+        metrics
+            .iter()
+            .map(|m| m.average_trust_score * m.active_miners as f64)
+            .sum::<f64>()
+            / miners as f64
+    } else if metrics.is_empty() {
+        0.0
+    } else {
+        metrics.iter().map(|m| m.average_trust_score).sum::<f64>() / metrics.len() as f64
+    }
+}
+
 impl Default for MetricsClient {
     fn default() -> Self {
         Self::new()
@@ -208,3 +294,106 @@ pub struct AggregatedMetrics {
     pub node_metrics: Vec<NodeMetrics>,
     pub errors: Vec<String>,
 }
+
+/// Cluster-wide rollup of node metrics
+#[derive(Debug, Clone, Serialize)]
+pub struct ClusterMetrics {
+    pub total_nodes: usize,
+    pub reporting_nodes: usize,
+    /// Highest chain height reported by any node
+    pub chain_height: u64,
+    /// Sum of each node's peer count
+    pub total_peers: usize,
+    /// Mean trust score across all active miners
+    pub average_trust_score: f64,
+    /// Combined transactions per second since the previous rollup
+    pub tx_throughput: f64,
+    pub errors: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{NodeStatus, NodeType};
+    use axum::{routing::get, Router};
+
+    /// Serve `body` as a node's Prometheus endpoint, returning the node
+    async fn mock_node(id: &str, body: Arc<Mutex<String>>) -> NodeInfo {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let metrics_port = listener.local_addr().unwrap().port();
+        let app = Router::new().route("/metrics", get(move || {
+            let body = body.clone();
+            async move { body.lock().clone() }
+        }));
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        NodeInfo {
+            id: id.to_string(),
+            node_type: NodeType::Validator,
+            status: NodeStatus::Running,
+            address: "127.0.0.1".to_string(),
+            port: metrics_port - 2,
+            started_at: None,
+            enable_dht: false,
+            dht_peer_count: 0,
+            bootstrap_nodes: Vec::new(),
+            key_seed: None,
+        }
+    }
+
+    fn exposition(height: u64, peers: u64, miners: u64, trust: f64, txs: u64) -> String {
+        format!(
+            "# TYPE bitcell_chain_height gauge\n\
+             bitcell_chain_height {}\n\
+             bitcell_peer_count {}\n\
+             bitcell_active_miners {}\n\
+             bitcell_average_trust_score {}\n\
+             bitcell_txs_processed_total {}\n",
+            height, peers, miners, trust, txs
+        )
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_two_nodes() {
+        let body_a = Arc::new(Mutex::new(exposition(120, 4, 3, 0.9, 1000)));
+        let body_b = Arc::new(Mutex::new(exposition(118, 6, 1, 0.5, 400)));
+        let nodes = vec![
+            mock_node("node-a", body_a.clone()).await,
+            mock_node("node-b", body_b.clone()).await,
+        ];
+        let client = MetricsClient::new();
+
+        let cluster = client.aggregate(&nodes).await;
+        assert_eq!(cluster.total_nodes, 2);
+        assert_eq!(cluster.reporting_nodes, 2);
+        assert_eq!(cluster.chain_height, 120);
+        assert_eq!(cluster.total_peers, 10);
+        // (0.9 * 3 + 0.5 * 1) / 4 miners
+        assert!((cluster.average_trust_score - 0.8).abs() < 1e-9);
+        // No previous sample yet
+        assert_eq!(cluster.tx_throughput, 0.0);
+
+        // 150 more transactions across both nodes
+        *body_a.lock() = exposition(121, 4, 3, 0.9, 1100);
+        *body_b.lock() = exposition(121, 6, 1, 0.5, 450);
+        let pause = Duration::from_millis(200);
+        let start = Instant::now();
+        tokio::time::sleep(pause).await;
+
+        let cluster = client.aggregate(&nodes).await;
+        let elapsed = start.elapsed().as_secs_f64();
+        assert!(cluster.tx_throughput > 0.0);
+        assert!(cluster.tx_throughput <= 150.0 / pause.as_secs_f64());
+        assert!(cluster.tx_throughput >= 150.0 / (elapsed + 1.0));
+
+        // An unreachable node is reported but not rolled up
+        let mut offline = nodes[0].clone();
+        offline.id = "node-c".to_string();
+        offline.port = 0;
+        let cluster = client.aggregate(&[nodes[1].clone(), offline]).await;
+        assert_eq!(cluster.reporting_nodes, 1);
+        assert_eq!(cluster.chain_height, 121);
+        assert_eq!(cluster.errors.len(), 1);
+        assert!(cluster.errors[0].starts_with("node-c"));
+    }
+}
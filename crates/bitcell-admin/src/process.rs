@@ -14,6 +14,8 @@ pub struct NodeConfig {
     pub data_dir: String,
     pub port: u16,
     pub rpc_port: u16,
+    /// Port of the node's localhost-only admin RPC listener
+    pub admin_rpc_port: u16,
     pub log_level: String,
     pub network: String,
     pub enable_dht: bool,
@@ -100,6 +102,7 @@ impl ProcessManager {
         
         cmd.arg("--port").arg(node.config.port.to_string())
             .arg("--rpc-port").arg(node.config.rpc_port.to_string())
+            .arg("--admin-rpc-port").arg(node.config.admin_rpc_port.to_string())
             .arg("--data-dir").arg(&node.config.data_dir)
             .env("RUST_LOG", &node.config.log_level);
         
@@ -220,6 +223,28 @@ impl ProcessManager {
         }
     }
 
+    /// Admin JSON-RPC URL of a running node (localhost-only listener)
+    pub fn admin_rpc_url(&self, id: &str) -> Result<String, String> {
+        let nodes = self.nodes.read();
+        let node = nodes.get(id)
+            .ok_or_else(|| format!("Node '{}' not found", id))?;
+
+        if node.process.is_none() {
+            return Err("Node is not running".to_string());
+        }
+
+        Ok(format!("http://127.0.0.1:{}/rpc", node.config.admin_rpc_port))
+    }
+
+    /// IDs of the nodes with a running process
+    pub fn running_node_ids(&self) -> Vec<String> {
+        let nodes = self.nodes.read();
+        nodes.iter()
+            .filter(|(_, node)| node.process.is_some())
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
     /// Get node information
     pub fn get_node(&self, id: &str) -> Option<NodeInfo> {
         let nodes = self.nodes.read();
@@ -860,6 +860,7 @@ pub async fn index() -> impl IntoResponse {
                         <option value="1000">Last 1000 lines</option>
                     </select>
                     <button onclick="refreshLogs()" class="btn">🔄 Refresh</button>
+                    <button id="log-follow" onclick="toggleFollowLogs()" class="btn">▶ Follow</button>
                 </div>
                 <pre id="log-content" class="log-output" style="background: #000; color: #0f8; padding: 15px; border-radius: 8px; font-family: 'Courier New', monospace; font-size: 12px; line-height: 1.4; max-height: 500px; overflow-y: auto; white-space: pre-wrap; word-wrap: break-word;"></pre>
             </div>
@@ -1630,6 +1631,7 @@ ${node.key_seed ? `Key Seed: ${node.key_seed}` : ''}
 
         // Log viewer functions
         let currentLogNodeId = null;
+        let logStream = null;
 
         async function viewLogs(nodeId) {
             currentLogNodeId = nodeId;
@@ -1663,7 +1665,51 @@ ${node.key_seed ? `Key Seed: ${node.key_seed}` : ''}
             }
         }
 
+        // EventSource cannot send an Authorization header, so the stream is
+        // opened with a short-lived token passed in the query string
+        async function toggleFollowLogs() {
+            if (logStream) {
+                stopFollowLogs();
+                return;
+            }
+            if (!currentLogNodeId) return;
+
+            const logContent = document.getElementById('log-content');
+            try {
+                const response = await fetch('/api/auth/stream-token', { method: 'POST' });
+                if (!response.ok) {
+                    throw new Error('Failed to authorize log stream');
+                }
+                const { token } = await response.json();
+
+                logStream = new EventSource(
+                    `/api/nodes/${encodeURIComponent(currentLogNodeId)}/logs/stream?token=${encodeURIComponent(token)}`
+                );
+                logStream.onmessage = (event) => {
+                    logContent.textContent += (logContent.textContent ? '\n' : '') + event.data;
+                    logContent.scrollTop = logContent.scrollHeight;
+                };
+                logStream.onerror = () => {
+                    // The token expires within a minute, so reconnects would be rejected
+                    stopFollowLogs();
+                };
+                document.getElementById('log-follow').textContent = '⏸ Stop';
+            } catch (error) {
+                console.error('Failed to follow logs:', error);
+                logContent.textContent += `\nError: ${error.message}`;
+            }
+        }
+
+        function stopFollowLogs() {
+            if (logStream) {
+                logStream.close();
+                logStream = null;
+            }
+            document.getElementById('log-follow').textContent = '▶ Follow';
+        }
+
         function closeLogModal() {
+            stopFollowLogs();
             document.getElementById('log-modal').style.display = 'none';
             currentLogNodeId = null;
         }
@@ -1729,14 +1775,25 @@ ${node.key_seed ? `Key Seed: ${node.key_seed}` : ''}
             }
         }
 
+        // Expand run-length encoded cells ({start, len, energy}, row-major) into rows
+        function decodeCells(runs, size) {
+            const grid = Array.from({ length: size }, () => new Array(size).fill(0));
+            for (const run of runs) {
+                for (let i = run.start; i < run.start + run.len && i < size * size; i++) {
+                    grid[Math.floor(i / size)][i % size] = run.energy;
+                }
+            }
+            return grid;
+        }
+
         function renderFrame(frameIndex) {
             if (frameIndex < 0 || frameIndex >= battleFrames.length) return;
 
             const frame = battleFrames[frameIndex];
             const canvas = document.getElementById('battle-canvas');
             const ctx = canvas.getContext('2d');
-            const grid = frame.grid;
-            const size = grid.length;
+            const size = frame.size;
+            const grid = decodeCells(frame.cells, size);
             const cellSize = canvas.width / size;
 
             ctx.clearRect(0, 0, canvas.width, canvas.height);
@@ -1900,8 +1957,8 @@ ${node.key_seed ? `Key Seed: ${node.key_seed}` : ''}
             const frame = blockBattleFrames[frameIndex];
             const canvas = document.getElementById('block-battle-canvas');
             const ctx = canvas.getContext('2d');
-            const grid = frame.grid;
-            const size = grid.length;
+            const size = frame.size;
+            const grid = decodeCells(frame.cells, size);
             const cellSize = canvas.width / size;
 
             ctx.clearRect(0, 0, canvas.width, canvas.height);
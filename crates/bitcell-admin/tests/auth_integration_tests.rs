@@ -7,7 +7,7 @@ use std::net::SocketAddr;
 async fn test_auth_flow_login_and_validate() {
     // Create admin console
     let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
-    let console = AdminConsole::new(addr);
+    let console = AdminConsole::new(addr).unwrap();
     
     // Get auth manager from console (via app state)
     // This test validates the auth manager works correctly
@@ -2,11 +2,12 @@
 //!
 //! Simulates CA evolution with two gliders and determines the winner.
 
+use crate::collision::{CollisionEvent, CollisionTracker, Owner};
 use crate::glider::Glider;
 use crate::grid::{Cell, Grid, Position};
-use crate::rules::{evolve_grid, evolve_n_steps};
+use crate::rules::{evolve_grid, evolve_n_steps, Ruleset};
+use crate::sparse::SparseGrid;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 /// Number of steps to simulate a battle
 pub const BATTLE_STEPS: usize = 1000;
@@ -26,6 +27,19 @@ pub enum BattleOutcome {
     Tie,
 }
 
+/// Outcome of a battle along with its glider collisions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollisionOutcome {
+    /// Winner, as decided by [`Battle::simulate`]
+    pub outcome: BattleOutcome,
+    /// Energy of the live cells attributed to glider A at the end
+    pub surviving_energy_a: u64,
+    /// Energy of the live cells attributed to glider B at the end
+    pub surviving_energy_b: u64,
+    /// Steps at which the gliders' live regions overlapped
+    pub collisions: Vec<CollisionEvent>,
+}
+
 /// Battle history for computing MII and TED tiebreakers
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattleHistory {
@@ -134,11 +148,8 @@ impl Battle {
         }
     }
 
-    /// Set up the initial grid with both gliders
-    fn setup_grid(&self) -> Grid {
-        let mut grid = Grid::new();
-
-        // Apply spawn position jitter based on entropy
+    /// Spawn positions for gliders A and B after entropy jitter
+    fn spawn_positions(&self) -> (Position, Position) {
         let (jitter_a_x, jitter_a_y) = self.calculate_spawn_jitter(0);
         let (jitter_b_x, jitter_b_y) = self.calculate_spawn_jitter(8);
         
@@ -150,6 +161,15 @@ impl Battle {
             (SPAWN_B.x as isize + jitter_b_x) as usize,
             (SPAWN_B.y as isize + jitter_b_y) as usize,
         );
+        (spawn_a, spawn_b)
+    }
+
+    /// Set up the initial grid with both gliders
+    fn setup_grid(&self) -> Grid {
+        let mut grid = Grid::new();
+
+        // Apply spawn position jitter based on entropy
+        let (spawn_a, spawn_b) = self.spawn_positions();
 
         // Place glider A at jittered spawn position A
         grid.set_pattern(spawn_a, &self.glider_a.cells());
@@ -254,7 +274,7 @@ impl Battle {
         if !self.track_history {
             // Fast path - no history tracking
             let final_grid = evolve_n_steps(&initial_grid, self.steps);
-            return (self.determine_outcome(&final_grid, None), None);
+            return (self.determine_outcome(&final_grid, None, None), None);
         }
 
         // Slow path - track all timesteps for tiebreaker
@@ -278,12 +298,152 @@ impl Battle {
             current_grid = next_grid;
         }
 
-        let outcome = self.determine_outcome(&current_grid, Some(&history));
+        let outcome = self.determine_outcome(&current_grid, Some(&history), None);
         (outcome, Some(history))
     }
 
+    /// Simulate the battle on the CPU reference path, cross-checking each step
+    /// against the GPU evolver when one is available.
+    ///
+    /// The outcome is always derived from the CPU grids, so every node reaches
+    /// the same result regardless of its hardware. A divergence between the
+    /// backends panics in debug builds and is returned as
+    /// [`Error::BattleError`](crate::Error::BattleError) in release builds.
+    ///
+    /// # Tie-breaking
+    ///
+    /// Equal regional energy is resolved in a fixed order, using only the battle
+    /// inputs and the CPU grids:
+    /// 1. Surviving energy of each glider once its collisions are resolved,
+    ///    then the side the collisions moved energy towards
+    /// 2. MII, then TED (only when history tracking is enabled)
+    /// 3. FNV-1a hash of entropy seed and glider; the lower hash wins and A
+    ///    wins if the hashes are equal
+    pub fn run_deterministic(&self) -> crate::Result<BattleOutcome> {
+        #[cfg(any(feature = "cuda", feature = "opencl"))]
+        let gpu = crate::gpu::create_gpu_evolver().ok();
+
+        let mut history = self.track_history.then(BattleHistory::new);
+        let mut current_grid = self.setup_grid();
+
+        for _step in 0..self.steps {
+            let next_grid = evolve_grid(&current_grid);
+
+            #[cfg(any(feature = "cuda", feature = "opencl"))]
+            if let Some(Ok(gpu_grid)) = gpu.as_ref().map(|evolver| evolver.evolve(&current_grid)) {
+                let diverged = gpu_grid.cells != next_grid.cells;
+                debug_assert!(!diverged, "CPU and GPU grids diverged at step {}", _step);
+                if diverged {
+                    return Err(crate::Error::BattleError(format!(
+                        "CPU and GPU grids diverged at step {}",
+                        _step
+                    )));
+                }
+            }
+
+            if let Some(history) = history.as_mut() {
+                history.record_timestep(
+                    &self.extract_region(&current_grid, true),
+                    &self.extract_region(&next_grid, true),
+                    &self.extract_region(&current_grid, false),
+                    &self.extract_region(&next_grid, false),
+                );
+            }
+
+            current_grid = next_grid;
+        }
+
+        Ok(self.determine_outcome(&current_grid, history.as_ref(), None))
+    }
+
+    /// Simulate the battle with collision tracking
+    ///
+    /// The winner is decided exactly as by [`simulate`](Self::simulate);
+    /// collisions and the energy each glider has left are reported alongside
+    /// it. Every live cell is attributed to the glider it descends from (see
+    /// [`crate::collision`]), so debris captured in a collision counts for
+    /// the glider that won it.
+    pub fn simulate_with_collisions(&self) -> CollisionOutcome {
+        let (tracker, history) = self.track_battle();
+        self.collision_outcome(tracker, history)
+    }
+
+    /// Evolve the battle's own grid and gliders with collision tracking
+    fn track_battle(&self) -> (CollisionTracker, Option<BattleHistory>) {
+        let (spawn_a, spawn_b) = self.spawn_positions();
+        self.track_collisions(
+            &self.setup_grid(),
+            (spawn_a, &self.glider_a.cells()),
+            (spawn_b, &self.glider_b.cells()),
+        )
+    }
+
+    /// Evolve `initial` with the given patterns placed and attributed to A
+    /// and B, recording the regional history if it is being tracked
+    fn track_collisions(
+        &self,
+        initial: &Grid,
+        (spawn_a, cells_a): (Position, &[Vec<Cell>]),
+        (spawn_b, cells_b): (Position, &[Vec<Cell>]),
+    ) -> (CollisionTracker, Option<BattleHistory>) {
+        // Other live cells, e.g. entropy noise, are tracked as neutral
+        let mut tracker = CollisionTracker::new(SparseGrid::from_grid(initial));
+        tracker.place(spawn_a, cells_a, Owner::A);
+        tracker.place(spawn_b, cells_b, Owner::B);
+
+        let rules = Ruleset::default();
+        let mut history = self.track_history.then(BattleHistory::new);
+        for _ in 0..self.steps {
+            let prev_regions = history.is_some().then(|| {
+                (
+                    self.extract_sparse_region(tracker.grid(), true),
+                    self.extract_sparse_region(tracker.grid(), false),
+                )
+            });
+
+            tracker.step(&rules);
+
+            if let (Some(history), Some((prev_region_a, prev_region_b))) =
+                (history.as_mut(), prev_regions)
+            {
+                history.record_timestep(
+                    &prev_region_a,
+                    &self.extract_sparse_region(tracker.grid(), true),
+                    &prev_region_b,
+                    &self.extract_sparse_region(tracker.grid(), false),
+                );
+            }
+        }
+        (tracker, history)
+    }
+
+    /// Decide a tracked battle from its final grid and collect its accounting
+    fn collision_outcome(
+        &self,
+        tracker: CollisionTracker,
+        history: Option<BattleHistory>,
+    ) -> CollisionOutcome {
+        let outcome =
+            self.determine_outcome(&tracker.grid().to_grid(), history.as_ref(), Some(&tracker));
+
+        CollisionOutcome {
+            outcome,
+            surviving_energy_a: tracker.surviving_energy(Owner::A),
+            surviving_energy_b: tracker.surviving_energy(Owner::B),
+            collisions: tracker.into_events(),
+        }
+    }
+
     /// Determine the outcome of the battle, using tiebreakers if necessary
-    fn determine_outcome(&self, final_grid: &Grid, history: Option<&BattleHistory>) -> BattleOutcome {
+    ///
+    /// `tracker` is the battle's collision tracking if it has already been
+    /// run; otherwise it is run when regional energy ties.
+    fn determine_outcome(
+        &self,
+        final_grid: &Grid,
+        history: Option<&BattleHistory>,
+        tracker: Option<&CollisionTracker>,
+    ) -> BattleOutcome {
         // Determine winner by energy in each half of the grid
         let (mut energy_a, mut energy_b) = self.measure_regional_energy(final_grid);
 
@@ -297,7 +457,7 @@ impl Battle {
         // Check for exact tie
         if energy_a == energy_b {
             // Run tiebreaker pipeline
-            return self.run_tiebreaker(history);
+            return self.run_tiebreaker(history, tracker);
         }
 
         // Clear winner
@@ -355,8 +515,25 @@ impl Battle {
     }
 
     /// Run the MII+ tiebreaker pipeline
-    fn run_tiebreaker(&self, history: Option<&BattleHistory>) -> BattleOutcome {
-        // Stage 1: MII (Mutual Influence Integral)
+    fn run_tiebreaker(
+        &self,
+        history: Option<&BattleHistory>,
+        tracker: Option<&CollisionTracker>,
+    ) -> BattleOutcome {
+        // Stage 1: Surviving energy after collisions
+        let tracked;
+        let tracker = match tracker {
+            Some(tracker) => tracker,
+            None => {
+                tracked = self.track_battle().0;
+                &tracked
+            }
+        };
+        if let Some(outcome) = self.surviving_energy_break(tracker) {
+            return outcome;
+        }
+
+        // Stage 2: MII (Mutual Influence Integral)
         if let Some(hist) = history {
             let (mii_a, mii_b) = self.compute_mii(hist);
             
@@ -366,7 +543,7 @@ impl Battle {
                 return BattleOutcome::BWins;
             }
             
-            // Stage 2: TED (Temporal Entropy Differential)
+            // Stage 3: TED (Temporal Entropy Differential)
             let (ted_a, ted_b) = self.compute_ted(hist);
             
             if ted_a > ted_b {
@@ -376,10 +553,28 @@ impl Battle {
             }
         }
         
-        // Stage 3: Lexicographic Seed Break (Final fallback)
+        // Stage 4: Lexicographic Seed Break (Final fallback)
         self.lexicographic_break()
     }
 
+    /// Decide by the energy each glider has left, then by the side its
+    /// collisions moved energy towards
+    fn surviving_energy_break(&self, tracker: &CollisionTracker) -> Option<BattleOutcome> {
+        let mut energy_a = tracker.surviving_energy(Owner::A);
+        let mut energy_b = tracker.surviving_energy(Owner::B);
+        if self.entropy_seed != [0u8; 32] {
+            let (fluct_a, fluct_b) = self.calculate_energy_fluctuations();
+            energy_a = ((energy_a as f64 * fluct_a) as u64).max(1);
+            energy_b = ((energy_b as f64 * fluct_b) as u64).max(1);
+        }
+
+        match energy_a.cmp(&energy_b).then(tracker.net_transfer().cmp(&0)) {
+            std::cmp::Ordering::Greater => Some(BattleOutcome::AWins),
+            std::cmp::Ordering::Less => Some(BattleOutcome::BWins),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
     /// Compute Mutual Influence Integral for both participants
     pub fn compute_mii(&self, history: &BattleHistory) -> (f64, f64) {
         let mii_a_to_b = self.compute_region_mii(&history.region_b_deltas);
@@ -419,15 +614,17 @@ impl Battle {
             return 0.0;
         }
         
-        let mut freq_map: HashMap<u8, usize> = HashMap::new();
+        // Counts are summed in ascending cell-value order so the floating point
+        // result does not depend on hash map iteration order
+        let mut freq = [0usize; 256];
         for &cell in cells {
-            *freq_map.entry(cell).or_insert(0) += 1;
+            freq[cell as usize] += 1;
         }
         
         let total = cells.len() as f64;
         let mut entropy = 0.0;
         
-        for count in freq_map.values() {
+        for count in &freq {
             if *count > 0 {
                 let p = *count as f64 / total;
                 entropy -= p * p.log2();
@@ -478,6 +675,15 @@ impl Battle {
 
     /// Extract energy values from a region
     fn extract_region(&self, grid: &Grid, is_region_a: bool) -> Vec<u8> {
+        self.extract_region_with(|pos| grid.get(pos), is_region_a)
+    }
+
+    /// Extract a spawn region from a sparse grid, as [`Self::extract_region`]
+    fn extract_sparse_region(&self, grid: &SparseGrid, is_region_a: bool) -> Vec<u8> {
+        self.extract_region_with(|pos| grid.get(pos), is_region_a)
+    }
+
+    fn extract_region_with(&self, get: impl Fn(Position) -> Cell, is_region_a: bool) -> Vec<u8> {
         let region_size = 128;
         let half_region = region_size / 2;
         let center = if is_region_a { SPAWN_A } else { SPAWN_B };
@@ -490,7 +696,7 @@ impl Battle {
                     center.x.wrapping_add(x).wrapping_sub(half_region),
                     center.y.wrapping_add(y).wrapping_sub(half_region),
                 );
-                cells.push(grid.get(pos).energy());
+                cells.push(get(pos).energy());
             }
         }
         cells
@@ -827,4 +1033,109 @@ mod tests {
             assert_eq!(outcome, BattleOutcome::AWins);
         }
     }
+
+    #[test]
+    fn test_run_deterministic_matches_simulate() {
+        let glider_a = Glider::with_energy(GliderPattern::Standard, SPAWN_A, 150);
+        let glider_b = Glider::with_energy(GliderPattern::Lightweight, SPAWN_B, 100);
+
+        let battle = Battle::with_steps(glider_a.clone(), glider_b.clone(), 20);
+        assert_eq!(battle.run_deterministic().unwrap(), battle.simulate());
+
+        let battle = Battle::with_history(glider_a, glider_b, 20, [7u8; 32]);
+        let first = battle.run_deterministic().unwrap();
+        assert_eq!(first, battle.simulate());
+        assert_eq!(first, battle.run_deterministic().unwrap());
+    }
+
+    #[test]
+    fn test_shannon_entropy_order_independent() {
+        let glider_a = Glider::new(GliderPattern::Standard, SPAWN_A);
+        let glider_b = Glider::new(GliderPattern::Standard, SPAWN_B);
+        let battle = Battle::new(glider_a, glider_b);
+
+        let cells: Vec<u8> = (0..=255u8).chain(0..100).collect();
+        let mut reversed = cells.clone();
+        reversed.reverse();
+
+        assert_eq!(
+            battle.shannon_entropy(&cells).to_bits(),
+            battle.shannon_entropy(&reversed).to_bits()
+        );
+    }
+
+    #[test]
+    fn test_simulate_with_collisions_without_contact() {
+        let glider_a = Glider::with_energy(GliderPattern::Standard, SPAWN_A, 150);
+        let glider_b = Glider::with_energy(GliderPattern::Standard, SPAWN_B, 100);
+        let battle = Battle::with_steps(glider_a, glider_b, 40);
+
+        // Spawns are far apart, so the gliders never meet in 40 steps and
+        // each keeps its own energy
+        let result = battle.simulate_with_collisions();
+        assert!(result.collisions.is_empty());
+        assert_eq!(result.surviving_energy_a, 5 * 150);
+        assert_eq!(result.surviving_energy_b, 5 * 100);
+        assert_eq!(result.outcome, BattleOutcome::AWins);
+    }
+
+    #[test]
+    fn test_collisions_logged_when_gliders_meet() {
+        // The glider travels down-right and the spaceship left, across its path
+        let glider_a = Glider::with_energy(GliderPattern::Standard, SPAWN_A, 150);
+        let glider_b = Glider::with_energy(GliderPattern::Lightweight, SPAWN_B, 100);
+        let battle = Battle::with_steps(glider_a.clone(), glider_b.clone(), 80);
+
+        let (tracker, _) = battle.track_collisions(
+            &Grid::new(),
+            (Position::new(10, 10), &glider_a.cells()),
+            (Position::new(40, 18), &glider_b.cells()),
+        );
+        let collisions = tracker.into_events();
+        assert!(!collisions.is_empty());
+        let first = &collisions[0];
+        assert!(first.step >= 30 && first.step <= 40, "first contact at step {}", first.step);
+        assert!(first.position.x > 10 && first.position.x < 45);
+    }
+
+    #[test]
+    fn test_collision_outcome_matches_simulate() {
+        let glider_a = Glider::with_energy(GliderPattern::Standard, SPAWN_A, 120);
+        let glider_b = Glider::with_energy(GliderPattern::Lightweight, SPAWN_B, 130);
+        let battle = Battle::with_entropy(glider_a, glider_b, 30, [7u8; 32]);
+
+        assert_eq!(battle.simulate_with_collisions().outcome, battle.simulate());
+    }
+
+    #[test]
+    fn test_head_on_collision_favours_higher_energy() {
+        let head_on = |energy_a: u8, energy_b: u8| {
+            let glider_a = Glider::with_energy(GliderPattern::Standard, SPAWN_A, energy_a);
+            let glider_b = Glider::with_energy(GliderPattern::Standard, SPAWN_B, energy_b);
+            let battle = Battle::with_steps(glider_a.clone(), glider_b.clone(), 60);
+
+            // B's glider rotated 180° so the two travel towards each other
+            let mut cells_b = glider_b.cells();
+            cells_b.reverse();
+            for row in &mut cells_b {
+                row.reverse();
+            }
+            let (tracker, history) = battle.track_collisions(
+                &Grid::new(),
+                (Position::new(10, 10), &glider_a.cells()),
+                (Position::new(19, 19), &cells_b),
+            );
+            battle.collision_outcome(tracker, history)
+        };
+
+        let result = head_on(200, 50);
+        assert!(!result.collisions.is_empty());
+        assert!(result.surviving_energy_a > result.surviving_energy_b);
+        assert_eq!(result.outcome, BattleOutcome::AWins);
+
+        let result = head_on(50, 200);
+        assert!(!result.collisions.is_empty());
+        assert!(result.surviving_energy_b > result.surviving_energy_a);
+        assert_eq!(result.outcome, BattleOutcome::BWins);
+    }
 }
@@ -0,0 +1,240 @@
+//! Glider collision detection and annihilation accounting
+//!
+//! Every live cell is attributed to the glider it descends from:
+//! - Surviving cells keep their owner
+//! - Born cells go to the side whose live neighbors carry more energy
+//!   (neutral on a draw or when only unowned neighbors contributed)
+//!
+//! A collision is recorded whenever a cell has live neighbors owned by both
+//! gliders, i.e. their live regions overlap.
+
+use crate::grid::{Cell, Position};
+use crate::rules::Ruleset;
+use crate::sparse::SparseGrid;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Which glider a live cell is attributed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Owner {
+    A,
+    B,
+    /// Noise or cells born from an even contest
+    Neutral,
+}
+
+/// A step in which the two gliders' live regions overlapped
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollisionEvent {
+    /// Step at which the overlap was evaluated (0-based, before evolving)
+    pub step: usize,
+    /// First contested position in row-major order
+    pub position: Position,
+    /// Net energy moved towards A across contested cells this step:
+    /// (A births - A deaths) - (B births - B deaths). Negative favors B.
+    pub energy_transfer: i64,
+}
+
+/// Evolves a sparse grid while tracking cell ownership and collisions
+#[derive(Debug, Clone)]
+pub struct CollisionTracker {
+    grid: SparseGrid,
+    owners: HashMap<Position, Owner>,
+    step: usize,
+    events: Vec<CollisionEvent>,
+}
+
+impl CollisionTracker {
+    /// Start tracking a grid; all existing live cells are neutral
+    pub fn new(grid: SparseGrid) -> Self {
+        let owners = grid.live_cells().map(|(pos, _)| (pos, Owner::Neutral)).collect();
+        Self {
+            grid,
+            owners,
+            step: 0,
+            events: Vec::new(),
+        }
+    }
+
+    /// Place a pattern and attribute its live cells to `owner`
+    pub fn place(&mut self, top_left: Position, pattern: &[Vec<Cell>], owner: Owner) {
+        let size = self.grid.grid_size();
+        for (dy, row) in pattern.iter().enumerate() {
+            for (dx, &cell) in row.iter().enumerate() {
+                let pos = Position::new(top_left.x + dx, top_left.y + dy).wrap_with_size(size);
+                self.grid.set(pos, cell);
+                if cell.is_alive() {
+                    self.owners.insert(pos, owner);
+                } else {
+                    self.owners.remove(&pos);
+                }
+            }
+        }
+    }
+
+    /// Evolve one step, recording a collision event if the gliders touched
+    pub fn step(&mut self, rules: &Ruleset) {
+        let size = self.grid.grid_size();
+        let next = self.grid.step(rules);
+
+        let mut candidates: HashSet<Position> = HashSet::new();
+        for (pos, _) in self.grid.live_cells() {
+            candidates.insert(pos);
+            candidates.extend(pos.neighbors_with_size(size));
+        }
+
+        let mut owners = HashMap::with_capacity(next.live_count());
+        let mut contested: Option<Position> = None;
+        let mut energy_transfer = 0i64;
+
+        for pos in candidates {
+            let (mut energy_a, mut energy_b) = (0u32, 0u32);
+            let (mut touches_a, mut touches_b) = (false, false);
+            for neighbor in pos.neighbors_with_size(size) {
+                let cell = self.grid.get(neighbor);
+                match self.owners.get(&neighbor) {
+                    Some(Owner::A) if cell.is_alive() => {
+                        touches_a = true;
+                        energy_a += cell.energy() as u32;
+                    }
+                    Some(Owner::B) if cell.is_alive() => {
+                        touches_b = true;
+                        energy_b += cell.energy() as u32;
+                    }
+                    _ => {}
+                }
+            }
+
+            let before = self.grid.get(pos);
+            let after = next.get(pos);
+            let owner = if before.is_alive() {
+                self.owners.get(&pos).copied().unwrap_or(Owner::Neutral)
+            } else if energy_a > energy_b {
+                Owner::A
+            } else if energy_b > energy_a {
+                Owner::B
+            } else {
+                Owner::Neutral
+            };
+
+            if after.is_alive() {
+                owners.insert(pos, owner);
+            }
+
+            if touches_a && touches_b {
+                let earliest = contested.is_none_or(|c| (pos.y, pos.x) < (c.y, c.x));
+                if earliest {
+                    contested = Some(pos);
+                }
+                let gained = after.energy() as i64 - before.energy() as i64;
+                energy_transfer += match owner {
+                    Owner::A => gained,
+                    Owner::B => -gained,
+                    Owner::Neutral => 0,
+                };
+            }
+        }
+
+        if let Some(position) = contested {
+            self.events.push(CollisionEvent {
+                step: self.step,
+                position,
+                energy_transfer,
+            });
+        }
+
+        self.grid = next;
+        self.owners = owners;
+        self.step += 1;
+    }
+
+    /// Evolve for N steps
+    pub fn run(&mut self, steps: usize, rules: &Ruleset) {
+        for _ in 0..steps {
+            self.step(rules);
+        }
+    }
+
+    /// Total energy of live cells attributed to `owner`
+    pub fn surviving_energy(&self, owner: Owner) -> u64 {
+        self.grid
+            .live_cells()
+            .filter(|(pos, _)| self.owners.get(pos) == Some(&owner))
+            .map(|(_, energy)| energy as u64)
+            .sum()
+    }
+
+    /// Sum of energy transfers over all recorded collisions
+    pub fn net_transfer(&self) -> i64 {
+        self.events.iter().map(|e| e.energy_transfer).sum()
+    }
+
+    /// Collision events recorded so far
+    pub fn events(&self) -> &[CollisionEvent] {
+        &self.events
+    }
+
+    /// Consume the tracker, returning the collision events
+    pub fn into_events(self) -> Vec<CollisionEvent> {
+        self.events
+    }
+
+    /// Current grid
+    pub fn grid(&self) -> &SparseGrid {
+        &self.grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glider::GliderPattern;
+
+    /// Standard glider rotated 180° so it travels up-left
+    fn reversed_glider(energy: u8) -> Vec<Vec<Cell>> {
+        let mut pattern = GliderPattern::Standard.cells(energy);
+        pattern.reverse();
+        for row in &mut pattern {
+            row.reverse();
+        }
+        pattern
+    }
+
+    #[test]
+    fn test_no_collision_when_apart() {
+        let mut tracker = CollisionTracker::new(SparseGrid::new());
+        tracker.place(Position::new(10, 10), &GliderPattern::Standard.cells(100), Owner::A);
+        tracker.place(Position::new(500, 10), &GliderPattern::Standard.cells(100), Owner::B);
+        tracker.run(20, &Ruleset::default());
+
+        assert!(tracker.events().is_empty());
+        assert_eq!(tracker.surviving_energy(Owner::A), 500);
+        assert_eq!(tracker.surviving_energy(Owner::B), 500);
+    }
+
+    fn head_on(energy_a: u8, energy_b: u8) -> CollisionTracker {
+        let mut tracker = CollisionTracker::new(SparseGrid::new());
+        tracker.place(Position::new(10, 10), &GliderPattern::Standard.cells(energy_a), Owner::A);
+        tracker.place(Position::new(19, 19), &reversed_glider(energy_b), Owner::B);
+        tracker.run(60, &Ruleset::default());
+        tracker
+    }
+
+    #[test]
+    fn test_opposing_gliders_collide() {
+        let tracker = head_on(200, 50);
+
+        assert!(!tracker.events().is_empty());
+        let first = &tracker.events()[0];
+        assert!(first.step > 0);
+        assert!(first.position.x > 10 && first.position.x < 22);
+
+        // The higher-energy glider captures the collision debris
+        assert!(tracker.surviving_energy(Owner::A) > tracker.surviving_energy(Owner::B));
+        assert!(tracker.net_transfer() > 0);
+
+        let tracker = head_on(50, 200);
+        assert!(tracker.surviving_energy(Owner::B) > tracker.surviving_energy(Owner::A));
+        assert!(tracker.net_transfer() < 0);
+    }
+}
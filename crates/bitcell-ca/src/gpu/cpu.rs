@@ -0,0 +1,57 @@
+//! CPU implementation of `GpuEvolver`
+//!
+//! Lets callers hold a `Box<dyn GpuEvolver>` whether or not a GPU is present.
+//! Evolution goes through the same rayon-parallel code as `Grid::step`.
+
+use super::{GpuBackend, GpuDeviceInfo, GpuError, GpuEvolver};
+use crate::grid::Grid;
+use crate::rules::{evolve_grid_into_with_rules, Ruleset};
+
+/// CPU evolver implementing the GPU evolver interface
+pub struct CpuEvolver {
+    rules: Ruleset,
+    device_info: GpuDeviceInfo,
+}
+
+impl CpuEvolver {
+    /// Create a CPU evolver using Conway's rules
+    pub fn new() -> Self {
+        Self::with_rules(Ruleset::default())
+    }
+
+    /// Create a CPU evolver using the given ruleset
+    pub fn with_rules(rules: Ruleset) -> Self {
+        let compute_units = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Self {
+            rules,
+            device_info: GpuDeviceInfo {
+                backend: GpuBackend::Cpu,
+                index: 0,
+                name: "CPU".to_string(),
+                memory: 0,
+                compute_units,
+            },
+        }
+    }
+}
+
+impl Default for CpuEvolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GpuEvolver for CpuEvolver {
+    fn evolve(&self, src: &Grid) -> Result<Grid, GpuError> {
+        Ok(src.step(&self.rules))
+    }
+
+    fn evolve_into(&self, src: &Grid, dst: &mut Grid) -> Result<(), GpuError> {
+        evolve_grid_into_with_rules(src, dst, &self.rules);
+        Ok(())
+    }
+
+    fn device_info(&self) -> &GpuDeviceInfo {
+        &self.device_info
+    }
+}
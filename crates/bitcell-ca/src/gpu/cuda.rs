@@ -11,6 +11,7 @@ use cudarc::{
 
 /// CUDA kernel source for CA evolution
 const CUDA_KERNEL: &str = r#"
+// Grids in a batch are packed back to back; blockIdx.z selects the grid
 extern "C" __global__ void evolve_ca(
     const unsigned char* src,
     unsigned char* dst,
@@ -23,6 +24,10 @@ extern "C" __global__ void evolve_ca(
         return;
     }
     
+    const size_t offset = (size_t)blockIdx.z * size * size;
+    src += offset;
+    dst += offset;
+    
     const unsigned int idx = y * size + x;
     const unsigned char cell = src[idx];
     
@@ -84,21 +89,18 @@ pub struct CudaEvolver {
 
 #[cfg(feature = "cuda")]
 impl CudaEvolver {
-    /// Create a new CUDA evolver
+    /// Create a new CUDA evolver on the first device
     pub fn new() -> Result<Self, GpuError> {
+        Self::with_device(0)
+    }
+    
+    /// Create a CUDA evolver on the device with the given ordinal
+    pub fn with_device(ordinal: usize) -> Result<Self, GpuError> {
         // Initialize CUDA device
-        let device = CudaDevice::new(0)
+        let device = CudaDevice::new(ordinal)
             .map_err(|e| GpuError::InitializationFailed(format!("Failed to initialize CUDA device: {}", e)))?;
         
-        let device_name = device.name()
-            .map_err(|e| GpuError::InitializationFailed(format!("Failed to get device name: {}", e)))?;
-        
-        let device_memory = device.total_memory()
-            .map_err(|e| GpuError::InitializationFailed(format!("Failed to get device memory: {}", e)))?;
-        
-        // Get compute units (multiprocessors)
-        let compute_units = device.attribute(cudarc::driver::sys::CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MULTIPROCESSOR_COUNT)
-            .map_err(|e| GpuError::InitializationFailed(format!("Failed to get compute units: {}", e)))?;
+        let device_info = query_device_info(&device, ordinal)?;
         
         // Load and compile the kernel
         device.load_ptx(
@@ -107,13 +109,6 @@ impl CudaEvolver {
             &["evolve_ca"]
         ).map_err(|e| GpuError::InitializationFailed(format!("Failed to load CUDA kernel: {}", e)))?;
         
-        let device_info = GpuDeviceInfo {
-            backend: GpuBackend::Cuda,
-            name: device_name,
-            memory: device_memory,
-            compute_units: compute_units as usize,
-        };
-        
         Ok(Self {
             device_info,
             device,
@@ -141,6 +136,7 @@ impl GpuEvolver for CudaEvolver {
         if dst.cells.len() != num_cells || dst.size != size {
             return Err(GpuError::UnsupportedGridSize(size));
         }
+        self.device_info.check_capacity(size, 1)?;
         
         // Extract raw cell data
         let src_data: Vec<u8> = src.cells.iter().map(|c| c.state).collect();
@@ -184,11 +180,92 @@ impl GpuEvolver for CudaEvolver {
         Ok(())
     }
     
+    fn evolve_batch(&self, grids: &[Grid]) -> Result<Vec<Grid>, GpuError> {
+        if grids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let Some((size, src_data)) = crate::gpu::pack_grids(grids) else {
+            // Mixed sizes cannot share a launch
+            return grids.iter().map(|grid| self.evolve(grid)).collect();
+        };
+        self.device_info.check_capacity(size, grids.len())?;
+        
+        // Upload every grid in one transfer
+        let src_gpu = self.device.htod_copy(src_data)
+            .map_err(|e| GpuError::MemoryTransferFailed(format!("Upload failed: {}", e)))?;
+        
+        let mut dst_gpu = self.device.alloc_zeros::<u8>(size * size * grids.len())
+            .map_err(|_| GpuError::MemoryAllocationFailed)?;
+        
+        // One z-slice of blocks per grid
+        let block_size = 16;
+        let grid_x = (size + block_size - 1) / block_size;
+        let grid_y = (size + block_size - 1) / block_size;
+        
+        let cfg = LaunchConfig {
+            grid_dim: (grid_x as u32, grid_y as u32, grids.len() as u32),
+            block_dim: (block_size as u32, block_size as u32, 1),
+            shared_mem_bytes: 0,
+        };
+        
+        let func = self.device.get_func("evolve_ca", "evolve_ca")
+            .map_err(|e| GpuError::KernelExecutionFailed(format!("Failed to get kernel function: {}", e)))?;
+        
+        unsafe {
+            func.launch(cfg, (&src_gpu, &mut dst_gpu, size as u32))
+                .map_err(|e| GpuError::KernelExecutionFailed(format!("Kernel launch failed: {}", e)))?;
+        }
+        
+        let dst_data = self.device.dtoh_sync_copy(&dst_gpu)
+            .map_err(|e| GpuError::MemoryTransferFailed(format!("Download failed: {}", e)))?;
+        
+        Ok(crate::gpu::unpack_grids(size, &dst_data))
+    }
+    
     fn device_info(&self) -> &GpuDeviceInfo {
         &self.device_info
     }
 }
 
+/// Read name, memory and multiprocessor count of a CUDA device
+#[cfg(feature = "cuda")]
+fn query_device_info(device: &CudaDevice, ordinal: usize) -> Result<GpuDeviceInfo, GpuError> {
+    let name = device.name()
+        .map_err(|e| GpuError::InitializationFailed(format!("Failed to get device name: {}", e)))?;
+    
+    let memory = device.total_memory()
+        .map_err(|e| GpuError::InitializationFailed(format!("Failed to get device memory: {}", e)))?;
+    
+    // Get compute units (multiprocessors)
+    let compute_units = device.attribute(cudarc::driver::sys::CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MULTIPROCESSOR_COUNT)
+        .map_err(|e| GpuError::InitializationFailed(format!("Failed to get compute units: {}", e)))?;
+    
+    Ok(GpuDeviceInfo {
+        backend: GpuBackend::Cuda,
+        index: ordinal,
+        name,
+        memory,
+        compute_units: compute_units as usize,
+    })
+}
+
+/// List all CUDA devices, in ordinal order
+#[cfg(feature = "cuda")]
+pub fn list_devices() -> Vec<GpuDeviceInfo> {
+    let count = CudaDevice::count().unwrap_or(0).max(0) as usize;
+    (0..count)
+        .filter_map(|ordinal| {
+            let device = CudaDevice::new(ordinal).ok()?;
+            query_device_info(&device, ordinal).ok()
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "cuda"))]
+pub fn list_devices() -> Vec<GpuDeviceInfo> {
+    Vec::new()
+}
+
 /// Check if CUDA is available
 #[cfg(feature = "cuda")]
 pub fn is_available() -> bool {
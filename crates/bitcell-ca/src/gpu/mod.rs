@@ -4,7 +4,11 @@
 //! using CUDA (NVIDIA) and OpenCL (AMD/Intel) backends with automatic
 //! fallback to CPU when GPU is not available.
 
-use crate::grid::{Grid, Cell, Position};
+use crate::grid::{Grid, GRID_SIZE};
+
+pub mod cpu;
+
+pub use cpu::CpuEvolver;
 
 #[cfg(feature = "cuda")]
 pub mod cuda;
@@ -29,11 +33,35 @@ pub enum GpuBackend {
 #[derive(Debug, Clone)]
 pub struct GpuDeviceInfo {
     pub backend: GpuBackend,
+    /// Device ordinal within its backend
+    pub index: usize,
     pub name: String,
     pub memory: usize, // in bytes
     pub compute_units: usize,
 }
 
+impl GpuDeviceInfo {
+    /// Device memory needed to evolve `grids` grids of `grid_size`×`grid_size`
+    /// cells at once (one source and one destination byte per cell)
+    pub fn required_memory(grid_size: usize, grids: usize) -> usize {
+        grid_size
+            .saturating_mul(grid_size)
+            .saturating_mul(2)
+            .saturating_mul(grids)
+    }
+    
+    /// Reject workloads larger than the device's memory
+    ///
+    /// A memory of 0 means unknown/unbounded (the CPU evolver) and is never
+    /// rejected.
+    pub fn check_capacity(&self, grid_size: usize, grids: usize) -> Result<(), GpuError> {
+        if self.memory > 0 && Self::required_memory(grid_size, grids) > self.memory {
+            return Err(GpuError::MemoryAllocationFailed);
+        }
+        Ok(())
+    }
+}
+
 /// Trait for GPU-accelerated CA evolution
 pub trait GpuEvolver: Send + Sync {
     /// Evolve a grid one step using GPU acceleration
@@ -42,6 +70,14 @@ pub trait GpuEvolver: Send + Sync {
     /// Evolve a grid one step in-place using GPU acceleration
     fn evolve_into(&self, src: &Grid, dst: &mut Grid) -> Result<(), GpuError>;
     
+    /// Evolve several grids one step each
+    ///
+    /// The default evolves them one at a time; GPU backends override this to
+    /// upload all grids in one buffer and evolve them in a single launch.
+    fn evolve_batch(&self, grids: &[Grid]) -> Result<Vec<Grid>, GpuError> {
+        grids.iter().map(|grid| self.evolve(grid)).collect()
+    }
+    
     /// Get device information
     fn device_info(&self) -> &GpuDeviceInfo;
 }
@@ -68,6 +104,40 @@ pub enum GpuError {
     UnsupportedGridSize(usize),
 }
 
+/// Pack same-sized grids into one contiguous buffer of cell states
+///
+/// Returns `None` when the grids differ in size and cannot share a launch.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+pub(crate) fn pack_grids(grids: &[Grid]) -> Option<(usize, Vec<u8>)> {
+    let size = grids.first()?.grid_size();
+    if grids.iter().any(|grid| grid.grid_size() != size) {
+        return None;
+    }
+    let data = grids
+        .iter()
+        .flat_map(|grid| grid.cells.iter().map(|cell| cell.state))
+        .collect();
+    Some((size, data))
+}
+
+/// Split a packed buffer of cell states back into grids of `size`
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+pub(crate) fn unpack_grids(size: usize, data: &[u8]) -> Vec<Grid> {
+    data.chunks(size * size)
+        .map(|states| {
+            let mut grid = Grid::with_size(if size == crate::grid::LARGE_GRID_SIZE {
+                crate::grid::GridSize::Large
+            } else {
+                crate::grid::GridSize::Standard
+            });
+            for (cell, &state) in grid.cells.iter_mut().zip(states) {
+                *cell = crate::grid::Cell { state };
+            }
+            grid
+        })
+        .collect()
+}
+
 /// Detect available GPU devices
 pub fn detect_gpu() -> Option<GpuBackend> {
     #[cfg(feature = "cuda")]
@@ -106,19 +176,67 @@ pub fn create_gpu_evolver() -> Result<Box<dyn GpuEvolver>, GpuError> {
     Err(GpuError::NotAvailable)
 }
 
+/// Create a GPU evolver, falling back to the CPU when no GPU is usable
+///
+/// Unlike `create_gpu_evolver` this always succeeds; check
+/// `device_info().backend` to see which backend was selected.
+pub fn create_evolver_with_cpu_fallback() -> Box<dyn GpuEvolver> {
+    create_gpu_evolver().unwrap_or_else(|_| Box::new(CpuEvolver::new()))
+}
+
+/// List GPU devices across all enabled backends (CUDA first, then OpenCL)
+///
+/// Positions in this list are the indices accepted by
+/// `create_gpu_evolver_for_device`.
+pub fn list_gpu_devices() -> Vec<GpuDeviceInfo> {
+    #[allow(unused_mut)]
+    let mut devices = Vec::new();
+    
+    #[cfg(feature = "cuda")]
+    devices.extend(cuda::list_devices());
+    
+    #[cfg(feature = "opencl")]
+    devices.extend(opencl::list_devices());
+    
+    devices
+}
+
+/// Create a GPU evolver on the device at `index` in `list_gpu_devices()`
+pub fn create_gpu_evolver_for_device(index: usize) -> Result<Box<dyn GpuEvolver>, GpuError> {
+    let device = list_gpu_devices()
+        .into_iter()
+        .nth(index)
+        .ok_or(GpuError::NotAvailable)?;
+    
+    let evolver = create_backend_evolver(device.backend, device.index)?;
+    evolver.device_info().check_capacity(GRID_SIZE, 1)?;
+    Ok(evolver)
+}
+
 /// Create a GPU evolver with specific backend
+///
+/// Fails with `GpuError::MemoryAllocationFailed` if the device cannot hold
+/// even a standard-size grid.
 pub fn create_gpu_evolver_with_backend(backend: GpuBackend) -> Result<Box<dyn GpuEvolver>, GpuError> {
+    let evolver = create_backend_evolver(backend, 0)?;
+    evolver.device_info().check_capacity(GRID_SIZE, 1)?;
+    Ok(evolver)
+}
+
+/// Open device `index` (ordinal within the backend)
+fn create_backend_evolver(backend: GpuBackend, index: usize) -> Result<Box<dyn GpuEvolver>, GpuError> {
     match backend {
         #[cfg(feature = "cuda")]
         GpuBackend::Cuda => {
-            Ok(Box::new(cuda::CudaEvolver::new()?))
+            Ok(Box::new(cuda::CudaEvolver::with_device(index)?))
         }
         #[cfg(feature = "opencl")]
         GpuBackend::OpenCL => {
-            Ok(Box::new(opencl::OpenCLEvolver::new()?))
+            Ok(Box::new(opencl::OpenCLEvolver::with_device(index)?))
         }
-        GpuBackend::Cpu => {
-            Err(GpuError::NotAvailable)
+        // The CPU counts as a single device
+        GpuBackend::Cpu if index == 0 => {
+            Ok(Box::new(CpuEvolver::new()))
         }
         #[allow(unreachable_patterns)]
         _ => Err(GpuError::NotAvailable),
@@ -128,10 +246,36 @@ pub fn create_gpu_evolver_with_backend(backend: GpuBackend) -> Result<Box<dyn Gp
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::grid::LARGE_GRID_SIZE;
 
     #[test]
     fn test_gpu_detection() {
         // This test just checks that detection doesn't panic
         let _backend = detect_gpu();
     }
+
+    #[test]
+    fn test_memory_capacity_guard() {
+        // Synthetic 8 MiB device: fits four 1024² grid pairs (2 MiB each)
+        // but not a single 4096² pair (32 MiB)
+        let device = GpuDeviceInfo {
+            backend: GpuBackend::Cpu,
+            index: 0,
+            name: "synthetic".to_string(),
+            memory: 8 * 1024 * 1024,
+            compute_units: 1,
+        };
+
+        assert_eq!(GpuDeviceInfo::required_memory(GRID_SIZE, 1), 2 * 1024 * 1024);
+        assert!(device.check_capacity(GRID_SIZE, 4).is_ok());
+        assert!(matches!(device.check_capacity(GRID_SIZE, 5), Err(GpuError::MemoryAllocationFailed)));
+        assert!(matches!(
+            device.check_capacity(LARGE_GRID_SIZE, 1),
+            Err(GpuError::MemoryAllocationFailed)
+        ));
+
+        // Unknown memory (the CPU evolver) is never rejected
+        let unbounded = GpuDeviceInfo { memory: 0, ..device };
+        assert!(unbounded.check_capacity(LARGE_GRID_SIZE, 16).is_ok());
+    }
 }
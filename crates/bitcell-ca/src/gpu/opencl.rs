@@ -17,6 +17,8 @@ use opencl3::{
 /// OpenCL kernel source for CA evolution
 const OPENCL_KERNEL: &str = r#"
 // Conway's Game of Life with energy - OpenCL kernel
+// Grids in a batch are packed back to back; global id 2 selects the grid
+// (it is 0 for a single-grid 2D launch)
 __kernel void evolve_ca(
     __global const uchar* src,
     __global uchar* dst,
@@ -29,6 +31,10 @@ __kernel void evolve_ca(
         return;
     }
     
+    const size_t offset = get_global_id(2) * size * size;
+    src += offset;
+    dst += offset;
+    
     const int idx = y * size + x;
     const uchar cell = src[idx];
     
@@ -92,25 +98,22 @@ pub struct OpenCLEvolver {
 
 #[cfg(feature = "opencl")]
 impl OpenCLEvolver {
-    /// Create a new OpenCL evolver
+    /// Create a new OpenCL evolver on the first GPU device
     pub fn new() -> Result<Self, GpuError> {
+        Self::with_device(0)
+    }
+    
+    /// Create an OpenCL evolver on the GPU device with the given index
+    pub fn with_device(index: usize) -> Result<Self, GpuError> {
         // Find all GPU devices
         let device_ids = get_all_devices(CL_DEVICE_TYPE_GPU)
             .map_err(|e| GpuError::InitializationFailed(format!("Failed to get GPU devices: {:?}", e)))?;
         
-        let device_id = device_ids.first()
-            .ok_or_else(|| GpuError::NotAvailable)?;
+        let device_id = device_ids.get(index)
+            .ok_or(GpuError::NotAvailable)?;
         
         let device = Device::new(*device_id);
-        
-        let device_name = device.name()
-            .map_err(|e| GpuError::InitializationFailed(format!("Failed to get device name: {:?}", e)))?;
-        
-        let device_memory = device.global_mem_size()
-            .map_err(|e| GpuError::InitializationFailed(format!("Failed to get device memory: {:?}", e)))?;
-        
-        let compute_units = device.max_compute_units()
-            .map_err(|e| GpuError::InitializationFailed(format!("Failed to get compute units: {:?}", e)))?;
+        let device_info = query_device_info(&device, index)?;
         
         // Create context and command queue
         let context = Context::from_device(&device)
@@ -123,13 +126,6 @@ impl OpenCLEvolver {
         let program = Program::create_and_build_from_source(&context, OPENCL_KERNEL, "")
             .map_err(|e| GpuError::InitializationFailed(format!("Failed to build OpenCL program: {:?}", e)))?;
         
-        let device_info = GpuDeviceInfo {
-            backend: GpuBackend::OpenCL,
-            name: device_name,
-            memory: device_memory as usize,
-            compute_units: compute_units as usize,
-        };
-        
         Ok(Self {
             device_info,
             context,
@@ -159,6 +155,7 @@ impl GpuEvolver for OpenCLEvolver {
         if dst.cells.len() != num_cells || dst.size != size {
             return Err(GpuError::UnsupportedGridSize(size));
         }
+        self.device_info.check_capacity(size, 1)?;
         
         // Extract raw cell data
         let src_data: Vec<cl_uchar> = src.cells.iter().map(|c| c.state).collect();
@@ -212,11 +209,101 @@ impl GpuEvolver for OpenCLEvolver {
         Ok(())
     }
     
+    fn evolve_batch(&self, grids: &[Grid]) -> Result<Vec<Grid>, GpuError> {
+        if grids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let Some((size, src_data)) = crate::gpu::pack_grids(grids) else {
+            // Mixed sizes cannot share a launch
+            return grids.iter().map(|grid| self.evolve(grid)).collect();
+        };
+        self.device_info.check_capacity(size, grids.len())?;
+        let total_cells = src_data.len();
+        
+        let mut src_buffer = unsafe {
+            Buffer::<cl_uchar>::create(&self.context, CL_MEM_READ_ONLY, total_cells, std::ptr::null_mut())
+                .map_err(|_| GpuError::MemoryAllocationFailed)?
+        };
+        
+        let dst_buffer = unsafe {
+            Buffer::<cl_uchar>::create(&self.context, CL_MEM_WRITE_ONLY, total_cells, std::ptr::null_mut())
+                .map_err(|_| GpuError::MemoryAllocationFailed)?
+        };
+        
+        // Upload every grid in one transfer
+        unsafe {
+            self.queue.enqueue_write_buffer(&mut src_buffer, CL_BLOCKING, 0, &src_data, &[])
+                .map_err(|e| GpuError::MemoryTransferFailed(format!("Upload failed: {:?}", e)))?;
+        }
+        
+        let kernel = Kernel::create(&self.program, "evolve_ca")
+            .map_err(|e| GpuError::KernelExecutionFailed(format!("Kernel creation failed: {:?}", e)))?;
+        
+        // The third dimension indexes the grid within the batch
+        let kernel_event = unsafe {
+            ExecuteKernel::new(&kernel)
+                .set_arg(&src_buffer)
+                .set_arg(&dst_buffer)
+                .set_arg(&(size as u32))
+                .set_global_work_sizes(&[size, size, grids.len()])
+                .enqueue_nd_range(&self.queue)
+                .map_err(|e| GpuError::KernelExecutionFailed(format!("Kernel execution failed: {:?}", e)))?
+        };
+        
+        kernel_event.wait()
+            .map_err(|e| GpuError::KernelExecutionFailed(format!("Kernel wait failed: {:?}", e)))?;
+        
+        let mut dst_data = vec![0u8; total_cells];
+        unsafe {
+            self.queue.enqueue_read_buffer(&dst_buffer, CL_BLOCKING, 0, &mut dst_data, &[])
+                .map_err(|e| GpuError::MemoryTransferFailed(format!("Download failed: {:?}", e)))?;
+        }
+        
+        Ok(crate::gpu::unpack_grids(size, &dst_data))
+    }
+    
     fn device_info(&self) -> &GpuDeviceInfo {
         &self.device_info
     }
 }
 
+/// Read name, memory and compute units of an OpenCL device
+#[cfg(feature = "opencl")]
+fn query_device_info(device: &Device, index: usize) -> Result<GpuDeviceInfo, GpuError> {
+    let name = device.name()
+        .map_err(|e| GpuError::InitializationFailed(format!("Failed to get device name: {:?}", e)))?;
+    
+    let memory = device.global_mem_size()
+        .map_err(|e| GpuError::InitializationFailed(format!("Failed to get device memory: {:?}", e)))?;
+    
+    let compute_units = device.max_compute_units()
+        .map_err(|e| GpuError::InitializationFailed(format!("Failed to get compute units: {:?}", e)))?;
+    
+    Ok(GpuDeviceInfo {
+        backend: GpuBackend::OpenCL,
+        index,
+        name,
+        memory: memory as usize,
+        compute_units: compute_units as usize,
+    })
+}
+
+/// List all OpenCL GPU devices, in platform enumeration order
+#[cfg(feature = "opencl")]
+pub fn list_devices() -> Vec<GpuDeviceInfo> {
+    get_all_devices(CL_DEVICE_TYPE_GPU)
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, id)| query_device_info(&Device::new(id), index).ok())
+        .collect()
+}
+
+#[cfg(not(feature = "opencl"))]
+pub fn list_devices() -> Vec<GpuDeviceInfo> {
+    Vec::new()
+}
+
 /// Check if OpenCL is available
 #[cfg(feature = "opencl")]
 pub fn is_available() -> bool {
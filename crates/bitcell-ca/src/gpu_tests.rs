@@ -130,6 +130,143 @@ fn test_gpu_cpu_equivalence() {
     }
 }
 
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[test]
+fn test_gpu_cpu_bit_identical_seeded_grid() {
+    use crate::{Battle, Glider, GliderPattern};
+    use crate::battle::{SPAWN_A, SPAWN_B};
+
+    let evolver = match create_gpu_evolver() {
+        Ok(evolver) => evolver,
+        Err(_) => {
+            println!("No GPU available, skipping bit-identical test");
+            return;
+        }
+    };
+
+    // Seeded initial grid with gliders plus entropy-derived noise
+    let battle = Battle::with_entropy(
+        Glider::new(GliderPattern::Standard, SPAWN_A),
+        Glider::new(GliderPattern::Heavyweight, SPAWN_B),
+        10,
+        [42u8; 32],
+    );
+    let mut grid = battle.initial_grid();
+
+    for step in 0..10 {
+        let cpu_result = evolve_grid(&grid);
+        let gpu_result = evolver.evolve(&grid).expect("GPU evolution failed");
+        assert!(cpu_result.cells == gpu_result.cells, "CPU and GPU diverged at step {}", step);
+        grid = cpu_result;
+    }
+
+    assert!(battle.run_deterministic().is_ok());
+}
+
+#[test]
+fn test_cpu_fallback_matches_grid_step() {
+    use crate::glider::GliderPattern;
+    use crate::gpu::{create_evolver_with_cpu_fallback, CpuEvolver, GpuBackend, GpuEvolver};
+    use crate::grid::GRID_SIZE;
+    use crate::rules::Ruleset;
+
+    let mut grid = Grid::new();
+    grid.set_pattern(Position::new(50, 50), &GliderPattern::Standard.cells(120));
+    grid.set_pattern(Position::new(GRID_SIZE - 3, GRID_SIZE - 3), &GliderPattern::Lightweight.cells(90));
+
+    // Always yields an evolver, GPU or not
+    let evolver = create_evolver_with_cpu_fallback();
+    assert_eq!(evolver.evolve(&grid).unwrap().cells, grid.step(&Ruleset::default()).cells);
+
+    let highlife = Ruleset::highlife();
+    let cpu = CpuEvolver::with_rules(highlife.clone());
+    assert_eq!(cpu.device_info().backend, GpuBackend::Cpu);
+
+    let mut expected = grid.clone();
+    let mut dst = Grid::new();
+    for _ in 0..8 {
+        cpu.evolve_into(&grid, &mut dst).unwrap();
+        expected = expected.step(&highlife);
+        assert_eq!(dst.cells, expected.cells);
+        std::mem::swap(&mut grid, &mut dst);
+    }
+}
+
+/// A few distinct grids, as a tournament round would evolve together
+fn battle_grids() -> Vec<Grid> {
+    use crate::glider::GliderPattern;
+
+    [GliderPattern::Standard, GliderPattern::Lightweight, GliderPattern::Heavyweight]
+        .into_iter()
+        .enumerate()
+        .map(|(i, pattern)| {
+            let mut grid = Grid::new();
+            grid.set_pattern(Position::new(40 * i, 30 + i), &pattern.cells(60 + 40 * i as u8));
+            grid.set(Position::new(500, 500 + i), Cell::alive(200));
+            grid
+        })
+        .collect()
+}
+
+#[test]
+fn test_evolve_batch_matches_individual() {
+    use crate::gpu::create_evolver_with_cpu_fallback;
+
+    let grids = battle_grids();
+    let evolver = create_evolver_with_cpu_fallback();
+    let batch = evolver.evolve_batch(&grids).unwrap();
+
+    assert_eq!(batch.len(), grids.len());
+    for (grid, evolved) in grids.iter().zip(&batch) {
+        assert_eq!(evolved.cells, evolve_grid(grid).cells);
+    }
+    assert!(evolver.evolve_batch(&[]).unwrap().is_empty());
+}
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[test]
+fn test_gpu_evolve_batch_matches_individual() {
+    let evolver = match create_gpu_evolver() {
+        Ok(evolver) => evolver,
+        Err(_) => {
+            println!("No GPU available, skipping batch test");
+            return;
+        }
+    };
+
+    let grids = battle_grids();
+    let batch = evolver.evolve_batch(&grids).expect("GPU batch evolution failed");
+    for (i, (grid, evolved)) in grids.iter().zip(&batch).enumerate() {
+        let single = evolver.evolve(grid).expect("GPU evolution failed");
+        assert!(evolved.cells == single.cells, "batch entry {} differs from single launch", i);
+        assert!(evolved.cells == evolve_grid(grid).cells, "batch entry {} differs from CPU", i);
+    }
+}
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[test]
+fn test_gpu_device_enumeration() {
+    use crate::gpu::{create_gpu_evolver_for_device, list_gpu_devices};
+
+    let devices = list_gpu_devices();
+    if devices.is_empty() {
+        println!("No GPU available, skipping device enumeration test");
+        assert!(create_gpu_evolver_for_device(0).is_err());
+        return;
+    }
+
+    for (i, device) in devices.iter().enumerate() {
+        println!("GPU {}: {} ({:?} #{}, {} bytes)", i, device.name, device.backend, device.index, device.memory);
+        assert!(device.memory > 0);
+
+        let evolver = create_gpu_evolver_for_device(i).expect("listed device should open");
+        assert_eq!(evolver.device_info().backend, device.backend);
+        assert_eq!(evolver.device_info().index, device.index);
+        assert_eq!(evolver.device_info().name, device.name);
+    }
+    assert!(create_gpu_evolver_for_device(devices.len()).is_err());
+}
+
 #[cfg(any(feature = "cuda", feature = "opencl"))]
 #[test]
 fn test_gpu_large_grid_support() {
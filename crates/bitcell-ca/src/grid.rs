@@ -185,6 +185,11 @@ impl Grid {
         }
     }
 
+    /// Evolve the grid one step under the given ruleset
+    pub fn step(&self, rules: &crate::rules::Ruleset) -> Grid {
+        crate::rules::evolve_grid_with_rules(self, rules)
+    }
+
     /// Clear the grid
     pub fn clear(&mut self) {
         for cell in &mut self.cells {
@@ -6,22 +6,32 @@
 //! - Conway-like rules with energy
 //! - Glider patterns and collision detection
 //! - Battle simulation and outcome determination
+//! - Sparse grid representation for mostly-empty battles
 //! - GPU acceleration (CUDA/OpenCL) with automatic fallback
 
 pub mod grid;
 pub mod rules;
 pub mod glider;
 pub mod battle;
+pub mod sparse;
+pub mod replay;
+pub mod collision;
 
-#[cfg(any(feature = "cuda", feature = "opencl"))]
 pub mod gpu;
 
 pub use grid::{Grid, Cell, Position, GridSize, GRID_SIZE, LARGE_GRID_SIZE};
 pub use glider::{Glider, GliderPattern};
-pub use battle::{Battle, BattleOutcome, BattleHistory};
+pub use rules::Ruleset;
+pub use sparse::SparseGrid;
+pub use replay::{CellRun, Frame};
+pub use collision::{CollisionEvent, CollisionTracker, Owner};
+pub use battle::{Battle, BattleOutcome, BattleHistory, CollisionOutcome};
 
-#[cfg(any(feature = "cuda", feature = "opencl"))]
-pub use gpu::{GpuBackend, GpuEvolver, GpuError, GpuDeviceInfo, detect_gpu, create_gpu_evolver, create_gpu_evolver_with_backend};
+pub use gpu::{
+    GpuBackend, GpuEvolver, GpuError, GpuDeviceInfo, CpuEvolver, detect_gpu, create_gpu_evolver,
+    create_gpu_evolver_with_backend, create_evolver_with_cpu_fallback, create_gpu_evolver_for_device,
+    list_gpu_devices,
+};
 
 /// Result type for CA operations
 pub type Result<T> = std::result::Result<T, Error>;
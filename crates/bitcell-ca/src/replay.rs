@@ -0,0 +1,159 @@
+//! Compact battle replay frames
+//!
+//! Replays for the wallet GUI and admin dashboard are sampled from a
+//! `BattleHistory` and run-length encode only the live cells, so a frame for a
+//! mostly-empty region is a handful of runs rather than a dense array.
+
+use crate::battle::BattleHistory;
+use serde::{Deserialize, Serialize};
+
+/// A run of consecutive live cells sharing the same energy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellRun {
+    /// Index of the first cell in the run (row-major)
+    pub start: u32,
+    /// Number of cells in the run
+    pub len: u32,
+    /// Energy of every cell in the run
+    pub energy: u8,
+}
+
+/// Run-length encode cell energies, omitting dead cells
+pub fn encode_cells(cells: &[u8]) -> Vec<CellRun> {
+    let mut runs: Vec<CellRun> = Vec::new();
+    for (idx, &energy) in cells.iter().enumerate() {
+        if energy == 0 {
+            continue;
+        }
+        match runs.last_mut() {
+            Some(run) if run.energy == energy && (run.start + run.len) as usize == idx => run.len += 1,
+            _ => runs.push(CellRun {
+                start: idx as u32,
+                len: 1,
+                energy,
+            }),
+        }
+    }
+    runs
+}
+
+/// Decode runs back into `len` cell energies (runs past the end are clipped)
+pub fn decode_cells(runs: &[CellRun], len: usize) -> Vec<u8> {
+    let mut cells = vec![0u8; len];
+    for run in runs {
+        let start = (run.start as usize).min(len);
+        let end = (run.start as usize + run.len as usize).min(len);
+        cells[start..end].fill(run.energy);
+    }
+    cells
+}
+
+/// A sampled replay frame holding both battle regions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Frame {
+    /// Simulation step this frame was captured after (1-based)
+    pub step: usize,
+    /// Number of cells in each region
+    pub region_cells: usize,
+    /// Live cells in the region around spawn A
+    pub region_a: Vec<CellRun>,
+    /// Live cells in the region around spawn B
+    pub region_b: Vec<CellRun>,
+}
+
+impl Frame {
+    /// Decoded cell energies for region A
+    pub fn region_a_states(&self) -> Vec<u8> {
+        decode_cells(&self.region_a, self.region_cells)
+    }
+
+    /// Decoded cell energies for region B
+    pub fn region_b_states(&self) -> Vec<u8> {
+        decode_cells(&self.region_b, self.region_cells)
+    }
+}
+
+impl BattleHistory {
+    /// Sample every `stride`-th recorded timestep as a compact frame.
+    ///
+    /// A stride of 0 is treated as 1.
+    pub fn to_frames(&self, stride: usize) -> Vec<Frame> {
+        let stride = stride.max(1);
+        self.region_a_states
+            .iter()
+            .zip(&self.region_b_states)
+            .enumerate()
+            .step_by(stride)
+            .map(|(idx, (region_a, region_b))| Frame {
+                step: idx + 1,
+                region_cells: region_a.len(),
+                region_a: encode_cells(region_a),
+                region_b: encode_cells(region_b),
+            })
+            .collect()
+    }
+
+    /// Rebuild a history from sampled frames.
+    ///
+    /// Region states are exact. Deltas are taken between consecutive frames,
+    /// so they match the original history only at stride 1; the first frame's
+    /// deltas are zero since its predecessor was not sampled.
+    pub fn from_frames(frames: &[Frame]) -> Self {
+        let mut history = BattleHistory::new();
+        for frame in frames {
+            let curr_a = frame.region_a_states();
+            let curr_b = frame.region_b_states();
+            let prev_a = history.region_a_states.last().cloned().unwrap_or_else(|| curr_a.clone());
+            let prev_b = history.region_b_states.last().cloned().unwrap_or_else(|| curr_b.clone());
+            history.record_timestep(&prev_a, &curr_a, &prev_b, &curr_b);
+        }
+        history
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::{Battle, SPAWN_A, SPAWN_B};
+    use crate::glider::{Glider, GliderPattern};
+
+    #[test]
+    fn test_encode_decode_cells() {
+        let cells = [0, 5, 5, 5, 0, 0, 7, 5, 5, 0];
+        let runs = encode_cells(&cells);
+
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[0], CellRun { start: 1, len: 3, energy: 5 });
+        assert_eq!(decode_cells(&runs, cells.len()), cells);
+        assert!(encode_cells(&[0; 16]).is_empty());
+    }
+
+    #[test]
+    fn test_history_frames_round_trip() {
+        let glider_a = Glider::with_energy(GliderPattern::Standard, SPAWN_A, 150);
+        let glider_b = Glider::with_energy(GliderPattern::Lightweight, SPAWN_B, 100);
+        let battle = Battle::with_history(glider_a, glider_b, 12, [0u8; 32]);
+        let (_, history) = battle.simulate_with_history();
+        let history = history.unwrap();
+
+        let frames = history.to_frames(4);
+        assert_eq!(frames.iter().map(|f| f.step).collect::<Vec<_>>(), vec![1, 5, 9]);
+
+        // Frames survive a JSON round trip and stay far smaller than dense regions
+        let json = serde_json::to_string(&frames).unwrap();
+        let frames: Vec<Frame> = serde_json::from_str(&json).unwrap();
+        assert!(frames.iter().all(|f| f.region_a.len() < 16 && f.region_b.len() < 16));
+
+        let decoded = BattleHistory::from_frames(&frames);
+        assert_eq!(decoded.region_a_states.len(), 3);
+        for (sample, idx) in [0, 4, 8].into_iter().enumerate() {
+            assert_eq!(decoded.region_a_states[sample], history.region_a_states[idx]);
+            assert_eq!(decoded.region_b_states[sample], history.region_b_states[idx]);
+        }
+
+        // At stride 1 the deltas match too (after the first frame)
+        let decoded = BattleHistory::from_frames(&history.to_frames(1));
+        assert_eq!(decoded.region_a_deltas[1..], history.region_a_deltas[1..]);
+        assert_eq!(decoded.region_b_deltas[1..], history.region_b_deltas[1..]);
+    }
+}
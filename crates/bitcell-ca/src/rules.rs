@@ -5,27 +5,111 @@
 //! - Dead cells with exactly 3 neighbors become alive
 //! - New cells inherit average energy from neighbors
 //! - Cells that die lose their energy
+//!
+//! The birth/survival counts and energy decay are configurable through
+//! [`Ruleset`]; the default ruleset is the one described above (B3/S23, no decay).
 
 use crate::grid::{Cell, Grid, Position};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Birth/survival rules with energy decay
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ruleset {
+    /// `birth[n]` is true if a dead cell with `n` live neighbors becomes alive
+    pub birth: [bool; 9],
+    /// `survival[n]` is true if a live cell with `n` live neighbors survives
+    pub survival: [bool; 9],
+    /// Energy a surviving cell loses each step (a cell drained to zero dies)
+    pub energy_decay: u8,
+}
+
+impl Ruleset {
+    /// Create a ruleset from birth and survival neighbor counts (counts above 8 are ignored)
+    pub fn new(birth: &[u8], survival: &[u8], energy_decay: u8) -> Self {
+        let mut rules = Self {
+            birth: [false; 9],
+            survival: [false; 9],
+            energy_decay,
+        };
+        for &n in birth.iter().filter(|&&n| n <= 8) {
+            rules.birth[n as usize] = true;
+        }
+        for &n in survival.iter().filter(|&&n| n <= 8) {
+            rules.survival[n as usize] = true;
+        }
+        rules
+    }
+
+    /// Conway's Game of Life (B3/S23)
+    pub fn conway() -> Self {
+        Self::new(&[3], &[2, 3], 0)
+    }
+
+    /// HighLife (B36/S23)
+    pub fn highlife() -> Self {
+        Self::new(&[3, 6], &[2, 3], 0)
+    }
+
+    /// Parse a ruleset in B/S notation, e.g. `"B36/S23"`
+    pub fn from_notation(notation: &str) -> crate::Result<Self> {
+        let invalid = || crate::Error::GridError(format!("Invalid rule notation: {}", notation));
+
+        let (birth, survival) = notation.split_once('/').ok_or_else(invalid)?;
+        let birth = birth.strip_prefix(['B', 'b']).ok_or_else(invalid)?;
+        let survival = survival.strip_prefix(['S', 's']).ok_or_else(invalid)?;
+
+        let parse_counts = |digits: &str| -> crate::Result<Vec<u8>> {
+            digits
+                .chars()
+                .map(|c| match c.to_digit(10) {
+                    Some(n) if n <= 8 => Ok(n as u8),
+                    _ => Err(invalid()),
+                })
+                .collect()
+        };
+
+        Ok(Self::new(&parse_counts(birth)?, &parse_counts(survival)?, 0))
+    }
+
+    /// Set the per-step energy decay
+    pub fn with_energy_decay(mut self, energy_decay: u8) -> Self {
+        self.energy_decay = energy_decay;
+        self
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self::conway()
+    }
+}
 
 /// Evolve a cell based on its neighbors (Conway-like rules with energy)
 pub fn evolve_cell(cell: Cell, neighbors: &[Cell; 8]) -> Cell {
+    evolve_cell_with_rules(cell, neighbors, &Ruleset::default())
+}
+
+/// Evolve a cell based on its neighbors under the given ruleset
+pub fn evolve_cell_with_rules(cell: Cell, neighbors: &[Cell; 8], rules: &Ruleset) -> Cell {
     let live_neighbors: Vec<&Cell> = neighbors.iter().filter(|c| c.is_alive()).collect();
     let live_count = live_neighbors.len();
 
     if cell.is_alive() {
         // Survival rules
-        if live_count == 2 || live_count == 3 {
-            // Cell survives, keeps its energy
-            cell
+        if rules.survival[live_count] {
+            // Cell survives, losing energy to decay (if any)
+            match cell.energy().saturating_sub(rules.energy_decay) {
+                0 => Cell::dead(),
+                energy => Cell::alive(energy),
+            }
         } else {
             // Cell dies (underpopulation or overpopulation)
             Cell::dead()
         }
     } else {
         // Birth rules
-        if live_count == 3 {
+        if rules.birth[live_count] {
             // Cell becomes alive with average energy of neighbors
             let avg_energy = if live_neighbors.is_empty() {
                 1
@@ -43,13 +127,23 @@ pub fn evolve_cell(cell: Cell, neighbors: &[Cell; 8]) -> Cell {
 
 /// Evolve the entire grid one step
 pub fn evolve_grid(grid: &Grid) -> Grid {
+    evolve_grid_with_rules(grid, &Ruleset::default())
+}
+
+/// Evolve the entire grid one step under the given ruleset
+pub fn evolve_grid_with_rules(grid: &Grid, rules: &Ruleset) -> Grid {
     let mut new_grid = Grid::new();
-    evolve_grid_into(grid, &mut new_grid);
+    evolve_grid_into_with_rules(grid, &mut new_grid, rules);
     new_grid
 }
 
 /// Evolve grid from src into dst (avoiding allocation)
 pub fn evolve_grid_into(src: &Grid, dst: &mut Grid) {
+    evolve_grid_into_with_rules(src, dst, &Ruleset::default())
+}
+
+/// Evolve grid from src into dst under the given ruleset
+pub fn evolve_grid_into_with_rules(src: &Grid, dst: &mut Grid, rules: &Ruleset) {
     let size = src.grid_size();
     
     // Ensure dst matches src size
@@ -83,13 +177,18 @@ pub fn evolve_grid_into(src: &Grid, dst: &mut Grid) {
                     src.get(neighbor_positions[7]),
                 ];
 
-                row_slice[x] = evolve_cell(cell, &neighbors);
+                row_slice[x] = evolve_cell_with_rules(cell, &neighbors, rules);
             }
         });
 }
 
 /// Evolve grid for N steps
 pub fn evolve_n_steps(grid: &Grid, steps: usize) -> Grid {
+    evolve_n_steps_with_rules(grid, steps, &Ruleset::default())
+}
+
+/// Evolve grid for N steps under the given ruleset
+pub fn evolve_n_steps_with_rules(grid: &Grid, steps: usize, rules: &Ruleset) -> Grid {
     let mut current = grid.clone();
     let size = grid.grid_size();
     let mut next = if size == crate::grid::LARGE_GRID_SIZE {
@@ -99,7 +198,7 @@ pub fn evolve_n_steps(grid: &Grid, steps: usize) -> Grid {
     };
     
     for _ in 0..steps {
-        evolve_grid_into(&current, &mut next);
+        evolve_grid_into_with_rules(&current, &mut next, rules);
         std::mem::swap(&mut current, &mut next);
     }
     
@@ -221,4 +320,57 @@ mod tests {
         // Block should remain stable
         assert_eq!(evolved.live_count(), 4);
     }
+
+    #[test]
+    fn test_glider_advances_under_b3_s23() {
+        let rules = Ruleset::from_notation("B3/S23").unwrap();
+        assert_eq!(rules, Ruleset::default());
+
+        let mut grid = Grid::new();
+        let glider = crate::glider::GliderPattern::Standard.cells(100);
+        grid.set_pattern(Position::new(10, 10), &glider);
+
+        // A glider repeats its shape every 4 generations, shifted one cell diagonally
+        let evolved = evolve_n_steps_with_rules(&grid, 4, &rules);
+        let mut expected = Grid::new();
+        expected.set_pattern(Position::new(11, 11), &glider);
+
+        assert_eq!(evolved.live_count(), 5);
+        assert_eq!(evolved.cells, expected.cells);
+        assert_eq!(grid.step(&rules).cells, evolve_grid(&grid).cells);
+    }
+
+    #[test]
+    fn test_different_ruleset_changes_next_grid() {
+        // Dead cell at (11, 11) has 6 live neighbors: born under HighLife only
+        let mut grid = Grid::new();
+        for pos in [(10, 10), (11, 10), (12, 10), (10, 12), (11, 12), (12, 12)] {
+            grid.set(Position::new(pos.0, pos.1), Cell::alive(100));
+        }
+
+        let conway = grid.step(&Ruleset::conway());
+        let highlife = grid.step(&Ruleset::highlife());
+
+        assert!(!conway.get(Position::new(11, 11)).is_alive());
+        assert!(highlife.get(Position::new(11, 11)).is_alive());
+        assert_ne!(conway.cells, highlife.cells);
+    }
+
+    #[test]
+    fn test_energy_decay() {
+        let rules = Ruleset::conway().with_energy_decay(30);
+        let mut neighbors = [Cell::dead(); 8];
+        neighbors[0] = Cell::alive(100);
+        neighbors[1] = Cell::alive(100);
+
+        assert_eq!(evolve_cell_with_rules(Cell::alive(100), &neighbors, &rules).energy(), 70);
+        assert!(!evolve_cell_with_rules(Cell::alive(20), &neighbors, &rules).is_alive());
+    }
+
+    #[test]
+    fn test_ruleset_notation() {
+        assert_eq!(Ruleset::from_notation("B36/S23").unwrap(), Ruleset::highlife());
+        assert!(Ruleset::from_notation("B9/S23").is_err());
+        assert!(Ruleset::from_notation("S23").is_err());
+    }
 }
@@ -0,0 +1,196 @@
+//! Sparse grid representation - stores only live cells
+//!
+//! Battles are mostly empty space around a handful of gliders, so keeping a
+//! dense 1024×1024 (or 4096×4096) array per grid wastes memory. `SparseGrid`
+//! keeps only live cells and evolves by visiting live cells and their
+//! neighbors, producing exactly the same result as the dense evolver.
+//!
+//! Rulesets with birth on 0 neighbors (B0) are not supported: every empty
+//! cell would be born, which a sparse representation cannot express.
+
+use crate::grid::{Cell, Grid, GridSize, Position, LARGE_GRID_SIZE};
+use crate::rules::{evolve_cell_with_rules, Ruleset};
+use std::collections::{HashMap, HashSet};
+
+/// Toroidal grid storing only live cells and their energy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid {
+    /// Live cells keyed by position
+    cells: HashMap<Position, u8>,
+    /// Grid size (width/height, since it's square)
+    size: usize,
+}
+
+impl SparseGrid {
+    /// Create an empty sparse grid with standard size (1024×1024)
+    pub fn new() -> Self {
+        Self::with_size(GridSize::Standard)
+    }
+
+    /// Create an empty sparse grid with specified size
+    pub fn with_size(grid_size: GridSize) -> Self {
+        Self {
+            cells: HashMap::new(),
+            size: grid_size.size(),
+        }
+    }
+
+    /// Build a sparse grid from the live cells of a dense grid
+    pub fn from_grid(grid: &Grid) -> Self {
+        let size = grid.grid_size();
+        let cells = grid
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.is_alive())
+            .map(|(idx, cell)| (Position::new(idx % size, idx / size), cell.energy()))
+            .collect();
+
+        Self { cells, size }
+    }
+
+    /// Expand into a dense grid
+    pub fn to_grid(&self) -> Grid {
+        let mut grid = Grid::with_size(if self.size == LARGE_GRID_SIZE {
+            GridSize::Large
+        } else {
+            GridSize::Standard
+        });
+        for (&pos, &energy) in &self.cells {
+            grid.set(pos, Cell::alive(energy));
+        }
+        grid
+    }
+
+    /// Get grid size
+    pub fn grid_size(&self) -> usize {
+        self.size
+    }
+
+    /// Get cell at position
+    pub fn get(&self, pos: Position) -> Cell {
+        let pos = pos.wrap_with_size(self.size);
+        self.cells
+            .get(&pos)
+            .map_or_else(Cell::dead, |&energy| Cell::alive(energy))
+    }
+
+    /// Set cell at position (setting a dead cell removes it)
+    pub fn set(&mut self, pos: Position, cell: Cell) {
+        let pos = pos.wrap_with_size(self.size);
+        if cell.is_alive() {
+            self.cells.insert(pos, cell.energy());
+        } else {
+            self.cells.remove(&pos);
+        }
+    }
+
+    /// Iterate over live cells as (position, energy)
+    pub fn live_cells(&self) -> impl Iterator<Item = (Position, u8)> + '_ {
+        self.cells.iter().map(|(&pos, &energy)| (pos, energy))
+    }
+
+    /// Count live cells
+    pub fn live_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Total energy in grid
+    pub fn total_energy(&self) -> u64 {
+        self.cells.values().map(|&energy| energy as u64).sum()
+    }
+
+    /// Evolve the grid one step under the given ruleset
+    pub fn step(&self, rules: &Ruleset) -> SparseGrid {
+        // Only live cells and their neighbors can be alive next step
+        let mut candidates: HashSet<Position> = HashSet::with_capacity(self.cells.len() * 9);
+        for &pos in self.cells.keys() {
+            candidates.insert(pos);
+            candidates.extend(pos.neighbors_with_size(self.size));
+        }
+
+        let cells = candidates
+            .into_iter()
+            .filter_map(|pos| {
+                let neighbor_positions = pos.neighbors_with_size(self.size);
+                let neighbors = neighbor_positions.map(|n| self.get(n));
+                let next = evolve_cell_with_rules(self.get(pos), &neighbors, rules);
+                next.is_alive().then_some((pos, next.energy()))
+            })
+            .collect();
+
+        Self {
+            cells,
+            size: self.size,
+        }
+    }
+
+    /// Evolve the grid for N steps under the given ruleset
+    pub fn evolve_n_steps(&self, steps: usize, rules: &Ruleset) -> SparseGrid {
+        let mut current = self.clone();
+        for _ in 0..steps {
+            current = current.step(rules);
+        }
+        current
+    }
+}
+
+impl Default for SparseGrid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&Grid> for SparseGrid {
+    fn from(grid: &Grid) -> Self {
+        Self::from_grid(grid)
+    }
+}
+
+impl From<&SparseGrid> for Grid {
+    fn from(grid: &SparseGrid) -> Self {
+        grid.to_grid()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glider::GliderPattern;
+    use crate::grid::GRID_SIZE;
+    use crate::rules::evolve_n_steps;
+
+    #[test]
+    fn test_dense_round_trip() {
+        let mut grid = Grid::new();
+        grid.set(Position::new(0, 0), Cell::alive(10));
+        grid.set(Position::new(GRID_SIZE - 1, 5), Cell::alive(200));
+
+        let sparse = SparseGrid::from_grid(&grid);
+        assert_eq!(sparse.live_count(), 2);
+        assert_eq!(sparse.total_energy(), grid.total_energy());
+        assert_eq!(sparse.get(Position::new(GRID_SIZE - 1, 5)), Cell::alive(200));
+        assert_eq!(sparse.to_grid().cells, grid.cells);
+    }
+
+    #[test]
+    fn test_glider_sparse_matches_dense() {
+        let mut grid = Grid::new();
+        // Place near the edge so the glider wraps around the torus
+        grid.set_pattern(
+            Position::new(GRID_SIZE - 10, GRID_SIZE - 10),
+            &GliderPattern::Standard.cells(120),
+        );
+
+        let mut sparse = SparseGrid::from_grid(&grid);
+        let rules = Ruleset::default();
+        for _ in 0..50 {
+            sparse = sparse.step(&rules);
+        }
+
+        let dense = evolve_n_steps(&grid, 50);
+        assert_eq!(sparse.live_count(), 5);
+        assert_eq!(sparse.to_grid().cells, dense.cells);
+        assert_eq!(sparse, SparseGrid::from_grid(&dense));
+    }
+}
@@ -2,11 +2,20 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Position in the source, both 1-based
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Span {
+    pub line: usize,
+    pub col: usize,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Contract {
     pub name: String,
     pub storage: Vec<StorageDecl>,
+    pub events: Vec<EventDecl>,
     pub functions: Vec<Function>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -15,6 +24,20 @@ pub struct StorageDecl {
     pub ty: Type,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventDecl {
+    pub name: String,
+    pub params: Vec<Parameter>,
+}
+
+impl EventDecl {
+    /// Canonical signature hashed into the event topic, e.g. `Transfer(address,address,uint)`
+    pub fn signature(&self) -> String {
+        let params: Vec<String> = self.params.iter().map(|p| p.ty.canonical_name()).collect();
+        format!("{}({})", self.name, params.join(","))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Type {
     Uint,
@@ -23,12 +46,35 @@ pub enum Type {
     Mapping(Box<Type>, Box<Type>),
 }
 
+impl Type {
+    /// Name of the type as written in source, used in function signatures
+    pub fn canonical_name(&self) -> String {
+        match self {
+            Type::Uint => "uint".to_string(),
+            Type::Bool => "bool".to_string(),
+            Type::Address => "address".to_string(),
+            Type::Mapping(key, value) => {
+                format!("mapping({}=>{})", key.canonical_name(), value.canonical_name())
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Function {
     pub name: String,
     pub params: Vec<Parameter>,
     pub return_type: Option<Type>,
     pub body: Vec<Statement>,
+    pub span: Span,
+}
+
+impl Function {
+    /// Canonical signature hashed into the function selector, e.g. `transfer(address,uint)`
+    pub fn signature(&self) -> String {
+        let params: Vec<String> = self.params.iter().map(|p| p.ty.canonical_name()).collect();
+        format!("{}({})", self.name, params.join(","))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -37,35 +83,80 @@ pub struct Parameter {
     pub ty: Type,
 }
 
+/// A statement; `span` is where it starts
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Statement {
     Let {
         name: String,
         value: Expression,
+        span: Span,
     },
     Assign {
         target: Expression,
         value: Expression,
+        span: Span,
     },
     If {
         condition: Expression,
         then_block: Vec<Statement>,
         else_block: Option<Vec<Statement>>,
+        span: Span,
+    },
+    While {
+        condition: Expression,
+        body: Vec<Statement>,
+        span: Span,
+    },
+    For {
+        init: Box<Statement>,
+        condition: Expression,
+        update: Box<Statement>,
+        body: Vec<Statement>,
+        span: Span,
     },
     Return {
         value: Option<Expression>,
+        span: Span,
     },
     Require {
         condition: Expression,
         message: String,
+        span: Span,
+    },
+    Emit {
+        event: String,
+        args: Vec<Expression>,
+        span: Span,
+    },
+    Expression {
+        expr: Expression,
+        span: Span,
     },
-    Expression(Expression),
+}
+
+impl Statement {
+    pub fn span(&self) -> Span {
+        match self {
+            Statement::Let { span, .. }
+            | Statement::Assign { span, .. }
+            | Statement::If { span, .. }
+            | Statement::While { span, .. }
+            | Statement::For { span, .. }
+            | Statement::Return { span, .. }
+            | Statement::Require { span, .. }
+            | Statement::Emit { span, .. }
+            | Statement::Expression { span, .. } => *span,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Expression {
     Literal(Literal),
-    Identifier(String),
+    Identifier {
+        name: String,
+        span: Span,
+    },
     Binary {
         left: Box<Expression>,
         op: BinaryOp,
@@ -79,6 +170,12 @@ pub enum Expression {
         name: String,
         args: Vec<Expression>,
     },
+    /// `call Contract.function(args)`: invoke a function of another contract
+    ExternalCall {
+        contract: String,
+        function: String,
+        args: Vec<Expression>,
+    },
     Index {
         expr: Box<Expression>,
         index: Box<Expression>,
@@ -2,7 +2,7 @@
 //!
 //! Compiles .bcl files to ZKVM bytecode
 
-use bitcell_compiler::{compile, CompilerError};
+use bitcell_compiler::{compile_program, CompilerError, Program};
 use std::fs;
 use std::path::PathBuf;
 
@@ -44,9 +44,9 @@ fn main() {
         
         println!("\nCompiling example...");
         match compile_source(example_source) {
-            Ok(instructions) => {
+            Ok(program) => {
                 println!("✓ Compilation successful!");
-                println!("Generated {} instructions", instructions.len());
+                println!("Generated {} instructions", program.instructions.len());
             }
             Err(e) => {
                 eprintln!("✗ Compilation failed: {}", e);
@@ -77,12 +77,12 @@ fn main() {
     
     // Compile
     match compile_source(&source) {
-        Ok(instructions) => {
+        Ok(program) => {
             println!("✓ Compilation successful!");
-            println!("Generated {} instructions", instructions.len());
+            println!("Generated {} instructions", program.instructions.len());
             
             // Serialize instructions to binary format
-            let bytecode = serialize_instructions(&instructions);
+            let bytecode = serialize_program(&program);
             
             // Write output
             if let Err(e) = fs::write(&output_path, bytecode) {
@@ -99,23 +99,24 @@ fn main() {
     }
 }
 
-fn compile_source(source: &str) -> Result<Vec<bitcell_zkvm::Instruction>, CompilerError> {
-    compile(source)
+fn compile_source(source: &str) -> Result<Program, CompilerError> {
+    compile_program(source)
 }
 
-fn serialize_instructions(instructions: &[bitcell_zkvm::Instruction]) -> Vec<u8> {
+fn serialize_program(program: &Program) -> Vec<u8> {
     // Simple binary serialization
-    // Format: [count: u32][instruction1][instruction2]...
+    // Format: [count: u32][instruction1][instruction2]...[data section]
     // Each instruction: [opcode: u8][rd: u8][rs1: u8][rs2_imm: u32]
+    // Data section: [message count: u32] then per message [len: u32][utf8 bytes]
     
     let mut bytes = Vec::new();
     
     // Write instruction count
-    let count = instructions.len() as u32;
+    let count = program.instructions.len() as u32;
     bytes.extend_from_slice(&count.to_le_bytes());
     
     // Write each instruction
-    for inst in instructions {
+    for inst in &program.instructions {
         // Opcode as u8
         bytes.push(opcode_to_byte(&inst.opcode));
         bytes.push(inst.rd);
@@ -123,6 +124,13 @@ fn serialize_instructions(instructions: &[bitcell_zkvm::Instruction]) -> Vec<u8>
         bytes.extend_from_slice(&inst.rs2_imm.to_le_bytes());
     }
     
+    // Write revert messages referenced by Revert instructions
+    bytes.extend_from_slice(&(program.revert_messages.len() as u32).to_le_bytes());
+    for message in &program.revert_messages {
+        bytes.extend_from_slice(&(message.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(message.as_bytes());
+    }
+    
     bytes
 }
 
@@ -151,5 +159,11 @@ fn opcode_to_byte(opcode: &bitcell_zkvm::OpCode) -> u8 {
         OpCode::Ret => 19,
         OpCode::Hash => 20,
         OpCode::Halt => 21,
+        OpCode::SLt => 22,
+        OpCode::SGt => 23,
+        OpCode::LoadImm => 24,
+        OpCode::Revert => 25,
+        OpCode::Log => 26,
+        OpCode::Gas => 27,
     }
 }
@@ -1,22 +1,362 @@
 //! Code generator for BCL to ZKVM bytecode
 
 use crate::ast::*;
+use crate::stdlib::{functions as builtins, memory};
 use crate::{CompilerError, Result};
+use bitcell_crypto::Hash256;
 use bitcell_zkvm::{Instruction, OpCode};
 use std::collections::HashMap;
 
-pub fn generate(contract: &Contract) -> Result<Vec<Instruction>> {
+/// Always zero; base register for absolute memory accesses
+const ZERO_REG: u8 = 0;
+
+/// Register holding a function's return value when execution stops
+pub const RETURN_REG: u8 = 1;
+
+/// Scratch register used to build immediates wider than 32 bits
+const SCRATCH_REG: u8 = 9;
+
+/// First register available for locals and temporaries (0-9 are reserved)
+const FIRST_GENERAL_REG: u8 = 10;
+
+/// Number of registers in the ZKVM register file
+const NUM_REGISTERS: u8 = 32;
+
+/// Compiled contract: bytecode plus the data it references
+#[derive(Debug, Clone, Default)]
+pub struct Program {
+    pub instructions: Vec<Instruction>,
+    /// Revert messages, indexed by the immediate of `Revert` instructions
+    pub revert_messages: Vec<String>,
+}
+
+pub fn generate(contracts: &[Contract]) -> Result<Vec<Instruction>> {
+    Ok(generate_program(contracts)?.instructions)
+}
+
+/// Generate one program containing every contract
+///
+/// The first contract is the entry point: its dispatcher starts at address 0.
+/// The others are only reachable through `call Contract.function(...)`.
+pub fn generate_program(contracts: &[Contract]) -> Result<Program> {
     let mut generator = CodeGenerator::new();
-    generator.generate_contract(contract)
+    for contract in contracts {
+        for func in &contract.functions {
+            let selector = function_selector(&func.signature());
+            generator.selectors.insert((contract.name.clone(), func.name.clone()), selector);
+        }
+    }
+    for (index, contract) in contracts.iter().enumerate() {
+        generator.generate_contract(index, contract)?;
+    }
+    generator.finish()
+}
+
+/// Selector the dispatcher compares against `memory::FUNCTION_SELECTOR`
+///
+/// The first four bytes (big-endian) of the SHA-256 of the function's
+/// canonical signature, e.g. `transfer(address,uint)`; see
+/// `Function::signature`.
+pub fn function_selector(signature: &str) -> u32 {
+    let hash = Hash256::hash(signature.as_bytes());
+    let bytes = hash.as_bytes();
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+/// Optimize generated bytecode in place
+///
+/// Folds arithmetic on known constants into `LoadImm`, removes instructions
+/// that can never execute (e.g. code after a `Ret` or `Halt`), jumps to the
+/// next instruction and side-effect-free instructions whose result is never
+/// read. Jump targets are remapped accordingly.
+///
+/// Follows the codegen calling convention: `ZERO_REG` is treated as zero if
+/// nothing writes it, and `RETURN_REG` is the only register observed once
+/// execution stops.
+pub fn optimize(instructions: &mut Vec<Instruction>) {
+    loop {
+        let folded = fold_constants(instructions);
+        let removed = remove_dead_code(instructions);
+        if !folded && !removed {
+            break;
+        }
+    }
+}
+
+/// Registers read by an instruction, as a bit mask
+fn read_mask(inst: &Instruction) -> u32 {
+    let regs: &[u8] = match inst.opcode {
+        OpCode::Add | OpCode::Sub | OpCode::Mul | OpCode::Div | OpCode::Mod
+        | OpCode::And | OpCode::Or | OpCode::Xor
+        | OpCode::Eq | OpCode::Lt | OpCode::Gt | OpCode::Le | OpCode::Ge
+        | OpCode::SLt | OpCode::SGt => &[inst.rs1, inst.rs2()],
+        OpCode::Hash => &[inst.rs1, inst.rs2(), inst.rd],
+        OpCode::Not | OpCode::Load | OpCode::Jz | OpCode::Log => &[inst.rs1],
+        OpCode::Store => &[inst.rs1, inst.rd],
+        OpCode::LoadImm | OpCode::Jmp | OpCode::Call | OpCode::Ret
+        | OpCode::Gas | OpCode::Halt | OpCode::Revert => &[],
+    };
+    regs.iter()
+        .filter(|&&reg| reg < NUM_REGISTERS)
+        .fold(0, |mask, &reg| mask | (1 << reg))
+}
+
+/// Register written by an instruction, if any
+fn written_register(inst: &Instruction) -> Option<u8> {
+    match inst.opcode {
+        OpCode::Add | OpCode::Sub | OpCode::Mul | OpCode::Div | OpCode::Mod
+        | OpCode::And | OpCode::Or | OpCode::Xor | OpCode::Not
+        | OpCode::Eq | OpCode::Lt | OpCode::Gt | OpCode::Le | OpCode::Ge
+        | OpCode::SLt | OpCode::SGt
+        | OpCode::Load | OpCode::LoadImm | OpCode::Gas => (inst.rd < NUM_REGISTERS).then_some(inst.rd),
+        OpCode::Store | OpCode::Hash | OpCode::Jmp | OpCode::Jz | OpCode::Call | OpCode::Ret
+        | OpCode::Halt | OpCode::Revert | OpCode::Log => None,
+    }
+}
+
+/// Whether an instruction only writes its destination register and cannot fail
+fn is_pure(inst: &Instruction) -> bool {
+    matches!(
+        inst.opcode,
+        OpCode::Add | OpCode::Sub | OpCode::Mul | OpCode::And | OpCode::Or
+            | OpCode::Xor | OpCode::Not | OpCode::Eq | OpCode::Lt | OpCode::Gt
+            | OpCode::Le | OpCode::Ge | OpCode::SLt | OpCode::SGt
+            | OpCode::LoadImm
+    )
+}
+
+/// Evaluate an arithmetic/comparison instruction on constant operands
+fn evaluate(opcode: OpCode, a: u64, b: u64) -> Option<u64> {
+    Some(match opcode {
+        OpCode::Add => a.wrapping_add(b),
+        OpCode::Sub => a.wrapping_sub(b),
+        OpCode::Mul => a.wrapping_mul(b),
+        OpCode::Div => a.checked_div(b)?,
+        OpCode::Mod => a.checked_rem(b)?,
+        OpCode::And => a & b,
+        OpCode::Or => a | b,
+        OpCode::Xor => a ^ b,
+        OpCode::Not => !a,
+        OpCode::Eq => (a == b) as u64,
+        OpCode::Lt => (a < b) as u64,
+        OpCode::Gt => (a > b) as u64,
+        OpCode::Le => (a <= b) as u64,
+        OpCode::Ge => (a >= b) as u64,
+        OpCode::SLt => ((a as i64) < (b as i64)) as u64,
+        OpCode::SGt => ((a as i64) > (b as i64)) as u64,
+        _ => return None,
+    })
+}
+
+/// Instructions that may run after `index`
+fn successors(instructions: &[Instruction], index: usize) -> Vec<usize> {
+    let inst = &instructions[index];
+    let next = index + 1;
+    let mut succ = match inst.opcode {
+        OpCode::Jmp => vec![inst.imm() as usize],
+        OpCode::Jz | OpCode::Call => vec![inst.imm() as usize, next],
+        OpCode::Ret | OpCode::Halt | OpCode::Revert => vec![],
+        _ => vec![next],
+    };
+    succ.retain(|&i| i < instructions.len());
+    succ
+}
+
+/// Replace operations on known constants with `LoadImm`
+///
+/// Constants are only tracked within straight-line code: knowledge is
+/// dropped at every jump target and after every control transfer.
+fn fold_constants(instructions: &mut [Instruction]) -> bool {
+    let zero_reg_constant = instructions.iter().all(|i| written_register(i) != Some(ZERO_REG));
+    let targets: std::collections::HashSet<usize> = instructions.iter()
+        .filter(|i| matches!(i.opcode, OpCode::Jmp | OpCode::Jz | OpCode::Call))
+        .map(|i| i.imm() as usize)
+        .collect();
+    
+    let reset = |known: &mut [Option<u64>; 32]| {
+        *known = [None; 32];
+        if zero_reg_constant {
+            known[ZERO_REG as usize] = Some(0);
+        }
+    };
+    
+    let mut known = [None; 32];
+    reset(&mut known);
+    let mut changed = false;
+    
+    for (index, inst) in instructions.iter_mut().enumerate() {
+        if targets.contains(&index) {
+            reset(&mut known);
+        }
+        
+        let operand = |reg: u8| known.get(reg as usize).copied().flatten();
+        let value = match inst.opcode {
+            OpCode::LoadImm => Some(inst.imm() as u64),
+            OpCode::Not => operand(inst.rs1).and_then(|a| evaluate(inst.opcode, a, 0)),
+            _ if is_pure(inst) || matches!(inst.opcode, OpCode::Div | OpCode::Mod) => {
+                match (operand(inst.rs1), operand(inst.rs2())) {
+                    (Some(a), Some(b)) => evaluate(inst.opcode, a, b),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        
+        if let Some(value) = value {
+            if inst.opcode != OpCode::LoadImm {
+                if let Ok(imm) = u32::try_from(value) {
+                    *inst = Instruction::new(OpCode::LoadImm, inst.rd, 0, imm);
+                    changed = true;
+                }
+            }
+        }
+        
+        if let Some(rd) = written_register(inst) {
+            known[rd as usize] = value;
+        }
+        if matches!(inst.opcode, OpCode::Jmp | OpCode::Call | OpCode::Ret | OpCode::Halt | OpCode::Revert) {
+            reset(&mut known);
+        }
+    }
+    
+    changed
+}
+
+/// Remove unreachable instructions and pure instructions with unused results
+fn remove_dead_code(instructions: &mut Vec<Instruction>) -> bool {
+    let len = instructions.len();
+    if len == 0 {
+        return false;
+    }
+    
+    // Reachability from the entry point
+    let mut reachable = vec![false; len];
+    let mut stack = vec![0];
+    while let Some(index) = stack.pop() {
+        if !reachable[index] {
+            reachable[index] = true;
+            stack.extend(successors(instructions, index));
+        }
+    }
+    
+    // Backward liveness; a callee may read anything, so a Call keeps all live
+    let has_calls = instructions.iter().any(|i| i.opcode == OpCode::Call);
+    let exit_live: u32 = 1 << RETURN_REG;
+    let mut live_in = vec![0u32; len];
+    let mut live_out = vec![0u32; len];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for index in (0..len).rev() {
+            let inst = &instructions[index];
+            let out = match inst.opcode {
+                OpCode::Call => u32::MAX,
+                OpCode::Ret if has_calls => u32::MAX,
+                OpCode::Ret | OpCode::Halt | OpCode::Revert => exit_live,
+                _ => {
+                    let succ = successors(instructions, index);
+                    // Falling off the end or jumping outside the program stops execution
+                    let expected = if inst.opcode == OpCode::Jz { 2 } else { 1 };
+                    let leaves = succ.len() < expected;
+                    succ.iter().fold(if leaves { exit_live } else { 0 }, |m, &s| m | live_in[s])
+                }
+            };
+            let written = written_register(inst).map_or(0, |rd| 1u32 << rd);
+            let input = read_mask(inst) | (out & !written);
+            if out != live_out[index] || input != live_in[index] {
+                live_out[index] = out;
+                live_in[index] = input;
+                changed = true;
+            }
+        }
+    }
+    
+    let keep: Vec<bool> = (0..len)
+        .map(|i| {
+            let inst = &instructions[i];
+            let dead_store = is_pure(inst)
+                && written_register(inst).is_none_or(|rd| live_out[i] & (1 << rd) == 0);
+            let jump_to_next = matches!(inst.opcode, OpCode::Jmp | OpCode::Jz)
+                && inst.imm() as usize == i + 1;
+            reachable[i] && !dead_store && !jump_to_next
+        })
+        .collect();
+    if keep.iter().all(|&k| k) {
+        return false;
+    }
+    
+    // new_index[i] is where instruction i (or the next kept one) ends up
+    let mut new_index = vec![0usize; len + 1];
+    let mut next = 0;
+    for i in 0..len {
+        new_index[i] = next;
+        if keep[i] {
+            next += 1;
+        }
+    }
+    new_index[len] = next;
+    
+    let mut optimized: Vec<Instruction> = instructions.iter()
+        .zip(&keep)
+        .filter(|(_, &k)| k)
+        .map(|(inst, _)| *inst)
+        .collect();
+    for inst in optimized.iter_mut() {
+        if matches!(inst.opcode, OpCode::Jmp | OpCode::Jz | OpCode::Call) {
+            let target = inst.imm() as usize;
+            if target <= len {
+                inst.rs2_imm = new_index[target] as u32;
+            }
+        }
+    }
+    
+    // A jump to a removed tail now points past the end; keep it valid
+    if optimized.iter().any(|i| {
+        matches!(i.opcode, OpCode::Jmp | OpCode::Jz | OpCode::Call) && i.imm() as usize == next
+    }) {
+        optimized.push(Instruction::new(OpCode::Halt, 0, 0, 0));
+    }
+    
+    *instructions = optimized;
+    true
+}
+
+/// Topic written by `emit` for an event
+///
+/// The first four bytes (big-endian) of the SHA-256 of `event:` followed by
+/// the event's canonical signature, e.g. `Transfer(address,address,uint)`;
+/// see `EventDecl::signature`. Events that share a name but not parameter
+/// types get distinct topics, and the prefix keeps topics apart from
+/// function selectors.
+pub fn event_topic(signature: &str) -> u32 {
+    let hash = Hash256::hash(format!("event:{}", signature).as_bytes());
+    let bytes = hash.as_bytes();
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
 }
 
+/// Message of the revert raised when no function matches the selector
+pub const UNKNOWN_SELECTOR_MESSAGE: &str = "Unknown function selector";
+
 struct CodeGenerator {
     instructions: Vec<Instruction>,
     storage_addrs: HashMap<String, u32>,
+    /// Dispatcher address of each contract generated so far
+    contract_entries: HashMap<String, u32>,
+    /// `Call` instructions waiting for their callee's dispatcher address
+    call_fixups: Vec<(usize, String)>,
+    /// Selector of every function, keyed by contract and function name
+    selectors: HashMap<(String, String), u32>,
+    /// Topic of every event of the contract being generated
+    event_topics: HashMap<String, u32>,
+    /// Spill frame of the contract being generated
+    frame_start: u32,
     local_regs: HashMap<String, u8>,
+    revert_messages: Vec<String>,
     next_storage_addr: u32,
     next_reg: u8,
-    label_counter: usize,
+    /// First register after the current function's locals; temporaries
+    /// are allocated from here and released after every statement
+    locals_end: u8,
 }
 
 impl CodeGenerator {
@@ -24,105 +364,191 @@ impl CodeGenerator {
         Self {
             instructions: Vec::new(),
             storage_addrs: HashMap::new(),
+            contract_entries: HashMap::new(),
+            call_fixups: Vec::new(),
+            selectors: HashMap::new(),
+            event_topics: HashMap::new(),
+            frame_start: memory::CALL_FRAMES_START,
             local_regs: HashMap::new(),
-            next_storage_addr: 0x200, // Storage starts at 0x200
-            next_reg: 10, // Registers 0-9 reserved for special purposes
-            label_counter: 0,
+            revert_messages: Vec::new(),
+            next_storage_addr: memory::STORAGE_START,
+            next_reg: FIRST_GENERAL_REG,
+            locals_end: FIRST_GENERAL_REG,
         }
     }
     
-    fn generate_contract(&mut self, contract: &Contract) -> Result<Vec<Instruction>> {
-        // Allocate storage addresses
+    fn generate_contract(&mut self, index: usize, contract: &Contract) -> Result<()> {
+        self.frame_start = u32::try_from(index)
+            .ok()
+            .and_then(|i| i.checked_mul(memory::CALL_FRAME_SIZE))
+            .and_then(|offset| memory::CALL_FRAMES_START.checked_add(offset))
+            .filter(|&start| start as usize + memory::CALL_FRAME_SIZE as usize <= bitcell_zkvm::MIN_MEMORY_LIMIT)
+            .ok_or_else(|| CompilerError::codegen("Too many contracts in one program".to_string()))?;
+        let entry = self.current_address();
+        self.contract_entries.insert(contract.name.clone(), entry);
+        
+        // Contracts share memory, so each gets storage after the previous one's
+        self.storage_addrs.clear();
         for decl in &contract.storage {
             self.storage_addrs.insert(decl.name.clone(), self.next_storage_addr);
             self.next_storage_addr += 8; // 8 bytes per storage slot
         }
+        self.event_topics = contract.events.iter()
+            .map(|event| (event.name.clone(), event_topic(&event.signature())))
+            .collect();
         
         // Generate function dispatcher
-        self.generate_dispatcher(&contract.functions)?;
+        let entry_jumps = self.generate_dispatcher(contract)?;
         
-        // Generate each function
-        for func in &contract.functions {
+        // Generate each function and point its dispatcher jump at it
+        for (func, jump) in contract.functions.iter().zip(entry_jumps) {
+            let entry = self.current_address();
+            self.patch_jump(jump, entry);
             self.generate_function(func)?;
         }
         
-        // Add halt instruction
-        self.emit(OpCode::Halt, 0, 0, 0);
+        Ok(())
+    }
+    
+    /// Resolve inter-contract calls and return the finished program
+    fn finish(mut self) -> Result<Program> {
+        for (index, contract) in std::mem::take(&mut self.call_fixups) {
+            let entry = *self.contract_entries.get(&contract).ok_or_else(|| {
+                CompilerError::codegen(format!("Undefined contract: {}", contract))
+            })?;
+            self.patch_jump(index, entry);
+        }
         
-        Ok(self.instructions.clone())
+        Ok(Program {
+            instructions: self.instructions,
+            revert_messages: self.revert_messages,
+        })
     }
     
-    fn generate_dispatcher(&mut self, functions: &[Function]) -> Result<()> {
-        // Load function selector from memory address 0x10 (msg.data[0])
-        self.emit(OpCode::Load, 1, 0, 0x10);
+    /// Emit the selector dispatch and return the index of each function's entry jump
+    fn generate_dispatcher(&mut self, contract: &Contract) -> Result<Vec<usize>> {
+        // Load function selector from memory (msg.data[0])
+        self.emit(OpCode::Load, 1, ZERO_REG, memory::FUNCTION_SELECTOR);
         
         // For each function, compare selector and jump to function
-        for (i, func) in functions.iter().enumerate() {
-            let func_id = self.hash_function_name(&func.name);
-            let func_addr = 100 + (i * 200) as u32; // Each function gets 200 instruction slots
-            
+        let mut entry_jumps = Vec::with_capacity(contract.functions.len());
+        for func in &contract.functions {
             // Load function ID into r2
-            self.emit_load_immediate(2, func_id);
+            let selector = self.selector(&contract.name, &func.name)?;
+            self.emit_load_immediate(2, selector as u64);
             
             // Compare r1 with r2, store result in r3
             self.emit(OpCode::Eq, 3, 1, 2);
             
             // If NOT equal (r3 == 0), skip to next check
             // If equal (r3 != 0), jump to function
-            let skip_addr = (self.instructions.len() + 2) as u32;
-            self.emit(OpCode::Jz, 0, 3, skip_addr);
-            self.emit(OpCode::Jmp, 0, 0, func_addr);
+            let skip = self.emit_jump(OpCode::Jz, 3);
+            entry_jumps.push(self.emit_jump(OpCode::Jmp, 0));
+            let next = self.current_address();
+            self.patch_jump(skip, next);
         }
         
         // If no function matched, revert
-        self.emit(OpCode::Halt, 0, 0, 0);
+        let index = self.revert_message_index(UNKNOWN_SELECTOR_MESSAGE);
+        self.emit(OpCode::Revert, 0, 0, index);
         
-        Ok(())
+        Ok(entry_jumps)
+    }
+    
+    fn selector(&self, contract: &str, function: &str) -> Result<u32> {
+        self.selectors.get(&(contract.to_string(), function.to_string())).copied().ok_or_else(|| {
+            CompilerError::codegen(format!("Undefined function: {}.{}", contract, function))
+        })
     }
     
     fn generate_function(&mut self, func: &Function) -> Result<()> {
         self.local_regs.clear();
-        self.next_reg = 10;
+        self.next_reg = FIRST_GENERAL_REG;
         
         // Allocate registers for parameters
         for (i, param) in func.params.iter().enumerate() {
-            let reg = self.alloc_register();
+            let reg = self.alloc_register()?;
             self.local_regs.insert(param.name.clone(), reg);
             
-            // Load parameter from memory (parameters start at 0x20)
-            let param_addr = 0x20 + (i * 8) as u32;
-            self.emit(OpCode::Load, reg, 0, param_addr);
+            // Load parameter from memory
+            let param_addr = memory::PARAMS_START + (i * 8) as u32;
+            self.emit(OpCode::Load, reg, ZERO_REG, param_addr);
         }
         
+        // Give every local its own register up front, so temporaries of a
+        // loop condition that is re-evaluated each iteration never alias a
+        // local declared inside the loop body
+        self.allocate_locals(&func.body)?;
+        self.locals_end = self.next_reg;
+        
         // Generate function body
         for stmt in &func.body {
             self.generate_statement(stmt)?;
         }
         
+        // Falling off the end returns to the calling contract, or stops
+        // execution at the top level
+        self.emit(OpCode::Ret, 0, 0, 0);
+        
+        Ok(())
+    }
+    
+    fn allocate_locals(&mut self, stmts: &[Statement]) -> Result<()> {
+        for stmt in stmts {
+            match stmt {
+                Statement::Let { name, .. } if !self.local_regs.contains_key(name) => {
+                    let reg = self.alloc_register()?;
+                    self.local_regs.insert(name.clone(), reg);
+                }
+                Statement::If { then_block, else_block, .. } => {
+                    self.allocate_locals(then_block)?;
+                    if let Some(else_stmts) = else_block {
+                        self.allocate_locals(else_stmts)?;
+                    }
+                }
+                Statement::While { body, .. } => {
+                    self.allocate_locals(body)?;
+                }
+                Statement::For { init, body, .. } => {
+                    self.allocate_locals(std::slice::from_ref(init))?;
+                    self.allocate_locals(body)?;
+                }
+                _ => {}
+            }
+        }
         Ok(())
     }
     
     fn generate_statement(&mut self, stmt: &Statement) -> Result<()> {
+        self.lower_statement(stmt).map_err(|e| e.at(stmt.span()))?;
+        
+        // Temporaries never outlive the statement that created them
+        self.next_reg = self.locals_end;
+        Ok(())
+    }
+    
+    fn lower_statement(&mut self, stmt: &Statement) -> Result<()> {
         match stmt {
-            Statement::Let { name, value } => {
-                let reg = self.alloc_register();
+            Statement::Let { name, value, .. } => {
+                let reg = self.local_regs.get(name).copied().ok_or_else(|| {
+                    CompilerError::codegen(format!("No register allocated for: {}", name))
+                })?;
                 self.generate_expression(value, reg)?;
-                self.local_regs.insert(name.clone(), reg);
                 Ok(())
             }
-            Statement::Assign { target, value } => {
+            Statement::Assign { target, value, .. } => {
                 match target {
-                    Expression::Identifier(name) => {
+                    Expression::Identifier { name, .. } => {
                         if let Some(&storage_addr) = self.storage_addrs.get(name) {
                             // Store to storage
-                            let value_reg = self.alloc_temp_register();
+                            let value_reg = self.alloc_temp_register()?;
                             self.generate_expression(value, value_reg)?;
-                            self.emit(OpCode::Store, 0, value_reg, storage_addr);
+                            self.emit(OpCode::Store, ZERO_REG, value_reg, storage_addr);
                         } else if let Some(&reg) = self.local_regs.get(name) {
                             // Store to local register
                             self.generate_expression(value, reg)?;
                         } else {
-                            return Err(CompilerError::CodeGenError(format!(
+                            return Err(CompilerError::codegen(format!(
                                 "Undefined variable: {}",
                                 name
                             )));
@@ -131,31 +557,27 @@ impl CodeGenerator {
                     Expression::Index { expr, index } => {
                         // For mapping[key] = value
                         // This is simplified - real implementation needs hash-based storage
-                        let key_reg = self.alloc_temp_register();
+                        let key_reg = self.alloc_temp_register()?;
                         self.generate_expression(index, key_reg)?;
                         
-                        let value_reg = self.alloc_temp_register();
+                        let value_reg = self.alloc_temp_register()?;
                         self.generate_expression(value, value_reg)?;
                         
                         // Compute storage address: base + hash(key)
-                        if let Expression::Identifier(name) = &**expr {
+                        if let Expression::Identifier { name, .. } = &**expr {
                             if let Some(&base_addr) = self.storage_addrs.get(name) {
                                 // Simple address computation: base + key (should be hash in real impl)
-                                let addr_reg = self.alloc_temp_register();
+                                let addr_reg = self.alloc_temp_register()?;
                                 self.emit_load_immediate(addr_reg, base_addr as u64);
                                 self.emit(OpCode::Add, addr_reg, addr_reg, key_reg as u32);
                                 
-                                // Store value at computed address (using addr_reg)
-                                // Note: ZKVM Store format is: Store rs2, rs1, offset
-                                // where mem[rs1 + offset] = rs2
-                                // Here we want mem[addr_reg] = value_reg
-                                self.emit(OpCode::Store, 0, value_reg, 0);
-                                // TODO: This needs proper addressing - currently simplified
+                                // mem[addr_reg] = value_reg
+                                self.emit(OpCode::Store, addr_reg, value_reg, 0);
                             }
                         }
                     }
                     _ => {
-                        return Err(CompilerError::CodeGenError(
+                        return Err(CompilerError::codegen(
                             "Invalid assignment target".to_string(),
                         ));
                     }
@@ -166,67 +588,128 @@ impl CodeGenerator {
                 condition,
                 then_block,
                 else_block,
+                ..
             } => {
-                let cond_reg = self.alloc_temp_register();
+                let cond_reg = self.alloc_temp_register()?;
                 self.generate_expression(condition, cond_reg)?;
                 
-                let else_label = self.new_label();
-                let end_label = self.new_label();
-                
                 // Jump to else if condition is false (0)
-                self.emit(OpCode::Jz, 0, cond_reg, else_label as u32);
+                let to_else = self.emit_jump(OpCode::Jz, cond_reg);
                 
                 // Then block
                 for stmt in then_block {
                     self.generate_statement(stmt)?;
                 }
-                self.emit(OpCode::Jmp, 0, 0, end_label as u32);
+                let to_end = self.emit_jump(OpCode::Jmp, 0);
                 
                 // Else block (or empty)
-                let _else_addr = self.instructions.len();
+                let else_addr = self.current_address();
+                self.patch_jump(to_else, else_addr);
                 if let Some(else_stmts) = else_block {
                     for stmt in else_stmts {
                         self.generate_statement(stmt)?;
                     }
                 }
                 
-                let _end_addr = self.instructions.len();
-                
-                // Patch jump addresses
-                // (In real implementation, we'd do a two-pass assembly or use labels)
+                let end_addr = self.current_address();
+                self.patch_jump(to_end, end_addr);
                 
                 Ok(())
             }
-            Statement::Return { value } => {
+            Statement::While { condition, body, .. } => {
+                self.generate_loop(condition, None, body)
+            }
+            Statement::For {
+                init,
+                condition,
+                update,
+                body,
+                ..
+            } => {
+                self.generate_statement(init)?;
+                self.generate_loop(condition, Some(update), body)
+            }
+            Statement::Return { value, .. } => {
                 if let Some(expr) = value {
-                    let result_reg = 0; // Return value in r0
-                    self.generate_expression(expr, result_reg)?;
+                    self.generate_expression(expr, RETURN_REG)?;
                 }
                 self.emit(OpCode::Ret, 0, 0, 0);
                 Ok(())
             }
-            Statement::Require { condition, message: _ } => {
-                let cond_reg = self.alloc_temp_register();
+            Statement::Require { condition, message, .. } => {
+                let cond_reg = self.alloc_temp_register()?;
                 self.generate_expression(condition, cond_reg)?;
                 
-                // If condition is 0 (false), jump to halt
-                let halt_addr = (self.instructions.len() + 1) as u32;
-                self.emit(OpCode::Jz, 0, cond_reg, halt_addr);
+                // Skip the revert when the condition holds (is non-zero)
+                self.emit(OpCode::Eq, cond_reg, cond_reg, ZERO_REG as u32);
+                let skip = self.emit_jump(OpCode::Jz, cond_reg);
+                
+                // Revert with the message from the data section
+                let index = self.revert_message_index(message);
+                self.emit(OpCode::Revert, 0, 0, index);
                 
-                // If condition is true, skip halt and continue
-                // Halt (revert) - this is the target of the Jz above
-                self.emit(OpCode::Halt, 0, 0, 0);
+                let continue_addr = self.current_address();
+                self.patch_jump(skip, continue_addr);
+                Ok(())
+            }
+            Statement::Emit { event, args, .. } => {
+                let count = u8::try_from(args.len()).map_err(|_| {
+                    CompilerError::codegen(format!("Too many arguments for event {}", event))
+                })?;
+                
+                // Write the arguments to scratch memory, then log them in one go
+                for (i, arg) in args.iter().enumerate() {
+                    let arg_reg = self.alloc_temp_register()?;
+                    self.generate_expression(arg, arg_reg)?;
+                    self.emit(OpCode::Store, ZERO_REG, arg_reg, memory::STACK_START + i as u32);
+                    self.next_reg = arg_reg;
+                }
                 
+                let topic = *self.event_topics.get(event).ok_or_else(|| {
+                    CompilerError::codegen(format!("Undefined event: {}", event))
+                })?;
+                let base_reg = self.alloc_temp_register()?;
+                self.emit_load_immediate(base_reg, memory::STACK_START as u64);
+                self.emit(OpCode::Log, count, base_reg, topic);
                 Ok(())
             }
-            Statement::Expression(expr) => {
-                let temp_reg = self.alloc_temp_register();
+            Statement::Expression { expr, .. } => {
+                let temp_reg = self.alloc_temp_register()?;
                 self.generate_expression(expr, temp_reg)?;
                 Ok(())
             }
         }
     }
     
+    /// Lower a loop to `top: cond; Jz end; body; update; Jmp top; end:`
+    ///
+    /// Loops are not bounded at compile time beyond rejecting constant-true
+    /// conditions; every iteration is metered by the ZKVM gas limit.
+    fn generate_loop(
+        &mut self,
+        condition: &Expression,
+        update: Option<&Statement>,
+        body: &[Statement],
+    ) -> Result<()> {
+        let top = self.current_address();
+        
+        let cond_reg = self.alloc_temp_register()?;
+        self.generate_expression(condition, cond_reg)?;
+        let exit = self.emit_jump(OpCode::Jz, cond_reg);
+        
+        for stmt in body {
+            self.generate_statement(stmt)?;
+        }
+        if let Some(update) = update {
+            self.generate_statement(update)?;
+        }
+        self.emit(OpCode::Jmp, 0, 0, top);
+        
+        let end = self.current_address();
+        self.patch_jump(exit, end);
+        Ok(())
+    }
+    
     fn generate_expression(&mut self, expr: &Expression, dest_reg: u8) -> Result<()> {
         match expr {
             Expression::Literal(lit) => {
@@ -244,28 +727,28 @@ impl CodeGenerator {
                 }
                 Ok(())
             }
-            Expression::Identifier(name) => {
+            Expression::Identifier { name, span } => {
                 if let Some(&storage_addr) = self.storage_addrs.get(name) {
                     // Load from storage
-                    self.emit(OpCode::Load, dest_reg, 0, storage_addr);
+                    self.emit(OpCode::Load, dest_reg, ZERO_REG, storage_addr);
                 } else if let Some(&reg) = self.local_regs.get(name) {
                     // Copy from local register
                     if reg != dest_reg {
-                        self.emit(OpCode::Add, dest_reg, reg, 0); // Copy via add with 0
+                        self.emit(OpCode::Add, dest_reg, reg, ZERO_REG as u32); // Copy via add with 0
                     }
                 } else {
-                    return Err(CompilerError::CodeGenError(format!(
+                    return Err(CompilerError::codegen(format!(
                         "Undefined variable: {}",
                         name
-                    )));
+                    )).at(*span));
                 }
                 Ok(())
             }
             Expression::Binary { left, op, right } => {
-                let left_reg = self.alloc_temp_register();
+                let left_reg = self.alloc_temp_register()?;
                 self.generate_expression(left, left_reg)?;
                 
-                let right_reg = self.alloc_temp_register();
+                let right_reg = self.alloc_temp_register()?;
                 self.generate_expression(right, right_reg)?;
                 
                 let opcode = match op {
@@ -282,9 +765,9 @@ impl CodeGenerator {
                     BinaryOp::And => OpCode::And,
                     BinaryOp::Or => OpCode::Or,
                     BinaryOp::Ne => {
-                        // Ne is implemented as !(==)
+                        // Ne is implemented as (a == b) == 0
                         self.emit(OpCode::Eq, dest_reg, left_reg, right_reg as u32);
-                        self.emit(OpCode::Not, dest_reg, dest_reg, 0);
+                        self.emit(OpCode::Eq, dest_reg, dest_reg, ZERO_REG as u32);
                         return Ok(());
                     }
                 };
@@ -296,13 +779,12 @@ impl CodeGenerator {
                 self.generate_expression(expr, dest_reg)?;
                 match op {
                     UnaryOp::Not => {
-                        self.emit(OpCode::Not, dest_reg, dest_reg, 0);
+                        // Logical not: x == 0 (bitwise Not would turn 1 into a non-zero value)
+                        self.emit(OpCode::Eq, dest_reg, dest_reg, ZERO_REG as u32);
                     }
                     UnaryOp::Neg => {
                         // Negation: 0 - x
-                        let zero_reg = self.alloc_temp_register();
-                        self.emit_load_immediate(zero_reg, 0);
-                        self.emit(OpCode::Sub, dest_reg, zero_reg, dest_reg as u32);
+                        self.emit(OpCode::Sub, dest_reg, ZERO_REG, dest_reg as u32);
                     }
                 }
                 Ok(())
@@ -312,15 +794,18 @@ impl CodeGenerator {
                 self.emit_load_immediate(dest_reg, 0);
                 Ok(())
             }
+            Expression::ExternalCall { contract, function, args } => {
+                self.generate_external_call(contract, function, args, dest_reg)
+            }
             Expression::Index { expr, index } => {
                 // Load from mapping
-                let key_reg = self.alloc_temp_register();
+                let key_reg = self.alloc_temp_register()?;
                 self.generate_expression(index, key_reg)?;
                 
-                if let Expression::Identifier(name) = &**expr {
+                if let Expression::Identifier { name, .. } = &**expr {
                     if let Some(&base_addr) = self.storage_addrs.get(name) {
                         // Compute address: base + hash(key)
-                        let addr_reg = self.alloc_temp_register();
+                        let addr_reg = self.alloc_temp_register()?;
                         self.emit_load_immediate(addr_reg, base_addr as u64);
                         self.emit(OpCode::Add, addr_reg, addr_reg, key_reg as u32);
                         
@@ -332,19 +817,19 @@ impl CodeGenerator {
             }
             Expression::MemberAccess { expr, member } => {
                 // Handle msg.sender, msg.value, block.number, etc.
-                if let Expression::Identifier(obj) = &**expr {
+                if let Expression::Identifier { name: obj, .. } = &**expr {
                     match (obj.as_str(), member.as_str()) {
                         ("msg", "sender") => {
-                            self.emit(OpCode::Load, dest_reg, 0, 0x14);  // Updated address
+                            self.emit(OpCode::Load, dest_reg, ZERO_REG, builtins::MSG_SENDER_ADDR);
                         }
                         ("msg", "value") => {
-                            self.emit(OpCode::Load, dest_reg, 0, 0x18);
+                            self.emit(OpCode::Load, dest_reg, ZERO_REG, builtins::MSG_VALUE_ADDR);
                         }
                         ("block", "number") => {
-                            self.emit(OpCode::Load, dest_reg, 0, 0x20);
+                            self.emit(OpCode::Load, dest_reg, ZERO_REG, builtins::BLOCK_NUMBER_ADDR);
                         }
                         ("block", "timestamp") => {
-                            self.emit(OpCode::Load, dest_reg, 0, 0x28);
+                            self.emit(OpCode::Load, dest_reg, ZERO_REG, builtins::BLOCK_TIMESTAMP_ADDR);
                         }
                         _ => {
                             // Unknown member access, load 0
@@ -360,46 +845,110 @@ impl CodeGenerator {
         }
     }
     
+    /// Lower `call contract.function(args)` into `dest_reg`
+    ///
+    /// The callee runs with the same registers and memory, so the caller
+    /// spills its locals and temporaries to its contract's frame around the
+    /// `Call`. Arguments and the selector are passed through the usual
+    /// `PARAMS_START` and `FUNCTION_SELECTOR` slots; the result comes back in
+    /// `RETURN_REG`.
+    fn generate_external_call(
+        &mut self,
+        contract: &str,
+        function: &str,
+        args: &[Expression],
+        dest_reg: u8,
+    ) -> Result<()> {
+        let mut arg_regs = Vec::with_capacity(args.len());
+        for arg in args {
+            let reg = self.alloc_temp_register()?;
+            self.generate_expression(arg, reg)?;
+            arg_regs.push(reg);
+        }
+        
+        let live: Vec<u8> = (FIRST_GENERAL_REG..self.next_reg).filter(|&r| r != dest_reg).collect();
+        for &reg in &live {
+            self.emit(OpCode::Store, ZERO_REG, reg, self.frame_start + reg as u32);
+        }
+        for (i, &reg) in arg_regs.iter().enumerate() {
+            self.emit(OpCode::Store, ZERO_REG, reg, memory::PARAMS_START + (i * 8) as u32);
+        }
+        let selector = self.selector(contract, function)?;
+        let selector_reg = self.alloc_temp_register()?;
+        self.emit_load_immediate(selector_reg, selector as u64);
+        self.emit(OpCode::Store, ZERO_REG, selector_reg, memory::FUNCTION_SELECTOR);
+        
+        let call = self.emit_jump(OpCode::Call, 0);
+        self.call_fixups.push((call, contract.to_string()));
+        
+        for &reg in &live {
+            self.emit(OpCode::Load, reg, ZERO_REG, self.frame_start + reg as u32);
+        }
+        if dest_reg != RETURN_REG {
+            self.emit(OpCode::Add, dest_reg, RETURN_REG, ZERO_REG as u32);
+        }
+        Ok(())
+    }
+    
+    /// Index of `message` in the data section, adding it if new
+    fn revert_message_index(&mut self, message: &str) -> u32 {
+        let index = match self.revert_messages.iter().position(|m| m == message) {
+            Some(index) => index,
+            None => {
+                self.revert_messages.push(message.to_string());
+                self.revert_messages.len() - 1
+            }
+        };
+        index as u32
+    }
+    
     fn emit(&mut self, opcode: OpCode, rd: u8, rs1: u8, rs2_imm: u32) {
         self.instructions.push(Instruction::new(opcode, rd, rs1, rs2_imm));
     }
     
-    fn emit_load_immediate(&mut self, reg: u8, value: u64) {
-        // Load immediate by adding to register 0 (assuming r0 is always zero)
-        // Note: This only works for values that fit in 32 bits
-        // For larger values, would need multiple instructions
-        let value_u32 = (value & 0xFFFFFFFF) as u32;
-        
-        // Add immediate to r0 (should be zero) to load the value
-        self.emit(OpCode::Add, reg, 0, value_u32);
+    /// Emit a jump with a placeholder target and return its index for `patch_jump`
+    fn emit_jump(&mut self, opcode: OpCode, cond_reg: u8) -> usize {
+        let index = self.instructions.len();
+        self.emit(opcode, 0, cond_reg, 0);
+        index
     }
     
-    fn alloc_register(&mut self) -> u8 {
-        let reg = self.next_reg;
-        self.next_reg += 1;
-        if self.next_reg >= 32 {
-            self.next_reg = 10; // Wrap around (in real impl, we'd do register allocation)
-        }
-        reg
+    fn patch_jump(&mut self, index: usize, target: u32) {
+        self.instructions[index].rs2_imm = target;
     }
     
-    fn alloc_temp_register(&mut self) -> u8 {
-        self.alloc_register()
+    fn current_address(&self) -> u32 {
+        self.instructions.len() as u32
+    }
+    
+    fn emit_load_immediate(&mut self, reg: u8, value: u64) {
+        if let Ok(value_u32) = u32::try_from(value) {
+            self.emit(OpCode::LoadImm, reg, 0, value_u32);
+            return;
+        }
+        
+        // reg = hi * 2^16 * 2^16 + lo
+        self.emit(OpCode::LoadImm, reg, 0, (value >> 32) as u32);
+        self.emit(OpCode::LoadImm, SCRATCH_REG, 0, 1 << 16);
+        self.emit(OpCode::Mul, reg, reg, SCRATCH_REG as u32);
+        self.emit(OpCode::Mul, reg, reg, SCRATCH_REG as u32);
+        self.emit(OpCode::LoadImm, SCRATCH_REG, 0, value as u32);
+        self.emit(OpCode::Add, reg, reg, SCRATCH_REG as u32);
     }
     
-    fn hash_function_name(&self, name: &str) -> u64 {
-        // Simple hash for function selector
-        let mut hash = 0u64;
-        for b in name.bytes() {
-            hash = hash.wrapping_mul(31).wrapping_add(b as u64);
+    fn alloc_register(&mut self) -> Result<u8> {
+        if self.next_reg >= NUM_REGISTERS {
+            return Err(CompilerError::codegen(
+                "Function needs more registers than the ZKVM provides".to_string(),
+            ));
         }
-        hash
+        let reg = self.next_reg;
+        self.next_reg += 1;
+        Ok(reg)
     }
     
-    fn new_label(&mut self) -> usize {
-        let label = self.label_counter;
-        self.label_counter += 1;
-        label
+    fn alloc_temp_register(&mut self) -> Result<u8> {
+        self.alloc_register()
     }
 }
 
@@ -409,6 +958,31 @@ mod tests {
     use crate::lexer::tokenize;
     use crate::parser::parse;
     use crate::semantic::analyze;
+    use bitcell_zkvm::{Interpreter, InterpreterError};
+    
+    fn compile_contract(source: &str) -> Program {
+        let tokens = tokenize(source).unwrap();
+        let contracts = parse(tokens).unwrap();
+        analyze(&contracts).unwrap();
+        generate_program(&contracts).unwrap()
+    }
+    
+    /// Call the function with `signature` on `args`, returning the interpreter after execution
+    fn call(
+        program: &Program,
+        signature: &str,
+        args: &[u64],
+        gas_limit: u64,
+    ) -> (Interpreter, std::result::Result<(), InterpreterError>) {
+        let mut interp = Interpreter::new(gas_limit)
+            .with_revert_messages(program.revert_messages.clone());
+        interp.write_memory(memory::FUNCTION_SELECTOR, function_selector(signature) as u64).unwrap();
+        for (i, arg) in args.iter().enumerate() {
+            interp.write_memory(memory::PARAMS_START + (i * 8) as u32, *arg).unwrap();
+        }
+        let result = interp.execute(&program.instructions);
+        (interp, result)
+    }
 
     #[test]
     fn test_codegen_simple() {
@@ -432,4 +1006,337 @@ mod tests {
         
         assert!(!instructions.is_empty());
     }
+
+    const LOOP_CONTRACT: &str = r#"
+        contract Loops {
+            storage {
+                total: uint;
+            }
+            
+            function sum_to(n: uint) -> uint {
+                let sum = 0;
+                for (let i = 1; i <= n; i = i + 1) {
+                    sum = sum + i;
+                }
+                total = sum;
+                return sum;
+            }
+            
+            function halve_until_odd(x: uint) -> uint {
+                while (x % 2 == 0 && x != 0) {
+                    let half = x / 2;
+                    x = half;
+                }
+                return x;
+            }
+            
+            function count_down(n: uint) -> uint {
+                let steps = 0;
+                while (n >= 0) {
+                    n = n - 1;
+                    steps = steps + 1;
+                }
+                return steps;
+            }
+        }
+    "#;
+
+    #[test]
+    fn test_for_loop_lowering() {
+        let program = compile_contract(LOOP_CONTRACT).instructions;
+        
+        // Every jump must land inside the program and the loop needs a back edge
+        for (addr, inst) in program.iter().enumerate() {
+            if matches!(inst.opcode, OpCode::Jmp | OpCode::Jz) {
+                assert!((inst.imm() as usize) < program.len(), "jump at {} out of range", addr);
+            }
+        }
+        assert!(program.iter().enumerate().any(|(addr, inst)| {
+            inst.opcode == OpCode::Jmp && (inst.imm() as usize) < addr
+        }));
+    }
+
+    #[test]
+    fn test_for_loop_executes() {
+        let program = compile_contract(LOOP_CONTRACT);
+        
+        let (interp, result) = call(&program, "sum_to(uint)", &[10], 10_000);
+        result.unwrap();
+        assert_eq!(interp.get_register(RETURN_REG), 55);
+        assert_eq!(interp.read_memory(memory::STORAGE_START).unwrap(), 55);
+        
+        let (interp, result) = call(&program, "sum_to(uint)", &[0], 10_000);
+        result.unwrap();
+        assert_eq!(interp.get_register(RETURN_REG), 0);
+    }
+
+    #[test]
+    fn test_while_loop_executes() {
+        let program = compile_contract(LOOP_CONTRACT);
+        
+        let (interp, result) = call(&program, "halve_until_odd(uint)", &[48], 10_000);
+        result.unwrap();
+        assert_eq!(interp.get_register(RETURN_REG), 3);
+    }
+
+    #[test]
+    fn test_unbounded_loop_runs_out_of_gas() {
+        let program = compile_contract(LOOP_CONTRACT);
+        
+        // `n >= 0` always holds for uint, so only the gas limit stops the loop
+        let (_, result) = call(&program, "count_down(uint)", &[5], 10_000);
+        assert!(matches!(result, Err(InterpreterError::OutOfGas)));
+    }
+
+    #[test]
+    fn test_dispatcher_jumps_to_selected_function() {
+        let source = r#"
+            contract Test {
+                function first() -> uint {
+                    return 1;
+                }
+                
+                function second() -> uint {
+                    return 2;
+                }
+            }
+        "#;
+        let program = compile_contract(source);
+        
+        let (interp, result) = call(&program, "second()", &[], 1_000);
+        result.unwrap();
+        assert_eq!(interp.get_register(RETURN_REG), 2);
+        
+        let (interp, result) = call(&program, "first()", &[], 1_000);
+        result.unwrap();
+        assert_eq!(interp.get_register(RETURN_REG), 1);
+    }
+
+    #[test]
+    fn test_if_else_executes() {
+        let source = r#"
+            contract Test {
+                function max(a: uint, b: uint) -> uint {
+                    if (a > b) {
+                        return a;
+                    } else {
+                        return b;
+                    }
+                }
+            }
+        "#;
+        let program = compile_contract(source);
+        
+        let (interp, result) = call(&program, "max(uint,uint)", &[3, 9], 1_000);
+        result.unwrap();
+        assert_eq!(interp.get_register(RETURN_REG), 9);
+        
+        let (interp, result) = call(&program, "max(uint,uint)", &[12, 9], 1_000);
+        result.unwrap();
+        assert_eq!(interp.get_register(RETURN_REG), 12);
+    }
+
+    #[test]
+    fn test_require_reverts_with_message() {
+        let program = compile_contract(crate::stdlib::patterns::COUNTER_CONTRACT);
+        assert_eq!(program.revert_messages, vec![
+            UNKNOWN_SELECTOR_MESSAGE.to_string(),
+            "Counter underflow".to_string(),
+        ]);
+        
+        // count == 0, so decrement must revert with the require message
+        let (_, result) = call(&program, "decrement()", &[], 1_000);
+        match result {
+            Err(InterpreterError::Reverted(msg)) => assert_eq!(msg, "Counter underflow"),
+            other => panic!("expected revert, got {:?}", other),
+        }
+        
+        // Once the counter is non-zero the require passes
+        let (mut interp, result) = call(&program, "increment()", &[], 1_000);
+        result.unwrap();
+        assert_eq!(interp.get_register(RETURN_REG), 1);
+        interp.write_memory(memory::FUNCTION_SELECTOR, function_selector("decrement()") as u64).unwrap();
+        interp.execute(&program.instructions).unwrap();
+        assert_eq!(interp.get_register(RETURN_REG), 0);
+    }
+
+    #[test]
+    fn test_emit_transfer_log() {
+        let source = r#"
+            contract Token {
+                storage {
+                    balances: mapping(address => uint);
+                }
+                
+                event Transfer(from: address, to: address, amount: uint);
+                
+                function transfer(to: address, amount: uint) -> bool {
+                    let sender = msg.sender;
+                    require(balances[sender] >= amount, "Insufficient balance");
+                    
+                    balances[sender] = balances[sender] - amount;
+                    balances[to] = balances[to] + amount;
+                    
+                    emit Transfer(sender, to, amount);
+                    return true;
+                }
+            }
+        "#;
+        let program = compile_contract(source);
+        
+        let mut interp = Interpreter::new(10_000)
+            .with_revert_messages(program.revert_messages.clone());
+        interp.write_memory(memory::FUNCTION_SELECTOR, function_selector("transfer(address,uint)") as u64).unwrap();
+        interp.write_memory(memory::PARAMS_START, 2).unwrap();
+        interp.write_memory(memory::PARAMS_START + 8, 30).unwrap();
+        interp.write_memory(builtins::MSG_SENDER_ADDR, 1).unwrap();
+        interp.write_memory(memory::STORAGE_START + 1, 100).unwrap();
+        interp.execute(&program.instructions).unwrap();
+        
+        assert_eq!(interp.get_register(RETURN_REG), 1);
+        assert_eq!(interp.read_memory(memory::STORAGE_START + 1).unwrap(), 70);
+        assert_eq!(interp.read_memory(memory::STORAGE_START + 2).unwrap(), 30);
+        
+        let logs = &interp.trace().logs;
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].topic, event_topic("Transfer(address,address,uint)") as u64);
+        assert_ne!(event_topic("Transfer(address,address,uint)"), event_topic("Transfer(address,uint)"));
+        assert_ne!(event_topic("transfer(address,uint)"), function_selector("transfer(address,uint)"));
+        assert_eq!(logs[0].data, vec![1, 2, 30]);
+    }
+
+    #[test]
+    fn test_optimize_shrinks_and_preserves_behavior() {
+        let source = r#"
+            contract Test {
+                storage {
+                    count: uint;
+                }
+                
+                function compute(n: uint) -> uint {
+                    let x = 2 * 3 + 4;
+                    count = count + 1 - 1;
+                    if (n > x - 5) {
+                        count = count + x * 10;
+                    }
+                    return count;
+                    count = 99;
+                }
+            }
+        "#;
+        let program = compile_contract(source);
+        let mut optimized = program.clone();
+        optimize(&mut optimized.instructions);
+        
+        assert!(optimized.instructions.len() < program.instructions.len());
+        // x = 2 * 3 + 4 is constant, so the multiplications in the body fold away
+        let muls = |p: &Program| p.instructions.iter().filter(|i| i.opcode == OpCode::Mul).count();
+        assert!(muls(&optimized) + 2 <= muls(&program));
+        
+        for n in [0, 5, 6, 100] {
+            let (before, result) = call(&program, "compute(uint)", &[n], 10_000);
+            result.unwrap();
+            let (after, result) = call(&optimized, "compute(uint)", &[n], 10_000);
+            result.unwrap();
+            
+            assert_eq!(before.get_register(RETURN_REG), after.get_register(RETURN_REG));
+            assert_eq!(
+                before.read_memory(memory::STORAGE_START).unwrap(),
+                after.read_memory(memory::STORAGE_START).unwrap()
+            );
+            assert!(after.gas_used() < before.gas_used());
+        }
+    }
+
+    #[test]
+    fn test_optimize_preserves_loops() {
+        let program = compile_contract(LOOP_CONTRACT);
+        let mut optimized = program.clone();
+        optimize(&mut optimized.instructions);
+        
+        for n in [0, 1, 10] {
+            let (interp, result) = call(&optimized, "sum_to(uint)", &[n], 10_000);
+            result.unwrap();
+            assert_eq!(interp.get_register(RETURN_REG), n * (n + 1) / 2);
+        }
+        let (interp, result) = call(&optimized, "halve_until_odd(uint)", &[48], 10_000);
+        result.unwrap();
+        assert_eq!(interp.get_register(RETURN_REG), 3);
+    }
+
+    const CALLER_CONTRACTS: &str = r#"
+        contract A {
+            function forward(x: uint) -> uint {
+                let keep = x + 1;
+                call B.set(x * 10);
+                let got = call B.get();
+                return got + keep;
+            }
+        }
+        
+        contract B {
+            storage {
+                value: uint;
+            }
+            
+            function set(v: uint) -> bool {
+                value = v;
+                return true;
+            }
+            
+            function get() -> uint {
+                return value;
+            }
+        }
+    "#;
+
+    #[test]
+    fn test_inter_contract_call_executes() {
+        let program = compile_contract(CALLER_CONTRACTS);
+        assert_eq!(program.instructions.iter().filter(|i| i.opcode == OpCode::Call).count(), 2);
+        
+        let mut optimized = program.clone();
+        optimize(&mut optimized.instructions);
+        
+        for program in [&program, &optimized] {
+            // A's local `keep` survives B reusing the same registers
+            let (interp, result) = call(program, "forward(uint)", &[4], 10_000);
+            result.unwrap();
+            assert_eq!(interp.get_register(RETURN_REG), 45);
+            assert_eq!(interp.read_memory(memory::STORAGE_START).unwrap(), 40);
+        }
+    }
+
+    #[test]
+    fn test_token_dispatch_by_selector() {
+        let program = compile_contract(crate::stdlib::patterns::TOKEN_CONTRACT);
+        let transfer = function_selector("transfer(address,uint)");
+        let balance_of = function_selector("balance_of(address)");
+        assert_ne!(transfer, balance_of);
+        
+        // transfer(to = 2, amount = 30) from address 1 holding 100
+        let mut interp = Interpreter::new(10_000)
+            .with_revert_messages(program.revert_messages.clone());
+        interp.write_memory(memory::FUNCTION_SELECTOR, transfer as u64).unwrap();
+        interp.write_memory(memory::PARAMS_START, 2).unwrap();
+        interp.write_memory(memory::PARAMS_START + 8, 30).unwrap();
+        interp.write_memory(builtins::MSG_SENDER_ADDR, 1).unwrap();
+        interp.write_memory(memory::STORAGE_START + 1, 100).unwrap();
+        interp.execute(&program.instructions).unwrap();
+        assert_eq!(interp.get_register(RETURN_REG), 1);
+        assert_eq!(interp.read_memory(memory::STORAGE_START + 1).unwrap(), 70);
+        
+        // balance_of(2) against the same storage returns the credited amount
+        interp.write_memory(memory::FUNCTION_SELECTOR, balance_of as u64).unwrap();
+        interp.write_memory(memory::PARAMS_START, 2).unwrap();
+        interp.execute(&program.instructions).unwrap();
+        assert_eq!(interp.get_register(RETURN_REG), 30);
+        
+        // A selector matching no function reverts
+        interp.write_memory(memory::FUNCTION_SELECTOR, function_selector("mint(uint)") as u64).unwrap();
+        match interp.execute(&program.instructions) {
+            Err(InterpreterError::Reverted(msg)) => assert_eq!(msg, UNKNOWN_SELECTOR_MESSAGE),
+            other => panic!("expected revert, got {:?}", other),
+        }
+    }
 }
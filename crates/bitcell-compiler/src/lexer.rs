@@ -1,5 +1,6 @@
 //! Lexical analyzer for BCL
 
+use crate::ast::Span;
 use crate::{CompilerError, Result};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -11,9 +12,14 @@ pub enum Token {
     Let,
     If,
     Else,
+    While,
+    For,
     Return,
     Require,
     Mapping,
+    Event,
+    Emit,
+    Call,
     
     // Types
     Uint,
@@ -62,13 +68,23 @@ pub enum Token {
     Eof,
 }
 
-pub fn tokenize(source: &str) -> Result<Vec<Token>> {
+/// A token and where it starts in the source
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedToken {
+    pub token: Token,
+    pub span: Span,
+}
+
+pub fn tokenize(source: &str) -> Result<Vec<SpannedToken>> {
     let mut tokens = Vec::new();
+    let mut spans = Vec::new();
     let mut chars = source.chars().peekable();
     let mut line = 1;
     let mut col = 1;
     
     while let Some(&ch) = chars.peek() {
+        // Each iteration produces at most one token, starting here
+        let start = Span { line, col };
         match ch {
             // Whitespace
             ' ' | '\t' | '\r' => {
@@ -348,9 +364,14 @@ pub fn tokenize(source: &str) -> Result<Vec<Token>> {
                     "let" => Token::Let,
                     "if" => Token::If,
                     "else" => Token::Else,
+                    "while" => Token::While,
+                    "for" => Token::For,
                     "return" => Token::Return,
                     "require" => Token::Require,
                     "mapping" => Token::Mapping,
+                    "event" => Token::Event,
+                    "emit" => Token::Emit,
+                    "call" => Token::Call,
                     "uint" => Token::Uint,
                     "bool" => Token::Bool,
                     "address" => Token::Address,
@@ -369,27 +390,43 @@ pub fn tokenize(source: &str) -> Result<Vec<Token>> {
                 });
             }
         }
+        spans.resize(tokens.len(), start);
     }
     
     tokens.push(Token::Eof);
-    Ok(tokens)
+    spans.push(Span { line, col });
+    Ok(tokens.into_iter()
+        .zip(spans)
+        .map(|(token, span)| SpannedToken { token, span })
+        .collect())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    
+    fn kinds(source: &str) -> Vec<Token> {
+        tokenize(source).unwrap().into_iter().map(|t| t.token).collect()
+    }
 
     #[test]
     fn test_tokenize_keywords() {
-        let tokens = tokenize("contract function storage").unwrap();
+        let tokens = kinds("contract function storage");
         assert_eq!(tokens[0], Token::Contract);
         assert_eq!(tokens[1], Token::Function);
         assert_eq!(tokens[2], Token::Storage);
     }
 
+    #[test]
+    fn test_tokenize_loop_keywords() {
+        let tokens = kinds("while for");
+        assert_eq!(tokens[0], Token::While);
+        assert_eq!(tokens[1], Token::For);
+    }
+
     #[test]
     fn test_tokenize_operators() {
-        let tokens = tokenize("+ - * == != <= >= && ||").unwrap();
+        let tokens = kinds("+ - * == != <= >= && ||");
         assert_eq!(tokens[0], Token::Plus);
         assert_eq!(tokens[1], Token::Minus);
         assert_eq!(tokens[2], Token::Star);
@@ -403,7 +440,7 @@ mod tests {
 
     #[test]
     fn test_tokenize_literals() {
-        let tokens = tokenize(r#"42 true false "hello""#).unwrap();
+        let tokens = kinds(r#"42 true false "hello""#);
         assert_eq!(tokens[0], Token::Number(42));
         assert_eq!(tokens[1], Token::True);
         assert_eq!(tokens[2], Token::False);
@@ -412,9 +449,23 @@ mod tests {
 
     #[test]
     fn test_tokenize_string_escapes() {
-        let tokens = tokenize(r#""Line 1\nLine 2" "Quote: \"test\"" "Tab:\there""#).unwrap();
+        let tokens = kinds(r#""Line 1\nLine 2" "Quote: \"test\"" "Tab:\there""#);
         assert_eq!(tokens[0], Token::String("Line 1\nLine 2".to_string()));
         assert_eq!(tokens[1], Token::String("Quote: \"test\"".to_string()));
         assert_eq!(tokens[2], Token::String("Tab:\there".to_string()));
     }
+
+    #[test]
+    fn test_token_spans() {
+        let tokens = tokenize("contract A {\n    // note\n    x >= 10;\n}").unwrap();
+        let span = |i: usize| (tokens[i].span.line, tokens[i].span.col);
+        
+        assert_eq!(span(0), (1, 1));
+        assert_eq!(span(1), (1, 10));
+        assert_eq!(tokens[3].token, Token::Identifier("x".to_string()));
+        assert_eq!(span(3), (3, 5));
+        assert_eq!(span(4), (3, 7));
+        assert_eq!(span(5), (3, 10));
+        assert_eq!(span(7), (4, 1));
+    }
 }
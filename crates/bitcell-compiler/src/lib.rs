@@ -26,6 +26,9 @@
 //!     }
 //! }
 //! ```
+//!
+//! A source file may declare several contracts. The first one is the entry
+//! point; it can invoke the others with `call Contract.function(args)`.
 
 pub mod ast;
 pub mod codegen;
@@ -34,6 +37,9 @@ pub mod parser;
 pub mod semantic;
 pub mod stdlib;
 
+pub use ast::Span;
+pub use codegen::Program;
+
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -44,31 +50,87 @@ pub enum CompilerError {
         col: usize,
         message: String,
     },
-    #[error("Parser error: {0}")]
-    ParserError(String),
-    #[error("Semantic error: {0}")]
-    SemanticError(String),
-    #[error("Code generation error: {0}")]
-    CodeGenError(String),
+    #[error("Parser error{}: {message}", location(.span))]
+    ParserError {
+        span: Option<Span>,
+        message: String,
+    },
+    #[error("Semantic error{}: {message}", location(.span))]
+    SemanticError {
+        span: Option<Span>,
+        message: String,
+    },
+    #[error("Code generation error{}: {message}", location(.span))]
+    CodeGenError {
+        span: Option<Span>,
+        message: String,
+    },
+}
+
+impl CompilerError {
+    pub fn parser(message: impl Into<String>) -> Self {
+        Self::ParserError { span: None, message: message.into() }
+    }
+    
+    pub fn semantic(message: impl Into<String>) -> Self {
+        Self::SemanticError { span: None, message: message.into() }
+    }
+    
+    pub fn codegen(message: impl Into<String>) -> Self {
+        Self::CodeGenError { span: None, message: message.into() }
+    }
+    
+    /// Attach `span` unless the error already points somewhere more precise
+    pub fn at(mut self, location: Span) -> Self {
+        match &mut self {
+            Self::ParserError { span, .. }
+            | Self::SemanticError { span, .. }
+            | Self::CodeGenError { span, .. } => {
+                span.get_or_insert(location);
+            }
+            Self::LexerError { .. } => {}
+        }
+        self
+    }
+    
+    /// Source location of the error, if known
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::LexerError { line, col, .. } => Some(Span { line: *line, col: *col }),
+            Self::ParserError { span, .. }
+            | Self::SemanticError { span, .. }
+            | Self::CodeGenError { span, .. } => *span,
+        }
+    }
+}
+
+fn location(span: &Option<Span>) -> String {
+    span.map(|s| format!(" at line {}, column {}", s.line, s.col)).unwrap_or_default()
 }
 
 pub type Result<T> = std::result::Result<T, CompilerError>;
 
 /// Compile BCL source code to ZKVM bytecode
 pub fn compile(source: &str) -> Result<Vec<bitcell_zkvm::Instruction>> {
+    Ok(compile_program(source)?.instructions)
+}
+
+/// Compile BCL source code to ZKVM bytecode plus its data section
+pub fn compile_program(source: &str) -> Result<Program> {
     // Lexical analysis
     let tokens = lexer::tokenize(source)?;
     
     // Parsing
-    let ast = parser::parse(tokens)?;
+    let contracts = parser::parse(tokens)?;
     
     // Semantic analysis
-    semantic::analyze(&ast)?;
+    semantic::analyze(&contracts)?;
     
     // Code generation
-    let instructions = codegen::generate(&ast)?;
+    let mut program = codegen::generate_program(&contracts)?;
+    codegen::optimize(&mut program.instructions);
     
-    Ok(instructions)
+    Ok(program)
 }
 
 #[cfg(test)]
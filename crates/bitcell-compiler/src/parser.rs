@@ -1,21 +1,32 @@
 //! Parser for BCL
 
 use crate::ast::*;
-use crate::lexer::Token;
+use crate::lexer::{SpannedToken, Token};
 use crate::{CompilerError, Result};
 
 pub struct Parser {
-    tokens: Vec<Token>,
+    tokens: Vec<SpannedToken>,
     pos: usize,
 }
 
 impl Parser {
-    fn new(tokens: Vec<Token>) -> Self {
+    fn new(tokens: Vec<SpannedToken>) -> Self {
         Self { tokens, pos: 0 }
     }
     
     fn current(&self) -> &Token {
-        self.tokens.get(self.pos).unwrap_or(&Token::Eof)
+        self.tokens.get(self.pos).map_or(&Token::Eof, |t| &t.token)
+    }
+    
+    /// Where the current token starts (the end of input once exhausted)
+    fn span(&self) -> Span {
+        self.tokens.get(self.pos)
+            .or(self.tokens.last())
+            .map_or_else(Span::default, |t| t.span)
+    }
+    
+    fn error(&self, message: impl Into<String>) -> CompilerError {
+        CompilerError::parser(message).at(self.span())
     }
     
     fn advance(&mut self) {
@@ -29,7 +40,7 @@ impl Parser {
             self.advance();
             Ok(())
         } else {
-            Err(CompilerError::ParserError(format!(
+            Err(self.error(format!(
                 "Expected {:?}, found {:?}",
                 token,
                 self.current()
@@ -37,7 +48,33 @@ impl Parser {
         }
     }
     
+    fn parse_identifier(&mut self, what: &str) -> Result<String> {
+        if let Token::Identifier(n) = self.current() {
+            let name = n.clone();
+            self.advance();
+            Ok(name)
+        } else {
+            Err(self.error(format!(
+                "Expected {}, found {:?}",
+                what,
+                self.current()
+            )))
+        }
+    }
+    
+    fn parse_contracts(&mut self) -> Result<Vec<Contract>> {
+        let mut contracts = Vec::new();
+        while self.current() != &Token::Eof {
+            contracts.push(self.parse_contract()?);
+        }
+        if contracts.is_empty() {
+            return Err(self.error("Expected at least one contract".to_string()));
+        }
+        Ok(contracts)
+    }
+    
     fn parse_contract(&mut self) -> Result<Contract> {
+        let span = self.span();
         self.expect(Token::Contract)?;
         
         let name = if let Token::Identifier(n) = self.current() {
@@ -45,12 +82,13 @@ impl Parser {
             self.advance();
             name
         } else {
-            return Err(CompilerError::ParserError("Expected contract name".to_string()));
+            return Err(self.error("Expected contract name".to_string()));
         };
         
         self.expect(Token::LBrace)?;
         
         let mut storage = Vec::new();
+        let mut events = Vec::new();
         let mut functions = Vec::new();
         
         while self.current() != &Token::RBrace && self.current() != &Token::Eof {
@@ -59,11 +97,14 @@ impl Parser {
                     self.advance();
                     storage = self.parse_storage()?;
                 }
+                Token::Event => {
+                    events.push(self.parse_event()?);
+                }
                 Token::Function => {
                     functions.push(self.parse_function()?);
                 }
                 _ => {
-                    return Err(CompilerError::ParserError(format!(
+                    return Err(self.error(format!(
                         "Unexpected token in contract: {:?}",
                         self.current()
                     )));
@@ -76,7 +117,9 @@ impl Parser {
         Ok(Contract {
             name,
             storage,
+            events,
             functions,
+            span,
         })
     }
     
@@ -91,7 +134,7 @@ impl Parser {
                 self.advance();
                 name
             } else {
-                return Err(CompilerError::ParserError("Expected storage variable name".to_string()));
+                return Err(self.error("Expected storage variable name".to_string()));
             };
             
             self.expect(Token::Colon)?;
@@ -125,7 +168,7 @@ impl Parser {
                 let key_type = self.parse_type()?;
                 // Only accept => for mapping syntax (Solidity-style)
                 if self.current() != &Token::FatArrow {
-                    return Err(CompilerError::ParserError(format!(
+                    return Err(self.error(format!(
                         "Expected '=>' for mapping type, found {:?}. Only '=>' is allowed for mapping types.",
                         self.current()
                     )));
@@ -135,7 +178,7 @@ impl Parser {
                 self.expect(Token::RParen)?;
                 Ok(Type::Mapping(Box::new(key_type), Box::new(value_type)))
             }
-            _ => Err(CompilerError::ParserError(format!(
+            _ => Err(self.error(format!(
                 "Expected type, found {:?}",
                 self.current()
             ))),
@@ -143,6 +186,7 @@ impl Parser {
     }
     
     fn parse_function(&mut self) -> Result<Function> {
+        let span = self.span();
         self.expect(Token::Function)?;
         
         let name = if let Token::Identifier(n) = self.current() {
@@ -150,7 +194,7 @@ impl Parser {
             self.advance();
             name
         } else {
-            return Err(CompilerError::ParserError("Expected function name".to_string()));
+            return Err(self.error("Expected function name".to_string()));
         };
         
         self.expect(Token::LParen)?;
@@ -173,9 +217,29 @@ impl Parser {
             params,
             return_type,
             body,
+            span,
         })
     }
     
+    fn parse_event(&mut self) -> Result<EventDecl> {
+        self.expect(Token::Event)?;
+        
+        let name = if let Token::Identifier(n) = self.current() {
+            let name = n.clone();
+            self.advance();
+            name
+        } else {
+            return Err(self.error("Expected event name".to_string()));
+        };
+        
+        self.expect(Token::LParen)?;
+        let params = self.parse_parameters()?;
+        self.expect(Token::RParen)?;
+        self.expect(Token::Semicolon)?;
+        
+        Ok(EventDecl { name, params })
+    }
+    
     fn parse_parameters(&mut self) -> Result<Vec<Parameter>> {
         let mut params = Vec::new();
         
@@ -185,7 +249,7 @@ impl Parser {
                 self.advance();
                 name
             } else {
-                return Err(CompilerError::ParserError("Expected parameter name".to_string()));
+                return Err(self.error("Expected parameter name".to_string()));
             };
             
             self.expect(Token::Colon)?;
@@ -197,7 +261,7 @@ impl Parser {
                 self.advance();
                 // Check for trailing comma
                 if self.current() == &Token::RParen {
-                    return Err(CompilerError::ParserError(
+                    return Err(self.error(
                         "Trailing comma in parameter list is not allowed".to_string()
                     ));
                 }
@@ -218,6 +282,7 @@ impl Parser {
     }
     
     fn parse_statement(&mut self) -> Result<Statement> {
+        let span = self.span();
         match self.current() {
             Token::Let => {
                 self.advance();
@@ -226,14 +291,14 @@ impl Parser {
                     self.advance();
                     name
                 } else {
-                    return Err(CompilerError::ParserError("Expected variable name".to_string()));
+                    return Err(self.error("Expected variable name".to_string()));
                 };
                 
                 self.expect(Token::Assign)?;
                 let value = self.parse_expression()?;
                 self.expect(Token::Semicolon)?;
                 
-                Ok(Statement::Let { name, value })
+                Ok(Statement::Let { name, value, span })
             }
             Token::If => {
                 self.advance();
@@ -258,6 +323,53 @@ impl Parser {
                     condition,
                     then_block,
                     else_block,
+                    span,
+                })
+            }
+            Token::While => {
+                self.advance();
+                self.expect(Token::LParen)?;
+                let condition = self.parse_expression()?;
+                self.expect(Token::RParen)?;
+                self.expect(Token::LBrace)?;
+                let body = self.parse_statements()?;
+                self.expect(Token::RBrace)?;
+                
+                Ok(Statement::While { condition, body, span })
+            }
+            Token::For => {
+                self.advance();
+                self.expect(Token::LParen)?;
+                
+                // Initializer must be a `let` (which consumes its own semicolon)
+                if self.current() != &Token::Let {
+                    return Err(self.error(format!(
+                        "Expected 'let' in for-loop initializer, found {:?}",
+                        self.current()
+                    )));
+                }
+                let init = self.parse_statement()?;
+                
+                let condition = self.parse_expression()?;
+                self.expect(Token::Semicolon)?;
+                
+                // Update is an assignment without a trailing semicolon
+                let update_span = self.span();
+                let target = self.parse_expression()?;
+                self.expect(Token::Assign)?;
+                let value = self.parse_expression()?;
+                self.expect(Token::RParen)?;
+                
+                self.expect(Token::LBrace)?;
+                let body = self.parse_statements()?;
+                self.expect(Token::RBrace)?;
+                
+                Ok(Statement::For {
+                    init: Box::new(init),
+                    condition,
+                    update: Box::new(Statement::Assign { target, value, span: update_span }),
+                    body,
+                    span,
                 })
             }
             Token::Return => {
@@ -268,7 +380,7 @@ impl Parser {
                     Some(self.parse_expression()?)
                 };
                 self.expect(Token::Semicolon)?;
-                Ok(Statement::Return { value })
+                Ok(Statement::Return { value, span })
             }
             Token::Require => {
                 self.advance();
@@ -280,27 +392,43 @@ impl Parser {
                     self.advance();
                     msg
                 } else {
-                    return Err(CompilerError::ParserError("Expected error message".to_string()));
+                    return Err(self.error("Expected error message".to_string()));
                 };
                 self.expect(Token::RParen)?;
                 self.expect(Token::Semicolon)?;
                 
-                Ok(Statement::Require { condition, message })
+                Ok(Statement::Require { condition, message, span })
             }
-            Token::Identifier(_) => {
+            Token::Emit => {
+                self.advance();
+                let event = if let Token::Identifier(n) = self.current() {
+                    let name = n.clone();
+                    self.advance();
+                    name
+                } else {
+                    return Err(self.error("Expected event name after 'emit'".to_string()));
+                };
+                self.expect(Token::LParen)?;
+                let args = self.parse_arguments()?;
+                self.expect(Token::RParen)?;
+                self.expect(Token::Semicolon)?;
+                
+                Ok(Statement::Emit { event, args, span })
+            }
+            Token::Identifier(_) | Token::Call => {
                 let expr = self.parse_expression()?;
                 
                 if self.current() == &Token::Assign {
                     self.advance();
                     let value = self.parse_expression()?;
                     self.expect(Token::Semicolon)?;
-                    Ok(Statement::Assign { target: expr, value })
+                    Ok(Statement::Assign { target: expr, value, span })
                 } else {
                     self.expect(Token::Semicolon)?;
-                    Ok(Statement::Expression(expr))
+                    Ok(Statement::Expression { expr, span })
                 }
             }
-            _ => Err(CompilerError::ParserError(format!(
+            _ => Err(self.error(format!(
                 "Unexpected token in statement: {:?}",
                 self.current()
             ))),
@@ -460,13 +588,13 @@ impl Parser {
                             member,
                         };
                     } else {
-                        return Err(CompilerError::ParserError(
+                        return Err(self.error(
                             "Expected identifier after '.'".to_string(),
                         ));
                     }
                 }
-                Token::LParen if matches!(expr, Expression::Identifier(_)) => {
-                    if let Expression::Identifier(name) = expr {
+                Token::LParen if matches!(expr, Expression::Identifier { .. }) => {
+                    if let Expression::Identifier { name, .. } = expr {
                         self.advance();
                         let args = self.parse_arguments()?;
                         self.expect(Token::RParen)?;
@@ -495,6 +623,7 @@ impl Parser {
     }
     
     fn parse_primary(&mut self) -> Result<Expression> {
+        let span = self.span();
         match self.current().clone() {
             Token::Number(n) => {
                 self.advance();
@@ -510,7 +639,17 @@ impl Parser {
             }
             Token::Identifier(name) => {
                 self.advance();
-                Ok(Expression::Identifier(name))
+                Ok(Expression::Identifier { name, span })
+            }
+            Token::Call => {
+                self.advance();
+                let contract = self.parse_identifier("contract name after 'call'")?;
+                self.expect(Token::Dot)?;
+                let function = self.parse_identifier("function name after '.'")?;
+                self.expect(Token::LParen)?;
+                let args = self.parse_arguments()?;
+                self.expect(Token::RParen)?;
+                Ok(Expression::ExternalCall { contract, function, args })
             }
             Token::LParen => {
                 self.advance();
@@ -518,7 +657,7 @@ impl Parser {
                 self.expect(Token::RParen)?;
                 Ok(expr)
             }
-            _ => Err(CompilerError::ParserError(format!(
+            _ => Err(self.error(format!(
                 "Unexpected token in expression: {:?}",
                 self.current()
             ))),
@@ -526,9 +665,10 @@ impl Parser {
     }
 }
 
-pub fn parse(tokens: Vec<Token>) -> Result<Contract> {
+/// Parse every contract in a source file, in declaration order
+pub fn parse(tokens: Vec<SpannedToken>) -> Result<Vec<Contract>> {
     let mut parser = Parser::new(tokens);
-    parser.parse_contract()
+    parser.parse_contracts()
 }
 
 #[cfg(test)]
@@ -552,10 +692,94 @@ mod tests {
         "#;
         
         let tokens = tokenize(source).unwrap();
-        let contract = parse(tokens).unwrap();
+        let contract = &parse(tokens).unwrap()[0];
         
         assert_eq!(contract.name, "Test");
         assert_eq!(contract.storage.len(), 1);
         assert_eq!(contract.functions.len(), 1);
     }
+
+    #[test]
+    fn test_parse_loops() {
+        let source = r#"
+            contract Loops {
+                function run(n: uint) -> uint {
+                    let sum = 0;
+                    for (let i = 0; i < n; i = i + 1) {
+                        sum = sum + i;
+                    }
+                    while (sum > 100) {
+                        sum = sum - 100;
+                    }
+                    return sum;
+                }
+            }
+        "#;
+        
+        let tokens = tokenize(source).unwrap();
+        let contract = &parse(tokens).unwrap()[0];
+        let body = &contract.functions[0].body;
+        
+        assert!(matches!(&body[1], Statement::For { init, update, .. }
+            if matches!(**init, Statement::Let { .. }) && matches!(**update, Statement::Assign { .. })));
+        assert!(matches!(&body[2], Statement::While { body, .. } if body.len() == 1));
+    }
+
+    #[test]
+    fn test_parse_event_and_emit() {
+        let source = r#"
+            contract Test {
+                event Transfer(from: address, to: address, amount: uint);
+                
+                function send(to: address, amount: uint) -> bool {
+                    emit Transfer(msg.sender, to, amount);
+                    return true;
+                }
+            }
+        "#;
+        
+        let tokens = tokenize(source).unwrap();
+        let contract = &parse(tokens).unwrap()[0];
+        
+        assert_eq!(contract.events.len(), 1);
+        assert_eq!(contract.events[0].name, "Transfer");
+        assert_eq!(contract.events[0].params.len(), 3);
+        assert!(matches!(&contract.functions[0].body[0], Statement::Emit { event, args, .. }
+            if event == "Transfer" && args.len() == 3));
+    }
+
+    #[test]
+    fn test_parse_multiple_contracts_with_call() {
+        let source = r#"
+            contract A {
+                function read() -> uint {
+                    return call B.get(1, 2);
+                }
+            }
+            
+            contract B {
+                function get(x: uint, y: uint) -> uint {
+                    return x + y;
+                }
+            }
+        "#;
+        
+        let contracts = parse(tokenize(source).unwrap()).unwrap();
+        
+        assert_eq!(contracts.len(), 2);
+        assert_eq!(contracts[1].name, "B");
+        assert!(matches!(&contracts[0].functions[0].body[0], Statement::Return {
+            value: Some(Expression::ExternalCall { contract, function, args }), ..
+        } if contract == "B" && function == "get" && args.len() == 2));
+    }
+
+    #[test]
+    fn test_parse_error_reports_location() {
+        let source = "contract Test {\n    function f() -> uint {\n        let x = 1\n        return x;\n    }\n}";
+        let err = parse(tokenize(source).unwrap()).unwrap_err();
+        
+        // The missing semicolon is noticed at the next token
+        assert_eq!(err.span(), Some(Span { line: 4, col: 9 }));
+        assert!(err.to_string().contains("Expected Semicolon, found Return"), "{}", err);
+    }
 }
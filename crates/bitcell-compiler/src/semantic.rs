@@ -4,21 +4,152 @@ use crate::ast::*;
 use crate::{CompilerError, Result};
 use std::collections::HashMap;
 
-pub fn analyze(contract: &Contract) -> Result<()> {
-    let mut analyzer = SemanticAnalyzer::new();
-    analyzer.analyze_contract(contract)
+/// Parameter types and return type of a function
+type Signature = (Vec<Type>, Option<Type>);
+
+pub fn analyze(contracts: &[Contract]) -> Result<()> {
+    let signatures = collect_signatures(contracts)?;
+    check_call_graph(contracts)?;
+    
+    for contract in contracts {
+        let mut analyzer = SemanticAnalyzer::new(&signatures);
+        analyzer.analyze_contract(contract)?;
+    }
+    Ok(())
+}
+
+/// Function signatures of every contract, keyed by contract then function name
+fn collect_signatures(contracts: &[Contract]) -> Result<HashMap<String, HashMap<String, Signature>>> {
+    let mut signatures = HashMap::new();
+    for contract in contracts {
+        let mut functions = HashMap::new();
+        for func in &contract.functions {
+            let params = func.params.iter().map(|p| p.ty.clone()).collect();
+            if functions.insert(func.name.clone(), (params, func.return_type.clone())).is_some() {
+                return Err(CompilerError::semantic(format!(
+                    "Duplicate function: {}.{}",
+                    contract.name, func.name
+                )).at(func.span));
+            }
+        }
+        if signatures.insert(contract.name.clone(), functions).is_some() {
+            return Err(CompilerError::semantic(format!(
+                "Duplicate contract: {}",
+                contract.name
+            )).at(contract.span));
+        }
+    }
+    Ok(signatures)
+}
+
+/// Reject cycles between contracts
+///
+/// Codegen spills a caller's registers to a frame owned by its contract, so
+/// a contract may only be active once on the call stack.
+fn check_call_graph(contracts: &[Contract]) -> Result<()> {
+    let callees: HashMap<&str, Vec<&str>> = contracts.iter()
+        .map(|c| {
+            let mut called = Vec::new();
+            for func in &c.functions {
+                collect_callees(&func.body, &mut called);
+            }
+            (c.name.as_str(), called)
+        })
+        .collect();
+    
+    fn visit<'a>(
+        name: &'a str,
+        callees: &HashMap<&'a str, Vec<&'a str>>,
+        path: &mut Vec<&'a str>,
+    ) -> Result<()> {
+        if path.contains(&name) {
+            path.push(name);
+            return Err(CompilerError::semantic(format!(
+                "Recursive contract call: {}",
+                path.join(" -> ")
+            )));
+        }
+        path.push(name);
+        for &callee in callees.get(name).into_iter().flatten() {
+            visit(callee, callees, path)?;
+        }
+        path.pop();
+        Ok(())
+    }
+    
+    for contract in contracts {
+        visit(&contract.name, &callees, &mut Vec::new())?;
+    }
+    Ok(())
 }
 
-struct SemanticAnalyzer {
+fn collect_callees<'a>(stmts: &'a [Statement], out: &mut Vec<&'a str>) {
+    fn expr<'a>(e: &'a Expression, out: &mut Vec<&'a str>) {
+        match e {
+            Expression::ExternalCall { contract, args, .. } => {
+                out.push(contract);
+                args.iter().for_each(|a| expr(a, out));
+            }
+            Expression::Call { args, .. } => args.iter().for_each(|a| expr(a, out)),
+            Expression::Binary { left, right, .. } => {
+                expr(left, out);
+                expr(right, out);
+            }
+            Expression::Index { expr: e, index } => {
+                expr(e, out);
+                expr(index, out);
+            }
+            Expression::Unary { expr: e, .. } | Expression::MemberAccess { expr: e, .. } => expr(e, out),
+            Expression::Literal(_) | Expression::Identifier { .. } => {}
+        }
+    }
+    
+    for stmt in stmts {
+        match stmt {
+            Statement::Let { value, .. } => expr(value, out),
+            Statement::Assign { target, value, .. } => {
+                expr(target, out);
+                expr(value, out);
+            }
+            Statement::If { condition, then_block, else_block, .. } => {
+                expr(condition, out);
+                collect_callees(then_block, out);
+                if let Some(else_stmts) = else_block {
+                    collect_callees(else_stmts, out);
+                }
+            }
+            Statement::While { condition, body, .. } => {
+                expr(condition, out);
+                collect_callees(body, out);
+            }
+            Statement::For { init, condition, update, body, .. } => {
+                collect_callees(std::slice::from_ref(init), out);
+                expr(condition, out);
+                collect_callees(std::slice::from_ref(update), out);
+                collect_callees(body, out);
+            }
+            Statement::Return { value, .. } => value.iter().for_each(|v| expr(v, out)),
+            Statement::Require { condition, .. } => expr(condition, out),
+            Statement::Emit { args, .. } => args.iter().for_each(|a| expr(a, out)),
+            Statement::Expression { expr: e, .. } => expr(e, out),
+        }
+    }
+}
+
+struct SemanticAnalyzer<'a> {
     storage_vars: HashMap<String, Type>,
     local_vars: HashMap<String, Type>,
+    events: HashMap<String, Vec<Type>>,
+    signatures: &'a HashMap<String, HashMap<String, Signature>>,
 }
 
-impl SemanticAnalyzer {
-    fn new() -> Self {
+impl<'a> SemanticAnalyzer<'a> {
+    fn new(signatures: &'a HashMap<String, HashMap<String, Signature>>) -> Self {
         Self {
             storage_vars: HashMap::new(),
             local_vars: HashMap::new(),
+            events: HashMap::new(),
+            signatures,
         }
     }
     
@@ -26,14 +157,26 @@ impl SemanticAnalyzer {
         // Collect storage variables
         for decl in &contract.storage {
             if self.storage_vars.contains_key(&decl.name) {
-                return Err(CompilerError::SemanticError(format!(
+                return Err(CompilerError::semantic(format!(
                     "Duplicate storage variable: {}",
                     decl.name
-                )));
+                )).at(contract.span));
             }
             self.storage_vars.insert(decl.name.clone(), decl.ty.clone());
         }
         
+        // Collect event signatures
+        for event in &contract.events {
+            if self.events.contains_key(&event.name) {
+                return Err(CompilerError::semantic(format!(
+                    "Duplicate event: {}",
+                    event.name
+                )).at(contract.span));
+            }
+            let param_types = event.params.iter().map(|p| p.ty.clone()).collect();
+            self.events.insert(event.name.clone(), param_types);
+        }
+        
         // Analyze each function
         for func in &contract.functions {
             self.analyze_function(func)?;
@@ -48,10 +191,10 @@ impl SemanticAnalyzer {
         // Add parameters to local scope
         for param in &func.params {
             if self.local_vars.contains_key(&param.name) {
-                return Err(CompilerError::SemanticError(format!(
+                return Err(CompilerError::semantic(format!(
                     "Duplicate parameter: {}",
                     param.name
-                )));
+                )).at(func.span));
             }
             self.local_vars.insert(param.name.clone(), param.ty.clone());
         }
@@ -65,20 +208,24 @@ impl SemanticAnalyzer {
     }
     
     fn analyze_statement_with_return_type(&mut self, stmt: &Statement, expected_return: &Option<Type>) -> Result<()> {
+        self.check_statement(stmt, expected_return).map_err(|e| e.at(stmt.span()))
+    }
+    
+    fn check_statement(&mut self, stmt: &Statement, expected_return: &Option<Type>) -> Result<()> {
         match stmt {
-            Statement::Let { name, value } => {
+            Statement::Let { name, value, .. } => {
                 let ty = self.type_of_expression(value)?;
                 self.local_vars.insert(name.clone(), ty);
                 Ok(())
             }
-            Statement::Assign { target, value } => {
+            Statement::Assign { target, value, .. } => {
                 let target_ty = self.type_of_expression(target)?;
                 let value_ty = self.type_of_expression(value)?;
                 
                 if target_ty != value_ty {
-                    return Err(CompilerError::SemanticError(format!(
-                        "Type mismatch in assignment: expected {:?}, found {:?}",
-                        target_ty, value_ty
+                    return Err(CompilerError::semantic(format!(
+                        "Type mismatch in assignment to '{}': expected {:?}, found {:?}",
+                        describe(target), target_ty, value_ty
                     )));
                 }
                 
@@ -88,10 +235,11 @@ impl SemanticAnalyzer {
                 condition,
                 then_block,
                 else_block,
+                ..
             } => {
                 let cond_ty = self.type_of_expression(condition)?;
                 if cond_ty != Type::Bool {
-                    return Err(CompilerError::SemanticError(
+                    return Err(CompilerError::semantic(
                         "If condition must be boolean".to_string(),
                     ));
                 }
@@ -108,26 +256,52 @@ impl SemanticAnalyzer {
                 
                 Ok(())
             }
-            Statement::Return { value } => {
+            Statement::While { condition, body, .. } => {
+                self.analyze_loop_condition(condition, body, None)?;
+                
+                for stmt in body {
+                    self.analyze_statement_with_return_type(stmt, expected_return)?;
+                }
+                
+                Ok(())
+            }
+            Statement::For {
+                init,
+                condition,
+                update,
+                body,
+                ..
+            } => {
+                self.analyze_statement_with_return_type(init, expected_return)?;
+                self.analyze_loop_condition(condition, body, Some(update))?;
+                self.analyze_statement_with_return_type(update, expected_return)?;
+                
+                for stmt in body {
+                    self.analyze_statement_with_return_type(stmt, expected_return)?;
+                }
+                
+                Ok(())
+            }
+            Statement::Return { value, .. } => {
                 match (value, expected_return) {
                     (Some(expr), Some(expected_ty)) => {
                         let actual_ty = self.type_of_expression(expr)?;
                         if actual_ty != *expected_ty {
-                            return Err(CompilerError::SemanticError(format!(
+                            return Err(CompilerError::semantic(format!(
                                 "Return type mismatch: expected {:?}, found {:?}",
                                 expected_ty, actual_ty
                             )));
                         }
                     }
                     (None, Some(expected_ty)) => {
-                        return Err(CompilerError::SemanticError(format!(
+                        return Err(CompilerError::semantic(format!(
                             "Function should return {:?}, but returns nothing",
                             expected_ty
                         )));
                     }
                     (Some(expr), None) => {
                         let _ = self.type_of_expression(expr)?;
-                        return Err(CompilerError::SemanticError(
+                        return Err(CompilerError::semantic(
                             "Function should not return a value".to_string(),
                         ));
                     }
@@ -138,19 +312,133 @@ impl SemanticAnalyzer {
             Statement::Require { condition, .. } => {
                 let cond_ty = self.type_of_expression(condition)?;
                 if cond_ty != Type::Bool {
-                    return Err(CompilerError::SemanticError(
+                    return Err(CompilerError::semantic(
                         "Require condition must be boolean".to_string(),
                     ));
                 }
                 Ok(())
             }
-            Statement::Expression(expr) => {
+            Statement::Emit { event, args, .. } => {
+                let param_types = self.events.get(event).ok_or_else(|| {
+                    CompilerError::semantic(format!("Undefined event: {}", event))
+                })?;
+                
+                if args.len() != param_types.len() {
+                    return Err(CompilerError::semantic(format!(
+                        "Event {} expects {} arguments, found {}",
+                        event,
+                        param_types.len(),
+                        args.len()
+                    )));
+                }
+                
+                for (i, (arg, expected_ty)) in args.iter().zip(param_types).enumerate() {
+                    let actual_ty = self.type_of_expression(arg)?;
+                    if actual_ty != *expected_ty {
+                        return Err(CompilerError::semantic(format!(
+                            "Type mismatch in argument {} of event {}: expected {:?}, found {:?}",
+                            i, event, expected_ty, actual_ty
+                        )));
+                    }
+                }
+                
+                Ok(())
+            }
+            Statement::Expression { expr: Expression::ExternalCall { contract, function, args }, .. } => {
+                // The result of a call statement is discarded, so it may return nothing
+                self.check_external_call(contract, function, args)?;
+                Ok(())
+            }
+            Statement::Expression { expr, .. } => {
                 self.type_of_expression(expr)?;
                 Ok(())
             }
         }
     }
     
+    /// Check `call contract.function(args)` and return the callee's return type
+    fn check_external_call(
+        &self,
+        contract: &str,
+        function: &str,
+        args: &[Expression],
+    ) -> Result<Option<Type>> {
+        let functions = self.signatures.get(contract).ok_or_else(|| {
+            CompilerError::semantic(format!("Undefined contract: {}", contract))
+        })?;
+        let (param_types, return_type) = functions.get(function).ok_or_else(|| {
+            CompilerError::semantic(format!("Undefined function: {}.{}", contract, function))
+        })?;
+        
+        if args.len() != param_types.len() {
+            return Err(CompilerError::semantic(format!(
+                "Function {}.{} expects {} arguments, found {}",
+                contract,
+                function,
+                param_types.len(),
+                args.len()
+            )));
+        }
+        
+        for (i, (arg, expected_ty)) in args.iter().zip(param_types).enumerate() {
+            let actual_ty = self.type_of_expression(arg)?;
+            if actual_ty != *expected_ty {
+                return Err(CompilerError::semantic(format!(
+                    "Type mismatch in argument {} of {}.{}: expected {:?}, found {:?}",
+                    i, contract, function, expected_ty, actual_ty
+                )));
+            }
+        }
+        
+        Ok(return_type.clone())
+    }
+    
+    fn analyze_loop_condition(
+        &self,
+        condition: &Expression,
+        body: &[Statement],
+        update: Option<&Statement>,
+    ) -> Result<()> {
+        let cond_ty = self.type_of_expression(condition)?;
+        if cond_ty != Type::Bool {
+            return Err(CompilerError::semantic(
+                "Loop condition must be boolean".to_string(),
+            ));
+        }
+        
+        // There is no `break`, so a loop whose condition is constant, or reads
+        // nothing the loop writes, never exits once entered unless its body
+        // returns or fails a `require`. Other loops are bounded at runtime by
+        // the ZKVM gas limit.
+        if may_exit(body) {
+            return Ok(());
+        }
+        match const_bool(condition) {
+            Some(false) => return Ok(()),
+            Some(true) => {
+                return Err(CompilerError::semantic(
+                    "Loop condition is always true; the loop would never terminate".to_string(),
+                ));
+            }
+            None => {}
+        }
+        
+        let mut reads = Vec::new();
+        let mut writes = Vec::new();
+        if collect_reads(condition, &mut reads)
+            && collect_writes(body, &mut writes)
+            && collect_writes(update.map(std::slice::from_ref).unwrap_or_default(), &mut writes)
+            && !reads.iter().any(|name| writes.contains(name))
+        {
+            return Err(CompilerError::semantic(format!(
+                "Loop condition {} is never updated by the loop; the loop would never terminate",
+                describe(condition)
+            )));
+        }
+        
+        Ok(())
+    }
+    
     fn type_of_expression(&self, expr: &Expression) -> Result<Type> {
         match expr {
             Expression::Literal(lit) => Ok(match lit {
@@ -158,16 +446,16 @@ impl SemanticAnalyzer {
                 Literal::Bool(_) => Type::Bool,
                 Literal::Address(_) => Type::Address,
             }),
-            Expression::Identifier(name) => {
+            Expression::Identifier { name, span } => {
                 if let Some(ty) = self.local_vars.get(name) {
                     Ok(ty.clone())
                 } else if let Some(ty) = self.storage_vars.get(name) {
                     Ok(ty.clone())
                 } else {
-                    Err(CompilerError::SemanticError(format!(
+                    Err(CompilerError::semantic(format!(
                         "Undefined variable: {}",
                         name
-                    )))
+                    )).at(*span))
                 }
             }
             Expression::Binary { left, op, right } => {
@@ -176,16 +464,19 @@ impl SemanticAnalyzer {
                 
                 match op {
                     BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => {
-                        if left_ty != Type::Uint || right_ty != Type::Uint {
-                            return Err(CompilerError::SemanticError(
-                                "Arithmetic operations require uint operands".to_string(),
-                            ));
+                        for (operand, ty) in [(left, &left_ty), (right, &right_ty)] {
+                            if *ty != Type::Uint {
+                                return Err(CompilerError::semantic(format!(
+                                    "Arithmetic operations require uint operands, but '{}' is {:?}",
+                                    describe(operand), ty
+                                )));
+                            }
                         }
                         
                         // Check for division by zero in constant expressions
                         if matches!(op, BinaryOp::Div | BinaryOp::Mod) {
                             if let Expression::Literal(Literal::Uint(0)) = &**right {
-                                return Err(CompilerError::SemanticError(
+                                return Err(CompilerError::semantic(
                                     "Division by zero in constant expression".to_string(),
                                 ));
                             }
@@ -195,15 +486,31 @@ impl SemanticAnalyzer {
                     }
                     BinaryOp::Eq | BinaryOp::Ne | BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
                         if left_ty != right_ty {
-                            return Err(CompilerError::SemanticError(
-                                "Comparison operands must have same type".to_string(),
-                            ));
+                            return Err(CompilerError::semantic(format!(
+                                "Comparison operands must have same type: '{}' is {:?}, '{}' is {:?}",
+                                describe(left), left_ty, describe(right), right_ty
+                            )));
+                        }
+                        
+                        // Only numbers are ordered; bools and addresses support ==/!= only
+                        if !matches!(op, BinaryOp::Eq | BinaryOp::Ne) && left_ty != Type::Uint {
+                            return Err(CompilerError::semantic(format!(
+                                "Ordering comparison requires uint operands, but '{}' is {:?}",
+                                describe(left), left_ty
+                            )));
+                        }
+                        
+                        if matches!(left_ty, Type::Mapping(..)) {
+                            return Err(CompilerError::semantic(format!(
+                                "Cannot compare mapping '{}'",
+                                describe(left)
+                            )));
                         }
                         Ok(Type::Bool)
                     }
                     BinaryOp::And | BinaryOp::Or => {
                         if left_ty != Type::Bool || right_ty != Type::Bool {
-                            return Err(CompilerError::SemanticError(
+                            return Err(CompilerError::semantic(
                                 "Logical operations require boolean operands".to_string(),
                             ));
                         }
@@ -216,7 +523,7 @@ impl SemanticAnalyzer {
                 match op {
                     UnaryOp::Not => {
                         if ty != Type::Bool {
-                            return Err(CompilerError::SemanticError(
+                            return Err(CompilerError::semantic(
                                 "Logical NOT requires boolean operand".to_string(),
                             ));
                         }
@@ -224,7 +531,7 @@ impl SemanticAnalyzer {
                     }
                     UnaryOp::Neg => {
                         if ty != Type::Uint {
-                            return Err(CompilerError::SemanticError(
+                            return Err(CompilerError::semantic(
                                 "Negation requires uint operand".to_string(),
                             ));
                         }
@@ -237,33 +544,200 @@ impl SemanticAnalyzer {
                 // In a full implementation, we'd look up the function signature
                 Ok(Type::Uint)
             }
-            Expression::Index { expr, .. } => {
+            Expression::ExternalCall { contract, function, args } => {
+                self.check_external_call(contract, function, args)?.ok_or_else(|| {
+                    CompilerError::semantic(format!(
+                        "Function {}.{} does not return a value",
+                        contract, function
+                    ))
+                })
+            }
+            Expression::Index { expr, index } => {
                 let ty = self.type_of_expression(expr)?;
                 match ty {
-                    Type::Mapping(_, value_ty) => Ok(*value_ty),
-                    _ => Err(CompilerError::SemanticError(
-                        "Index operation requires mapping".to_string(),
-                    )),
+                    Type::Mapping(key_ty, value_ty) => {
+                        let index_ty = self.type_of_expression(index)?;
+                        if index_ty != *key_ty {
+                            return Err(CompilerError::semantic(format!(
+                                "Mapping key type mismatch for '{}': expected {:?}, found {:?} ('{}')",
+                                describe(expr), key_ty, index_ty, describe(index)
+                            )));
+                        }
+                        Ok(*value_ty)
+                    }
+                    _ => Err(CompilerError::semantic(format!(
+                        "Index operation requires mapping, but '{}' is {:?}",
+                        describe(expr), ty
+                    ))),
                 }
             }
             Expression::MemberAccess { expr, member } => {
                 // Handle common member access patterns
-                if let Expression::Identifier(obj) = &**expr {
+                if let Expression::Identifier { name: obj, .. } = &**expr {
                     match (obj.as_str(), member.as_str()) {
                         ("msg", "sender") => Ok(Type::Address),
                         ("msg", "value") => Ok(Type::Uint),
                         ("block", "number") => Ok(Type::Uint),
                         ("block", "timestamp") => Ok(Type::Uint),
-                        _ => Ok(Type::Uint),  // Default to Uint for unknown members
+                        _ => Err(CompilerError::semantic(format!(
+                            "Unknown member: {}.{}",
+                            obj, member
+                        ))),
                     }
                 } else {
-                    Ok(Type::Uint)
+                    Err(CompilerError::semantic(format!(
+                        "Unsupported member access: {}",
+                        describe(expr)
+                    )))
                 }
             }
         }
     }
 }
 
+/// Whether `stmts` may leave the enclosing function: a `return`, or a
+/// `require` that can fail, anywhere in them
+fn may_exit(stmts: &[Statement]) -> bool {
+    stmts.iter().any(|stmt| match stmt {
+        Statement::Return { .. } | Statement::Require { .. } => true,
+        Statement::If { then_block, else_block, .. } => {
+            may_exit(then_block) || may_exit(else_block.as_deref().unwrap_or_default())
+        }
+        Statement::While { body, .. } | Statement::For { body, .. } => may_exit(body),
+        _ => false,
+    })
+}
+
+/// Value of a boolean expression built only from literals
+fn const_bool(expr: &Expression) -> Option<bool> {
+    match expr {
+        Expression::Literal(Literal::Bool(b)) => Some(*b),
+        Expression::Unary { op: UnaryOp::Not, expr } => const_bool(expr).map(|b| !b),
+        Expression::Binary { left, op, right } => match op {
+            BinaryOp::And => match (const_bool(left), const_bool(right)) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (l, r) => Some(l? && r?),
+            },
+            BinaryOp::Or => match (const_bool(left), const_bool(right)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (l, r) => Some(l? || r?),
+            },
+            BinaryOp::Eq | BinaryOp::Ne => {
+                let equal = match (const_uint(left), const_uint(right)) {
+                    (Some(l), Some(r)) => l == r,
+                    _ => const_bool(left)? == const_bool(right)?,
+                };
+                Some(equal == (*op == BinaryOp::Eq))
+            }
+            BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
+                let (l, r) = (const_uint(left)?, const_uint(right)?);
+                Some(match op {
+                    BinaryOp::Lt => l < r,
+                    BinaryOp::Le => l <= r,
+                    BinaryOp::Gt => l > r,
+                    _ => l >= r,
+                })
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Value of an integer expression built only from literals
+fn const_uint(expr: &Expression) -> Option<u64> {
+    match expr {
+        Expression::Literal(Literal::Uint(n)) => Some(*n),
+        Expression::Binary { left, op, right } => {
+            let (l, r) = (const_uint(left)?, const_uint(right)?);
+            match op {
+                BinaryOp::Add => l.checked_add(r),
+                BinaryOp::Sub => l.checked_sub(r),
+                BinaryOp::Mul => l.checked_mul(r),
+                BinaryOp::Div => l.checked_div(r),
+                BinaryOp::Mod => l.checked_rem(r),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Collect the variables `expr` reads; false if it calls a function,
+/// whose result may change between iterations
+fn collect_reads<'a>(expr: &'a Expression, out: &mut Vec<&'a str>) -> bool {
+    match expr {
+        Expression::Literal(_) => true,
+        Expression::Identifier { name, .. } => {
+            out.push(name);
+            true
+        }
+        Expression::Binary { left, right, .. } => collect_reads(left, out) && collect_reads(right, out),
+        Expression::Index { expr, index } => collect_reads(expr, out) && collect_reads(index, out),
+        Expression::Unary { expr, .. } | Expression::MemberAccess { expr, .. } => collect_reads(expr, out),
+        Expression::Call { .. } | Expression::ExternalCall { .. } => false,
+    }
+}
+
+/// Collect the variables `stmts` assign or declare; false if they call a
+/// function, which may write storage behind the loop's back
+fn collect_writes<'a>(stmts: &'a [Statement], out: &mut Vec<&'a str>) -> bool {
+    fn root(expr: &Expression) -> Option<&str> {
+        match expr {
+            Expression::Identifier { name, .. } => Some(name),
+            Expression::Index { expr, .. } | Expression::MemberAccess { expr, .. } => root(expr),
+            _ => None,
+        }
+    }
+    fn pure(expr: &Expression) -> bool {
+        collect_reads(expr, &mut Vec::new())
+    }
+    
+    stmts.iter().all(|stmt| match stmt {
+        Statement::Let { name, value, .. } => {
+            out.push(name);
+            pure(value)
+        }
+        Statement::Assign { target, value, .. } => {
+            out.extend(root(target));
+            pure(target) && pure(value)
+        }
+        Statement::If { condition, then_block, else_block, .. } => {
+            pure(condition)
+                && collect_writes(then_block, out)
+                && collect_writes(else_block.as_deref().unwrap_or_default(), out)
+        }
+        Statement::While { condition, body, .. } => pure(condition) && collect_writes(body, out),
+        Statement::For { init, condition, update, body, .. } => {
+            collect_writes(std::slice::from_ref(init), out)
+                && pure(condition)
+                && collect_writes(std::slice::from_ref(update), out)
+                && collect_writes(body, out)
+        }
+        Statement::Return { .. } => true,
+        Statement::Require { condition, .. } => pure(condition),
+        Statement::Emit { args, .. } => args.iter().all(pure),
+        Statement::Expression { expr, .. } => pure(expr),
+    })
+}
+
+/// Short source-like rendering of an expression for error messages
+fn describe(expr: &Expression) -> String {
+    match expr {
+        Expression::Literal(Literal::Uint(n)) => n.to_string(),
+        Expression::Literal(Literal::Bool(b)) => b.to_string(),
+        Expression::Literal(Literal::Address(a)) => a.clone(),
+        Expression::Identifier { name, .. } => name.clone(),
+        Expression::Binary { .. } | Expression::Unary { .. } => "expression".to_string(),
+        Expression::Call { name, .. } => format!("{}(...)", name),
+        Expression::ExternalCall { contract, function, .. } => {
+            format!("call {}.{}(...)", contract, function)
+        }
+        Expression::Index { expr, index } => format!("{}[{}]", describe(expr), describe(index)),
+        Expression::MemberAccess { expr, member } => format!("{}.{}", describe(expr), member),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -362,4 +836,380 @@ mod tests {
             assert!(e.to_string().contains("Return type mismatch"));
         }
     }
+
+    #[test]
+    fn test_loop_condition_must_be_boolean() {
+        let source = r#"
+            contract Test {
+                function run(n: uint) -> uint {
+                    while (n) {
+                        n = n - 1;
+                    }
+                    return n;
+                }
+            }
+        "#;
+        
+        let tokens = tokenize(source).unwrap();
+        let contract = parse(tokens).unwrap();
+        let result = analyze(&contract);
+        
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert!(e.to_string().contains("Loop condition must be boolean"));
+        }
+    }
+
+    #[test]
+    fn test_unbounded_loop_rejected() {
+        let source = r#"
+            contract Test {
+                function spin() -> uint {
+                    let x = 0;
+                    while (true) {
+                        x = x + 1;
+                    }
+                    return x;
+                }
+            }
+        "#;
+        
+        let tokens = tokenize(source).unwrap();
+        let contract = parse(tokens).unwrap();
+        let result = analyze(&contract);
+        
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert!(e.to_string().contains("never terminate"));
+        }
+    }
+
+    #[test]
+    fn test_constant_and_invariant_loops_rejected() {
+        let spin = |condition: &str| format!(r#"
+            contract Test {{
+                storage {{ limit: uint; }}
+                function spin(n: uint) -> uint {{
+                    let x = 0;
+                    while ({}) {{
+                        x = x + 1;
+                    }}
+                    return x;
+                }}
+            }}
+        "#, condition);
+        
+        for condition in ["1 < 2", "!false", "true || n > 3", "n > limit"] {
+            let err = analyze_source(&spin(condition)).unwrap_err();
+            assert!(err.to_string().contains("never terminate"), "{}: {}", condition, err);
+        }
+        
+        // Conditions that the loop can change, or that are never entered, are fine
+        for condition in ["x < n", "false && n > 3", "x < limit"] {
+            assert!(analyze_source(&spin(condition)).is_ok(), "{}", condition);
+        }
+    }
+
+    #[test]
+    fn test_loop_with_early_exit_accepted() {
+        let source = r#"
+            contract Test {
+                function first_above(limit: uint) -> uint {
+                    let x = 0;
+                    while (true) {
+                        if (x > limit) {
+                            return x;
+                        }
+                        x = x + 1;
+                    }
+                    return x;
+                }
+                
+                function drain(n: uint) -> uint {
+                    let x = 0;
+                    while (n > 3) {
+                        require(x < 10, "too many steps");
+                        x = x + 1;
+                    }
+                    return x;
+                }
+            }
+        "#;
+        
+        assert!(analyze_source(source).is_ok());
+    }
+
+    #[test]
+    fn test_emit_checks_event_signature() {
+        let source = r#"
+            contract Test {
+                event Transfer(from: address, to: address, amount: uint);
+                
+                function send(to: address) -> bool {
+                    emit Transfer(msg.sender, to);
+                    return true;
+                }
+            }
+        "#;
+        
+        let tokens = tokenize(source).unwrap();
+        let contract = parse(tokens).unwrap();
+        let result = analyze(&contract);
+        
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert!(e.to_string().contains("expects 3 arguments"));
+        }
+    }
+
+    #[test]
+    fn test_emit_undefined_event() {
+        let source = r#"
+            contract Test {
+                function send() -> bool {
+                    emit Missing(1);
+                    return true;
+                }
+            }
+        "#;
+        
+        let tokens = tokenize(source).unwrap();
+        let contract = parse(tokens).unwrap();
+        let result = analyze(&contract);
+        
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert!(e.to_string().contains("Undefined event"));
+        }
+    }
+
+    fn analyze_source(source: &str) -> Result<()> {
+        let tokens = tokenize(source).unwrap();
+        let contract = parse(tokens).unwrap();
+        analyze(&contract)
+    }
+
+    #[test]
+    fn test_assign_bool_to_uint_storage() {
+        let result = analyze_source(r#"
+            contract Test {
+                storage {
+                    count: uint;
+                    active: bool;
+                }
+                
+                function set() -> bool {
+                    count = active;
+                    return true;
+                }
+            }
+        "#);
+        
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("assignment to 'count'"), "{}", msg);
+        assert!(msg.contains("expected Uint, found Bool"), "{}", msg);
+    }
+
+    #[test]
+    fn test_assign_uint_to_address_storage() {
+        let result = analyze_source(r#"
+            contract Test {
+                storage {
+                    owner: address;
+                }
+                
+                function set(x: uint) -> bool {
+                    owner = x;
+                    return true;
+                }
+            }
+        "#);
+        
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("assignment to 'owner'"), "{}", msg);
+        assert!(msg.contains("expected Address, found Uint"), "{}", msg);
+    }
+
+    #[test]
+    fn test_compare_address_with_uint() {
+        let result = analyze_source(r#"
+            contract Test {
+                storage {
+                    owner: address;
+                }
+                
+                function check(x: uint) -> bool {
+                    return owner == x;
+                }
+            }
+        "#);
+        
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("'owner' is Address, 'x' is Uint"), "{}", msg);
+    }
+
+    #[test]
+    fn test_ordering_comparison_on_bool() {
+        let result = analyze_source(r#"
+            contract Test {
+                function check(a: bool, b: bool) -> bool {
+                    return a < b;
+                }
+            }
+        "#);
+        
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("Ordering comparison requires uint operands, but 'a' is Bool"), "{}", msg);
+    }
+
+    #[test]
+    fn test_mapping_key_type_mismatch() {
+        let result = analyze_source(r#"
+            contract Test {
+                storage {
+                    balances: mapping(address => uint);
+                }
+                
+                function get(id: uint) -> uint {
+                    return balances[id];
+                }
+            }
+        "#);
+        
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("Mapping key type mismatch for 'balances'"), "{}", msg);
+        assert!(msg.contains("expected Address, found Uint ('id')"), "{}", msg);
+    }
+
+    #[test]
+    fn test_mapping_value_type_mismatch() {
+        let result = analyze_source(r#"
+            contract Test {
+                storage {
+                    balances: mapping(address => uint);
+                }
+                
+                function set(to: address) -> bool {
+                    balances[to] = true;
+                    return true;
+                }
+            }
+        "#);
+        
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("assignment to 'balances[to]'"), "{}", msg);
+    }
+
+    #[test]
+    fn test_arithmetic_on_address() {
+        let result = analyze_source(r#"
+            contract Test {
+                function add(a: address, b: uint) -> uint {
+                    return a + b;
+                }
+            }
+        "#);
+        
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("but 'a' is Address"), "{}", msg);
+    }
+
+    const CALLEE: &str = r#"
+        contract B {
+            storage {
+                value: uint;
+            }
+            
+            function get() -> uint {
+                return value;
+            }
+            
+            function set(v: uint) -> bool {
+                value = v;
+                return true;
+            }
+        }
+    "#;
+
+    #[test]
+    fn test_external_call_arity() {
+        let source = format!(r#"
+            contract A {{
+                function read() -> uint {{
+                    return call B.get(1);
+                }}
+            }}
+            {}
+        "#, CALLEE);
+        
+        let msg = analyze_source(&source).unwrap_err().to_string();
+        assert!(msg.contains("Function B.get expects 0 arguments, found 1"), "{}", msg);
+    }
+
+    #[test]
+    fn test_external_call_undefined_callee() {
+        let source = format!(r#"
+            contract A {{
+                function read() -> uint {{
+                    return call B.missing();
+                }}
+            }}
+            {}
+        "#, CALLEE);
+        let msg = analyze_source(&source).unwrap_err().to_string();
+        assert!(msg.contains("Undefined function: B.missing"), "{}", msg);
+        
+        let msg = analyze_source(r#"
+            contract A {
+                function read() -> uint {
+                    return call C.get();
+                }
+            }
+        "#).unwrap_err().to_string();
+        assert!(msg.contains("Undefined contract: C"), "{}", msg);
+    }
+
+    #[test]
+    fn test_external_call_cycle_rejected() {
+        let msg = analyze_source(r#"
+            contract A {
+                function ping() -> uint {
+                    return call B.pong();
+                }
+            }
+            
+            contract B {
+                function pong() -> uint {
+                    return call A.ping();
+                }
+            }
+        "#).unwrap_err().to_string();
+        assert!(msg.contains("Recursive contract call: A -> B -> A"), "{}", msg);
+    }
+
+    #[test]
+    fn test_undefined_variable_reports_location() {
+        let err = analyze_source("contract Test {\n    function get() -> uint {\n        let x = 1;\n        return x + missing;\n    }\n}").unwrap_err();
+        
+        assert_eq!(err.span(), Some(Span { line: 4, col: 20 }));
+        let msg = err.to_string();
+        assert!(msg.contains("at line 4, column 20: Undefined variable: missing"), "{}", msg);
+    }
+
+    #[test]
+    fn test_statement_error_reports_statement_location() {
+        let err = analyze_source(r#"
+            contract Test {
+                function get(flag: bool) -> uint {
+                    if (flag) {
+                        return true;
+                    }
+                    return 0;
+                }
+            }
+        "#).unwrap_err();
+        
+        // The nested return is blamed, not the enclosing if
+        assert_eq!(err.span(), Some(Span { line: 5, col: 25 }));
+    }
 }
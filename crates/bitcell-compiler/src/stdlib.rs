@@ -28,6 +28,12 @@ pub mod memory {
     
     /// Temporary/stack memory start
     pub const STACK_START: u32 = 0x1000;
+    
+    /// Register spill frames for inter-contract calls, one per contract
+    pub const CALL_FRAMES_START: u32 = 0x1800;
+    
+    /// Words in a call frame: one per ZKVM register
+    pub const CALL_FRAME_SIZE: u32 = 32;
 }
 
 /// Common contract patterns
@@ -118,6 +124,14 @@ mod tests {
     use super::*;
     use crate::compile;
 
+    #[test]
+    fn test_memory_layout_fits_min_vm_memory() {
+        // The VM never shrinks memory below this, so the layout stays addressable
+        const { assert!(memory::STORAGE_START < memory::STACK_START) };
+        const { assert!(memory::STACK_START < memory::CALL_FRAMES_START) };
+        const { assert!((memory::CALL_FRAMES_START as usize) < bitcell_zkvm::MIN_MEMORY_LIMIT) };
+    }
+
     #[test]
     fn test_compile_token_contract() {
         let result = compile(patterns::TOKEN_CONTRACT);
@@ -43,6 +43,33 @@ impl BlockHeader {
         let serialized = bincode::serialize(self).expect("header serialization should never fail");
         Hash256::hash(&serialized)
     }
+
+    /// Check the consensus rules this header must satisfy on top of `parent`
+    ///
+    /// Covers height continuity, the `prev_hash` link, strictly increasing
+    /// timestamps and non-zero work. VRF proofs are not verified here.
+    pub fn validate_against_parent(&self, parent: &BlockHeader) -> crate::Result<()> {
+        if parent.height.checked_add(1) != Some(self.height) {
+            return Err(crate::Error::InvalidHeader(format!(
+                "height {} does not follow parent height {}",
+                self.height, parent.height
+            )));
+        }
+
+        if self.prev_hash != parent.hash() {
+            return Err(crate::Error::InvalidHeader("invalid parent hash".to_string()));
+        }
+
+        if self.timestamp <= parent.timestamp {
+            return Err(crate::Error::InvalidHeader("timestamp not increasing".to_string()));
+        }
+
+        if self.work == 0 {
+            return Err(crate::Error::InvalidHeader("header carries no work".to_string()));
+        }
+
+        Ok(())
+    }
 }
 
 /// Full block
@@ -83,6 +110,26 @@ impl Block {
     pub fn work(&self) -> u64 {
         self.header.work
     }
+
+    /// Check the block against gas and size limits
+    ///
+    /// Gas is the sum of the transactions' gas limits; size is the length of
+    /// the bincode-serialized block. Either may equal its limit.
+    pub fn validate_limits(&self, max_gas: u64, max_bytes: usize) -> crate::Result<()> {
+        let gas = self.transactions
+            .iter()
+            .try_fold(0u64, |total, tx| total.checked_add(tx.gas_limit));
+        if gas.is_none_or(|gas| gas > max_gas) {
+            return Err(crate::Error::InvalidBlock);
+        }
+
+        let size = bincode::serialized_size(self).map_err(|_| crate::Error::InvalidBlock)?;
+        if size > max_bytes as u64 {
+            return Err(crate::Error::InvalidBlock);
+        }
+
+        Ok(())
+    }
 }
 
 /// Transaction
@@ -109,6 +156,9 @@ pub struct Transaction {
     /// Transaction data
     pub data: Vec<u8>,
     
+    /// Notes a private spend consumes (empty otherwise)
+    pub spends: Vec<PrivateSpend>,
+    
     /// Signature
     pub signature: Signature,
 }
@@ -134,10 +184,38 @@ impl Transaction {
         data.extend_from_slice(&self.gas_limit.to_le_bytes());
         data.extend_from_slice(&self.gas_price.to_le_bytes());
         data.extend_from_slice(&self.data);
-        Hash256::hash(&data)
+        let hash = Hash256::hash(&data);
+        if self.spends.is_empty() {
+            return hash;
+        }
+        
+        // A private spend signs its spends, proofs included, on top of the
+        // transfer hash
+        let mut spend = hash.as_bytes().to_vec();
+        spend.extend(bincode::serialize(&self.spends).expect("spend serialization should never fail"));
+        Hash256::hash(&spend)
     }
 }
 
+/// A note consumed by a private spend
+///
+/// `proof` is a Groth16 proof of the state transition circuit over
+/// `(old_root, new_root, nullifier, commitment)`: the spender knows the note
+/// behind `nullifier`. Field elements are 32 little-endian bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivateSpend {
+    /// Nullifier (key image) of the consumed note
+    pub nullifier: [u8; 32],
+    /// Note tree root the consumed note is proven under
+    pub old_root: [u8; 32],
+    /// Note tree root once the new note replaces it
+    pub new_root: [u8; 32],
+    /// Commitment to the new note
+    pub commitment: [u8; 32],
+    /// Serialized spend proof
+    pub proof: Vec<u8>,
+}
+
 /// Battle proof (placeholder for ZK proof)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattleProof {
@@ -183,6 +261,88 @@ mod tests {
         assert_eq!(hash1, hash2);
     }
 
+    #[test]
+    fn test_validate_against_parent() {
+        let sk = SecretKey::generate();
+        let parent = BlockHeader {
+            height: 1,
+            prev_hash: Hash256::zero(),
+            tx_root: Hash256::zero(),
+            state_root: Hash256::zero(),
+            timestamp: 100,
+            proposer: sk.public_key(),
+            vrf_output: [0u8; 32],
+            vrf_proof: vec![],
+            work: 1000,
+        };
+        let child = BlockHeader {
+            height: 2,
+            prev_hash: parent.hash(),
+            timestamp: 110,
+            ..parent.clone()
+        };
+        assert!(child.validate_against_parent(&parent).is_ok());
+
+        let broken_link = BlockHeader { prev_hash: Hash256::zero(), ..child.clone() };
+        assert!(broken_link.validate_against_parent(&parent).is_err());
+
+        let stale = BlockHeader { timestamp: 100, ..child.clone() };
+        assert!(stale.validate_against_parent(&parent).is_err());
+
+        let skipped = BlockHeader { height: 3, ..child.clone() };
+        assert!(skipped.validate_against_parent(&parent).is_err());
+
+        let no_work = BlockHeader { work: 0, ..child };
+        assert!(no_work.validate_against_parent(&parent).is_err());
+    }
+
+    #[test]
+    fn test_validate_limits() {
+        let sk = SecretKey::generate();
+        let tx = Transaction {
+            nonce: 0,
+            from: sk.public_key(),
+            to: sk.public_key(),
+            amount: 1,
+            gas_limit: 21000,
+            gas_price: 1,
+            data: vec![],
+            spends: vec![],
+            signature: sk.sign(b"tx"),
+        };
+        let block = Block {
+            header: BlockHeader {
+                height: 1,
+                prev_hash: Hash256::zero(),
+                tx_root: Hash256::zero(),
+                state_root: Hash256::zero(),
+                timestamp: 0,
+                proposer: sk.public_key(),
+                vrf_output: [0u8; 32],
+                vrf_proof: vec![],
+                work: 1000,
+            },
+            transactions: vec![tx.clone(), tx],
+            battle_proofs: vec![],
+            signature: sk.sign(b"block"),
+            finality_votes: vec![],
+            finality_status: FinalityStatus::Pending,
+        };
+        let size = bincode::serialize(&block).unwrap().len();
+
+        // Exactly at both limits is accepted
+        assert!(block.validate_limits(42_000, size).is_ok());
+
+        // One unit over either limit is rejected
+        assert!(matches!(block.validate_limits(41_999, size), Err(crate::Error::InvalidBlock)));
+        assert!(matches!(block.validate_limits(42_000, size - 1), Err(crate::Error::InvalidBlock)));
+
+        // Summed gas that overflows u64 is rejected rather than wrapping
+        let mut overflowing = block.clone();
+        overflowing.transactions[0].gas_limit = u64::MAX;
+        assert!(overflowing.validate_limits(u64::MAX, usize::MAX).is_err());
+    }
+
     #[test]
     fn test_transaction_hash() {
         let sk = SecretKey::generate();
@@ -194,6 +354,7 @@ mod tests {
             gas_limit: 21000,
             gas_price: 1,
             data: vec![],
+            spends: vec![],
             signature: sk.sign(b"dummy"),
         };
 
@@ -216,6 +377,7 @@ mod tests {
             gas_limit: 21000,
             gas_price: 1000,
             data: vec![],
+            spends: vec![],
             signature: placeholder_sig,
         };
         
@@ -249,6 +411,7 @@ mod tests {
             gas_limit: 21000,
             gas_price: 1000,
             data: vec![],
+            spends: vec![],
             signature: sig1,
         };
         
@@ -260,6 +423,7 @@ mod tests {
             gas_limit: 21000,
             gas_price: 1000,
             data: vec![],
+            spends: vec![],
             signature: sig2,
         };
         
@@ -269,4 +433,46 @@ mod tests {
         // Full hashes should be different (signature included)
         assert_ne!(tx1.hash(), tx2.hash());
     }
+
+    #[test]
+    fn test_spends_signed_and_strictly_encoded() {
+        let sk = SecretKey::generate();
+        let mut tx = Transaction {
+            nonce: 1,
+            from: sk.public_key(),
+            to: sk.public_key(),
+            amount: 100,
+            gas_limit: 21000,
+            gas_price: 1000,
+            data: vec![1, 2, 3],
+            spends: vec![],
+            signature: sk.sign(b"placeholder"),
+        };
+        let plain_hash = tx.signing_hash();
+        
+        // The spends, proofs included, are covered by the signature
+        let spend = |nullifier: u8| PrivateSpend {
+            nullifier: [nullifier; 32],
+            old_root: [3u8; 32],
+            new_root: [4u8; 32],
+            commitment: [5u8; 32],
+            proof: vec![6u8; 8],
+        };
+        tx.spends = vec![spend(1), spend(2)];
+        assert_ne!(tx.signing_hash(), plain_hash);
+        tx.spends.pop();
+        let one_spend_hash = tx.signing_hash();
+        assert_ne!(one_spend_hash, plain_hash);
+        tx.spends[0].proof[0] ^= 1;
+        assert_ne!(tx.signing_hash(), one_spend_hash);
+        
+        // Spends round-trip, and a truncated one fails to decode
+        let encoded = bincode::serialize(&tx).unwrap();
+        let decoded: Transaction = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded.spends, tx.spends);
+        let spends_end = encoded.len() - bincode::serialized_size(&tx.signature).unwrap() as usize;
+        let mut truncated = encoded[..spends_end - 1].to_vec();
+        truncated.extend_from_slice(&encoded[spends_end..]);
+        assert!(bincode::deserialize::<Transaction>(&truncated).is_err());
+    }
 }
@@ -111,6 +111,35 @@ impl EquivocationEvidence {
         
         true
     }
+
+    /// The validator that double-signed
+    pub fn validator(&self) -> &PublicKey {
+        &self.vote1.validator
+    }
+
+    /// Whether `other` proves the same equivocation, in either vote order
+    fn same_offence(&self, other: &EquivocationEvidence) -> bool {
+        let hashes = |e: &EquivocationEvidence| {
+            let (a, b) = (e.vote1.block_hash, e.vote2.block_hash);
+            if a.as_bytes() <= b.as_bytes() { (a, b) } else { (b, a) }
+        };
+        self.vote1.validator == other.vote1.validator
+            && self.vote1.block_height == other.vote1.block_height
+            && self.vote1.round == other.vote1.round
+            && self.vote1.vote_type == other.vote1.vote_type
+            && hashes(self) == hashes(other)
+    }
+
+    /// Convert to EBSL evidence for `StateManager::submit_evidence`
+    ///
+    /// Submit it under `self.validator()`'s key bytes.
+    pub fn to_ebsl_evidence(&self, epoch: u64) -> bitcell_ebsl::Evidence {
+        bitcell_ebsl::Evidence::new(
+            bitcell_ebsl::EvidenceType::Equivocation,
+            epoch,
+            self.evidence_height,
+        )
+    }
 }
 
 /// Status of a block's finality
@@ -223,6 +252,10 @@ impl FinalityGadget {
     
     /// Add a vote and update finality status
     /// Returns Ok(()) if vote was processed, Err if equivocation detected
+    ///
+    /// A conflicting vote is never counted. Evidence is produced once per
+    /// equivocation: replaying an already-reported conflicting vote is
+    /// ignored.
     pub fn add_vote(&mut self, vote: FinalityVote) -> Result<(), EquivocationEvidence> {
         // Verify vote signature
         if !vote.verify() {
@@ -253,10 +286,13 @@ impl FinalityGadget {
                         evidence_height: vote.block_height,
                     };
                     
-                    // Record equivocation
-                    self.equivocations.entry(vote.validator.clone())
-                        .or_insert_with(Vec::new)
-                        .push(evidence.clone());
+                    // Record equivocation, unless already reported
+                    let recorded = self.equivocations.entry(vote.validator)
+                        .or_default();
+                    if recorded.iter().any(|e| e.same_offence(&evidence)) {
+                        return Ok(());
+                    }
+                    recorded.push(evidence.clone());
                     
                     return Err(evidence);
                 } else {
@@ -482,6 +518,33 @@ mod tests {
         assert_eq!(evidence.vote2.block_hash, block_hash2);
     }
     
+    #[test]
+    fn test_equivocation_reported_once() {
+        let (keys, stakes) = create_test_validators(1);
+        let mut gadget = FinalityGadget::new(stakes);
+        let validator = keys[0].public_key();
+        
+        let vote1 = create_vote(&keys[0], Hash256::hash(b"block 1"), 7, VoteType::Precommit, 0);
+        let vote2 = create_vote(&keys[0], Hash256::hash(b"block 2"), 7, VoteType::Precommit, 0);
+        gadget.add_vote(vote1.clone()).unwrap();
+        
+        let evidence = gadget.add_vote(vote2.clone()).unwrap_err();
+        assert_eq!(evidence.validator(), &validator);
+        
+        // Replaying either vote does not produce the evidence again
+        assert!(gadget.add_vote(vote2).is_ok());
+        assert!(gadget.add_vote(vote1).is_ok());
+        assert_eq!(gadget.get_validator_equivocations(&validator).len(), 1);
+        
+        // The conflicting vote never counted toward its block
+        assert!(gadget.get_vote_stats(&Hash256::hash(b"block 2")).is_none());
+        
+        let ebsl = evidence.to_ebsl_evidence(3);
+        assert_eq!(ebsl.evidence_type, bitcell_ebsl::EvidenceType::Equivocation);
+        assert_eq!(ebsl.epoch, 3);
+        assert_eq!(ebsl.block_height, 7);
+    }
+    
     #[test]
     fn test_equivocation_different_rounds_ok() {
         let (keys, stakes) = create_test_validators(1);
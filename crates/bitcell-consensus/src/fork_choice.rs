@@ -15,6 +15,31 @@ pub struct ChainState {
     
     /// Chain tips
     pub tips: Vec<Hash256>,
+
+    /// Tip of the currently selected chain
+    pub head: Option<Hash256>,
+}
+
+/// Effect of a header on the selected chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReorgOutcome {
+    /// The header was already known
+    Duplicate,
+    /// The header's parent is unknown; it is not stored
+    Orphan,
+    /// The header is on a branch no heavier than the selected chain
+    SideBranch,
+    /// The header extends the selected chain and is the new head
+    Extended(Hash256),
+    /// A competing branch became heavier and its tip is the new head
+    ///
+    /// `reverted` lists the abandoned blocks from the old head back toward
+    /// the common ancestor (the order to undo them in); `applied` lists the
+    /// new branch from just after the ancestor up to the new head.
+    Reorg {
+        reverted: Vec<Hash256>,
+        applied: Vec<Hash256>,
+    },
 }
 
 impl ChainState {
@@ -23,6 +48,7 @@ impl ChainState {
             blocks: HashMap::new(),
             headers: HashMap::new(),
             tips: Vec::new(),
+            head: None,
         }
     }
 
@@ -56,6 +82,70 @@ impl ChainState {
         work
     }
 
+    /// Add a header and update the selected chain
+    ///
+    /// The head moves only to a strictly heavier chain, so on equal work
+    /// the chain seen first is kept.
+    pub fn apply_header(&mut self, header: BlockHeader) -> ReorgOutcome {
+        let hash = header.hash();
+        if self.headers.contains_key(&hash) {
+            return ReorgOutcome::Duplicate;
+        }
+        let prev_hash = header.prev_hash;
+        if header.height != 0 && !self.headers.contains_key(&prev_hash) {
+            return ReorgOutcome::Orphan;
+        }
+        self.headers.insert(hash, header);
+
+        self.tips.retain(|tip| *tip != prev_hash);
+        self.tips.push(hash);
+
+        let old_head = match self.head {
+            Some(head) => head,
+            None => {
+                self.head = Some(hash);
+                return ReorgOutcome::Extended(hash);
+            }
+        };
+        if self.chain_work(hash) <= self.chain_work(old_head) {
+            return ReorgOutcome::SideBranch;
+        }
+
+        self.head = Some(hash);
+        if prev_hash == old_head {
+            return ReorgOutcome::Extended(hash);
+        }
+
+        let (reverted, mut applied) = self.diverging_branches(old_head, hash);
+        applied.reverse();
+        ReorgOutcome::Reorg { reverted, applied }
+    }
+
+    /// Walk back from `a` and `b` to their common ancestor
+    ///
+    /// Returns the hashes passed on each side, tip first, excluding the
+    /// ancestor itself.
+    fn diverging_branches(&self, mut a: Hash256, mut b: Hash256) -> (Vec<Hash256>, Vec<Hash256>) {
+        let height = |hash: &Hash256| self.headers.get(hash).map(|h| h.height);
+        let parent = |hash: &Hash256| self.headers.get(hash).map(|h| h.prev_hash);
+        let (mut from_a, mut from_b) = (Vec::new(), Vec::new());
+
+        while a != b {
+            let (Some(height_a), Some(height_b)) = (height(&a), height(&b)) else {
+                break;
+            };
+            if height_a >= height_b {
+                from_a.push(a);
+                a = parent(&a).unwrap_or(a);
+            }
+            if height_b >= height_a {
+                from_b.push(b);
+                b = parent(&b).unwrap_or(b);
+            }
+        }
+        (from_a, from_b)
+    }
+
     /// Select the heaviest chain tip
     pub fn best_tip(&self) -> Option<Hash256> {
         self.tips
@@ -147,4 +237,52 @@ mod tests {
         let best = state.best_tip().unwrap();
         assert_eq!(best, block1b_hash);
     }
+
+    #[test]
+    fn test_apply_header_reorg() {
+        let mut state = ChainState::new();
+        let genesis = create_test_block(0, Hash256::zero(), 100).header;
+        let genesis_hash = genesis.hash();
+        assert_eq!(state.apply_header(genesis.clone()), ReorgOutcome::Extended(genesis_hash));
+        assert_eq!(state.apply_header(genesis), ReorgOutcome::Duplicate);
+
+        // Branch A: genesis <- a1 <- a2 <- a3
+        let mut branch_a = Vec::new();
+        let mut prev = genesis_hash;
+        for height in 1..=3 {
+            let header = create_test_block(height, prev, 100).header;
+            prev = header.hash();
+            assert_eq!(state.apply_header(header), ReorgOutcome::Extended(prev));
+            branch_a.push(prev);
+        }
+
+        // Branch B forks after a1 and carries more work per block
+        let b2 = create_test_block(2, branch_a[0], 120).header;
+        let b2_hash = b2.hash();
+        assert_eq!(state.apply_header(b2), ReorgOutcome::SideBranch);
+
+        // b3 ties branch A (100+100+120+80 vs 400): the head stays put
+        let b3 = create_test_block(3, b2_hash, 80).header;
+        let b3_hash = b3.hash();
+        assert_eq!(state.apply_header(b3), ReorgOutcome::SideBranch);
+        assert_eq!(state.head, Some(branch_a[2]));
+
+        // b4 overtakes: a3 and a2 are reverted, b2..b4 applied in order
+        let b4 = create_test_block(4, b3_hash, 100).header;
+        let b4_hash = b4.hash();
+        assert_eq!(
+            state.apply_header(b4),
+            ReorgOutcome::Reorg {
+                reverted: vec![branch_a[2], branch_a[1]],
+                applied: vec![b2_hash, b3_hash, b4_hash],
+            }
+        );
+        assert_eq!(state.head, Some(b4_hash));
+        assert_eq!(state.best_tip(), Some(b4_hash));
+
+        // A header with an unknown parent is not stored
+        let orphan = create_test_block(9, Hash256::hash(b"unknown"), 1_000).header;
+        assert_eq!(state.apply_header(orphan), ReorgOutcome::Orphan);
+        assert_eq!(state.head, Some(b4_hash));
+    }
 }
@@ -13,9 +13,9 @@ pub mod fork_choice;
 pub mod orchestrator;
 pub mod finality;
 
-pub use block::{Block, BlockHeader, Transaction, BattleProof};
+pub use block::{Block, BlockHeader, Transaction, PrivateSpend, BattleProof};
 pub use tournament::{Tournament, TournamentPhase, GliderCommitment, GliderReveal, TournamentMatch};
-pub use fork_choice::ChainState;
+pub use fork_choice::{ChainState, ReorgOutcome};
 pub use orchestrator::TournamentOrchestrator;
 pub use finality::{FinalityGadget, FinalityVote, FinalityStatus, VoteType, EquivocationEvidence};
 
@@ -26,6 +26,9 @@ pub enum Error {
     #[error("Invalid block")]
     InvalidBlock,
     
+    #[error("Invalid header: {0}")]
+    InvalidHeader(String),
+    
     #[error("Tournament error: {0}")]
     TournamentError(String),
     
@@ -23,6 +23,9 @@ pub struct TournamentOrchestrator {
     /// Block time in seconds
     pub block_time: u64,
     
+    /// CA steps simulated per battle
+    pub battle_steps: usize,
+    
     /// Global tournament metrics
     pub metrics: TournamentMetrics,
 }
@@ -53,6 +56,7 @@ impl TournamentOrchestrator {
             ebsl_params: EbslParams::default(),
             miner_evidence: HashMap::new(),
             block_time: 600, // 10 minutes
+            battle_steps: bitcell_ca::battle::BATTLE_STEPS,
             metrics: TournamentMetrics::default(),
         }
     }
@@ -62,6 +66,9 @@ impl TournamentOrchestrator {
         if self.tournament.phase != TournamentPhase::Commit {
             return Err(Error::TournamentError("Not in commit phase".to_string()));
         }
+        if self.tournament.commitments.iter().any(|c| c.commitment == commitment.commitment) {
+            return Err(Error::TournamentError("Duplicate commitment".to_string()));
+        }
 
         self.tournament.commitments.push(commitment);
         Ok(())
@@ -78,12 +85,26 @@ impl TournamentOrchestrator {
     }
 
     /// Process reveal
+    ///
+    /// The reveal must open a commitment made for its miner's key. Each
+    /// commitment is consumed when opened, and each miner reveals once.
     pub fn process_reveal(&mut self, reveal: GliderReveal) -> Result<()> {
         if self.tournament.phase != TournamentPhase::Reveal {
             return Err(Error::TournamentError("Not in reveal phase".to_string()));
         }
+        if self.tournament.reveals.iter().any(|r| r.miner == reveal.miner) {
+            return Err(Error::TournamentError("Miner has already revealed".to_string()));
+        }
 
-        // Verify reveal matches commitment (simplified)
+        // Commitments are anonymous, so the reveal only has to open one of them
+        let opened = reveal.commitment();
+        let Some(index) = self.tournament.commitments.iter().position(|c| c.commitment == opened) else {
+            return Err(Error::TournamentError(
+                "Reveal does not match any commitment".to_string()
+            ));
+        };
+        self.tournament.commitments.swap_remove(index);
+        
         self.tournament.reveals.push(reveal);
         Ok(())
     }
@@ -145,9 +166,9 @@ impl TournamentOrchestrator {
                     
                     // Configure battle
                     let battle = if is_final {
-                        bitcell_ca::Battle::with_history(p_a.glider.clone(), p_b.glider.clone(), 1000, entropy)
+                        bitcell_ca::Battle::with_history(p_a.glider.clone(), p_b.glider.clone(), self.battle_steps, entropy)
                     } else {
-                        bitcell_ca::Battle::with_entropy(p_a.glider.clone(), p_b.glider.clone(), 1000, entropy)
+                        bitcell_ca::Battle::with_entropy(p_a.glider.clone(), p_b.glider.clone(), self.battle_steps, entropy)
                     };
                     
                     // Run simulation
@@ -311,7 +332,10 @@ impl TournamentOrchestrator {
     /// Record evidence for a miner
     pub fn record_evidence(&mut self, miner: PublicKey, evidence_type: EvidenceType) {
         let counters = self.miner_evidence.entry(miner).or_insert_with(EvidenceCounters::new);
-        counters.add_evidence(Evidence::new(evidence_type, 0, self.tournament.height));
+        counters.add_evidence_with_params(
+            Evidence::new(evidence_type, 0, self.tournament.height),
+            &self.ebsl_params,
+        );
     }
 
     /// Check if miner is eligible based on EBSL
@@ -364,6 +388,98 @@ mod tests {
         assert!(counters.r > 0.0);
     }
 
+    #[test]
+    fn test_evidence_uses_configured_weights() {
+        let pk = SecretKey::generate().public_key();
+        let mut orch = TournamentOrchestrator::new(1, vec![pk], Hash256::zero());
+        orch.ebsl_params = EbslParams::default().with_weight(EvidenceType::InvalidBlock, 3.0);
+
+        orch.record_evidence(pk, EvidenceType::InvalidBlock);
+
+        assert_eq!(orch.miner_evidence.get(&pk).unwrap().s, 3.0);
+    }
+
+    #[test]
+    fn test_reveal_must_open_commitment() {
+        use bitcell_ca::{Glider, GliderPattern, Position};
+        
+        let pk = SecretKey::generate().public_key();
+        let mut orch = TournamentOrchestrator::new(1, vec![pk], Hash256::zero());
+        
+        let committed = Glider::new(GliderPattern::Standard, Position::new(10, 10));
+        orch.process_commit(GliderCommitment::new(&committed, b"nonce", &pk, 1)).unwrap();
+        orch.advance_to_reveal().unwrap();
+        
+        // Swapping the glider after committing is rejected
+        let swapped = GliderReveal {
+            glider: Glider::new(GliderPattern::Heavyweight, Position::new(10, 10)),
+            nonce: b"nonce".to_vec(),
+            miner: pk,
+        };
+        assert!(orch.process_reveal(swapped).is_err());
+        assert!(orch.tournament.reveals.is_empty());
+        
+        let honest = GliderReveal {
+            glider: committed,
+            nonce: b"nonce".to_vec(),
+            miner: pk,
+        };
+        orch.process_reveal(honest).unwrap();
+        assert_eq!(orch.tournament.reveals.len(), 1);
+    }
+
+    #[test]
+    fn test_reveal_replay_by_other_miner_rejected() {
+        use bitcell_ca::{Glider, GliderPattern, Position};
+        
+        let honest = SecretKey::generate().public_key();
+        let copycat = SecretKey::generate().public_key();
+        let mut orch = TournamentOrchestrator::new(1, vec![honest, copycat], Hash256::zero());
+        
+        let glider = Glider::new(GliderPattern::Standard, Position::new(10, 10));
+        orch.process_commit(GliderCommitment::new(&glider, b"nonce", &honest, 1)).unwrap();
+        orch.advance_to_reveal().unwrap();
+        
+        // Once the honest reveal is public, another miner replays it under its own key
+        let reveal = GliderReveal {
+            glider: glider.clone(),
+            nonce: b"nonce".to_vec(),
+            miner: honest,
+        };
+        let replay = GliderReveal { miner: copycat, ..reveal.clone() };
+        assert!(orch.process_reveal(replay.clone()).is_err());
+        
+        orch.process_reveal(reveal).unwrap();
+        // The commitment is consumed, so replaying afterwards fails too
+        assert!(orch.tournament.commitments.is_empty());
+        assert!(orch.process_reveal(replay).is_err());
+        assert_eq!(orch.tournament.reveals.len(), 1);
+    }
+
+    #[test]
+    fn test_duplicate_reveal_rejected() {
+        use bitcell_ca::{Glider, GliderPattern, Position};
+        
+        let pk = SecretKey::generate().public_key();
+        let mut orch = TournamentOrchestrator::new(1, vec![pk], Hash256::zero());
+        
+        // Two commitments from the same miner cannot buy two reveals
+        let first = Glider::new(GliderPattern::Standard, Position::new(10, 10));
+        let second = Glider::new(GliderPattern::Heavyweight, Position::new(10, 10));
+        orch.process_commit(GliderCommitment::new(&first, b"a", &pk, 1)).unwrap();
+        orch.process_commit(GliderCommitment::new(&second, b"b", &pk, 1)).unwrap();
+        assert!(orch.process_commit(GliderCommitment::new(&first, b"a", &pk, 1)).is_err());
+        orch.advance_to_reveal().unwrap();
+        
+        let reveal = GliderReveal { glider: first, nonce: b"a".to_vec(), miner: pk };
+        orch.process_reveal(reveal.clone()).unwrap();
+        assert!(orch.process_reveal(reveal).is_err());
+        
+        let other = GliderReveal { glider: second, nonce: b"b".to_vec(), miner: pk };
+        assert!(orch.process_reveal(other).is_err());
+        assert_eq!(orch.tournament.reveals.len(), 1);
+    }
+
     #[test]
     fn test_full_tournament_flow() {
         use bitcell_ca::{Glider, GliderPattern, Position};
@@ -384,24 +500,22 @@ mod tests {
         let seed = Hash256::hash(b"tournament_seed");
         let mut orch = TournamentOrchestrator::new(100, miners.clone(), seed);
         
+        let gliders: Vec<Glider> = (0..miners.len())
+            .map(|i| Glider::new(GliderPattern::Standard, Position::new(i * 10, i * 10)))
+            .collect();
+        
         // Commit phase
-        for miner in &miners {
-            let commit = GliderCommitment {
-                commitment: Hash256::zero(),
-                ring_signature: vec![],
-                height: 100,
-            };
-            orch.process_commit(commit).unwrap();
+        for (glider, miner) in gliders.iter().zip(&miners) {
+            orch.process_commit(GliderCommitment::new(glider, b"nonce", miner, 100)).unwrap();
         }
         
         orch.advance_to_reveal().unwrap();
         
         // Reveal phase
-        for (i, miner) in miners.iter().enumerate() {
-            let glider = Glider::new(GliderPattern::Standard, Position::new(i * 10, i * 10));
+        for (glider, miner) in gliders.iter().zip(&miners) {
             let reveal = GliderReveal {
-                glider,
-                nonce: vec![],
+                glider: glider.clone(),
+                nonce: b"nonce".to_vec(),
                 miner: *miner,
             };
             orch.process_reveal(reveal).unwrap();
@@ -446,20 +560,20 @@ mod tests {
         let mut orch = TournamentOrchestrator::new(100, miners.clone(), Hash256::zero());
         
         // Setup tournament that will trigger adaptive strategy
+        let gliders: Vec<Glider> = (0..miners.len())
+            .map(|i| Glider::new(GliderPattern::Standard, Position::new(i*10, i*10)))
+            .collect();
+        
         // Commit
-        for miner in &miners {
-            orch.process_commit(GliderCommitment {
-                commitment: Hash256::zero(),
-                ring_signature: vec![],
-                height: 100,
-            }).unwrap();
+        for (glider, miner) in gliders.iter().zip(&miners) {
+            orch.process_commit(GliderCommitment::new(glider, &[], miner, 100)).unwrap();
         }
         orch.advance_to_reveal().unwrap();
         
         // Reveal
-        for (i, miner) in miners.iter().enumerate() {
+        for (glider, miner) in gliders.iter().zip(&miners) {
             orch.process_reveal(GliderReveal {
-                glider: Glider::new(GliderPattern::Standard, Position::new(i*10, i*10)),
+                glider: glider.clone(),
                 nonce: vec![],
                 miner: *miner,
             }).unwrap();
@@ -23,7 +23,7 @@ pub enum TournamentPhase {
 /// Glider commitment
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GliderCommitment {
-    /// Hash of (glider_pattern || nonce)
+    /// Hash of (glider || miner || nonce), see [`GliderCommitment::hash_glider`]
     pub commitment: Hash256,
     
     /// Ring signature (anonymous)
@@ -46,6 +46,41 @@ pub struct GliderReveal {
     pub miner: PublicKey,
 }
 
+impl GliderCommitment {
+    /// Commit `miner` to `glider` under a secret `nonce` (without a ring signature)
+    pub fn new(glider: &Glider, nonce: &[u8], miner: &PublicKey, height: u64) -> Self {
+        Self {
+            commitment: Self::hash_glider(glider, nonce, miner),
+            ring_signature: vec![],
+            height,
+        }
+    }
+    
+    /// Hash binding a glider's pattern, position and energy and the
+    /// committing miner's key to a nonce
+    ///
+    /// Including the key stops another miner from revealing (replaying) a
+    /// commitment it did not make; the nonce keeps the key hidden until the
+    /// reveal.
+    pub fn hash_glider(glider: &Glider, nonce: &[u8], miner: &PublicKey) -> Hash256 {
+        Hash256::hash_multiple(&[
+            &glider.pattern.to_bytes(),
+            &(glider.position.x as u64).to_le_bytes(),
+            &(glider.position.y as u64).to_le_bytes(),
+            &[glider.energy],
+            miner.as_bytes(),
+            nonce,
+        ])
+    }
+}
+
+impl GliderReveal {
+    /// The commitment hash this reveal opens
+    pub fn commitment(&self) -> Hash256 {
+        GliderCommitment::hash_glider(&self.glider, &self.nonce, &self.miner)
+    }
+}
+
 /// A single match in the tournament
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TournamentMatch {
@@ -154,6 +189,34 @@ mod tests {
         assert!(!tournament.is_complete());
     }
 
+    #[test]
+    fn test_commitment_binds_glider() {
+        use bitcell_ca::{GliderPattern, Position};
+        
+        let glider = Glider::new(GliderPattern::Standard, Position::new(10, 20));
+        let miner = SecretKey::generate().public_key();
+        let commitment = GliderCommitment::new(&glider, b"nonce", &miner, 5);
+        
+        let reveal = GliderReveal {
+            glider: glider.clone(),
+            nonce: b"nonce".to_vec(),
+            miner,
+        };
+        assert_eq!(reveal.commitment(), commitment.commitment);
+        
+        let moved = GliderReveal {
+            glider: Glider::new(GliderPattern::Standard, Position::new(11, 20)),
+            ..reveal.clone()
+        };
+        assert_ne!(moved.commitment(), commitment.commitment);
+        
+        let other_miner = GliderReveal { miner: SecretKey::generate().public_key(), ..reveal.clone() };
+        assert_ne!(other_miner.commitment(), commitment.commitment);
+        
+        let other_nonce = GliderReveal { nonce: b"other".to_vec(), ..reveal };
+        assert_ne!(other_nonce.commitment(), commitment.commitment);
+    }
+
     #[test]
     fn test_tournament_phases() {
         let mut tournament = Tournament::new(1, vec![], Hash256::zero());
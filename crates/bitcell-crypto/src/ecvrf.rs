@@ -140,6 +140,29 @@ pub struct EcvrfProof {
 }
 
 impl EcvrfProof {
+    /// Serialized size: Gamma, challenge and response, 32 bytes each
+    pub const SIZE: usize = 96;
+
+    /// Serialize as `gamma || c || s`
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        bytes[..32].copy_from_slice(&self.gamma);
+        bytes[32..64].copy_from_slice(&self.c);
+        bytes[64..].copy_from_slice(&self.s);
+        bytes
+    }
+
+    /// Deserialize from `gamma || c || s`
+    ///
+    /// The components are only checked when the proof is verified.
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Self {
+        let mut proof = Self { gamma: [0u8; 32], c: [0u8; 32], s: [0u8; 32] };
+        proof.gamma.copy_from_slice(&bytes[..32]);
+        proof.c.copy_from_slice(&bytes[32..64]);
+        proof.s.copy_from_slice(&bytes[64..]);
+        proof
+    }
+
     /// Verify the ECVRF proof and recover the output
     pub fn verify(&self, public_key: &EcvrfPublicKey, alpha: &[u8]) -> Result<EcvrfOutput> {
         // Decompress Gamma
@@ -1,8 +1,10 @@
 //! Hash functions for BitCell
 //!
-//! Provides SHA-256 for general use and Blake3 for performance-critical paths.
-//! Poseidon will be added for circuit-friendly hashing.
+//! Provides SHA-256 for general use and Blake3 for performance-critical paths,
+//! plus the canonical circuit-friendly Poseidon hash over BN254.
 
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::fmt;
@@ -44,6 +46,33 @@ impl Hash256 {
     }
 }
 
+/// Canonical Poseidon hash over a variable number of BN254 field elements
+///
+/// Uses the same permutation and parameters as the zkp Poseidon gadgets. The
+/// element count is absorbed first, so `[x]` and `[x, 0]` hash differently;
+/// in-circuit callers must prepend the same length. For the fixed-arity 2-to-1
+/// compression used by Merkle nodes, use [`crate::poseidon_hash_two`].
+pub fn poseidon_hash(inputs: &[Fr]) -> Fr {
+    let mut prefixed = Vec::with_capacity(1 + inputs.len());
+    prefixed.push(Fr::from(inputs.len() as u64));
+    prefixed.extend_from_slice(inputs);
+    crate::poseidon::poseidon_hash_many(&prefixed)
+}
+
+/// Poseidon hash of arbitrary bytes
+///
+/// The data is length-prefixed and packed into 31-byte little-endian field
+/// elements; the output field element is encoded as 32 little-endian bytes.
+pub fn poseidon_hash_bytes(data: &[u8]) -> Hash256 {
+    let mut inputs = Vec::with_capacity(1 + data.len().div_ceil(31));
+    inputs.push(Fr::from(data.len() as u64));
+    inputs.extend(data.chunks(31).map(Fr::from_le_bytes_mod_order));
+    
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&crate::poseidon::poseidon_hash_many(&inputs).into_bigint().to_bytes_le());
+    Hash256(bytes)
+}
+
 impl fmt::Debug for Hash256 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Hash256({})", hex::encode(&self.0[..8]))
@@ -127,4 +156,27 @@ mod tests {
         let h3 = Hash256::hash_multiple(&[b"world", b"hello"]);
         assert_ne!(h1, h3);
     }
+
+    #[test]
+    fn test_poseidon_hash_is_length_prefixed() {
+        let (a, b) = (Fr::from(1u64), Fr::from(2u64));
+        assert_eq!(
+            poseidon_hash(&[a, b]),
+            crate::poseidon::poseidon_hash_many(&[Fr::from(2u64), a, b])
+        );
+        
+        // Inputs that only differ by trailing zeros stay apart
+        assert_ne!(poseidon_hash(&[a]), poseidon_hash(&[a, Fr::from(0u64)]));
+        assert_ne!(poseidon_hash(&[]), poseidon_hash(&[Fr::from(0u64)]));
+    }
+
+    #[test]
+    fn test_poseidon_hash_bytes() {
+        assert_eq!(poseidon_hash_bytes(b"bitcell"), poseidon_hash_bytes(b"bitcell"));
+        assert_ne!(poseidon_hash_bytes(b"bitcell"), poseidon_hash_bytes(b"bitcel"));
+        
+        // Length prefix separates inputs that only differ by trailing zeros
+        assert_ne!(poseidon_hash_bytes(&[1]), poseidon_hash_bytes(&[1, 0]));
+        assert_ne!(poseidon_hash_bytes(&[]), poseidon_hash_bytes(&[0]));
+    }
 }
@@ -17,17 +17,17 @@ pub mod merkle;
 pub mod ring;
 pub mod clsag;
 
-pub use hash::{Hash256, Hashable};
+pub use hash::{Hash256, Hashable, poseidon_hash, poseidon_hash_bytes};
 pub use poseidon::{
     PoseidonParams, PoseidonHasher, poseidon_bn254,
     poseidon_hash_two, poseidon_hash_one, poseidon_hash_many,
 };
-pub use signature::{PublicKey, SecretKey, Signature};
+pub use signature::{PublicKey, SecretKey, Signature, verify_batch};
 pub use vrf::{VrfProof, VrfOutput};
 pub use ecvrf::{EcvrfSecretKey, EcvrfPublicKey, EcvrfProof, EcvrfOutput, combine_ecvrf_outputs};
 pub use clsag::{ClsagSecretKey, ClsagPublicKey, ClsagSignature, KeyImage};
 pub use commitment::PedersenCommitment;
-pub use merkle::MerkleTree;
+pub use merkle::{MerkleTree, MultiProof, account_leaf};
 
 /// Standard result type for cryptographic operations
 pub type Result<T> = std::result::Result<T, Error>;
@@ -86,6 +86,94 @@ impl MerkleTree {
         })
     }
 
+    /// Generate one proof covering all leaves at `indices`
+    ///
+    /// Duplicate indices are ignored. Returns `None` if `indices` is empty or
+    /// any index is out of range.
+    pub fn multi_proof(&self, indices: &[usize]) -> Option<MultiProof> {
+        let mut known: Vec<usize> = indices.to_vec();
+        known.sort_unstable();
+        known.dedup();
+        if known.is_empty() || known.iter().any(|&i| i >= self.leaves.len()) {
+            return None;
+        }
+        let proven = known.clone();
+
+        let mut hashes = Vec::new();
+        for level in &self.nodes[..self.nodes.len() - 1] {
+            let mut next = Vec::new();
+            let mut k = 0;
+            while k < known.len() {
+                let index = known[k];
+                let sibling = index ^ 1;
+                if index % 2 == 0 && known.get(k + 1) == Some(&sibling) {
+                    // Both children are known
+                    k += 1;
+                } else if sibling < level.len() {
+                    hashes.push(level[sibling]);
+                }
+                // Otherwise the node is duplicated and needs no sibling
+                next.push(index / 2);
+                k += 1;
+            }
+            known = next;
+        }
+
+        Some(MultiProof {
+            leaf_count: self.leaves.len(),
+            indices: proven,
+            hashes,
+        })
+    }
+
+    /// Verify a multi-proof; `leaves` are the leaf hashes in `proof.indices` order
+    pub fn verify_multi_proof(root: Hash256, leaves: &[Hash256], proof: &MultiProof) -> bool {
+        if leaves.is_empty() || leaves.len() != proof.indices.len() {
+            return false;
+        }
+        if proof.indices.windows(2).any(|w| w[0] >= w[1])
+            || proof.indices.last().is_some_and(|&i| i >= proof.leaf_count)
+        {
+            return false;
+        }
+
+        let mut level: Vec<(usize, Hash256)> = proof.indices.iter().copied().zip(leaves.iter().copied()).collect();
+        let mut hashes = proof.hashes.iter();
+        let mut level_len = proof.leaf_count;
+
+        while level_len > 1 {
+            let mut next = Vec::new();
+            let mut k = 0;
+            while k < level.len() {
+                let (index, hash) = level[k];
+                let sibling_index = index ^ 1;
+                let sibling = if index % 2 == 0 && level.get(k + 1).map(|n| n.0) == Some(sibling_index) {
+                    k += 1;
+                    level[k].1
+                } else if sibling_index >= level_len {
+                    hash // Duplicated odd node
+                } else {
+                    match hashes.next() {
+                        Some(sibling) => *sibling,
+                        None => return false,
+                    }
+                };
+
+                let parent = if index % 2 == 0 {
+                    hash_pair(&hash, &sibling)
+                } else {
+                    hash_pair(&sibling, &hash)
+                };
+                next.push((index / 2, parent));
+                k += 1;
+            }
+            level = next;
+            level_len = level_len.div_ceil(2);
+        }
+
+        hashes.next().is_none() && level.len() == 1 && level[0].1 == root
+    }
+
     /// Verify a Merkle proof against a root
     pub fn verify_proof(root: Hash256, proof: &MerkleProof) -> bool {
         let mut current = proof.leaf;
@@ -112,6 +200,32 @@ pub struct MerkleProof {
     pub path: Vec<Hash256>,
 }
 
+/// Inclusion proof for several leaves at once
+///
+/// Internal nodes that can be recomputed from the proven leaves are left
+/// out, so overlapping paths are only paid for once.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MultiProof {
+    /// Number of leaves in the tree
+    pub leaf_count: usize,
+    /// Proven leaf indices, strictly increasing
+    pub indices: Vec<usize>,
+    /// Sibling hashes the verifier cannot derive, level by level from the leaves
+    pub hashes: Vec<Hash256>,
+}
+
+/// State tree leaf for an account: hash(pubkey || balance_le || nonce_le)
+///
+/// Shared by the state manager that builds the tree and light clients that
+/// verify proofs against its root.
+pub fn account_leaf(pubkey: &[u8], balance: u64, nonce: u64) -> Hash256 {
+    Hash256::hash_multiple(&[pubkey, &balance.to_le_bytes(), &nonce.to_le_bytes()])
+}
+
+fn hash_pair(left: &Hash256, right: &Hash256) -> Hash256 {
+    Hash256::hash_multiple(&[left.as_bytes(), right.as_bytes()])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,6 +284,44 @@ mod tests {
         assert!(!MerkleTree::verify_proof(root, &proof));
     }
 
+    #[test]
+    fn test_multi_proof_smaller_than_single_proofs() {
+        let leaves: Vec<Hash256> = (0..8u8).map(|i| Hash256::hash(&[i])).collect();
+        let tree = MerkleTree::new(leaves.clone());
+        let root = tree.root();
+        let indices = [1, 2, 5];
+
+        let singles: Vec<MerkleProof> = indices.iter().map(|&i| tree.prove(i).unwrap()).collect();
+        assert!(singles.iter().all(|p| MerkleTree::verify_proof(root, p)));
+
+        let multi = tree.multi_proof(&indices).unwrap();
+        let proven: Vec<Hash256> = indices.iter().map(|&i| leaves[i]).collect();
+        assert!(MerkleTree::verify_multi_proof(root, &proven, &multi));
+
+        let single_size: usize = singles.iter().map(|p| p.path.len()).sum();
+        assert!(multi.hashes.len() < single_size);
+
+        // A wrong leaf fails
+        let mut tampered = proven.clone();
+        tampered[1] = Hash256::hash(b"wrong");
+        assert!(!MerkleTree::verify_multi_proof(root, &tampered, &multi));
+    }
+
+    #[test]
+    fn test_multi_proof_odd_tree() {
+        let leaves: Vec<Hash256> = (0..5u8).map(|i| Hash256::hash(&[i])).collect();
+        let tree = MerkleTree::new(leaves.clone());
+
+        for indices in [vec![4], vec![0, 4], vec![3, 4], vec![0, 1, 2, 3, 4]] {
+            let multi = tree.multi_proof(&indices).unwrap();
+            let proven: Vec<Hash256> = indices.iter().map(|&i| leaves[i]).collect();
+            assert!(MerkleTree::verify_multi_proof(tree.root(), &proven, &multi));
+        }
+
+        assert!(tree.multi_proof(&[]).is_none());
+        assert!(tree.multi_proof(&[5]).is_none());
+    }
+
     #[test]
     fn test_empty_tree() {
         let tree = MerkleTree::new(vec![]);
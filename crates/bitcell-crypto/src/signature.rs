@@ -7,7 +7,10 @@ use k256::ecdsa::{
     signature::{Signer, Verifier},
     Signature as K256Signature, SigningKey, VerifyingKey,
 };
+use k256::elliptic_curve::subtle::ConstantTimeEq;
 use rand::rngs::OsRng;
+use rayon::prelude::*;
+use zeroize::ZeroizeOnDrop;
 
 use std::fmt;
 
@@ -66,8 +69,21 @@ impl fmt::Display for PublicKey {
 }
 
 /// ECDSA secret key
+///
+/// The key material is wiped when the key is dropped (each clone wipes its
+/// own copy). Compare keys with [`SecretKey::ct_eq`], never via their bytes.
+#[derive(Clone)]
 pub struct SecretKey(SigningKey);
 
+// `SigningKey` zeroizes its scalar in its own `Drop`, which runs when a
+// `SecretKey` is dropped; this fails to compile if that ever stops holding.
+const _: fn() = || {
+    fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+    assert_zeroize_on_drop::<SigningKey>();
+};
+
+impl ZeroizeOnDrop for SecretKey {}
+
 impl SecretKey {
     /// Generate a new random key pair
     pub fn generate() -> Self {
@@ -98,6 +114,11 @@ impl SecretKey {
         Signature(sig.to_bytes().into())
     }
 
+    /// Compare two secret keys in constant time
+    pub fn ct_eq(&self, other: &SecretKey) -> bool {
+        self.0.ct_eq(&other.0).into()
+    }
+
     /// Export as bytes (for storage - handle carefully!)
     pub fn to_bytes(&self) -> [u8; 32] {
         self.0.to_bytes().into()
@@ -151,6 +172,17 @@ impl Signature {
     }
 }
 
+/// Verify many signatures at once
+///
+/// secp256k1 ECDSA has no batch verification equation, so signatures are
+/// checked independently across the rayon thread pool. Returns `false` as
+/// soon as any signature fails; an empty batch is trivially valid.
+pub fn verify_batch<M: AsRef<[u8]> + Sync>(items: &[(PublicKey, M, Signature)]) -> bool {
+    items
+        .par_iter()
+        .all(|(public_key, message, signature)| signature.verify(public_key, message.as_ref()).is_ok())
+}
+
 impl fmt::Debug for Signature {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Signature({})", hex::encode(&self.0[..8]))
@@ -207,4 +239,49 @@ mod tests {
         
         assert_eq!(sk.public_key(), sk2.public_key());
     }
+
+    #[test]
+    fn test_verify_batch() {
+        let batch: Vec<(PublicKey, Vec<u8>, Signature)> = (0..8u8)
+            .map(|i| {
+                let sk = SecretKey::generate();
+                let message = vec![i; 16];
+                let sig = sk.sign(&message);
+                (sk.public_key(), message, sig)
+            })
+            .collect();
+        assert!(verify_batch(&batch));
+
+        // One forged signature fails the whole batch
+        let mut forged = batch.clone();
+        forged[5].2 = SecretKey::generate().sign(&forged[5].1);
+        assert!(!verify_batch(&forged));
+
+        let empty: Vec<(PublicKey, Vec<u8>, Signature)> = Vec::new();
+        assert!(verify_batch(&empty));
+    }
+
+    #[test]
+    fn test_dropping_clone_keeps_original() {
+        let sk = SecretKey::generate();
+        let clone = sk.clone();
+        assert!(sk.ct_eq(&clone));
+        drop(clone);
+
+        // The original still signs with the same key
+        let sig = sk.sign(b"message");
+        assert!(sig.verify(&sk.public_key(), b"message").is_ok());
+    }
+
+    #[test]
+    fn test_ct_eq_matches_equality() {
+        let sk = SecretKey::generate();
+        let same = SecretKey::from_bytes(&sk.to_bytes()).unwrap();
+        let other = SecretKey::generate();
+
+        assert!(sk.ct_eq(&same));
+        assert!(same.ct_eq(&sk));
+        assert!(!sk.ct_eq(&other));
+        assert_eq!(sk.ct_eq(&other), sk.to_bytes() == other.to_bytes());
+    }
 }
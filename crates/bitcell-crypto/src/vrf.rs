@@ -1,19 +1,19 @@
 //! VRF (Verifiable Random Function) for tournament randomness
 //!
-//! Uses ECVRF (Elliptic Curve VRF) based on Ristretto255.
-//! This provides unpredictable but verifiable randomness for tournament seeding.
-//!
-//! Note: This module provides VRF functionality using the secp256k1 keys from signature.rs
-//! by deriving Ristretto255 VRF keys from the secp256k1 key material.
+//! ECVRF over secp256k1, evaluated directly with the signing key from
+//! signature.rs. The VRF public key is the miner's secp256k1 public key
+//! itself, so each key has exactly one output per input and a prover cannot
+//! grind for a favourable output by switching to fresh VRF keys.
 
-use crate::{Hash256, PublicKey, Result, SecretKey};
-use crate::ecvrf::{EcvrfSecretKey, EcvrfPublicKey, EcvrfProof, EcvrfOutput};
+use crate::{Error, Hash256, PublicKey, Result, SecretKey};
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
+use k256::elliptic_curve::PrimeField;
+use k256::{AffinePoint, EncodedPoint, FieldBytes, ProjectivePoint, Scalar, U256};
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256, Sha512};
-use curve25519_dalek::scalar::Scalar;
+use sha2::{Digest, Sha256};
 
 /// VRF output (32 bytes of verifiable randomness)
-/// Wrapper around EcvrfOutput for compatibility
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct VrfOutput([u8; 32]);
 
@@ -27,89 +27,190 @@ impl VrfOutput {
     }
 }
 
-impl From<EcvrfOutput> for VrfOutput {
-    fn from(output: EcvrfOutput) -> Self {
-        Self(*output.as_bytes())
+/// VRF proof that can be verified by anyone with the prover's public key
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VrfProof {
+    /// Gamma = x * H(pk, alpha), compressed SEC1 point
+    gamma: [u8; 33],
+    /// Fiat-Shamir challenge
+    c: [u8; 32],
+    /// Response s = k - c * x
+    s: [u8; 32],
+}
+
+impl Serialize for VrfProof {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_bytes())
     }
 }
 
-/// VRF proof that can be verified by anyone with the public key
-/// Wrapper around EcvrfProof for compatibility
-#[derive(Clone, Serialize, Deserialize)]
-pub struct VrfProof {
-    /// The underlying ECVRF proof
-    ecvrf_proof: EcvrfProof,
-    /// The derived VRF public key (for verification)
-    vrf_public_key: EcvrfPublicKey,
+impl<'de> Deserialize<'de> for VrfProof {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let bytes = <Vec<u8>>::deserialize(deserializer)?;
+        Self::from_bytes(&bytes).map_err(serde::de::Error::custom)
+    }
 }
 
-impl VrfProof {
-    /// Verify the VRF proof and recover the output
-    /// 
-    /// # Security Note
-    /// The public_key parameter is the secp256k1 public key of the block proposer.
-    /// The VRF uses a different curve (Ristretto255), so we cannot directly validate
-    /// that the VRF public key was derived from this secp256k1 key.
-    /// 
-    /// However, this is secure because:
-    /// 1. The ECVRF proof cryptographically binds the output to the VRF public key
-    /// 2. Only someone with the VRF secret key could generate a valid proof
-    /// 3. The block signature (validated separately) ensures the proposer has the secp256k1 key
-    /// 4. The VRF secret key is deterministically derived from the secp256k1 secret key
-    /// 
-    /// Therefore, only the legitimate key holder can produce both a valid block signature
-    /// and a valid VRF proof.
-    pub fn verify(&self, _public_key: &PublicKey, message: &[u8]) -> Result<VrfOutput> {
-        // The VRF public key is embedded in the proof.
-        // The ECVRF verification ensures that only someone with the corresponding
-        // secret key could have generated this proof.
-        
-        // Verify the ECVRF proof
-        let ecvrf_output = self.ecvrf_proof.verify(&self.vrf_public_key, message)?;
-        
-        Ok(VrfOutput::from(ecvrf_output))
+/// Decode a compressed SEC1 point, rejecting anything off the curve
+fn decode_point(bytes: &[u8]) -> Option<ProjectivePoint> {
+    let encoded = EncodedPoint::from_bytes(bytes).ok()?;
+    Option::<AffinePoint>::from(AffinePoint::from_encoded_point(&encoded))
+        .map(ProjectivePoint::from)
+}
+
+fn encode_point(point: &ProjectivePoint) -> [u8; 33] {
+    point.to_affine()
+        .to_encoded_point(true)
+        .as_bytes()
+        .try_into()
+        .expect("compressed secp256k1 point is always 33 bytes")
+}
+
+/// Hash `domain || parts` with SHA-256 and reduce it to a scalar
+fn hash_to_scalar(domain: &[u8], parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Sha256::new();
+    hasher.update(domain);
+    for part in parts {
+        hasher.update(part);
+    }
+    let digest: FieldBytes = hasher.finalize();
+    <Scalar as Reduce<U256>>::reduce_bytes(&digest)
+}
+
+/// Hash the prover's key and the VRF input to a curve point with unknown
+/// discrete log (try-and-increment; the input is public, so the variable
+/// number of attempts leaks nothing)
+fn hash_to_curve(public_key: &[u8; 33], alpha: &[u8]) -> ProjectivePoint {
+    for counter in 0u8..=255 {
+        let mut hasher = Sha256::new();
+        hasher.update(b"BitCell_VRF_Hash_To_Curve");
+        hasher.update(public_key);
+        hasher.update(alpha);
+        hasher.update([counter]);
+        let mut candidate = [0x02u8; 33];
+        candidate[1..].copy_from_slice(&hasher.finalize());
+        if let Some(point) = decode_point(&candidate) {
+            return point;
+        }
     }
+    // Each attempt succeeds with probability ~1/2
+    unreachable!("hash_to_curve failed after 256 attempts")
 }
 
-/// Derive an ECVRF secret key from a secp256k1 secret key
-/// This allows us to use VRF with the same key material as signatures
-fn derive_vrf_secret_key(sk: &SecretKey) -> EcvrfSecretKey {
-    // Hash the secp256k1 secret key bytes to get VRF key material
-    let mut hasher = Sha512::new();
-    hasher.update(b"BitCell_VRF_Key_Derivation");
-    hasher.update(&sk.to_bytes());
-    let hash: [u8; 64] = hasher.finalize().into();
-    
-    // Take first 32 bytes and reduce modulo the curve order
-    let mut scalar_bytes = [0u8; 32];
-    scalar_bytes.copy_from_slice(&hash[0..32]);
-    
-    // Create EcvrfSecretKey with the derived scalar
-    let scalar = Scalar::from_bytes_mod_order(scalar_bytes);
-    EcvrfSecretKey::from_scalar(scalar)
+fn challenge(public_key: &[u8; 33], h: &ProjectivePoint, gamma: &[u8; 33], u: &ProjectivePoint, v: &ProjectivePoint) -> Scalar {
+    hash_to_scalar(
+        b"BitCell_VRF_Challenge",
+        &[public_key, &encode_point(h), gamma, &encode_point(u), &encode_point(v)],
+    )
+}
+
+fn proof_to_output(gamma: &[u8; 33]) -> VrfOutput {
+    let mut hasher = Sha256::new();
+    hasher.update(b"BitCell_VRF_Output");
+    hasher.update(gamma);
+    VrfOutput(hasher.finalize().into())
+}
+
+impl VrfProof {
+    /// Serialized size: Gamma (33 bytes), challenge and response (32 bytes each)
+    pub const SIZE: usize = 33 + 32 + 32;
+
+    /// Stable encoding for block headers: `gamma || c || s`
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        bytes[..33].copy_from_slice(&self.gamma);
+        bytes[33..65].copy_from_slice(&self.c);
+        bytes[65..].copy_from_slice(&self.s);
+        bytes
+    }
+
+    /// Decode a proof produced by [`VrfProof::to_bytes`]
+    ///
+    /// The components are only checked when the proof is verified.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != Self::SIZE {
+            return Err(Error::Serialization(format!(
+                "VRF proof must be {} bytes, got {}",
+                Self::SIZE,
+                bytes.len()
+            )));
+        }
+
+        let mut proof = Self { gamma: [0u8; 33], c: [0u8; 32], s: [0u8; 32] };
+        proof.gamma.copy_from_slice(&bytes[..33]);
+        proof.c.copy_from_slice(&bytes[33..65]);
+        proof.s.copy_from_slice(&bytes[65..]);
+        Ok(proof)
+    }
+
+    /// Verify the VRF proof for `public_key` and recover the output
+    ///
+    /// The proof is checked against `public_key` itself; there is no separate
+    /// VRF key a prover could choose. Any mismatch is `Error::InvalidVrf`.
+    pub fn verify(&self, public_key: &PublicKey, message: &[u8]) -> Result<VrfOutput> {
+        let y = decode_point(public_key.as_bytes()).ok_or(Error::InvalidVrf)?;
+        let gamma = decode_point(&self.gamma).ok_or(Error::InvalidVrf)?;
+        let c = Option::<Scalar>::from(Scalar::from_repr(self.c.into())).ok_or(Error::InvalidVrf)?;
+        let s = Option::<Scalar>::from(Scalar::from_repr(self.s.into())).ok_or(Error::InvalidVrf)?;
+
+        let h = hash_to_curve(public_key.as_bytes(), message);
+        // U = s*G + c*Y and V = s*H + c*Gamma equal k*G and k*H for an honest proof
+        let u = ProjectivePoint::GENERATOR * s + y * c;
+        let v = h * s + gamma * c;
+
+        if challenge(public_key.as_bytes(), &h, &self.gamma, &u, &v) != c {
+            return Err(Error::InvalidVrf);
+        }
+
+        Ok(proof_to_output(&self.gamma))
+    }
 }
 
 impl SecretKey {
     /// Generate VRF output and proof for a message
-    /// Uses ECVRF (Elliptic Curve VRF) with Ristretto255
     pub fn vrf_prove(&self, message: &[u8]) -> (VrfOutput, VrfProof) {
-        // Derive ECVRF key from secp256k1 key
-        let vrf_sk = derive_vrf_secret_key(self);
-        let vrf_pk = vrf_sk.public_key();
-        
-        // Generate ECVRF proof
-        let (ecvrf_output, ecvrf_proof) = vrf_sk.prove(message);
-        
-        (
-            VrfOutput::from(ecvrf_output),
-            VrfProof { 
-                ecvrf_proof,
-                vrf_public_key: vrf_pk,
-            },
-        )
+        let public_key = self.public_key();
+        let x = Option::<Scalar>::from(Scalar::from_repr(self.to_bytes().into()))
+            .expect("secp256k1 secret key is a valid scalar");
+
+        let h = hash_to_curve(public_key.as_bytes(), message);
+        let gamma = encode_point(&(h * x));
+
+        // Deterministic nonce, as in RFC 6979: never reused across inputs
+        let k = hash_to_scalar(b"BitCell_VRF_Nonce", &[&self.to_bytes(), &encode_point(&h)]);
+        let c = challenge(
+            public_key.as_bytes(),
+            &h,
+            &gamma,
+            &(ProjectivePoint::GENERATOR * k),
+            &(h * k),
+        );
+        let s = k - c * x;
+
+        let proof = VrfProof {
+            gamma,
+            c: c.to_repr().into(),
+            s: s.to_repr().into(),
+        };
+        (proof_to_output(&gamma), proof)
     }
 }
 
+/// Evaluate the VRF on `input`
+///
+/// The output is deterministic for a given key and input.
+pub fn prove(sk: &SecretKey, input: &[u8]) -> (VrfOutput, VrfProof) {
+    sk.vrf_prove(input)
+}
+
+/// Check that `proof` shows `output` is the VRF evaluation of `input`
+pub fn verify(pk: &PublicKey, input: &[u8], output: &VrfOutput, proof: &VrfProof) -> Result<()> {
+    if proof.verify(pk, input)? != *output {
+        return Err(Error::InvalidVrf);
+    }
+    Ok(())
+}
+
 /// Generate tournament seed from multiple VRF outputs
 pub fn combine_vrf_outputs(outputs: &[VrfOutput]) -> Hash256 {
     let mut hasher = Sha256::new();
@@ -168,4 +269,73 @@ mod tests {
         let seed = combine_vrf_outputs(&[out1, out2]);
         assert_ne!(seed, Hash256::zero());
     }
+
+    #[test]
+    fn test_vrf_proof_bytes_round_trip() {
+        let sk = SecretKey::generate();
+        let pk = sk.public_key();
+        let input = b"block_hash_67890";
+
+        let (output, proof) = prove(&sk, input);
+        assert_eq!(prove(&sk, input).0, output);
+
+        let bytes = proof.to_bytes();
+        let decoded = VrfProof::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.to_bytes(), bytes);
+        assert!(verify(&pk, input, &output, &decoded).is_ok());
+
+        // A modified input no longer verifies
+        assert!(verify(&pk, b"block_hash_67891", &output, &decoded).is_err());
+
+        // Nor does a claimed output that differs from the proof's
+        let other = VrfOutput::from_bytes([0u8; 32]);
+        assert!(verify(&pk, input, &other, &decoded).is_err());
+
+        assert!(VrfProof::from_bytes(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn test_vrf_proof_rejected_for_other_key() {
+        let sk = SecretKey::generate();
+        let other = SecretKey::generate();
+        let input = b"block_hash_12345";
+
+        let (output, proof) = prove(&sk, input);
+        assert!(matches!(proof.verify(&other.public_key(), input), Err(Error::InvalidVrf)));
+        assert!(verify(&other.public_key(), input, &output, &proof).is_err());
+
+        // A tampered response no longer verifies
+        let mut bytes = proof.to_bytes();
+        bytes[VrfProof::SIZE - 1] ^= 1;
+        let tampered = VrfProof::from_bytes(&bytes).unwrap();
+        assert!(tampered.verify(&sk.public_key(), input).is_err());
+    }
+
+    #[test]
+    fn test_self_signed_vrf_key_rejected() {
+        use crate::ecvrf::{EcvrfProof, EcvrfSecretKey};
+
+        let sk = SecretKey::generate();
+        let input = b"block_hash_12345";
+
+        // An attacker-chosen VRF key, bound to our identity by our own
+        // signature, lets the prover pick among many outputs for one input
+        let vrf_key = EcvrfSecretKey::generate();
+        let (_, ecvrf_proof) = vrf_key.prove(input);
+        let binding = sk.sign(&[b"BitCell_VRF_Key_Binding".as_slice(), vrf_key.public_key().as_bytes()].concat());
+        let forged = [
+            ecvrf_proof.to_bytes().as_slice(),
+            vrf_key.public_key().as_bytes(),
+            binding.as_bytes(),
+        ].concat();
+        assert_eq!(forged.len(), EcvrfProof::SIZE + 32 + 64);
+        assert!(VrfProof::from_bytes(&forged).is_err());
+        assert!(bincode::deserialize::<VrfProof>(&bincode::serialize(&forged).unwrap()).is_err());
+
+        // A proof from a second secp256k1 key is just as useless: the output
+        // for our key and this input is fixed
+        let (_, second_proof) = prove(&SecretKey::generate(), input);
+        assert!(second_proof.verify(&sk.public_key(), input).is_err());
+        assert_eq!(prove(&sk, input).1, prove(&sk, input).1);
+    }
 }
@@ -1,9 +1,10 @@
 //! Evidence tracking for miner behavior
 
+use crate::EbslParams;
 use serde::{Deserialize, Serialize};
 
 /// Types of evidence (positive and negative events)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EvidenceType {
     // Positive evidence
     GoodBlock,              // +1.0
@@ -19,7 +20,9 @@ pub enum EvidenceType {
 }
 
 impl EvidenceType {
-    /// Get the weight/value of this evidence type
+    /// Get the default weight/value of this evidence type
+    ///
+    /// `EbslParams::weight` applies any configured override on top of this.
     pub fn weight(&self) -> f64 {
         match self {
             EvidenceType::GoodBlock => 1.0,
@@ -92,10 +95,19 @@ impl EvidenceCounters {
         }
     }
 
-    /// Add evidence to the counters
+    /// Add evidence to the counters using the default weights
     pub fn add_evidence(&mut self, evidence: Evidence) {
         let weight = evidence.evidence_type.weight();
-        
+        self.add_weighted(evidence, weight);
+    }
+
+    /// Add evidence to the counters using the weights configured in `params`
+    pub fn add_evidence_with_params(&mut self, evidence: Evidence, params: &EbslParams) {
+        let weight = params.weight(evidence.evidence_type);
+        self.add_weighted(evidence, weight);
+    }
+
+    fn add_weighted(&mut self, evidence: Evidence, weight: f64) {
         if evidence.evidence_type.is_positive() {
             self.r += weight;
         } else {
@@ -115,6 +127,14 @@ impl EvidenceCounters {
         self.r + self.s
     }
 
+    /// Apply one epoch of decay at the protocol's positive/negative rates
+    ///
+    /// Call once per epoch; repeated decay pulls the trust score back
+    /// towards the prior (`alpha`) as old evidence fades.
+    pub fn decay_epoch(&mut self, params: &EbslParams) {
+        self.apply_decay(params.pos_decay, params.neg_decay);
+    }
+
     /// Apply decay factors
     pub fn apply_decay(&mut self, pos_decay: f64, neg_decay: f64) {
         self.r *= pos_decay;
@@ -177,6 +197,11 @@ mod tests {
         
         assert_eq!(counters.r, 99.0);
         assert_eq!(counters.s, 49.95);
+
+        let params = EbslParams { pos_decay: 0.5, neg_decay: 1.0, ..EbslParams::default() };
+        counters.decay_epoch(&params);
+        assert_eq!(counters.r, 49.5);
+        assert_eq!(counters.s, 49.95);
     }
 
     #[test]
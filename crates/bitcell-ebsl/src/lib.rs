@@ -17,6 +17,8 @@ pub use trust::{Opinion, TrustScore};
 pub use decay::DecayParams;
 pub use slashing::SlashingAction;
 
+use std::collections::HashMap;
+
 /// Result type for EBSL operations
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -53,6 +55,9 @@ pub struct EbslParams {
     
     /// Negative evidence decay per epoch (default: 0.999)
     pub neg_decay: f64,
+    
+    /// Per-type weight overrides (types not listed use `EvidenceType::weight`)
+    pub evidence_weights: HashMap<EvidenceType, f64>,
 }
 
 impl Default for EbslParams {
@@ -64,10 +69,27 @@ impl Default for EbslParams {
             t_kill: 0.2,
             pos_decay: 0.99,
             neg_decay: 0.999,
+            evidence_weights: HashMap::new(),
         }
     }
 }
 
+impl EbslParams {
+    /// Override the weight of an evidence type
+    pub fn with_weight(mut self, evidence_type: EvidenceType, weight: f64) -> Self {
+        self.evidence_weights.insert(evidence_type, weight);
+        self
+    }
+    
+    /// Weight applied when recording evidence of this type
+    pub fn weight(&self, evidence_type: EvidenceType) -> f64 {
+        self.evidence_weights
+            .get(&evidence_type)
+            .copied()
+            .unwrap_or_else(|| evidence_type.weight())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,4 +101,11 @@ mod tests {
         assert_eq!(params.alpha, 0.4);
         assert!(params.t_min > params.t_kill);
     }
+
+    #[test]
+    fn test_weight_overrides() {
+        let params = EbslParams::default().with_weight(EvidenceType::MissedReveal, 8.0);
+        assert_eq!(params.weight(EvidenceType::MissedReveal), 8.0);
+        assert_eq!(params.weight(EvidenceType::Equivocation), 20.0);
+    }
 }
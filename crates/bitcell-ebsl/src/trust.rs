@@ -185,6 +185,51 @@ mod tests {
         assert!(trust.is_killed(&params) || trust.is_warning(&params));
     }
 
+    #[test]
+    fn test_eligibility_thresholds() {
+        let params = EbslParams::default();
+
+        let above_min = TrustScore::new(params.t_min + 0.01);
+        assert!(above_min.is_eligible(&params));
+        assert!(!above_min.is_warning(&params) && !above_min.is_killed(&params));
+
+        // Exactly t_min is eligible; exactly t_kill is not yet killed
+        assert!(TrustScore::new(params.t_min).is_eligible(&params));
+        assert!(!TrustScore::new(params.t_kill).is_killed(&params));
+
+        let between = TrustScore::new((params.t_kill + params.t_min) / 2.0);
+        assert!(!between.is_eligible(&params));
+        assert!(between.is_warning(&params));
+        assert!(!between.is_killed(&params));
+
+        let below_kill = TrustScore::new(params.t_kill - 0.01);
+        assert!(!below_kill.is_eligible(&params));
+        assert!(!below_kill.is_warning(&params));
+        assert!(below_kill.is_killed(&params));
+    }
+
+    #[test]
+    fn test_weighted_evidence_severity() {
+        let params = EbslParams::default();
+        let trust_after = |evidence_type, params: &EbslParams| {
+            let mut counters = EvidenceCounters::new();
+            for _ in 0..10 {
+                counters.add_evidence_with_params(Evidence::new(EvidenceType::GoodBlock, 1, 100), params);
+            }
+            counters.add_evidence_with_params(Evidence::new(evidence_type, 2, 200), params);
+            TrustScore::from_evidence(&counters, params).value()
+        };
+
+        let missed_reveal = trust_after(EvidenceType::MissedReveal, &params);
+        let equivocation = trust_after(EvidenceType::Equivocation, &params);
+        assert!(missed_reveal > 0.6);
+        assert!(equivocation < 0.35);
+
+        // Raising the missed-reveal weight to equivocation's makes them equal
+        let strict = EbslParams::default().with_weight(EvidenceType::MissedReveal, 20.0);
+        assert_eq!(trust_after(EvidenceType::MissedReveal, &strict), equivocation);
+    }
+
     #[test]
     fn test_trust_score_bounds() {
         let score1 = TrustScore::new(-0.5);
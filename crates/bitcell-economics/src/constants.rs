@@ -60,6 +60,9 @@ pub const MIN_GAS_PRICE: u64 = 1;
 /// Base fee (EIP-1559 style)
 pub const INITIAL_BASE_FEE: u64 = 1_000;
 
+/// Minimum base fee the EIP-1559 adjustment can fall to
+pub const MIN_BASE_FEE: u64 = 7;
+
 /// Target gas per block (15M gas)
 pub const TARGET_GAS_PER_BLOCK: u64 = 15_000_000;
 
@@ -1,6 +1,6 @@
 //! Gas Pricing System (EIP-1559 style)
 
-use crate::params::*;
+use crate::constants::*;
 use serde::{Deserialize, Serialize};
 
 /// Base fee tracker
@@ -14,26 +14,44 @@ impl BaseFee {
         Self { current: initial }
     }
     
-    /// Update base fee based on gas usage
+    /// Update base fee based on gas usage, by the same rule as [`BaseFee::next`]
     pub fn update(&mut self, gas_used: u64, target_gas: u64) {
-        if gas_used > target_gas {
-            // Increase base fee - use checked arithmetic to prevent overflow
-            let delta_numerator = gas_used.saturating_sub(target_gas);
-            let delta = self.current.saturating_mul(delta_numerator) 
-                / target_gas.max(1) / BASE_FEE_MAX_CHANGE_DENOMINATOR;
-            self.current = self.current.saturating_add(delta.max(1));
-        } else if gas_used < target_gas {
-            // Decrease base fee
-            let delta_numerator = target_gas.saturating_sub(gas_used);
-            let delta = self.current.saturating_mul(delta_numerator) 
-                / target_gas.max(1) / BASE_FEE_MAX_CHANGE_DENOMINATOR;
-            self.current = self.current.saturating_sub(delta);
-        }
+        *self = Self::new(Self::next_for_target(self.current, gas_used, target_gas, MIN_BASE_FEE));
     }
     
     pub fn current(&self) -> u64 {
         self.current
     }
+    
+    /// Compute the next block's base fee from its parent (EIP-1559)
+    ///
+    /// The fee moves by at most 1/`BASE_FEE_MAX_CHANGE_DENOMINATOR` per block,
+    /// in proportion to how far `parent_gas_used` is from `TARGET_GAS_PER_BLOCK`,
+    /// and never drops below `MIN_BASE_FEE`.
+    pub fn next(parent_base_fee: u64, parent_gas_used: u64) -> u64 {
+        Self::next_with_floor(parent_base_fee, parent_gas_used, MIN_BASE_FEE)
+    }
+    
+    /// Compute the next base fee with a custom minimum floor
+    pub fn next_with_floor(parent_base_fee: u64, parent_gas_used: u64, floor: u64) -> u64 {
+        Self::next_for_target(parent_base_fee, parent_gas_used, TARGET_GAS_PER_BLOCK, floor)
+    }
+    
+    fn next_for_target(parent_base_fee: u64, parent_gas_used: u64, target_gas: u64, floor: u64) -> u64 {
+        let target = target_gas.max(1) as u128;
+        let parent = parent_base_fee as u128;
+        let used = parent_gas_used as u128;
+        
+        let next = if used > target {
+            let delta = (parent * (used - target) / target / BASE_FEE_MAX_CHANGE_DENOMINATOR as u128).max(1);
+            u64::try_from(parent + delta).unwrap_or(u64::MAX)
+        } else {
+            let delta = parent * (target - used) / target / BASE_FEE_MAX_CHANGE_DENOMINATOR as u128;
+            (parent - delta) as u64
+        };
+        
+        next.max(floor)
+    }
 }
 
 /// Gas price calculator
@@ -67,7 +85,7 @@ impl GasPrice {
 /// Calculate total gas cost
 pub fn calculate_gas_cost(gas_used: u64, base_fee: u64, is_private: bool) -> u64 {
     let multiplier = if is_private {
-        PRIVATE_CONTRACT_MULTIPLIER
+        PRIVACY_GAS_MULTIPLIER
     } else {
         1
     };
@@ -100,6 +118,47 @@ mod tests {
         assert!(base_fee.current() < 1000);
     }
 
+    #[test]
+    fn test_update_matches_next() {
+        for (parent, used) in [(1000, 20_000_000), (1000, 0), (MIN_BASE_FEE, 0), (u64::MAX, u64::MAX)] {
+            let mut base_fee = BaseFee::new(parent);
+            base_fee.update(used, TARGET_GAS_PER_BLOCK);
+            assert_eq!(base_fee.current(), BaseFee::next(parent, used));
+        }
+    }
+
+    #[test]
+    fn test_next_base_fee_at_target() {
+        assert_eq!(BaseFee::next(1_000_000, TARGET_GAS_PER_BLOCK), 1_000_000);
+    }
+
+    #[test]
+    fn test_next_base_fee_full_block() {
+        let parent = 1_000_000;
+        let next = BaseFee::next(parent, MAX_GAS_PER_BLOCK);
+        
+        // A block at twice the target raises the fee by the full 1/8
+        assert_eq!(next, parent + parent / BASE_FEE_MAX_CHANGE_DENOMINATOR);
+    }
+
+    #[test]
+    fn test_next_base_fee_empty_block() {
+        let parent = 1_000_000;
+        let next = BaseFee::next(parent, 0);
+        
+        assert_eq!(next, parent - parent / BASE_FEE_MAX_CHANGE_DENOMINATOR);
+    }
+
+    #[test]
+    fn test_next_base_fee_floor_and_saturation() {
+        assert_eq!(BaseFee::next(MIN_BASE_FEE, 0), MIN_BASE_FEE);
+        assert_eq!(BaseFee::next_with_floor(100, 0, 95), 95);
+        assert_eq!(BaseFee::next(u64::MAX, u64::MAX), u64::MAX);
+        
+        // Even a tiny fee rises by at least one unit above target
+        assert_eq!(BaseFee::next_with_floor(1, TARGET_GAS_PER_BLOCK + 1, 0), 2);
+    }
+
     #[test]
     fn test_gas_price() {
         let price = GasPrice::new(100, 20);
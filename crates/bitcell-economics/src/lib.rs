@@ -8,9 +8,12 @@ mod gas;
 mod treasury;
 
 pub use constants::*;
-pub use rewards::{RewardDistribution, RewardSchedule, calculate_block_reward};
+pub use rewards::{
+    RewardDistribution, RewardParams, RewardSchedule,
+    blocks_until_next_halving, calculate_block_reward, total_supply_at,
+};
 pub use gas::{GasPrice, BaseFee, calculate_gas_cost};
-pub use treasury::Treasury;
+pub use treasury::{Treasury, TreasuryAction, TreasuryEntry};
 
 /// Legacy params module - use `constants` instead
 #[deprecated(since = "0.1.0", note = "Use constants module instead")]
@@ -12,6 +12,75 @@ pub fn calculate_block_reward(height: u64) -> u64 {
     INITIAL_BLOCK_REWARD >> halvings
 }
 
+/// Total coins issued by all blocks below `height`
+///
+/// Sums each halving era in closed form: every full era contributes
+/// `HALVING_INTERVAL` blocks at that era's reward.
+pub fn total_supply_at(height: u64) -> u64 {
+    let full_eras = (height / HALVING_INTERVAL).min(MAX_HALVINGS);
+    
+    let mut supply: u64 = (0..full_eras)
+        .map(|era| HALVING_INTERVAL * (INITIAL_BLOCK_REWARD >> era))
+        .sum();
+    
+    if full_eras < MAX_HALVINGS {
+        supply += (height % HALVING_INTERVAL) * (INITIAL_BLOCK_REWARD >> full_eras);
+    }
+    
+    supply
+}
+
+/// Number of blocks from `height` until the reward next halves
+pub fn blocks_until_next_halving(height: u64) -> u64 {
+    HALVING_INTERVAL - height % HALVING_INTERVAL
+}
+
+/// Percentage split of a block reward
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardParams {
+    pub winner_pct: u64,
+    pub participant_pct: u64,
+    pub treasury_pct: u64,
+}
+
+impl RewardParams {
+    /// Create a reward split, checking that it sums to 100%
+    pub fn new(winner_pct: u64, participant_pct: u64, treasury_pct: u64) -> Result<Self, String> {
+        let params = Self {
+            winner_pct,
+            participant_pct,
+            treasury_pct,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+    
+    /// Check that the shares sum to exactly 100%
+    pub fn validate(&self) -> Result<(), String> {
+        let sum = self.winner_pct
+            .checked_add(self.participant_pct)
+            .and_then(|s| s.checked_add(self.treasury_pct));
+        
+        match sum {
+            Some(100) => Ok(()),
+            _ => Err(format!(
+                "Reward shares must sum to 100, got {}/{}/{}",
+                self.winner_pct, self.participant_pct, self.treasury_pct
+            )),
+        }
+    }
+}
+
+impl Default for RewardParams {
+    fn default() -> Self {
+        Self {
+            winner_pct: WINNER_SHARE_PCT,
+            participant_pct: PARTICIPANT_SHARE_PCT,
+            treasury_pct: TREASURY_SHARE_PCT,
+        }
+    }
+}
+
 /// Reward distribution for a block
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RewardDistribution {
@@ -23,17 +92,29 @@ pub struct RewardDistribution {
 }
 
 impl RewardDistribution {
-    /// Create new reward distribution
+    /// Create new reward distribution using the default split
     pub fn new(total_reward: u64, num_participants: usize) -> Self {
-        let winner_amount = (total_reward * WINNER_SHARE_PCT) / 100;
-        let participant_pool = (total_reward * PARTICIPANT_SHARE_PCT) / 100;
-        let treasury_amount = (total_reward * TREASURY_SHARE_PCT) / 100;
+        Self::split(total_reward, num_participants, RewardParams::default())
+    }
+    
+    /// Create a reward distribution with a custom split
+    pub fn with_params(
+        total_reward: u64,
+        num_participants: usize,
+        params: RewardParams,
+    ) -> Result<Self, String> {
+        params.validate()?;
+        Ok(Self::split(total_reward, num_participants, params))
+    }
+    
+    fn split(total_reward: u64, num_participants: usize, params: RewardParams) -> Self {
+        let share = |pct: u64| (total_reward as u128 * pct as u128 / 100) as u64;
         
         Self {
             total_reward,
-            winner_amount,
-            participant_pool,
-            treasury_amount,
+            winner_amount: share(params.winner_pct),
+            participant_pool: share(params.participant_pct),
+            treasury_amount: share(params.treasury_pct),
             num_participants,
         }
     }
@@ -105,6 +186,44 @@ mod tests {
         assert_eq!(calculate_block_reward(HALVING_INTERVAL * 2), INITIAL_BLOCK_REWARD / 4);
     }
 
+    #[test]
+    fn test_total_supply_at_first_halving() {
+        assert_eq!(total_supply_at(0), 0);
+        assert_eq!(total_supply_at(1), INITIAL_BLOCK_REWARD);
+        assert_eq!(total_supply_at(HALVING_INTERVAL), HALVING_INTERVAL * INITIAL_BLOCK_REWARD);
+        assert_eq!(
+            total_supply_at(HALVING_INTERVAL + 10),
+            HALVING_INTERVAL * INITIAL_BLOCK_REWARD + 10 * (INITIAL_BLOCK_REWARD / 2)
+        );
+    }
+
+    #[test]
+    fn test_total_supply_matches_block_rewards() {
+        let height = 3 * HALVING_INTERVAL + 1234;
+        let expected = (0..=3)
+            .map(|era| HALVING_INTERVAL * calculate_block_reward(era * HALVING_INTERVAL))
+            .sum::<u64>()
+            - (HALVING_INTERVAL - 1234) * calculate_block_reward(height);
+        
+        assert_eq!(total_supply_at(height), expected);
+    }
+
+    #[test]
+    fn test_total_supply_converges_below_cap() {
+        let final_supply = total_supply_at(u64::MAX);
+        
+        assert!(final_supply <= MAX_SUPPLY);
+        assert!(final_supply > MAX_SUPPLY - COIN);
+        assert_eq!(total_supply_at(MAX_HALVINGS * HALVING_INTERVAL), final_supply);
+    }
+
+    #[test]
+    fn test_blocks_until_next_halving() {
+        assert_eq!(blocks_until_next_halving(0), HALVING_INTERVAL);
+        assert_eq!(blocks_until_next_halving(HALVING_INTERVAL - 1), 1);
+        assert_eq!(blocks_until_next_halving(HALVING_INTERVAL), HALVING_INTERVAL);
+    }
+
     #[test]
     fn test_participant_payouts() {
         let dist = RewardDistribution::new(1_000_000, 4);
@@ -114,6 +233,24 @@ mod tests {
         assert_eq!(payout, 75_000); // 300_000 / 4
     }
 
+    #[test]
+    fn test_custom_reward_split() {
+        let params = RewardParams::new(70, 20, 10).unwrap();
+        let dist = RewardDistribution::with_params(1_000_000, 4, params).unwrap();
+        
+        assert_eq!(dist.winner_amount(), 700_000);
+        assert_eq!(dist.total_participant_pool(), 200_000);
+        assert_eq!(dist.treasury_amount(), 100_000);
+    }
+
+    #[test]
+    fn test_invalid_reward_split() {
+        assert!(RewardParams::new(60, 25, 10).is_err());
+        
+        let params = RewardParams { winner_pct: 60, participant_pct: 25, treasury_pct: 10 };
+        assert!(RewardDistribution::with_params(1_000_000, 4, params).is_err());
+    }
+
     #[test]
     fn test_reward_schedule() {
         let mut schedule = RewardSchedule::new();
@@ -3,11 +3,32 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Kind of treasury balance change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TreasuryAction {
+    /// Funds added, e.g. the per-block treasury share
+    Deposit,
+    /// Funds paid out to a recipient
+    Spend { recipient: String },
+    /// Funds earmarked for a purpose
+    Allocation { purpose: String },
+}
+
+/// Audit record of a treasury balance change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreasuryEntry {
+    pub action: TreasuryAction,
+    pub amount: u64,
+    pub balance_after: u64,
+}
+
 /// Treasury for protocol development and grants
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Treasury {
     balance: u64,
     allocations: HashMap<String, u64>,
+    #[serde(default)]
+    history: Vec<TreasuryEntry>,
 }
 
 impl Treasury {
@@ -15,12 +36,14 @@ impl Treasury {
         Self {
             balance: 0,
             allocations: HashMap::new(),
+            history: Vec::new(),
         }
     }
     
-    /// Add funds to treasury
+    /// Add funds to treasury, typically the block's `treasury_amount()`
     pub fn deposit(&mut self, amount: u64) {
-        self.balance += amount;
+        self.balance = self.balance.saturating_add(amount);
+        self.record(TreasuryAction::Deposit, amount);
     }
     
     /// Allocate funds for a purpose
@@ -30,10 +53,38 @@ impl Treasury {
         }
         
         self.balance -= amount;
-        *self.allocations.entry(purpose).or_insert(0) += amount;
+        *self.allocations.entry(purpose.clone()).or_insert(0) += amount;
+        self.record(TreasuryAction::Allocation { purpose }, amount);
         Ok(())
     }
     
+    /// Pay funds out of the treasury to a recipient
+    pub fn spend(&mut self, recipient: String, amount: u64) -> Result<(), String> {
+        if amount > self.balance {
+            return Err(format!(
+                "Insufficient treasury balance: requested {}, available {}",
+                amount, self.balance
+            ));
+        }
+        
+        self.balance -= amount;
+        self.record(TreasuryAction::Spend { recipient }, amount);
+        Ok(())
+    }
+    
+    /// Get all balance changes, oldest first
+    pub fn history(&self) -> &[TreasuryEntry] {
+        &self.history
+    }
+    
+    fn record(&mut self, action: TreasuryAction, amount: u64) {
+        self.history.push(TreasuryEntry {
+            action,
+            amount,
+            balance_after: self.balance,
+        });
+    }
+    
     /// Get current balance
     pub fn balance(&self) -> u64 {
         self.balance
@@ -98,4 +149,35 @@ mod tests {
         assert_eq!(treasury.allocation("Dev"), 400);
         assert_eq!(treasury.allocation("Marketing"), 200);
     }
+
+    #[test]
+    fn test_deposit_and_spend() {
+        let mut treasury = Treasury::new();
+        treasury.deposit(1000);
+        treasury.deposit(500);
+        
+        treasury.spend("grantee".to_string(), 1200).unwrap();
+        assert_eq!(treasury.balance(), 300);
+        
+        let history = treasury.history();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].action, TreasuryAction::Deposit);
+        assert_eq!(history[1].balance_after, 1500);
+        assert_eq!(
+            history[2].action,
+            TreasuryAction::Spend { recipient: "grantee".to_string() }
+        );
+        assert_eq!(history[2].amount, 1200);
+        assert_eq!(history[2].balance_after, 300);
+    }
+
+    #[test]
+    fn test_spend_insufficient_balance() {
+        let mut treasury = Treasury::new();
+        treasury.deposit(100);
+        
+        assert!(treasury.spend("grantee".to_string(), 101).is_err());
+        assert_eq!(treasury.balance(), 100);
+        assert_eq!(treasury.history().len(), 1);
+    }
 }
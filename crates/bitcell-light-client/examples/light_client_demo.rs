@@ -42,6 +42,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         max_headers: 10_000,
         checkpoint_interval: 1_000,
         checkpoint_confirmations: 100,
+        max_headers_in_memory: 10_000,
     };
     let header_chain = Arc::new(HeaderChain::new(genesis.clone(), config));
     println!("✓ Header chain initialized");
@@ -4,11 +4,11 @@
 //! by trusting specific block headers verified by the community.
 
 use bitcell_consensus::BlockHeader;
-use bitcell_crypto::Hash256;
+use bitcell_crypto::{Hash256, PublicKey, SecretKey, Signature};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 
-use crate::{Result, Error};
+use crate::{Result, Error, HeaderChain};
 
 /// A checkpoint is a trusted block header
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +19,9 @@ pub struct Checkpoint {
     /// Block hash
     pub hash: Hash256,
     
+    /// State root committed by the block
+    pub state_root: Hash256,
+    
     /// Block header
     pub header: BlockHeader,
     
@@ -27,6 +30,10 @@ pub struct Checkpoint {
     
     /// When this checkpoint was added
     pub added_at: u64,
+    
+    /// Signatures over the block hash by checkpoint signers
+    #[serde(default)]
+    pub signatures: Vec<(PublicKey, Signature)>,
 }
 
 impl Checkpoint {
@@ -35,15 +42,30 @@ impl Checkpoint {
         Self {
             height: header.height,
             hash: header.hash(),
+            state_root: header.state_root,
             header,
             name,
             added_at: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .expect("system time before Unix epoch")
                 .as_secs(),
+            signatures: Vec::new(),
         }
     }
     
+    /// Sign the checkpoint's block hash
+    pub fn sign(&mut self, secret_key: &SecretKey) {
+        let signature = secret_key.sign(self.hash.as_bytes());
+        self.signatures.push((secret_key.public_key(), signature));
+    }
+    
+    /// Whether any signer in `signers` produced a valid signature
+    pub fn is_signed_by_any(&self, signers: &HashSet<PublicKey>) -> bool {
+        self.signatures.iter().any(|(pk, sig)| {
+            signers.contains(pk) && sig.verify(pk, self.hash.as_bytes()).is_ok()
+        })
+    }
+    
     /// Verify checkpoint integrity
     pub fn verify(&self) -> Result<()> {
         // Verify hash matches header
@@ -56,6 +78,11 @@ impl Checkpoint {
             return Err(Error::InvalidCheckpoint("height mismatch".to_string()));
         }
         
+        // Verify state root matches
+        if self.state_root != self.header.state_root {
+            return Err(Error::InvalidCheckpoint("state root mismatch".to_string()));
+        }
+        
         Ok(())
     }
 }
@@ -64,6 +91,9 @@ impl Checkpoint {
 pub struct CheckpointManager {
     /// Checkpoints sorted by height
     checkpoints: BTreeMap<u64, Checkpoint>,
+    
+    /// Keys whose signatures make a checkpoint trusted for adoption
+    trusted_signers: HashSet<PublicKey>,
 }
 
 impl CheckpointManager {
@@ -71,6 +101,7 @@ impl CheckpointManager {
     pub fn new() -> Self {
         let mut manager = Self {
             checkpoints: BTreeMap::new(),
+            trusted_signers: HashSet::new(),
         };
         
         // Add hardcoded checkpoints for testnet/mainnet
@@ -86,6 +117,12 @@ impl CheckpointManager {
         // Additional checkpoints would be added here in production
     }
     
+    /// Trust checkpoints signed by any of `signers`
+    pub fn with_trusted_signers(mut self, signers: impl IntoIterator<Item = PublicKey>) -> Self {
+        self.trusted_signers.extend(signers);
+        self
+    }
+    
     /// Add a checkpoint
     pub fn add_checkpoint(&mut self, checkpoint: Checkpoint) -> Result<()> {
         checkpoint.verify()?;
@@ -93,6 +130,39 @@ impl CheckpointManager {
         Ok(())
     }
     
+    /// Verify a checkpoint and make it the starting point of `chain`
+    ///
+    /// The chain restarts from the checkpoint header, so syncing continues
+    /// from its height instead of genesis. The checkpoint must either match a
+    /// registered checkpoint or carry a signature from a trusted signer, since
+    /// a self-consistent header alone proves nothing about the canonical chain.
+    /// Rejects checkpoints that conflict with a registered checkpoint or with
+    /// headers `chain` already holds.
+    pub fn verify_and_adopt(&mut self, checkpoint: Checkpoint, chain: &HeaderChain) -> Result<()> {
+        checkpoint.verify()?;
+        
+        match self.checkpoints.get(&checkpoint.height) {
+            Some(existing) if existing.hash != checkpoint.hash => {
+                return Err(Error::InvalidCheckpoint(format!(
+                    "conflicts with registered checkpoint at height {}",
+                    checkpoint.height
+                )));
+            }
+            Some(_) => {}
+            None if checkpoint.is_signed_by_any(&self.trusted_signers) => {}
+            None => {
+                return Err(Error::InvalidCheckpoint(format!(
+                    "checkpoint at height {} is not signed by a trusted signer",
+                    checkpoint.height
+                )));
+            }
+        }
+        
+        chain.adopt_checkpoint(&checkpoint)?;
+        self.checkpoints.insert(checkpoint.height, checkpoint);
+        Ok(())
+    }
+    
     /// Get checkpoint at or before a height
     pub fn get_checkpoint_at_or_before(&self, height: u64) -> Option<&Checkpoint> {
         self.checkpoints.range(..=height).next_back().map(|(_, cp)| cp)
@@ -130,6 +200,15 @@ mod tests {
     use super::*;
     use bitcell_crypto::{SecretKey, Hash256};
 
+    fn create_next_header(parent: &BlockHeader) -> BlockHeader {
+        BlockHeader {
+            height: parent.height + 1,
+            prev_hash: parent.hash(),
+            timestamp: parent.timestamp + 10,
+            ..parent.clone()
+        }
+    }
+
     fn create_test_header(height: u64) -> BlockHeader {
         BlockHeader {
             height,
@@ -184,4 +263,93 @@ mod tests {
         assert_eq!(manager.all_checkpoints().len(), 5);
         assert!(manager.get_checkpoint(3000).is_none());
     }
+
+    #[test]
+    fn test_checkpoint_state_root_mismatch() {
+        let mut checkpoint = Checkpoint::new(create_test_header(1000), "CP".to_string());
+        checkpoint.state_root = Hash256::hash(b"forged");
+        
+        assert!(matches!(checkpoint.verify(), Err(Error::InvalidCheckpoint(_))));
+    }
+
+    #[test]
+    fn test_verify_and_adopt() {
+        let chain = HeaderChain::new(create_test_header(0), Default::default());
+        let signer = SecretKey::generate();
+        let mut manager = CheckpointManager::new().with_trusted_signers([signer.public_key()]);
+        
+        let mut checkpoint = Checkpoint::new(create_test_header(1000), "CP 1000".to_string());
+        checkpoint.sign(&signer);
+        manager.verify_and_adopt(checkpoint.clone(), &chain).unwrap();
+        
+        assert_eq!(chain.tip_height(), 1000);
+        assert_eq!(chain.tip_hash(), checkpoint.hash);
+        assert!(chain.get_header(0).is_none());
+        assert_eq!(manager.latest_checkpoint().unwrap().height, 1000);
+        
+        // Headers extend the chain from the checkpoint
+        chain.add_header(create_next_header(&checkpoint.header)).unwrap();
+        assert_eq!(chain.tip_height(), 1001);
+    }
+
+    #[test]
+    fn test_adopt_conflicting_checkpoint() {
+        let genesis = create_test_header(0);
+        let chain = HeaderChain::new(genesis.clone(), Default::default());
+        let mut prev = genesis;
+        for _ in 0..10 {
+            let next = create_next_header(&prev);
+            chain.add_header(next.clone()).unwrap();
+            prev = next;
+        }
+        
+        let signer = SecretKey::generate();
+        let mut manager = CheckpointManager::new().with_trusted_signers([signer.public_key()]);
+        
+        // A different header at an already-synced height
+        let mut forged = Checkpoint::new(create_test_header(5), "Forged".to_string());
+        forged.sign(&signer);
+        let result = manager.verify_and_adopt(forged, &chain);
+        assert!(matches!(result, Err(Error::InvalidCheckpoint(_))));
+        assert_eq!(chain.tip_height(), 10);
+        assert!(manager.get_checkpoint(5).is_none());
+        
+        // The header we already have is accepted without resetting the chain
+        let mut matching = Checkpoint::new(chain.get_header(5).unwrap(), "CP 5".to_string());
+        matching.sign(&signer);
+        manager.verify_and_adopt(matching, &chain).unwrap();
+        assert_eq!(chain.tip_height(), 10);
+    }
+
+    #[test]
+    fn test_adopt_untrusted_checkpoint() {
+        let chain = HeaderChain::new(create_test_header(0), Default::default());
+        let trusted = SecretKey::generate();
+        let mut manager = CheckpointManager::new().with_trusted_signers([trusted.public_key()]);
+        
+        // Self-consistent, but unsigned or signed by an unknown key
+        let mut forged = Checkpoint::new(create_test_header(1000), "Forged".to_string());
+        forged.verify().unwrap();
+        let result = manager.verify_and_adopt(forged.clone(), &chain);
+        assert!(matches!(result, Err(Error::InvalidCheckpoint(_))));
+        
+        forged.sign(&SecretKey::generate());
+        let result = manager.verify_and_adopt(forged.clone(), &chain);
+        assert!(matches!(result, Err(Error::InvalidCheckpoint(_))));
+        
+        // A trusted key's name on someone else's signature doesn't help
+        let (_, sig) = forged.signatures[0];
+        forged.signatures = vec![(trusted.public_key(), sig)];
+        let result = manager.verify_and_adopt(forged, &chain);
+        assert!(matches!(result, Err(Error::InvalidCheckpoint(_))));
+        
+        assert_eq!(chain.tip_height(), 0);
+        assert!(manager.get_checkpoint(1000).is_none());
+        
+        // A registered checkpoint is a known-good root and needs no signature
+        let known = Checkpoint::new(create_test_header(1000), "Known".to_string());
+        manager.add_checkpoint(known.clone()).unwrap();
+        manager.verify_and_adopt(known, &chain).unwrap();
+        assert_eq!(chain.tip_height(), 1000);
+    }
 }
@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::{Result, Error};
+use crate::{Result, Error, Checkpoint};
 
 /// Configuration for header chain
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -23,6 +23,9 @@ pub struct HeaderChainConfig {
     
     /// Minimum checkpoint confirmations before accepting
     pub checkpoint_confirmations: u64,
+    
+    /// Headers to retain when the memory estimate exceeds its cap
+    pub max_headers_in_memory: usize,
 }
 
 impl Default for HeaderChainConfig {
@@ -31,10 +34,17 @@ impl Default for HeaderChainConfig {
             max_headers: 10_000,
             checkpoint_interval: 1_000,
             checkpoint_confirmations: 100,
+            max_headers_in_memory: 10_000,
         }
     }
 }
 
+/// Approximate bytes held per stored header, excluding VRF proof bytes.
+/// Each header is stored in both the height and hash maps, plus a work entry.
+const HEADER_MEMORY_OVERHEAD: usize = 2 * std::mem::size_of::<BlockHeader>()
+    + std::mem::size_of::<Hash256>()
+    + 3 * std::mem::size_of::<u64>();
+
 /// Header-only blockchain storage
 ///
 /// Stores only block headers and validates chain continuity.
@@ -56,6 +66,9 @@ pub struct HeaderChain {
     
     /// Total work at each height (for fork choice)
     total_work: Arc<RwLock<HashMap<u64, u64>>>,
+    
+    /// Height of the trusted base header (genesis or adopted checkpoint)
+    base_height: Arc<RwLock<u64>>,
 }
 
 impl HeaderChain {
@@ -81,6 +94,7 @@ impl HeaderChain {
             tip_height: Arc::new(RwLock::new(genesis_height)),
             tip_hash: Arc::new(RwLock::new(genesis_hash)),
             total_work: Arc::new(RwLock::new(total_work)),
+            base_height: Arc::new(RwLock::new(genesis_height)),
         }
     }
     
@@ -94,6 +108,57 @@ impl HeaderChain {
         *self.tip_hash.read()
     }
     
+    /// Get the height of the trusted base header
+    pub fn base_height(&self) -> u64 {
+        *self.base_height.read()
+    }
+    
+    /// Restart the chain from a trusted checkpoint
+    ///
+    /// Headers below the checkpoint are dropped and new headers must extend
+    /// the checkpoint header. Fails with `Error::InvalidCheckpoint` if the
+    /// checkpoint conflicts with a header already in the chain.
+    pub fn adopt_checkpoint(&self, checkpoint: &Checkpoint) -> Result<()> {
+        checkpoint.verify()?;
+        
+        if let Some(existing) = self.get_header(checkpoint.height) {
+            if existing.hash() != checkpoint.hash {
+                return Err(Error::InvalidCheckpoint(format!(
+                    "conflicts with synced header at height {}",
+                    checkpoint.height
+                )));
+            }
+            return Ok(()); // Already on our chain
+        }
+        
+        if checkpoint.height <= self.tip_height() {
+            return Err(Error::InvalidCheckpoint(format!(
+                "header at height {} was pruned and cannot be compared",
+                checkpoint.height
+            )));
+        }
+        
+        let header = checkpoint.header.clone();
+        let mut headers = self.headers.write();
+        let mut header_by_hash = self.header_by_hash.write();
+        let mut total_work = self.total_work.write();
+        
+        headers.clear();
+        header_by_hash.clear();
+        total_work.clear();
+        
+        // Work before the checkpoint is unknown, so it becomes the new base
+        total_work.insert(checkpoint.height, header.work);
+        headers.insert(checkpoint.height, header.clone());
+        header_by_hash.insert(checkpoint.hash, header);
+        
+        *self.tip_height.write() = checkpoint.height;
+        *self.tip_hash.write() = checkpoint.hash;
+        *self.base_height.write() = checkpoint.height;
+        
+        Ok(())
+    }
+    
     /// Get header by height
     pub fn get_header(&self, height: u64) -> Option<BlockHeader> {
         self.headers.read().get(&height).cloned()
@@ -157,20 +222,11 @@ impl HeaderChain {
         let parent = self.get_header(parent_height)
             .ok_or_else(|| Error::InvalidHeader("missing parent header".to_string()))?;
         
-        // Check parent hash
-        if header.prev_hash != parent.hash() {
-            return Err(Error::InvalidHeader("invalid parent hash".to_string()));
-        }
-        
-        // Check timestamp is after parent
-        if header.timestamp <= parent.timestamp {
-            return Err(Error::InvalidHeader("timestamp not increasing".to_string()));
-        }
-        
-        // VRF validation would go here in production
-        // For now, we trust the headers from checkpointed nodes
-        
-        Ok(())
+        // Parent link, timestamp and work checks are consensus rules.
+        // VRF validation would go here in production; for now we trust the
+        // headers from checkpointed nodes.
+        header.validate_against_parent(&parent)
+            .map_err(|e| Error::InvalidHeader(e.to_string()))
     }
     
     /// Prune old headers to maintain memory limits
@@ -184,24 +240,60 @@ impl HeaderChain {
             0
         };
         
+        self.evict_below(keep_from, None);
+        
+        Ok(())
+    }
+    
+    /// Evict headers until the chain fits within `max_headers_in_memory`
+    ///
+    /// Headers below the latest checkpoint go first, since the checkpoint
+    /// already vouches for them; if that is not enough the oldest remaining
+    /// headers are evicted. The trusted base, the checkpoint header itself and
+    /// the most recent headers are always kept. Returns the number evicted.
+    pub fn prune_to_cap(&self, checkpoint_height: Option<u64>) -> usize {
+        let tip_height = *self.tip_height.read();
+        let window_start = (tip_height + 1)
+            .saturating_sub(self.config.max_headers_in_memory as u64);
+        let keep_from = checkpoint_height
+            .filter(|&h| h <= tip_height)
+            .map_or(window_start, |h| h.max(window_start));
+        
+        self.evict_below(keep_from, checkpoint_height)
+    }
+    
+    /// Whether the memory estimate exceeds the configured cap
+    pub fn exceeds_memory_cap(&self) -> bool {
+        self.memory_estimate() > self.memory_cap()
+    }
+    
+    /// Approximate byte budget for `max_headers_in_memory` headers
+    pub fn memory_cap(&self) -> usize {
+        self.config.max_headers_in_memory.saturating_mul(HEADER_MEMORY_OVERHEAD)
+    }
+    
+    /// Remove headers below `keep_from`, except the base and `keep`
+    fn evict_below(&self, keep_from: u64, keep: Option<u64>) -> usize {
+        let base_height = *self.base_height.read();
+        
         let mut headers = self.headers.write();
         let mut header_by_hash = self.header_by_hash.write();
         let mut total_work = self.total_work.write();
         
         // Remove old headers
         let heights_to_remove: Vec<u64> = headers.keys()
-            .filter(|&&h| h < keep_from && h > 0) // Keep genesis
+            .filter(|&&h| h < keep_from && h != base_height && Some(h) != keep) // Keep the trusted base and pinned header
             .copied()
             .collect();
         
-        for height in heights_to_remove {
+        for &height in &heights_to_remove {
             if let Some(header) = headers.remove(&height) {
                 header_by_hash.remove(&header.hash());
             }
             total_work.remove(&height);
         }
         
-        Ok(())
+        heights_to_remove.len()
     }
     
     /// Get total work at a given height
@@ -219,11 +311,18 @@ impl HeaderChain {
     
     /// Get estimated memory usage in bytes
     pub fn memory_usage(&self) -> usize {
-        let header_count = self.headers.read().len();
-        // Approximate: each header ~500 bytes with overhead
-        header_count * 500 + 
-        self.header_by_hash.read().len() * 32 + // Hash keys
-        self.total_work.read().len() * 16 // Height->work map
+        self.memory_estimate()
+    }
+    
+    /// Approximate byte footprint of the stored headers
+    ///
+    /// Counts the fixed per-header overhead plus VRF proof bytes, which are
+    /// held once in each index. Allocator and hash map slack is not included.
+    pub fn memory_estimate(&self) -> usize {
+        self.headers.read()
+            .values()
+            .map(|h| HEADER_MEMORY_OVERHEAD + 2 * h.vrf_proof.len())
+            .sum()
     }
 }
 
@@ -314,4 +413,32 @@ mod tests {
         let memory = chain.memory_usage();
         assert!(memory < 20 * 500 + 10000); // Less than full 20 headers
     }
+
+    #[test]
+    fn test_prune_to_cap_keeps_checkpoint() {
+        let genesis = create_genesis();
+        let config = HeaderChainConfig {
+            max_headers_in_memory: 10,
+            ..HeaderChainConfig::default()
+        };
+        let chain = HeaderChain::new(genesis.clone(), config);
+        
+        let mut prev = genesis;
+        for _ in 0..50 {
+            let next = create_next_header(&prev);
+            chain.add_header(next.clone()).unwrap();
+            prev = next;
+        }
+        assert!(chain.exceeds_memory_cap());
+        
+        let evicted = chain.prune_to_cap(Some(20));
+        
+        // Genesis, the checkpoint and the last 10 headers remain
+        assert_eq!(evicted, 51 - 12);
+        assert!(chain.get_header(0).is_some());
+        assert!(chain.get_header(20).is_some());
+        assert!(chain.get_header(40).is_none());
+        assert!(chain.get_header(41).is_some());
+        assert_eq!(chain.memory_estimate(), 12 * HEADER_MEMORY_OVERHEAD);
+    }
 }
@@ -31,7 +31,7 @@ pub mod protocol;
 pub mod checkpoints;
 
 pub use header_chain::{HeaderChain, HeaderChainConfig};
-pub use sync::{HeaderSync, SyncStatus};
+pub use sync::{HeaderSync, HeaderSource, SyncStatus};
 pub use proofs::{StateProof, StateProofRequest};
 pub use wallet::{LightWallet, WalletMode};
 pub use protocol::{LightClientMessage, LightClientProtocol};
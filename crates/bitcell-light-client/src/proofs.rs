@@ -2,7 +2,7 @@
 //!
 //! Light clients verify state by requesting Merkle proofs from full nodes.
 
-use bitcell_crypto::{Hash256, merkle::MerkleProof};
+use bitcell_crypto::{Hash256, account_leaf, merkle::MerkleProof};
 use serde::{Deserialize, Serialize};
 
 use crate::{Result, Error};
@@ -84,6 +84,9 @@ pub struct StateProof {
     pub proof: MerkleProof,
     
     /// The actual value (encoded)
+    ///
+    /// For account proofs this is `encode_account_value(balance, nonce)`,
+    /// since the state tree leaf commits to both.
     pub value: Vec<u8>,
     
     /// Whether the key exists in the state
@@ -96,27 +99,7 @@ impl StateProof {
     /// Returns Ok(()) if the proof is valid and the key exists (or doesn't exist as claimed).
     /// Returns Err if the state root mismatches or the Merkle proof is invalid.
     pub fn verify(&self, expected_state_root: &Hash256) -> Result<()> {
-        // Check state root matches
-        if self.state_root != *expected_state_root {
-            return Err(Error::InvalidProof(
-                "state root mismatch".to_string()
-            ));
-        }
-        
-        // Verify the Merkle proof
-        let valid = bitcell_crypto::MerkleTree::verify_proof(
-            self.state_root,
-            &self.proof
-        );
-        
-        if !valid {
-            return Err(Error::InvalidProof(
-                "merkle proof verification failed".to_string()
-            ));
-        }
-        
-        // Proof is valid
-        Ok(())
+        verify_state_proof(self, expected_state_root)
     }
     
     /// Extract balance from a balance proof
@@ -131,9 +114,7 @@ impl StateProof {
             return Ok(0); // Account doesn't exist = 0 balance
         }
         
-        // Decode balance from value
-        bincode::deserialize(&self.value)
-            .map_err(|e| Error::InvalidProof(format!("failed to decode balance: {}", e)))
+        decode_account_value(&self.value).map(|(balance, _)| balance)
     }
     
     /// Extract nonce from a nonce proof
@@ -148,9 +129,7 @@ impl StateProof {
             return Ok(0); // Account doesn't exist = 0 nonce
         }
         
-        // Decode nonce from value
-        bincode::deserialize(&self.value)
-            .map_err(|e| Error::InvalidProof(format!("failed to decode nonce: {}", e)))
+        decode_account_value(&self.value).map(|(_, nonce)| nonce)
     }
     
     /// Check if transaction is included
@@ -165,6 +144,80 @@ impl StateProof {
     }
 }
 
+/// A full node that can answer state proof requests
+pub trait StateProofSource {
+    /// Fetch a proof answering `request`
+    fn get_state_proof(&self, request: &StateProofRequest) -> Result<StateProof>;
+}
+
+/// Encode an account's balance and nonce as a proof value
+pub fn encode_account_value(balance: u64, nonce: u64) -> Vec<u8> {
+    let mut value = Vec::with_capacity(16);
+    value.extend_from_slice(&balance.to_le_bytes());
+    value.extend_from_slice(&nonce.to_le_bytes());
+    value
+}
+
+/// Decode a proof value produced by `encode_account_value`
+fn decode_account_value(value: &[u8]) -> Result<(u64, u64)> {
+    let value: [u8; 16] = value.try_into()
+        .map_err(|_| Error::InvalidProof("account value must be 16 bytes".to_string()))?;
+    let (balance, nonce) = value.split_at(8);
+    Ok((
+        u64::from_le_bytes(balance.try_into().expect("split at 8")),
+        u64::from_le_bytes(nonce.try_into().expect("split at 8")),
+    ))
+}
+
+/// Compute the state tree leaf a proof claims
+///
+/// Account proofs use the same leaf as the state manager; other proof types
+/// commit to `key || value`.
+fn proof_leaf(proof: &StateProof) -> Result<Hash256> {
+    match proof.request.proof_type {
+        StateProofType::AccountBalance | StateProofType::AccountNonce => {
+            let (balance, nonce) = decode_account_value(&proof.value)?;
+            Ok(account_leaf(&proof.request.key, balance, nonce))
+        }
+        StateProofType::TransactionInclusion | StateProofType::StorageSlot => {
+            Ok(Hash256::hash_multiple(&[&proof.request.key, &proof.value]))
+        }
+    }
+}
+
+/// Verify a state proof against a state root taken from the header chain
+///
+/// The leaf is recomputed from the proof's key and value, then checked against
+/// the root with `MerkleTree::verify_proof`. Only membership proofs can be
+/// checked against a binary Merkle tree, so proofs claiming absence are
+/// rejected.
+pub fn verify_state_proof(proof: &StateProof, expected_root: &Hash256) -> Result<()> {
+    if proof.state_root != *expected_root {
+        return Err(Error::InvalidProof("state root mismatch".to_string()));
+    }
+    
+    if !proof.exists {
+        return Err(Error::InvalidProof(
+            "non-membership proofs cannot be verified".to_string()
+        ));
+    }
+    
+    let leaf = proof_leaf(proof)?;
+    if proof.proof.leaf != leaf {
+        return Err(Error::InvalidProof(
+            "leaf does not commit to the claimed value".to_string()
+        ));
+    }
+    
+    if !bitcell_crypto::MerkleTree::verify_proof(*expected_root, &proof.proof) {
+        return Err(Error::InvalidProof(
+            "merkle proof verification failed".to_string()
+        ));
+    }
+    
+    Ok(())
+}
+
 /// Batch proof request for multiple state items
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchProofRequest {
@@ -198,6 +251,86 @@ impl BatchProofResponse {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bitcell_crypto::MerkleTree;
+
+    /// Build a balance proof for `account` from a small state tree
+    fn balance_proof(account: &[u8], balance: u64) -> (StateProof, Hash256) {
+        let value = encode_account_value(balance, 3);
+        let leaves = vec![
+            account_leaf(b"alice", 7, 0),
+            account_leaf(account, balance, 3),
+            account_leaf(b"carol", 9, 1),
+        ];
+        let tree = MerkleTree::new(leaves);
+        let root = tree.root();
+        
+        let proof = StateProof {
+            request: StateProofRequest::balance(10, account),
+            state_root: root,
+            proof: tree.prove(1).unwrap(),
+            value,
+            exists: true,
+        };
+        (proof, root)
+    }
+
+    #[test]
+    fn test_verify_valid_state_proof() {
+        let (proof, root) = balance_proof(b"bob", 5_000);
+        
+        assert!(verify_state_proof(&proof, &root).is_ok());
+        assert_eq!(proof.extract_balance().unwrap(), 5_000);
+    }
+
+    #[test]
+    fn test_verify_tampered_leaf() {
+        let (mut proof, root) = balance_proof(b"bob", 5_000);
+        proof.proof.leaf = account_leaf(b"bob", 1_000_000, 3);
+        
+        assert!(matches!(verify_state_proof(&proof, &root), Err(Error::InvalidProof(_))));
+    }
+
+    #[test]
+    fn test_verify_tampered_value() {
+        let (mut proof, root) = balance_proof(b"bob", 5_000);
+        proof.value = encode_account_value(1_000_000, 3);
+        
+        assert!(matches!(verify_state_proof(&proof, &root), Err(Error::InvalidProof(_))));
+    }
+
+    #[test]
+    fn test_verify_wrong_root() {
+        let (proof, _) = balance_proof(b"bob", 5_000);
+        
+        assert!(verify_state_proof(&proof, &Hash256::hash(b"other")).is_err());
+    }
+
+    #[test]
+    fn test_verify_proof_from_state_manager() {
+        let mut state = bitcell_state::StateManager::new();
+        let accounts: Vec<[u8; 33]> = (1..=5u8).map(|i| [i; 33]).collect();
+        for (i, pubkey) in accounts.iter().enumerate() {
+            state.credit_account(*pubkey, 1_000 * (i as u64 + 1)).unwrap();
+        }
+        let bob = accounts[2];
+
+        let (account, merkle_proof) = state.prove_account(&bob).unwrap();
+        let proof = StateProof {
+            request: StateProofRequest::balance(10, &bob),
+            state_root: state.state_root,
+            proof: merkle_proof,
+            value: encode_account_value(account.balance, account.nonce),
+            exists: true,
+        };
+
+        assert!(verify_state_proof(&proof, &state.state_root).is_ok());
+        assert_eq!(proof.extract_balance().unwrap(), 3_000);
+
+        // A forged balance no longer matches the state manager's leaf
+        let mut forged = proof.clone();
+        forged.value = encode_account_value(1_000_000, account.nonce);
+        assert!(verify_state_proof(&forged, &state.state_root).is_err());
+    }
 
     #[test]
     fn test_proof_request_creation() {
@@ -222,7 +355,7 @@ mod tests {
                 leaf: Hash256::hash(b"leaf"),
                 path: vec![],
             },
-            value: bincode::serialize(&1000u64).unwrap(),
+            value: encode_account_value(1000, 0),
             exists: true,
         };
         
@@ -245,7 +378,7 @@ mod tests {
                 leaf: Hash256::hash(b"leaf"),
                 path: vec![],
             },
-            value: bincode::serialize(&balance).unwrap(),
+            value: encode_account_value(balance, 0),
             exists: true,
         };
         
@@ -5,10 +5,10 @@
 use bitcell_consensus::BlockHeader;
 use parking_lot::RwLock;
 use std::sync::Arc;
-use tokio::time::{sleep, Duration};
 
 use crate::{
-    Result, HeaderChain, CheckpointManager, Checkpoint,
+    Result, Error, HeaderChain, CheckpointManager, Checkpoint,
+    protocol::GetHeadersRequest,
 };
 
 /// Sync status
@@ -20,8 +20,13 @@ pub enum SyncStatus {
     /// Syncing from checkpoint
     SyncingFromCheckpoint,
     
-    /// Syncing headers
-    SyncingHeaders,
+    /// Requesting headers from peers
+    Syncing {
+        /// Current tip height
+        current: u64,
+        /// Height being synced to
+        target: u64,
+    },
     
     /// Fully synced
     Synced,
@@ -30,7 +35,17 @@ pub enum SyncStatus {
     Error,
 }
 
+/// A peer that can serve ranges of block headers
+pub trait HeaderSource {
+    /// Fetch the headers described by `request`, in ascending height order
+    fn get_headers(&self, request: &GetHeadersRequest) -> Result<Vec<BlockHeader>>;
+}
+
 /// Header synchronization manager
+///
+/// Drives a request/response loop: [`HeaderSync::step`] yields the next
+/// batch to request from the current tip and [`HeaderSync::handle_headers`]
+/// validates and applies the peer's response.
 pub struct HeaderSync {
     /// Header chain being synced
     header_chain: Arc<HeaderChain>,
@@ -63,6 +78,12 @@ impl HeaderSync {
         }
     }
     
+    /// Set the number of headers requested per batch
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+    
     /// Get current sync status
     pub fn status(&self) -> SyncStatus {
         *self.status.read()
@@ -84,8 +105,14 @@ impl HeaderSync {
         ((current as f64) / (target as f64)).min(1.0)
     }
     
-    /// Start syncing to a target height
-    pub async fn sync_to(&self, target_height: u64) -> Result<()> {
+    /// Set the height to sync to without starting any requests
+    pub fn set_target(&self, target_height: u64) {
+        *self.target_height.write() = Some(target_height);
+        self.refresh_status(target_height);
+    }
+    
+    /// Start syncing to a target height using headers served by `source`
+    pub async fn sync_to<S: HeaderSource + ?Sized>(&self, source: &S, target_height: u64) -> Result<()> {
         *self.target_height.write() = Some(target_height);
         *self.status.write() = SyncStatus::SyncingFromCheckpoint;
         
@@ -97,16 +124,131 @@ impl HeaderSync {
         }
         
         // Then sync remaining headers
-        *self.status.write() = SyncStatus::SyncingHeaders;
-        self.sync_remaining_headers(target_height).await?;
+        self.sync_with(source, target_height)
+    }
+    
+    /// Run the request/response loop against `source` until the target is reached
+    pub fn sync_with<S: HeaderSource + ?Sized>(&self, source: &S, target_height: u64) -> Result<()> {
+        self.set_target(target_height);
         
-        // Only mark as synced if we actually reached the target
-        if self.header_chain.tip_height() >= target_height {
-            *self.status.write() = SyncStatus::Synced;
+        while let Some(request) = self.step() {
+            tracing::debug!(
+                "Requesting headers from {} to {}",
+                request.start_height,
+                request.end_height
+            );
+            
+            let headers = match source.get_headers(&request) {
+                Ok(headers) => headers,
+                Err(e) => {
+                    *self.status.write() = SyncStatus::Error;
+                    return Err(e);
+                }
+            };
+            
+            self.handle_headers(headers)?;
         }
+        
         Ok(())
     }
     
+    /// Get the next batch of headers to request, if any
+    ///
+    /// Returns `None` when no target is set or the tip has reached it.
+    pub fn step(&self) -> Option<GetHeadersRequest> {
+        let target = (*self.target_height.read())?;
+        let tip = self.header_chain.tip_height();
+        
+        self.refresh_status(target);
+        if tip >= target {
+            return None;
+        }
+        
+        let start = tip + 1;
+        let end = std::cmp::min(tip.saturating_add(self.batch_size as u64), target);
+        Some(GetHeadersRequest::new(start, end, (end - start + 1) as usize))
+    }
+    
+    /// Validate and apply a batch of headers received in response to [`HeaderSync::step`]
+    ///
+    /// Every header must link to its predecessor and pass the consensus
+    /// header checks. The whole batch is rejected if any header is invalid,
+    /// or if it holds more headers than the request or the target allow.
+    pub fn handle_headers(&self, headers: Vec<BlockHeader>) -> Result<usize> {
+        let result = self.apply_headers(headers);
+        
+        match &result {
+            Ok(_) => {
+                let target = *self.target_height.read();
+                if let Some(target) = target {
+                    self.refresh_status(target);
+                }
+            }
+            Err(_) => *self.status.write() = SyncStatus::Error,
+        }
+        
+        result
+    }
+    
+    /// Validate a batch against the current tip, then append it
+    fn apply_headers(&self, headers: Vec<BlockHeader>) -> Result<usize> {
+        if headers.is_empty() {
+            return Err(Error::SyncError("peer returned no headers".to_string()));
+        }
+        
+        let tip_height = self.header_chain.tip_height();
+        
+        // Headers are consecutive from the tip, so bounding the count bounds
+        // the heights to the batch that `step` requested
+        let mut end_height = tip_height.saturating_add(self.batch_size as u64);
+        if let Some(target) = *self.target_height.read() {
+            end_height = end_height.min(target);
+        }
+        if headers.len() as u64 > end_height.saturating_sub(tip_height) {
+            return Err(Error::SyncError(format!(
+                "peer returned {} headers past height {}, requested up to height {}",
+                headers.len(),
+                tip_height,
+                end_height
+            )));
+        }
+        
+        let mut parent = self.header_chain.get_header(tip_height)
+            .ok_or_else(|| Error::SyncError("missing tip header".to_string()))?;
+        
+        for header in &headers {
+            header.validate_against_parent(&parent).map_err(|e| {
+                Error::InvalidHeader(format!("header at height {}: {}", header.height, e))
+            })?;
+            parent = header.clone();
+        }
+        
+        let count = headers.len();
+        for header in headers {
+            self.header_chain.add_header(header)?;
+        }
+        
+        if self.header_chain.exceeds_memory_cap() {
+            let checkpoint_height = self.checkpoint_manager.read()
+                .latest_checkpoint()
+                .map(|cp| cp.height);
+            let evicted = self.header_chain.prune_to_cap(checkpoint_height);
+            tracing::debug!("Evicted {} headers to stay within memory cap", evicted);
+        }
+        
+        Ok(count)
+    }
+    
+    /// Recompute the status from the current tip and target
+    fn refresh_status(&self, target: u64) {
+        let current = self.header_chain.tip_height();
+        *self.status.write() = if current >= target {
+            SyncStatus::Synced
+        } else {
+            SyncStatus::Syncing { current, target }
+        };
+    }
+    
     /// Sync from the best checkpoint
     async fn sync_from_checkpoint(&self, target_height: u64) -> Result<()> {
         let checkpoint_manager = self.checkpoint_manager.read();
@@ -115,42 +257,13 @@ impl HeaderSync {
         if let Some(cp) = checkpoint {
             if cp.height > self.header_chain.tip_height() {
                 tracing::info!("Using checkpoint at height {}", cp.height);
-                self.header_chain.add_header(cp.header.clone())?;
+                self.header_chain.adopt_checkpoint(cp)?;
             }
         }
         
         Ok(())
     }
     
-    /// Sync remaining headers from current tip to target
-    async fn sync_remaining_headers(&self, target_height: u64) -> Result<()> {
-        let mut current_height = self.header_chain.tip_height();
-        
-        while current_height < target_height {
-            let end_height = std::cmp::min(
-                current_height + self.batch_size as u64,
-                target_height
-            );
-            
-            // In a real implementation, this would request headers from peers
-            // For now, this is a placeholder that would be implemented with
-            // the actual network protocol
-            tracing::debug!(
-                "Would request headers from {} to {}",
-                current_height + 1,
-                end_height
-            );
-            
-            // Simulate some network delay
-            sleep(Duration::from_millis(100)).await;
-            
-            // Break if no progress (would need peer responses in real impl)
-            break;
-        }
-        
-        Ok(())
-    }
-    
     /// Add a batch of headers received from a peer
     pub fn add_header_batch(&self, headers: Vec<BlockHeader>) -> Result<usize> {
         let mut added = 0;
@@ -269,4 +382,194 @@ mod tests {
         let progress = sync.progress();
         assert!(progress < 0.01);
     }
+
+    /// In-memory peer serving a pre-built chain
+    struct MockSource {
+        chain: Vec<BlockHeader>,
+    }
+
+    impl MockSource {
+        fn new(genesis: &BlockHeader, length: u64) -> Self {
+            let mut chain = vec![genesis.clone()];
+            for _ in 0..length {
+                let next = create_next_header(chain.last().unwrap());
+                chain.push(next);
+            }
+            Self { chain }
+        }
+    }
+
+    impl HeaderSource for MockSource {
+        fn get_headers(&self, request: &GetHeadersRequest) -> Result<Vec<BlockHeader>> {
+            Ok(self.chain
+                .iter()
+                .filter(|h| h.height >= request.start_height && h.height <= request.end_height)
+                .take(request.max_count)
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_step_requests_batches_from_tip() {
+        let genesis = create_genesis();
+        let chain = Arc::new(HeaderChain::new(genesis, HeaderChainConfig::default()));
+        let checkpoint_manager = Arc::new(RwLock::new(CheckpointManager::new()));
+        let sync = HeaderSync::new(chain, checkpoint_manager).with_batch_size(16);
+        
+        assert!(sync.step().is_none());
+        
+        sync.set_target(50);
+        let request = sync.step().unwrap();
+        assert_eq!(request.start_height, 1);
+        assert_eq!(request.end_height, 16);
+        assert_eq!(request.max_count, 16);
+        assert_eq!(sync.status(), SyncStatus::Syncing { current: 0, target: 50 });
+    }
+
+    #[test]
+    fn test_sync_mock_chain_to_synced() {
+        let genesis = create_genesis();
+        let source = MockSource::new(&genesis, 50);
+        let chain = Arc::new(HeaderChain::new(genesis, HeaderChainConfig::default()));
+        let checkpoint_manager = Arc::new(RwLock::new(CheckpointManager::new()));
+        let sync = HeaderSync::new(chain.clone(), checkpoint_manager).with_batch_size(16);
+        
+        sync.sync_with(&source, 50).unwrap();
+        
+        assert_eq!(sync.status(), SyncStatus::Synced);
+        assert_eq!(chain.tip_height(), 50);
+        assert_eq!(chain.tip_hash(), source.chain[50].hash());
+        assert_eq!(sync.progress(), 1.0);
+        assert!(sync.step().is_none());
+    }
+
+    #[test]
+    fn test_rejects_broken_parent_link() {
+        let genesis = create_genesis();
+        let mut source = MockSource::new(&genesis, 50);
+        source.chain[20].prev_hash = Hash256::zero();
+        
+        let chain = Arc::new(HeaderChain::new(genesis, HeaderChainConfig::default()));
+        let checkpoint_manager = Arc::new(RwLock::new(CheckpointManager::new()));
+        let sync = HeaderSync::new(chain.clone(), checkpoint_manager).with_batch_size(16);
+        
+        let result = sync.sync_with(&source, 50);
+        
+        assert!(matches!(result, Err(Error::InvalidHeader(_))));
+        assert_eq!(sync.status(), SyncStatus::Error);
+        // The first batch was applied, the batch containing the bad header was not
+        assert_eq!(chain.tip_height(), 16);
+    }
+
+    /// Peer that ignores the requested range and serves its whole chain
+    struct OverfullSource(MockSource);
+
+    impl HeaderSource for OverfullSource {
+        fn get_headers(&self, request: &GetHeadersRequest) -> Result<Vec<BlockHeader>> {
+            Ok(self.0.chain[request.start_height as usize..].to_vec())
+        }
+    }
+
+    #[test]
+    fn test_rejects_headers_beyond_request() {
+        let genesis = create_genesis();
+        let source = OverfullSource(MockSource::new(&genesis, 50));
+        let chain = Arc::new(HeaderChain::new(genesis, HeaderChainConfig::default()));
+        let checkpoint_manager = Arc::new(RwLock::new(CheckpointManager::new()));
+        
+        // More headers than the batch size
+        let sync = HeaderSync::new(chain.clone(), checkpoint_manager.clone()).with_batch_size(16);
+        assert!(matches!(sync.sync_with(&source, 50), Err(Error::SyncError(_))));
+        assert_eq!(sync.status(), SyncStatus::Error);
+        assert_eq!(chain.tip_height(), 0);
+        
+        // Headers past the sync target
+        let sync = HeaderSync::new(chain.clone(), checkpoint_manager).with_batch_size(100);
+        assert!(matches!(sync.sync_with(&source, 20), Err(Error::SyncError(_))));
+        assert_eq!(chain.tip_height(), 0);
+    }
+
+    #[test]
+    fn test_sync_forward_from_adopted_checkpoint() {
+        let genesis = create_genesis();
+        let checkpoint_header = BlockHeader {
+            height: 1000,
+            timestamp: 10_000,
+            prev_hash: Hash256::hash(b"block 999"),
+            ..create_genesis()
+        };
+        let source = MockSource::new(&checkpoint_header, 20);
+        
+        let chain = Arc::new(HeaderChain::new(genesis, HeaderChainConfig::default()));
+        let signer = SecretKey::generate();
+        let checkpoint_manager = Arc::new(RwLock::new(
+            CheckpointManager::new().with_trusted_signers([signer.public_key()]),
+        ));
+        let mut checkpoint = Checkpoint::new(checkpoint_header, "CP 1000".to_string());
+        checkpoint.sign(&signer);
+        checkpoint_manager.write().verify_and_adopt(checkpoint, &chain).unwrap();
+        
+        let sync = HeaderSync::new(chain.clone(), checkpoint_manager).with_batch_size(8);
+        sync.set_target(1020);
+        assert_eq!(sync.step().unwrap().start_height, 1001);
+        
+        sync.sync_with(&source, 1020).unwrap();
+        assert_eq!(sync.status(), SyncStatus::Synced);
+        assert_eq!(chain.tip_height(), 1020);
+        assert_eq!(chain.base_height(), 1000);
+    }
+
+    /// Peer generating a linear chain on demand, holding only the last header
+    struct SyntheticSource {
+        last: std::cell::RefCell<BlockHeader>,
+    }
+
+    impl HeaderSource for SyntheticSource {
+        fn get_headers(&self, request: &GetHeadersRequest) -> Result<Vec<BlockHeader>> {
+            let mut last = self.last.borrow_mut();
+            assert_eq!(last.height + 1, request.start_height);
+            
+            let mut headers = Vec::with_capacity(request.max_count);
+            while headers.len() < request.max_count {
+                let next = BlockHeader {
+                    height: last.height + 1,
+                    prev_hash: last.hash(),
+                    timestamp: last.timestamp + 10,
+                    ..last.clone()
+                };
+                headers.push(next.clone());
+                *last = next;
+            }
+            Ok(headers)
+        }
+    }
+
+    #[test]
+    fn test_memory_stays_bounded_during_long_sync() {
+        let genesis = create_genesis();
+        let source = SyntheticSource { last: std::cell::RefCell::new(genesis.clone()) };
+        let config = HeaderChainConfig {
+            max_headers: usize::MAX,
+            max_headers_in_memory: 1_000,
+            ..HeaderChainConfig::default()
+        };
+        let chain = Arc::new(HeaderChain::new(genesis, config));
+        let checkpoint_manager = Arc::new(RwLock::new(CheckpointManager::new()));
+        let sync = HeaderSync::new(chain.clone(), checkpoint_manager);
+        
+        sync.set_target(100_000);
+        let mut peak = 0;
+        while let Some(request) = sync.step() {
+            sync.handle_headers(source.get_headers(&request).unwrap()).unwrap();
+            peak = peak.max(chain.memory_estimate());
+        }
+        
+        assert_eq!(sync.status(), SyncStatus::Synced);
+        assert_eq!(chain.tip_height(), 100_000);
+        // Genesis plus the retained window
+        assert!(peak <= chain.memory_cap() + chain.memory_cap() / 1_000);
+        assert!(chain.get_header(100_000 - 999).is_some());
+        assert!(chain.get_header(100_000 - 1_000).is_none());
+    }
 }
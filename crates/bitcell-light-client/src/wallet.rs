@@ -11,6 +11,7 @@ use std::sync::Arc;
 use crate::{
     Result, Error, HeaderChain, StateProofRequest, StateProof,
     LightClientProtocol, LightClientMessage,
+    proofs::{verify_state_proof, StateProofSource},
 };
 
 /// Wallet operating mode
@@ -62,6 +63,9 @@ pub struct LightWallet {
     
     /// Pending transactions
     pending_txs: Arc<RwLock<Vec<Hash256>>>,
+    
+    /// Full node answering state proof requests
+    proof_source: Option<Arc<dyn StateProofSource + Send + Sync>>,
 }
 
 impl LightWallet {
@@ -79,6 +83,7 @@ impl LightWallet {
             protocol,
             account_cache: Arc::new(RwLock::new(HashMap::new())),
             pending_txs: Arc::new(RwLock::new(Vec::new())),
+            proof_source: None,
         }
     }
     
@@ -98,9 +103,16 @@ impl LightWallet {
             protocol,
             account_cache: Arc::new(RwLock::new(HashMap::new())),
             pending_txs: Arc::new(RwLock::new(Vec::new())),
+            proof_source: None,
         }
     }
     
+    /// Use `source` to fetch state proofs for balance queries
+    pub fn with_proof_source(mut self, source: Arc<dyn StateProofSource + Send + Sync>) -> Self {
+        self.proof_source = Some(source);
+        self
+    }
+    
     /// Get wallet address
     pub fn address(&self) -> &PublicKey {
         &self.public_key
@@ -129,13 +141,21 @@ impl LightWallet {
         
         // Need to request proof from full node
         let tip_height = self.header_chain.tip_height();
-        let _request = StateProofRequest::balance(tip_height, account.as_bytes());
+        let request = StateProofRequest::balance(tip_height, account.as_bytes());
         
-        // In a real implementation, this would send the request over the network
-        // For now, we return an error indicating network communication is needed
-        Err(Error::NetworkError(
-            "network proof request not implemented - requires full node connection".to_string()
-        ))
+        let source = self.proof_source.as_ref().ok_or_else(|| Error::NetworkError(
+            "no full node connection for state proof requests".to_string()
+        ))?;
+        let proof = source.get_state_proof(&request)?;
+        
+        if proof.request.block_height != request.block_height || proof.request.key != request.key {
+            return Err(Error::InvalidProof("proof does not answer the request".to_string()));
+        }
+        
+        let balance = proof.extract_balance()?;
+        self.update_from_proof(proof)?;
+        
+        Ok(balance)
     }
     
     /// Update account info from a state proof
@@ -145,7 +165,7 @@ impl LightWallet {
             .ok_or_else(|| Error::InvalidProof("block not in header chain".to_string()))?;
         
         // Verify proof against state root
-        proof.verify(&header.state_root)?;
+        verify_state_proof(&proof, &header.state_root)?;
         
         // Extract account info based on proof type
         if let Ok(balance) = proof.extract_balance() {
@@ -215,6 +235,7 @@ impl LightWallet {
             gas_limit,
             gas_price,
             data: vec![],
+            spends: vec![],
             signature: Signature::from_bytes([0u8; 64]),
         };
         
@@ -281,8 +302,10 @@ impl LightWallet {
 mod tests {
     use super::*;
     use crate::{HeaderChainConfig};
+    use crate::proofs::encode_account_value;
+    use bitcell_crypto::account_leaf;
     use bitcell_consensus::BlockHeader;
-    use bitcell_crypto::{Hash256, SecretKey};
+    use bitcell_crypto::{Hash256, MerkleTree, SecretKey};
 
     fn create_genesis() -> BlockHeader {
         BlockHeader {
@@ -368,4 +391,81 @@ mod tests {
         assert!(usage > 0);
         assert!(usage < 10_000); // Should be very small initially
     }
+
+    /// Full node serving a fixed proof, optionally with a forged value
+    struct MockProofSource {
+        tree: MerkleTree,
+        account: PublicKey,
+        balance: u64,
+        forged_balance: Option<u64>,
+    }
+
+    impl MockProofSource {
+        fn new(account: PublicKey, balance: u64) -> Self {
+            let leaves = vec![
+                account_leaf(b"other", 1, 0),
+                account_leaf(account.as_bytes(), balance, 0),
+            ];
+            Self { tree: MerkleTree::new(leaves), account, balance, forged_balance: None }
+        }
+    }
+
+    impl StateProofSource for MockProofSource {
+        fn get_state_proof(&self, request: &StateProofRequest) -> Result<StateProof> {
+            assert_eq!(request.key, self.account.as_bytes().to_vec());
+            let balance = self.forged_balance.unwrap_or(self.balance);
+            Ok(StateProof {
+                request: request.clone(),
+                state_root: self.tree.root(),
+                proof: self.tree.prove(1).unwrap(),
+                value: encode_account_value(balance, 0),
+                exists: true,
+            })
+        }
+    }
+
+    fn chain_with_state_root(state_root: Hash256) -> Arc<HeaderChain> {
+        let genesis = BlockHeader { state_root, ..create_genesis() };
+        Arc::new(HeaderChain::new(genesis, HeaderChainConfig::default()))
+    }
+
+    #[tokio::test]
+    async fn test_get_balance_with_verified_proof() {
+        let pk = SecretKey::generate().public_key();
+        let source = MockProofSource::new(pk, 12_345);
+        let chain = chain_with_state_root(source.tree.root());
+        let protocol = Arc::new(LightClientProtocol::new());
+        
+        let wallet = LightWallet::read_only(pk, chain, protocol)
+            .with_proof_source(Arc::new(source));
+        
+        assert_eq!(wallet.get_balance().await.unwrap(), 12_345);
+        assert_eq!(wallet.cached_account_info(&pk).unwrap().balance, 12_345);
+    }
+
+    #[tokio::test]
+    async fn test_get_balance_rejects_forged_value() {
+        let pk = SecretKey::generate().public_key();
+        let mut source = MockProofSource::new(pk, 12_345);
+        source.forged_balance = Some(1_000_000);
+        let chain = chain_with_state_root(source.tree.root());
+        let protocol = Arc::new(LightClientProtocol::new());
+        
+        let wallet = LightWallet::read_only(pk, chain, protocol)
+            .with_proof_source(Arc::new(source));
+        
+        assert!(matches!(wallet.get_balance().await, Err(Error::InvalidProof(_))));
+        assert!(wallet.cached_account_info(&pk).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_balance_without_source() {
+        let pk = SecretKey::generate().public_key();
+        let chain = chain_with_state_root(Hash256::zero());
+        let protocol = Arc::new(LightClientProtocol::new());
+        
+        let wallet = LightWallet::read_only(pk, chain, protocol);
+        
+        assert!(matches!(wallet.get_balance().await, Err(Error::NetworkError(_))));
+    }
 }
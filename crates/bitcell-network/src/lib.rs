@@ -26,6 +26,7 @@ pub mod transport;
 
 pub use messages::{Message, MessageType};
 pub use peer::{PeerInfo, PeerManager, PeerReputation};
+pub use transport::{FrameCodec, DEFAULT_MAX_FRAME_SIZE};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -2,6 +2,18 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Lowest possible reputation score
+pub const MIN_SCORE: f64 = 0.0;
+/// Highest possible reputation score
+pub const MAX_SCORE: f64 = 1.0;
+/// Score of an unknown peer, and the value scores decay toward
+pub const NEUTRAL_SCORE: f64 = 0.5;
+/// Peers at or below this score are banned
+pub const BAN_THRESHOLD: f64 = 0.1;
+/// Time for a score's distance from neutral to halve
+pub const DECAY_HALF_LIFE: Duration = Duration::from_secs(600);
 
 /// Peer information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,19 +34,25 @@ impl PeerInfo {
 }
 
 /// Peer reputation tracker
+///
+/// Scores lie in `[MIN_SCORE, MAX_SCORE]` and start at `NEUTRAL_SCORE`.
+/// Over time a score relaxes back toward neutral, halving its distance every
+/// `DECAY_HALF_LIFE`, so old rewards and penalties fade.
 #[derive(Debug, Clone)]
 pub struct PeerReputation {
     pub score: f64,
     pub good_messages: u64,
     pub bad_messages: u64,
+    last_update: Instant,
 }
 
 impl PeerReputation {
     pub fn new() -> Self {
         Self {
-            score: 1.0,
+            score: NEUTRAL_SCORE,
             good_messages: 0,
             bad_messages: 0,
+            last_update: Instant::now(),
         }
     }
 
@@ -53,6 +71,29 @@ impl PeerReputation {
             self.score = 0.0;
         }
     }
+
+    /// Raise the score by `delta`, saturating at `MAX_SCORE`
+    pub fn reward(&mut self, delta: f64) {
+        self.score = (self.score + delta).clamp(MIN_SCORE, MAX_SCORE);
+    }
+
+    /// Lower the score by `delta`, saturating at `MIN_SCORE`
+    pub fn penalize(&mut self, delta: f64) {
+        self.score = (self.score - delta).clamp(MIN_SCORE, MAX_SCORE);
+    }
+
+    /// Relax the score toward neutral for the time elapsed until `now`
+    pub fn decay(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_update);
+        let remaining = 0.5f64.powf(elapsed.as_secs_f64() / DECAY_HALF_LIFE.as_secs_f64());
+        self.score = NEUTRAL_SCORE + (self.score - NEUTRAL_SCORE) * remaining;
+        self.last_update = self.last_update.max(now);
+    }
+
+    /// Whether the score has fallen to the ban threshold
+    pub fn should_ban(&self) -> bool {
+        self.score <= BAN_THRESHOLD
+    }
 }
 
 impl Default for PeerReputation {
@@ -62,14 +103,20 @@ impl Default for PeerReputation {
 }
 
 /// Peer manager
+///
+/// Tracks a reputation per peer id. Peers whose reputation reaches the ban
+/// threshold are withheld from dialing until their score decays back above
+/// it.
 pub struct PeerManager {
     peers: HashMap<String, PeerInfo>,
+    reputations: HashMap<String, PeerReputation>,
 }
 
 impl PeerManager {
     pub fn new() -> Self {
         Self {
             peers: HashMap::new(),
+            reputations: HashMap::new(),
         }
     }
 
@@ -84,6 +131,62 @@ impl PeerManager {
     pub fn peer_count(&self) -> usize {
         self.peers.len()
     }
+
+    /// Reputation of `peer`, if it has been scored
+    pub fn reputation(&self, peer: &str) -> Option<&PeerReputation> {
+        self.reputations.get(peer)
+    }
+
+    /// Raise `peer`'s reputation by `delta`
+    pub fn reward(&mut self, peer: &str, delta: f64) {
+        self.adjust(peer, |rep| rep.reward(delta));
+    }
+
+    /// Lower `peer`'s reputation by `delta`
+    pub fn penalize(&mut self, peer: &str, delta: f64) {
+        self.adjust(peer, |rep| rep.penalize(delta));
+    }
+
+    fn adjust(&mut self, peer: &str, f: impl FnOnce(&mut PeerReputation)) {
+        let rep = self.reputations.entry(peer.to_string()).or_default();
+        rep.decay(Instant::now());
+        f(rep);
+        if let Some(info) = self.peers.get_mut(peer) {
+            info.reputation = rep.score;
+        }
+    }
+
+    /// Apply time-based decay to every reputation up to `now`
+    pub fn decay(&mut self, now: Instant) {
+        for (id, rep) in &mut self.reputations {
+            rep.decay(now);
+            if let Some(info) = self.peers.get_mut(id) {
+                info.reputation = rep.score;
+            }
+        }
+    }
+
+    /// Whether `peer` should be banned; unscored peers are not
+    pub fn should_ban(&self, peer: &str) -> bool {
+        self.reputations.get(peer).is_some_and(PeerReputation::should_ban)
+    }
+
+    /// Ids of all currently banned peers
+    pub fn banned_peers(&self) -> Vec<String> {
+        self.reputations
+            .iter()
+            .filter(|(_, rep)| rep.should_ban())
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Known peers that may be dialed, i.e. all but banned ones
+    pub fn dialable_peers(&self) -> Vec<&PeerInfo> {
+        self.peers
+            .values()
+            .filter(|peer| !self.should_ban(&peer.id))
+            .collect()
+    }
 }
 
 impl Default for PeerManager {
@@ -111,13 +214,68 @@ mod tests {
     #[test]
     fn test_peer_reputation() {
         let mut rep = PeerReputation::new();
-        assert_eq!(rep.score, 1.0);
+        assert_eq!(rep.score, NEUTRAL_SCORE);
         
         rep.record_good_message();
         assert!(rep.good_messages == 1);
         
         rep.record_bad_message();
         assert!(rep.bad_messages == 1);
-        assert!(rep.score < 1.0);
+        assert!(rep.score < NEUTRAL_SCORE);
+    }
+
+    #[test]
+    fn test_reputation_saturates() {
+        let mut rep = PeerReputation::new();
+        rep.reward(10.0);
+        assert_eq!(rep.score, MAX_SCORE);
+        rep.penalize(10.0);
+        assert_eq!(rep.score, MIN_SCORE);
+    }
+
+    #[test]
+    fn test_repeated_penalties_ban_peer() {
+        let mut pm = PeerManager::new();
+        pm.add_peer(PeerInfo::new("good".to_string(), "127.0.0.1:8080".to_string()));
+        pm.add_peer(PeerInfo::new("bad".to_string(), "127.0.0.1:8081".to_string()));
+        pm.reward("good", 0.1);
+
+        for _ in 0..2 {
+            pm.penalize("bad", 0.15);
+            assert!(!pm.should_ban("bad"));
+        }
+        pm.penalize("bad", 0.15);
+        assert!(pm.should_ban("bad"));
+        assert_eq!(pm.banned_peers(), vec!["bad".to_string()]);
+
+        let dialable: Vec<&str> = pm.dialable_peers().iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(dialable, vec!["good"]);
+        assert!(pm.get_peer("bad").unwrap().reputation <= BAN_THRESHOLD);
+    }
+
+    #[test]
+    fn test_reputation_decays_toward_neutral() {
+        let mut pm = PeerManager::new();
+        pm.add_peer(PeerInfo::new("peer1".to_string(), "127.0.0.1:8080".to_string()));
+        pm.penalize("peer1", 0.5);
+        assert!(pm.should_ban("peer1"));
+
+        // One half-life halves the distance from neutral, unbanning the peer
+        let start = Instant::now();
+        pm.decay(start + DECAY_HALF_LIFE);
+        let score = pm.reputation("peer1").unwrap().score;
+        assert!((score - 0.25).abs() < 0.01, "score {}", score);
+        assert!(!pm.should_ban("peer1"));
+        assert_eq!(pm.dialable_peers().len(), 1);
+
+        // After many half-lives it is back to neutral
+        pm.decay(start + DECAY_HALF_LIFE * 20);
+        assert!((pm.reputation("peer1").unwrap().score - NEUTRAL_SCORE).abs() < 1e-3);
+
+        // Rewards fade the same way
+        let mut rep = PeerReputation::new();
+        rep.reward(0.4);
+        rep.decay(Instant::now() + DECAY_HALF_LIFE);
+        assert!((rep.score - 0.7).abs() < 0.01);
     }
 }
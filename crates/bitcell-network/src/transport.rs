@@ -10,12 +10,125 @@
 
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::sync::mpsc;
 use libp2p::Multiaddr;
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::messages::{Block, GliderCommit, GliderReveal, Transaction};
 use crate::peer::PeerReputation;
 
+/// Default maximum frame payload (10MB)
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 10_000_000;
+
+/// Size of the big-endian length prefix
+const LENGTH_PREFIX_SIZE: usize = 4;
+
+/// Length-prefixed framing for bincode-serialized messages
+///
+/// Each frame is a 4-byte big-endian payload length followed by the
+/// bincode-serialized message, e.g. a [`crate::Message`]. The codec is agnostic of
+/// the message type, so the node's TCP protocol frames its own messages
+/// with it too. The declared length is checked against
+/// `max_frame_size` before any payload buffer is allocated, so a peer cannot
+/// make us reserve memory by announcing a huge frame.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameCodec {
+    max_frame_size: usize,
+}
+
+impl FrameCodec {
+    pub fn new(max_frame_size: usize) -> Self {
+        Self { max_frame_size }
+    }
+
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    fn check_len(&self, len: usize) -> crate::Result<()> {
+        if len > self.max_frame_size {
+            return Err(crate::Error::Transport(format!(
+                "Frame of {} bytes exceeds maximum of {}",
+                len, self.max_frame_size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Serialize `msg` into a single frame
+    pub fn encode<M: Serialize>(&self, msg: &M) -> crate::Result<Vec<u8>> {
+        let payload = bincode::serialize(msg)
+            .map_err(|e| crate::Error::Transport(format!("Serialization error: {}", e)))?;
+        self.check_len(payload.len())?;
+
+        let mut frame = Vec::with_capacity(LENGTH_PREFIX_SIZE + payload.len());
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&payload);
+        Ok(frame)
+    }
+
+    /// Decode the first frame in `buf`
+    ///
+    /// Returns the message and the number of bytes consumed, or `None` if
+    /// `buf` does not yet hold a complete frame.
+    pub fn decode<M: DeserializeOwned>(&self, buf: &[u8]) -> crate::Result<Option<(M, usize)>> {
+        let Some(prefix) = buf.get(..LENGTH_PREFIX_SIZE) else {
+            return Ok(None);
+        };
+        let len = u32::from_be_bytes(prefix.try_into().unwrap()) as usize;
+        self.check_len(len)?;
+
+        let end = LENGTH_PREFIX_SIZE + len;
+        let Some(payload) = buf.get(LENGTH_PREFIX_SIZE..end) else {
+            return Ok(None);
+        };
+        Ok(Some((Self::deserialize(payload)?, end)))
+    }
+
+    /// Write `msg` to `writer` as one frame
+    pub async fn write_frame<W, M>(&self, writer: &mut W, msg: &M) -> crate::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+        M: Serialize,
+    {
+        let frame = self.encode(msg)?;
+        writer.write_all(&frame).await
+            .map_err(|e| crate::Error::Transport(format!("Write error: {}", e)))?;
+        writer.flush().await
+            .map_err(|e| crate::Error::Transport(format!("Flush error: {}", e)))
+    }
+
+    /// Read one frame from `reader`
+    pub async fn read_frame<R, M>(&self, reader: &mut R) -> crate::Result<M>
+    where
+        R: AsyncRead + Unpin,
+        M: DeserializeOwned,
+    {
+        let mut prefix = [0u8; LENGTH_PREFIX_SIZE];
+        reader.read_exact(&mut prefix).await
+            .map_err(|e| crate::Error::Transport(format!("Read error: {}", e)))?;
+        let len = u32::from_be_bytes(prefix) as usize;
+        self.check_len(len)?;
+
+        let mut payload = vec![0u8; len];
+        reader.read_exact(&mut payload).await
+            .map_err(|e| crate::Error::Transport(format!("Read error: {}", e)))?;
+        Self::deserialize(&payload)
+    }
+
+    fn deserialize<M: DeserializeOwned>(payload: &[u8]) -> crate::Result<M> {
+        bincode::deserialize(payload)
+            .map_err(|e| crate::Error::Transport(format!("Deserialization error: {}", e)))
+    }
+}
+
+impl Default for FrameCodec {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_FRAME_SIZE)
+    }
+}
+
 /// P2P network manager with libp2p Gossipsub
 /// 
 /// This is a stub implementation. The full production implementation is in bitcell-node/src/dht.rs.
@@ -116,6 +229,76 @@ impl NetworkManager {
 mod tests {
     use super::*;
 
+    use crate::messages::{Message, MessageType};
+
+    fn sample_messages() -> Vec<Message> {
+        vec![
+            Message::new(MessageType::GetPeers),
+            Message::new(MessageType::GetBlock(bitcell_crypto::Hash256::hash(b"block"))),
+        ]
+    }
+
+    #[test]
+    fn test_frame_round_trip() {
+        let codec = FrameCodec::default();
+        let mut stream = Vec::new();
+        for msg in sample_messages() {
+            stream.extend(codec.encode(&msg).unwrap());
+        }
+
+        // An incomplete frame waits for more bytes
+        assert!(codec.decode::<Message>(&stream[..3]).unwrap().is_none());
+        assert!(codec.decode::<Message>(&stream[..5]).unwrap().is_none());
+
+        let (first, used) = codec.decode::<Message>(&stream).unwrap().unwrap();
+        assert!(matches!(first.message_type, MessageType::GetPeers));
+        let (second, rest) = codec.decode::<Message>(&stream[used..]).unwrap().unwrap();
+        assert_eq!(used + rest, stream.len());
+        match second.message_type {
+            MessageType::GetBlock(hash) => assert_eq!(hash, bitcell_crypto::Hash256::hash(b"block")),
+            other => panic!("unexpected message {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_frame_round_trip_over_stream() {
+        let codec = FrameCodec::default();
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        for msg in sample_messages() {
+            codec.write_frame(&mut client, &msg).await.unwrap();
+        }
+
+        let first: Message = codec.read_frame(&mut server).await.unwrap();
+        assert!(matches!(first.message_type, MessageType::GetPeers));
+        let second: Message = codec.read_frame(&mut server).await.unwrap();
+        assert!(matches!(second.message_type, MessageType::GetBlock(_)));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_frame_rejected_before_allocation() {
+        let codec = FrameCodec::new(1024);
+
+        // Only the prefix is sent: reading the payload would fail with EOF,
+        // so a size error shows the length was checked first
+        let header = u32::MAX.to_be_bytes();
+        let mut reader: &[u8] = &header;
+        match codec.read_frame::<_, Message>(&mut reader).await {
+            Err(crate::Error::Transport(e)) => assert!(e.contains("exceeds maximum")),
+            other => panic!("expected oversized frame error, got {:?}", other.map(|_| ())),
+        }
+        match codec.decode::<Message>(&header) {
+            Err(crate::Error::Transport(e)) => assert!(e.contains("exceeds maximum")),
+            other => panic!("expected oversized frame error, got {:?}", other.map(|_| ())),
+        }
+
+        // Encoding refuses messages that would exceed the limit too
+        let tiny = FrameCodec::new(2);
+        assert!(matches!(
+            tiny.encode(&Message::new(MessageType::GetBlock(bitcell_crypto::Hash256::zero()))),
+            Err(crate::Error::Transport(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_network_creation() {
         let (block_tx, _) = mpsc::channel(100);
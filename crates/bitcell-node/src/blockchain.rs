@@ -6,16 +6,29 @@
 ///! - Transaction indexing for efficient lookups
 ///! - State management with Merkle tree root computation
 use crate::{Result, MetricsRegistry};
-use bitcell_consensus::{Block, BlockHeader, Transaction, BattleProof};
+use crate::contracts::ContractRegistry;
+use bitcell_consensus::{Block, BlockHeader, Transaction, BattleProof, FinalityStatus};
 use bitcell_crypto::{Hash256, PublicKey, SecretKey};
-use bitcell_economics::{INITIAL_BLOCK_REWARD, HALVING_INTERVAL, MAX_HALVINGS};
-use bitcell_state::StateManager;
+use bitcell_ebsl::EbslParams;
+use bitcell_economics::{INITIAL_BLOCK_REWARD, HALVING_INTERVAL, MAX_HALVINGS, MAX_GAS_PER_BLOCK};
+use bitcell_state::{BlockTransaction, StateManager};
+use bitcell_zkp::{SpendPublicInputs, SpendVerifier};
 use std::sync::{Arc, RwLock};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tokio::sync::broadcast;
 
 /// Genesis block height
 pub const GENESIS_HEIGHT: u64 = 0;
 
+/// Maximum serialized block size in bytes
+pub const MAX_BLOCK_SIZE: usize = 4_000_000;
+
+/// Blocks per EBSL epoch; evidence decays once when a block closes an epoch
+pub const BLOCKS_PER_EPOCH: u64 = 100;
+
+/// Capacity of the new block notification channel
+const BLOCK_EVENT_CAPACITY: usize = 64;
+
 /// Transaction location in blockchain (block height and index within block)
 #[derive(Clone, Debug)]
 pub struct TxLocation {
@@ -23,6 +36,18 @@ pub struct TxLocation {
     pub tx_index: usize,
 }
 
+/// Outcome of executing a transaction in a block
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxReceipt {
+    pub tx_hash: Hash256,
+    pub block_hash: Hash256,
+    pub block_height: u64,
+    pub tx_index: usize,
+    pub gas_used: u64,
+    /// False if the transaction failed; only its gas and nonce were consumed
+    pub success: bool,
+}
+
 /// Blockchain manager
 /// 
 /// Maintains the blockchain state including blocks, transactions, and state root.
@@ -40,6 +65,15 @@ pub struct Blockchain {
     
     /// Transaction hash index for O(1) lookups (tx_hash -> location)
     tx_index: Arc<RwLock<HashMap<Hash256, TxLocation>>>,
+
+    /// Block hash index (block_hash -> height)
+    block_index: Arc<RwLock<HashMap<Hash256, u64>>>,
+
+    /// Execution receipts (tx_hash -> receipt)
+    receipts: Arc<RwLock<HashMap<Hash256, TxReceipt>>>,
+
+    /// Compiled contract code by address
+    contracts: ContractRegistry,
     
     /// State manager
     state: Arc<RwLock<StateManager>>,
@@ -49,6 +83,15 @@ pub struct Blockchain {
     
     /// Node secret key for signing
     secret_key: Arc<SecretKey>,
+
+    /// EBSL parameters for evidence decay and miner eligibility
+    ebsl_params: EbslParams,
+
+    /// Verifies the proofs of private spends; without one they are rejected
+    spend_verifier: Option<Arc<SpendVerifier>>,
+
+    /// Notifies subscribers of each block appended to the chain
+    block_events: broadcast::Sender<Block>,
 }
 
 impl Blockchain {
@@ -65,9 +108,15 @@ impl Blockchain {
             latest_hash: Arc::new(RwLock::new(genesis_hash)),
             blocks: Arc::new(RwLock::new(blocks)),
             tx_index: Arc::new(RwLock::new(HashMap::new())),
+            block_index: Arc::new(RwLock::new(HashMap::from([(genesis_hash, GENESIS_HEIGHT)]))),
+            receipts: Arc::new(RwLock::new(HashMap::new())),
+            contracts: ContractRegistry::new(),
             state: Arc::new(RwLock::new(StateManager::new())),
             metrics,
             secret_key,
+            ebsl_params: EbslParams::default(),
+            spend_verifier: None,
+            block_events: broadcast::channel(BLOCK_EVENT_CAPACITY).0,
         };
         
         // Initialize metrics
@@ -113,9 +162,15 @@ impl Blockchain {
             latest_hash: Arc::new(RwLock::new(genesis_hash)),
             blocks: Arc::new(RwLock::new(blocks)),
             tx_index: Arc::new(RwLock::new(HashMap::new())),
+            block_index: Arc::new(RwLock::new(HashMap::from([(genesis_hash, GENESIS_HEIGHT)]))),
+            receipts: Arc::new(RwLock::new(HashMap::new())),
+            contracts: ContractRegistry::new(),
             state: Arc::new(RwLock::new(state)),
             metrics: metrics.clone(),
             secret_key,
+            ebsl_params: EbslParams::default(),
+            spend_verifier: None,
+            block_events: broadcast::channel(BLOCK_EVENT_CAPACITY).0,
         };
         
         // Initialize metrics
@@ -125,6 +180,55 @@ impl Blockchain {
         Ok(blockchain)
     }
     
+    /// Use `params` for evidence decay and miner eligibility
+    pub fn with_ebsl_params(mut self, params: EbslParams) -> Self {
+        self.ebsl_params = params;
+        self
+    }
+
+    /// Accept private spends whose proofs `verifier` accepts
+    pub fn with_spend_verifier(mut self, verifier: SpendVerifier) -> Self {
+        self.spend_verifier = Some(Arc::new(verifier));
+        self
+    }
+
+    /// EBSL parameters this chain decays evidence with
+    pub fn ebsl_params(&self) -> &EbslParams {
+        &self.ebsl_params
+    }
+    
+    /// Credit the balances listed in a genesis allocation file
+    ///
+    /// The file is a JSON object mapping hex-encoded public keys to balances.
+    /// It only applies to a fresh chain, at genesis height with no accounts
+    /// yet, so restarting on persisted state does not credit twice. Returns
+    /// the number of accounts credited.
+    pub fn apply_genesis_alloc(&self, path: &std::path::Path) -> Result<usize> {
+        let data = std::fs::read_to_string(path)
+            .map_err(|e| crate::Error::Config(format!("Failed to read {}: {}", path.display(), e)))?;
+        let alloc: BTreeMap<String, u64> = serde_json::from_str(&data)
+            .map_err(|e| crate::Error::Config(format!("Invalid genesis allocation: {}", e)))?;
+        let balances = alloc.iter()
+            .map(|(key, balance)| {
+                let bytes = hex::decode(key.strip_prefix("0x").unwrap_or(key)).ok()
+                    .and_then(|bytes| <[u8; 33]>::try_from(bytes).ok())
+                    .ok_or_else(|| crate::Error::Config(format!("Invalid public key in genesis allocation: {}", key)))?;
+                Ok((bytes, *balance))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut state = self.state.write()
+            .map_err(|_| crate::Error::Lock("State lock poisoned".to_string()))?;
+        if self.height() != GENESIS_HEIGHT || !state.accounts.is_empty() {
+            return Ok(0);
+        }
+        for (pubkey, balance) in &balances {
+            state.credit_account(*pubkey, *balance)
+                .map_err(|e| crate::Error::Node(format!("Failed to credit genesis allocation: {}", e)))?;
+        }
+        Ok(balances.len())
+    }
+
     /// Create genesis block
     fn create_genesis_block(secret_key: &SecretKey) -> Block {
         let header = BlockHeader {
@@ -144,6 +248,8 @@ impl Blockchain {
             transactions: vec![],
             battle_proofs: vec![],
             signature: secret_key.sign(&[0u8; 32]),
+            finality_votes: vec![],
+            finality_status: FinalityStatus::Pending,
         }
     }
     
@@ -180,6 +286,35 @@ impl Blockchain {
         }).get(&height).cloned()
     }
 
+    /// Get block by hash
+    ///
+    /// Returns the block with the given hash, or None if not found.
+    pub fn get_block_by_hash(&self, block_hash: &Hash256) -> Option<Block> {
+        let height = self.block_index.read().unwrap_or_else(|e| {
+            tracing::error!("Lock poisoned in get_block_by_hash() - prior panic detected: {}", e);
+            e.into_inner()
+        }).get(block_hash).copied()?;
+        self.get_block(height)
+    }
+
+    /// Get the execution receipt of a transaction included in the chain
+    pub fn get_transaction_receipt(&self, tx_hash: &Hash256) -> Option<TxReceipt> {
+        self.receipts.read().unwrap_or_else(|e| {
+            tracing::error!("Lock poisoned in get_transaction_receipt() - prior panic detected: {}", e);
+            e.into_inner()
+        }).get(tx_hash).cloned()
+    }
+
+    /// Gas charged for a transaction: the base cost plus call data
+    pub fn intrinsic_gas(tx: &Transaction) -> u64 {
+        crate::contracts::intrinsic_gas(&tx.data)
+    }
+
+    /// Deployed contracts
+    pub fn contracts(&self) -> &ContractRegistry {
+        &self.contracts
+    }
+
     /// Get transaction by hash using the O(1) hash index
     ///
     /// Returns the transaction and its location (block height, index) if found.
@@ -206,6 +341,21 @@ impl Blockchain {
         None
     }
 
+    /// Subscribe to blocks as they are appended to the chain
+    pub fn subscribe_blocks(&self) -> broadcast::Receiver<Block> {
+        self.block_events.subscribe()
+    }
+
+    /// Flush applied state to persistent storage
+    ///
+    /// A no-op for chains without a storage backend.
+    pub fn flush(&self) -> Result<()> {
+        let state = self.state.read()
+            .map_err(|_| crate::Error::Lock("State lock poisoned".to_string()))?;
+        state.flush()
+            .map_err(|e| crate::Error::Node(format!("Failed to flush state: {}", e)))
+    }
+
     /// Get state manager (read-only access)
     pub fn state(&self) -> Arc<RwLock<StateManager>> {
         Arc::clone(&self.state)
@@ -222,6 +372,10 @@ impl Blockchain {
     }
     
     /// Produce a new block
+    ///
+    /// `transactions` are taken in order while they fit the block gas and
+    /// size limits; ones that do not fit are left out (with their sender's
+    /// later transactions) rather than producing an invalid block.
     pub fn produce_block(
         &self,
         transactions: Vec<Transaction>,
@@ -232,9 +386,6 @@ impl Blockchain {
         let new_height = current_height + 1;
         let prev_hash = self.latest_hash();
         
-        // Calculate transaction root
-        let tx_root = self.calculate_tx_root(&transactions);
-        
         // Get current state root
         let state_root = {
             let state = self.state.read().unwrap_or_else(|e| {
@@ -277,11 +428,11 @@ impl Blockchain {
             (vrf_output, bincode::serialize(&vrf_proof).unwrap_or_default())
         };
 
-        // Create block header
-        let header = BlockHeader {
+        // Create block header; tx_root is filled in once transactions are chosen
+        let mut header = BlockHeader {
             height: new_height,
             prev_hash,
-            tx_root,
+            tx_root: Hash256::zero(),
             state_root,
             timestamp: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
@@ -293,6 +444,23 @@ impl Blockchain {
             work: battle_proofs.len() as u64 * 1000, // Simplified work calculation
         };
         
+        // Keep the block within MAX_GAS_PER_BLOCK and MAX_BLOCK_SIZE. The
+        // header's encoded size does not depend on the transactions.
+        let base_size = bincode::serialized_size(&Block {
+            header: header.clone(),
+            transactions: vec![],
+            battle_proofs: battle_proofs.clone(),
+            signature: bitcell_crypto::Signature::from_bytes([0u8; 64]),
+            finality_votes: vec![],
+            finality_status: FinalityStatus::Pending,
+        }).map_err(|e| crate::Error::Node(format!("Failed to size block: {}", e)))?;
+        let transactions = fit_block_limits(
+            transactions,
+            MAX_GAS_PER_BLOCK,
+            (MAX_BLOCK_SIZE as u64).saturating_sub(base_size),
+        );
+        header.tx_root = self.calculate_tx_root(&transactions);
+        
         // Sign the block
         let header_hash = header.hash();
         let signature = self.secret_key.sign(header_hash.as_bytes());
@@ -302,6 +470,8 @@ impl Blockchain {
             transactions,
             battle_proofs,
             signature,
+            finality_votes: vec![],
+            finality_status: FinalityStatus::Pending,
         };
         
         Ok(block)
@@ -323,6 +493,10 @@ impl Blockchain {
         if block.header.prev_hash != self.latest_hash() {
             return Err(crate::Error::Node("Previous hash mismatch".to_string()));
         }
+
+        // Check gas and size limits
+        block.validate_limits(MAX_GAS_PER_BLOCK, MAX_BLOCK_SIZE)
+            .map_err(|_| crate::Error::Node("Block exceeds gas or size limit".to_string()))?;
         
         // Verify signature
         let header_hash = block.header.hash();
@@ -366,9 +540,14 @@ impl Blockchain {
             return Err(crate::Error::Node("Transaction root mismatch".to_string()));
         }
         
-        // Validate individual transactions
+        // Validate individual transactions. Every included transaction
+        // consumes its nonce, so a sender's later ones are checked against
+        // the nonce after its earlier ones in this block.
+        let mut next_nonces: HashMap<[u8; 33], u64> = HashMap::new();
         for tx in &block.transactions {
-            self.validate_transaction(tx)?;
+            let pending_nonce = next_nonces.get(tx.from.as_bytes()).copied();
+            self.validate_transaction(tx, pending_nonce)?;
+            next_nonces.insert(*tx.from.as_bytes(), tx.nonce + 1);
         }
         
         Ok(())
@@ -382,45 +561,55 @@ impl Blockchain {
         let block_height = block.header.height;
         let block_hash = block.hash();
         
-        // Apply transactions to state
+        // Apply transactions to state, recording gas used and whether each succeeded
+        let mut outcomes = Vec::with_capacity(block.transactions.len());
         {
             let mut state = self.state.write().unwrap_or_else(|e| {
                 tracing::error!("Lock poisoned in add_block() while writing state - prior panic detected: {}", e);
                 e.into_inner()
             });
             
-            // Apply block reward to proposer
+            // A transaction without gas for its call data runs out before
+            // executing. The reward and transactions apply as one unit, so a
+            // storage failure leaves the state as it was.
             let reward = Self::calculate_block_reward(block_height);
-            if reward > 0 {
-                match state.credit_account(*block.header.proposer.as_bytes(), reward) {
-                    Ok(_) => {
-                        tracing::info!("Block reward credited: {} units to proposer", reward);
+            let block_txs: Vec<BlockTransaction> = block.transactions.iter()
+                .map(|tx| {
+                    let intrinsic_gas = Self::intrinsic_gas(tx);
+                    let gas_used = intrinsic_gas.min(tx.gas_limit);
+                    BlockTransaction {
+                        from: *tx.from.as_bytes(),
+                        to: *tx.to.as_bytes(),
+                        amount: tx.amount,
+                        nonce: tx.nonce,
+                        nullifiers: tx.spends.iter().map(|spend| spend.nullifier).collect(),
+                        fee: gas_used.saturating_mul(tx.gas_price),
+                        out_of_gas: tx.gas_limit < intrinsic_gas,
                     }
-                    Err(e) => {
-                        tracing::error!("Failed to credit block reward: {:?}", e);
-                        return Err(crate::Error::Node("Failed to credit block reward".to_string()));
-                    }
-                }
+                })
+                .collect();
+            let applied = state.apply_block(
+                (reward > 0).then_some((*block.header.proposer.as_bytes(), reward)),
+                &block_txs,
+            ).map_err(|e| {
+                tracing::error!("Failed to apply block {}: {:?}", block_height, e);
+                crate::Error::Node(format!("Failed to apply block: {:?}", e))
+            })?;
+            if reward > 0 {
+                tracing::info!("Block reward credited: {} units to proposer", reward);
             }
-            
-            for tx in &block.transactions {
-                // Apply transaction and update state
-                match state.apply_transaction(
-                    *tx.from.as_bytes(),
-                    *tx.to.as_bytes(),
-                    tx.amount,
-                    tx.nonce,
-                ) {
-                    Ok(new_state_root) => {
-                        // State updated successfully
-                        tracing::debug!("Transaction applied, new state root: {:?}", new_state_root);
-                    }
-                    Err(e) => {
-                        tracing::warn!("Failed to apply transaction: {:?}", e);
-                        // In production, this should rollback the entire block
-                        // For now, we just skip the transaction
-                    }
+
+            for ((tx, block_tx), success) in block.transactions.iter().zip(&block_txs).zip(applied) {
+                if !success {
+                    tracing::warn!("Transaction {:?} failed; charged {} for its gas", tx.hash(), block_tx.fee);
                 }
+                outcomes.push((success, Self::intrinsic_gas(tx).min(tx.gas_limit)));
+            }
+
+            // Fade old evidence once per epoch so trust drifts back towards
+            // the prior without fresh behaviour.
+            if block_height % BLOCKS_PER_EPOCH == 0 {
+                state.decay_all_evidence(&self.ebsl_params);
             }
         }
         
@@ -438,6 +627,30 @@ impl Blockchain {
             }
             tracing::debug!("Indexed {} transactions in block {}", block.transactions.len(), block_height);
         }
+        {
+            let mut receipts = self.receipts.write().unwrap_or_else(|e| {
+                tracing::error!("Lock poisoned in add_block() while storing receipts - prior panic detected: {}", e);
+                e.into_inner()
+            });
+            for (idx, (tx, (success, gas_used))) in block.transactions.iter().zip(outcomes).enumerate() {
+                let tx_hash = tx.hash();
+                receipts.insert(tx_hash, TxReceipt {
+                    tx_hash,
+                    block_hash,
+                    block_height,
+                    tx_index: idx,
+                    gas_used,
+                    success,
+                });
+            }
+        }
+        {
+            let mut block_index = self.block_index.write().unwrap_or_else(|e| {
+                tracing::error!("Lock poisoned in add_block() while indexing block - prior panic detected: {}", e);
+                e.into_inner()
+            });
+            block_index.insert(block_hash, block_height);
+        }
         
         // Store block
         {
@@ -445,7 +658,17 @@ impl Blockchain {
                 tracing::error!("Lock poisoned in add_block() while storing block - prior panic detected: {}", e);
                 e.into_inner()
             });
-            blocks.insert(block_height, block);
+            // Genesis has a zero timestamp, so no interval is recorded for block 1
+            let parent_timestamp = block_height
+                .checked_sub(1)
+                .and_then(|parent| blocks.get(&parent))
+                .map(|parent| parent.header.timestamp)
+                .filter(|&timestamp| timestamp > 0);
+            if let Some(parent_timestamp) = parent_timestamp {
+                let interval_secs = block.header.timestamp.saturating_sub(parent_timestamp);
+                self.metrics.record_block_time(interval_secs.saturating_mul(1000));
+            }
+            blocks.insert(block_height, block.clone());
         }
         
         // Update chain tip
@@ -466,6 +689,9 @@ impl Blockchain {
         
         // Update metrics
         self.metrics.set_chain_height(block_height);
+
+        // Notify subscribers; having none is not an error
+        let _ = self.block_events.send(block);
         
         Ok(())
     }
@@ -484,13 +710,43 @@ impl Blockchain {
         Hash256::hash(&combined)
     }
     
+    /// Check the proofs of a transaction's private spends
+    ///
+    /// A transaction may only spend nullifiers it proves it owns, so nobody
+    /// can burn another spender's nullifiers by copying them into a plain
+    /// transfer.
+    pub fn verify_spends(&self, tx: &Transaction) -> Result<()> {
+        if tx.spends.is_empty() {
+            return Ok(());
+        }
+        let verifier = self.spend_verifier.as_ref().ok_or_else(|| {
+            crate::Error::Node("Private spends require a spend verifying key".to_string())
+        })?;
+        for spend in &tx.spends {
+            let public_inputs = SpendPublicInputs {
+                old_root: spend.old_root,
+                new_root: spend.new_root,
+                nullifier: spend.nullifier,
+                commitment: spend.commitment,
+            };
+            if !matches!(verifier.verify(&spend.proof, &public_inputs), Ok(true)) {
+                return Err(crate::Error::Node("Invalid spend proof".to_string()));
+            }
+        }
+        Ok(())
+    }
+
     /// Validate a single transaction
-    fn validate_transaction(&self, tx: &Transaction) -> Result<()> {
-        // Verify signature
-        let tx_hash = tx.hash();
-        if tx.signature.verify(&tx.from, tx_hash.as_bytes()).is_err() {
+    ///
+    /// `pending_nonce` is the sender's nonce after its transactions earlier in
+    /// the same block, if it has any; otherwise the account nonce is expected.
+    fn validate_transaction(&self, tx: &Transaction, pending_nonce: Option<u64>) -> Result<()> {
+        // Verify signature over the signing hash (which excludes the signature)
+        let signing_hash = tx.signing_hash();
+        if tx.signature.verify(&tx.from, signing_hash.as_bytes()).is_err() {
             return Err(crate::Error::Node("Invalid transaction signature".to_string()));
         }
+        self.verify_spends(tx)?;
         
         // Check nonce and balance
         let state = self.state.read().unwrap_or_else(|e| {
@@ -498,10 +754,11 @@ impl Blockchain {
             e.into_inner()
         });
         if let Some(account) = state.get_account(tx.from.as_bytes()) {
-            if tx.nonce != account.nonce {
+            let expected_nonce = pending_nonce.unwrap_or(account.nonce);
+            if tx.nonce != expected_nonce {
                 return Err(crate::Error::Node(format!(
                     "Invalid nonce: expected {}, got {}",
-                    account.nonce, tx.nonce
+                    expected_nonce, tx.nonce
                 )));
             }
             
@@ -516,9 +773,41 @@ impl Blockchain {
     }
 }
 
+/// Keep the transactions, in order, whose summed gas limits and serialized
+/// sizes stay within `max_gas` and `max_bytes`
+///
+/// Transactions that do not fit are skipped rather than ending selection, so
+/// one oversize transaction cannot stall block production. Once a sender's
+/// transaction is skipped its later ones are too, as they would leave a
+/// nonce gap.
+fn fit_block_limits(transactions: Vec<Transaction>, max_gas: u64, max_bytes: u64) -> Vec<Transaction> {
+    let (mut gas, mut bytes) = (0u64, 0u64);
+    let mut skipped_senders = HashSet::new();
+    transactions
+        .into_iter()
+        .filter(|tx| {
+            if skipped_senders.contains(tx.from.as_bytes()) {
+                return false;
+            }
+            let size = bincode::serialized_size(tx).unwrap_or(u64::MAX);
+            match (gas.checked_add(tx.gas_limit), bytes.checked_add(size)) {
+                (Some(next_gas), Some(next_bytes)) if next_gas <= max_gas && next_bytes <= max_bytes => {
+                    (gas, bytes) = (next_gas, next_bytes);
+                    true
+                }
+                _ => {
+                    skipped_senders.insert(*tx.from.as_bytes());
+                    false
+                }
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bitcell_consensus::PrivateSpend;
     
     #[test]
     fn test_genesis_block_creation() {
@@ -639,6 +928,396 @@ mod tests {
         blockchain.validate_block(&block2).expect("Second block should be valid");
     }
     
+    #[test]
+    fn test_add_block_notifies_subscribers() {
+        let sk = Arc::new(SecretKey::generate());
+        let blockchain = Blockchain::new(sk.clone(), MetricsRegistry::new());
+        let mut events = blockchain.subscribe_blocks();
+
+        let block = blockchain.produce_block(vec![], vec![], sk.public_key()).unwrap();
+        let hash = block.hash();
+        blockchain.add_block(block).unwrap();
+
+        assert_eq!(events.try_recv().unwrap().hash(), hash);
+    }
+
+    #[test]
+    fn test_add_block_decays_evidence_at_epoch_boundary() {
+        use bitcell_ebsl::{Evidence, EvidenceType};
+
+        let sk = Arc::new(SecretKey::generate());
+        let params = EbslParams { pos_decay: 0.5, ..EbslParams::default() };
+        let blockchain = Blockchain::new(sk.clone(), MetricsRegistry::new())
+            .with_ebsl_params(params.clone());
+        let miner = [7u8; 33];
+        blockchain.state().write().unwrap()
+            .submit_evidence(miner, Evidence::new(EvidenceType::GoodBlock, 0, 0), &params)
+            .unwrap();
+        let r = || blockchain.state().read().unwrap().get_evidence_counters(&miner).unwrap().r;
+
+        // Blocks inside an epoch leave evidence alone
+        for _ in 1..BLOCKS_PER_EPOCH {
+            let block = blockchain.produce_block(vec![], vec![], sk.public_key()).unwrap();
+            blockchain.add_block(block).unwrap();
+        }
+        assert_eq!(r(), 1.0);
+
+        // The block closing the epoch decays it with the chain's parameters
+        let block = blockchain.produce_block(vec![], vec![], sk.public_key()).unwrap();
+        blockchain.add_block(block).unwrap();
+        assert_eq!(blockchain.height(), BLOCKS_PER_EPOCH);
+        assert_eq!(r(), params.pos_decay);
+    }
+    
+    fn signed_transfer(sk: &SecretKey, to: PublicKey, amount: u64, nonce: u64) -> Transaction {
+        let mut tx = Transaction {
+            nonce,
+            from: sk.public_key(),
+            to,
+            amount,
+            gas_limit: 21000,
+            gas_price: 1,
+            data: vec![],
+            spends: vec![],
+            signature: sk.sign(b"placeholder"),
+        };
+        tx.signature = sk.sign(tx.signing_hash().as_bytes());
+        tx
+    }
+
+    /// A spend verifier and a prover of spends of the note `[leaf; 32]`
+    fn spend_prover() -> (SpendVerifier, impl Fn(u8) -> PrivateSpend) {
+        let (pk, vk) = bitcell_zkp::StateCircuit::setup().unwrap();
+        let prove = move |leaf: u8| {
+            let inputs = bitcell_zkp::SpendProofInputs::new(
+                [leaf; 32],
+                vec![[0u8; 32]; bitcell_zkp::MERKLE_DEPTH],
+                vec![false; bitcell_zkp::MERKLE_DEPTH],
+                [0u8; 32],
+            ).unwrap();
+            PrivateSpend {
+                nullifier: inputs.public.nullifier,
+                old_root: inputs.public.old_root,
+                new_root: inputs.public.new_root,
+                commitment: inputs.public.commitment,
+                proof: inputs.prove(&pk).unwrap(),
+            }
+        };
+        (SpendVerifier::new(vk), prove)
+    }
+
+    #[test]
+    fn test_block_over_gas_limit_rejected() {
+        let sk = Arc::new(SecretKey::generate());
+        let blockchain = Blockchain::new(sk.clone(), MetricsRegistry::new());
+        let sender = SecretKey::generate();
+
+        let mut tx = signed_transfer(&sender, sk.public_key(), 1, 0);
+        tx.gas_limit = MAX_GAS_PER_BLOCK + 1;
+        let mut block = blockchain.produce_block(vec![], vec![], sk.public_key()).unwrap();
+        block.transactions.push(tx);
+
+        let err = blockchain.add_block(block).unwrap_err();
+        assert!(err.to_string().contains("gas or size limit"));
+        assert_eq!(blockchain.height(), GENESIS_HEIGHT);
+    }
+
+    #[test]
+    fn test_produce_block_stays_within_limits() {
+        let sk = Arc::new(SecretKey::generate());
+        let blockchain = Blockchain::new(sk.clone(), MetricsRegistry::new());
+        let to = sk.public_key();
+
+        // An unincludable transaction, and the same sender's next nonce
+        let stuck = SecretKey::generate();
+        let mut oversize = signed_transfer(&stuck, to, 1, 0);
+        oversize.gas_limit = MAX_GAS_PER_BLOCK + 1;
+        let mut txs = vec![oversize, signed_transfer(&stuck, to, 1, 1)];
+
+        // Four 10M-gas transactions; only three fit in 30M
+        for _ in 0..4 {
+            let mut tx = signed_transfer(&SecretKey::generate(), to, 1, 0);
+            tx.gas_limit = 10_000_000;
+            txs.push(tx);
+        }
+        let block = blockchain.produce_block(txs, vec![], to).unwrap();
+        assert_eq!(block.transactions.len(), 3);
+        assert!(block.transactions.iter().all(|tx| tx.from != stuck.public_key()));
+        assert!(block.validate_limits(MAX_GAS_PER_BLOCK, MAX_BLOCK_SIZE).is_ok());
+
+        // Three 1.5MB transactions, each with gas for its call data; only
+        // two fit in 4MB
+        let recipient = SecretKey::generate().public_key();
+        let txs: Vec<_> = (0..3)
+            .map(|_| {
+                let sender = SecretKey::generate();
+                blockchain.state.write().unwrap()
+                    .credit_account(*sender.public_key().as_bytes(), 10).unwrap();
+                let mut tx = signed_transfer(&sender, recipient, 1, 0);
+                tx.data = vec![0u8; 1_500_000];
+                tx.gas_limit = Blockchain::intrinsic_gas(&tx);
+                tx.signature = sender.sign(tx.signing_hash().as_bytes());
+                tx
+            })
+            .collect();
+        let block = blockchain.produce_block(txs, vec![], to).unwrap();
+        assert_eq!(block.transactions.len(), 2);
+        assert!(block.validate_limits(MAX_GAS_PER_BLOCK, MAX_BLOCK_SIZE).is_ok());
+
+        // The trimmed block is accepted by validation and its transfers apply
+        blockchain.add_block(block.clone()).unwrap();
+        assert_eq!(blockchain.height(), 1);
+        let state = blockchain.state.read().unwrap();
+        for tx in &block.transactions {
+            assert!(blockchain.get_transaction_receipt(&tx.hash()).unwrap().success);
+            let sender = state.get_account(tx.from.as_bytes()).unwrap();
+            assert_eq!((sender.balance, sender.nonce), (9, 1));
+        }
+        assert_eq!(state.get_account(recipient.as_bytes()).unwrap().balance, 2);
+    }
+
+    #[test]
+    fn test_receipt_and_block_hash_lookup() {
+        let sk = Arc::new(SecretKey::generate());
+        let blockchain = Blockchain::new(sk.clone(), MetricsRegistry::new());
+        let sender = SecretKey::generate();
+        blockchain.state.write().unwrap()
+            .credit_account(*sender.public_key().as_bytes(), 1000).unwrap();
+
+        let tx = signed_transfer(&sender, sk.public_key(), 100, 0);
+        let tx_hash = tx.hash();
+        let block = blockchain.produce_block(vec![tx], vec![], sk.public_key()).unwrap();
+        let block_hash = block.hash();
+        blockchain.add_block(block).unwrap();
+
+        assert_eq!(blockchain.get_block_by_hash(&block_hash).unwrap().header.height, 1);
+        let receipt = blockchain.get_transaction_receipt(&tx_hash).unwrap();
+        assert_eq!(receipt.block_height, 1);
+        assert_eq!(receipt.block_hash, block_hash);
+        assert_eq!(receipt.tx_index, 0);
+        assert_eq!(receipt.gas_used, bitcell_economics::GAS_TX_BASE);
+        assert!(receipt.success);
+
+        assert!(blockchain.get_block_by_hash(&Hash256::zero()).is_none());
+        assert!(blockchain.get_transaction_receipt(&Hash256::zero()).is_none());
+    }
+
+    #[test]
+    fn test_block_with_consecutive_nonces_from_one_sender() {
+        use crate::tx_pool::TransactionPool;
+
+        let sk = Arc::new(SecretKey::generate());
+        let blockchain = Blockchain::new(sk.clone(), MetricsRegistry::new());
+        let sender = SecretKey::generate();
+        let to = SecretKey::generate().public_key();
+        blockchain.state.write().unwrap()
+            .credit_account(*sender.public_key().as_bytes(), 1000).unwrap();
+
+        // The pool offers both nonces for one block, as the validator asks it
+        let pool = TransactionPool::new(100);
+        pool.add_transaction_with_account_nonce(signed_transfer(&sender, to, 100, 1), 0).unwrap();
+        pool.add_transaction_with_account_nonce(signed_transfer(&sender, to, 200, 0), 0).unwrap();
+        let txs = pool.best_transactions_within(MAX_GAS_PER_BLOCK, MAX_BLOCK_SIZE as u64);
+        assert_eq!(txs.iter().map(|tx| tx.nonce).collect::<Vec<_>>(), vec![0, 1]);
+
+        let block = blockchain.produce_block(txs, vec![], sk.public_key()).unwrap();
+        blockchain.add_block(block.clone()).unwrap();
+        assert_eq!(blockchain.height(), 1);
+        assert!(block.transactions.iter()
+            .all(|tx| blockchain.get_transaction_receipt(&tx.hash()).unwrap().success));
+
+        let state = blockchain.state.read().unwrap();
+        let account = state.get_account(sender.public_key().as_bytes()).unwrap();
+        assert_eq!((account.balance, account.nonce), (700, 2));
+        assert_eq!(state.get_account(to.as_bytes()).unwrap().balance, 300);
+    }
+
+    #[test]
+    fn test_block_with_nonce_gap_rejected() {
+        let sk = Arc::new(SecretKey::generate());
+        let blockchain = Blockchain::new(sk.clone(), MetricsRegistry::new());
+        let sender = SecretKey::generate();
+        blockchain.state.write().unwrap()
+            .credit_account(*sender.public_key().as_bytes(), 1000).unwrap();
+
+        // Nonce 0 then 2, and nonce 0 twice
+        for nonces in [[0, 2], [0, 0]] {
+            let txs = nonces.iter()
+                .map(|&nonce| signed_transfer(&sender, sk.public_key(), 1, nonce))
+                .collect();
+            let block = blockchain.produce_block(txs, vec![], sk.public_key()).unwrap();
+            let err = blockchain.add_block(block).unwrap_err();
+            assert!(err.to_string().contains("Invalid nonce"), "{}", err);
+        }
+        assert_eq!(blockchain.height(), GENESIS_HEIGHT);
+    }
+
+    #[test]
+    fn test_failed_transaction_charged_and_not_replayable() {
+        let sk = Arc::new(SecretKey::generate());
+        let blockchain = Blockchain::new(sk.clone(), MetricsRegistry::new());
+        let sender = SecretKey::generate();
+        let to = SecretKey::generate().public_key();
+        blockchain.state.write().unwrap()
+            .credit_account(*sender.public_key().as_bytes(), 100_000).unwrap();
+
+        // Call data costs more than the gas limit allows
+        let mut starved = signed_transfer(&sender, to, 100, 0);
+        starved.data = vec![1u8; 4];
+        starved.signature = sender.sign(starved.signing_hash().as_bytes());
+        assert!(starved.gas_limit < Blockchain::intrinsic_gas(&starved));
+
+        let block = blockchain.produce_block(vec![starved.clone()], vec![], sk.public_key()).unwrap();
+        assert_eq!(block.transactions.len(), 1);
+        blockchain.add_block(block).unwrap();
+
+        let receipt = blockchain.get_transaction_receipt(&starved.hash()).unwrap();
+        assert!(!receipt.success);
+        assert_eq!(receipt.gas_used, starved.gas_limit);
+        {
+            let state = blockchain.state.read().unwrap();
+            let account = state.get_account(sender.public_key().as_bytes()).unwrap();
+            assert_eq!(account.nonce, 1);
+            assert_eq!(account.balance, 100_000 - starved.gas_limit * starved.gas_price);
+            assert!(state.get_account(to.as_bytes()).is_none());
+        }
+
+        // The consumed nonce makes the same signed transaction invalid
+        let mut replay = blockchain.produce_block(vec![], vec![], sk.public_key()).unwrap();
+        replay.transactions.push(starved);
+        assert!(blockchain.add_block(replay).is_err());
+    }
+    
+    #[test]
+    fn test_reused_nullifier_rejected() {
+        let sk = Arc::new(SecretKey::generate());
+        let (verifier, prove) = spend_prover();
+        let blockchain = Blockchain::new(sk.clone(), MetricsRegistry::new())
+            .with_spend_verifier(verifier);
+        let to = SecretKey::generate().public_key();
+
+        let spend = |spends: Vec<PrivateSpend>| {
+            let sender = SecretKey::generate();
+            blockchain.state.write().unwrap()
+                .credit_account(*sender.public_key().as_bytes(), 100_000).unwrap();
+            let mut tx = signed_transfer(&sender, to, 100, 0);
+            tx.spends = spends;
+            tx.gas_limit = 100_000;
+            tx.signature = sender.sign(tx.signing_hash().as_bytes());
+            tx
+        };
+
+        let first = spend(vec![prove(1)]);
+        let block = blockchain.produce_block(vec![first.clone()], vec![], sk.public_key()).unwrap();
+        blockchain.add_block(block).unwrap();
+        assert!(blockchain.get_transaction_receipt(&first.hash()).unwrap().success);
+
+        // Reusing the nullifier in a later block fails without moving funds;
+        // a fresh one still goes through
+        let reused = spend(vec![prove(1)]);
+        let fresh = spend(vec![prove(2)]);
+        let block = blockchain.produce_block(
+            vec![reused.clone(), fresh.clone()],
+            vec![],
+            sk.public_key(),
+        ).unwrap();
+        assert_eq!(block.transactions.len(), 2);
+        blockchain.add_block(block).unwrap();
+
+        assert!(!blockchain.get_transaction_receipt(&reused.hash()).unwrap().success);
+        assert!(blockchain.get_transaction_receipt(&fresh.hash()).unwrap().success);
+        let state = blockchain.state.read().unwrap();
+        let reused_sender = state.get_account(reused.from.as_bytes()).unwrap();
+        assert_eq!((reused_sender.balance, reused_sender.nonce), (100_000 - 21_000, 1));
+        assert_eq!(state.get_account(to.as_bytes()).unwrap().balance, 200);
+        assert!(state.is_nullifier_spent(&fresh.spends[0].nullifier));
+    }
+    
+    #[test]
+    fn test_failed_spend_leaves_nullifiers_unspent() {
+        let sk = Arc::new(SecretKey::generate());
+        let (verifier, prove) = spend_prover();
+        let blockchain = Blockchain::new(sk.clone(), MetricsRegistry::new())
+            .with_spend_verifier(verifier);
+        let sender = SecretKey::generate();
+        let to = SecretKey::generate().public_key();
+        blockchain.state.write().unwrap()
+            .credit_account(*sender.public_key().as_bytes(), 100_000).unwrap();
+        
+        let spend = |nonce, amount, spends: Vec<PrivateSpend>| {
+            let mut tx = signed_transfer(&sender, to, amount, nonce);
+            tx.spends = spends;
+            tx.gas_limit = 100_000;
+            tx.signature = sender.sign(tx.signing_hash().as_bytes());
+            tx
+        };
+        
+        // A nullifier listed twice is rejected outright, though its gas and
+        // nonce are still consumed
+        let repeated = spend(0, 100, vec![prove(3), prove(3)]);
+        let block = blockchain.produce_block(vec![repeated.clone()], vec![], sk.public_key()).unwrap();
+        blockchain.add_block(block).unwrap();
+        assert!(!blockchain.get_transaction_receipt(&repeated.hash()).unwrap().success);
+
+        // The second spend is covered by the balance before the block, so it
+        // passes validation, but fails once the first has applied
+        let first = spend(1, 100, vec![]);
+        let overdraw = spend(2, 100_000 - 21_000, vec![prove(4)]);
+        let block = blockchain.produce_block(
+            vec![first.clone(), overdraw.clone()],
+            vec![],
+            sk.public_key(),
+        ).unwrap();
+        assert_eq!(block.transactions.len(), 2);
+        blockchain.add_block(block).unwrap();
+        
+        assert!(blockchain.get_transaction_receipt(&first.hash()).unwrap().success);
+        assert!(!blockchain.get_transaction_receipt(&overdraw.hash()).unwrap().success);
+        let state = blockchain.state.read().unwrap();
+        assert!(!state.is_nullifier_spent(&repeated.spends[0].nullifier));
+        assert!(!state.is_nullifier_spent(&overdraw.spends[0].nullifier));
+        let account = state.get_account(sender.public_key().as_bytes()).unwrap();
+        assert_eq!((account.balance, account.nonce), (100_000 - 2 * 21_000 - 100, 3));
+    }
+
+    #[test]
+    fn test_unproven_spend_rejected() {
+        let sk = Arc::new(SecretKey::generate());
+        let (verifier, prove) = spend_prover();
+        let sender = SecretKey::generate();
+        let to = SecretKey::generate().public_key();
+        let spend = |spends: Vec<PrivateSpend>| {
+            let mut tx = signed_transfer(&sender, to, 100, 0);
+            tx.spends = spends;
+            tx.gas_limit = 100_000;
+            tx.signature = sender.sign(tx.signing_hash().as_bytes());
+            tx
+        };
+
+        // Copying a victim's nullifier next to someone else's proof does not
+        // burn it
+        let victim = prove(5);
+        let front_run = spend(vec![PrivateSpend { nullifier: victim.nullifier, ..prove(6) }]);
+
+        let unverified = Blockchain::new(sk.clone(), MetricsRegistry::new());
+        let verified = Blockchain::new(sk.clone(), MetricsRegistry::new())
+            .with_spend_verifier(verifier);
+        for blockchain in [&unverified, &verified] {
+            blockchain.state.write().unwrap()
+                .credit_account(*sender.public_key().as_bytes(), 100_000).unwrap();
+            let mut block = blockchain.produce_block(vec![], vec![], sk.public_key()).unwrap();
+            block.transactions.push(front_run.clone());
+            assert!(blockchain.verify_spends(&front_run).is_err());
+            assert!(blockchain.add_block(block).is_err());
+            assert!(!blockchain.state.read().unwrap().is_nullifier_spent(&victim.nullifier));
+        }
+
+        // Only a chain with the verifying key accepts a proven spend
+        let proven = spend(vec![victim]);
+        assert!(unverified.verify_spends(&proven).is_err());
+        assert!(verified.verify_spends(&proven).is_ok());
+    }
+    
     #[test]
     fn test_vrf_deterministic() {
         // VRF should be deterministic - same input should produce same output
@@ -8,14 +8,68 @@ pub struct NodeConfig {
     pub mode: NodeMode,
     pub network_port: u16,
     pub rpc_port: u16,
+    /// Port of the admin JSON-RPC listener, bound to localhost only
+    #[serde(default = "default_admin_rpc_port")]
+    pub admin_rpc_port: u16,
     pub enable_dht: bool,
     pub bootstrap_nodes: Vec<String>,
+    /// Dial attempts per bootstrap node before giving up (exponential backoff between tries)
+    #[serde(default = "default_bootstrap_max_attempts")]
+    pub bootstrap_max_attempts: u32,
     pub key_seed: Option<String>,
     /// Block production interval in seconds.
     /// Defaults to 10 seconds for testing. Use 600 (10 minutes) for production.
     pub block_time_secs: u64,
     /// Data directory for persistent storage. If None, uses in-memory storage only.
     pub data_dir: Option<std::path::PathBuf>,
+    /// Maximum number of pending transactions held in the mempool
+    #[serde(default = "default_tx_pool_size")]
+    pub tx_pool_size: usize,
+    /// Maximum number of connected peers; further connections are refused
+    #[serde(default = "default_max_peers")]
+    pub max_peers: usize,
+    /// Log filter, e.g. `info` or `info,bitcell_node=debug`
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    /// JSON file mapping hex public keys to initial balances, applied on a fresh chain
+    #[serde(default)]
+    pub genesis_alloc: Option<std::path::PathBuf>,
+}
+
+/// Upper bound accepted for `tx_pool_size`
+pub const MAX_TX_POOL_SIZE: usize = 1_000_000;
+
+/// Upper bound accepted for `max_peers`
+pub const MAX_PEERS_LIMIT: usize = 1_000;
+
+fn default_tx_pool_size() -> usize {
+    10_000
+}
+
+fn default_max_peers() -> usize {
+    50
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+/// Prometheus metrics port served by the node binary: two above the P2P port
+pub fn metrics_port_for(p2p_port: u16) -> u16 {
+    p2p_port.saturating_add(2)
+}
+
+/// Admin listener port used when none is given: two above the RPC port
+pub fn default_admin_rpc_port_for(rpc_port: u16) -> u16 {
+    rpc_port.saturating_add(2)
+}
+
+fn default_admin_rpc_port() -> u16 {
+    default_admin_rpc_port_for(NodeConfig::default().rpc_port)
+}
+
+fn default_bootstrap_max_attempts() -> u32 {
+    5
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,11 +85,73 @@ impl Default for NodeConfig {
             mode: NodeMode::Validator,
             network_port: 30333,
             rpc_port: 9933,
+            admin_rpc_port: default_admin_rpc_port_for(9933),
             enable_dht: false, // Disabled by default for backwards compatibility
             bootstrap_nodes: vec![],
+            bootstrap_max_attempts: default_bootstrap_max_attempts(),
             key_seed: None,
             block_time_secs: 10, // Default to 10 seconds for testing
             data_dir: None, // Default to in-memory storage for testing
+            tx_pool_size: default_tx_pool_size(),
+            max_peers: default_max_peers(),
+            log_level: default_log_level(),
+            genesis_alloc: None,
         }
     }
 }
+
+/// Outcome of reloading the configuration of a running node
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigReload {
+    /// Changed fields that were applied in place
+    pub applied: Vec<String>,
+    /// Changed fields that were left as-is because they need a restart
+    pub restart_required: Vec<String>,
+}
+
+impl NodeConfig {
+    /// Fields a running node applies without a restart
+    pub fn reloadable_fields() -> &'static [&'static str] {
+        &["tx_pool_size", "max_peers", "log_level"]
+    }
+
+    /// Check that the limits are usable
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_TX_POOL_SIZE).contains(&self.tx_pool_size) {
+            return Err(format!("tx_pool_size must be between 1 and {}", MAX_TX_POOL_SIZE));
+        }
+        if !(1..=MAX_PEERS_LIMIT).contains(&self.max_peers) {
+            return Err(format!("max_peers must be between 1 and {}", MAX_PEERS_LIMIT));
+        }
+        crate::logging::validate_filter(&self.log_level)
+    }
+
+    /// Names of the fields whose values differ between `self` and `other`
+    pub fn changed_fields(&self, other: &NodeConfig) -> Vec<String> {
+        let (Ok(serde_json::Value::Object(ours)), Ok(serde_json::Value::Object(theirs))) =
+            (serde_json::to_value(self), serde_json::to_value(other))
+        else {
+            return Vec::new();
+        };
+        ours.into_iter()
+            .filter(|(field, value)| theirs.get(field) != Some(value))
+            .map(|(field, _)| field)
+            .collect()
+    }
+
+    /// Take the reloadable fields from `new`, reporting any other changes
+    ///
+    /// `new` is validated first; nothing is applied if it is rejected.
+    pub fn reload(&mut self, new: &NodeConfig) -> Result<ConfigReload, String> {
+        new.validate()?;
+        let (applied, restart_required) = self
+            .changed_fields(new)
+            .into_iter()
+            .partition(|field| Self::reloadable_fields().contains(&field.as_str()));
+        self.tx_pool_size = new.tx_pool_size;
+        self.max_peers = new.max_peers;
+        self.log_level = new.log_level.clone();
+        Ok(ConfigReload { applied, restart_required })
+    }
+}
+
@@ -0,0 +1,301 @@
+//! Contract registry and gas metering
+//!
+//! Holds compiled BCL contracts and their storage by address. Calls are
+//! metered in a ZKVM interpreter capped at the caller's gas limit; storage
+//! writes only persist once the caller commits a successful call.
+//!
+//! Call data layout: the function selector (zero-extended) followed by each
+//! argument, all as little-endian `u64`s. The caller is exposed to contracts
+//! as `msg.sender` through [`address_word`].
+
+use bitcell_compiler::codegen::function_selector;
+use bitcell_compiler::stdlib::{functions, memory};
+use bitcell_compiler::Program;
+use bitcell_economics::{GAS_TX_BASE, GAS_TX_DATA_NONZERO, GAS_TX_DATA_ZERO, MAX_GAS_PER_BLOCK};
+use bitcell_zkvm::Interpreter;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Percentage added on top of metered gas to absorb state-dependent paths
+pub const GAS_ESTIMATE_MARGIN_PCT: u64 = 20;
+
+/// Contract account address (the account's public key bytes)
+pub type ContractAddress = [u8; 33];
+
+/// Contract storage cells by memory address
+pub type ContractStorage = HashMap<u32, u64>;
+
+/// Compiled contracts and their storage by address
+#[derive(Clone, Default)]
+pub struct ContractRegistry {
+    programs: Arc<RwLock<HashMap<ContractAddress, Program>>>,
+    storage: Arc<RwLock<HashMap<ContractAddress, ContractStorage>>>,
+}
+
+/// Result of executing a transaction's call, pending `ContractRegistry::commit`
+#[derive(Debug)]
+pub struct ContractCall {
+    /// Intrinsic plus execution gas, never above the transaction's gas limit
+    pub gas_used: u64,
+    /// Why the call failed or reverted, if it did
+    pub result: Result<(), String>,
+    /// Storage after a successful contract call
+    storage: Option<(ContractAddress, ContractStorage)>,
+}
+
+impl ContractRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compile `source` and register it at `address`, replacing any previous
+    /// code and clearing its storage
+    pub fn deploy(&self, address: ContractAddress, source: &str) -> Result<(), String> {
+        let program = bitcell_compiler::compile_program(source)
+            .map_err(|e| format!("Failed to compile contract: {}", e))?;
+        self.programs.write().unwrap().insert(address, program);
+        self.storage.write().unwrap().remove(&address);
+        Ok(())
+    }
+
+    /// Whether `address` holds contract code
+    pub fn is_contract(&self, address: &ContractAddress) -> bool {
+        self.programs.read().unwrap().contains_key(address)
+    }
+
+    /// Estimate the gas limit for a transaction from `from` to `to` carrying
+    /// `data`
+    ///
+    /// Transactions to plain accounts cost their intrinsic gas (`GAS_TX_BASE`
+    /// plus call data). Contract calls are executed as `from` against the
+    /// contract's current storage without committing; the result is the
+    /// intrinsic cost plus the metered execution gas, raised by
+    /// `GAS_ESTIMATE_MARGIN_PCT`.
+    pub fn estimate_gas(
+        &self,
+        from: &ContractAddress,
+        to: &ContractAddress,
+        data: &[u8],
+    ) -> Result<u64, String> {
+        if !self.is_contract(to) {
+            return Ok(intrinsic_gas(data));
+        }
+
+        let call = self.execute(from, to, data, MAX_GAS_PER_BLOCK);
+        call.result?;
+        Ok(call.gas_used + call.gas_used * GAS_ESTIMATE_MARGIN_PCT / 100)
+    }
+
+    /// Execute a transaction from `from` to `to` carrying `data` within
+    /// `gas_limit`
+    ///
+    /// Transfers to plain accounts only use their intrinsic gas. Contract
+    /// calls run with `from` as `msg.sender` against the contract's storage
+    /// with whatever gas remains after the intrinsic cost; running out,
+    /// reverting or failing marks the call failed, and the gas spent up to
+    /// that point is still used. Nothing is written until the call is passed
+    /// to [`ContractRegistry::commit`].
+    pub fn execute(
+        &self,
+        from: &ContractAddress,
+        to: &ContractAddress,
+        data: &[u8],
+        gas_limit: u64,
+    ) -> ContractCall {
+        let intrinsic = intrinsic_gas(data);
+        if intrinsic > gas_limit {
+            return ContractCall {
+                gas_used: gas_limit,
+                result: Err(format!("Intrinsic gas {} exceeds gas limit {}", intrinsic, gas_limit)),
+                storage: None,
+            };
+        }
+
+        let programs = self.programs.read().unwrap();
+        let program = match programs.get(to) {
+            Some(program) => program,
+            None => return ContractCall { gas_used: intrinsic, result: Ok(()), storage: None },
+        };
+
+        let storage = self.storage.read().unwrap().get(to).cloned().unwrap_or_default();
+        let mut interp = Interpreter::new(gas_limit - intrinsic)
+            .with_revert_messages(program.revert_messages.clone());
+        let result = load_storage(&mut interp, &storage)
+            .and_then(|_| run_call(&mut interp, program, from, data));
+        let storage = match result {
+            Ok(()) => Some((*to, read_storage(&interp))),
+            Err(_) => None,
+        };
+
+        ContractCall {
+            gas_used: intrinsic + interp.gas_used(),
+            result,
+            storage,
+        }
+    }
+
+    /// Persist the storage written by a successful call; failed calls and
+    /// plain transfers leave storage untouched
+    pub fn commit(&self, call: ContractCall) {
+        if let Some((address, storage)) = call.storage {
+            self.storage.write().unwrap().insert(address, storage);
+        }
+    }
+}
+
+/// Encode a call with `args` to the function whose canonical signature is
+/// `signature`, e.g. `transfer(address,uint)`
+pub fn encode_call(signature: &str, args: &[u64]) -> Vec<u8> {
+    std::iter::once(function_selector(signature) as u64)
+        .chain(args.iter().copied())
+        .flat_map(u64::to_le_bytes)
+        .collect()
+}
+
+/// Word a contract sees as `msg.sender` for the account `address`: the first
+/// eight bytes of its key after the compression prefix, little-endian
+pub fn address_word(address: &ContractAddress) -> u64 {
+    u64::from_le_bytes(address[1..9].try_into().unwrap())
+}
+
+/// Intrinsic gas of a transaction carrying `data`: the base cost plus call data
+pub(crate) fn intrinsic_gas(data: &[u8]) -> u64 {
+    data.iter().fold(GAS_TX_BASE, |gas, byte| {
+        gas + if *byte == 0 { GAS_TX_DATA_ZERO } else { GAS_TX_DATA_NONZERO }
+    })
+}
+
+fn load_storage(interp: &mut Interpreter, storage: &ContractStorage) -> Result<(), String> {
+    for (&addr, &value) in storage {
+        interp.write_memory(addr, value).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Non-zero cells of the storage region after execution
+fn read_storage(interp: &Interpreter) -> ContractStorage {
+    (memory::STORAGE_START..memory::STACK_START)
+        .filter_map(|addr| match interp.read_memory(addr) {
+            Ok(0) | Err(_) => None,
+            Ok(value) => Some((addr, value)),
+        })
+        .collect()
+}
+
+fn run_call(
+    interp: &mut Interpreter,
+    program: &Program,
+    from: &ContractAddress,
+    data: &[u8],
+) -> Result<(), String> {
+    let chunks = data.chunks_exact(8);
+    if data.is_empty() || !chunks.remainder().is_empty() {
+        return Err("Call data must be a selector followed by 8-byte arguments".to_string());
+    }
+    let mut words = chunks.map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()));
+
+    let write = |interp: &mut Interpreter, addr: u32, value: u64| {
+        interp.write_memory(addr, value).map_err(|e| e.to_string())
+    };
+    write(interp, functions::MSG_SENDER_ADDR, address_word(from))?;
+    write(interp, memory::FUNCTION_SELECTOR, words.next().unwrap())?;
+    for (i, arg) in words.enumerate() {
+        write(interp, memory::PARAMS_START + (i * 8) as u32, arg)?;
+    }
+
+    interp.execute(&program.instructions).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcell_compiler::stdlib::patterns::COUNTER_CONTRACT;
+
+    const CALLER: ContractAddress = [3u8; 33];
+
+    #[test]
+    fn test_plain_transfer_estimate() {
+        let registry = ContractRegistry::new();
+        assert_eq!(registry.estimate_gas(&CALLER, &[2u8; 33], &[]).unwrap(), GAS_TX_BASE);
+
+        // Data sent to a plain account is still charged
+        let data = [0u8, 1, 2];
+        assert_eq!(
+            registry.estimate_gas(&CALLER, &[2u8; 33], &data).unwrap(),
+            GAS_TX_BASE + GAS_TX_DATA_ZERO + 2 * GAS_TX_DATA_NONZERO
+        );
+    }
+
+    #[test]
+    fn test_counter_increment_estimate() {
+        let registry = ContractRegistry::new();
+        let address = [7u8; 33];
+        registry.deploy(address, COUNTER_CONTRACT).unwrap();
+        assert!(registry.is_contract(&address));
+
+        let data = encode_call("increment()", &[]);
+        let estimate = registry.estimate_gas(&CALLER, &address, &data).unwrap();
+
+        // Above the intrinsic cost, but a short function stays cheap
+        let floor = intrinsic_gas(&data);
+        assert!(estimate > floor);
+        assert!(estimate < floor + floor * GAS_ESTIMATE_MARGIN_PCT / 100 + 1_000);
+    }
+
+    #[test]
+    fn test_reverting_call_fails_estimate() {
+        let registry = ContractRegistry::new();
+        let address = [7u8; 33];
+        registry.deploy(address, COUNTER_CONTRACT).unwrap();
+
+        // Decrementing a fresh counter hits its require()
+        let err = registry.estimate_gas(&CALLER, &address, &encode_call("decrement()", &[])).unwrap_err();
+        assert!(err.contains("Counter underflow"));
+    }
+
+    #[test]
+    fn test_execute_meters_within_gas_limit() {
+        let registry = ContractRegistry::new();
+        let address = [7u8; 33];
+        registry.deploy(address, COUNTER_CONTRACT).unwrap();
+
+        let data = encode_call("increment()", &[]);
+        let call = registry.execute(&CALLER, &address, &data, MAX_GAS_PER_BLOCK);
+        assert!(call.result.is_ok());
+        assert!(call.gas_used > intrinsic_gas(&data));
+        assert!(call.gas_used < registry.estimate_gas(&CALLER, &address, &data).unwrap());
+
+        // A limit between the intrinsic cost and the execution cost runs out
+        let limit = intrinsic_gas(&data) + 1;
+        let starved = registry.execute(&CALLER, &address, &data, limit);
+        assert!(starved.result.is_err());
+        assert!(starved.gas_used <= limit);
+
+        // Transfers to plain accounts only pay the intrinsic cost
+        let call = registry.execute(&CALLER, &[2u8; 33], &data, MAX_GAS_PER_BLOCK);
+        assert_eq!(call.gas_used, intrinsic_gas(&data));
+        assert!(call.result.is_ok());
+    }
+
+    #[test]
+    fn test_committed_storage_persists_between_calls() {
+        let registry = ContractRegistry::new();
+        let address = [7u8; 33];
+        registry.deploy(address, COUNTER_CONTRACT).unwrap();
+        let decrement = encode_call("decrement()", &[]);
+
+        // Decrementing a fresh counter reverts, and spent gas is still used
+        let reverted = registry.execute(&CALLER, &address, &decrement, MAX_GAS_PER_BLOCK);
+        assert!(reverted.result.unwrap_err().contains("Counter underflow"));
+        assert!(reverted.gas_used > intrinsic_gas(&decrement));
+
+        // An uncommitted increment leaves the counter at zero
+        let increment = encode_call("increment()", &[]);
+        let _ = registry.execute(&CALLER, &address, &increment, MAX_GAS_PER_BLOCK);
+        assert!(registry.execute(&CALLER, &address, &decrement, MAX_GAS_PER_BLOCK).result.is_err());
+
+        registry.commit(registry.execute(&CALLER, &address, &increment, MAX_GAS_PER_BLOCK));
+        assert!(registry.execute(&CALLER, &address, &decrement, MAX_GAS_PER_BLOCK).result.is_ok());
+    }
+}
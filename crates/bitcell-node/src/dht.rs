@@ -51,6 +51,8 @@ pub struct CompactBlock {
     pub battle_proofs: Vec<bitcell_consensus::BattleProof>,
     /// Block signature (preserved from original block)
     pub signature: bitcell_crypto::Signature,
+    /// Finality votes (preserved from original block)
+    pub finality_votes: Vec<bitcell_consensus::FinalityVote>,
 }
 
 impl CompactBlock {
@@ -79,6 +81,7 @@ impl CompactBlock {
             prefilled_txs,
             battle_proofs: block.battle_proofs.clone(),
             signature: block.signature,
+            finality_votes: block.finality_votes.clone(),
         }
     }
     
@@ -126,6 +129,8 @@ impl CompactBlock {
             transactions,
             battle_proofs: self.battle_proofs.clone(),
             signature: self.signature,
+            finality_votes: self.finality_votes.clone(),
+            finality_status: bitcell_consensus::FinalityStatus::Pending,
         })
     }
 }
@@ -429,9 +434,101 @@ impl DhtManager {
     }
 }
 
+/// Delay before the first bootstrap redial; doubles on every further failure
+pub const BOOTSTRAP_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound on the delay between two bootstrap dial attempts
+pub const BOOTSTRAP_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Dial a bootstrap node, retrying with exponential backoff
+///
+/// `dial` is called with the 1-based attempt number, at most `max_attempts`
+/// times. Every attempt and every failure is counted in `metrics`. Returns
+/// the first success, or the last error once all attempts are used up.
+pub async fn dial_with_backoff<F, Fut, T, E>(
+    target: &str,
+    max_attempts: u32,
+    base_delay: Duration,
+    metrics: &crate::MetricsRegistry,
+    mut dial: F,
+) -> Result<T, E>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: std::fmt::Display,
+{
+    let max_attempts = max_attempts.max(1);
+    let mut delay = base_delay;
+    let mut attempt = 1;
+    loop {
+        metrics.inc_dht_bootstrap_attempts();
+        tracing::info!("Bootstrap dial to {} (attempt {}/{})", target, attempt, max_attempts);
+        match dial(attempt).await {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                metrics.inc_dht_bootstrap_failures();
+                if attempt >= max_attempts {
+                    tracing::warn!("Giving up on bootstrap node {} after {} attempts: {}", target, attempt, e);
+                    return Err(e);
+                }
+                tracing::warn!("Bootstrap dial to {} failed: {}; retrying in {:?}", target, e, delay);
+            }
+        }
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(BOOTSTRAP_MAX_DELAY);
+        attempt += 1;
+    }
+}
+
 /// Information about a discovered peer
 #[derive(Debug, Clone)]
 pub struct PeerInfo {
     pub peer_id: PeerId,
     pub addresses: Vec<Multiaddr>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MetricsRegistry;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn test_bootstrap_retries_until_success() {
+        let metrics = MetricsRegistry::new();
+        let calls = AtomicU32::new(0);
+
+        // Mock dialer: fails twice, then connects
+        let result = dial_with_backoff("mock", 5, Duration::from_millis(1), &metrics, |attempt| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            async move {
+                if attempt < 3 {
+                    Err(format!("connection refused (attempt {})", attempt))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        assert_eq!(metrics.get_dht_bootstrap_attempts(), 3);
+        assert_eq!(metrics.get_dht_bootstrap_failures(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_gives_up_after_max_attempts() {
+        let metrics = MetricsRegistry::new();
+
+        let result: Result<(), String> =
+            dial_with_backoff("mock", 2, Duration::from_millis(1), &metrics, |_| async {
+                Err("unreachable".to_string())
+            })
+            .await;
+
+        assert_eq!(result, Err("unreachable".to_string()));
+        assert_eq!(metrics.get_dht_bootstrap_attempts(), 2);
+        assert_eq!(metrics.get_dht_bootstrap_failures(), 2);
+    }
+}
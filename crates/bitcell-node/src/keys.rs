@@ -4,6 +4,7 @@ use bitcell_crypto::SecretKey;
 use std::fs;
 use std::path::Path;
 use crate::{Result, Error};
+use bitcell_wallet::{Chain, ChainConfig, DerivationPath, Mnemonic, Wallet, WalletConfig};
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 
 /// Load a secret key from a file
@@ -97,15 +98,41 @@ fn load_secret_key_from_pem(pem: &str) -> Result<SecretKey> {
     }
 }
 
+/// BIP44 account reserved for node identity keys ("NODE" in ASCII)
+///
+/// Wallets only derive addresses under account 0, so a node key never
+/// doubles as a spending key for the same phrase.
+pub const NODE_KEY_ACCOUNT: u32 = 0x4E4F_4445;
+
+/// Derivation path of the node key: `m/44'/<BitCell>'/<NODE_KEY_ACCOUNT>'/0'/0`
+pub fn node_key_path() -> DerivationPath {
+    DerivationPath::bip44(Chain::BitCell.coin_type(), NODE_KEY_ACCOUNT, 0, 0)
+}
+
 /// Derive a secret key from a BIP39 mnemonic phrase
-pub fn derive_secret_key_from_mnemonic(mnemonic: &str) -> Result<SecretKey> {
-    // Use SHA-256 of the mnemonic as the seed
-    // In production, use proper BIP39 derivation with PBKDF2
-    let hash = bitcell_crypto::Hash256::hash(mnemonic.as_bytes());
-    SecretKey::from_bytes(hash.as_bytes())
+///
+/// Uses the dedicated [`node_key_path`] (empty passphrase) rather than a
+/// wallet address.
+pub fn derive_secret_key_from_mnemonic(phrase: &str) -> Result<SecretKey> {
+    let mnemonic = Mnemonic::from_phrase(phrase)
+        .map_err(|e| Error::Node(format!("Invalid mnemonic: {}", e)))?;
+    let config = WalletConfig {
+        chains: vec![ChainConfig::new(Chain::BitCell)],
+        auto_generate_addresses: false,
+        ..WalletConfig::default()
+    };
+    Wallet::from_mnemonic(&mnemonic, "", config)
+        .derive_secret_key(&node_key_path())
         .map_err(|e| Error::Node(format!("Failed to derive key from mnemonic: {}", e)))
 }
 
+/// Read a BIP39 mnemonic phrase from a file, ignoring surrounding whitespace
+pub fn load_mnemonic_from_file(path: &Path) -> Result<String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| Error::Node(format!("Failed to read mnemonic file: {}", e)))?;
+    Ok(contents.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
 /// Derive a secret key from a simple string seed
 pub fn derive_secret_key_from_seed(seed: &str) -> SecretKey {
     let hash = bitcell_crypto::Hash256::hash(seed.as_bytes());
@@ -114,10 +141,14 @@ pub fn derive_secret_key_from_seed(seed: &str) -> SecretKey {
 }
 
 /// Resolve secret key from CLI arguments in priority order
+///
+/// `private_key_hex` > `key_file_path` > `mnemonic_file` > `mnemonic` >
+/// `key_seed`; a random key is generated when none are given.
 pub fn resolve_secret_key(
     private_key_hex: Option<&str>,
     key_file_path: Option<&Path>,
     mnemonic: Option<&str>,
+    mnemonic_file: Option<&Path>,
     key_seed: Option<&str>,
 ) -> Result<SecretKey> {
     // Priority 1: Direct hex private key
@@ -132,7 +163,11 @@ pub fn resolve_secret_key(
         return load_secret_key_from_file(path);
     }
     
-    // Priority 3: Mnemonic phrase
+    // Priority 3: Mnemonic phrase, from a file or given directly
+    if let Some(path) = mnemonic_file {
+        tracing::info!("Deriving key from mnemonic file: {}", path.display());
+        return derive_secret_key_from_mnemonic(&load_mnemonic_from_file(path)?);
+    }
     if let Some(phrase) = mnemonic {
         tracing::info!("Deriving key from mnemonic phrase");
         return derive_secret_key_from_mnemonic(phrase);
@@ -173,4 +208,59 @@ mod tests {
         let sk2 = derive_secret_key_from_seed("test-seed");
         assert_eq!(sk1.public_key(), sk2.public_key());
     }
+    
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon \
+                          abandon abandon abandon abandon abandon about";
+    
+    #[test]
+    fn test_mnemonic_derivation_is_stable() {
+        let sk1 = derive_secret_key_from_mnemonic(PHRASE).unwrap();
+        let sk2 = derive_secret_key_from_mnemonic(PHRASE).unwrap();
+        assert_eq!(sk1.public_key(), sk2.public_key());
+        
+        // Differs from hashing the phrase as a plain seed
+        assert_ne!(sk1.public_key(), derive_secret_key_from_seed(PHRASE).public_key());
+        
+        assert!(derive_secret_key_from_mnemonic("not a valid mnemonic").is_err());
+    }
+    
+    #[test]
+    fn test_mnemonic_node_key_is_not_a_wallet_key() {
+        let node_key = derive_secret_key_from_mnemonic(PHRASE).unwrap().public_key();
+        
+        let mnemonic = Mnemonic::from_phrase(PHRASE).unwrap();
+        let mut wallet = Wallet::from_mnemonic(&mnemonic, "", WalletConfig::default());
+        for index in 0..5 {
+            let address = wallet.generate_address(Chain::BitCell, index).unwrap();
+            let wallet_key = wallet.get_secret_key_for_address(&address).unwrap().public_key();
+            assert_ne!(node_key, wallet_key);
+        }
+    }
+    
+    #[test]
+    fn test_resolve_precedence() {
+        let hex_key = "11".repeat(32);
+        let file_key = "22".repeat(32);
+        let dir = tempfile::TempDir::new().unwrap();
+        let key_file = dir.path().join("node.key");
+        fs::write(&key_file, &file_key).unwrap();
+        
+        let from_hex = load_secret_key_from_hex(&hex_key).unwrap().public_key();
+        let from_file = load_secret_key_from_hex(&file_key).unwrap().public_key();
+        let from_mnemonic = derive_secret_key_from_mnemonic(PHRASE).unwrap().public_key();
+        let from_seed = derive_secret_key_from_seed("seed").public_key();
+        
+        let phrase_file = dir.path().join("node.mnemonic");
+        fs::write(&phrase_file, format!("  {}\n", PHRASE)).unwrap();
+        
+        let resolve = |hex: Option<&str>, file: Option<&Path>, phrase: Option<&str>, phrase_file: Option<&Path>, seed: Option<&str>| {
+            resolve_secret_key(hex, file, phrase, phrase_file, seed).unwrap().public_key()
+        };
+        
+        assert_eq!(resolve(Some(&hex_key), Some(&key_file), Some(PHRASE), Some(&phrase_file), Some("seed")), from_hex);
+        assert_eq!(resolve(None, Some(&key_file), Some(PHRASE), Some(&phrase_file), Some("seed")), from_file);
+        assert_eq!(resolve(None, None, Some("not a valid mnemonic"), Some(&phrase_file), Some("seed")), from_mnemonic);
+        assert_eq!(resolve(None, None, Some(PHRASE), None, Some("seed")), from_mnemonic);
+        assert_eq!(resolve(None, None, None, None, Some("seed")), from_seed);
+    }
 }
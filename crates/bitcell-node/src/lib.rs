@@ -14,15 +14,17 @@ pub mod tournament;
 pub mod network;
 pub mod dht;
 pub mod keys;
+pub mod contracts;
 
-pub use config::NodeConfig;
+pub use config::{ConfigReload, NodeConfig};
 pub use validator::ValidatorNode;
 pub use miner::MinerNode;
-pub use monitoring::{MetricsRegistry, logging};
-pub use blockchain::Blockchain;
+pub use monitoring::{MessageType, MetricsRegistry, logging};
+pub use blockchain::{Blockchain, TxReceipt};
 pub use tx_pool::TransactionPool;
 pub use tournament::TournamentManager;
 pub use network::NetworkManager;
+pub use contracts::ContractRegistry;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
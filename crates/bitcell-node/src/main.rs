@@ -20,6 +20,9 @@ enum Commands {
         port: u16,
         #[arg(long, default_value_t = 30334)]
         rpc_port: u16,
+        /// Localhost-only admin RPC port (defaults to the RPC port + 2)
+        #[arg(long)]
+        admin_rpc_port: Option<u16>,
         #[arg(long)]
         data_dir: Option<PathBuf>,
         #[arg(long)]
@@ -32,6 +35,14 @@ enum Commands {
         key_file: Option<PathBuf>,
         #[arg(long)]
         private_key: Option<String>,
+        /// BIP39 mnemonic phrase to derive the node key from (prefer the
+        /// environment variable or --mnemonic-file; arguments are visible to
+        /// other local users)
+        #[arg(long, env = "BITCELL_MNEMONIC", hide_env_values = true)]
+        mnemonic: Option<String>,
+        /// File holding the BIP39 mnemonic phrase to derive the node key from
+        #[arg(long, conflicts_with = "mnemonic")]
+        mnemonic_file: Option<PathBuf>,
     },
     /// Run as miner
     Miner {
@@ -39,6 +50,9 @@ enum Commands {
         port: u16,
         #[arg(long, default_value_t = 30334)]
         rpc_port: u16,
+        /// Localhost-only admin RPC port (defaults to the RPC port + 2)
+        #[arg(long)]
+        admin_rpc_port: Option<u16>,
         #[arg(long)]
         data_dir: Option<PathBuf>,
         #[arg(long)]
@@ -51,6 +65,14 @@ enum Commands {
         key_file: Option<PathBuf>,
         #[arg(long)]
         private_key: Option<String>,
+        /// BIP39 mnemonic phrase to derive the node key from (prefer the
+        /// environment variable or --mnemonic-file; arguments are visible to
+        /// other local users)
+        #[arg(long, env = "BITCELL_MNEMONIC", hide_env_values = true)]
+        mnemonic: Option<String>,
+        /// File holding the BIP39 mnemonic phrase to derive the node key from
+        #[arg(long, conflicts_with = "mnemonic")]
+        mnemonic_file: Option<PathBuf>,
     },
     /// Run as full node
     FullNode {
@@ -58,6 +80,9 @@ enum Commands {
         port: u16,
         #[arg(long, default_value_t = 30334)]
         rpc_port: u16,
+        /// Localhost-only admin RPC port (defaults to the RPC port + 2)
+        #[arg(long)]
+        admin_rpc_port: Option<u16>,
         #[arg(long)]
         data_dir: Option<PathBuf>,
         #[arg(long)]
@@ -70,6 +95,14 @@ enum Commands {
         key_file: Option<PathBuf>,
         #[arg(long)]
         private_key: Option<String>,
+        /// BIP39 mnemonic phrase to derive the node key from (prefer the
+        /// environment variable or --mnemonic-file; arguments are visible to
+        /// other local users)
+        #[arg(long, env = "BITCELL_MNEMONIC", hide_env_values = true)]
+        mnemonic: Option<String>,
+        /// File holding the BIP39 mnemonic phrase to derive the node key from
+        #[arg(long, conflicts_with = "mnemonic")]
+        mnemonic_file: Option<PathBuf>,
     },
     /// Show version
     Version,
@@ -79,13 +112,25 @@ enum Commands {
 async fn main() {
     let cli = Cli::parse();
 
+    // Install the tracing subscriber; `bitcell_reloadConfig` swaps its filter live
+    let log_filter = match bitcell_node::logging::LogFilterHandle::init(&NodeConfig::default().log_level) {
+        Ok(handle) => Some(handle),
+        Err(e) => {
+            eprintln!("Failed to initialize logging: {}", e);
+            None
+        }
+    };
+
     match cli.command {
-        Commands::Validator { port, rpc_port, data_dir, enable_dht, bootstrap, key_seed, key_file, private_key } => {
+        Commands::Validator { port, rpc_port, admin_rpc_port, data_dir, enable_dht, bootstrap, key_seed, key_file, private_key, mnemonic, mnemonic_file } => {
             println!("🌌 BitCell Validator Node");
             println!("=========================");
             
             let mut config = NodeConfig::default();
             config.network_port = port;
+            config.rpc_port = rpc_port;
+            config.admin_rpc_port = admin_rpc_port
+                .unwrap_or_else(|| bitcell_node::config::default_admin_rpc_port_for(rpc_port));
             config.enable_dht = enable_dht;
             config.key_seed = key_seed.clone();
             config.data_dir = data_dir;
@@ -97,7 +142,8 @@ async fn main() {
             let secret_key = match bitcell_node::keys::resolve_secret_key(
                 private_key.as_deref(),
                 key_file.as_deref(),
-                None, // Mnemonic not yet supported in CLI args
+                mnemonic.as_deref(),
+                mnemonic_file.as_deref(),
                 key_seed.as_deref()
             ) {
                 Ok(sk) => std::sync::Arc::new(sk),
@@ -130,7 +176,7 @@ async fn main() {
             };
             
             // Start metrics server on port + 2 to avoid conflict with P2P port (30333) and RPC port (30334)
-            let metrics_port = port + 2;
+            let metrics_port = bitcell_node::config::metrics_port_for(port);
             
             // Generate node_id from public key
             let node_id = hex::encode(secret_key.public_key().as_bytes());
@@ -141,11 +187,20 @@ async fn main() {
                 network: (*node.network).clone(),
                 tx_pool: node.tx_pool.clone(),
                 tournament_manager: Some(node.tournament_manager.clone()),
-                config: node.config.clone(),
+                config: std::sync::Arc::new(parking_lot::RwLock::new(node.config.clone())),
+                log_filter: log_filter.clone(),
                 node_type: "validator".to_string(),
                 node_id,
             };
             
+            let admin_state = rpc_state.clone();
+            let admin_rpc_port = node.config.admin_rpc_port;
+            tokio::spawn(async move {
+                if let Err(e) = bitcell_node::rpc::run_admin_server(admin_state, admin_rpc_port).await {
+                    eprintln!("Admin RPC server error: {}", e);
+                }
+            });
+
             tokio::spawn(async move {
                 println!("RPC server listening on 0.0.0.0:{}", rpc_port);
                 if let Err(e) = bitcell_node::rpc::run_server(rpc_state, rpc_port).await {
@@ -166,13 +221,20 @@ async fn main() {
             // Keep running
             tokio::signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
             println!("\nShutting down...");
+            if let Err(e) = node.shutdown().await {
+                eprintln!("Error during shutdown: {}", e);
+                std::process::exit(1);
+            }
         }
-        Commands::Miner { port, rpc_port, data_dir, enable_dht, bootstrap, key_seed, key_file, private_key } => {
+        Commands::Miner { port, rpc_port, admin_rpc_port, data_dir, enable_dht, bootstrap, key_seed, key_file, private_key, mnemonic, mnemonic_file } => {
             println!("⛏️  BitCell Miner Node");
             println!("======================");
             
             let mut config = NodeConfig::default();
             config.network_port = port;
+            config.rpc_port = rpc_port;
+            config.admin_rpc_port = admin_rpc_port
+                .unwrap_or_else(|| bitcell_node::config::default_admin_rpc_port_for(rpc_port));
             config.enable_dht = enable_dht;
             config.key_seed = key_seed.clone();
             config.data_dir = data_dir;
@@ -184,7 +246,8 @@ async fn main() {
             let secret_key = match bitcell_node::keys::resolve_secret_key(
                 private_key.as_deref(),
                 key_file.as_deref(),
-                None,
+                mnemonic.as_deref(),
+                mnemonic_file.as_deref(),
                 key_seed.as_deref()
             ) {
                 Ok(sk) => std::sync::Arc::new(sk),
@@ -204,7 +267,7 @@ async fn main() {
                 }
             };
             
-            let metrics_port = port + 2;
+            let metrics_port = bitcell_node::config::metrics_port_for(port);
 
             // Generate node_id from public key
             let node_id = hex::encode(secret_key.public_key().as_bytes());
@@ -215,11 +278,20 @@ async fn main() {
                 network: (*node.network).clone(),
                 tx_pool: node.tx_pool.clone(),
                 tournament_manager: None, // Miner doesn't have tournament manager yet
-                config: node.config.clone(),
+                config: std::sync::Arc::new(parking_lot::RwLock::new(node.config.clone())),
+                log_filter: log_filter.clone(),
                 node_type: "miner".to_string(),
                 node_id,
             };
             
+            let admin_state = rpc_state.clone();
+            let admin_rpc_port = node.config.admin_rpc_port;
+            tokio::spawn(async move {
+                if let Err(e) = bitcell_node::rpc::run_admin_server(admin_state, admin_rpc_port).await {
+                    eprintln!("Admin RPC server error: {}", e);
+                }
+            });
+
             tokio::spawn(async move {
                 println!("RPC server listening on 0.0.0.0:{}", rpc_port);
                 if let Err(e) = bitcell_node::rpc::run_server(rpc_state, rpc_port).await {
@@ -239,13 +311,20 @@ async fn main() {
             
             tokio::signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
             println!("\nShutting down...");
+            if let Err(e) = node.shutdown().await {
+                eprintln!("Error during shutdown: {}", e);
+                std::process::exit(1);
+            }
         }
-        Commands::FullNode { port, rpc_port, data_dir, enable_dht, bootstrap, key_seed, key_file, private_key } => {
+        Commands::FullNode { port, rpc_port, admin_rpc_port, data_dir, enable_dht, bootstrap, key_seed, key_file, private_key, mnemonic, mnemonic_file } => {
             println!("🌍 BitCell Full Node");
             println!("====================");
             
             let mut config = NodeConfig::default();
             config.network_port = port;
+            config.rpc_port = rpc_port;
+            config.admin_rpc_port = admin_rpc_port
+                .unwrap_or_else(|| bitcell_node::config::default_admin_rpc_port_for(rpc_port));
             config.enable_dht = enable_dht;
             config.key_seed = key_seed.clone();
             config.data_dir = data_dir;
@@ -257,7 +336,8 @@ async fn main() {
             let secret_key = match bitcell_node::keys::resolve_secret_key(
                 private_key.as_deref(),
                 key_file.as_deref(),
-                None,
+                mnemonic.as_deref(),
+                mnemonic_file.as_deref(),
                 key_seed.as_deref()
             ) {
                 Ok(sk) => std::sync::Arc::new(sk),
@@ -278,7 +358,7 @@ async fn main() {
                 }
             };
             
-            let metrics_port = port + 2;
+            let metrics_port = bitcell_node::config::metrics_port_for(port);
 
             // Generate node_id from public key
             let node_id = hex::encode(secret_key.public_key().as_bytes());
@@ -289,11 +369,20 @@ async fn main() {
                 network: (*node.network).clone(),
                 tx_pool: node.tx_pool.clone(),
                 tournament_manager: Some(node.tournament_manager.clone()),
-                config: node.config.clone(),
+                config: std::sync::Arc::new(parking_lot::RwLock::new(node.config.clone())),
+                log_filter: log_filter.clone(),
                 node_type: "full_node".to_string(),
                 node_id,
             };
             
+            let admin_state = rpc_state.clone();
+            let admin_rpc_port = node.config.admin_rpc_port;
+            tokio::spawn(async move {
+                if let Err(e) = bitcell_node::rpc::run_admin_server(admin_state, admin_rpc_port).await {
+                    eprintln!("Admin RPC server error: {}", e);
+                }
+            });
+
             tokio::spawn(async move {
                 println!("RPC server listening on 0.0.0.0:{}", rpc_port);
                 if let Err(e) = bitcell_node::rpc::run_server(rpc_state, rpc_port).await {
@@ -313,6 +402,10 @@ async fn main() {
             
             tokio::signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
             println!("\nShutting down...");
+            if let Err(e) = node.shutdown().await {
+                eprintln!("Error during shutdown: {}", e);
+                std::process::exit(1);
+            }
         }
         Commands::Version => {
             println!("bitcell-node v0.1.0");
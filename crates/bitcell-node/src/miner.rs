@@ -1,5 +1,6 @@
 //! Miner node implementation
 use crate::{NodeConfig, Result, MetricsRegistry, Blockchain, TransactionPool, NetworkManager};
+use crate::tx_pool::MEMPOOL_FILE;
 use bitcell_crypto::SecretKey;
 use bitcell_ca::{Glider, GliderPattern};
 use std::sync::Arc;
@@ -22,6 +23,7 @@ impl MinerNode {
     }
 
     pub fn with_key(config: NodeConfig, secret_key: Arc<SecretKey>) -> crate::Result<Self> {
+        config.validate().map_err(crate::Error::Config)?;
         let metrics = MetricsRegistry::new();
         
         // Create blockchain with or without persistent storage based on config
@@ -38,7 +40,24 @@ impl MinerNode {
             Blockchain::new(secret_key.clone(), metrics.clone())
         };
         
+        if let Some(ref alloc_path) = config.genesis_alloc {
+            let credited = blockchain.apply_genesis_alloc(alloc_path)?;
+            if credited > 0 {
+                println!("💰 Credited {} genesis accounts from {}", credited, alloc_path.display());
+            }
+        }
+        
         let network = Arc::new(NetworkManager::new(secret_key.public_key(), metrics.clone()));
+        network.set_max_peers(config.max_peers);
+        
+        let tx_pool = TransactionPool::new(config.tx_pool_size);
+        if let Some(ref data_path) = config.data_dir {
+            match tx_pool.load(&data_path.join(MEMPOOL_FILE)) {
+                Ok(0) => {}
+                Ok(n) => println!("Restored {} pooled transactions", n),
+                Err(e) => eprintln!("Failed to restore transaction pool: {}", e),
+            }
+        }
         
         Ok(Self {
             config,
@@ -46,7 +65,7 @@ impl MinerNode {
             glider_strategy: GliderPattern::Standard,
             metrics,
             blockchain,
-            tx_pool: TransactionPool::default(),
+            tx_pool,
             network,
         })
     }
@@ -56,7 +75,11 @@ impl MinerNode {
         println!("Glider strategy: {:?}", self.glider_strategy);
         
         // Start network layer
-        self.network.start(self.config.network_port, self.config.bootstrap_nodes.clone()).await?;
+        self.network.start(
+            self.config.network_port,
+            self.config.bootstrap_nodes.clone(),
+            self.config.bootstrap_max_attempts,
+        ).await?;
         
         // Enable DHT if configured
         if self.config.enable_dht {
@@ -83,6 +106,7 @@ impl MinerNode {
             gas_limit: 21000,
             gas_price: 1,
             data: vec![],
+            spends: vec![],
             signature: dummy_sig,
         };
         
@@ -159,6 +183,20 @@ impl MinerNode {
         Ok(())
     }
 
+    /// Close peer connections and persist state
+    ///
+    /// Saves the transaction pool and flushes state storage when a data
+    /// directory is configured.
+    pub async fn shutdown(&mut self) -> Result<()> {
+        self.network.shutdown().await;
+        
+        if let Some(ref data_path) = self.config.data_dir {
+            self.tx_pool.save(&data_path.join(MEMPOOL_FILE))
+                .map_err(crate::Error::Node)?;
+        }
+        self.blockchain.flush()
+    }
+
     pub fn generate_glider(&self) -> Glider {
         Glider::new(self.glider_strategy, bitcell_ca::Position::new(256, 512))
     }
@@ -0,0 +1,92 @@
+//! Lock-free latency histograms with Prometheus export
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Bucket upper bounds (ms) for proof generation
+pub const PROOF_GEN_BUCKETS_MS: &[u64] = &[100, 250, 500, 1_000, 2_500, 5_000, 10_000, 30_000, 60_000];
+
+/// Bucket upper bounds (ms) for proof verification
+pub const PROOF_VERIFY_BUCKETS_MS: &[u64] = &[1, 5, 10, 25, 50, 100, 250, 500, 1_000];
+
+/// Bucket upper bounds (ms) for the interval between blocks
+pub const BLOCK_TIME_BUCKETS_MS: &[u64] = &[1_000, 2_000, 5_000, 10_000, 15_000, 30_000, 60_000, 120_000];
+
+/// Fixed-bucket histogram of millisecond observations
+pub struct Histogram {
+    /// Inclusive upper bound of each bucket; `+Inf` is implicit
+    bounds: &'static [u64],
+    /// Per-bucket (non-cumulative) counts, with a trailing overflow bucket
+    buckets: Vec<AtomicU64>,
+    sum: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Histogram {
+    pub fn new(bounds: &'static [u64]) -> Self {
+        Self {
+            bounds,
+            buckets: (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    /// Record one observation
+    pub fn observe(&self, value_ms: u64) {
+        let idx = self.bounds.partition_point(|&bound| bound < value_ms);
+        self.buckets[idx].fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(value_ms, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Cumulative counts for each bound, followed by the `+Inf` total
+    pub fn cumulative_counts(&self) -> Vec<u64> {
+        self.buckets
+            .iter()
+            .scan(0, |total, bucket| {
+                *total += bucket.load(Ordering::Relaxed);
+                Some(*total)
+            })
+            .collect()
+    }
+
+    pub fn sum(&self) -> u64 {
+        self.sum.load(Ordering::Relaxed)
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Append this histogram in Prometheus text format
+    pub fn write_prometheus(&self, out: &mut String, name: &str, help: &str) {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} histogram", name);
+        let counts = self.cumulative_counts();
+        for (bound, count) in self.bounds.iter().zip(&counts) {
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bound, count);
+        }
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, counts[self.bounds.len()]);
+        let _ = writeln!(out, "{}_sum {}", name, self.sum());
+        let _ = writeln!(out, "{}_count {}", name, self.count());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_boundaries() {
+        let histogram = Histogram::new(&[10, 100]);
+        for value in [0, 10, 11, 100, 101, 5_000] {
+            histogram.observe(value);
+        }
+
+        // Bounds are inclusive: 10 lands in le=10, 100 in le=100
+        assert_eq!(histogram.cumulative_counts(), vec![2, 4, 6]);
+        assert_eq!(histogram.count(), 6);
+        assert_eq!(histogram.sum(), 5_222);
+    }
+}
@@ -1,6 +1,7 @@
 //! Structured logging for BitCell nodes
 
 use std::fmt;
+use tracing_subscriber::{layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry};
 
 /// Log levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -105,6 +106,46 @@ impl Logger {
     }
 }
 
+/// Check that `filter` parses as a tracing filter, e.g. `info,bitcell_node=debug`
+pub fn validate_filter(filter: &str) -> Result<(), String> {
+    EnvFilter::try_new(filter)
+        .map(|_| ())
+        .map_err(|e| format!("Invalid log level '{}': {}", filter, e))
+}
+
+/// Handle for changing the filter of the global tracing subscriber at runtime
+#[derive(Clone)]
+pub struct LogFilterHandle {
+    handle: reload::Handle<EnvFilter, Registry>,
+}
+
+impl LogFilterHandle {
+    /// Install the global tracing subscriber with `filter`
+    ///
+    /// `RUST_LOG`, when set, takes precedence over `filter` at startup.
+    pub fn init(filter: &str) -> Result<Self, String> {
+        let initial = EnvFilter::try_from_default_env()
+            .or_else(|_| EnvFilter::try_new(filter))
+            .map_err(|e| format!("Invalid log level '{}': {}", filter, e))?;
+        let (layer, handle) = reload::Layer::new(initial);
+        tracing_subscriber::registry()
+            .with(layer)
+            .with(tracing_subscriber::fmt::layer())
+            .try_init()
+            .map_err(|e| format!("Failed to install logger: {}", e))?;
+        Ok(Self { handle })
+    }
+
+    /// Replace the active filter
+    pub fn set_filter(&self, filter: &str) -> Result<(), String> {
+        let filter = EnvFilter::try_new(filter)
+            .map_err(|e| format!("Invalid log level '{}': {}", filter, e))?;
+        self.handle
+            .reload(filter)
+            .map_err(|e| format!("Failed to reload log level: {}", e))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,4 +188,11 @@ mod tests {
         logger.debug("test", "This is debug");
         logger.info("test", "This is info");
     }
+
+    #[test]
+    fn test_validate_filter() {
+        assert!(validate_filter("info").is_ok());
+        assert!(validate_filter("warn,bitcell_node=debug").is_ok());
+        assert!(validate_filter("bitcell_node=loud").is_err());
+    }
 }
@@ -4,10 +4,56 @@
 
 pub mod metrics;
 pub mod logging;
+pub mod histogram;
 
-use std::sync::Arc;
+use histogram::{Histogram, BLOCK_TIME_BUCKETS_MS, PROOF_GEN_BUCKETS_MS, PROOF_VERIFY_BUCKETS_MS};
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
+/// Running sum (fixed-point * 1000) and count of trust score samples
+#[derive(Debug, Default)]
+struct TrustSamples {
+    sum: u64,
+    count: u64,
+}
+
+/// Message categories for the per-type network counters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageType {
+    Block,
+    Transaction,
+    Vote,
+    Header,
+    /// Handshakes, pings, peer exchange
+    Control,
+}
+
+impl MessageType {
+    pub const ALL: [MessageType; 5] = [
+        MessageType::Block,
+        MessageType::Transaction,
+        MessageType::Vote,
+        MessageType::Header,
+        MessageType::Control,
+    ];
+    
+    /// Value of the Prometheus `type` label
+    pub fn label(&self) -> &'static str {
+        match self {
+            MessageType::Block => "block",
+            MessageType::Transaction => "transaction",
+            MessageType::Vote => "vote",
+            MessageType::Header => "header",
+            MessageType::Control => "control",
+        }
+    }
+    
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
 /// Global metrics registry
 #[derive(Clone)]
 pub struct MetricsRegistry {
@@ -21,6 +67,8 @@ pub struct MetricsRegistry {
     bytes_received: Arc<AtomicU64>,
     messages_sent: Arc<AtomicU64>,
     messages_received: Arc<AtomicU64>,
+    messages_sent_by_type: Arc<[AtomicU64; MessageType::ALL.len()]>,
+    messages_received_by_type: Arc<[AtomicU64; MessageType::ALL.len()]>,
     
     // Transaction pool metrics
     pending_txs: Arc<AtomicUsize>,
@@ -31,15 +79,27 @@ pub struct MetricsRegistry {
     proofs_verified: Arc<AtomicU64>,
     proof_gen_time_ms: Arc<AtomicU64>,
     proof_verify_time_ms: Arc<AtomicU64>,
+    proof_gen_hist: Arc<Histogram>,
+    proof_verify_hist: Arc<Histogram>,
+    proof_batches_verified: Arc<AtomicU64>,
+    batch_proofs_verified: Arc<AtomicU64>,
+    batch_proof_bytes: Arc<AtomicU64>,
+    batch_verify_time_ms: Arc<AtomicU64>,
+    
+    // Block production metrics
+    block_time_hist: Arc<Histogram>,
     
     // EBSL metrics
     active_miners: Arc<AtomicUsize>,
     banned_miners: Arc<AtomicUsize>,
-    avg_trust_score: Arc<AtomicU64>, // Stored as fixed-point * 1000
+    // Behind one lock so readers never see a sum without its count
+    trust_score_samples: Arc<Mutex<TrustSamples>>,
     slashing_events: Arc<AtomicU64>,
     
     // DHT metrics
     dht_peer_count: Arc<AtomicUsize>,
+    dht_bootstrap_attempts: Arc<AtomicU64>,
+    dht_bootstrap_failures: Arc<AtomicU64>,
 }
 
 impl MetricsRegistry {
@@ -52,17 +112,28 @@ impl MetricsRegistry {
             bytes_received: Arc::new(AtomicU64::new(0)),
             messages_sent: Arc::new(AtomicU64::new(0)),
             messages_received: Arc::new(AtomicU64::new(0)),
+            messages_sent_by_type: Arc::new(Default::default()),
+            messages_received_by_type: Arc::new(Default::default()),
             pending_txs: Arc::new(AtomicUsize::new(0)),
             total_txs_processed: Arc::new(AtomicU64::new(0)),
             proofs_generated: Arc::new(AtomicU64::new(0)),
             proofs_verified: Arc::new(AtomicU64::new(0)),
             proof_gen_time_ms: Arc::new(AtomicU64::new(0)),
             proof_verify_time_ms: Arc::new(AtomicU64::new(0)),
+            proof_gen_hist: Arc::new(Histogram::new(PROOF_GEN_BUCKETS_MS)),
+            proof_verify_hist: Arc::new(Histogram::new(PROOF_VERIFY_BUCKETS_MS)),
+            proof_batches_verified: Arc::new(AtomicU64::new(0)),
+            batch_proofs_verified: Arc::new(AtomicU64::new(0)),
+            batch_proof_bytes: Arc::new(AtomicU64::new(0)),
+            batch_verify_time_ms: Arc::new(AtomicU64::new(0)),
+            block_time_hist: Arc::new(Histogram::new(BLOCK_TIME_BUCKETS_MS)),
             active_miners: Arc::new(AtomicUsize::new(0)),
             banned_miners: Arc::new(AtomicUsize::new(0)),
-            avg_trust_score: Arc::new(AtomicU64::new(0)),
+            trust_score_samples: Arc::new(Mutex::new(TrustSamples::default())),
             slashing_events: Arc::new(AtomicU64::new(0)),
             dht_peer_count: Arc::new(AtomicUsize::new(0)),
+            dht_bootstrap_attempts: Arc::new(AtomicU64::new(0)),
+            dht_bootstrap_failures: Arc::new(AtomicU64::new(0)),
         }
     }
     
@@ -124,6 +195,26 @@ impl MetricsRegistry {
         self.messages_received.load(Ordering::Relaxed)
     }
     
+    /// Count a sent message in both the total and its per-type counter
+    pub fn add_message_sent_typed(&self, message_type: MessageType) {
+        self.add_message_sent();
+        self.messages_sent_by_type[message_type.index()].fetch_add(1, Ordering::Relaxed);
+    }
+    
+    /// Count a received message in both the total and its per-type counter
+    pub fn add_message_received_typed(&self, message_type: MessageType) {
+        self.add_message_received();
+        self.messages_received_by_type[message_type.index()].fetch_add(1, Ordering::Relaxed);
+    }
+    
+    pub fn get_messages_sent_typed(&self, message_type: MessageType) -> u64 {
+        self.messages_sent_by_type[message_type.index()].load(Ordering::Relaxed)
+    }
+    
+    pub fn get_messages_received_typed(&self, message_type: MessageType) -> u64 {
+        self.messages_received_by_type[message_type.index()].load(Ordering::Relaxed)
+    }
+    
     // Transaction pool metrics
     pub fn set_pending_txs(&self, count: usize) {
         self.pending_txs.store(count, Ordering::Relaxed);
@@ -152,10 +243,12 @@ impl MetricsRegistry {
     
     pub fn record_proof_gen_time(&self, time_ms: u64) {
         self.proof_gen_time_ms.store(time_ms, Ordering::Relaxed);
+        self.proof_gen_hist.observe(time_ms);
     }
     
     pub fn record_proof_verify_time(&self, time_ms: u64) {
         self.proof_verify_time_ms.store(time_ms, Ordering::Relaxed);
+        self.proof_verify_hist.observe(time_ms);
     }
     
     pub fn get_proofs_generated(&self) -> u64 {
@@ -166,6 +259,38 @@ impl MetricsRegistry {
         self.proofs_verified.load(Ordering::Relaxed)
     }
     
+    /// Record one batch verification, as reported by the ZKP batch
+    /// verifier's `AggregationStats`
+    pub fn record_batch_verification(&self, num_proofs: usize, size_bytes: usize, verify_time_ms: u64) {
+        self.proof_batches_verified.fetch_add(1, Ordering::Relaxed);
+        self.batch_proofs_verified.fetch_add(num_proofs as u64, Ordering::Relaxed);
+        self.batch_proof_bytes.fetch_add(size_bytes as u64, Ordering::Relaxed);
+        self.batch_verify_time_ms.store(verify_time_ms, Ordering::Relaxed);
+    }
+    
+    pub fn get_proof_batches_verified(&self) -> u64 {
+        self.proof_batches_verified.load(Ordering::Relaxed)
+    }
+    
+    pub fn get_batch_proofs_verified(&self) -> u64 {
+        self.batch_proofs_verified.load(Ordering::Relaxed)
+    }
+    
+    pub fn get_batch_proof_bytes(&self) -> u64 {
+        self.batch_proof_bytes.load(Ordering::Relaxed)
+    }
+    
+    pub fn get_batch_verify_time_ms(&self) -> u64 {
+        self.batch_verify_time_ms.load(Ordering::Relaxed)
+    }
+    
+    // Block production metrics
+    
+    /// Record the interval between consecutive blocks
+    pub fn record_block_time(&self, time_ms: u64) {
+        self.block_time_hist.observe(time_ms);
+    }
+    
     // EBSL metrics
     pub fn set_active_miners(&self, count: usize) {
         self.active_miners.store(count, Ordering::Relaxed);
@@ -183,17 +308,46 @@ impl MetricsRegistry {
         self.banned_miners.load(Ordering::Relaxed)
     }
     
+    /// Store a trust score as fixed-point * 1000 for atomic operations
+    ///
+    /// Trust scores are in range [0.0, 1.0], so this provides 3 decimal
+    /// places of precision without overflow risk.
+    fn trust_fixed_point(score: f64) -> u64 {
+        (score.clamp(0.0, 1.0) * 1000.0).round() as u64
+    }
+    
+    fn trust_samples(&self) -> std::sync::MutexGuard<'_, TrustSamples> {
+        self.trust_score_samples.lock().unwrap_or_else(|e| e.into_inner())
+    }
+    
+    /// Replace all samples with a precomputed average
     pub fn set_average_trust_score(&self, score: f64) {
-        // Store as fixed-point * 1000 for atomic operations
-        // Trust scores are typically in range [0.0, 1.0], so this provides
-        // 3 decimal places of precision without overflow risk
-        let clamped_score = score.clamp(0.0, 1.0);
-        let fixed_point = (clamped_score * 1000.0) as u64;
-        self.avg_trust_score.store(fixed_point, Ordering::Relaxed);
+        *self.trust_samples() = TrustSamples {
+            sum: Self::trust_fixed_point(score),
+            count: 1,
+        };
     }
     
+    /// Add one miner's trust score to the running average
+    pub fn record_trust_sample(&self, score: f64) {
+        let mut samples = self.trust_samples();
+        samples.sum += Self::trust_fixed_point(score);
+        samples.count += 1;
+    }
+    
+    /// Clear the running average, e.g. at the start of an epoch
+    pub fn reset_trust_samples(&self) {
+        *self.trust_samples() = TrustSamples::default();
+    }
+    
+    /// Mean of the recorded samples, rounded to 3 decimals (0.0 when there
+    /// are none)
     pub fn get_average_trust_score(&self) -> f64 {
-        let fixed_point = self.avg_trust_score.load(Ordering::Relaxed);
+        let samples = self.trust_samples();
+        if samples.count == 0 {
+            return 0.0;
+        }
+        let fixed_point = (samples.sum + samples.count / 2) / samples.count;
         fixed_point as f64 / 1000.0
     }
     
@@ -214,9 +368,25 @@ impl MetricsRegistry {
         self.dht_peer_count.load(Ordering::Relaxed)
     }
     
+    pub fn inc_dht_bootstrap_attempts(&self) {
+        self.dht_bootstrap_attempts.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    pub fn get_dht_bootstrap_attempts(&self) -> u64 {
+        self.dht_bootstrap_attempts.load(Ordering::Relaxed)
+    }
+    
+    pub fn inc_dht_bootstrap_failures(&self) {
+        self.dht_bootstrap_failures.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    pub fn get_dht_bootstrap_failures(&self) -> u64 {
+        self.dht_bootstrap_failures.load(Ordering::Relaxed)
+    }
+    
     /// Export metrics in Prometheus format
     pub fn export_prometheus(&self) -> String {
-        format!(
+        let mut out = format!(
             "# HELP bitcell_chain_height Current blockchain height\n\
              # TYPE bitcell_chain_height gauge\n\
              bitcell_chain_height {}\n\
@@ -233,6 +403,14 @@ impl MetricsRegistry {
              # TYPE bitcell_dht_peer_count gauge\n\
              bitcell_dht_peer_count {}\n\
              \n\
+             # HELP bitcell_dht_bootstrap_attempts_total Total bootstrap dial attempts\n\
+             # TYPE bitcell_dht_bootstrap_attempts_total counter\n\
+             bitcell_dht_bootstrap_attempts_total {}\n\
+             \n\
+             # HELP bitcell_dht_bootstrap_failures_total Total failed bootstrap dial attempts\n\
+             # TYPE bitcell_dht_bootstrap_failures_total counter\n\
+             bitcell_dht_bootstrap_failures_total {}\n\
+             \n\
              # HELP bitcell_bytes_sent_total Total bytes sent\n\
              # TYPE bitcell_bytes_sent_total counter\n\
              bitcell_bytes_sent_total {}\n\
@@ -265,6 +443,22 @@ impl MetricsRegistry {
              # TYPE bitcell_proofs_verified_total counter\n\
              bitcell_proofs_verified_total {}\n\
              \n\
+             # HELP bitcell_proof_batches_verified_total Total proof batches verified\n\
+             # TYPE bitcell_proof_batches_verified_total counter\n\
+             bitcell_proof_batches_verified_total {}\n\
+             \n\
+             # HELP bitcell_batch_proofs_verified_total Total proofs verified in batches\n\
+             # TYPE bitcell_batch_proofs_verified_total counter\n\
+             bitcell_batch_proofs_verified_total {}\n\
+             \n\
+             # HELP bitcell_batch_proof_bytes_total Total compressed size of batch-verified proofs\n\
+             # TYPE bitcell_batch_proof_bytes_total counter\n\
+             bitcell_batch_proof_bytes_total {}\n\
+             \n\
+             # HELP bitcell_batch_verify_time_ms Time taken by the last batch verification\n\
+             # TYPE bitcell_batch_verify_time_ms gauge\n\
+             bitcell_batch_verify_time_ms {}\n\
+             \n\
              # HELP bitcell_active_miners Number of active eligible miners\n\
              # TYPE bitcell_active_miners gauge\n\
              bitcell_active_miners {}\n\
@@ -284,6 +478,8 @@ impl MetricsRegistry {
             self.get_sync_progress(),
             self.get_peer_count(),
             self.get_dht_peer_count(),
+            self.get_dht_bootstrap_attempts(),
+            self.get_dht_bootstrap_failures(),
             self.get_bytes_sent(),
             self.get_bytes_received(),
             self.get_messages_sent(),
@@ -292,11 +488,60 @@ impl MetricsRegistry {
             self.get_total_txs_processed(),
             self.get_proofs_generated(),
             self.get_proofs_verified(),
+            self.get_proof_batches_verified(),
+            self.get_batch_proofs_verified(),
+            self.get_batch_proof_bytes(),
+            self.get_batch_verify_time_ms(),
             self.get_active_miners(),
             self.get_banned_miners(),
             self.get_average_trust_score(),
             self.get_slashing_events(),
-        )
+        );
+        
+        out.push_str(
+            "\n# HELP bitcell_messages_sent_by_type_total Messages sent by message type\n\
+             # TYPE bitcell_messages_sent_by_type_total counter\n",
+        );
+        for message_type in MessageType::ALL {
+            let _ = writeln!(
+                out,
+                "bitcell_messages_sent_by_type_total{{type=\"{}\"}} {}",
+                message_type.label(),
+                self.get_messages_sent_typed(message_type),
+            );
+        }
+        out.push_str(
+            "\n# HELP bitcell_messages_received_by_type_total Messages received by message type\n\
+             # TYPE bitcell_messages_received_by_type_total counter\n",
+        );
+        for message_type in MessageType::ALL {
+            let _ = writeln!(
+                out,
+                "bitcell_messages_received_by_type_total{{type=\"{}\"}} {}",
+                message_type.label(),
+                self.get_messages_received_typed(message_type),
+            );
+        }
+        
+        out.push('\n');
+        self.proof_gen_hist.write_prometheus(
+            &mut out,
+            "bitcell_proof_generation_ms",
+            "Proof generation time in milliseconds",
+        );
+        out.push('\n');
+        self.proof_verify_hist.write_prometheus(
+            &mut out,
+            "bitcell_proof_verification_ms",
+            "Proof verification time in milliseconds",
+        );
+        out.push('\n');
+        self.block_time_hist.write_prometheus(
+            &mut out,
+            "bitcell_block_time_ms",
+            "Interval between consecutive blocks in milliseconds",
+        );
+        out
     }
 }
 
@@ -360,6 +605,37 @@ mod tests {
         assert_eq!(metrics.get_slashing_events(), 2);
     }
 
+    #[test]
+    fn test_running_average_trust_score() {
+        let metrics = MetricsRegistry::new();
+        assert_eq!(metrics.get_average_trust_score(), 0.0);
+        
+        metrics.record_trust_sample(0.8);
+        metrics.record_trust_sample(0.9);
+        assert_eq!(metrics.get_average_trust_score(), 0.85);
+        assert!(metrics.export_prometheus().contains("bitcell_average_trust_score 0.85\n"));
+        
+        // A new epoch starts from scratch
+        metrics.reset_trust_samples();
+        metrics.record_trust_sample(0.3);
+        assert_eq!(metrics.get_average_trust_score(), 0.3);
+        
+        // Samples and the mean are rounded, not truncated
+        metrics.reset_trust_samples();
+        metrics.record_trust_sample(0.6999);
+        assert_eq!(metrics.get_average_trust_score(), 0.7);
+        metrics.record_trust_sample(0.7);
+        metrics.record_trust_sample(0.701);
+        assert_eq!(metrics.get_average_trust_score(), 0.7);
+        metrics.set_average_trust_score(2.0 / 3.0);
+        assert_eq!(metrics.get_average_trust_score(), 0.667);
+        
+        // A precomputed average counts as a single sample
+        metrics.set_average_trust_score(0.9);
+        metrics.record_trust_sample(0.5);
+        assert_eq!(metrics.get_average_trust_score(), 0.7);
+    }
+
     #[test]
     fn test_new_metrics_in_prometheus_export() {
         let metrics = MetricsRegistry::new();
@@ -370,6 +646,8 @@ mod tests {
         metrics.add_message_received();
         metrics.set_average_trust_score(0.875);
         metrics.inc_slashing_events();
+        metrics.record_batch_verification(4, 512, 12);
+        metrics.record_batch_verification(2, 256, 7);
         
         let export = metrics.export_prometheus();
         
@@ -378,5 +656,69 @@ mod tests {
         assert!(export.contains("bitcell_messages_received_total 1"));
         assert!(export.contains("bitcell_average_trust_score 0.875"));
         assert!(export.contains("bitcell_slashing_events_total 1"));
+        assert!(export.contains("bitcell_proof_batches_verified_total 2\n"));
+        assert!(export.contains("bitcell_batch_proofs_verified_total 6\n"));
+        assert!(export.contains("bitcell_batch_proof_bytes_total 768\n"));
+        assert!(export.contains("bitcell_batch_verify_time_ms 7\n"));
+    }
+
+    #[test]
+    fn test_typed_message_counters() {
+        let metrics = MetricsRegistry::new();
+        
+        for message_type in [
+            MessageType::Block,
+            MessageType::Transaction,
+            MessageType::Transaction,
+            MessageType::Transaction,
+            MessageType::Vote,
+            MessageType::Vote,
+        ] {
+            metrics.add_message_sent_typed(message_type);
+        }
+        metrics.add_message_received_typed(MessageType::Header);
+        
+        assert_eq!(metrics.get_messages_sent(), 6);
+        assert_eq!(metrics.get_messages_sent_typed(MessageType::Transaction), 3);
+        assert_eq!(metrics.get_messages_received(), 1);
+        
+        let export = metrics.export_prometheus();
+        assert!(export.contains("bitcell_messages_sent_total 6"));
+        assert!(export.contains("bitcell_messages_sent_by_type_total{type=\"block\"} 1"));
+        assert!(export.contains("bitcell_messages_sent_by_type_total{type=\"transaction\"} 3"));
+        assert!(export.contains("bitcell_messages_sent_by_type_total{type=\"vote\"} 2"));
+        assert!(export.contains("bitcell_messages_sent_by_type_total{type=\"header\"} 0"));
+        assert!(export.contains("bitcell_messages_received_by_type_total{type=\"header\"} 1"));
+    }
+
+    #[test]
+    fn test_histograms_in_prometheus_export() {
+        let metrics = MetricsRegistry::new();
+        
+        for ms in [80, 400, 450, 3_000, 90_000] {
+            metrics.record_proof_gen_time(ms);
+        }
+        metrics.record_proof_verify_time(7);
+        metrics.record_block_time(9_500);
+        metrics.record_block_time(10_200);
+        
+        let export = metrics.export_prometheus();
+        
+        // Buckets are cumulative
+        assert!(export.contains("# TYPE bitcell_proof_generation_ms histogram"));
+        assert!(export.contains("bitcell_proof_generation_ms_bucket{le=\"100\"} 1"));
+        assert!(export.contains("bitcell_proof_generation_ms_bucket{le=\"500\"} 3"));
+        assert!(export.contains("bitcell_proof_generation_ms_bucket{le=\"5000\"} 4"));
+        assert!(export.contains("bitcell_proof_generation_ms_bucket{le=\"60000\"} 4"));
+        assert!(export.contains("bitcell_proof_generation_ms_bucket{le=\"+Inf\"} 5"));
+        assert!(export.contains("bitcell_proof_generation_ms_sum 93930"));
+        assert!(export.contains("bitcell_proof_generation_ms_count 5"));
+        
+        assert!(export.contains("bitcell_proof_verification_ms_bucket{le=\"5\"} 0"));
+        assert!(export.contains("bitcell_proof_verification_ms_bucket{le=\"10\"} 1"));
+        
+        assert!(export.contains("bitcell_block_time_ms_bucket{le=\"10000\"} 1"));
+        assert!(export.contains("bitcell_block_time_ms_bucket{le=\"15000\"} 2"));
+        assert!(export.contains("bitcell_block_time_ms_count 2"));
     }
 }
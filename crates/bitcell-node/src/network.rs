@@ -1,18 +1,18 @@
 ///! Network manager with TCP-based P2P communication
 
 use crate::{Result, MetricsRegistry};
+use crate::monitoring::MessageType;
 use bitcell_consensus::{Block, Transaction};
 use bitcell_crypto::PublicKey;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::collections::{HashMap, HashSet};
 use parking_lot::RwLock;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 use tokio::net::{TcpListener, TcpStream};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use serde::{Serialize, Deserialize};
-
-/// Maximum message size limit (10MB) to prevent memory exhaustion attacks
-const MAX_MESSAGE_SIZE: usize = 10_000_000;
+use bitcell_network::FrameCodec;
 
 /// Network message types
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,6 +66,15 @@ pub struct NetworkManager {
     
     /// DHT manager
     dht: Arc<RwLock<Option<crate::dht::DhtManager>>>,
+    
+    /// Set to true once shutdown begins; background loops exit on change
+    shutdown: Arc<watch::Sender<bool>>,
+
+    /// Connection limit; handshakes beyond it are refused
+    max_peers: Arc<AtomicUsize>,
+
+    /// Message framing; rejects oversized frames before allocating them
+    codec: FrameCodec,
 }
 
 impl NetworkManager {
@@ -80,8 +89,26 @@ impl NetworkManager {
             block_tx: Arc::new(RwLock::new(None)),
             tx_tx: Arc::new(RwLock::new(None)),
             dht: Arc::new(RwLock::new(None)),
+            shutdown: Arc::new(watch::channel(false).0),
+            max_peers: Arc::new(AtomicUsize::new(crate::NodeConfig::default().max_peers)),
+            codec: FrameCodec::default(),
         }
     }
+
+    /// Maximum number of connected peers
+    pub fn max_peers(&self) -> usize {
+        self.max_peers.load(Ordering::Relaxed)
+    }
+
+    /// Change the connection limit; existing connections above it are kept
+    pub fn set_max_peers(&self, max_peers: usize) {
+        self.max_peers.store(max_peers, Ordering::Relaxed);
+    }
+
+    /// Whether a new connection from `peer_id` would exceed the peer limit
+    fn at_peer_limit(&self, peers: &HashMap<PublicKey, PeerConnection>, peer_id: &PublicKey) -> bool {
+        !peers.contains_key(peer_id) && peers.len() >= self.max_peers()
+    }
     
     /// Enable DHT
     pub fn enable_dht(&self, secret_key: &bitcell_crypto::SecretKey, bootstrap: Vec<String>) -> Result<()> {
@@ -108,8 +135,9 @@ impl NetworkManager {
     /// Start the network listener
     ///
     /// Binds to the specified port and starts accepting connections.
-    /// Also initiates DHT discovery if bootstrap nodes are provided.
-    pub async fn start(&self, port: u16, bootstrap_nodes: Vec<String>) -> Result<()> {
+    /// Also initiates DHT discovery if bootstrap nodes are provided; each
+    /// bootstrap node is dialed up to `bootstrap_max_attempts` times.
+    pub async fn start(&self, port: u16, bootstrap_nodes: Vec<String>, bootstrap_max_attempts: u32) -> Result<()> {
         let addr = format!("0.0.0.0:{}", port);
         
         // Update local address
@@ -130,105 +158,101 @@ impl NetworkManager {
             network.accept_connections(listener).await;
         });
         
-        // Start DHT discovery if enabled
-        let dht_clone = self.dht.clone();
-        let network_clone = self.clone();
-        let bootstrap_nodes_clone = bootstrap_nodes.clone();
+        // Dial bootstrap nodes and start DHT discovery in the background
+        let network = self.clone();
+        tokio::spawn(async move {
+            network.bootstrap(bootstrap_nodes, bootstrap_max_attempts).await;
+        });
         
+        // Spawn peer discovery task
+        let network = self.clone();
         tokio::spawn(async move {
+            network.peer_discovery_loop().await;
+        });
+        
+        self.metrics.set_peer_count(self.peer_count());
+        
+        Ok(())
+    }
+    
+    /// Dial `bootstrap_nodes`, then run DHT discovery if the DHT is enabled
+    ///
+    /// Bootstrap nodes are dialed whether or not the DHT is enabled, each
+    /// retried with backoff up to `max_attempts` times and all concurrently.
+    /// The DHT manager stays in place for broadcasts meanwhile, and
+    /// everything stops as soon as the network shuts down.
+    async fn bootstrap(&self, bootstrap_nodes: Vec<String>, max_attempts: u32) {
+        let mut shutdown = self.shutdown.subscribe();
+        if *shutdown.borrow_and_update() {
+            return;
+        }
+        
+        let work = async {
             // Wait a bit for listener to start
             tokio::time::sleep(std::time::Duration::from_secs(2)).await;
             
-            let mut dht_manager = {
-                let mut guard = dht_clone.write();
-                guard.take()
-            };
+            // DhtManager might reject addresses without Peer IDs, so
+            // bootstrap nodes from the config are dialed directly
+            let dials = bootstrap_nodes
+                .iter()
+                .filter_map(|addr| Self::socket_addr(addr))
+                .map(|connect_addr| async move {
+                    let _ = crate::dht::dial_with_backoff(
+                        &connect_addr,
+                        max_attempts,
+                        crate::dht::BOOTSTRAP_BASE_DELAY,
+                        &self.metrics,
+                        |_| self.connect_to_peer(&connect_addr),
+                    ).await;
+                });
+            if !bootstrap_nodes.is_empty() {
+                tracing::info!("Connecting to {} bootstrap nodes...", bootstrap_nodes.len());
+            }
+            futures::future::join_all(dials).await;
             
-            if let Some(mut dht) = dht_manager {
+            let dht = self.dht.read().clone();
+            if let Some(dht) = dht {
                 tracing::info!("Starting DHT discovery...");
-                
-                // 1. Connect to explicit bootstrap nodes from config
-                // This is necessary because DhtManager might reject addresses without Peer IDs
-                if !bootstrap_nodes_clone.is_empty() {
-                    tracing::info!("Connecting to {} bootstrap nodes...", bootstrap_nodes_clone.len());
-                    for addr_str in bootstrap_nodes_clone {
-                        // Extract IP and port from multiaddr string /ip4/x.x.x.x/tcp/yyyy
-                        // Also handle /p2p/Qm... suffix if present
-                        if let Some(start) = addr_str.find("/ip4/") {
-                            if let Some(tcp_start) = addr_str.find("/tcp/") {
-                                let ip = &addr_str[start+5..tcp_start];
-                                let rest = &addr_str[tcp_start+5..];
-                                
-                                // Check if there's a /p2p/ or /ipfs/ suffix
-                                let port = if let Some(p2p_start) = rest.find("/p2p/") {
-                                    &rest[..p2p_start]
-                                } else if let Some(ipfs_start) = rest.find("/ipfs/") {
-                                    &rest[..ipfs_start]
-                                } else {
-                                    rest
-                                };
-                                
-                                let connect_addr = format!("{}:{}", ip, port);
-                                tracing::info!("Connecting to bootstrap node: {}", connect_addr);
-                                let _ = network_clone.connect_to_peer(&connect_addr).await;
-                            }
-                        }
-                    }
-                }
-
                 if let Ok(peers) = dht.start_discovery().await {
                     tracing::info!("DHT discovery found {} peers", peers.len());
                     for peer in peers {
                         for addr in peer.addresses {
-                            // Convert multiaddr to string address if possible
-                            // For now, we assume TCP/IP addresses
-                            // This is a simplification - in a real implementation we'd handle Multiaddr properly
-                            let addr_str = addr.to_string();
-                            // Extract IP and port from multiaddr string /ip4/x.x.x.x/tcp/yyyy
-                            if let Some(start) = addr_str.find("/ip4/") {
-                                if let Some(tcp_start) = addr_str.find("/tcp/") {
-                                    let ip = &addr_str[start+5..tcp_start];
-                                    let rest = &addr_str[tcp_start+5..];
-                                    
-                                    // Check if there's a /p2p/ or /ipfs/ suffix
-                                    let port = if let Some(p2p_start) = rest.find("/p2p/") {
-                                        &rest[..p2p_start]
-                                    } else if let Some(ipfs_start) = rest.find("/ipfs/") {
-                                        &rest[..ipfs_start]
-                                    } else {
-                                        rest
-                                    };
-                                    
-                                    let connect_addr = format!("{}:{}", ip, port);
-                                    tracing::info!("DHT discovered peer: {}", connect_addr);
-                                    let _ = network_clone.connect_to_peer(&connect_addr).await;
-                                }
+                            if let Some(connect_addr) = Self::socket_addr(&addr.to_string()) {
+                                tracing::info!("DHT discovered peer: {}", connect_addr);
+                                let _ = self.connect_to_peer(&connect_addr).await;
                             }
                         }
                     }
                 }
-                
-                // Put it back
-                let mut guard = dht_clone.write();
-                *guard = Some(dht);
             }
-        });
-        
-        // Spawn peer discovery task
-        let network = self.clone();
-        tokio::spawn(async move {
-            network.peer_discovery_loop().await;
-        });
-        
-        self.metrics.set_peer_count(self.peer_count());
+        };
         
-        Ok(())
+        tokio::select! {
+            _ = shutdown.changed() => {}
+            _ = work => {}
+        }
+    }
+    
+    /// `ip:port` of a `/ip4/x.x.x.x/tcp/yyyy` multiaddr, ignoring any
+    /// `/p2p/` or `/ipfs/` suffix
+    fn socket_addr(multiaddr: &str) -> Option<String> {
+        let start = multiaddr.find("/ip4/")?;
+        let tcp_start = multiaddr.find("/tcp/")?;
+        let ip = multiaddr.get(start + 5..tcp_start)?;
+        let rest = &multiaddr[tcp_start + 5..];
+        let port = rest.split("/p2p/").next()?.split("/ipfs/").next()?;
+        Some(format!("{}:{}", ip, port))
     }
     
     /// Accept incoming connections
     async fn accept_connections(&self, listener: TcpListener) {
+        let mut shutdown = self.shutdown.subscribe();
         loop {
-            match listener.accept().await {
+            let accepted = tokio::select! {
+                _ = shutdown.changed() => break,
+                accepted = listener.accept() => accepted,
+            };
+            match accepted {
                 Ok((socket, addr)) => {
                     tracing::info!("Accepted connection from {}", addr);
                     let network = self.clone();
@@ -270,6 +294,9 @@ impl NetworkManager {
         // Store peer connection
         {
             let mut peers = self.peers.write();
+            if self.at_peer_limit(&peers, &peer_id) {
+                return Err(format!("Peer limit of {} reached, refusing {:?}", self.max_peers(), peer_id).into());
+            }
             peers.insert(peer_id, PeerConnection {
                 peer_id,
                 address: "unknown".to_string(),
@@ -293,8 +320,13 @@ impl NetworkManager {
     
     /// Handle incoming messages from a peer
     async fn handle_messages(&self, mut reader: tokio::io::ReadHalf<TcpStream>, peer_id: PublicKey) -> Result<()> {
+        let mut shutdown = self.shutdown.subscribe();
         loop {
-            match self.receive_message_from_reader(&mut reader).await {
+            let received = tokio::select! {
+                _ = shutdown.changed() => break,
+                received = self.receive_message(&mut reader) => received,
+            };
+            match received {
                 Ok(msg) => {
                     match msg {
                         NetworkMessage::Ping => {
@@ -352,14 +384,9 @@ impl NetworkManager {
                 guard.take()
             };
             if let Some(mut writer) = writer_opt {
-                // Serialize the message
-                let data = bincode::serialize(msg)
-                    .map_err(|e| format!("Serialization error: {}", e))?;
-                let len = (data.len() as u32).to_be_bytes();
-                // Send length prefix and data
-                writer.write_all(&len).await
-                    .map_err(|e| format!("Write error: {}", e))?;
-                writer.write_all(&data).await
+                // Frame the message and send it
+                let frame = self.codec.encode(msg).map_err(|e| e.to_string())?;
+                writer.write_all(&frame).await
                     .map_err(|e| format!("Write error: {}", e))?;
                 writer.flush().await
                     .map_err(|e| format!("Flush error: {}", e))?;
@@ -367,68 +394,22 @@ impl NetworkManager {
                 let mut guard = writer_arc.write();
                 *guard = Some(writer);
                 // Update metrics
-                self.metrics.add_bytes_sent(data.len() as u64);
+                self.metrics.add_bytes_sent(frame.len() as u64);
             }
         }
         Ok(())
     }
     
-    /// Send a message over a socket
-    async fn send_message(&self, socket: &mut TcpStream, msg: &NetworkMessage) -> Result<()> {
-        let data = bincode::serialize(msg)
-            .map_err(|e| format!("Serialization error: {}", e))?;
-        let len = data.len() as u32;
-        
-        socket.write_all(&len.to_be_bytes()).await
-            .map_err(|e| format!("Write error: {}", e))?;
-        socket.write_all(&data).await
-            .map_err(|e| format!("Write error: {}", e))?;
-        socket.flush().await
-            .map_err(|e| format!("Flush error: {}", e))?;
-        
-        Ok(())
-    }
-    
-    /// Receive a message from a socket
-    async fn receive_message(&self, socket: &mut TcpStream) -> Result<NetworkMessage> {
-        let mut len_bytes = [0u8; 4];
-        socket.read_exact(&mut len_bytes).await
-            .map_err(|e| format!("Read error: {}", e))?;
-        let len = u32::from_be_bytes(len_bytes) as usize;
-        
-        if len > MAX_MESSAGE_SIZE {
-            return Err("Message too large".into());
-        }
-        
-        let mut data = vec![0u8; len];
-        socket.read_exact(&mut data).await
-            .map_err(|e| format!("Read error: {}", e))?;
-        
-        let msg = bincode::deserialize(&data)
-            .map_err(|e| format!("Deserialization error: {}", e))?;
-        
-        Ok(msg)
+    /// Send a message as one frame
+    async fn send_message<W: AsyncWrite + Unpin>(&self, writer: &mut W, msg: &NetworkMessage) -> Result<()> {
+        self.codec.write_frame(writer, msg).await
+            .map_err(|e| e.to_string().into())
     }
     
-    /// Receive a message from a reader
-    async fn receive_message_from_reader(&self, reader: &mut tokio::io::ReadHalf<TcpStream>) -> Result<NetworkMessage> {
-        let mut len_bytes = [0u8; 4];
-        reader.read_exact(&mut len_bytes).await
-            .map_err(|e| format!("Read error: {}", e))?;
-        let len = u32::from_be_bytes(len_bytes) as usize;
-        
-        if len > MAX_MESSAGE_SIZE {
-            return Err("Message too large".into());
-        }
-        
-        let mut data = vec![0u8; len];
-        reader.read_exact(&mut data).await
-            .map_err(|e| format!("Read error: {}", e))?;
-        
-        let msg = bincode::deserialize(&data)
-            .map_err(|e| format!("Deserialization error: {}", e))?;
-        
-        Ok(msg)
+    /// Receive one framed message
+    async fn receive_message<R: AsyncRead + Unpin>(&self, reader: &mut R) -> Result<NetworkMessage> {
+        self.codec.read_frame(reader).await
+            .map_err(|e| e.to_string().into())
     }
     
     /// Connect to a peer
@@ -479,6 +460,9 @@ impl NetworkManager {
                 // Store peer
                 {
                     let mut peers = self.peers.write();
+                    if self.at_peer_limit(&peers, &peer_id) {
+                        return Err(format!("Peer limit of {} reached, not adding {}", self.max_peers(), address).into());
+                    }
                     peers.insert(peer_id, PeerConnection {
                         peer_id,
                         address: address.to_string(),
@@ -511,9 +495,13 @@ impl NetworkManager {
     /// Peer discovery loop
     async fn peer_discovery_loop(&self) {
         let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
+        let mut shutdown = self.shutdown.subscribe();
         
         loop {
-            interval.tick().await;
+            tokio::select! {
+                _ = shutdown.changed() => break,
+                _ = interval.tick() => {}
+            }
             
             // Get list of known addresses and filter out ones we're already connected to
             let addresses_to_try: Vec<String> = {
@@ -586,7 +574,7 @@ impl NetworkManager {
         self.metrics.add_bytes_sent(block_size * peer_ids.len() as u64);
         // Update message counter for each peer we sent to
         for _ in &peer_ids {
-            self.metrics.add_message_sent();
+            self.metrics.add_message_sent_typed(MessageType::Block);
         }
         
         // Broadcast via Gossipsub using compact blocks for bandwidth efficiency
@@ -629,7 +617,7 @@ impl NetworkManager {
         self.metrics.add_bytes_sent(tx_size * peer_ids.len() as u64);
         // Update message counter for each peer we sent to
         for _ in &peer_ids {
-            self.metrics.add_message_sent();
+            self.metrics.add_message_sent_typed(MessageType::Transaction);
         }
         
         // Broadcast via Gossipsub
@@ -661,7 +649,7 @@ impl NetworkManager {
     pub async fn handle_incoming_block(&self, block: Block) -> Result<()> {
         let block_size = bincode::serialize(&block).unwrap_or_default().len() as u64;
         self.metrics.add_bytes_received(block_size);
-        self.metrics.add_message_received();
+        self.metrics.add_message_received_typed(MessageType::Block);
         
         // Forward to block processing channel
         let tx_opt = {
@@ -679,7 +667,7 @@ impl NetworkManager {
     pub async fn handle_incoming_transaction(&self, tx: Transaction) -> Result<()> {
         let tx_size = bincode::serialize(&tx).unwrap_or_default().len() as u64;
         self.metrics.add_bytes_received(tx_size);
-        self.metrics.add_message_received();
+        self.metrics.add_message_received_typed(MessageType::Transaction);
         
         // Forward to transaction processing channel
         let sender_opt = {
@@ -705,6 +693,36 @@ impl NetworkManager {
         *tx_tx = Some(tx);
     }
     
+    /// Stop accepting connections and close every peer connection
+    ///
+    /// Each peer's write half is shut down so the remote side sees a clean
+    /// EOF. Dropping the DHT handle closes its command channel, which ends
+    /// the libp2p swarm task.
+    pub async fn shutdown(&self) {
+        self.shutdown.send_replace(true);
+        
+        let writers: Vec<_> = {
+            let mut peers = self.peers.write();
+            let writers = peers.drain().map(|(_, peer)| peer.writer).collect();
+            self.metrics.set_peer_count(0);
+            writers
+        };
+        for writer_arc in writers {
+            let writer_opt = writer_arc.write().take();
+            if let Some(mut writer) = writer_opt {
+                let _ = writer.shutdown().await;
+            }
+        }
+        
+        self.dht.write().take();
+        tracing::info!("Network shut down");
+    }
+    
+    /// Whether `shutdown` has been called
+    pub fn is_shut_down(&self) -> bool {
+        *self.shutdown.borrow()
+    }
+    
     /// Add a bootstrap peer address (for initial connection)
     pub fn add_bootstrap_peer(&self, address: String) {
         let mut known = self.known_addresses.write();
@@ -744,4 +762,133 @@ mod tests {
         let network = NetworkManager::new(pk, metrics);
         assert_eq!(network.peer_count(), 0);
     }
+
+    #[tokio::test]
+    async fn test_messages_use_frame_codec() {
+        let pk = SecretKey::generate().public_key();
+        let network = NetworkManager::new(pk, MetricsRegistry::new());
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        
+        network.send_message(&mut client, &NetworkMessage::Handshake { peer_id: pk }).await.unwrap();
+        match network.receive_message(&mut server).await.unwrap() {
+            NetworkMessage::Handshake { peer_id } => assert_eq!(peer_id, pk),
+            other => panic!("unexpected message {:?}", other),
+        }
+        
+        // Oversized frames are refused from the length prefix alone
+        let header = (bitcell_network::DEFAULT_MAX_FRAME_SIZE as u32 + 1).to_be_bytes();
+        let mut reader: &[u8] = &header;
+        assert!(network.receive_message(&mut reader).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_shutdown_closes_peers() {
+        let server = NetworkManager::new(SecretKey::generate().public_key(), MetricsRegistry::new());
+        let client = NetworkManager::new(SecretKey::generate().public_key(), MetricsRegistry::new());
+        
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let accepting = server.clone();
+        tokio::spawn(async move { accepting.accept_connections(listener).await });
+        
+        let wait_for_server_peers = |count: usize| {
+            let server = server.clone();
+            async move {
+                for _ in 0..50 {
+                    if server.peer_count() == count {
+                        break;
+                    }
+                    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                }
+                server.peer_count()
+            }
+        };
+        
+        client.connect_to_peer(&addr).await.unwrap();
+        assert_eq!(client.peer_count(), 1);
+        assert_eq!(wait_for_server_peers(1).await, 1);
+        
+        client.shutdown().await;
+        assert!(client.is_shut_down());
+        assert_eq!(client.peer_count(), 0);
+        
+        // The server sees EOF and drops the connection
+        assert_eq!(wait_for_server_peers(0).await, 0);
+    }
+
+    #[test]
+    fn test_socket_addr_from_multiaddr() {
+        assert_eq!(
+            NetworkManager::socket_addr("/ip4/10.0.0.1/tcp/30333").as_deref(),
+            Some("10.0.0.1:30333")
+        );
+        assert_eq!(
+            NetworkManager::socket_addr("/ip4/10.0.0.1/tcp/30333/p2p/QmPeer").as_deref(),
+            Some("10.0.0.1:30333")
+        );
+        assert_eq!(NetworkManager::socket_addr("10.0.0.1:30333"), None);
+    }
+
+    /// Multiaddr of a local port nothing listens on
+    async fn closed_multiaddr() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        format!("/ip4/127.0.0.1/tcp/{}", port)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_bootstrap_retries_without_dht() {
+        let metrics = MetricsRegistry::new();
+        let network = NetworkManager::new(SecretKey::generate().public_key(), metrics.clone());
+        let nodes = vec![closed_multiaddr().await, closed_multiaddr().await];
+
+        network.bootstrap(nodes, 3).await;
+        assert_eq!(metrics.get_dht_bootstrap_attempts(), 6);
+        assert_eq!(metrics.get_dht_bootstrap_failures(), 6);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_bootstrap_stops_on_shutdown() {
+        let metrics = MetricsRegistry::new();
+        let network = NetworkManager::new(SecretKey::generate().public_key(), metrics.clone());
+        let nodes = vec![closed_multiaddr().await];
+
+        let bootstrapping = network.clone();
+        let handle = tokio::spawn(async move { bootstrapping.bootstrap(nodes, 100).await });
+        while metrics.get_dht_bootstrap_attempts() == 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+
+        network.shutdown().await;
+        tokio::time::timeout(std::time::Duration::from_secs(1), handle)
+            .await
+            .expect("bootstrap should stop on shutdown")
+            .unwrap();
+        assert!(metrics.get_dht_bootstrap_attempts() < 100);
+    }
+
+    #[tokio::test]
+    async fn test_max_peers_refuses_extra_connections() {
+        let server = NetworkManager::new(SecretKey::generate().public_key(), MetricsRegistry::new());
+        server.set_max_peers(1);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let accepting = server.clone();
+        tokio::spawn(async move { accepting.accept_connections(listener).await });
+
+        for _ in 0..2 {
+            let client = NetworkManager::new(SecretKey::generate().public_key(), MetricsRegistry::new());
+            client.connect_to_peer(&addr).await.unwrap();
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        assert_eq!(server.peer_count(), 1);
+
+        // Raising the limit lets the next peer in
+        server.set_max_peers(2);
+        let client = NetworkManager::new(SecretKey::generate().public_key(), MetricsRegistry::new());
+        client.connect_to_peer(&addr).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        assert_eq!(server.peer_count(), 2);
+    }
 }
@@ -21,18 +21,27 @@ pub struct RpcState {
     pub network: NetworkManager,
     pub tx_pool: TransactionPool,
     pub tournament_manager: Option<Arc<TournamentManager>>,
-    pub config: NodeConfig,
+    /// Running configuration, updated in place by `bitcell_reloadConfig`
+    pub config: Arc<parking_lot::RwLock<NodeConfig>>,
+    /// Tracing filter handle, used to apply `log_level` reloads
+    pub log_filter: Option<crate::logging::LogFilterHandle>,
     pub node_type: String, // "validator", "miner", "full"
     pub node_id: String,   // Unique node identifier (public key hex)
 }
 
-/// Start the RPC server
-pub async fn run_server(state: RpcState, port: u16) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let app = Router::new()
+/// Build the RPC router: JSON-RPC on `/rpc`, REST on `/api/v1` and
+/// WebSocket subscriptions on `/ws`
+pub fn router(state: RpcState) -> Router {
+    Router::new()
         .route("/rpc", post(handle_json_rpc))
         .nest("/api/v1", api_router())
         .nest("/ws", crate::ws::ws_router())
-        .with_state(state);
+        .with_state(state)
+}
+
+/// Start the RPC server
+pub async fn run_server(state: RpcState, port: u16) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let app = router(state);
 
     let addr = format!("0.0.0.0:{}", port);
     tracing::info!("RPC server listening on {}", addr);
@@ -43,6 +52,29 @@ pub async fn run_server(state: RpcState, port: u16) -> Result<(), Box<dyn std::e
     Ok(())
 }
 
+/// Build the admin router: node management JSON-RPC on `/rpc`
+///
+/// These methods change the running node, so they are only served by
+/// `run_admin_server`, never by the public listener.
+pub fn admin_router(state: RpcState) -> Router {
+    Router::new()
+        .route("/rpc", post(handle_admin_rpc))
+        .with_state(state)
+}
+
+/// Start the admin RPC server, reachable from localhost only
+pub async fn run_admin_server(state: RpcState, port: u16) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let app = admin_router(state);
+
+    let addr = format!("127.0.0.1:{}", port);
+    tracing::info!("Admin RPC server listening on {}", addr);
+
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
+
 /// JSON-RPC Request
 #[derive(Debug, Deserialize)]
 struct JsonRpcRequest {
@@ -91,11 +123,14 @@ async fn handle_json_rpc(
         // Standard Namespace
         "eth_blockNumber" => eth_block_number(&state).await,
         "eth_getBlockByNumber" => eth_get_block_by_number(&state, req.params).await,
+        "eth_getBlockByHash" => eth_get_block_by_hash(&state, req.params).await,
         "eth_getTransactionByHash" => eth_get_transaction_by_hash(&state, req.params).await,
+        "eth_getTransactionReceipt" => eth_get_transaction_receipt(&state, req.params).await,
         "eth_getBalance" => eth_get_balance(&state, req.params).await,
         "eth_sendRawTransaction" => eth_send_raw_transaction(&state, req.params).await,
         "eth_getTransactionCount" => eth_get_transaction_count(&state, req.params).await,
         "eth_gasPrice" => eth_gas_price(&state).await,
+        "eth_estimateGas" => eth_estimate_gas(&state, req.params).await,
         
         // BitCell Namespace
         "bitcell_getNodeInfo" => bitcell_get_node_info(&state).await,
@@ -117,18 +152,47 @@ async fn handle_json_rpc(
         }),
     };
 
+    rpc_response(req.id, result)
+}
+
+/// Handle admin JSON-RPC requests
+async fn handle_admin_rpc(
+    State(state): State<RpcState>,
+    Json(req): Json<JsonRpcRequest>,
+) -> Json<JsonRpcResponse> {
+    if req.jsonrpc != "2.0" {
+        return rpc_response(req.id, Err(JsonRpcError {
+            code: -32600,
+            message: "Invalid Request".to_string(),
+            data: None,
+        }));
+    }
+
+    let result = match req.method.as_str() {
+        "bitcell_reloadConfig" => bitcell_reload_config(&state, req.params).await,
+        _ => Err(JsonRpcError {
+            code: -32601,
+            message: "Method not found".to_string(),
+            data: None,
+        }),
+    };
+
+    rpc_response(req.id, result)
+}
+
+fn rpc_response(id: Option<Value>, result: Result<Value, JsonRpcError>) -> Json<JsonRpcResponse> {
     match result {
         Ok(val) => Json(JsonRpcResponse {
             jsonrpc: "2.0".to_string(),
             result: Some(val),
             error: None,
-            id: req.id,
+            id,
         }),
         Err(err) => Json(JsonRpcResponse {
             jsonrpc: "2.0".to_string(),
             result: None,
             error: Some(err),
-            id: req.id,
+            id,
         }),
     }
 }
@@ -204,62 +268,147 @@ async fn eth_get_block_by_number(state: &RpcState, params: Option<Value>) -> Res
         })?
     };
     
-    if let Some(block) = state.blockchain.get_block(height) {
-        let transactions = if include_txs {
-            let txs: Vec<Value> = block.transactions.iter().enumerate().map(|(i, tx)| {
-                json!({
-                    "hash": format!("0x{}", hex::encode(tx.hash().as_bytes())),
-                    "nonce": format!("0x{:x}", tx.nonce),
-                    "blockHash": format!("0x{}", hex::encode(block.hash().as_bytes())),
-                    "blockNumber": format!("0x{:x}", block.header.height),
-                    "transactionIndex": format!("0x{:x}", i),
-                    "from": format!("0x{}", hex::encode(tx.from.as_bytes())),
-                    "to": format!("0x{}", hex::encode(tx.to.as_bytes())),
-                    "value": format!("0x{:x}", tx.amount),
-                    "gas": format!("0x{:x}", tx.gas_limit),
-                    "gasPrice": format!("0x{:x}", tx.gas_price),
-                    "input": format!("0x{}", hex::encode(&tx.data)),
-                })
-            }).collect();
-            json!(txs)
-        } else {
-            let tx_hashes: Vec<String> = block.transactions.iter()
-                .map(|tx| format!("0x{}", hex::encode(tx.hash().as_bytes())))
-                .collect();
-            json!(tx_hashes)
-        };
-
-        // Calculate actual block size
-        let block_size = bincode::serialized_size(&block).unwrap_or(0);
+    Ok(state.blockchain.get_block(height)
+        .map(|block| block_to_json(&block, include_txs))
+        .unwrap_or(Value::Null))
+}
 
-        Ok(json!({
-            "number": format!("0x{:x}", block.header.height),
-            "hash": format!("0x{}", hex::encode(block.hash().as_bytes())),
-            "parentHash": format!("0x{}", hex::encode(block.header.prev_hash.as_bytes())),
-            "nonce": format!("0x{:016x}", block.header.work),
-            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347", // Empty uncle hash
-            "logsBloom": format!("0x{}", hex::encode(&EMPTY_BLOOM_FILTER)),
-            "transactionsRoot": format!("0x{}", hex::encode(block.header.tx_root.as_bytes())),
-            "stateRoot": format!("0x{}", hex::encode(block.header.state_root.as_bytes())),
-            "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421", // Empty receipts root
-            "miner": format!("0x{}", hex::encode(block.header.proposer.as_bytes())),
-            "difficulty": "0x1",
-            "totalDifficulty": format!("0x{:x}", block.header.height), // Simplified
-            "extraData": "0x",
-            "size": format!("0x{:x}", block_size),
-            "gasLimit": "0x1fffffffffffff",
-            "gasUsed": "0x0",
-            "timestamp": format!("0x{:x}", block.header.timestamp),
-            "transactions": transactions,
-            "uncles": [],
-            "vrfOutput": format!("0x{}", hex::encode(block.header.vrf_output)),
-            "battleProofsCount": block.battle_proofs.len()
-        }))
+/// JSON representation of a block, with full transactions or just their hashes
+fn block_to_json(block: &bitcell_consensus::Block, include_txs: bool) -> Value {
+    let transactions = if include_txs {
+        let txs: Vec<Value> = block.transactions.iter().enumerate().map(|(i, tx)| {
+            json!({
+                "hash": format!("0x{}", hex::encode(tx.hash().as_bytes())),
+                "nonce": format!("0x{:x}", tx.nonce),
+                "blockHash": format!("0x{}", hex::encode(block.hash().as_bytes())),
+                "blockNumber": format!("0x{:x}", block.header.height),
+                "transactionIndex": format!("0x{:x}", i),
+                "from": format!("0x{}", hex::encode(tx.from.as_bytes())),
+                "to": format!("0x{}", hex::encode(tx.to.as_bytes())),
+                "value": format!("0x{:x}", tx.amount),
+                "gas": format!("0x{:x}", tx.gas_limit),
+                "gasPrice": format!("0x{:x}", tx.gas_price),
+                "input": format!("0x{}", hex::encode(&tx.data)),
+            })
+        }).collect();
+        json!(txs)
     } else {
-        Ok(Value::Null)
+        let tx_hashes: Vec<String> = block.transactions.iter()
+            .map(|tx| format!("0x{}", hex::encode(tx.hash().as_bytes())))
+            .collect();
+        json!(tx_hashes)
+    };
+
+    // Calculate actual block size
+    let block_size = bincode::serialized_size(&block).unwrap_or(0);
+
+    json!({
+        "number": format!("0x{:x}", block.header.height),
+        "hash": format!("0x{}", hex::encode(block.hash().as_bytes())),
+        "parentHash": format!("0x{}", hex::encode(block.header.prev_hash.as_bytes())),
+        "nonce": format!("0x{:016x}", block.header.work),
+        "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347", // Empty uncle hash
+        "logsBloom": format!("0x{}", hex::encode(EMPTY_BLOOM_FILTER)),
+        "transactionsRoot": format!("0x{}", hex::encode(block.header.tx_root.as_bytes())),
+        "stateRoot": format!("0x{}", hex::encode(block.header.state_root.as_bytes())),
+        "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421", // Empty receipts root
+        "miner": format!("0x{}", hex::encode(block.header.proposer.as_bytes())),
+        "difficulty": "0x1",
+        "totalDifficulty": format!("0x{:x}", block.header.height), // Simplified
+        "extraData": "0x",
+        "size": format!("0x{:x}", block_size),
+        "gasLimit": "0x1fffffffffffff",
+        "gasUsed": "0x0",
+        "timestamp": format!("0x{:x}", block.header.timestamp),
+        "transactions": transactions,
+        "uncles": [],
+        "vrfOutput": format!("0x{}", hex::encode(block.header.vrf_output)),
+        "battleProofsCount": block.battle_proofs.len()
+    })
+}
+
+/// Parse a 0x-prefixed 32-byte hash parameter
+fn parse_hash(param: &str, what: &str) -> Result<bitcell_crypto::Hash256, JsonRpcError> {
+    let hex_str = param.strip_prefix("0x").unwrap_or(param);
+    let bytes = hex::decode(hex_str).map_err(|_| JsonRpcError {
+        code: -32602,
+        message: "Invalid hex encoding".to_string(),
+        data: None,
+    })?;
+    
+    let hash: [u8; 32] = bytes.try_into().map_err(|_| JsonRpcError {
+        code: -32602,
+        message: format!("{} hash must be 32 bytes", what),
+        data: None,
+    })?;
+    Ok(bitcell_crypto::Hash256::from(hash))
+}
+
+/// First parameter as a hash
+fn hash_param(params: Option<Value>, what: &str) -> Result<bitcell_crypto::Hash256, JsonRpcError> {
+    let params = params.ok_or(JsonRpcError {
+        code: -32602,
+        message: "Invalid params".to_string(),
+        data: None,
+    })?;
+
+    let hash_str = params.as_array()
+        .and_then(|args| args.first())
+        .and_then(|arg| arg.as_str())
+        .ok_or(JsonRpcError {
+            code: -32602,
+            message: format!("Missing {} hash", what.to_lowercase()),
+            data: None,
+        })?;
+
+    parse_hash(hash_str, what)
+}
+
+/// Error for a well-formed lookup that matched nothing
+fn not_found(what: &str) -> JsonRpcError {
+    JsonRpcError {
+        code: -32001,
+        message: format!("{} not found", what),
+        data: None,
     }
 }
 
+/// Get block by hash
+async fn eth_get_block_by_hash(state: &RpcState, params: Option<Value>) -> Result<Value, JsonRpcError> {
+    let include_txs = params.as_ref()
+        .and_then(|p| p.get(1))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let block_hash = hash_param(params, "Block")?;
+
+    state.blockchain.get_block_by_hash(&block_hash)
+        .map(|block| block_to_json(&block, include_txs))
+        .ok_or_else(|| not_found("Block"))
+}
+
+/// Get the execution receipt of a mined transaction
+async fn eth_get_transaction_receipt(state: &RpcState, params: Option<Value>) -> Result<Value, JsonRpcError> {
+    let tx_hash = hash_param(params, "Transaction")?;
+
+    let receipt = state.blockchain.get_transaction_receipt(&tx_hash)
+        .ok_or_else(|| not_found("Transaction receipt"))?;
+    let (tx, _) = state.blockchain.get_transaction_by_hash(&tx_hash)
+        .ok_or_else(|| not_found("Transaction"))?;
+
+    Ok(json!({
+        "transactionHash": format!("0x{}", hex::encode(receipt.tx_hash.as_bytes())),
+        "transactionIndex": format!("0x{:x}", receipt.tx_index),
+        "blockHash": format!("0x{}", hex::encode(receipt.block_hash.as_bytes())),
+        "blockNumber": format!("0x{:x}", receipt.block_height),
+        "from": format!("0x{}", hex::encode(tx.from.as_bytes())),
+        "to": format!("0x{}", hex::encode(tx.to.as_bytes())),
+        "gasUsed": format!("0x{:x}", receipt.gas_used),
+        "effectiveGasPrice": format!("0x{:x}", tx.gas_price),
+        "status": if receipt.success { "0x1" } else { "0x0" },
+        "logs": [],
+    }))
+}
+
 async fn eth_get_transaction_by_hash(state: &RpcState, params: Option<Value>) -> Result<Value, JsonRpcError> {
     let params = params.ok_or(JsonRpcError {
         code: -32602,
@@ -287,24 +436,7 @@ async fn eth_get_transaction_by_hash(state: &RpcState, params: Option<Value>) ->
         data: None,
     })?;
     
-    let tx_hash_hex = tx_hash_str.strip_prefix("0x").unwrap_or(tx_hash_str);
-    let tx_hash_bytes = hex::decode(tx_hash_hex).map_err(|_| JsonRpcError {
-        code: -32602,
-        message: "Invalid hex encoding".to_string(),
-        data: None,
-    })?;
-    
-    if tx_hash_bytes.len() != 32 {
-        return Err(JsonRpcError {
-            code: -32602,
-            message: "Transaction hash must be 32 bytes".to_string(),
-            data: None,
-        });
-    }
-    
-    let mut hash = [0u8; 32];
-    hash.copy_from_slice(&tx_hash_bytes);
-    let target_hash = bitcell_crypto::Hash256::from(hash);
+    let target_hash = parse_hash(tx_hash_str, "Transaction")?;
 
     // Use efficient O(1) lookup via transaction hash index
     if let Some((tx, location)) = state.blockchain.get_transaction_by_hash(&target_hash) {
@@ -472,6 +604,53 @@ async fn eth_gas_price(_state: &RpcState) -> Result<Value, JsonRpcError> {
     Ok(json!(format!("0x{:x}", DEFAULT_GAS_PRICE)))
 }
 
+/// Estimate the gas limit for a call `{from, to, data}`
+///
+/// Transfers to plain accounts cost the base 21000; calls to deployed
+/// contracts are metered in the ZKVM with a safety margin.
+async fn eth_estimate_gas(state: &RpcState, params: Option<Value>) -> Result<Value, JsonRpcError> {
+    let call = params.as_ref()
+        .and_then(|p| p.get(0))
+        .and_then(|c| c.as_object())
+        .ok_or(JsonRpcError {
+            code: -32602,
+            message: "Expected a call object".to_string(),
+            data: None,
+        })?;
+
+    let decode_hex = |field: &str| -> Result<Vec<u8>, JsonRpcError> {
+        let value = call.get(field).and_then(|v| v.as_str()).unwrap_or("");
+        hex::decode(value.strip_prefix("0x").unwrap_or(value)).map_err(|_| JsonRpcError {
+            code: -32602,
+            message: format!("Invalid hex encoding in '{}'", field),
+            data: None,
+        })
+    };
+
+    let decode_address = |field: &str| -> Result<[u8; 33], JsonRpcError> {
+        decode_hex(field)?.try_into().map_err(|_| JsonRpcError {
+            code: -32602,
+            message: format!("'{}' must be a 33-byte public key", field),
+            data: None,
+        })
+    };
+
+    // Without a sender the call runs as the all-zero address, like eth_call
+    let from = match call.get("from") {
+        Some(_) => decode_address("from")?,
+        None => [0u8; 33],
+    };
+    let to = decode_address("to")?;
+    let data = decode_hex("data")?;
+
+    let gas = state.blockchain.contracts().estimate_gas(&from, &to, &data).map_err(|e| JsonRpcError {
+        code: -32000,
+        message: format!("Gas estimation failed: {}", e),
+        data: None,
+    })?;
+    Ok(json!(format!("0x{:x}", gas)))
+}
+
 async fn eth_send_raw_transaction(state: &RpcState, params: Option<Value>) -> Result<Value, JsonRpcError> {
     let params = params.ok_or(JsonRpcError {
         code: -32602,
@@ -523,8 +702,18 @@ async fn eth_send_raw_transaction(state: &RpcState, params: Option<Value>) -> Re
             data: None,
         });
     }
-    
+
+    // Private spends must prove ownership of their nullifiers
+    if let Err(e) = state.blockchain.verify_spends(&tx) {
+        return Err(JsonRpcError {
+            code: -32602,
+            message: e.to_string(),
+            data: None,
+        });
+    }
+
     // Validate nonce and balance
+    let mut account_nonce = 0;
     {
         let state_lock = state.blockchain.state();
         let state_guard = state_lock.read().map_err(|_| JsonRpcError {
@@ -534,15 +723,19 @@ async fn eth_send_raw_transaction(state: &RpcState, params: Option<Value>) -> Re
         })?;
         
         if let Some(account) = state_guard.get_account(tx.from.as_bytes()) {
-            if tx.nonce != account.nonce {
+            // Future nonces are queued by the pool until the gap is filled
+            if tx.nonce < account.nonce {
                 return Err(JsonRpcError {
                     code: -32602,
-                    message: format!("Invalid nonce: expected {}, got {}", account.nonce, tx.nonce),
+                    message: format!("Invalid nonce: expected at least {}, got {}", account.nonce, tx.nonce),
                     data: None,
                 });
             }
+            account_nonce = account.nonce;
             
-            if tx.amount > account.balance {
+            // The sender's other pooled transactions spend from the same balance
+            let committed = state.tx_pool.pending_amount(&tx.from, tx.nonce);
+            if tx.amount.saturating_add(committed) > account.balance {
                 return Err(JsonRpcError {
                     code: -32602,
                     message: "Insufficient balance".to_string(),
@@ -604,7 +797,7 @@ async fn eth_send_raw_transaction(state: &RpcState, params: Option<Value>) -> Re
     }
     
     // Add to transaction pool
-    if let Err(e) = state.tx_pool.add_transaction(tx.clone()) {
+    if let Err(e) = state.tx_pool.add_transaction_with_account_nonce(tx.clone(), account_nonce) {
         return Err(JsonRpcError {
             code: -32603,
             message: format!("Failed to add transaction to pool: {}", e),
@@ -637,6 +830,59 @@ async fn bitcell_get_peer_count(state: &RpcState) -> Result<Value, JsonRpcError>
     Ok(json!(count))
 }
 
+/// Apply a partial config (`[{"field": value, ...}]`) to the running node
+///
+/// Served on the admin listener only. Fields listed in
+/// `NodeConfig::reloadable_fields` take effect immediately; other changes are
+/// reported under `restart_required` and not applied. Out-of-range values
+/// are rejected without changing anything.
+async fn bitcell_reload_config(state: &RpcState, params: Option<Value>) -> Result<Value, JsonRpcError> {
+    let invalid = |message: String| JsonRpcError {
+        code: -32602,
+        message,
+        data: None,
+    };
+    let changes = params
+        .as_ref()
+        .and_then(|p| p.get(0))
+        .and_then(Value::as_object)
+        .ok_or_else(|| invalid("Params must be [config object]".to_string()))?;
+
+    let mut config = state.config.write();
+    let mut merged = serde_json::to_value(&*config).map_err(|e| JsonRpcError {
+        code: -32603,
+        message: format!("Failed to serialize config: {}", e),
+        data: None,
+    })?;
+    let fields = merged.as_object_mut().expect("NodeConfig serializes to an object");
+    for (field, value) in changes {
+        if !fields.contains_key(field) {
+            return Err(invalid(format!("Unknown config field: {}", field)));
+        }
+        fields.insert(field.clone(), value.clone());
+    }
+    let new: NodeConfig = serde_json::from_value(merged)
+        .map_err(|e| invalid(format!("Invalid config: {}", e)))?;
+
+    let mut updated = config.clone();
+    let reload = updated.reload(&new).map_err(invalid)?;
+    if let Some(log_filter) = &state.log_filter {
+        log_filter.set_filter(&updated.log_level).map_err(|message| JsonRpcError {
+            code: -32603,
+            message,
+            data: None,
+        })?;
+    }
+    *config = updated;
+    state.tx_pool.set_max_size(config.tx_pool_size);
+    state.network.set_max_peers(config.max_peers);
+    tracing::info!(
+        "Config reloaded: applied {:?}, restart required for {:?}",
+        reload.applied, reload.restart_required
+    );
+    Ok(json!(reload))
+}
+
 async fn bitcell_get_network_metrics(state: &RpcState) -> Result<Value, JsonRpcError> {
     Ok(json!({
         "peer_count": state.network.peer_count(),
@@ -961,17 +1207,16 @@ async fn bitcell_get_battle_replay(state: &RpcState, params: Option<Value>) -> R
     let sample_steps: Vec<usize> = (0..=100).step_by(10).collect();
     let grids = battle.grid_states(&sample_steps);
     
-    // Serialize grids to simple 2D arrays for JSON
-    let serialized_grids: Vec<Vec<Vec<u8>>> = grids.iter().map(|grid| {
-        // Downsample for UI performance (1024x1024 is too big for JSON)
-        // We'll return a 64x64 view centered on the action
-        let view_size = 64;
+    // Downsample for UI performance (1024x1024 is too big for JSON): return a
+    // 64x64 view centered on the action, run-length encoded row-major
+    let view_size = 64;
+    let serialized_grids: Vec<Value> = grids.iter().map(|grid| {
         let center_y = 512;
         let center_x = 512;
         let start_y = center_y - view_size / 2;
         let start_x = center_x - view_size / 2;
         
-        let mut view = vec![vec![0u8; view_size]; view_size];
+        let mut view = vec![0u8; view_size * view_size];
         
         for y in 0..view_size {
             for x in 0..view_size {
@@ -980,11 +1225,14 @@ async fn bitcell_get_battle_replay(state: &RpcState, params: Option<Value>) -> R
                 if cell.is_alive() {
                     // 1 for Player A (left), 2 for Player B (right)
                     // Simplified logic: left side is A, right side is B
-                    view[y][x] = if (start_x + x) < 512 { 1 } else { 2 };
+                    view[y * view_size + x] = if (start_x + x) < 512 { 1 } else { 2 };
                 }
             }
         }
-        view
+        json!({
+            "size": view_size,
+            "cells": bitcell_ca::replay::encode_cells(&view),
+        })
     }).collect();
     
     let outcome = battle.simulate();
@@ -1099,3 +1347,297 @@ async fn get_mining_status(
         "auto_miner": false // TODO: Check auto miner status
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MetricsRegistry;
+    use bitcell_consensus::Transaction;
+    use bitcell_crypto::SecretKey;
+
+    fn test_state() -> (RpcState, Arc<SecretKey>) {
+        let sk = Arc::new(SecretKey::generate());
+        let metrics = MetricsRegistry::new();
+        let state = RpcState {
+            blockchain: Blockchain::new(sk.clone(), metrics.clone()),
+            network: NetworkManager::new(sk.public_key(), metrics),
+            tx_pool: TransactionPool::default(),
+            tournament_manager: None,
+            config: Arc::new(parking_lot::RwLock::new(NodeConfig::default())),
+            log_filter: None,
+            node_type: "validator".to_string(),
+            node_id: hex::encode(sk.public_key().as_bytes()),
+        };
+        (state, sk)
+    }
+
+    #[tokio::test]
+    async fn test_mined_transaction_receipt() {
+        let (state, sk) = test_state();
+        let sender = SecretKey::generate();
+        state.blockchain.state().write().unwrap()
+            .credit_account(*sender.public_key().as_bytes(), 1000).unwrap();
+
+        // Submit through the pool and mine it
+        let mut tx = Transaction {
+            nonce: 0,
+            from: sender.public_key(),
+            to: sk.public_key(),
+            amount: 100,
+            gas_limit: 21000,
+            gas_price: 1,
+            data: vec![],
+            spends: vec![],
+            signature: sender.sign(b"placeholder"),
+        };
+        tx.signature = sender.sign(tx.signing_hash().as_bytes());
+        let raw = format!("0x{}", hex::encode(bincode::serialize(&tx).unwrap()));
+        let tx_hash = eth_send_raw_transaction(&state, Some(json!([raw]))).await.unwrap();
+
+        let txs = state.tx_pool.get_transactions(10);
+        let block = state.blockchain.produce_block(txs, vec![], sk.public_key()).unwrap();
+        let block_hash = format!("0x{}", hex::encode(block.hash().as_bytes()));
+        state.blockchain.add_block(block).unwrap();
+
+        let receipt = eth_get_transaction_receipt(&state, Some(json!([tx_hash.clone()]))).await.unwrap();
+        assert_eq!(receipt["transactionHash"], tx_hash);
+        assert_eq!(receipt["blockNumber"], "0x1");
+        assert_eq!(receipt["blockHash"], block_hash.as_str());
+        assert_eq!(receipt["transactionIndex"], "0x0");
+        assert_eq!(receipt["gasUsed"], format!("0x{:x}", bitcell_economics::GAS_TX_BASE));
+        assert_eq!(receipt["status"], "0x1");
+
+        let block = eth_get_block_by_hash(&state, Some(json!([block_hash]))).await.unwrap();
+        assert_eq!(block["number"], "0x1");
+        assert_eq!(block["transactions"][0], tx_hash);
+    }
+
+    #[tokio::test]
+    async fn test_balance_check_counts_pooled_transactions() {
+        let (state, sk) = test_state();
+        let sender = SecretKey::generate();
+        state.blockchain.state().write().unwrap()
+            .credit_account(*sender.public_key().as_bytes(), 1000).unwrap();
+
+        let send = |nonce: u64, amount: u64, gas_price: u64| {
+            let mut tx = Transaction {
+                nonce,
+                from: sender.public_key(),
+                to: sk.public_key(),
+                amount,
+                gas_limit: 21000,
+                gas_price,
+                data: vec![],
+                spends: vec![],
+                signature: sender.sign(b"placeholder"),
+            };
+            tx.signature = sender.sign(tx.signing_hash().as_bytes());
+            let raw = format!("0x{}", hex::encode(bincode::serialize(&tx).unwrap()));
+            eth_send_raw_transaction(&state, Some(json!([raw])))
+        };
+
+        send(0, 600, 1).await.unwrap();
+        let err = send(1, 600, 1).await.unwrap_err();
+        assert_eq!(err.message, "Insufficient balance");
+
+        // Replacing the pooled transaction only counts the replacement
+        send(0, 900, 2).await.unwrap();
+        assert_eq!(state.tx_pool.pending_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unproven_spend_not_pooled() {
+        let (state, sk) = test_state();
+        let sender = SecretKey::generate();
+        state.blockchain.state().write().unwrap()
+            .credit_account(*sender.public_key().as_bytes(), 100_000).unwrap();
+
+        let mut tx = Transaction {
+            nonce: 0,
+            from: sender.public_key(),
+            to: sk.public_key(),
+            amount: 100,
+            gas_limit: 21000,
+            gas_price: 1,
+            data: vec![],
+            spends: vec![bitcell_consensus::PrivateSpend {
+                nullifier: [1u8; 32],
+                old_root: [0u8; 32],
+                new_root: [0u8; 32],
+                commitment: [0u8; 32],
+                proof: vec![0u8; 128],
+            }],
+            signature: sender.sign(b"placeholder"),
+        };
+        tx.signature = sender.sign(tx.signing_hash().as_bytes());
+        let raw = format!("0x{}", hex::encode(bincode::serialize(&tx).unwrap()));
+        let err = eth_send_raw_transaction(&state, Some(json!([raw]))).await.unwrap_err();
+        assert_eq!(err.code, -32602);
+        assert_eq!(state.tx_pool.pending_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_gas() {
+        use crate::contracts::encode_call;
+        use bitcell_compiler::stdlib::patterns::COUNTER_CONTRACT;
+
+        let (state, _) = test_state();
+        let contract = [7u8; 33];
+        state.blockchain.contracts().deploy(contract, COUNTER_CONTRACT).unwrap();
+        let estimate = |to: &[u8], data: &[u8]| {
+            let params = json!([{
+                "to": format!("0x{}", hex::encode(to)),
+                "data": format!("0x{}", hex::encode(data)),
+            }]);
+            eth_estimate_gas(&state, Some(params))
+        };
+
+        let transfer = estimate(&[2u8; 33], &[]).await.unwrap();
+        assert_eq!(transfer, "0x5208"); // 21000
+
+        // Call data to a plain account pays intrinsic gas only
+        let data_transfer = estimate(&[2u8; 33], &encode_call("increment()", &[])).await.unwrap();
+        let data_gas = crate::contracts::intrinsic_gas(&encode_call("increment()", &[]));
+        assert_eq!(data_transfer, format!("0x{:x}", data_gas));
+
+        let call = estimate(&contract, &encode_call("increment()", &[])).await.unwrap();
+        let gas = u64::from_str_radix(call.as_str().unwrap().trim_start_matches("0x"), 16).unwrap();
+        assert!(gas > 21_000 && gas < 30_000, "unexpected estimate {}", gas);
+
+        let err = estimate(&contract, &encode_call("decrement()", &[])).await.unwrap_err();
+        assert_eq!(err.code, -32000);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_gas_as_sender() {
+        use crate::contracts::encode_call;
+        const OWNED_CONTRACT: &str = r#"
+contract Owned {
+    storage {
+        owner: address;
+        claimed: bool;
+    }
+
+    function claim() -> bool {
+        require(!claimed, "Already claimed");
+        owner = msg.sender;
+        claimed = true;
+        return true;
+    }
+
+    function poke() -> bool {
+        require(msg.sender == owner, "Not owner");
+        return true;
+    }
+}
+"#;
+
+        let (state, _) = test_state();
+        let contract = [7u8; 33];
+        let owner = [3u8; 33];
+        let contracts = state.blockchain.contracts();
+        contracts.deploy(contract, OWNED_CONTRACT).unwrap();
+        contracts.commit(contracts.execute(&owner, &contract, &encode_call("claim()", &[]), 1_000_000));
+
+        let estimate = |from: Option<[u8; 33]>| {
+            let mut call = json!({
+                "to": format!("0x{}", hex::encode(contract)),
+                "data": format!("0x{}", hex::encode(encode_call("poke()", &[]))),
+            });
+            if let Some(from) = from {
+                call["from"] = json!(format!("0x{}", hex::encode(from)));
+            }
+            eth_estimate_gas(&state, Some(json!([call])))
+        };
+
+        estimate(Some(owner)).await.unwrap();
+        let err = estimate(Some([4u8; 33])).await.unwrap_err();
+        assert!(err.message.contains("Not owner"), "{}", err.message);
+        assert!(estimate(None).await.is_err());
+
+        let params = json!([{ "from": "0x0102", "to": format!("0x{}", hex::encode(contract)) }]);
+        let err = eth_estimate_gas(&state, Some(params)).await.unwrap_err();
+        assert_eq!(err.code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_hashes_are_not_found() {
+        let (state, _) = test_state();
+        let unknown = format!("0x{}", "ab".repeat(32));
+
+        let err = eth_get_transaction_receipt(&state, Some(json!([unknown.clone()]))).await.unwrap_err();
+        assert_eq!(err.code, -32001);
+
+        let err = eth_get_block_by_hash(&state, Some(json!([unknown]))).await.unwrap_err();
+        assert_eq!(err.code, -32001);
+
+        let err = eth_get_block_by_hash(&state, Some(json!(["0x1234"]))).await.unwrap_err();
+        assert_eq!(err.code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_reload_config_applies_live() {
+        let (state, _) = test_state();
+        let default_port = NodeConfig::default().network_port;
+
+        let params = json!([{ "tx_pool_size": 42, "network_port": 40000 }]);
+        let result = bitcell_reload_config(&state, Some(params)).await.unwrap();
+        assert_eq!(result["applied"], json!(["tx_pool_size"]));
+        assert_eq!(result["restart_required"], json!(["network_port"]));
+
+        // The running node picks up the new limit; the port waits for a restart
+        assert_eq!(state.config.read().tx_pool_size, 42);
+        assert_eq!(state.tx_pool.max_size(), 42);
+        assert_eq!(state.config.read().network_port, default_port);
+
+        let err = bitcell_reload_config(&state, Some(json!([{ "max_peerz": 1 }]))).await.unwrap_err();
+        assert_eq!(err.code, -32602);
+
+        let params = json!([{ "max_peers": 7, "log_level": "warn,bitcell_node=debug" }]);
+        let result = bitcell_reload_config(&state, Some(params)).await.unwrap();
+        assert_eq!(result["applied"], json!(["log_level", "max_peers"]));
+        assert_eq!(state.network.max_peers(), 7);
+        assert_eq!(state.config.read().log_level, "warn,bitcell_node=debug");
+    }
+
+    #[tokio::test]
+    async fn test_reload_config_rejects_bad_limits() {
+        let (state, _) = test_state();
+
+        for changes in [
+            json!({ "tx_pool_size": 0 }),
+            json!({ "tx_pool_size": crate::config::MAX_TX_POOL_SIZE + 1 }),
+            json!({ "max_peers": 0 }),
+            json!({ "log_level": "bitcell_node=loud" }),
+            // A bad value rejects the whole request, including valid fields
+            json!({ "tx_pool_size": 42, "max_peers": 1_000_000 }),
+        ] {
+            let err = bitcell_reload_config(&state, Some(json!([changes]))).await.unwrap_err();
+            assert_eq!(err.code, -32602);
+        }
+
+        let defaults = NodeConfig::default();
+        assert_eq!(state.config.read().tx_pool_size, defaults.tx_pool_size);
+        assert_eq!(state.tx_pool.max_size(), defaults.tx_pool_size);
+        assert_eq!(state.network.max_peers(), defaults.max_peers);
+    }
+
+    #[tokio::test]
+    async fn test_reload_config_only_on_admin_listener() {
+        let (state, _) = test_state();
+        let request = || serde_json::from_value::<JsonRpcRequest>(json!({
+            "jsonrpc": "2.0",
+            "method": "bitcell_reloadConfig",
+            "params": [{ "tx_pool_size": 42 }],
+            "id": 1
+        })).unwrap();
+
+        let public = handle_json_rpc(State(state.clone()), Json(request())).await.0;
+        assert_eq!(public.error.unwrap().code, -32601);
+        assert_eq!(state.tx_pool.max_size(), NodeConfig::default().tx_pool_size);
+
+        let admin = handle_admin_rpc(State(state.clone()), Json(request())).await.0;
+        assert!(admin.error.is_none());
+        assert_eq!(state.tx_pool.max_size(), 42);
+    }
+}
@@ -48,6 +48,12 @@ impl TournamentManager {
         }
     }
     
+    /// Use `params` for trust scores and eligibility
+    pub fn with_ebsl_params(mut self, params: EbslParams) -> Self {
+        self.ebsl_params = params;
+        self
+    }
+    
     /// Start a new tournament for the given height
     pub async fn start_tournament(&self, height: u64, eligible_miners: Vec<PublicKey>, seed: Hash256) {
         let mut tournament = self.tournament.write().await;
@@ -166,7 +172,10 @@ impl TournamentManager {
             
             // Add evidence with current block height
             let height = *self.current_height.read().unwrap();
-            counters.add_evidence(bitcell_ebsl::Evidence::new(evidence_type, 0, height));
+            counters.add_evidence_with_params(
+                bitcell_ebsl::Evidence::new(evidence_type, 0, height),
+                &self.ebsl_params,
+            );
             
             // Track slashing events (negative evidence)
             if evidence_type.is_negative() {
@@ -216,15 +225,18 @@ impl TournamentManager {
         
         let mut active_count = 0;
         let mut banned_count = 0;
-        let mut total_trust_score = 0.0;
-        let mut miner_count = 0;
+        
+        // Recompute the fleet average from this round's scores
+        self.metrics.reset_trust_samples();
+        if evidence_map.is_empty() {
+            // Use default trust score when no miners
+            self.metrics.record_trust_sample(DEFAULT_TRUST_SCORE);
+        }
         
         for (_miner, counters) in evidence_map.iter() {
             let trust = TrustScore::from_evidence(counters, &self.ebsl_params);
             let trust_value = trust.value();
-            
-            total_trust_score += trust_value;
-            miner_count += 1;
+            self.metrics.record_trust_sample(trust_value);
             
             if trust.is_eligible(&self.ebsl_params) {
                 active_count += 1;
@@ -235,15 +247,6 @@ impl TournamentManager {
         
         self.metrics.set_active_miners(active_count);
         self.metrics.set_banned_miners(banned_count);
-        
-        // Calculate average trust score
-        if miner_count > 0 {
-            let avg_trust = total_trust_score / miner_count as f64;
-            self.metrics.set_average_trust_score(avg_trust);
-        } else {
-            // Use default trust score when no miners
-            self.metrics.set_average_trust_score(DEFAULT_TRUST_SCORE);
-        }
     }
 }
 
@@ -263,19 +266,23 @@ pub async fn run_tournament_cycle(
     // For single-node testing, we'll submit commitments/reveals ourselves
     // In production, miners would do this over the network
     
-    // Commit phase - submit a dummy commitment for each miner
+    // Every miner plays the same simple glider for testing
+    let glider = Glider::new(
+        GliderPattern::Standard,
+        Position::new(100, 100),
+    );
+    
+    // Dummy nonce (in production this would be random)
+    let nonce = vec![height as u8];
+    
+    // Commit phase - commit to the glider each miner will reveal
     println!("Tournament: Commit phase ({}s)", COMMIT_PHASE_SECS);
     
     // Submit commitments for all miners
-    for _miner_pk in &eligible_miners {
-        // Create dummy commitment
-        let commitment_data = format!("{:?}{}", height, seed);
-        let commitment_hash = bitcell_crypto::Hash256::hash(commitment_data.as_bytes());
-        
+    for miner_pk in &eligible_miners {
         let commitment = bitcell_consensus::GliderCommitment {
-            commitment: commitment_hash,
             ring_signature: vec![0u8; 64], // Dummy signature
-            height,
+            ..bitcell_consensus::GliderCommitment::new(&glider, &nonce, miner_pk, height)
         };
         
         let _ = manager.add_commitment(commitment).await;
@@ -291,18 +298,9 @@ pub async fn run_tournament_cycle(
     
     // Submit reveals for all miners
     for miner_pk in &eligible_miners {
-        // Create a simple glider for testing
-        let glider = Glider::new(
-            GliderPattern::Standard,
-            Position::new(100, 100),
-        );
-        
-        // Dummy nonce (in production this would be random)  
-        let nonce = vec![height as u8];
-        
         let reveal = bitcell_consensus::GliderReveal {
-            glider,
-            nonce,
+            glider: glider.clone(),
+            nonce: nonce.clone(),
             miner: *miner_pk,
         };
         
@@ -1,21 +1,49 @@
-///! Transaction pool (mempool) for pending transactions
+//! Transaction pool (mempool) for pending transactions
+//!
+//! Transactions are tracked per sender by nonce. Those continuing the
+//! sender's account nonce without gaps are executable and offered to block
+//! assembly; later ones wait in a queue until the gap is filled.
 
+use crate::blockchain::MAX_BLOCK_SIZE;
 use bitcell_consensus::Transaction;
 use bitcell_crypto::Hash256;
-use std::collections::{HashMap, BTreeSet};
+use bitcell_economics::MAX_GAS_PER_BLOCK;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap};
+use std::cmp::Reverse;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
+
+/// Capacity of the accepted transaction notification channel
+const TX_EVENT_CAPACITY: usize = 1024;
+
+/// Default minimum gas price increase, in percent, to replace a pending transaction
+pub const DEFAULT_PRICE_BUMP_PCT: u64 = 10;
+
+/// Default maximum number of queued (future-nonce) transactions per sender
+pub const DEFAULT_MAX_QUEUED_PER_SENDER: usize = 64;
+
+/// Share of the pool capacity, in percent, that queued transactions may use
+pub const QUEUED_POOL_SHARE_PCT: usize = 25;
+
+/// File name of the saved pool inside the node's data directory
+pub const MEMPOOL_FILE: &str = "mempool.bin";
+
+type Sender = [u8; 33];
 
 /// Transaction with priority score for ordering
 #[derive(Debug, Clone)]
 struct PendingTransaction {
     tx: Transaction,
+    hash: Hash256,
     received_at: u64,
     priority: u64, // gas_price for now
 }
 
 impl PartialEq for PendingTransaction {
     fn eq(&self, other: &Self) -> bool {
-        self.tx.hash() == other.tx.hash()
+        self.hash == other.hash
     }
 }
 
@@ -29,58 +57,229 @@ impl PartialOrd for PendingTransaction {
 
 impl Ord for PendingTransaction {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // Higher priority first, then older first
+        // Higher priority first, then lower nonce, then older first.
+        // The hash keeps distinct transactions from comparing equal.
         other.priority.cmp(&self.priority)
+            .then(self.tx.nonce.cmp(&other.tx.nonce))
             .then(self.received_at.cmp(&other.received_at))
+            .then_with(|| self.hash.as_bytes().cmp(other.hash.as_bytes()))
+    }
+}
+
+/// One sender's transactions
+#[derive(Default)]
+struct SenderTransactions {
+    /// Next nonce the chain expects from this sender
+    base_nonce: u64,
+    /// All of the sender's transactions by nonce
+    by_nonce: BTreeMap<u64, PendingTransaction>,
+}
+
+impl SenderTransactions {
+    /// Nonces `base_nonce, base_nonce + 1, ...` present without a gap
+    fn executable(&self) -> impl Iterator<Item = &PendingTransaction> {
+        self.by_nonce
+            .range(self.base_nonce..)
+            .zip(self.base_nonce..)
+            .take_while(|((nonce, _), expected)| *nonce == expected)
+            .map(|((_, ptx), _)| ptx)
+    }
+}
+
+#[derive(Default)]
+struct PoolInner {
+    /// Executable transactions ordered by priority
+    pending: BTreeSet<PendingTransaction>,
+    /// Transactions waiting on a nonce gap, ordered by priority
+    queued: BTreeSet<PendingTransaction>,
+    /// Transaction lookup by hash (executable and queued)
+    tx_map: HashMap<Hash256, PendingTransaction>,
+    /// Transactions grouped by sender
+    ///
+    /// A sender's entry outlives its transactions so its next nonce is not
+    /// forgotten once they are included; see `prune_idle_senders`.
+    senders: HashMap<Sender, SenderTransactions>,
+}
+
+impl PoolInner {
+    /// Recompute which of `sender`'s transactions are executable
+    fn refresh_sender(&mut self, sender: &Sender) {
+        let Some(txs) = self.senders.get(sender) else {
+            return;
+        };
+        for ptx in txs.by_nonce.values() {
+            self.pending.remove(ptx);
+            self.queued.remove(ptx);
+        }
+        let executable: Vec<_> = txs.executable().cloned().collect();
+        let run = txs.base_nonce..txs.base_nonce + executable.len() as u64;
+        self.queued.extend(
+            txs.by_nonce.values()
+                .filter(|ptx| !run.contains(&ptx.tx.nonce))
+                .cloned(),
+        );
+        self.pending.extend(executable);
+    }
+
+    /// Remove a transaction, keeping its sender's next nonce
+    fn remove(&mut self, hash: &Hash256) -> Option<PendingTransaction> {
+        let ptx = self.tx_map.remove(hash)?;
+        self.pending.remove(&ptx);
+        self.queued.remove(&ptx);
+        if let Some(txs) = self.senders.get_mut(ptx.tx.from.as_bytes()) {
+            txs.by_nonce.remove(&ptx.tx.nonce);
+        }
+        Some(ptx)
+    }
+
+    /// Forget senders without transactions once there are more than `max`
+    /// senders in total
+    ///
+    /// Their next nonce is then taken from the chain again, which the RPC
+    /// path always supplies.
+    fn prune_idle_senders(&mut self, max: usize) {
+        if self.senders.len() > max {
+            self.senders.retain(|_, txs| !txs.by_nonce.is_empty());
+        }
+    }
+
+    fn evict_lowest(&mut self) -> Option<Transaction> {
+        let lowest = self.pending.last()?.hash;
+        let ptx = self.remove(&lowest)?;
+        // Later nonces of the sender are no longer executable
+        self.refresh_sender(ptx.tx.from.as_bytes());
+        tracing::debug!("Evicted transaction {:?} with gas price {}", ptx.hash, ptx.priority);
+        Some(ptx.tx)
+    }
+
+    fn evict_lowest_queued(&mut self) -> Option<Transaction> {
+        let lowest = self.queued.last()?.hash;
+        let ptx = self.remove(&lowest)?;
+        tracing::debug!("Evicted queued transaction {:?} with gas price {}", ptx.hash, ptx.priority);
+        Some(ptx.tx)
     }
 }
 
 /// Transaction pool
+///
+/// Keeps at most `max_size` transactions. When full, a new executable
+/// transaction first displaces the lowest-priority queued one, then the
+/// lowest-priority executable one if it pays a strictly higher gas price. A
+/// transaction reusing a pending nonce replaces it only if its gas price is
+/// at least `price_bump_pct` percent higher.
+///
+/// Queued transactions are bounded separately: at most
+/// `max_queued_per_sender` per sender and `QUEUED_POOL_SHARE_PCT` percent of
+/// the pool overall. A new queued transaction may only displace a cheaper
+/// queued one.
 #[derive(Clone)]
 pub struct TransactionPool {
-    /// Pending transactions ordered by priority
-    pending: Arc<RwLock<BTreeSet<PendingTransaction>>>,
+    inner: Arc<RwLock<PoolInner>>,
     
-    /// Transaction lookup by hash
-    tx_map: Arc<RwLock<HashMap<Hash256, Transaction>>>,
-    
-    /// Maximum pool size
-    max_size: usize,
+    /// Maximum pool size, shared by all clones so it can be changed live
+    max_size: Arc<AtomicUsize>,
+
+    /// Minimum gas price increase (percent) for replace-by-fee
+    price_bump_pct: u64,
+
+    /// Maximum queued transactions per sender
+    max_queued_per_sender: usize,
+
+    /// Notifies subscribers of each accepted transaction
+    tx_events: broadcast::Sender<Transaction>,
 }
 
 impl TransactionPool {
     /// Create a new transaction pool
     pub fn new(max_size: usize) -> Self {
         Self {
-            pending: Arc::new(RwLock::new(BTreeSet::new())),
-            tx_map: Arc::new(RwLock::new(HashMap::new())),
-            max_size,
+            inner: Arc::new(RwLock::new(PoolInner::default())),
+            max_size: Arc::new(AtomicUsize::new(max_size)),
+            price_bump_pct: DEFAULT_PRICE_BUMP_PCT,
+            max_queued_per_sender: DEFAULT_MAX_QUEUED_PER_SENDER,
+            tx_events: broadcast::channel(TX_EVENT_CAPACITY).0,
         }
     }
+
+    /// Set the minimum gas price increase, in percent, for replace-by-fee
+    pub fn with_price_bump(mut self, pct: u64) -> Self {
+        self.price_bump_pct = pct;
+        self
+    }
+
+    /// Set the maximum number of queued transactions per sender
+    pub fn with_max_queued_per_sender(mut self, max: usize) -> Self {
+        self.max_queued_per_sender = max;
+        self
+    }
+
+    /// Subscribe to transactions as they are accepted into the pool
+    pub fn subscribe(&self) -> broadcast::Receiver<Transaction> {
+        self.tx_events.subscribe()
+    }
+
+    /// Maximum number of transactions the pool holds
+    pub fn max_size(&self) -> usize {
+        self.max_size.load(Ordering::Relaxed)
+    }
+
+    /// Change the pool capacity
+    ///
+    /// Takes effect for every clone of the pool. Shrinking does not evict
+    /// anything; while over the limit, new transactions are only accepted by
+    /// displacing a cheaper one.
+    pub fn set_max_size(&self, max_size: usize) {
+        self.max_size.store(max_size, Ordering::Relaxed);
+    }
+
+    /// Maximum number of queued transactions across all senders
+    pub fn max_queued(&self) -> usize {
+        (self.max_size() * QUEUED_POOL_SHARE_PCT / 100).max(1)
+    }
     
     /// Add a transaction to the pool
+    ///
+    /// For a sender the pool has not seen, the transaction's own nonce is
+    /// taken as the sender's next nonce. Use
+    /// [`add_transaction_with_account_nonce`](Self::add_transaction_with_account_nonce)
+    /// when the on-chain nonce is known.
     pub fn add_transaction(&self, tx: Transaction) -> Result<(), String> {
-        let tx_hash = tx.hash();
-        
-        // Check if already in pool
-        {
-            let tx_map = self.tx_map.read().unwrap();
-            if tx_map.contains_key(&tx_hash) {
-                return Err("Transaction already in pool".to_string());
-            }
+        self.insert(tx, None)
+    }
+
+    /// Add a transaction from a sender whose on-chain nonce is `account_nonce`
+    ///
+    /// Transactions with a nonce above the next expected one are queued until
+    /// the gap is filled.
+    pub fn add_transaction_with_account_nonce(&self, tx: Transaction, account_nonce: u64) -> Result<(), String> {
+        if tx.nonce < account_nonce {
+            return Err(format!("Nonce too low: expected at least {}, got {}", account_nonce, tx.nonce));
         }
-        
-        // Check pool size
-        {
-            let pending = self.pending.read().unwrap();
-            if pending.len() >= self.max_size {
-                return Err("Transaction pool full".to_string());
-            }
+        self.insert(tx, Some(account_nonce))
+    }
+
+    fn insert(&self, tx: Transaction, account_nonce: Option<u64>) -> Result<(), String> {
+        // A transaction no block can hold would sit in the pool forever
+        if tx.gas_limit > MAX_GAS_PER_BLOCK {
+            return Err(format!("Gas limit {} exceeds the block gas limit {}", tx.gas_limit, MAX_GAS_PER_BLOCK));
         }
+        // Nor can one that runs out of gas paying for its own call data
+        let intrinsic_gas = crate::contracts::intrinsic_gas(&tx.data);
+        if tx.gas_limit < intrinsic_gas {
+            return Err(format!("Gas limit {} is below the intrinsic gas {}", tx.gas_limit, intrinsic_gas));
+        }
+        let size = bincode::serialized_size(&tx).map_err(|e| format!("Serialization error: {}", e))?;
+        if size > MAX_BLOCK_SIZE as u64 {
+            return Err(format!("Transaction of {} bytes exceeds the block size limit {}", size, MAX_BLOCK_SIZE));
+        }
+
+        let tx_hash = tx.hash();
+        let sender = *tx.from.as_bytes();
         
         // Create pending transaction
         let pending_tx = PendingTransaction {
             tx: tx.clone(),
+            hash: tx_hash,
             received_at: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
@@ -88,58 +287,263 @@ impl TransactionPool {
             priority: tx.gas_price,
         };
         
-        // Add to pool
         {
-            let mut pending = self.pending.write().unwrap();
-            pending.insert(pending_tx);
-        }
-        {
-            let mut tx_map = self.tx_map.write().unwrap();
-            tx_map.insert(tx_hash, tx);
+            let mut inner = self.inner.write().unwrap();
+
+            // Check if already in pool
+            if inner.tx_map.contains_key(&tx_hash) {
+                return Err("Transaction already in pool".to_string());
+            }
+
+            let known = inner.senders.get(&sender);
+            if let Some(base_nonce) = known.map(|txs| txs.base_nonce) {
+                if tx.nonce < base_nonce {
+                    return Err(format!("Nonce too low: expected at least {}, got {}", base_nonce, tx.nonce));
+                }
+            }
+            let base_nonce = account_nonce
+                .or(known.map(|txs| txs.base_nonce))
+                .unwrap_or(tx.nonce);
+            let replaced = known
+                .and_then(|txs| txs.by_nonce.get(&tx.nonce))
+                .map(|old| (old.hash, old.priority));
+
+            if let Some((old_hash, old_price)) = replaced {
+                // Replace-by-fee
+                let bump = old_price.saturating_mul(self.price_bump_pct) / 100;
+                let min_price = old_price.saturating_add(bump).max(old_price.saturating_add(1));
+                if tx.gas_price < min_price {
+                    return Err(format!(
+                        "Replacement transaction underpriced: gas price must be at least {}",
+                        min_price
+                    ));
+                }
+                // Keep the sender entry (and its base nonce) while swapping
+                inner.remove(&old_hash);
+            } else {
+                // The first nonce the sender's gap-free run does not cover
+                let next_executable = known.map_or(base_nonce, |txs| {
+                    txs.by_nonce.range(base_nonce..)
+                        .zip(base_nonce..)
+                        .take_while(|((nonce, _), expected)| *nonce == expected)
+                        .count() as u64
+                        + base_nonce
+                });
+                let is_queued = tx.nonce > next_executable;
+
+                if is_queued {
+                    let sender_queued = known.map_or(0, |txs| {
+                        txs.by_nonce.range(next_executable..).count()
+                    });
+                    if sender_queued >= self.max_queued_per_sender {
+                        return Err(format!(
+                            "Too many queued transactions from sender (limit {})",
+                            self.max_queued_per_sender
+                        ));
+                    }
+                }
+
+                // Make room if the queue or the pool is full
+                let queue_full = is_queued && inner.queued.len() >= self.max_queued();
+                if queue_full || inner.tx_map.len() >= self.max_size() {
+                    if is_queued {
+                        // Queued transactions only compete with each other
+                        let outbid = inner.queued.last().is_some_and(|lowest| pending_tx.priority > lowest.priority);
+                        if !outbid {
+                            return Err(if queue_full { "Transaction queue full" } else { "Transaction pool full" }.to_string());
+                        }
+                        inner.evict_lowest_queued();
+                    } else if !inner.queued.is_empty() {
+                        inner.evict_lowest_queued();
+                    } else {
+                        let outbid = inner.pending.last().is_some_and(|lowest| pending_tx.priority > lowest.priority);
+                        if !outbid {
+                            return Err("Transaction pool full".to_string());
+                        }
+                        inner.evict_lowest();
+                    }
+                }
+            }
+
+            let txs = inner.senders.entry(sender).or_insert_with(|| SenderTransactions {
+                base_nonce,
+                by_nonce: BTreeMap::new(),
+            });
+            txs.base_nonce = base_nonce;
+            txs.by_nonce.insert(tx.nonce, pending_tx.clone());
+            inner.tx_map.insert(tx_hash, pending_tx);
+            inner.refresh_sender(&sender);
+            inner.prune_idle_senders(self.max_size());
         }
+
+        // Notify subscribers; having none is not an error
+        let _ = self.tx_events.send(tx);
         
         Ok(())
     }
+
+    /// Remove and return the lowest-priority executable transaction
+    pub fn evict_lowest(&self) -> Option<Transaction> {
+        self.inner.write().unwrap().evict_lowest()
+    }
     
+    /// Highest-fee executable transactions for block assembly
+    ///
+    /// Ordered by gas price, except that each sender's transactions always
+    /// appear in nonce order.
+    pub fn best_transactions(&self, limit: usize) -> Vec<Transaction> {
+        self.select(limit, u64::MAX, u64::MAX)
+    }
+
+    /// Highest-fee executable transactions whose summed gas limits and
+    /// serialized sizes stay within `max_gas` and `max_bytes`
+    ///
+    /// A transaction that does not fit the remaining budget is skipped and
+    /// selection continues with other senders; the skipped sender's later
+    /// nonces are left out too.
+    pub fn best_transactions_within(&self, max_gas: u64, max_bytes: u64) -> Vec<Transaction> {
+        self.select(usize::MAX, max_gas, max_bytes)
+    }
+
+    fn select(&self, limit: usize, max_gas: u64, max_bytes: u64) -> Vec<Transaction> {
+        let inner = self.inner.read().unwrap();
+
+        // Merge the senders' nonce-ordered queues by priority
+        let mut queues: Vec<_> = inner.senders.values().map(|txs| txs.executable()).collect();
+        let mut heads = BinaryHeap::new();
+        for (i, queue) in queues.iter_mut().enumerate() {
+            if let Some(ptx) = queue.next() {
+                heads.push(Reverse((ptx, i)));
+            }
+        }
+
+        let mut best = Vec::with_capacity(limit.min(inner.pending.len()));
+        let (mut gas, mut bytes) = (0u64, 0u64);
+        while best.len() < limit {
+            let Some(Reverse((ptx, i))) = heads.pop() else {
+                break;
+            };
+            let size = bincode::serialized_size(&ptx.tx).unwrap_or(u64::MAX);
+            let (Some(next_gas), Some(next_bytes)) = (gas.checked_add(ptx.tx.gas_limit), bytes.checked_add(size)) else {
+                continue;
+            };
+            if next_gas > max_gas || next_bytes > max_bytes {
+                // Dropping the queue keeps the sender's later nonces out too
+                continue;
+            }
+            (gas, bytes) = (next_gas, next_bytes);
+            best.push(ptx.tx.clone());
+            if let Some(next) = queues[i].next() {
+                heads.push(Reverse((next, i)));
+            }
+        }
+        best
+    }
+
     /// Get top N transactions for block inclusion
     pub fn get_transactions(&self, count: usize) -> Vec<Transaction> {
-        let pending = self.pending.read().unwrap();
-        pending.iter()
-            .take(count)
-            .map(|ptx| ptx.tx.clone())
-            .collect()
+        self.best_transactions(count)
     }
     
     /// Remove transactions (after they've been included in a block)
+    ///
+    /// Each sender's next nonce advances past the removed transactions, which
+    /// may make queued transactions executable.
     pub fn remove_transactions(&self, tx_hashes: &[Hash256]) {
-        let mut pending = self.pending.write().unwrap();
-        let mut tx_map = self.tx_map.write().unwrap();
+        let mut inner = self.inner.write().unwrap();
+        let mut touched = Vec::new();
         
         for hash in tx_hashes {
-            if tx_map.remove(hash).is_some() {
-                // Remove from pending set
-                pending.retain(|ptx| ptx.tx.hash() != *hash);
+            if let Some(ptx) = inner.remove(hash) {
+                let sender = *ptx.tx.from.as_bytes();
+                if let Some(txs) = inner.senders.get_mut(&sender) {
+                    txs.base_nonce = txs.base_nonce.max(ptx.tx.nonce + 1);
+                }
+                touched.push(sender);
             }
         }
+        for sender in touched {
+            inner.refresh_sender(&sender);
+        }
+        inner.prune_idle_senders(self.max_size());
+    }
+
+    /// Total amount `sender`'s pooled transactions transfer, excluding the
+    /// one at `except_nonce` (which a new transaction would replace)
+    pub fn pending_amount(&self, sender: &bitcell_crypto::PublicKey, except_nonce: u64) -> u64 {
+        let inner = self.inner.read().unwrap();
+        inner.senders.get(sender.as_bytes()).map_or(0, |txs| {
+            txs.by_nonce.iter()
+                .filter(|(nonce, _)| **nonce != except_nonce)
+                .fold(0u64, |total, (_, ptx)| total.saturating_add(ptx.tx.amount))
+        })
     }
     
-    /// Get number of pending transactions
+    /// Get number of executable transactions
     pub fn pending_count(&self) -> usize {
-        self.pending.read().unwrap().len()
+        self.inner.read().unwrap().pending.len()
+    }
+
+    /// Get number of transactions waiting on a nonce gap
+    pub fn queued_count(&self) -> usize {
+        self.inner.read().unwrap().queued.len()
     }
     
-    /// Get all pending transactions
+    /// Get all executable transactions
     pub fn get_pending_transactions(&self) -> Vec<Transaction> {
-        let pending = self.pending.read().unwrap();
-        pending.iter().map(|ptx| ptx.tx.clone()).collect()
+        let inner = self.inner.read().unwrap();
+        inner.pending.iter().map(|ptx| ptx.tx.clone()).collect()
+    }
+    
+    /// Write all pooled transactions to `path`
+    ///
+    /// Each sender's next expected nonce is saved with its transactions, so
+    /// queued ones stay queued after [`load`](Self::load).
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let senders: Vec<(u64, Vec<Transaction>)> = {
+            let inner = self.inner.read().unwrap();
+            inner.senders.values()
+                .filter(|txs| !txs.by_nonce.is_empty())
+                .map(|txs| (txs.base_nonce, txs.by_nonce.values().map(|ptx| ptx.tx.clone()).collect()))
+                .collect()
+        };
+        let data = bincode::serialize(&senders)
+            .map_err(|e| format!("Serialization error: {}", e))?;
+        std::fs::write(path, data)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Re-add transactions written by [`save`](Self::save)
+    ///
+    /// A missing file loads nothing. Transactions the pool rejects, e.g.
+    /// once it is full, are skipped. Returns the number accepted.
+    pub fn load(&self, path: &Path) -> Result<usize, String> {
+        let data = match std::fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        let senders: Vec<(u64, Vec<Transaction>)> = bincode::deserialize(&data)
+            .map_err(|e| format!("Deserialization error: {}", e))?;
+
+        let mut loaded = 0;
+        for (base_nonce, txs) in senders {
+            for tx in txs {
+                if self.add_transaction_with_account_nonce(tx, base_nonce).is_ok() {
+                    loaded += 1;
+                }
+            }
+        }
+        Ok(loaded)
     }
     
     /// Clear all transactions
     pub fn clear(&self) {
-        let mut pending = self.pending.write().unwrap();
-        let mut tx_map = self.tx_map.write().unwrap();
-        pending.clear();
-        tx_map.clear();
+        let mut inner = self.inner.write().unwrap();
+        inner.pending.clear();
+        inner.queued.clear();
+        inner.tx_map.clear();
+        inner.senders.clear();
     }
 }
 
@@ -166,6 +570,7 @@ mod tests {
             gas_limit: 21000,
             gas_price,
             data: vec![],
+            spends: vec![],
             signature: sk.sign(b"test"),
         }
     }
@@ -198,6 +603,200 @@ mod tests {
         assert_eq!(txs[0].gas_price, 30);
     }
     
+    #[test]
+    fn test_add_transaction_notifies_subscribers() {
+        let pool = TransactionPool::new(100);
+        let mut events = pool.subscribe();
+        let tx = create_test_tx(0, 10);
+
+        pool.add_transaction(tx.clone()).unwrap();
+        assert_eq!(events.try_recv().unwrap().hash(), tx.hash());
+
+        // Rejected duplicates are not announced
+        assert!(pool.add_transaction(tx).is_err());
+        assert!(events.try_recv().is_err());
+    }
+    
+    #[test]
+    fn test_higher_fee_displaces_lowest_at_capacity() {
+        let pool = TransactionPool::new(2);
+        let low = create_test_tx(0, 10);
+        pool.add_transaction(low.clone()).unwrap();
+        pool.add_transaction(create_test_tx(0, 20)).unwrap();
+
+        // Not outbidding the lowest fee is rejected
+        assert!(pool.add_transaction(create_test_tx(0, 10)).is_err());
+
+        pool.add_transaction(create_test_tx(0, 30)).unwrap();
+        assert_eq!(pool.pending_count(), 2);
+        assert!(pool.get_pending_transactions().iter().all(|tx| tx.hash() != low.hash()));
+
+        assert_eq!(pool.evict_lowest().unwrap().gas_price, 20);
+        assert_eq!(pool.pending_count(), 1);
+    }
+
+    #[test]
+    fn test_best_transactions_fee_descending() {
+        let pool = TransactionPool::new(100);
+        for gas_price in [5, 50, 20, 20, 1] {
+            pool.add_transaction(create_test_tx(0, gas_price)).unwrap();
+        }
+
+        let fees: Vec<u64> = pool.best_transactions(10).iter().map(|tx| tx.gas_price).collect();
+        assert_eq!(fees, vec![50, 20, 20, 5, 1]);
+        assert_eq!(pool.best_transactions(2).len(), 2);
+    }
+    
+    fn signed_tx(sk: &SecretKey, nonce: u64, gas_price: u64) -> Transaction {
+        Transaction {
+            nonce,
+            from: sk.public_key(),
+            to: sk.public_key(),
+            amount: 100,
+            gas_limit: 21000,
+            gas_price,
+            data: vec![],
+            spends: vec![],
+            signature: sk.sign(b"test"),
+        }
+    }
+
+    #[test]
+    fn test_nonce_gap_is_queued_until_filled() {
+        let pool = TransactionPool::new(100);
+        let sk = SecretKey::generate();
+
+        pool.add_transaction_with_account_nonce(signed_tx(&sk, 2, 10), 0).unwrap();
+        assert_eq!(pool.pending_count(), 0);
+        assert_eq!(pool.queued_count(), 1);
+        assert!(pool.best_transactions(10).is_empty());
+
+        pool.add_transaction_with_account_nonce(signed_tx(&sk, 0, 10), 0).unwrap();
+        assert_eq!(pool.pending_count(), 1);
+        assert_eq!(pool.queued_count(), 1);
+
+        // Nonce 1 fills the gap and releases nonce 2
+        pool.add_transaction_with_account_nonce(signed_tx(&sk, 1, 10), 0).unwrap();
+        assert_eq!(pool.pending_count(), 3);
+        assert_eq!(pool.queued_count(), 0);
+
+        // Stale nonces are rejected
+        assert!(pool.add_transaction_with_account_nonce(signed_tx(&sk, 0, 50), 1).is_err());
+    }
+
+    #[test]
+    fn test_best_transactions_keep_sender_nonce_order() {
+        let pool = TransactionPool::new(100);
+        let sk = SecretKey::generate();
+        pool.add_transaction_with_account_nonce(signed_tx(&sk, 0, 5), 0).unwrap();
+        pool.add_transaction_with_account_nonce(signed_tx(&sk, 1, 50), 0).unwrap();
+        pool.add_transaction(create_test_tx(0, 20)).unwrap();
+
+        let order: Vec<(u64, u64)> = pool.best_transactions(10).iter()
+            .map(|tx| (tx.nonce, tx.gas_price))
+            .collect();
+        assert_eq!(order, vec![(0, 20), (0, 5), (1, 50)]);
+    }
+
+    #[test]
+    fn test_best_transactions_within_budget_skips_what_does_not_fit() {
+        let pool = TransactionPool::new(100);
+        let big = SecretKey::generate();
+        let mut heavy = signed_tx(&big, 0, 100);
+        heavy.gas_limit = 50_000;
+        pool.add_transaction(heavy).unwrap();
+        // Queued behind the heavy transaction, so it must be left out with it
+        pool.add_transaction(signed_tx(&big, 1, 100)).unwrap();
+        pool.add_transaction(create_test_tx(0, 20)).unwrap();
+        pool.add_transaction(create_test_tx(0, 10)).unwrap();
+
+        // The top-priority transaction does not fit; cheaper ones still do
+        let picked = pool.best_transactions_within(45_000, u64::MAX);
+        let prices: Vec<u64> = picked.iter().map(|tx| tx.gas_price).collect();
+        assert_eq!(prices, vec![20, 10]);
+
+        // The byte budget is respected too
+        let size = bincode::serialized_size(&picked[0]).unwrap();
+        assert_eq!(pool.best_transactions_within(u64::MAX, size).len(), 1);
+        assert_eq!(pool.best_transactions_within(u64::MAX, 3 * size).len(), 3);
+    }
+
+    #[test]
+    fn test_unincludable_transactions_rejected() {
+        let pool = TransactionPool::new(100);
+        let sk = SecretKey::generate();
+
+        let mut tx = signed_tx(&sk, 0, 10);
+        tx.gas_limit = MAX_GAS_PER_BLOCK + 1;
+        assert!(pool.add_transaction(tx).is_err());
+
+        let mut tx = signed_tx(&sk, 0, 10);
+        tx.data = vec![0u8; MAX_BLOCK_SIZE];
+        tx.gas_limit = crate::contracts::intrinsic_gas(&tx.data);
+        assert!(pool.add_transaction(tx).unwrap_err().contains("block size"));
+
+        // Too little gas to pay for its own call data
+        let mut tx = signed_tx(&sk, 0, 10);
+        tx.data = vec![1u8; 4];
+        assert!(pool.add_transaction(tx).unwrap_err().contains("intrinsic gas"));
+        assert_eq!(pool.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_replace_by_fee() {
+        let pool = TransactionPool::new(100).with_price_bump(10);
+        let sk = SecretKey::generate();
+        pool.add_transaction(signed_tx(&sk, 0, 100)).unwrap();
+
+        // 5% more is below the bump
+        assert!(pool.add_transaction(signed_tx(&sk, 0, 105)).is_err());
+        assert_eq!(pool.best_transactions(10)[0].gas_price, 100);
+
+        // 20% more replaces it
+        pool.add_transaction(signed_tx(&sk, 0, 120)).unwrap();
+        assert_eq!(pool.pending_count(), 1);
+        assert_eq!(pool.best_transactions(10)[0].gas_price, 120);
+    }
+
+    #[test]
+    fn test_inclusion_advances_sender_nonce() {
+        let pool = TransactionPool::new(100);
+        let sk = SecretKey::generate();
+        let first = signed_tx(&sk, 0, 10);
+        pool.add_transaction(first.clone()).unwrap();
+        pool.add_transaction(signed_tx(&sk, 1, 10)).unwrap();
+        pool.add_transaction(signed_tx(&sk, 3, 10)).unwrap();
+        assert_eq!(pool.pending_count(), 2);
+
+        pool.remove_transactions(&[first.hash()]);
+        assert_eq!(pool.pending_count(), 1);
+        assert_eq!(pool.queued_count(), 1);
+
+        // Filling nonce 2 releases nonce 3 as well
+        pool.add_transaction(signed_tx(&sk, 2, 10)).unwrap();
+        assert_eq!(pool.pending_count(), 3);
+        assert_eq!(pool.queued_count(), 0);
+    }
+    
+    #[test]
+    fn test_save_and_load_keeps_queued_transactions() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(MEMPOOL_FILE);
+        let pool = TransactionPool::new(100);
+        let sk = SecretKey::generate();
+        pool.add_transaction_with_account_nonce(signed_tx(&sk, 4, 10), 4).unwrap();
+        pool.add_transaction_with_account_nonce(signed_tx(&sk, 6, 10), 4).unwrap();
+        pool.save(&path).unwrap();
+
+        let restored = TransactionPool::new(100);
+        assert_eq!(restored.load(&path).unwrap(), 2);
+        assert_eq!(restored.pending_count(), 1);
+        assert_eq!(restored.queued_count(), 1);
+
+        // Nothing saved yet is not an error
+        assert_eq!(restored.load(&dir.path().join("missing.bin")).unwrap(), 0);
+    }
+    
     #[test]
     fn test_remove_transactions() {
         let pool = TransactionPool::new(100);
@@ -210,4 +809,98 @@ mod tests {
         pool.remove_transactions(&[tx_hash]);
         assert_eq!(pool.pending_count(), 0);
     }
+
+    #[test]
+    fn test_executable_displaces_queued_at_capacity() {
+        let pool = TransactionPool::new(8);
+        let spammer = SecretKey::generate();
+        pool.add_transaction_with_account_nonce(signed_tx(&spammer, 5, 100), 0).unwrap();
+        pool.add_transaction_with_account_nonce(signed_tx(&spammer, 6, 100), 0).unwrap();
+        for _ in 0..6 {
+            pool.add_transaction(create_test_tx(0, 50)).unwrap();
+        }
+        assert_eq!(pool.queued_count(), 2);
+
+        // A cheap executable transaction still gets in by dropping a queued one
+        pool.add_transaction(create_test_tx(0, 1)).unwrap();
+        assert_eq!(pool.pending_count(), 7);
+        assert_eq!(pool.queued_count(), 1);
+    }
+
+    #[test]
+    fn test_queued_transactions_bounded_overall() {
+        // 25% of 8 leaves room for two queued transactions
+        let pool = TransactionPool::new(8);
+        for _ in 0..2 {
+            pool.add_transaction_with_account_nonce(signed_tx(&SecretKey::generate(), 3, 10), 0).unwrap();
+        }
+
+        let err = pool.add_transaction_with_account_nonce(signed_tx(&SecretKey::generate(), 3, 10), 0).unwrap_err();
+        assert!(err.contains("queue full"), "{}", err);
+
+        // A better-paying queued transaction displaces the cheapest one
+        pool.add_transaction_with_account_nonce(signed_tx(&SecretKey::generate(), 3, 20), 0).unwrap();
+        assert_eq!(pool.queued_count(), 2);
+
+        // Executable transactions are unaffected by the queue limit
+        pool.add_transaction(create_test_tx(0, 1)).unwrap();
+        assert_eq!(pool.pending_count(), 1);
+    }
+
+    #[test]
+    fn test_queued_transactions_bounded_per_sender() {
+        let pool = TransactionPool::new(100).with_max_queued_per_sender(2);
+        let sk = SecretKey::generate();
+        pool.add_transaction_with_account_nonce(signed_tx(&sk, 2, 10), 0).unwrap();
+        pool.add_transaction_with_account_nonce(signed_tx(&sk, 3, 10), 0).unwrap();
+
+        let err = pool.add_transaction_with_account_nonce(signed_tx(&sk, 4, 10), 0).unwrap_err();
+        assert!(err.contains("Too many queued"), "{}", err);
+
+        // Executable nonces and other senders are still accepted
+        pool.add_transaction_with_account_nonce(signed_tx(&sk, 0, 10), 0).unwrap();
+        pool.add_transaction_with_account_nonce(signed_tx(&SecretKey::generate(), 4, 10), 0).unwrap();
+        assert_eq!(pool.queued_count(), 3);
+    }
+
+    #[test]
+    fn test_replace_by_fee_at_max_gas_price() {
+        let pool = TransactionPool::new(100).with_price_bump(10);
+        let sk = SecretKey::generate();
+        pool.add_transaction(signed_tx(&sk, 0, u64::MAX - 1)).unwrap();
+
+        // The bump saturates instead of overflowing
+        pool.add_transaction(signed_tx(&sk, 0, u64::MAX)).unwrap();
+        assert_eq!(pool.best_transactions(1)[0].gas_price, u64::MAX);
+        assert!(pool.add_transaction(signed_tx(&sk, 0, u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_removal_keeps_sender_nonce() {
+        let pool = TransactionPool::new(100);
+        let sk = SecretKey::generate();
+        let first = signed_tx(&sk, 0, 10);
+        pool.add_transaction(first.clone()).unwrap();
+        pool.remove_transactions(&[first.hash()]);
+        assert_eq!(pool.pending_count(), 0);
+
+        // The included nonce cannot be reused, and a gap is still a gap
+        assert!(pool.add_transaction(signed_tx(&sk, 0, 20)).is_err());
+        pool.add_transaction(signed_tx(&sk, 2, 10)).unwrap();
+        assert_eq!(pool.queued_count(), 1);
+        pool.add_transaction(signed_tx(&sk, 1, 10)).unwrap();
+        assert_eq!(pool.pending_count(), 2);
+    }
+
+    #[test]
+    fn test_pending_amount() {
+        let pool = TransactionPool::new(100);
+        let sk = SecretKey::generate();
+        pool.add_transaction(signed_tx(&sk, 0, 10)).unwrap();
+        pool.add_transaction(signed_tx(&sk, 1, 10)).unwrap();
+
+        assert_eq!(pool.pending_amount(&sk.public_key(), u64::MAX), 200);
+        assert_eq!(pool.pending_amount(&sk.public_key(), 1), 100);
+        assert_eq!(pool.pending_amount(&SecretKey::generate().public_key(), 0), 0);
+    }
 }
@@ -1,16 +1,19 @@
 //! Validator node implementation
 
 use crate::{NodeConfig, Result, MetricsRegistry, Blockchain, TransactionPool};
+use crate::blockchain::MAX_BLOCK_SIZE;
+use crate::tx_pool::MEMPOOL_FILE;
 use bitcell_consensus::Block;
 use bitcell_network::PeerManager;
-use bitcell_crypto::SecretKey;
+use bitcell_crypto::{PublicKey, SecretKey};
+use bitcell_ebsl::TrustScore;
+use bitcell_economics::MAX_GAS_PER_BLOCK;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
 use tokio::time;
 
-/// Max transactions per block
-const MAX_TXS_PER_BLOCK: usize = 1000;
-
 /// Validator node
 pub struct ValidatorNode {
     pub config: NodeConfig,
@@ -21,6 +24,10 @@ pub struct ValidatorNode {
     pub secret_key: Arc<SecretKey>,
     pub tournament_manager: Arc<crate::tournament::TournamentManager>,
     pub network: Arc<crate::network::NetworkManager>,
+    /// Set to true to stop block production
+    shutdown: watch::Sender<bool>,
+    /// Block production loop, once started
+    block_production: Option<JoinHandle<()>>,
 }
 
 impl ValidatorNode {
@@ -36,7 +43,10 @@ impl ValidatorNode {
     }
 
     pub fn with_key(config: NodeConfig, secret_key: Arc<SecretKey>) -> crate::Result<Self> {
+        config.validate().map_err(crate::Error::Config)?;
         let metrics = MetricsRegistry::new();
+        // One set of EBSL parameters for evidence decay and tournament eligibility
+        let ebsl_params = bitcell_ebsl::EbslParams::default();
         
         // Create blockchain with or without persistent storage based on config
         let blockchain = if let Some(ref data_path) = config.data_dir {
@@ -50,27 +60,49 @@ impl ValidatorNode {
         } else {
             println!("⚠️  Using in-memory storage (data will not persist)");
             Blockchain::new(secret_key.clone(), metrics.clone())
-        };
+        }
+        .with_ebsl_params(ebsl_params.clone());
+        
+        if let Some(ref alloc_path) = config.genesis_alloc {
+            let credited = blockchain.apply_genesis_alloc(alloc_path)?;
+            if credited > 0 {
+                println!("💰 Credited {} genesis accounts from {}", credited, alloc_path.display());
+            }
+        }
         
-        let tournament_manager = Arc::new(crate::tournament::TournamentManager::new(metrics.clone()));
+        let tournament_manager = Arc::new(
+            crate::tournament::TournamentManager::new(metrics.clone()).with_ebsl_params(ebsl_params),
+        );
         let network = Arc::new(crate::network::NetworkManager::new(secret_key.public_key(), metrics.clone()));
+        network.set_max_peers(config.max_peers);
+        
+        let tx_pool = TransactionPool::new(config.tx_pool_size);
+        if let Some(ref data_path) = config.data_dir {
+            match tx_pool.load(&data_path.join(MEMPOOL_FILE)) {
+                Ok(0) => {}
+                Ok(n) => println!("Restored {} pooled transactions", n),
+                Err(e) => eprintln!("Failed to restore transaction pool: {}", e),
+            }
+        }
         
         Ok(Self {
             config,
             peers: PeerManager::new(),
             metrics,
             blockchain,
-            tx_pool: TransactionPool::default(),
+            tx_pool,
             secret_key,
             tournament_manager,
             network,
+            shutdown: watch::channel(false).0,
+            block_production: None,
         })
     }
 
     pub async fn start(&mut self) -> Result<()> {
         println!("Starting validator node on port {}", self.config.network_port);
-        // Launch network and metrics server (metrics on network_port + 1)
-        let metrics_port = self.config.network_port + 1;
+        // Launch network and metrics server on the binary's metrics port
+        let metrics_port = crate::config::metrics_port_for(self.config.network_port);
         self.start_with_metrics(metrics_port).await
     }
 
@@ -78,7 +110,11 @@ impl ValidatorNode {
         println!("Starting validator node on port {}", self.config.network_port);
         
         // Start network layer
-        self.network.start(self.config.network_port, self.config.bootstrap_nodes.clone()).await?;
+        self.network.start(
+            self.config.network_port,
+            self.config.bootstrap_nodes.clone(),
+            self.config.bootstrap_max_attempts,
+        ).await?;
         
         // Enable DHT if configured
         if self.config.enable_dht {
@@ -180,17 +216,37 @@ impl ValidatorNode {
         let tournament_manager = self.tournament_manager.clone();
         let network = self.network.clone();
         let block_time_secs = self.config.block_time_secs;
+        let mut shutdown = self.shutdown.subscribe();
         
-        tokio::spawn(async move {
+        self.block_production = Some(tokio::spawn(async move {
             let mut interval = time::interval(Duration::from_secs(block_time_secs));
             let mut next_height = 1u64;
             
             loop {
-                interval.tick().await;
+                // A block already in progress is finished before stopping
+                tokio::select! {
+                    _ = shutdown.changed() => break,
+                    _ = interval.tick() => {}
+                }
                 
-                // For simplified implementation, create a tournament with just this validator
-                // In production, this would include all eligible miners from EBSL
-                let eligible_miners = vec![secret_key.public_key()];
+                // Bonded miners that meet the EBSL t_min threshold. This
+                // validator joins only when its own trust is eligible too
+                let eligible_miners: Vec<PublicKey> = {
+                    let state = blockchain.state();
+                    let state = state.read().unwrap();
+                    let params = blockchain.ebsl_params();
+                    let mut miners: Vec<PublicKey> = state
+                        .eligible_miners(params)
+                        .into_iter()
+                        .filter_map(|miner| PublicKey::from_bytes(miner).ok())
+                        .collect();
+                    let own_key = secret_key.public_key();
+                    let own_trust = TrustScore::new(state.calculate_trust_score(own_key.as_bytes(), params));
+                    if own_trust.is_eligible(params) && !miners.contains(&own_key) {
+                        miners.push(own_key);
+                    }
+                    miners
+                };
                 let seed = bitcell_crypto::Hash256::hash(&next_height.to_le_bytes());
                 
                 println!("\n=== Starting tournament for block height {} ===", next_height);
@@ -205,14 +261,14 @@ impl ValidatorNode {
                     Ok(winner) => {
                         println!("Tournament winner selected: {:?}", winner);
                         
-                        // Get pending transactions
-                        let pending_txs = tx_pool.get_transactions(MAX_TXS_PER_BLOCK);
+                        // Highest-fee pending transactions that fit the block gas and size limits
+                        let pending_txs = tx_pool.best_transactions_within(MAX_GAS_PER_BLOCK, MAX_BLOCK_SIZE as u64);
                         
                         // Get battle proofs from tournament
                         let battle_proofs = tournament_manager.get_battle_proofs().await;
                         
                         // Produce block with tournament winner as proposer
-                        match blockchain.produce_block(pending_txs.clone(), battle_proofs, winner) {
+                        match blockchain.produce_block(pending_txs, battle_proofs, winner) {
                             Ok(block) => {
                                 println!("Produced block at height {}", block.header.height);
                                 
@@ -234,14 +290,14 @@ impl ValidatorNode {
                                 );
                                 
                                 // Remove included transactions from pool
-                                let tx_hashes: Vec<_> = pending_txs.iter().map(|tx| tx.hash()).collect();
+                                let tx_hashes: Vec<_> = block.transactions.iter().map(|tx| tx.hash()).collect();
                                 tx_pool.remove_transactions(&tx_hashes);
                                 
                                 // Update metrics
                                 metrics.set_chain_height(blockchain.height());
                                 metrics.set_pending_txs(tx_pool.pending_count());
                                 
-                                for _tx in &pending_txs {
+                                for _tx in &block.transactions {
                                     metrics.inc_total_txs_processed();
                                 }
                                 
@@ -268,18 +324,36 @@ impl ValidatorNode {
                     }
                 }
             }
-        });
+        }));
         
 
         Ok(())
     }
 
+    /// Stop producing blocks, close peer connections and persist state
+    ///
+    /// Waits for any block in progress, then saves the transaction pool and
+    /// flushes state storage when a data directory is configured.
+    pub async fn shutdown(&mut self) -> Result<()> {
+        self.shutdown.send_replace(true);
+        if let Some(handle) = self.block_production.take() {
+            let _ = handle.await;
+        }
+        
+        self.network.shutdown().await;
+        
+        if let Some(ref data_path) = self.config.data_dir {
+            self.tx_pool.save(&data_path.join(MEMPOOL_FILE))
+                .map_err(crate::Error::Node)?;
+        }
+        self.blockchain.flush()
+    }
+
     pub fn validate_block(&self, block: &Block) -> bool {
         self.blockchain.validate_block(block).is_ok()
     }
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,4 +366,75 @@ mod tests {
         let state_guard = state.read().unwrap();
         assert_eq!(state_guard.accounts.len(), 0);
     }
+
+    #[test]
+    fn test_genesis_alloc_seeds_balances() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let alice = SecretKey::generate().public_key();
+        let bob = SecretKey::generate().public_key();
+        let alloc_path = dir.path().join("genesis.json");
+        let alloc = format!(
+            r#"{{"{}": 1000000, "0x{}": 250}}"#,
+            hex::encode(alice.as_bytes()),
+            hex::encode(bob.as_bytes()),
+        );
+        std::fs::write(&alloc_path, alloc).unwrap();
+
+        let config = NodeConfig {
+            genesis_alloc: Some(alloc_path.clone()),
+            ..NodeConfig::default()
+        };
+        let node = ValidatorNode::new(config).unwrap();
+        let state = node.blockchain.state();
+        {
+            let state = state.read().unwrap();
+            assert_eq!(state.get_account(alice.as_bytes()).unwrap().balance, 1_000_000);
+            assert_eq!(state.get_account(bob.as_bytes()).unwrap().balance, 250);
+        }
+
+        // Applying again to a seeded chain is a no-op
+        assert_eq!(node.blockchain.apply_genesis_alloc(&alloc_path).unwrap(), 0);
+        assert_eq!(state.read().unwrap().get_account(alice.as_bytes()).unwrap().balance, 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_persists_state_and_pool() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = NodeConfig {
+            data_dir: Some(dir.path().to_path_buf()),
+            ..NodeConfig::default()
+        };
+        let secret_key = Arc::new(SecretKey::generate());
+        let proposer = secret_key.public_key();
+        let mut node = ValidatorNode::with_key(config.clone(), secret_key.clone()).unwrap();
+
+        let block = node.blockchain.produce_block(vec![], vec![], proposer).unwrap();
+        node.blockchain.add_block(block).unwrap();
+        let balance = node.blockchain.state().read().unwrap()
+            .get_account_owned(proposer.as_bytes()).unwrap().balance;
+        assert!(balance > 0);
+
+        let tx = bitcell_consensus::Transaction {
+            nonce: 0,
+            from: proposer,
+            to: proposer,
+            amount: 1,
+            gas_limit: 21000,
+            gas_price: 1,
+            data: vec![],
+            spends: vec![],
+            signature: secret_key.sign(b"test"),
+        };
+        node.tx_pool.add_transaction(tx.clone()).unwrap();
+
+        node.shutdown().await.unwrap();
+        drop(node);
+
+        // Reopening the data directory sees the applied block and the pool
+        let node = ValidatorNode::with_key(config, secret_key).unwrap();
+        let account = node.blockchain.state().read().unwrap()
+            .get_account_owned(proposer.as_bytes()).unwrap();
+        assert_eq!(account.balance, balance);
+        assert_eq!(node.tx_pool.get_pending_transactions()[0].hash(), tx.hash());
+    }
 }
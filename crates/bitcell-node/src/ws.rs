@@ -11,12 +11,13 @@ use futures::{sink::SinkExt, stream::StreamExt};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::broadcast;
 use tokio::time;
 use crate::rpc::RpcState;
+use bitcell_consensus::{Block, Transaction};
 
 /// Maximum subscriptions per client
 const MAX_SUBSCRIPTIONS_PER_CLIENT: usize = 100;
@@ -185,108 +186,68 @@ fn log_matches_filter(log_data: &Value, filter: &LogFilter) -> bool {
     true
 }
 
-/// Global event broadcaster - single instance per server
-pub struct GlobalEventBroadcaster {
-    tx: broadcast::Sender<GlobalEvent>,
+/// `newHeads` payload for a block
+fn new_head_event(block: &Block) -> Value {
+    json!({
+        "number": format!("0x{:x}", block.header.height),
+        "hash": format!("0x{}", hex::encode(block.hash().as_bytes())),
+        "parentHash": format!("0x{}", hex::encode(block.header.prev_hash.as_bytes())),
+        "timestamp": format!("0x{:x}", block.header.timestamp),
+        "miner": format!("0x{}", hex::encode(block.header.proposer.as_bytes())),
+        "transactionsRoot": format!("0x{}", hex::encode(block.header.tx_root.as_bytes())),
+        "stateRoot": format!("0x{}", hex::encode(block.header.state_root.as_bytes())),
+    })
 }
 
-impl GlobalEventBroadcaster {
-    pub fn new() -> Self {
-        let (tx, _) = broadcast::channel(1000);
-        Self { tx }
-    }
-
-    pub fn broadcast(&self, event: GlobalEvent) {
-        // Ignore errors if no receivers
-        let _ = self.tx.send(event);
-    }
-
-    pub fn subscribe(&self) -> broadcast::Receiver<GlobalEvent> {
-        self.tx.subscribe()
-    }
+/// `logs` payloads for the transactions of a block
+fn log_events(block: &Block) -> Vec<Value> {
+    let block_hash = format!("0x{}", hex::encode(block.hash().as_bytes()));
+    block.transactions.iter().enumerate().map(|(tx_index, tx)| {
+        // In a real implementation, we'd get logs from transaction receipts
+        // For now, we create a placeholder log structure
+        // TODO: Implement actual log extraction from receipts
+        json!({
+            "address": format!("0x{}", hex::encode(tx.to.as_bytes())),
+            "topics": [],
+            "data": format!("0x{}", hex::encode(&tx.data)),
+            "blockNumber": format!("0x{:x}", block.header.height),
+            "transactionHash": format!("0x{}", hex::encode(tx.hash().as_bytes())),
+            "transactionIndex": format!("0x{:x}", tx_index),
+            "blockHash": block_hash,
+            "logIndex": "0x0",
+            "removed": false
+        })
+    }).collect()
 }
 
-/// Start global monitors (should be called once at server startup)
-pub fn start_global_monitors(state: RpcState, broadcaster: Arc<GlobalEventBroadcaster>) {
-    // Block monitor
-    {
-        let state = state.clone();
-        let broadcaster = broadcaster.clone();
-        tokio::spawn(async move {
-            let mut interval = time::interval(Duration::from_secs(1));
-            let mut last_height = state.blockchain.height();
+/// `pendingTransactions` payload: the transaction hash
+fn pending_transaction_event(tx: &Transaction) -> Value {
+    json!(format!("0x{}", hex::encode(tx.hash().as_bytes())))
+}
 
-            loop {
-                interval.tick().await;
-                let current_height = state.blockchain.height();
-                if current_height > last_height {
-                    if let Some(block) = state.blockchain.get_block(current_height) {
-                        let block_data = json!({
-                            "number": format!("0x{:x}", block.header.height),
-                            "hash": format!("0x{}", hex::encode(block.hash().as_bytes())),
-                            "parentHash": format!("0x{}", hex::encode(block.header.prev_hash.as_bytes())),
-                            "timestamp": format!("0x{:x}", block.header.timestamp),
-                            "miner": format!("0x{}", hex::encode(block.header.proposer.as_bytes())),
-                            "transactionsRoot": format!("0x{}", hex::encode(block.header.tx_root.as_bytes())),
-                            "stateRoot": format!("0x{}", hex::encode(block.header.state_root.as_bytes())),
-                        });
-                        broadcaster.broadcast(GlobalEvent::NewBlock(block_data));
-                        
-                        // Also extract and broadcast logs from the block
-                        for (tx_index, tx) in block.transactions.iter().enumerate() {
-                            // In a real implementation, we'd get logs from transaction receipts
-                            // For now, we create a placeholder log structure
-                            // TODO: Implement actual log extraction from receipts
-                            let log_data = json!({
-                                "address": format!("0x{}", hex::encode(tx.to.as_bytes())),
-                                "topics": [],
-                                "data": format!("0x{}", hex::encode(&tx.data)),
-                                "blockNumber": format!("0x{:x}", block.header.height),
-                                "transactionHash": format!("0x{}", hex::encode(tx.hash().as_bytes())),
-                                "transactionIndex": format!("0x{:x}", tx_index),
-                                "blockHash": format!("0x{}", hex::encode(block.hash().as_bytes())),
-                                "logIndex": "0x0",
-                                "removed": false
-                            });
-                            broadcaster.broadcast(GlobalEvent::Log(log_data));
-                        }
-                    }
-                    last_height = current_height;
-                }
-            }
-        });
-    }
+/// Wait for the next chain or mempool event
+///
+/// Returns `None` once either source has shut down.
+async fn next_events(
+    blocks: &mut broadcast::Receiver<Block>,
+    txs: &mut broadcast::Receiver<Transaction>,
+) -> Option<Vec<GlobalEvent>> {
+    let events = tokio::select! {
+        block = blocks.recv() => block.map(|block| {
+            let mut events = vec![GlobalEvent::NewBlock(new_head_event(&block))];
+            events.extend(log_events(&block).into_iter().map(GlobalEvent::Log));
+            events
+        }),
+        tx = txs.recv() => tx.map(|tx| vec![GlobalEvent::PendingTransaction(pending_transaction_event(&tx))]),
+    };
 
-    // Pending transaction monitor
-    {
-        let state = state.clone();
-        let broadcaster = broadcaster.clone();
-        tokio::spawn(async move {
-            let mut interval = time::interval(Duration::from_millis(500));
-            let mut seen_txs = HashSet::new();
-
-            loop {
-                interval.tick().await;
-                let pending_txs = state.tx_pool.get_pending_transactions();
-                
-                // Broadcast only new transactions we haven't seen before
-                for tx in &pending_txs {
-                    let tx_hash = tx.hash();
-                    if !seen_txs.contains(&tx_hash) {
-                        let tx_hash_hex = format!("0x{}", hex::encode(tx_hash.as_bytes()));
-                        broadcaster.broadcast(GlobalEvent::PendingTransaction(json!(tx_hash_hex)));
-                        seen_txs.insert(tx_hash);
-                    }
-                }
-                
-                // Prevent unbounded memory growth
-                if seen_txs.len() > 10000 {
-                    let current_hashes: HashSet<_> = 
-                        pending_txs.iter().map(|tx| tx.hash()).collect();
-                    seen_txs.retain(|hash| current_hashes.contains(hash));
-                }
-            }
-        });
+    match events {
+        Ok(events) => Some(events),
+        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+            tracing::warn!("Client lagging behind events, skipped {}", skipped);
+            Some(Vec::new())
+        }
+        Err(broadcast::error::RecvError::Closed) => None,
     }
 }
 
@@ -302,12 +263,9 @@ async fn handle_json_rpc_socket(socket: WebSocket, state: RpcState) {
     let (mut sender, mut receiver) = socket.split();
     let subscription_manager = Arc::new(SubscriptionManager::new());
     
-    // Get global broadcaster from state - for now we'll create a local one
-    // TODO: Store broadcaster in RpcState for proper global sharing
-    let broadcaster = Arc::new(GlobalEventBroadcaster::new());
-    start_global_monitors(state.clone(), broadcaster.clone());
-    
-    let mut global_rx = broadcaster.subscribe();
+    // Events are pushed by the blockchain and transaction pool as they happen
+    let mut block_rx = state.blockchain.subscribe_blocks();
+    let mut tx_rx = state.tx_pool.subscribe();
     
     let message_count = Arc::new(RwLock::new(0usize));
     let last_reset = Arc::new(RwLock::new(time::Instant::now()));
@@ -330,24 +288,15 @@ async fn handle_json_rpc_socket(socket: WebSocket, state: RpcState) {
         let subscription_manager = subscription_manager.clone();
         let tx = tx.clone();
         tokio::spawn(async move {
-            loop {
-                match global_rx.recv().await {
-                    Ok(event) => {
-                        let notifications = subscription_manager.get_matching_subscriptions(&event);
-                        for (_sub_id, notification) in notifications {
-                            if tx.send(Message::Text(notification.to_string())).is_err() {
-                                tracing::debug!("Failed to queue notification");
-                                return;
-                            }
+            while let Some(events) = next_events(&mut block_rx, &mut tx_rx).await {
+                for event in events {
+                    let notifications = subscription_manager.get_matching_subscriptions(&event);
+                    for (_sub_id, notification) in notifications {
+                        if tx.send(Message::Text(notification.to_string())).is_err() {
+                            tracing::debug!("Failed to queue notification");
+                            return;
                         }
                     }
-                    Err(broadcast::error::RecvError::Lagged(_)) => {
-                        tracing::warn!("Client lagging behind events");
-                        continue;
-                    }
-                    Err(broadcast::error::RecvError::Closed) => {
-                        break;
-                    }
                 }
             }
         })
@@ -459,7 +408,7 @@ async fn handle_subscription_request(
     }
 
     match req.method.as_str() {
-        "eth_subscribe" => {
+        "eth_subscribe" | "subscribe" => {
             let count = subscription_manager.subscription_count();
             if count >= MAX_SUBSCRIPTIONS_PER_CLIENT {
                 return SubscriptionResponse {
@@ -512,7 +461,7 @@ async fn handle_subscription_request(
                 }),
             }
         }
-        "eth_unsubscribe" => {
+        "eth_unsubscribe" | "unsubscribe" => {
             if let Some(params) = req.params {
                 if let Some(sub_id) = params.get(0).and_then(|v| v.as_str()) {
                     let success = subscription_manager.unsubscribe(sub_id);
@@ -251,3 +251,87 @@ async fn test_max_subscriptions_per_client() {
     
     println!("Subscription limit hit: {}", limit_hit);
 }
+
+/// Serve RPC for a fresh in-memory node on an ephemeral port
+async fn spawn_node() -> (std::net::SocketAddr, bitcell_node::rpc::RpcState) {
+    use bitcell_crypto::SecretKey;
+    use bitcell_node::{Blockchain, MetricsRegistry, NetworkManager, NodeConfig, TransactionPool};
+    use std::sync::Arc;
+
+    let sk = Arc::new(SecretKey::generate());
+    let metrics = MetricsRegistry::new();
+    let state = bitcell_node::rpc::RpcState {
+        blockchain: Blockchain::new(sk.clone(), metrics.clone()),
+        network: NetworkManager::new(sk.public_key(), metrics),
+        tx_pool: TransactionPool::default(),
+        tournament_manager: None,
+        config: Arc::new(parking_lot::RwLock::new(NodeConfig::default())),
+        log_filter: None,
+        node_type: "validator".to_string(),
+        node_id: hex::encode(sk.public_key().as_bytes()),
+    };
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = bitcell_node::rpc::router(state.clone());
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    (addr, state)
+}
+
+#[tokio::test]
+async fn test_pending_transaction_is_pushed_to_subscriber() {
+    use bitcell_consensus::Transaction;
+    use bitcell_crypto::SecretKey;
+
+    let (addr, state) = spawn_node().await;
+    let (ws_stream, _) = connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+    let (mut write, mut read) = ws_stream.split();
+
+    let subscribe_req = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "subscribe",
+        "params": ["pendingTransactions"]
+    });
+    write.send(Message::Text(subscribe_req.to_string())).await.unwrap();
+
+    let sub_id = match read.next().await {
+        Some(Ok(Message::Text(text))) => {
+            let response: Value = serde_json::from_str(&text).unwrap();
+            response["result"].as_str().unwrap().to_string()
+        }
+        other => panic!("Unexpected subscription response: {:?}", other),
+    };
+
+    // Submit a transaction to the node's pool
+    let sk = SecretKey::generate();
+    let mut tx = Transaction {
+        nonce: 0,
+        from: sk.public_key(),
+        to: SecretKey::generate().public_key(),
+        amount: 100,
+        gas_limit: 21000,
+        gas_price: 1,
+        data: vec![],
+        spends: vec![],
+        signature: sk.sign(b"placeholder"),
+    };
+    tx.signature = sk.sign(tx.signing_hash().as_bytes());
+    let tx_hash = format!("0x{}", hex::encode(tx.hash().as_bytes()));
+    state.tx_pool.add_transaction(tx).unwrap();
+
+    let notification = tokio::time::timeout(Duration::from_secs(5), read.next())
+        .await
+        .expect("pendingTransactions event should arrive");
+    let notification: Value = match notification {
+        Some(Ok(Message::Text(text))) => serde_json::from_str(&text).unwrap(),
+        other => panic!("Unexpected message: {:?}", other),
+    };
+
+    assert_eq!(notification["method"], "eth_subscription");
+    assert_eq!(notification["params"]["subscription"], sub_id.as_str());
+    assert_eq!(notification["params"]["result"], tx_hash.as_str());
+}
@@ -5,9 +5,13 @@
 
 use bitcell_consensus::{GliderCommitment, GliderReveal, TournamentOrchestrator};
 use bitcell_crypto::{Hash256, PublicKey, SecretKey};
-use bitcell_ca::{Glider, GliderPattern, Position};
+use bitcell_ca::{Battle, Glider, GliderPattern, Position};
+use bitcell_ebsl::{EvidenceCounters, EvidenceType, TrustScore};
+use bitcell_economics::{calculate_block_reward, RewardDistribution};
 
-use rand::Rng;
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
 
 /// Derive a spawn position from a public key for varied positions
 fn derive_position_from_pubkey(pk: &PublicKey) -> Position {
@@ -17,6 +21,25 @@ fn derive_position_from_pubkey(pk: &PublicKey) -> Position {
     Position::new(x, y)
 }
 
+/// Derive a secret key deterministically from an RNG
+fn secret_key_from_rng<R: Rng + ?Sized>(rng: &mut R) -> SecretKey {
+    loop {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        // Out-of-range scalars are astronomically rare; just draw again
+        if let Ok(sk) = SecretKey::from_bytes(&bytes) {
+            return sk;
+        }
+    }
+}
+
+/// Derive a per-round commitment nonce that only the key holder can predict
+fn commitment_nonce(sk: &SecretKey, height: u64) -> Vec<u8> {
+    Hash256::hash_multiple(&[&sk.to_bytes(), &height.to_le_bytes()])
+        .as_bytes()
+        .to_vec()
+}
+
 /// Trait defining a miner's behavior in the simulation
 pub trait MinerAgent {
     /// Get the miner's public key
@@ -26,7 +49,9 @@ pub trait MinerAgent {
     fn generate_commitment(&mut self, height: u64) -> GliderCommitment;
     
     /// Generate a reveal for the current round (if they choose to reveal)
-    fn generate_reveal(&mut self, height: u64) -> Option<GliderReveal>;
+    ///
+    /// Any randomness must come from `rng` so runs are reproducible.
+    fn generate_reveal(&mut self, height: u64, rng: &mut dyn RngCore) -> Option<GliderReveal>;
     
     /// Name of the agent type (for logging)
     fn name(&self) -> &str;
@@ -40,9 +65,14 @@ pub struct HonestMiner {
 }
 
 impl HonestMiner {
-    pub fn new() -> Self {
+    /// Create a miner whose key is drawn from `rng`
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::with_key(secret_key_from_rng(rng))
+    }
+    
+    fn with_key(sk: SecretKey) -> Self {
         Self {
-            sk: SecretKey::generate(),
+            sk,
             current_glider: None,
             current_nonce: Vec::new(),
         }
@@ -57,20 +87,17 @@ impl MinerAgent for HonestMiner {
     fn generate_commitment(&mut self, height: u64) -> GliderCommitment {
         let position = derive_position_from_pubkey(&self.public_key());
         let glider = Glider::new(GliderPattern::Standard, position);
-        let nonce = vec![0u8; 32]; // Simplified nonce
+        let nonce = commitment_nonce(&self.sk, height);
+        let commitment = GliderCommitment::new(&glider, &nonce, &self.public_key(), height);
         
         // Store for reveal
-        self.current_glider = Some(glider.clone());
-        self.current_nonce = nonce.clone();
+        self.current_glider = Some(glider);
+        self.current_nonce = nonce;
         
-        GliderCommitment {
-            commitment: Hash256::zero(), // Placeholder for actual commitment hash
-            ring_signature: vec![],
-            height,
-        }
+        commitment
     }
 
-    fn generate_reveal(&mut self, _height: u64) -> Option<GliderReveal> {
+    fn generate_reveal(&mut self, _height: u64, _rng: &mut dyn RngCore) -> Option<GliderReveal> {
         if let Some(glider) = &self.current_glider {
             Some(GliderReveal {
                 glider: glider.clone(),
@@ -91,13 +118,20 @@ impl MinerAgent for HonestMiner {
 pub struct TieFarmer {
     sk: SecretKey,
     current_glider: Option<Glider>,
+    current_nonce: Vec<u8>,
 }
 
 impl TieFarmer {
-    pub fn new() -> Self {
+    /// Create a tie farmer whose key is drawn from `rng`
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::with_key(secret_key_from_rng(rng))
+    }
+    
+    fn with_key(sk: SecretKey) -> Self {
         Self {
-            sk: SecretKey::generate(),
+            sk,
             current_glider: None,
+            current_nonce: Vec::new(),
         }
     }
 }
@@ -111,19 +145,19 @@ impl MinerAgent for TieFarmer {
         let position = derive_position_from_pubkey(&self.public_key());
         // Tie farmer picks a symmetric pattern (e.g., Heavyweight)
         let glider = Glider::new(GliderPattern::Heavyweight, position);
+        let nonce = commitment_nonce(&self.sk, height);
+        let commitment = GliderCommitment::new(&glider, &nonce, &self.public_key(), height);
+        
         self.current_glider = Some(glider);
+        self.current_nonce = nonce;
         
-        GliderCommitment {
-            commitment: Hash256::zero(),
-            ring_signature: vec![],
-            height,
-        }
+        commitment
     }
 
-    fn generate_reveal(&mut self, _height: u64) -> Option<GliderReveal> {
+    fn generate_reveal(&mut self, _height: u64, _rng: &mut dyn RngCore) -> Option<GliderReveal> {
         self.current_glider.as_ref().map(|g| GliderReveal {
             glider: g.clone(),
-            nonce: vec![],
+            nonce: self.current_nonce.clone(),
             miner: self.public_key(),
         })
     }
@@ -137,13 +171,20 @@ impl MinerAgent for TieFarmer {
 pub struct ChaosSpammer {
     sk: SecretKey,
     current_glider: Option<Glider>,
+    current_nonce: Vec<u8>,
 }
 
 impl ChaosSpammer {
-    pub fn new() -> Self {
+    /// Create a chaos spammer whose key is drawn from `rng`
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::with_key(secret_key_from_rng(rng))
+    }
+    
+    fn with_key(sk: SecretKey) -> Self {
         Self {
-            sk: SecretKey::generate(),
+            sk,
             current_glider: None,
+            current_nonce: Vec::new(),
         }
     }
 }
@@ -158,19 +199,19 @@ impl MinerAgent for ChaosSpammer {
         // Chaos spammer uses a custom high-entropy pattern (simulated here with Heavyweight for now)
         // In a real scenario, this would be a random blob
         let glider = Glider::new(GliderPattern::Heavyweight, position);
+        let nonce = commitment_nonce(&self.sk, height);
+        let commitment = GliderCommitment::new(&glider, &nonce, &self.public_key(), height);
+        
         self.current_glider = Some(glider);
+        self.current_nonce = nonce;
         
-        GliderCommitment {
-            commitment: Hash256::zero(),
-            ring_signature: vec![],
-            height,
-        }
+        commitment
     }
 
-    fn generate_reveal(&mut self, _height: u64) -> Option<GliderReveal> {
+    fn generate_reveal(&mut self, _height: u64, _rng: &mut dyn RngCore) -> Option<GliderReveal> {
         self.current_glider.as_ref().map(|g| GliderReveal {
             glider: g.clone(),
-            nonce: vec![],
+            nonce: self.current_nonce.clone(),
             miner: self.public_key(),
         })
     }
@@ -184,14 +225,21 @@ impl MinerAgent for ChaosSpammer {
 pub struct FlakyGriefer {
     sk: SecretKey,
     current_glider: Option<Glider>,
+    current_nonce: Vec<u8>,
     failure_rate: f64,
 }
 
 impl FlakyGriefer {
-    pub fn new(failure_rate: f64) -> Self {
+    /// Create a flaky griefer whose key is drawn from `rng`
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R, failure_rate: f64) -> Self {
+        Self::with_key(secret_key_from_rng(rng), failure_rate)
+    }
+    
+    fn with_key(sk: SecretKey, failure_rate: f64) -> Self {
         Self {
-            sk: SecretKey::generate(),
+            sk,
             current_glider: None,
+            current_nonce: Vec::new(),
             failure_rate,
         }
     }
@@ -205,24 +253,23 @@ impl MinerAgent for FlakyGriefer {
     fn generate_commitment(&mut self, height: u64) -> GliderCommitment {
         let position = derive_position_from_pubkey(&self.public_key());
         let glider = Glider::new(GliderPattern::Standard, position);
+        let nonce = commitment_nonce(&self.sk, height);
+        let commitment = GliderCommitment::new(&glider, &nonce, &self.public_key(), height);
+        
         self.current_glider = Some(glider);
+        self.current_nonce = nonce;
         
-        GliderCommitment {
-            commitment: Hash256::zero(),
-            ring_signature: vec![],
-            height,
-        }
+        commitment
     }
 
-    fn generate_reveal(&mut self, _height: u64) -> Option<GliderReveal> {
-        let mut rng = rand::thread_rng();
+    fn generate_reveal(&mut self, _height: u64, rng: &mut dyn RngCore) -> Option<GliderReveal> {
         if rng.gen_bool(self.failure_rate) {
             // Fail to reveal
             None
         } else {
             self.current_glider.as_ref().map(|g| GliderReveal {
                 glider: g.clone(),
-                nonce: vec![],
+                nonce: self.current_nonce.clone(),
                 miner: self.public_key(),
             })
         }
@@ -233,23 +280,148 @@ impl MinerAgent for FlakyGriefer {
     }
 }
 
+/// Withholding Miner: Commits every round but only reveals when its own
+/// lookahead battle against the expected opponent says it would win by at
+/// least `min_margin` energy
+pub struct WithholdingMiner {
+    sk: SecretKey,
+    current_glider: Option<Glider>,
+    current_nonce: Vec<u8>,
+    pattern: GliderPattern,
+    expected_opponent: GliderPattern,
+    lookahead_steps: usize,
+    min_margin: u64,
+}
+
+impl WithholdingMiner {
+    /// Steps simulated when predicting a battle (much shorter than a real battle)
+    pub const DEFAULT_LOOKAHEAD_STEPS: usize = 100;
+    
+    /// Energy lead required to reveal; 1 withholds on predicted ties
+    pub const DEFAULT_MIN_MARGIN: u64 = 1;
+    
+    /// Create a withholding miner whose key is drawn from `rng`
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R, expected_opponent: GliderPattern) -> Self {
+        Self::with_key(secret_key_from_rng(rng), expected_opponent)
+    }
+    
+    fn with_key(sk: SecretKey, expected_opponent: GliderPattern) -> Self {
+        Self {
+            sk,
+            current_glider: None,
+            current_nonce: Vec::new(),
+            pattern: GliderPattern::Heavyweight,
+            expected_opponent,
+            lookahead_steps: Self::DEFAULT_LOOKAHEAD_STEPS,
+            min_margin: Self::DEFAULT_MIN_MARGIN,
+        }
+    }
+    
+    /// Commit to `pattern` instead of a Heavyweight
+    pub fn with_pattern(mut self, pattern: GliderPattern) -> Self {
+        self.pattern = pattern;
+        self
+    }
+    
+    /// Simulate `steps` when predicting a battle
+    pub fn with_lookahead_steps(mut self, steps: usize) -> Self {
+        self.lookahead_steps = steps;
+        self
+    }
+    
+    /// Require a predicted energy lead of `margin` before revealing
+    pub fn with_min_margin(mut self, margin: u64) -> Self {
+        self.min_margin = margin;
+        self
+    }
+    
+    /// Predict whether `glider` beats the expected opponent by `min_margin`
+    ///
+    /// Compares regional energy rather than the battle outcome, whose
+    /// tiebreaker would count an even lookahead as a win for one side.
+    fn would_win(&self, glider: &Glider) -> bool {
+        let opponent = Glider::new(self.expected_opponent, glider.position);
+        let battle = Battle::with_steps(glider.clone(), opponent, self.lookahead_steps);
+        let (own, theirs) = battle.measure_regional_energy(&battle.final_grid());
+        own >= theirs.saturating_add(self.min_margin)
+    }
+}
+
+impl MinerAgent for WithholdingMiner {
+    fn public_key(&self) -> PublicKey {
+        self.sk.public_key()
+    }
+
+    fn generate_commitment(&mut self, height: u64) -> GliderCommitment {
+        let position = derive_position_from_pubkey(&self.public_key());
+        let glider = Glider::new(self.pattern, position);
+        let nonce = commitment_nonce(&self.sk, height);
+        let commitment = GliderCommitment::new(&glider, &nonce, &self.public_key(), height);
+        
+        self.current_glider = Some(glider);
+        self.current_nonce = nonce;
+        
+        commitment
+    }
+
+    fn generate_reveal(&mut self, _height: u64, _rng: &mut dyn RngCore) -> Option<GliderReveal> {
+        let glider = self.current_glider.as_ref()?;
+        if !self.would_win(glider) {
+            // Withhold: the commitment stays unopened
+            return None;
+        }
+        
+        Some(GliderReveal {
+            glider: glider.clone(),
+            nonce: self.current_nonce.clone(),
+            miner: self.public_key(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "WithholdingMiner"
+    }
+}
+
 /// Simulation Engine
 pub struct SimulationEngine {
     pub orchestrator: TournamentOrchestrator,
     pub agents: Vec<Box<dyn MinerAgent>>,
     pub history: Vec<SimulationEpochResult>,
+    /// Source of all agent randomness, seeded for reproducible runs
+    rng: StdRng,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SimulationEpochResult {
     pub height: u64,
     pub winner: Option<PublicKey>,
     pub mii_usage: f64,
     pub avg_rounds: f64,
+    /// Agents that committed but did not reveal, in agent order
+    pub no_reveals: Vec<PublicKey>,
+    /// Agents whose reveal did not open their commitment, in agent order
+    pub rejected_reveals: Vec<PublicKey>,
+    /// Per-agent rewards and reputation for this epoch
+    pub agent_outcomes: HashMap<PublicKey, AgentOutcome>,
+}
+
+/// What a single agent gained or lost in one epoch
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgentOutcome {
+    /// Block reward share earned this epoch
+    pub rewards: u64,
+    /// Positive evidence recorded this epoch
+    pub evidence_gained: f64,
+    /// Negative evidence recorded this epoch
+    pub evidence_lost: f64,
+    /// EBSL trust score at the end of the epoch
+    pub trust_score: f64,
 }
 
 impl SimulationEngine {
-    pub fn new(agents: Vec<Box<dyn MinerAgent>>) -> Self {
+    /// Create an engine whose agent decisions are driven by `seed`
+    pub fn new(agents: Vec<Box<dyn MinerAgent>>, seed: u64) -> Self {
         let miners: Vec<PublicKey> = agents.iter().map(|a| a.public_key()).collect();
         let orchestrator = TournamentOrchestrator::new(1, miners, Hash256::zero());
         
@@ -257,11 +429,26 @@ impl SimulationEngine {
             orchestrator,
             agents,
             history: Vec::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+    
+    /// Simulate `steps` CA generations per battle instead of the full length
+    pub fn with_battle_steps(mut self, steps: usize) -> Self {
+        self.orchestrator.battle_steps = steps;
+        self
+    }
+    
+    /// Run `n` consecutive epochs
+    pub fn run_epochs(&mut self, n: usize) {
+        for _ in 0..n {
+            self.run_epoch();
         }
     }
     
     pub fn run_epoch(&mut self) {
         let height = self.orchestrator.tournament.height;
+        let evidence_before = self.evidence_snapshot();
         
         // 1. Commit Phase
         for agent in &mut self.agents {
@@ -272,23 +459,47 @@ impl SimulationEngine {
         self.orchestrator.advance_to_reveal().unwrap();
         
         // 2. Reveal Phase
+        let mut no_reveals = Vec::new();
+        let mut rejected_reveals = Vec::new();
         for agent in &mut self.agents {
-            if let Some(reveal) = agent.generate_reveal(height) {
-                let _ = self.orchestrator.process_reveal(reveal);
+            match agent.generate_reveal(height, &mut self.rng) {
+                Some(reveal) => {
+                    if self.orchestrator.process_reveal(reveal).is_err() {
+                        rejected_reveals.push(agent.public_key());
+                    }
+                }
+                None => no_reveals.push(agent.public_key()),
             }
         }
         
+        // Liveness evidence: a commitment left unopened (withheld or not
+        // matching the reveal) is a missed reveal
+        let unopened: Vec<PublicKey> = no_reveals.iter().chain(&rejected_reveals).copied().collect();
+        for agent in &self.agents {
+            let pk = agent.public_key();
+            let evidence = if unopened.contains(&pk) {
+                EvidenceType::MissedReveal
+            } else {
+                EvidenceType::HonestParticipation
+            };
+            self.orchestrator.record_evidence(pk, evidence);
+        }
+        
         self.orchestrator.advance_to_battle().unwrap();
         
         // 3. Battle Phase
         let winner = self.orchestrator.run_battles().ok();
         
         // 4. Record Metrics
+        let agent_outcomes = self.agent_outcomes(height, winner, &unopened, &evidence_before);
         let result = SimulationEpochResult {
             height,
             winner,
             mii_usage: self.orchestrator.metrics.mii_usage_rate,
             avg_rounds: self.orchestrator.metrics.avg_rounds,
+            no_reveals,
+            rejected_reveals,
+            agent_outcomes,
         };
         self.history.push(result);
         
@@ -296,9 +507,71 @@ impl SimulationEngine {
         // For simulation, we just bump height and clear tournament state but keep evidence
         let miners: Vec<PublicKey> = self.agents.iter().map(|a| a.public_key()).collect();
         let old_evidence = self.orchestrator.miner_evidence.clone();
+        let battle_steps = self.orchestrator.battle_steps;
         
         self.orchestrator = TournamentOrchestrator::new(height + 1, miners, Hash256::zero());
         self.orchestrator.miner_evidence = old_evidence;
+        self.orchestrator.battle_steps = battle_steps;
+    }
+    
+    /// Current (positive, negative) evidence totals for each agent
+    fn evidence_snapshot(&self) -> HashMap<PublicKey, (f64, f64)> {
+        self.agents
+            .iter()
+            .map(|agent| {
+                let pk = agent.public_key();
+                let totals = self.orchestrator.miner_evidence
+                    .get(&pk)
+                    .map_or((0.0, 0.0), |c| (c.r, c.s));
+                (pk, totals)
+            })
+            .collect()
+    }
+    
+    /// Compute each agent's rewards and reputation change for the epoch
+    fn agent_outcomes(
+        &self,
+        height: u64,
+        winner: Option<PublicKey>,
+        unopened: &[PublicKey],
+        evidence_before: &HashMap<PublicKey, (f64, f64)>,
+    ) -> HashMap<PublicKey, AgentOutcome> {
+        let participants = self.agents.len() - unopened.len();
+        let distribution = RewardDistribution::new(calculate_block_reward(height), participants);
+        let evidence_after = self.evidence_snapshot();
+        let no_evidence = EvidenceCounters::new();
+        
+        self.agents
+            .iter()
+            .map(|agent| {
+                let pk = agent.public_key();
+                
+                // Rewards are only paid when a block was produced
+                let mut rewards = 0;
+                if winner.is_some() && !unopened.contains(&pk) {
+                    let wins = self.orchestrator.tournament.matches
+                        .iter()
+                        .filter(|m| m.winner == pk)
+                        .count() as u32;
+                    rewards += distribution.participant_payout(wins);
+                    if winner == Some(pk) {
+                        rewards += distribution.winner_amount();
+                    }
+                }
+                
+                let (r_before, s_before) = evidence_before.get(&pk).copied().unwrap_or((0.0, 0.0));
+                let (r_after, s_after) = evidence_after.get(&pk).copied().unwrap_or((0.0, 0.0));
+                let counters = self.orchestrator.miner_evidence.get(&pk).unwrap_or(&no_evidence);
+                
+                let outcome = AgentOutcome {
+                    rewards,
+                    evidence_gained: r_after - r_before,
+                    evidence_lost: s_after - s_before,
+                    trust_score: TrustScore::from_evidence(counters, &self.orchestrator.ebsl_params).value(),
+                };
+                (pk, outcome)
+            })
+            .collect()
     }
 }
 
@@ -308,21 +581,27 @@ mod tests {
 
     #[test]
     fn test_simulation_scenario() {
-        // Create a mix of agents
+        // Create a mix of agents; a Heavyweight only expects to beat a
+        // lighter opponent, so one withholder always reveals and one never does
+        let mut rng = StdRng::seed_from_u64(42);
+        let withholder = WithholdingMiner::from_rng(&mut rng, GliderPattern::Heavyweight);
+        let withholder_key = withholder.public_key();
+        let revealer = WithholdingMiner::from_rng(&mut rng, GliderPattern::Standard);
+        let revealer_key = revealer.public_key();
         let agents: Vec<Box<dyn MinerAgent>> = vec![
-            Box::new(HonestMiner::new()),
-            Box::new(HonestMiner::new()),
-            Box::new(TieFarmer::new()),
-            Box::new(ChaosSpammer::new()),
-            Box::new(FlakyGriefer::new(0.5)), // 50% failure rate
+            Box::new(HonestMiner::from_rng(&mut rng)),
+            Box::new(HonestMiner::from_rng(&mut rng)),
+            Box::new(TieFarmer::from_rng(&mut rng)),
+            Box::new(ChaosSpammer::from_rng(&mut rng)),
+            Box::new(FlakyGriefer::from_rng(&mut rng, 0.5)), // 50% failure rate
+            Box::new(withholder),
+            Box::new(revealer),
         ];
         
-        let mut engine = SimulationEngine::new(agents);
+        let mut engine = SimulationEngine::new(agents, 42);
         
         // Run 2 epochs (reduced for test speed)
-        for _ in 0..2 {
-            engine.run_epoch();
-        }
+        engine.run_epochs(2);
         
         // Verify history
         assert_eq!(engine.history.len(), 2);
@@ -333,9 +612,180 @@ mod tests {
                 result.height, result.winner, result.mii_usage, result.avg_rounds);
         }
         
+        // Every withheld reveal is recorded as negative evidence
+        for result in &engine.history {
+            assert!(result.no_reveals.contains(&withholder_key));
+            let outcome = &result.agent_outcomes[&withholder_key];
+            assert!(outcome.evidence_lost > 0.0);
+            assert_eq!(outcome.rewards, 0);
+            
+            assert!(!result.no_reveals.contains(&revealer_key));
+            assert_eq!(result.agent_outcomes[&revealer_key].evidence_lost, 0.0);
+        }
+        let expected_s = engine.history.len() as f64 * EvidenceType::MissedReveal.weight();
+        let counters = &engine.orchestrator.miner_evidence[&withholder_key];
+        assert_eq!(counters.s, expected_s);
+        assert_eq!(engine.orchestrator.miner_evidence[&revealer_key].s, 0.0);
+        
         // Verify evidence accumulation
         // Honest miners should have positive reputation (if they won or participated well)
         // We can't easily check internal state of orchestrator here without exposing it more,
         // but we can check that the engine ran without panicking.
     }
+
+    #[test]
+    fn test_withhold_decision_follows_parameters() {
+        let glider = |pattern| Glider::new(pattern, Position::new(64, 64));
+        let mut rng = StdRng::seed_from_u64(5);
+        
+        // Even matchups are withheld by default, lopsided ones revealed
+        let miner = WithholdingMiner::from_rng(&mut rng, GliderPattern::Heavyweight);
+        assert!(!miner.would_win(&glider(GliderPattern::Heavyweight)));
+        assert!(!miner.would_win(&glider(GliderPattern::Standard)));
+        let miner = WithholdingMiner::from_rng(&mut rng, GliderPattern::Standard);
+        assert!(miner.would_win(&glider(GliderPattern::Heavyweight)));
+        
+        // A zero margin reveals on a predicted tie
+        let miner = WithholdingMiner::from_rng(&mut rng, GliderPattern::Heavyweight).with_min_margin(0);
+        assert!(miner.would_win(&glider(GliderPattern::Heavyweight)));
+        
+        // A Lightweight leads a Standard, but not by more than the margin
+        let miner = WithholdingMiner::from_rng(&mut rng, GliderPattern::Standard)
+            .with_pattern(GliderPattern::Lightweight);
+        assert!(miner.would_win(&glider(GliderPattern::Lightweight)));
+        let miner = miner.with_min_margin(1_000);
+        assert!(!miner.would_win(&glider(GliderPattern::Lightweight)));
+        
+        // A Heavyweight outruns its region long before a Standard glider does
+        let miner = WithholdingMiner::from_rng(&mut rng, GliderPattern::Standard).with_lookahead_steps(200);
+        assert!(!miner.would_win(&glider(GliderPattern::Heavyweight)));
+    }
+
+    /// Commits to one glider, then reveals a different one
+    struct TamperingMiner {
+        sk: SecretKey,
+        current_nonce: Vec<u8>,
+    }
+
+    impl MinerAgent for TamperingMiner {
+        fn public_key(&self) -> PublicKey {
+            self.sk.public_key()
+        }
+
+        fn generate_commitment(&mut self, height: u64) -> GliderCommitment {
+            let glider = Glider::new(GliderPattern::Standard, Position::new(64, 64));
+            self.current_nonce = commitment_nonce(&self.sk, height);
+            GliderCommitment::new(&glider, &self.current_nonce, &self.public_key(), height)
+        }
+
+        fn generate_reveal(&mut self, _height: u64, _rng: &mut dyn RngCore) -> Option<GliderReveal> {
+            Some(GliderReveal {
+                glider: Glider::new(GliderPattern::Heavyweight, Position::new(64, 64)),
+                nonce: self.current_nonce.clone(),
+                miner: self.public_key(),
+            })
+        }
+
+        fn name(&self) -> &str {
+            "TamperingMiner"
+        }
+    }
+
+    #[test]
+    fn test_tampered_reveal_is_rejected() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let honest_a = HonestMiner::from_rng(&mut rng);
+        let honest_b = HonestMiner::from_rng(&mut rng);
+        let tamperer = TamperingMiner {
+            sk: secret_key_from_rng(&mut rng),
+            current_nonce: Vec::new(),
+        };
+        let honest_keys = [honest_a.public_key(), honest_b.public_key()];
+        let tamperer_key = tamperer.public_key();
+        
+        let agents: Vec<Box<dyn MinerAgent>> = vec![
+            Box::new(honest_a),
+            Box::new(honest_b),
+            Box::new(tamperer),
+        ];
+        let mut engine = SimulationEngine::new(agents, 3);
+        engine.run_epoch();
+        
+        let result = &engine.history[0];
+        assert_eq!(result.rejected_reveals, vec![tamperer_key]);
+        assert!(result.no_reveals.is_empty());
+        assert_ne!(result.winner, Some(tamperer_key));
+        assert!(honest_keys.contains(&result.winner.unwrap()));
+        
+        let outcome = &result.agent_outcomes[&tamperer_key];
+        assert_eq!(outcome.rewards, 0);
+        assert_eq!(outcome.evidence_lost, EvidenceType::MissedReveal.weight());
+    }
+
+    /// Build the standard agent mix with keys derived from `seed`
+    fn seeded_agents(seed: u64) -> Vec<Box<dyn MinerAgent>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        vec![
+            Box::new(HonestMiner::from_rng(&mut rng)),
+            Box::new(TieFarmer::from_rng(&mut rng)),
+            Box::new(ChaosSpammer::from_rng(&mut rng)),
+            Box::new(FlakyGriefer::from_rng(&mut rng, 0.5)),
+        ]
+    }
+
+    #[test]
+    fn test_seeded_runs_are_reproducible() {
+        // Short battles keep three epochs per engine cheap
+        let mut first = SimulationEngine::new(seeded_agents(7), 7).with_battle_steps(20);
+        let mut second = SimulationEngine::new(seeded_agents(7), 7).with_battle_steps(20);
+        
+        first.run_epochs(3);
+        second.run_epochs(3);
+        
+        assert_eq!(first.history.len(), 3);
+        assert_eq!(first.history, second.history);
+    }
+
+    #[test]
+    fn test_agent_outcomes_track_reputation() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let honest_a = HonestMiner::from_rng(&mut rng);
+        let honest_b = HonestMiner::from_rng(&mut rng);
+        let griefer = FlakyGriefer::from_rng(&mut rng, 1.0); // Never reveals
+        let honest_keys = [honest_a.public_key(), honest_b.public_key()];
+        let griefer_key = griefer.public_key();
+        
+        let agents: Vec<Box<dyn MinerAgent>> = vec![
+            Box::new(honest_a),
+            Box::new(honest_b),
+            Box::new(griefer),
+        ];
+        let mut engine = SimulationEngine::new(agents, 11);
+        engine.run_epochs(3);
+        
+        let mut last_griefer_trust = TrustScore::from_evidence(
+            &EvidenceCounters::new(),
+            &engine.orchestrator.ebsl_params,
+        ).value();
+        
+        for result in &engine.history {
+            for pk in &honest_keys {
+                let outcome = &result.agent_outcomes[pk];
+                assert!(outcome.evidence_gained > 0.0);
+                assert_eq!(outcome.evidence_lost, 0.0);
+                assert!(outcome.rewards > 0);
+            }
+            
+            let griefer_outcome = &result.agent_outcomes[&griefer_key];
+            assert_eq!(griefer_outcome.rewards, 0);
+            assert!(griefer_outcome.evidence_lost > 0.0);
+            assert!(griefer_outcome.trust_score < last_griefer_trust);
+            last_griefer_trust = griefer_outcome.trust_score;
+        }
+        
+        let final_epoch = engine.history.last().unwrap();
+        for pk in &honest_keys {
+            assert!(final_epoch.agent_outcomes[pk].trust_score > last_griefer_trust);
+        }
+    }
 }
@@ -41,6 +41,18 @@ impl BondState {
             unlock_epoch: current_epoch + unbonding_period,
         };
     }
+
+    /// Whether the bond is barred from participating at `epoch`
+    ///
+    /// Unbonding (including temporary bans) lifts at `unlock_epoch`;
+    /// slashed bonds stay banned permanently.
+    pub fn is_banned_at(&self, epoch: u64) -> bool {
+        match self.status {
+            BondStatus::Active => false,
+            BondStatus::Unbonding { unlock_epoch } => epoch < unlock_epoch,
+            BondStatus::Slashed { .. } => true,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -73,4 +85,18 @@ mod tests {
             panic!("Expected unbonding status");
         }
     }
+
+    #[test]
+    fn test_is_banned_at() {
+        let mut bond = BondState::new(1000, 0);
+        assert!(!bond.is_banned_at(0));
+
+        bond.start_unbonding(10, 5);
+        assert!(bond.is_banned_at(10));
+        assert!(bond.is_banned_at(14));
+        assert!(!bond.is_banned_at(15));
+
+        bond.slash(100);
+        assert!(bond.is_banned_at(u64::MAX));
+    }
 }
@@ -11,16 +11,22 @@
 pub mod account;
 pub mod bonds;
 pub mod storage;
+pub mod tree;
 
 pub use account::{Account, AccountState};
 pub use bonds::{BondState, BondStatus};
 pub use storage::{StorageManager, PruningStats};
+pub use tree::StateTree;
 
-use bitcell_crypto::Hash256;
-use bitcell_ebsl::{Evidence, EvidenceType, EvidenceCounters, SlashingAction};
-use std::collections::HashMap;
+use bitcell_crypto::{Hash256, merkle::MerkleProof};
+use bitcell_ebsl::{EbslParams, Evidence, EvidenceCounters, SlashingAction, TrustScore};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+/// Format version written at the start of every exported state snapshot
+pub const SNAPSHOT_VERSION: u32 = 2;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, thiserror::Error)]
@@ -34,13 +40,49 @@ pub enum Error {
     #[error("Invalid bond")]
     InvalidBond,
 
+    #[error("Invalid nonce: expected {expected}, got {got}")]
+    InvalidNonce { expected: u64, got: u64 },
+
     #[error("Balance overflow")]
     BalanceOverflow,
 
+    #[error("Double spend: nullifier already used")]
+    DoubleSpend,
+
     #[error("Storage error: {0}")]
     StorageError(String),
 }
 
+/// A transaction as applied by [`StateManager::apply_block`]
+#[derive(Debug, Clone)]
+pub struct BlockTransaction {
+    pub from: [u8; 33],
+    pub to: [u8; 33],
+    pub amount: u64,
+    pub nonce: u64,
+    /// Nullifiers spent if the transfer applies
+    pub nullifiers: Vec<[u8; 32]>,
+    /// Charged to the sender, up to its balance, if the transaction fails
+    pub fee: u64,
+    /// Whether the transaction ran out of gas before it could execute
+    pub out_of_gas: bool,
+}
+
+/// Serialized body of a state snapshot (follows the 4-byte version header)
+///
+/// Keys are stored as `Vec<u8>` because serde does not implement
+/// (de)serialization for `[u8; 33]`.
+#[derive(Serialize, Deserialize)]
+struct StateSnapshot {
+    state_root: Hash256,
+    accounts: Vec<(Vec<u8>, Account)>,
+    bonds: Vec<(Vec<u8>, BondState)>,
+    evidence_counters: Vec<(Vec<u8>, EvidenceCounters)>,
+    /// Commits to `nullifiers`; see `StateManager::nullifier_root`
+    nullifier_root: Hash256,
+    nullifiers: Vec<[u8; 32]>,
+}
+
 /// Global state manager
 pub struct StateManager {
     /// Account states (in-memory cache)
@@ -54,6 +96,12 @@ pub struct StateManager {
     
     /// State root
     pub state_root: Hash256,
+
+    /// Incremental Merkle tree backing `state_root`
+    state_tree: StateTree,
+
+    /// Nullifiers (key images) of spent private notes
+    nullifiers: HashSet<[u8; 32]>,
     
     /// Optional persistent storage backend
     storage: Option<Arc<StorageManager>>,
@@ -66,6 +114,8 @@ impl StateManager {
             bonds: HashMap::new(),
             evidence_counters: HashMap::new(),
             state_root: Hash256::zero(),
+            state_tree: StateTree::new(),
+            nullifiers: HashSet::new(),
             storage: None,
         }
     }
@@ -77,11 +127,34 @@ impl StateManager {
             bonds: HashMap::new(),
             evidence_counters: HashMap::new(),
             state_root: Hash256::zero(),
+            state_tree: StateTree::new(),
+            nullifiers: HashSet::new(),
             storage: Some(storage),
         };
         
-        // Load existing state from storage if available
-        // This is a simplified version - production would iterate all accounts
+        // Load all persisted accounts so the state root matches the pre-restart root
+        if let Some(storage) = &manager.storage {
+            let accounts = storage.iter_accounts()
+                .map_err(Error::StorageError)?;
+            manager.accounts.extend(accounts);
+            let bonds = storage.load_bonds()
+                .map_err(Error::StorageError)?;
+            manager.bonds.extend(bonds);
+            let nullifiers = storage.load_nullifiers()
+                .map_err(Error::StorageError)?;
+            manager.nullifiers.extend(nullifiers);
+            let evidence = storage.load_evidence()
+                .map_err(Error::StorageError)?;
+            manager.evidence_counters.extend(evidence);
+        }
+
+        tracing::info!(
+            accounts = manager.accounts.len(),
+            bonds = manager.bonds.len(),
+            nullifiers = manager.nullifiers.len(),
+            "Loaded accounts from storage"
+        );
+
         manager.recompute_root();
         Ok(manager)
     }
@@ -140,7 +213,7 @@ impl StateManager {
             }
         }
         
-        self.recompute_root();
+        self.update_leaf(pubkey);
     }
 
     /// Get bond state (returns reference to cached value)
@@ -196,33 +269,105 @@ impl StateManager {
                 );
             }
         }
-        
-        self.recompute_root();
     }
 
-    /// Recompute state root using Merkle tree
-    fn recompute_root(&mut self) {
-        // Build Merkle tree from account data
-        let mut leaves = Vec::new();
-        
+    /// Write every cached account and bond through to storage and flush it
+    ///
+    /// Called on shutdown so nothing is left in RocksDB memtables. A no-op
+    /// without a storage backend.
+    pub fn flush(&self) -> Result<()> {
+        let storage = match &self.storage {
+            Some(storage) => storage,
+            None => return Ok(()),
+        };
         for (pubkey, account) in &self.accounts {
-            // Create leaf: hash(pubkey || balance || nonce)
-            let mut data = Vec::new();
-            data.extend_from_slice(pubkey);
-            data.extend_from_slice(&account.balance.to_le_bytes());
-            data.extend_from_slice(&account.nonce.to_le_bytes());
-            leaves.push(Hash256::hash(&data));
+            storage.store_account(pubkey, account).map_err(Error::StorageError)?;
         }
-        
-        // If no accounts, use zero hash
-        if leaves.is_empty() {
-            self.state_root = Hash256::zero();
-            return;
+        for (pubkey, bond) in &self.bonds {
+            storage.store_bond(pubkey, bond).map_err(Error::StorageError)?;
         }
-        
-        // Build Merkle tree and get root
-        let tree = bitcell_crypto::MerkleTree::new(leaves);
-        self.state_root = tree.root();
+        for (validator, counters) in &self.evidence_counters {
+            storage.store_evidence(validator, counters).map_err(Error::StorageError)?;
+        }
+        storage.flush().map_err(Error::StorageError)
+    }
+
+    /// Persist a cached account to storage, if a backend is configured
+    ///
+    /// Storage errors are logged but not propagated (eventual consistency model).
+    fn persist_account(&self, pubkey: &[u8; 33]) {
+        if let (Some(storage), Some(account)) = (&self.storage, self.accounts.get(pubkey)) {
+            if let Err(e) = storage.store_account(pubkey, account) {
+                tracing::error!(
+                    pubkey = %hex::encode(pubkey),
+                    error = %e,
+                    "Failed to persist account to storage. State may be inconsistent on restart."
+                );
+            }
+        }
+    }
+
+    /// Persist a cached bond to storage, if a backend is configured
+    ///
+    /// Storage errors are logged but not propagated (eventual consistency model).
+    fn persist_bond(&self, pubkey: &[u8; 33]) {
+        if let (Some(storage), Some(bond)) = (&self.storage, self.bonds.get(pubkey)) {
+            if let Err(e) = storage.store_bond(pubkey, bond) {
+                tracing::error!(
+                    pubkey = %hex::encode(pubkey),
+                    error = %e,
+                    "Failed to persist bond to storage. State may be inconsistent on restart."
+                );
+            }
+        }
+    }
+
+    /// Persist a validator's evidence counters, if a backend is configured
+    ///
+    /// Storage errors are logged but not propagated (eventual consistency model).
+    fn persist_evidence(&self, validator: &[u8; 33]) {
+        if let (Some(storage), Some(counters)) = (&self.storage, self.evidence_counters.get(validator)) {
+            if let Err(e) = storage.store_evidence(validator, counters) {
+                tracing::error!(
+                    validator = %hex::encode(validator),
+                    error = %e,
+                    "Failed to persist evidence to storage. State may be inconsistent on restart."
+                );
+            }
+        }
+    }
+
+    /// Compute the Merkle leaf for an account: hash(pubkey || balance || nonce)
+    fn account_leaf(pubkey: &[u8; 33], account: &Account) -> Hash256 {
+        bitcell_crypto::account_leaf(pubkey, account.balance, account.nonce)
+    }
+
+    /// Merkle inclusion proof for an account against the current state root
+    ///
+    /// Returns the account together with the proof so a light client can
+    /// recompute the leaf. `None` if the account is not in the state tree.
+    pub fn prove_account(&self, pubkey: &[u8; 33]) -> Option<(Account, MerkleProof)> {
+        let account = self.accounts.get(pubkey)?.clone();
+        let proof = self.state_tree.prove(pubkey)?;
+        Some((account, proof))
+    }
+
+    /// Refresh a single account's leaf and update the state root incrementally
+    fn update_leaf(&mut self, pubkey: [u8; 33]) {
+        if let Some(account) = self.accounts.get(&pubkey) {
+            let leaf = Self::account_leaf(&pubkey, account);
+            self.state_tree.update(pubkey, leaf);
+        }
+        self.state_root = self.state_tree.root();
+    }
+
+    /// Rebuild the state tree from scratch using all cached accounts
+    fn recompute_root(&mut self) {
+        self.state_tree.clear();
+        for (pubkey, account) in &self.accounts {
+            self.state_tree.update(*pubkey, Self::account_leaf(pubkey, account));
+        }
+        self.state_root = self.state_tree.root();
     }
     
     /// Apply a transaction (returns updated state root)
@@ -239,7 +384,10 @@ impl StateManager {
         
         // Verify nonce
         if from_account.nonce != nonce {
-            return Err(Error::InvalidBond); // Reusing error type
+            return Err(Error::InvalidNonce {
+                expected: from_account.nonce,
+                got: nonce,
+            });
         }
         
         // Verify balance
@@ -251,20 +399,224 @@ impl StateManager {
         let mut updated_from = from_account.clone();
         updated_from.balance -= amount;
         updated_from.nonce += 1;
-        self.accounts.insert(from, updated_from);
         
-        // Update receiver (create if doesn't exist)
-        let mut to_account = self.accounts.get(&to)
-            .cloned()
-            .unwrap_or(Account { balance: 0, nonce: 0 });
-        to_account.balance += amount;
+        // Update receiver (create if doesn't exist). A self-transfer must see
+        // the debited sender, otherwise the credit would be applied to stale data.
+        let mut to_account = if to == from {
+            updated_from.clone()
+        } else {
+            self.accounts.get(&to)
+                .cloned()
+                .unwrap_or(Account { balance: 0, nonce: 0 })
+        };
+        to_account.balance = to_account.balance.checked_add(amount)
+            .ok_or(Error::BalanceOverflow)?;
+        
+        // Only commit once both sides are known to be valid
+        self.accounts.insert(from, updated_from);
         self.accounts.insert(to, to_account);
         
-        // Recompute and return new state root
-        self.recompute_root();
+        self.persist_account(&from);
+        self.persist_account(&to);
+        
+        // Update the touched leaves and return new state root
+        self.update_leaf(from);
+        self.update_leaf(to);
+        Ok(self.state_root)
+    }
+
+    /// Check that the nullifiers of a private spend are unspent and distinct
+    ///
+    /// Returns `Error::DoubleSpend` if any was recorded before or appears
+    /// twice in `nullifiers`. Nothing is recorded; see
+    /// [`StateManager::record_nullifiers`].
+    pub fn check_nullifiers(&self, nullifiers: &[[u8; 32]]) -> Result<()> {
+        let mut seen = HashSet::with_capacity(nullifiers.len());
+        if nullifiers.iter().any(|n| self.nullifiers.contains(n) || !seen.insert(n)) {
+            return Err(Error::DoubleSpend);
+        }
+        Ok(())
+    }
+
+    /// Record the nullifiers of a private spend, rejecting any already seen
+    ///
+    /// The nullifiers are checked with [`StateManager::check_nullifiers`]
+    /// first and either all recorded or none. Private transaction application
+    /// must call this once the spend has been applied.
+    ///
+    /// Unlike accounts, nullifiers are written through to storage before this
+    /// returns: losing one on restart would re-open the double spend. If the
+    /// write fails nothing is recorded and the error is returned.
+    pub fn record_nullifiers(&mut self, nullifiers: &[[u8; 32]]) -> Result<()> {
+        self.check_nullifiers(nullifiers)?;
+        if let Some(storage) = &self.storage {
+            storage.store_nullifiers(nullifiers).map_err(Error::StorageError)?;
+        }
+        self.nullifiers.extend(nullifiers.iter().copied());
+        Ok(())
+    }
+
+    /// Record a single spent nullifier; see [`StateManager::record_nullifiers`]
+    pub fn check_and_record_nullifier(&mut self, nullifier: [u8; 32]) -> Result<()> {
+        self.record_nullifiers(&[nullifier])
+    }
+
+    /// Commitment to the nullifier set: hash of all nullifiers in sorted order
+    pub fn nullifier_root(&self) -> Hash256 {
+        Self::hash_nullifiers(&self.sorted_nullifiers())
+    }
+
+    fn sorted_nullifiers(&self) -> Vec<[u8; 32]> {
+        let mut nullifiers: Vec<_> = self.nullifiers.iter().copied().collect();
+        nullifiers.sort_unstable();
+        nullifiers
+    }
+
+    fn hash_nullifiers(sorted: &[[u8; 32]]) -> Hash256 {
+        let items: Vec<&[u8]> = sorted.iter().map(|n| n.as_slice()).collect();
+        Hash256::hash_multiple(&items)
+    }
+
+    /// Whether `nullifier` has already been spent
+    pub fn is_nullifier_spent(&self, nullifier: &[u8; 32]) -> bool {
+        self.nullifiers.contains(nullifier)
+    }
+
+    /// Apply a batch of transactions atomically (returns updated state root)
+    ///
+    /// Each `(from, to, amount, nonce)` entry is validated and applied against a
+    /// scratch copy of the touched accounts, so later transactions in the batch
+    /// see the effects of earlier ones. If any transaction fails, the error is
+    /// returned and `self.accounts` is left untouched. The state root is
+    /// only updated once the whole batch has been applied.
+    pub fn apply_transactions(
+        &mut self,
+        txs: &[([u8; 33], [u8; 33], u64, u64)],
+    ) -> Result<Hash256> {
+        let mut scratch: HashMap<[u8; 33], Account> = HashMap::new();
+        for &(from, to, amount, nonce) in txs {
+            self.scratch_transfer(&mut scratch, from, to, amount, nonce)?;
+        }
+
+        // Every transaction succeeded - commit the scratch state
+        self.commit_scratch(scratch);
         Ok(self.state_root)
     }
 
+    /// Apply a block's reward and transactions as one unit
+    ///
+    /// Transactions apply in order against a scratch copy of the touched
+    /// accounts, so later ones see the effects of earlier ones. One that runs
+    /// out of gas, spends a nullifier already spent (before or earlier in the
+    /// block), or whose transfer is invalid fails: its sender is charged
+    /// `fee` and its nonce still advances, so the signed transaction cannot be
+    /// replayed. Its nullifiers stay unspent.
+    ///
+    /// The spent nullifiers are written to storage before anything is
+    /// committed. If that write fails the error is returned and the state is
+    /// left as it was. Returns whether each transaction applied.
+    pub fn apply_block(
+        &mut self,
+        reward: Option<([u8; 33], u64)>,
+        txs: &[BlockTransaction],
+    ) -> Result<Vec<bool>> {
+        let mut scratch: HashMap<[u8; 33], Account> = HashMap::new();
+        if let Some((proposer, amount)) = reward {
+            let mut account = self.accounts.get(&proposer)
+                .cloned()
+                .unwrap_or(Account { balance: 0, nonce: 0 });
+            account.balance = account.balance.checked_add(amount)
+                .ok_or(Error::BalanceOverflow)?;
+            scratch.insert(proposer, account);
+        }
+
+        let mut spent = Vec::new();
+        let mut spent_set = HashSet::new();
+        let mut outcomes = Vec::with_capacity(txs.len());
+        for tx in txs {
+            let unspent = self.check_nullifiers(&tx.nullifiers).is_ok()
+                && tx.nullifiers.iter().all(|n| !spent_set.contains(n));
+            let applied = !tx.out_of_gas
+                && unspent
+                && self.scratch_transfer(&mut scratch, tx.from, tx.to, tx.amount, tx.nonce).is_ok();
+
+            if applied {
+                spent.extend_from_slice(&tx.nullifiers);
+                spent_set.extend(tx.nullifiers.iter().copied());
+            } else if let Some(mut account) = scratch.get(&tx.from)
+                .or_else(|| self.accounts.get(&tx.from))
+                .filter(|account| account.nonce == tx.nonce)
+                .cloned()
+            {
+                account.balance = account.balance.saturating_sub(tx.fee);
+                account.nonce += 1;
+                scratch.insert(tx.from, account);
+            }
+            outcomes.push(applied);
+        }
+
+        if !spent.is_empty() {
+            if let Some(storage) = &self.storage {
+                storage.store_nullifiers(&spent).map_err(Error::StorageError)?;
+            }
+            self.nullifiers.extend(spent);
+        }
+        self.commit_scratch(scratch);
+        Ok(outcomes)
+    }
+
+    /// Apply a transfer to `scratch`, reading accounts it does not hold yet
+    /// from committed state
+    ///
+    /// On error `scratch` is left unchanged.
+    fn scratch_transfer(
+        &self,
+        scratch: &mut HashMap<[u8; 33], Account>,
+        from: [u8; 33],
+        to: [u8; 33],
+        amount: u64,
+        nonce: u64,
+    ) -> Result<()> {
+        let account = |pubkey: &[u8; 33]| scratch.get(pubkey).or_else(|| self.accounts.get(pubkey)).cloned();
+
+        let mut from_account = account(&from).ok_or(Error::AccountNotFound)?;
+        if from_account.nonce != nonce {
+            return Err(Error::InvalidNonce {
+                expected: from_account.nonce,
+                got: nonce,
+            });
+        }
+        if from_account.balance < amount {
+            return Err(Error::InsufficientBalance);
+        }
+        from_account.balance -= amount;
+        from_account.nonce += 1;
+
+        // A self-transfer must see the debited sender
+        let mut to_account = if to == from {
+            from_account.clone()
+        } else {
+            account(&to).unwrap_or(Account { balance: 0, nonce: 0 })
+        };
+        to_account.balance = to_account.balance.checked_add(amount)
+            .ok_or(Error::BalanceOverflow)?;
+
+        scratch.insert(from, from_account);
+        scratch.insert(to, to_account);
+        Ok(())
+    }
+
+    /// Commit scratch accounts, persisting them and updating their leaves
+    fn commit_scratch(&mut self, scratch: HashMap<[u8; 33], Account>) {
+        let touched: Vec<[u8; 33]> = scratch.keys().copied().collect();
+        self.accounts.extend(scratch);
+
+        for pubkey in touched {
+            self.persist_account(&pubkey);
+            self.update_leaf(pubkey);
+        }
+    }
+
     /// Credit an account (minting/coinbase)
     /// Returns the new state root on success, or an error if overflow would occur.
     /// Note: This method should only be called by blockchain core during block processing.
@@ -284,21 +636,146 @@ impl StateManager {
         );
         
         self.accounts.insert(pubkey, account);
+        self.persist_account(&pubkey);
         
-        self.recompute_root();
+        self.update_leaf(pubkey);
+        Ok(self.state_root)
+    }
+    
+    /// Export the full state as a versioned snapshot for fast sync
+    ///
+    /// Layout: `version (u32 BE) || bincode(StateSnapshot)`. Entries are sorted
+    /// by public key so identical states always produce identical bytes.
+    pub fn export_snapshot(&self) -> Vec<u8> {
+        fn sorted<V: Clone>(map: &HashMap<[u8; 33], V>) -> Vec<(Vec<u8>, V)> {
+            let mut entries: Vec<_> = map.iter()
+                .map(|(k, v)| (k.to_vec(), v.clone()))
+                .collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            entries
+        }
+
+        let snapshot = StateSnapshot {
+            state_root: self.state_root,
+            accounts: sorted(&self.accounts),
+            bonds: sorted(&self.bonds),
+            evidence_counters: sorted(&self.evidence_counters),
+            nullifier_root: self.nullifier_root(),
+            nullifiers: self.sorted_nullifiers(),
+        };
+
+        let mut data = SNAPSHOT_VERSION.to_be_bytes().to_vec();
+        // Serializing plain in-memory structs into a Vec cannot fail
+        data.extend(bincode::serialize(&snapshot).expect("state snapshot serialization"));
+        data
+    }
+
+    /// Replace the current state with a snapshot produced by `export_snapshot`
+    ///
+    /// The state root is recomputed from the imported accounts and must match
+    /// the root embedded in the snapshot; otherwise `Error::StorageError` is
+    /// returned and the current state is left untouched. If storage is
+    /// configured, its accounts, bonds, evidence and nullifiers are replaced
+    /// by the snapshot's in one atomic write before the import takes effect.
+    pub fn import_snapshot(&mut self, data: &[u8]) -> Result<Hash256> {
+        if data.len() < 4 {
+            return Err(Error::StorageError("Snapshot too short".to_string()));
+        }
+
+        let version = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+        if version != SNAPSHOT_VERSION {
+            return Err(Error::StorageError(format!(
+                "Unsupported snapshot version: expected {}, got {}",
+                SNAPSHOT_VERSION, version
+            )));
+        }
+
+        let snapshot: StateSnapshot = bincode::deserialize(&data[4..])
+            .map_err(|e| Error::StorageError(format!("Invalid snapshot data: {}", e)))?;
+
+        fn into_map<V>(entries: Vec<(Vec<u8>, V)>) -> Result<HashMap<[u8; 33], V>> {
+            entries.into_iter()
+                .map(|(k, v)| {
+                    let key: [u8; 33] = k.as_slice().try_into().map_err(|_| {
+                        Error::StorageError(format!("Invalid snapshot key length: {}", k.len()))
+                    })?;
+                    Ok((key, v))
+                })
+                .collect()
+        }
+
+        let accounts = into_map(snapshot.accounts)?;
+        let bonds = into_map(snapshot.bonds)?;
+        let evidence_counters = into_map(snapshot.evidence_counters)?;
+
+        // Verify the embedded root before touching any live state
+        let mut state_tree = StateTree::new();
+        for (pubkey, account) in &accounts {
+            state_tree.update(*pubkey, Self::account_leaf(pubkey, account));
+        }
+        if state_tree.root() != snapshot.state_root {
+            return Err(Error::StorageError(format!(
+                "Snapshot state root mismatch: expected {}, computed {}",
+                snapshot.state_root,
+                state_tree.root()
+            )));
+        }
+        let mut sorted_nullifiers = snapshot.nullifiers;
+        sorted_nullifiers.sort_unstable();
+        sorted_nullifiers.dedup();
+        let nullifier_root = Self::hash_nullifiers(&sorted_nullifiers);
+        if nullifier_root != snapshot.nullifier_root {
+            return Err(Error::StorageError(format!(
+                "Snapshot nullifier root mismatch: expected {}, computed {}",
+                snapshot.nullifier_root,
+                nullifier_root
+            )));
+        }
+
+        // Storage must match the snapshot before the import takes effect: a
+        // restart must neither forget its spends nor resurrect stale entries
+        if let Some(storage) = &self.storage {
+            storage.replace_state(&accounts, &bonds, &evidence_counters, &sorted_nullifiers)
+                .map_err(Error::StorageError)?;
+        }
+
+        self.accounts = accounts;
+        self.bonds = bonds;
+        self.evidence_counters = evidence_counters;
+        self.nullifiers = sorted_nullifiers.into_iter().collect();
+        self.state_tree = state_tree;
+        self.state_root = snapshot.state_root;
+
+        tracing::info!(
+            accounts = self.accounts.len(),
+            bonds = self.bonds.len(),
+            nullifiers = self.nullifiers.len(),
+            state_root = %self.state_root,
+            "Imported state snapshot"
+        );
+
         Ok(self.state_root)
     }
     
     /// Submit evidence for a validator (used by finality gadget for equivocation)
-    pub fn submit_evidence(&mut self, validator: [u8; 33], evidence: Evidence) -> Result<()> {
+    ///
+    /// Evidence is weighted according to `params`.
+    pub fn submit_evidence(
+        &mut self,
+        validator: [u8; 33],
+        evidence: Evidence,
+        params: &EbslParams,
+    ) -> Result<()> {
         let counters = self.evidence_counters.entry(validator)
             .or_insert_with(EvidenceCounters::new);
+        let evidence_type = evidence.evidence_type;
         
-        counters.add_evidence(evidence);
+        counters.add_evidence_with_params(evidence, params);
+        self.persist_evidence(&validator);
         
         tracing::info!(
             validator = %hex::encode(&validator),
-            evidence_type = ?evidence.evidence_type,
+            evidence_type = ?evidence_type,
             "Evidence submitted"
         );
         
@@ -306,7 +783,15 @@ impl StateManager {
     }
     
     /// Apply slashing to a validator based on slashing action
-    pub fn apply_slashing(&mut self, validator: [u8; 33], action: SlashingAction) -> Result<()> {
+    ///
+    /// `current_epoch` anchors time-based actions: a `TemporaryBan(n)` keeps the
+    /// validator unbonded until `current_epoch + n`.
+    pub fn apply_slashing(
+        &mut self,
+        validator: [u8; 33],
+        action: SlashingAction,
+        current_epoch: u64,
+    ) -> Result<()> {
         match action {
             SlashingAction::None => {
                 // No action needed
@@ -329,6 +814,7 @@ impl StateManager {
                         remaining_bond = bond.amount,
                         "Partial slashing applied"
                     );
+                    self.persist_bond(&validator);
                 }
                 Ok(())
             }
@@ -338,13 +824,14 @@ impl StateManager {
                 if let Some(bond) = self.bonds.get_mut(&validator) {
                     let slashed_amount = bond.amount;
                     bond.amount = 0;
-                    bond.status = BondStatus::Slashed;
+                    bond.status = BondStatus::Slashed { amount: slashed_amount };
                     
                     tracing::error!(
                         validator = %hex::encode(&validator),
                         slashed_amount = slashed_amount,
                         "Full slashing applied with permanent ban"
                     );
+                    self.persist_bond(&validator);
                 }
                 Ok(())
             }
@@ -352,13 +839,16 @@ impl StateManager {
             SlashingAction::TemporaryBan(epochs) => {
                 // Mark as temporarily banned
                 if let Some(bond) = self.bonds.get_mut(&validator) {
-                    bond.status = BondStatus::Unbonding { unlock_epoch: epochs };
+                    let unlock_epoch = current_epoch.saturating_add(epochs);
+                    bond.status = BondStatus::Unbonding { unlock_epoch };
                     
                     tracing::warn!(
                         validator = %hex::encode(&validator),
                         ban_epochs = epochs,
+                        unlock_epoch = unlock_epoch,
                         "Temporary ban applied"
                     );
+                    self.persist_bond(&validator);
                 }
                 Ok(())
             }
@@ -370,13 +860,51 @@ impl StateManager {
         self.evidence_counters.get(validator)
     }
     
+    /// Apply one epoch of EBSL decay to every validator's evidence
+    ///
+    /// Call once per epoch boundary. Positive and negative evidence decay at
+    /// `params.pos_decay` and `params.neg_decay` respectively.
+    pub fn decay_all_evidence(&mut self, params: &EbslParams) {
+        for counters in self.evidence_counters.values_mut() {
+            counters.decay_epoch(params);
+        }
+        if let Some(storage) = &self.storage {
+            for (validator, counters) in &self.evidence_counters {
+                if let Err(e) = storage.store_evidence(validator, counters) {
+                    tracing::error!(
+                        validator = %hex::encode(validator),
+                        error = %e,
+                        "Failed to persist evidence to storage. State may be inconsistent on restart."
+                    );
+                }
+            }
+        }
+    }
+    
+    /// Bonded miners whose EBSL trust meets `t_min`, in ascending key order
+    ///
+    /// This is the candidate set for tournament assembly: miners without an
+    /// active bond, or with trust below `params.t_min`, are excluded.
+    pub fn eligible_miners(&self, params: &EbslParams) -> Vec<[u8; 33]> {
+        let mut miners: Vec<[u8; 33]> = self.bonds
+            .iter()
+            .filter(|(_, bond)| bond.is_active())
+            .map(|(miner, _)| *miner)
+            .filter(|miner| {
+                TrustScore::new(self.calculate_trust_score(miner, params)).is_eligible(params)
+            })
+            .collect();
+        miners.sort();
+        miners
+    }
+    
     /// Calculate trust score for a validator using EBSL
-    pub fn calculate_trust_score(&self, validator: &[u8; 33]) -> f64 {
+    pub fn calculate_trust_score(&self, validator: &[u8; 33], params: &EbslParams) -> f64 {
+        let no_evidence = EvidenceCounters::new();
         let counters = self.evidence_counters.get(validator)
-            .unwrap_or(&EvidenceCounters::new());
+            .unwrap_or(&no_evidence);
         
-        let params = bitcell_ebsl::EbslParams::default();
-        let trust = bitcell_ebsl::trust::TrustScore::from_evidence(counters, &params);
+        let trust = TrustScore::from_evidence(counters, params);
         
         trust.value()
     }
@@ -391,6 +919,7 @@ impl Default for StateManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bitcell_ebsl::EvidenceType;
     use tempfile::TempDir;
 
     #[test]
@@ -461,6 +990,271 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_state_root_restored_after_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let alice = [1u8; 33];
+        let bob = [2u8; 33];
+
+        let root_before = {
+            let storage = Arc::new(StorageManager::new(temp_dir.path()).unwrap());
+            let mut sm = StateManager::with_storage(storage).unwrap();
+            for i in 0u8..8 {
+                sm.update_account([i + 10; 33], Account { balance: 100 * i as u64, nonce: 0 });
+            }
+            sm.credit_account(alice, 5000).unwrap();
+            sm.apply_transaction(alice, bob, 1200, 0).unwrap();
+            assert_ne!(sm.state_root, Hash256::zero());
+            sm.state_root
+        };
+
+        // Storage and manager are dropped; reopen from disk
+        let storage = Arc::new(StorageManager::new(temp_dir.path()).unwrap());
+        let sm = StateManager::with_storage(storage).unwrap();
+
+        assert_eq!(sm.accounts.len(), 10);
+        assert_eq!(sm.get_account(&bob).unwrap().balance, 1200);
+        assert_eq!(sm.state_root, root_before);
+    }
+
+    #[test]
+    fn test_temporary_ban_is_epoch_relative() {
+        let mut sm = StateManager::new();
+        let validator = [7u8; 33];
+        sm.update_bond(validator, BondState::new(1000, 0));
+
+        sm.apply_slashing(validator, SlashingAction::TemporaryBan(5), 10).unwrap();
+
+        let bond = sm.get_bond(&validator).unwrap();
+        assert_eq!(bond.status, BondStatus::Unbonding { unlock_epoch: 15 });
+        assert!(bond.is_banned_at(10));
+        assert!(bond.is_banned_at(14));
+        assert!(!bond.is_banned_at(15));
+    }
+
+    #[test]
+    fn test_slashing_survives_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let banned = [7u8; 33];
+        let slashed = [8u8; 33];
+        {
+            let storage = Arc::new(StorageManager::new(temp_dir.path()).unwrap());
+            let mut sm = StateManager::with_storage(storage).unwrap();
+            sm.update_bond(banned, BondState::new(1000, 0));
+            sm.update_bond(slashed, BondState::new(1000, 0));
+            sm.apply_slashing(banned, SlashingAction::TemporaryBan(5), 10).unwrap();
+            sm.apply_slashing(slashed, SlashingAction::Partial(25), 10).unwrap();
+            // Dropped without flush(), as after an unclean shutdown
+        }
+
+        let storage = Arc::new(StorageManager::new(temp_dir.path()).unwrap());
+        let sm = StateManager::with_storage(storage).unwrap();
+        assert_eq!(sm.get_bond(&banned).unwrap().status, BondStatus::Unbonding { unlock_epoch: 15 });
+        assert_eq!(sm.get_bond(&slashed).unwrap().amount, 750);
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut sm = StateManager::new();
+        for i in 0..300u32 {
+            let mut pubkey = [0u8; 33];
+            pubkey[..4].copy_from_slice(&i.to_be_bytes());
+            sm.update_account(pubkey, Account { balance: 1000 + i as u64, nonce: i as u64 % 7 });
+        }
+        sm.update_bond([9u8; 33], BondState::new(5000, 3));
+        sm.submit_evidence([9u8; 33], Evidence::new(EvidenceType::GoodBlock, 1, 10), &EbslParams::default()).unwrap();
+        sm.check_and_record_nullifier([5u8; 32]).unwrap();
+
+        let snapshot = sm.export_snapshot();
+        assert_eq!(&snapshot[..4], &SNAPSHOT_VERSION.to_be_bytes());
+
+        let mut restored = StateManager::new();
+        let root = restored.import_snapshot(&snapshot).unwrap();
+
+        assert_eq!(root, sm.state_root);
+        assert_eq!(restored.state_root, sm.state_root);
+        assert_eq!(restored.accounts.len(), 300);
+        assert_eq!(restored.get_bond(&[9u8; 33]).unwrap().amount, 5000);
+        assert_eq!(restored.get_evidence_counters(&[9u8; 33]).unwrap().r, 1.0);
+        assert!(restored.is_nullifier_spent(&[5u8; 32]));
+
+        // Exporting the restored state yields identical bytes
+        assert_eq!(restored.export_snapshot(), snapshot);
+    }
+
+    #[test]
+    fn test_submit_evidence_uses_configured_weights() {
+        let mut sm = StateManager::new();
+        let validator = [3u8; 33];
+        let params = EbslParams::default().with_weight(EvidenceType::Equivocation, 50.0);
+
+        sm.submit_evidence(validator, Evidence::new(EvidenceType::Equivocation, 0, 0), &params).unwrap();
+
+        assert_eq!(sm.get_evidence_counters(&validator).unwrap().s, 50.0);
+    }
+
+    #[test]
+    fn test_trust_decays_toward_prior() {
+        let mut sm = StateManager::new();
+        let miner = [7u8; 33];
+        for epoch in 0..20 {
+            sm.submit_evidence(miner, Evidence::new(EvidenceType::GoodBlock, epoch, epoch * 10), &EbslParams::default()).unwrap();
+        }
+
+        let offender = [8u8; 33];
+        sm.submit_evidence(offender, Evidence::new(EvidenceType::MissedCommitment, 0, 0), &EbslParams::default()).unwrap();
+
+        let params = EbslParams::default();
+        let mut trust = sm.calculate_trust_score(&miner, &params);
+        assert!(trust > params.alpha);
+
+        // Each simulated epoch pulls trust monotonically back toward the prior
+        for _ in 0..500 {
+            sm.decay_all_evidence(&params);
+            let next = sm.calculate_trust_score(&miner, &params);
+            assert!(next < trust && next > params.alpha);
+            trust = next;
+        }
+        assert!(trust - params.alpha < 0.1);
+
+        // Positive and negative counters decay at their own rates
+        let r = sm.get_evidence_counters(&miner).unwrap().r;
+        let s = sm.get_evidence_counters(&offender).unwrap().s;
+        assert!((r - 20.0 * params.pos_decay.powi(500)).abs() < 1e-9);
+        assert!((s - 2.0 * params.neg_decay.powi(500)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_eligible_miners_thresholds() {
+        let params = EbslParams::default();
+        let mut sm = StateManager::new();
+        let good = |epoch| Evidence::new(EvidenceType::GoodBlock, epoch, epoch);
+
+        // 3 good blocks: trust 0.76, just above t_min
+        let above = [1u8; 33];
+        // 2 good blocks: trust 0.70, between t_kill and t_min
+        let warning = [2u8; 33];
+        // One missed reveal: trust ~0.13, below t_kill
+        let killed = [3u8; 33];
+        // Trusted but never bonded
+        let unbonded = [4u8; 33];
+
+        for miner in [above, warning, killed] {
+            sm.update_bond(miner, BondState::new(1000, 0));
+        }
+        for epoch in 0..3 {
+            sm.submit_evidence(above, good(epoch), &EbslParams::default()).unwrap();
+            sm.submit_evidence(unbonded, good(epoch), &EbslParams::default()).unwrap();
+        }
+        for epoch in 0..2 {
+            sm.submit_evidence(warning, good(epoch), &EbslParams::default()).unwrap();
+        }
+        sm.submit_evidence(killed, Evidence::new(EvidenceType::MissedReveal, 0, 0), &EbslParams::default()).unwrap();
+
+        let score = |miner: &[u8; 33]| TrustScore::new(sm.calculate_trust_score(miner, &params));
+        assert!(score(&above).is_eligible(&params) && score(&above).value() < 0.77);
+        assert!(score(&warning).is_warning(&params));
+        assert!(score(&killed).is_killed(&params));
+
+        assert_eq!(sm.eligible_miners(&params), vec![above]);
+    }
+
+    #[test]
+    fn test_snapshot_import_replaces_stored_state() {
+        let mut source = StateManager::new();
+        source.update_account([1u8; 33], Account { balance: 100, nonce: 1 });
+        source.update_bond([2u8; 33], BondState::new(500, 0));
+        source.submit_evidence([2u8; 33], Evidence::new(EvidenceType::GoodBlock, 1, 1), &EbslParams::default()).unwrap();
+        source.check_and_record_nullifier([3u8; 32]).unwrap();
+        let snapshot = source.export_snapshot();
+
+        let temp_dir = TempDir::new().unwrap();
+        {
+            // Pre-existing state the snapshot does not contain
+            let storage = Arc::new(StorageManager::new(temp_dir.path()).unwrap());
+            let mut sm = StateManager::with_storage(storage).unwrap();
+            sm.update_account([9u8; 33], Account { balance: 7, nonce: 0 });
+            sm.update_bond([9u8; 33], BondState::new(900, 0));
+            sm.submit_evidence([9u8; 33], Evidence::new(EvidenceType::Equivocation, 1, 1), &EbslParams::default()).unwrap();
+            sm.check_and_record_nullifier([8u8; 32]).unwrap();
+
+            sm.import_snapshot(&snapshot).unwrap();
+            sm.flush().unwrap();
+        }
+
+        let storage = Arc::new(StorageManager::new(temp_dir.path()).unwrap());
+        let reopened = StateManager::with_storage(storage).unwrap();
+        assert_eq!(reopened.state_root, source.state_root);
+        assert_eq!(reopened.accounts.len(), 1);
+        assert!(reopened.get_account_owned(&[9u8; 33]).is_none());
+        assert!(reopened.get_bond_owned(&[9u8; 33]).is_none());
+        assert_eq!(reopened.get_bond_owned(&[2u8; 33]).unwrap().amount, 500);
+        assert!(reopened.get_evidence_counters(&[9u8; 33]).is_none());
+        assert_eq!(reopened.get_evidence_counters(&[2u8; 33]).unwrap().r, 1.0);
+        assert!(!reopened.is_nullifier_spent(&[8u8; 32]));
+        assert!(reopened.is_nullifier_spent(&[3u8; 32]));
+    }
+
+    #[test]
+    fn test_evidence_survives_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let storage = Arc::new(StorageManager::new(temp_dir.path()).unwrap());
+            let mut sm = StateManager::with_storage(storage).unwrap();
+            sm.submit_evidence([4u8; 33], Evidence::new(EvidenceType::GoodBlock, 1, 1), &EbslParams::default()).unwrap();
+        }
+
+        let storage = Arc::new(StorageManager::new(temp_dir.path()).unwrap());
+        let reopened = StateManager::with_storage(storage).unwrap();
+        assert_eq!(reopened.get_evidence_counters(&[4u8; 33]).unwrap().r, 1.0);
+    }
+
+    #[test]
+    fn test_bonded_miner_eligible_after_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let miner = [6u8; 33];
+        {
+            let storage = Arc::new(StorageManager::new(temp_dir.path()).unwrap());
+            let mut sm = StateManager::with_storage(storage).unwrap();
+            sm.update_bond(miner, BondState::new(1000, 0));
+            for epoch in 0..3 {
+                sm.submit_evidence(miner, Evidence::new(EvidenceType::GoodBlock, epoch, epoch), &EbslParams::default()).unwrap();
+            }
+            assert_eq!(sm.eligible_miners(&EbslParams::default()), vec![miner]);
+        }
+
+        let storage = Arc::new(StorageManager::new(temp_dir.path()).unwrap());
+        let reopened = StateManager::with_storage(storage).unwrap();
+        assert_eq!(reopened.get_bond(&miner).unwrap().amount, 1000);
+        assert_eq!(reopened.eligible_miners(&EbslParams::default()), vec![miner]);
+    }
+
+    #[test]
+    fn test_snapshot_root_mismatch_rejected() {
+        let mut sm = StateManager::new();
+        sm.update_account([1u8; 33], Account { balance: 100, nonce: 0 });
+        sm.update_account([2u8; 33], Account { balance: 200, nonce: 0 });
+
+        let mut snapshot = sm.export_snapshot();
+        // The state root immediately follows the 4-byte version header
+        snapshot[4] ^= 0xff;
+
+        let mut target = StateManager::new();
+        target.update_account([3u8; 33], Account { balance: 300, nonce: 0 });
+        let root_before = target.state_root;
+
+        assert!(matches!(target.import_snapshot(&snapshot), Err(Error::StorageError(_))));
+        assert_eq!(target.state_root, root_before);
+        assert_eq!(target.accounts.len(), 1);
+    }
+
+    #[test]
+    fn test_snapshot_unknown_version_rejected() {
+        let mut snapshot = StateManager::new().export_snapshot();
+        snapshot[..4].copy_from_slice(&99u32.to_be_bytes());
+        assert!(StateManager::new().import_snapshot(&snapshot).is_err());
+    }
+
     #[test]
     fn test_state_manager_get_or_create_account() {
         let mut sm = StateManager::new();
@@ -481,4 +1275,259 @@ mod tests {
         assert!(sm.get_account(&pubkey).is_some());
         assert_eq!(sm.get_account_owned(&pubkey).unwrap().balance, 500);
     }
+
+    fn block_tx(from: [u8; 33], nonce: u64, amount: u64, nullifiers: &[[u8; 32]]) -> BlockTransaction {
+        BlockTransaction {
+            from,
+            to: [9u8; 33],
+            amount,
+            nonce,
+            nullifiers: nullifiers.to_vec(),
+            fee: 100,
+            out_of_gas: false,
+        }
+    }
+
+    #[test]
+    fn test_apply_block_charges_failed_transactions() {
+        let mut sm = StateManager::new();
+        let sender = [4u8; 33];
+        sm.credit_account(sender, 250).unwrap();
+
+        let starved = BlockTransaction { out_of_gas: true, ..block_tx(sender, 0, 10, &[]) };
+        let outcomes = sm.apply_block(Some(([8u8; 33], 50)), &[
+            starved,
+            block_tx(sender, 1, 100, &[]),
+            // Overdraws; the fee is capped at the remaining balance
+            block_tx(sender, 2, 250, &[]),
+            // A stale nonce is neither applied nor charged
+            block_tx(sender, 1, 0, &[]),
+        ]).unwrap();
+        assert_eq!(outcomes, vec![false, true, false, false]);
+
+        let account = sm.get_account(&sender).unwrap();
+        assert_eq!((account.balance, account.nonce), (0, 3));
+        assert_eq!(sm.get_account(&[9u8; 33]).unwrap().balance, 100);
+        assert_eq!(sm.get_account(&[8u8; 33]).unwrap().balance, 50);
+    }
+
+    #[test]
+    fn test_apply_block_spends_nullifiers_of_applied_transfers_only() {
+        let mut sm = StateManager::new();
+        let (rich, poor) = ([4u8; 33], [5u8; 33]);
+        sm.credit_account(rich, 1_000).unwrap();
+        sm.credit_account(poor, 1_000).unwrap();
+        sm.check_and_record_nullifier([1u8; 32]).unwrap();
+
+        let outcomes = sm.apply_block(None, &[
+            block_tx(rich, 0, 10, &[[1u8; 32]]),
+            block_tx(rich, 1, 10, &[[2u8; 32], [2u8; 32]]),
+            block_tx(poor, 0, 5_000, &[[3u8; 32]]),
+            block_tx(rich, 2, 10, &[[3u8; 32]]),
+            block_tx(poor, 1, 10, &[[3u8; 32]]),
+        ]).unwrap();
+        assert_eq!(outcomes, vec![false, false, false, true, false]);
+        assert!(sm.is_nullifier_spent(&[3u8; 32]));
+        assert!(!sm.is_nullifier_spent(&[2u8; 32]));
+    }
+
+    #[test]
+    fn test_repeated_nullifier_rejected() {
+        let mut sm = StateManager::new();
+        let nullifier = [7u8; 32];
+
+        sm.check_and_record_nullifier(nullifier).unwrap();
+        assert!(sm.is_nullifier_spent(&nullifier));
+
+        let result = sm.check_and_record_nullifier(nullifier);
+        assert!(matches!(result, Err(Error::DoubleSpend)));
+    }
+
+    #[test]
+    fn test_duplicate_nullifier_in_spend_rejected() {
+        let mut sm = StateManager::new();
+
+        assert!(matches!(sm.record_nullifiers(&[[1u8; 32], [1u8; 32]]), Err(Error::DoubleSpend)));
+        assert!(!sm.is_nullifier_spent(&[1u8; 32]));
+
+        // One spent nullifier rejects the whole spend
+        sm.check_and_record_nullifier([2u8; 32]).unwrap();
+        assert!(matches!(sm.record_nullifiers(&[[3u8; 32], [2u8; 32]]), Err(Error::DoubleSpend)));
+        assert!(!sm.is_nullifier_spent(&[3u8; 32]));
+
+        sm.record_nullifiers(&[[1u8; 32], [3u8; 32]]).unwrap();
+        assert!(sm.is_nullifier_spent(&[1u8; 32]) && sm.is_nullifier_spent(&[3u8; 32]));
+    }
+
+    #[test]
+    fn test_nullifier_rejected_after_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let nullifier = [7u8; 32];
+
+        {
+            let storage = Arc::new(StorageManager::new(temp_dir.path()).unwrap());
+            let mut sm = StateManager::with_storage(storage).unwrap();
+            sm.check_and_record_nullifier(nullifier).unwrap();
+        }
+
+        let storage = Arc::new(StorageManager::new(temp_dir.path()).unwrap());
+        let mut sm = StateManager::with_storage(storage).unwrap();
+        assert!(sm.is_nullifier_spent(&nullifier));
+        assert!(matches!(sm.check_and_record_nullifier(nullifier), Err(Error::DoubleSpend)));
+        sm.check_and_record_nullifier([8u8; 32]).unwrap();
+    }
+
+    #[test]
+    fn test_nullifier_rejected_after_snapshot_sync() {
+        let mut sm = StateManager::new();
+        sm.update_account([1u8; 33], Account { balance: 100, nonce: 0 });
+        sm.check_and_record_nullifier([7u8; 32]).unwrap();
+        let snapshot = sm.export_snapshot();
+
+        // Synced into a fresh persistent node, then restarted
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let storage = Arc::new(StorageManager::new(temp_dir.path()).unwrap());
+            let mut synced = StateManager::with_storage(storage).unwrap();
+            synced.import_snapshot(&snapshot).unwrap();
+            assert_eq!(synced.nullifier_root(), sm.nullifier_root());
+            assert!(matches!(synced.check_and_record_nullifier([7u8; 32]), Err(Error::DoubleSpend)));
+        }
+        let storage = Arc::new(StorageManager::new(temp_dir.path()).unwrap());
+        let mut restarted = StateManager::with_storage(storage).unwrap();
+        assert!(matches!(restarted.check_and_record_nullifier([7u8; 32]), Err(Error::DoubleSpend)));
+    }
+
+    #[test]
+    fn test_snapshot_nullifier_root_mismatch_rejected() {
+        let mut sm = StateManager::new();
+        sm.check_and_record_nullifier([7u8; 32]).unwrap();
+        let mut snapshot = sm.export_snapshot();
+
+        // Drop the spend from the serialized set: the last 32 bytes
+        let len = snapshot.len();
+        snapshot[len - 1] ^= 0xff;
+
+        let mut target = StateManager::new();
+        assert!(matches!(target.import_snapshot(&snapshot), Err(Error::StorageError(_))));
+        assert!(!target.is_nullifier_spent(&[7u8; 32]));
+    }
+
+    #[test]
+    fn test_distinct_nullifiers_accepted() {
+        let mut sm = StateManager::new();
+
+        for i in 0..10u8 {
+            sm.check_and_record_nullifier([i; 32]).unwrap();
+        }
+        assert!(!sm.is_nullifier_spent(&[10u8; 32]));
+    }
+
+    #[test]
+    fn test_apply_transaction_nonce_mismatch() {
+        let mut sm = StateManager::new();
+        let alice = [1u8; 33];
+        let bob = [2u8; 33];
+
+        sm.update_account(alice, Account { balance: 1000, nonce: 3 });
+
+        let result = sm.apply_transaction(alice, bob, 100, 5);
+        assert!(matches!(result, Err(Error::InvalidNonce { expected: 3, got: 5 })));
+        assert_eq!(sm.get_account(&alice).unwrap().balance, 1000);
+        assert!(sm.get_account(&bob).is_none());
+    }
+
+    #[test]
+    fn test_apply_transaction_receiver_overflow() {
+        let mut sm = StateManager::new();
+        let alice = [1u8; 33];
+        let bob = [2u8; 33];
+
+        sm.update_account(alice, Account { balance: 1000, nonce: 0 });
+        sm.update_account(bob, Account { balance: u64::MAX - 10, nonce: 0 });
+        let root_before = sm.state_root;
+
+        let result = sm.apply_transaction(alice, bob, 100, 0);
+        assert!(matches!(result, Err(Error::BalanceOverflow)));
+
+        // Sender must not be debited when the credit fails
+        let alice_account = sm.get_account(&alice).unwrap();
+        assert_eq!(alice_account.balance, 1000);
+        assert_eq!(alice_account.nonce, 0);
+        assert_eq!(sm.get_account(&bob).unwrap().balance, u64::MAX - 10);
+        assert_eq!(sm.state_root, root_before);
+    }
+
+    #[test]
+    fn test_incremental_root_matches_full_rebuild() {
+        let mut sm = StateManager::new();
+
+        let mut seed: u64 = 42;
+        for i in 0..200u64 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let pubkey = [(seed >> 58) as u8; 33];
+
+            match i % 3 {
+                0 => sm.update_account(pubkey, Account { balance: seed >> 32, nonce: i }),
+                1 => { sm.credit_account(pubkey, seed >> 48).unwrap(); }
+                _ => {
+                    let to = [(seed >> 52) as u8; 33];
+                    let (balance, nonce) = sm.get_account(&pubkey)
+                        .map(|a| (a.balance, a.nonce))
+                        .unwrap_or((0, 0));
+                    let _ = sm.apply_transaction(pubkey, to, balance / 2, nonce);
+                }
+            }
+
+            let incremental = sm.state_root;
+            sm.recompute_root();
+            assert_eq!(incremental, sm.state_root, "root diverged at step {}", i);
+        }
+    }
+
+    #[test]
+    fn test_apply_transactions_batch() {
+        let mut sm = StateManager::new();
+        let alice = [1u8; 33];
+        let bob = [2u8; 33];
+        let carol = [3u8; 33];
+
+        sm.update_account(alice, Account { balance: 1000, nonce: 0 });
+
+        // Bob spends funds he only receives earlier in the same batch
+        let root = sm.apply_transactions(&[
+            (alice, bob, 300, 0),
+            (alice, carol, 200, 1),
+            (bob, carol, 100, 0),
+        ]).unwrap();
+
+        assert_eq!(root, sm.state_root);
+        assert_eq!(sm.get_account(&alice).unwrap().balance, 500);
+        assert_eq!(sm.get_account(&alice).unwrap().nonce, 2);
+        assert_eq!(sm.get_account(&bob).unwrap().balance, 200);
+        assert_eq!(sm.get_account(&carol).unwrap().balance, 300);
+        assert_eq!(sm.get_account(&bob).unwrap().nonce, 1);
+    }
+
+    #[test]
+    fn test_apply_transactions_rolls_back_on_failure() {
+        let mut sm = StateManager::new();
+        let alice = [1u8; 33];
+        let bob = [2u8; 33];
+
+        sm.update_account(alice, Account { balance: 1000, nonce: 0 });
+        let root_before = sm.state_root;
+
+        // Second transaction overspends, so the whole batch must be rejected
+        let result = sm.apply_transactions(&[
+            (alice, bob, 600, 0),
+            (alice, bob, 600, 1),
+        ]);
+
+        assert!(matches!(result, Err(Error::InsufficientBalance)));
+        assert_eq!(sm.state_root, root_before);
+        assert_eq!(sm.get_account(&alice).unwrap().balance, 1000);
+        assert_eq!(sm.get_account(&alice).unwrap().nonce, 0);
+        assert!(sm.get_account(&bob).is_none());
+    }
 }
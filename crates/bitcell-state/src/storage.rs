@@ -1,10 +1,13 @@
 /// RocksDB persistent storage layer
 /// Provides durable storage for blocks, state, and chain data
 
-use rocksdb::{DB, Options, WriteBatch};
+use rocksdb::{DB, IteratorMode, Options, WriteBatch};
 use std::path::Path;
 use std::sync::Arc;
 
+use bitcell_ebsl::EvidenceCounters;
+use std::collections::HashMap;
+
 use crate::{Account, BondState};
 
 /// Database column families
@@ -17,6 +20,22 @@ const CF_BONDS: &str = "bonds";
 const CF_STATE_ROOTS: &str = "state_roots";
 const CF_CHAIN_INDEX: &str = "chain_index";
 const CF_SNAPSHOTS: &str = "snapshots";
+const CF_NULLIFIERS: &str = "nullifiers";
+const CF_EVIDENCE: &str = "evidence";
+
+const COLUMN_FAMILIES: [&str; 11] = [
+    CF_BLOCKS,
+    CF_HEADERS,
+    CF_TRANSACTIONS,
+    CF_TX_BY_SENDER,
+    CF_ACCOUNTS,
+    CF_BONDS,
+    CF_STATE_ROOTS,
+    CF_CHAIN_INDEX,
+    CF_SNAPSHOTS,
+    CF_NULLIFIERS,
+    CF_EVIDENCE,
+];
 
 /// Persistent storage manager
 pub struct StorageManager {
@@ -30,25 +49,23 @@ impl StorageManager {
         opts.create_if_missing(true);
         opts.create_missing_column_families(true);
         
-        let cfs = vec![
-            CF_BLOCKS,
-            CF_HEADERS,
-            CF_TRANSACTIONS,
-            CF_TX_BY_SENDER,
-            CF_ACCOUNTS,
-            CF_BONDS,
-            CF_STATE_ROOTS,
-            CF_CHAIN_INDEX,
-            CF_SNAPSHOTS,
-        ];
-        
-        let db = DB::open_cf(&opts, path, cfs)?;
+        let db = DB::open_cf(&opts, path, COLUMN_FAMILIES)?;
         
         Ok(Self {
             db: Arc::new(db),
         })
     }
 
+    /// Flush all column families' memtables to disk
+    pub fn flush(&self) -> Result<(), String> {
+        for name in COLUMN_FAMILIES {
+            let cf = self.db.cf_handle(name)
+                .ok_or_else(|| format!("Column family {} not found", name))?;
+            self.db.flush_cf(cf).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
     /// Store a block header
     pub fn store_header(&self, height: u64, hash: &[u8], header: &[u8]) -> Result<(), String> {
         let cf = self.db.cf_handle(CF_HEADERS)
@@ -131,6 +148,48 @@ impl StorageManager {
         }
     }
 
+    /// Iterate over all stored accounts
+    ///
+    /// Yields `(pubkey, account)` pairs in key order. Entries with a malformed
+    /// key or undecodable account data are skipped with a warning, so a single
+    /// corrupt record cannot prevent the node from starting.
+    pub fn iter_accounts(&self) -> Result<impl Iterator<Item = ([u8; 33], Account)> + '_, String> {
+        let cf = self.db.cf_handle(CF_ACCOUNTS)
+            .ok_or_else(|| "Accounts column family not found".to_string())?;
+
+        let iter = self.db.iterator_cf(cf, IteratorMode::Start).filter_map(|item| {
+            let (key, value) = match item {
+                Ok(kv) => kv,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to read account entry");
+                    return None;
+                }
+            };
+
+            let pubkey: [u8; 33] = match key.as_ref().try_into() {
+                Ok(pubkey) => pubkey,
+                Err(_) => {
+                    tracing::warn!(key_len = key.len(), "Skipping account with invalid key length");
+                    return None;
+                }
+            };
+
+            match bincode::deserialize::<Account>(&value) {
+                Ok(account) => Some((pubkey, account)),
+                Err(e) => {
+                    tracing::warn!(
+                        pubkey = %hex::encode(pubkey),
+                        error = %e,
+                        "Skipping undecodable account"
+                    );
+                    None
+                }
+            }
+        });
+
+        Ok(iter)
+    }
+
     /// Store bond state
     pub fn store_bond(&self, miner_id: &[u8], bond: &BondState) -> Result<(), String> {
         let cf = self.db.cf_handle(CF_BONDS)
@@ -151,6 +210,129 @@ impl StorageManager {
         }
     }
 
+    /// All stored bonds, in key order
+    pub fn load_bonds(&self) -> Result<Vec<([u8; 33], BondState)>, String> {
+        let cf = self.db.cf_handle(CF_BONDS)
+            .ok_or_else(|| "Bonds column family not found".to_string())?;
+        self.db.iterator_cf(cf, IteratorMode::Start)
+            .map(|item| {
+                let (key, value) = item.map_err(|e| e.to_string())?;
+                let miner = <[u8; 33]>::try_from(key.as_ref()).map_err(|_| {
+                    format!("Invalid bond key length: {}", key.len())
+                })?;
+                let bond = bincode::deserialize(&value)
+                    .map_err(|e| format!("Deserialization error: {}", e))?;
+                Ok((miner, bond))
+            })
+            .collect()
+    }
+
+    /// Record a spent nullifier
+    pub fn store_nullifier(&self, nullifier: &[u8; 32]) -> Result<(), String> {
+        self.store_nullifiers(std::slice::from_ref(nullifier))
+    }
+
+    /// Record the nullifiers of one spend in a single atomic write
+    pub fn store_nullifiers(&self, nullifiers: &[[u8; 32]]) -> Result<(), String> {
+        let cf = self.db.cf_handle(CF_NULLIFIERS)
+            .ok_or_else(|| "Nullifiers column family not found".to_string())?;
+        let mut batch = WriteBatch::default();
+        for nullifier in nullifiers {
+            batch.put_cf(cf, nullifier, b"");
+        }
+        self.db.write(batch).map_err(|e| e.to_string())
+    }
+
+    /// All recorded nullifiers, in key order
+    pub fn load_nullifiers(&self) -> Result<Vec<[u8; 32]>, String> {
+        let cf = self.db.cf_handle(CF_NULLIFIERS)
+            .ok_or_else(|| "Nullifiers column family not found".to_string())?;
+        self.db.iterator_cf(cf, IteratorMode::Start)
+            .map(|item| {
+                let (key, _) = item.map_err(|e| e.to_string())?;
+                <[u8; 32]>::try_from(key.as_ref()).map_err(|_| {
+                    format!("Invalid nullifier key length: {}", key.len())
+                })
+            })
+            .collect()
+    }
+
+    /// Store a validator's EBSL evidence counters
+    pub fn store_evidence(&self, validator: &[u8], counters: &EvidenceCounters) -> Result<(), String> {
+        let cf = self.db.cf_handle(CF_EVIDENCE)
+            .ok_or_else(|| "Evidence column family not found".to_string())?;
+        let data = bincode::serialize(counters)
+            .map_err(|e| format!("Serialization error: {}", e))?;
+        self.db.put_cf(cf, validator, data).map_err(|e| e.to_string())
+    }
+
+    /// All stored evidence counters, in key order
+    pub fn load_evidence(&self) -> Result<Vec<([u8; 33], EvidenceCounters)>, String> {
+        let cf = self.db.cf_handle(CF_EVIDENCE)
+            .ok_or_else(|| "Evidence column family not found".to_string())?;
+        self.db.iterator_cf(cf, IteratorMode::Start)
+            .map(|item| {
+                let (key, value) = item.map_err(|e| e.to_string())?;
+                let validator = <[u8; 33]>::try_from(key.as_ref()).map_err(|_| {
+                    format!("Invalid evidence key length: {}", key.len())
+                })?;
+                let counters = bincode::deserialize(&value)
+                    .map_err(|e| format!("Deserialization error: {}", e))?;
+                Ok((validator, counters))
+            })
+            .collect()
+    }
+
+    /// Atomically replace all accounts, bonds, evidence and nullifiers
+    ///
+    /// Used by snapshot import: every existing entry in those column families
+    /// is deleted in the same write batch that stores the new ones, so no
+    /// stale record survives and a crash leaves either the old or the new state.
+    pub fn replace_state(
+        &self,
+        accounts: &HashMap<[u8; 33], Account>,
+        bonds: &HashMap<[u8; 33], BondState>,
+        evidence: &HashMap<[u8; 33], EvidenceCounters>,
+        nullifiers: &[[u8; 32]],
+    ) -> Result<(), String> {
+        fn serialize<T: serde::Serialize>(value: &T) -> Result<Vec<u8>, String> {
+            bincode::serialize(value).map_err(|e| format!("Serialization error: {}", e))
+        }
+
+        let mut batch = WriteBatch::default();
+        for name in [CF_ACCOUNTS, CF_BONDS, CF_EVIDENCE, CF_NULLIFIERS] {
+            let cf = self.db.cf_handle(name)
+                .ok_or_else(|| format!("Column family {} not found", name))?;
+            for item in self.db.iterator_cf(cf, IteratorMode::Start) {
+                let (key, _) = item.map_err(|e| e.to_string())?;
+                batch.delete_cf(cf, key);
+            }
+        }
+
+        let cf = self.db.cf_handle(CF_ACCOUNTS)
+            .ok_or_else(|| "Accounts column family not found".to_string())?;
+        for (pubkey, account) in accounts {
+            batch.put_cf(cf, pubkey, serialize(account)?);
+        }
+        let cf = self.db.cf_handle(CF_BONDS)
+            .ok_or_else(|| "Bonds column family not found".to_string())?;
+        for (pubkey, bond) in bonds {
+            batch.put_cf(cf, pubkey, serialize(bond)?);
+        }
+        let cf = self.db.cf_handle(CF_EVIDENCE)
+            .ok_or_else(|| "Evidence column family not found".to_string())?;
+        for (validator, counters) in evidence {
+            batch.put_cf(cf, validator, serialize(counters)?);
+        }
+        let cf = self.db.cf_handle(CF_NULLIFIERS)
+            .ok_or_else(|| "Nullifiers column family not found".to_string())?;
+        for nullifier in nullifiers {
+            batch.put_cf(cf, nullifier, b"");
+        }
+
+        self.db.write(batch).map_err(|e| e.to_string())
+    }
+
     /// Store state root for a given height
     pub fn store_state_root(&self, height: u64, root: &[u8]) -> Result<(), String> {
         let cf = self.db.cf_handle(CF_STATE_ROOTS)
@@ -884,6 +1066,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_iter_accounts() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = StorageManager::new(temp_dir.path()).unwrap();
+
+        for i in 0u8..5 {
+            storage.store_account(&[i; 33], &Account { balance: i as u64 * 100, nonce: i as u64 }).unwrap();
+        }
+
+        let accounts: Vec<_> = storage.iter_accounts().unwrap().collect();
+        assert_eq!(accounts.len(), 5);
+        for (i, (pubkey, account)) in accounts.iter().enumerate() {
+            assert_eq!(pubkey, &[i as u8; 33]);
+            assert_eq!(account.balance, i as u64 * 100);
+            assert_eq!(account.nonce, i as u64);
+        }
+    }
+
     #[test]
     fn test_bond_persistence() {
         let temp_dir = TempDir::new().unwrap();
@@ -907,6 +1107,9 @@ mod tests {
             assert_eq!(retrieved.amount, 5000);
             assert_eq!(retrieved.locked_epoch, 10);
             assert!(retrieved.is_active());
+            let all = storage.load_bonds().unwrap();
+            assert_eq!(all.len(), 1);
+            assert_eq!(all[0].0, miner_id);
         }
     }
 
@@ -1006,4 +1209,17 @@ mod tests {
             assert_eq!(root.unwrap().as_slice(), expected.as_bytes());
         }
     }
+
+    #[test]
+    fn test_nullifier_persistence() {
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let storage = StorageManager::new(temp_dir.path()).unwrap();
+            storage.store_nullifier(&[2u8; 32]).unwrap();
+            storage.store_nullifier(&[1u8; 32]).unwrap();
+        }
+
+        let storage = StorageManager::new(temp_dir.path()).unwrap();
+        assert_eq!(storage.load_nullifiers().unwrap(), vec![[1u8; 32], [2u8; 32]]);
+    }
 }
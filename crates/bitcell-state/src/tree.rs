@@ -0,0 +1,198 @@
+//! Incremental state Merkle tree
+//!
+//! Keeps account leaves sorted by public key together with every internal
+//! level of the tree, so a single account mutation only rehashes the path
+//! from its leaf to the root. The resulting root is identical to building a
+//! `bitcell_crypto::MerkleTree` from the same sorted leaves.
+
+use bitcell_crypto::{Hash256, merkle::MerkleProof};
+
+/// Merkle tree over account leaves, keyed and ordered by public key
+#[derive(Debug, Clone, Default)]
+pub struct StateTree {
+    /// Sorted leaf keys (index i corresponds to `levels[0][i]`)
+    keys: Vec<[u8; 33]>,
+
+    /// Tree levels, from leaves (`levels[0]`) up to the root level
+    levels: Vec<Vec<Hash256>>,
+}
+
+impl StateTree {
+    /// Create an empty tree
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of leaves in the tree
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether the tree has no leaves
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Remove all leaves
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.levels.clear();
+    }
+
+    /// Current root (zero hash for an empty tree)
+    pub fn root(&self) -> Hash256 {
+        self.levels.last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or(Hash256::zero())
+    }
+
+    /// Insert or update the leaf for `key`
+    ///
+    /// Updating an existing leaf rehashes only its path to the root. Inserting
+    /// a new key shifts the leaves after it, so every node to the right of the
+    /// insertion point is recomputed.
+    pub fn update(&mut self, key: [u8; 33], leaf: Hash256) {
+        match self.keys.binary_search(&key) {
+            Ok(index) => {
+                self.levels[0][index] = leaf;
+                self.update_path(index);
+            }
+            Err(index) => {
+                self.keys.insert(index, key);
+                if self.levels.is_empty() {
+                    self.levels.push(Vec::new());
+                }
+                self.levels[0].insert(index, leaf);
+                self.rebuild_from(index);
+            }
+        }
+    }
+
+    /// Inclusion proof for the leaf stored under `key`
+    ///
+    /// A node without a right sibling is paired with itself, matching
+    /// `parent_hash`, so the proof verifies with `MerkleTree::verify_proof`.
+    pub fn prove(&self, key: &[u8; 33]) -> Option<MerkleProof> {
+        let index = self.keys.binary_search(key).ok()?;
+        let mut path = Vec::with_capacity(self.levels.len().saturating_sub(1));
+        let mut node = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = level.get(node ^ 1).copied().unwrap_or(level[node]);
+            path.push(sibling);
+            node /= 2;
+        }
+        Some(MerkleProof { index, leaf: self.levels[0][index], path })
+    }
+
+    /// Rehash the path from leaf `index` up to the root
+    fn update_path(&mut self, mut index: usize) {
+        for level in 0..self.levels.len() - 1 {
+            let parent = Self::parent_hash(&self.levels[level], index & !1);
+            index /= 2;
+            self.levels[level + 1][index] = parent;
+        }
+    }
+
+    /// Recompute every node at or to the right of leaf `start` on all levels
+    fn rebuild_from(&mut self, mut start: usize) {
+        let mut level = 0;
+
+        while self.levels[level].len() > 1 {
+            let parent_len = self.levels[level].len().div_ceil(2);
+            if self.levels.len() == level + 1 {
+                self.levels.push(Vec::with_capacity(parent_len));
+            }
+
+            let parent_start = start / 2;
+            let parents: Vec<Hash256> = (parent_start..parent_len)
+                .map(|i| Self::parent_hash(&self.levels[level], i * 2))
+                .collect();
+
+            let next = &mut self.levels[level + 1];
+            next.truncate(parent_start);
+            next.extend(parents);
+
+            start = parent_start;
+            level += 1;
+        }
+
+        // Drop stale levels above the new root
+        self.levels.truncate(level + 1);
+    }
+
+    /// Hash the pair starting at `left`, duplicating it if it has no sibling
+    fn parent_hash(level: &[Hash256], left: usize) -> Hash256 {
+        let l = level[left];
+        let r = level.get(left + 1).copied().unwrap_or(l);
+        Hash256::hash_multiple(&[l.as_bytes(), r.as_bytes()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcell_crypto::MerkleTree;
+    use std::collections::BTreeMap;
+
+    fn full_rebuild(leaves: &BTreeMap<[u8; 33], Hash256>) -> Hash256 {
+        if leaves.is_empty() {
+            return Hash256::zero();
+        }
+        MerkleTree::new(leaves.values().copied().collect()).root()
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        let tree = StateTree::new();
+        assert!(tree.is_empty());
+        assert_eq!(tree.root(), Hash256::zero());
+    }
+
+    #[test]
+    fn test_single_leaf_is_root() {
+        let mut tree = StateTree::new();
+        let leaf = Hash256::hash(b"leaf");
+        tree.update([1u8; 33], leaf);
+        assert_eq!(tree.root(), leaf);
+    }
+
+    #[test]
+    fn test_incremental_matches_full_rebuild() {
+        let mut tree = StateTree::new();
+        let mut reference = BTreeMap::new();
+
+        // Simple LCG so the sequence is reproducible without extra deps
+        let mut seed: u64 = 0x5eed;
+        for step in 0..500u64 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+
+            // Small key space so we get a mix of inserts and in-place updates
+            let mut key = [0u8; 33];
+            key[0] = (seed >> 56) as u8 % 64;
+            key[32] = (seed >> 48) as u8 % 4;
+            let leaf = Hash256::hash(&step.to_le_bytes());
+
+            tree.update(key, leaf);
+            reference.insert(key, leaf);
+
+            assert_eq!(tree.len(), reference.len());
+            assert_eq!(tree.root(), full_rebuild(&reference), "mismatch at step {}", step);
+        }
+    }
+
+    #[test]
+    fn test_proofs_verify_against_root() {
+        let mut tree = StateTree::new();
+        for i in 0..7u8 {
+            tree.update([i; 33], Hash256::hash(&[i]));
+        }
+
+        for i in 0..7u8 {
+            let proof = tree.prove(&[i; 33]).unwrap();
+            assert_eq!(proof.leaf, Hash256::hash(&[i]));
+            assert!(MerkleTree::verify_proof(tree.root(), &proof), "leaf {} failed", i);
+        }
+        assert!(tree.prove(&[9u8; 33]).is_none());
+    }
+}
@@ -0,0 +1,59 @@
+//! System clipboard access
+
+use arboard::Clipboard;
+use std::cell::RefCell;
+
+thread_local! {
+    /// One handle per thread, kept alive: on X11 and Wayland the copied text
+    /// is served by this process and disappears when the handle is dropped
+    static CLIPBOARD: RefCell<Option<Clipboard>> = const { RefCell::new(None) };
+}
+
+fn with_clipboard<T>(
+    f: impl FnOnce(&mut Clipboard) -> Result<T, arboard::Error>,
+) -> Result<T, arboard::Error> {
+    CLIPBOARD.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        if slot.is_none() {
+            *slot = Some(Clipboard::new()?);
+        }
+        f(slot.as_mut().expect("clipboard initialised above"))
+    })
+}
+
+/// Replace the clipboard contents with `text`
+pub fn set_text(text: &str) -> Result<(), arboard::Error> {
+    with_clipboard(|clipboard| clipboard.set_text(text))
+}
+
+/// Read the current clipboard contents as text
+#[cfg(test)]
+pub fn get_text() -> Result<String, arboard::Error> {
+    with_clipboard(|clipboard| clipboard.get_text())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// CI runners and SSH sessions usually have no display to own a clipboard
+    fn headless() -> bool {
+        cfg!(target_os = "linux")
+            && std::env::var_os("DISPLAY").is_none()
+            && std::env::var_os("WAYLAND_DISPLAY").is_none()
+    }
+
+    #[test]
+    fn test_set_then_get_round_trips() {
+        if headless() {
+            eprintln!("skipping clipboard round-trip: no display");
+            return;
+        }
+
+        let public_key = bitcell_crypto::SecretKey::generate().public_key();
+        let text = bitcell_wallet::Address::from_public_key_bitcell(&public_key, 0)
+            .to_string_formatted();
+        set_text(&text).unwrap();
+        assert_eq!(get_text().unwrap(), text);
+    }
+}
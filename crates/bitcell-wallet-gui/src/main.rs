@@ -4,7 +4,10 @@
 //! Targets: macOS, Linux, Windows
 //! Features: 60fps smooth interactions, accessibility support, no WebView
 
-use bitcell_wallet::{Chain, Mnemonic, Wallet, WalletConfig};
+use bitcell_wallet::{
+    Address, Balance, Chain, Mnemonic, SignedBitCellTransaction, TransactionDirection, TransactionHistory,
+    TransactionRecord, Wallet, WalletConfig, DEFAULT_HISTORY_FILE,
+};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -13,6 +16,7 @@ slint::include_modules!();
 mod rpc_client;
 use rpc_client::RpcClient;
 
+mod clipboard;
 mod qrcode;
 mod game_viz;
 
@@ -57,24 +61,21 @@ fn chain_display_name(chain: Chain) -> &'static str {
     }
 }
 
-/// Parse address string to PublicKey
-/// For BitCell addresses, the address is the hex-encoded public key with optional prefix
+/// Parse a recipient address string to the PublicKey it pays to
+///
+/// Accepts a BitCell address (`BCL1...`) or a hex-encoded compressed public
+/// key with optional `0x` prefix.
 fn parse_address_to_pubkey(address: &str) -> Result<bitcell_crypto::PublicKey, String> {
-    // Remove common prefixes
     let address = address.trim();
-    let address = if address.starts_with("0x") {
-        &address[2..]
-    } else if address.starts_with("BC1") || address.starts_with("bc1") {
-        // BitCell address format - for now, just strip prefix
-        // In a real implementation, this would decode the address properly
-        &address[3..]
-    } else {
-        address
-    };
-    
-    // Decode hex to bytes
-    let bytes = hex::decode(address)
-        .map_err(|e| format!("Invalid hex in address: {}", e))?;
+    if let Ok(parsed) = address.parse::<Address>() {
+        if parsed.chain() == Chain::BitCell {
+            return parsed.bitcell_public_key().map_err(|e| e.to_string());
+        }
+    }
+
+    let hex_key = address.strip_prefix("0x").unwrap_or(address);
+    let bytes = hex::decode(hex_key)
+        .map_err(|_| "Address must be a BCL1 address or a hex public key".to_string())?;
     
     if bytes.len() != 33 {
         return Err(format!("Address must be 33 bytes (compressed public key), got {}", bytes.len()));
@@ -87,6 +88,22 @@ fn parse_address_to_pubkey(address: &str) -> Result<bitcell_crypto::PublicKey, S
         .map_err(|e| format!("Invalid public key: {}", e))
 }
 
+/// Check that a recipient belongs to the chain being sent on
+///
+/// BitCell recipients may also be given as a hex public key, which carries no
+/// chain prefix for `Address::check_chain_prefix` to inspect.
+fn check_recipient(address: &str, chain: Chain) -> Result<(), String> {
+    if chain == Chain::BitCell && parse_address_to_pubkey(address).is_ok() {
+        return Ok(());
+    }
+    Address::check_chain_prefix(address, chain).map_err(|e| e.to_string())
+}
+
+/// Account identifier the node's RPC expects: the hex-encoded public key
+fn rpc_account(public_key: &bitcell_crypto::PublicKey) -> String {
+    format!("0x{}", hex::encode(public_key.as_bytes()))
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize logging
@@ -186,21 +203,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         if let Some(grids) = replay.get("grid_states").and_then(|v| v.as_array()) {
                             // Take the last frame for now
                             if let Some(last_frame) = grids.last() {
-                                if let Some(rows) = last_frame.as_array() {
-                                    height = rows.len() as u32;
-                                    if height > 0 {
-                                        width = rows[0].as_array().map(|r| r.len()).unwrap_or(0) as u32;
-                                        
-                                        for row in rows {
-                                            if let Some(cells) = row.as_array() {
-                                                let row_vec: Vec<u8> = cells.iter()
-                                                    .map(|c| c.as_u64().unwrap_or(0) as u8)
-                                                    .collect();
-                                                grid_data.push(row_vec);
-                                            }
-                                        }
-                                        has_grid = true;
-                                    }
+                                let size = last_frame.get("size").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+                                let runs = last_frame
+                                    .get("cells")
+                                    .filter(|_| size > 0)
+                                    .and_then(|v| serde_json::from_value::<Vec<bitcell_ca::CellRun>>(v.clone()).ok());
+                                if let Some(runs) = runs {
+                                    let cells = bitcell_ca::replay::decode_cells(&runs, size * size);
+                                    grid_data = cells.chunks(size).map(|row| row.to_vec()).collect();
+                                    width = size as u32;
+                                    height = size as u32;
+                                    has_grid = true;
                                 }
                             }
                         }
@@ -433,6 +446,11 @@ fn setup_callbacks(window: &MainWindow, state: Rc<RefCell<AppState>>) {
             
             let chain = parse_chain(&chain_str);
             
+            if let Err(e) = check_recipient(&to_address, chain) {
+                wallet_state.set_status_message(format!("Recipient rejected: {}", e).into());
+                return;
+            }
+            
             // Validate amount before conversion to prevent overflow
             // Max safe value: u64::MAX / 100_000_000 ≈ 184 billion
             const MAX_AMOUNT: f64 = 184_467_440_737.0; // u64::MAX / 100_000_000
@@ -447,7 +465,7 @@ fn setup_callbacks(window: &MainWindow, state: Rc<RefCell<AppState>>) {
             let amount_units = (amount * 100_000_000.0) as u64;
             
             // Get wallet info and secret key before async operation
-            let (from_addr_formatted, secret_key, rpc_client) = {
+            let (from_account, from_address, secret_key, rpc_client) = {
                 let app_state = state.borrow();
                 
                 let wallet = match &app_state.wallet {
@@ -490,7 +508,7 @@ fn setup_callbacks(window: &MainWindow, state: Rc<RefCell<AppState>>) {
                     }
                 };
                 
-                (from_addr_obj.to_string_formatted(), sk, rpc)
+                (rpc_account(&sk.public_key()), from_addr_obj.to_string(), sk, rpc)
             };
             
             // Set loading state
@@ -503,7 +521,7 @@ fn setup_callbacks(window: &MainWindow, state: Rc<RefCell<AppState>>) {
             // Async nonce fetch and transaction creation
             tokio::spawn(async move {
                 // Get nonce from node
-                let nonce = match rpc_client.get_transaction_count(&from_addr_formatted).await {
+                let nonce = match rpc_client.get_transaction_count(&from_account).await {
                     Ok(n) => n,
                     Err(e) => {
                         let _ = slint::invoke_from_event_loop(move || {
@@ -526,21 +544,7 @@ fn setup_callbacks(window: &MainWindow, state: Rc<RefCell<AppState>>) {
                 // Gas limit for simple transfer
                 let gas_limit = 21000u64;
                 
-                // Parse addresses to PublicKey format
-                let from_pk = match parse_address_to_pubkey(&from_addr_formatted) {
-                    Ok(pk) => pk,
-                    Err(e) => {
-                        let _ = slint::invoke_from_event_loop(move || {
-                            if let Some(window) = window_weak.upgrade() {
-                                let ws = window.global::<WalletState>();
-                                ws.set_is_loading(false);
-                                ws.set_status_message(format!("Invalid from address: {}", e).into());
-                            }
-                        });
-                        return;
-                    }
-                };
-                
+                // Parse recipient to PublicKey format
                 let to_pk = match parse_address_to_pubkey(&to_address) {
                     Ok(pk) => pk,
                     Err(e) => {
@@ -555,26 +559,18 @@ fn setup_callbacks(window: &MainWindow, state: Rc<RefCell<AppState>>) {
                     }
                 };
                 
-                // Create consensus transaction (without signature initially)
-                let mut tx = bitcell_consensus::Transaction {
+                // Build and sign the consensus transaction (sender is derived from the key)
+                let signed = SignedBitCellTransaction::sign(
+                    &secret_key,
+                    to_pk,
+                    amount_units,
                     nonce,
-                    from: from_pk,
-                    to: to_pk,
-                    amount: amount_units,
-                    gas_limit,
                     gas_price,
-                    data: vec![],
-                    signature: bitcell_crypto::Signature::from_bytes([0u8; 64]), // Placeholder
-                };
-                
-                // Compute signing hash (hash of transaction WITHOUT signature field)
-                let signing_hash = tx.signing_hash();
-                
-                // Sign the transaction
-                tx.signature = secret_key.sign(signing_hash.as_bytes());
+                    gas_limit,
+                );
                 
                 // Serialize transaction
-                let tx_bytes = match bincode::serialize(&tx) {
+                let tx_bytes = match signed.to_bytes() {
                     Ok(bytes) => bytes,
                     Err(e) => {
                         let _ = slint::invoke_from_event_loop(move || {
@@ -591,13 +587,36 @@ fn setup_callbacks(window: &MainWindow, state: Rc<RefCell<AppState>>) {
                 // Send transaction via RPC
                 match rpc_client.send_raw_transaction_bytes(&tx_bytes).await {
                     Ok(tx_hash) => {
+                        // Record the pending transfer for `bitcell-wallet history`
+                        let timestamp = std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .map(|d| d.as_secs())
+                            .unwrap_or(0);
+                        let record = TransactionRecord::new(
+                            tx_hash.clone(),
+                            chain,
+                            TransactionDirection::Outgoing,
+                            from_address,
+                            to_address,
+                            amount_units,
+                            gas_price.saturating_mul(gas_limit),
+                            timestamp,
+                        );
+                        let history_note = match TransactionHistory::record_to_file(
+                            std::path::Path::new(DEFAULT_HISTORY_FILE),
+                            record,
+                        ) {
+                            Ok(()) => String::new(),
+                            Err(e) => format!("\nWarning: not saved to history: {}", e),
+                        };
+                        
                         let _ = slint::invoke_from_event_loop(move || {
                             if let Some(window) = window_weak.upgrade() {
                                 let ws = window.global::<WalletState>();
                                 ws.set_is_loading(false);
                                 ws.set_status_message(format!(
-                                    "Transaction sent successfully!\nHash: {}", 
-                                    tx_hash
+                                    "Transaction sent successfully!\nHash: {}{}", 
+                                    tx_hash, history_note
                                 ).into());
                             }
                         });
@@ -621,6 +640,9 @@ fn setup_callbacks(window: &MainWindow, state: Rc<RefCell<AppState>>) {
         let state = state.clone();
         let window_weak = window.as_weak();
         
+        // Balances are fetched on the tokio runtime, then applied to the wallet
+        // on the UI thread (AppState is not Send) via `slint::spawn_local`,
+        // after which `update_addresses` rebuilds the address model.
         wallet_state.on_refresh_balances(move || {
             let window = window_weak.unwrap();
             let wallet_state = window.global::<WalletState>();
@@ -631,32 +653,67 @@ fn setup_callbacks(window: &MainWindow, state: Rc<RefCell<AppState>>) {
             if let Some(rpc_client) = &app_state.rpc_client {
                 let client = rpc_client.clone();
                 let window_weak = window.as_weak();
+                let state = state.clone();
                 
                 // Get addresses to refresh
-                let addresses: Vec<String> = if let Some(ref wallet) = app_state.wallet {
-                    wallet.all_addresses().iter().map(|a| a.to_string_formatted()).collect()
+                let addresses: Vec<Address> = if let Some(ref wallet) = app_state.wallet {
+                    wallet.all_addresses().to_vec()
                 } else {
                     vec![]
                 };
                 
-                tokio::spawn(async move {
-                    // Fetch balances
+                let fetch = tokio::spawn(async move {
+                    // Fetch balances (returned as hex quantities). The node
+                    // keys accounts by public key, so only BitCell addresses
+                    // can be queried.
                     let mut updates = Vec::new();
-                    for addr in addresses {
-                        if let Ok(balance) = client.get_balance(&addr).await {
-                            updates.push((addr, balance));
+                    let mut errors = Vec::new();
+                    for addr in addresses.into_iter().filter(|a| a.chain() == Chain::BitCell) {
+                        let account = match addr.bitcell_public_key() {
+                            Ok(pk) => rpc_account(&pk),
+                            Err(e) => {
+                                errors.push(format!("{}: {}", addr, e));
+                                continue;
+                            }
+                        };
+                        match client.get_balance(&account).await {
+                            Ok(balance) => match u64::from_str_radix(balance.trim_start_matches("0x"), 16) {
+                                Ok(amount) => updates.push((addr, amount)),
+                                Err(e) => errors.push(format!("invalid balance {}: {}", balance, e)),
+                            },
+                            Err(e) => errors.push(e),
                         }
                     }
+                    (updates, errors)
+                });
+                
+                let _ = slint::spawn_local(async move {
+                    let (updates, errors) = match fetch.await {
+                        Ok(result) => result,
+                        Err(e) => (Vec::new(), vec![e.to_string()]),
+                    };
                     
-                    let _ = slint::invoke_from_event_loop(move || {
-                        if let Some(window) = window_weak.upgrade() {
-                            let wallet_state = window.global::<WalletState>();
-                            wallet_state.set_is_loading(false);
-                            wallet_state.set_status_message(format!("Updated {} balances", updates.len()).into());
-                            // Note: Updating the actual model requires more complex logic to map back to the wallet
-                            // For now we just verify connectivity and data fetching works
+                    if let Some(ref mut wallet) = state.borrow_mut().wallet {
+                        for (addr, amount) in &updates {
+                            wallet.set_balance(addr, Balance::new(*amount, addr.chain()));
                         }
-                    });
+                    }
+                    
+                    if let Some(window) = window_weak.upgrade() {
+                        let wallet_state = window.global::<WalletState>();
+                        update_addresses(&wallet_state, &state);
+                        wallet_state.set_is_loading(false);
+                        let message = match errors.first() {
+                            None => format!("Updated {} balances", updates.len()),
+                            Some(e) => format!(
+                                "Updated {} balances, {} failed: {}",
+                                updates.len(),
+                                errors.len(),
+                                e
+                            ),
+                        };
+                        wallet_state.set_status_message(message.into());
+                    }
                 });
             } else {
                 wallet_state.set_is_loading(false);
@@ -673,50 +730,10 @@ fn setup_callbacks(window: &MainWindow, state: Rc<RefCell<AppState>>) {
             let window = window_weak.unwrap();
             let wallet_state = window.global::<WalletState>();
             
-            // Platform-specific clipboard handling
-            #[cfg(target_os = "linux")]
-            {
-                if let Ok(mut child) = std::process::Command::new("xclip")
-                    .args(["-selection", "clipboard"])
-                    .stdin(std::process::Stdio::piped())
-                    .spawn()
-                {
-                    use std::io::Write;
-                    if let Some(ref mut stdin) = child.stdin {
-                        let _ = stdin.write_all(text.as_bytes());
-                    }
-                }
-            }
-            
-            #[cfg(target_os = "macos")]
-            {
-                if let Ok(mut child) = std::process::Command::new("pbcopy")
-                    .stdin(std::process::Stdio::piped())
-                    .spawn()
-                {
-                    use std::io::Write;
-                    if let Some(ref mut stdin) = child.stdin {
-                        let _ = stdin.write_all(text.as_bytes());
-                    }
-                }
-            }
-            
-            #[cfg(target_os = "windows")]
-            {
-                // Windows clipboard via PowerShell using stdin to avoid injection
-                if let Ok(mut child) = std::process::Command::new("powershell")
-                    .args(["-Command", "Set-Clipboard -Value $input"])
-                    .stdin(std::process::Stdio::piped())
-                    .spawn()
-                {
-                    use std::io::Write;
-                    if let Some(ref mut stdin) = child.stdin {
-                        let _ = stdin.write_all(text.as_bytes());
-                    }
-                }
+            match clipboard::set_text(&text) {
+                Ok(()) => wallet_state.set_status_message("Copied to clipboard".into()),
+                Err(e) => wallet_state.set_status_message(format!("Failed to copy to clipboard: {}", e).into()),
             }
-            
-            wallet_state.set_status_message("Copied to clipboard".into());
         });
     }
 }
@@ -743,3 +760,30 @@ fn update_addresses(wallet_state: &WalletState, state: &Rc<RefCell<AppState>>) {
         wallet_state.set_addresses(model.into());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_public_key_recipient_accepted_for_bitcell() {
+        let pk = bitcell_crypto::SecretKey::generate().public_key();
+        let hex_key = hex::encode(pk.as_bytes());
+
+        assert!(check_recipient(&hex_key, Chain::BitCell).is_ok());
+        assert!(check_recipient(&format!("0x{}", hex_key), Chain::BitCell).is_ok());
+        assert_eq!(parse_address_to_pubkey(&format!("0x{}", hex_key)).unwrap(), pk);
+
+        let address = Address::from_public_key_bitcell(&pk, 0).to_string_formatted();
+        assert!(check_recipient(&address, Chain::BitCell).is_ok());
+    }
+
+    #[test]
+    fn test_foreign_recipient_rejected_for_bitcell() {
+        let pk = bitcell_crypto::SecretKey::generate().public_key();
+        let eth = Address::from_public_key_ethereum(&pk, false, 0).to_string_formatted();
+
+        assert!(check_recipient(&eth, Chain::BitCell).is_err());
+        assert!(check_recipient(&hex::encode(pk.as_bytes()), Chain::Ethereum).is_err());
+    }
+}
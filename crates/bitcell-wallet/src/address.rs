@@ -3,14 +3,81 @@
 //! Provides address generation, formatting, and validation for multiple chains.
 
 use crate::{Chain, Error, Result};
+use bech32::primitives::decode::CheckedHrpstring;
+use bech32::{Bech32m, Hrp};
 use bitcell_crypto::PublicKey;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+/// Human-readable part of BitCell addresses
+///
+/// Distinct from Bitcoin's `bc`, so neither chain's decoder accepts the other's
+/// addresses.
+pub const BITCELL_HRP: &str = "bcl";
+
+/// BitCell HRP as a Bech32 [`Hrp`]
+const BITCELL: Hrp = Hrp::parse_unchecked(BITCELL_HRP);
+
+/// Prefix of legacy BitCell addresses (`BC1` + Base58 of the 20-byte key hash)
+const LEGACY_BITCELL_PREFIX: &str = "BC1";
+
+/// Encode bytes as a lowercase Bech32m string
+fn bech32m_encode(hrp: Hrp, payload: &[u8]) -> String {
+    bech32::encode::<Bech32m>(hrp, payload).expect("address payloads fit the Bech32m length limit")
+}
+
+/// Decode a Bech32m string, validating the HRP, checksum and padding
+fn bech32m_decode(expected_hrp: Hrp, s: &str) -> Result<Vec<u8>> {
+    let checked = CheckedHrpstring::new::<Bech32m>(s)
+        .map_err(|e| Error::InvalidAddress(e.to_string()))?;
+    if checked.hrp() != expected_hrp {
+        return Err(Error::InvalidAddress(format!("Expected prefix {}1", expected_hrp.to_lowercase())));
+    }
+    checked.validate_segwit_padding()
+        .map_err(|e| Error::InvalidAddress(e.to_string()))?;
+    Ok(checked.byte_iter().collect())
+}
+
+/// Decode a Bitcoin SegWit address (BIP-173/350), returning its network
+///
+/// Version 0 programs use the Bech32 checksum and later versions Bech32m.
+fn segwit_decode(s: &str) -> Result<Chain> {
+    let (hrp, _version, _program) = bech32::segwit::decode(s)
+        .map_err(|e| Error::InvalidAddress(e.to_string()))?;
+    if hrp == bech32::hrp::BC {
+        Ok(Chain::Bitcoin)
+    } else if hrp == bech32::hrp::TB {
+        Ok(Chain::BitcoinTestnet)
+    } else {
+        Err(Error::InvalidAddress("Unknown SegWit prefix".into()))
+    }
+}
+
+/// Decode a Base58Check Bitcoin address, returning its network
+///
+/// Accepts P2PKH and P2SH version bytes for mainnet and testnet.
+fn base58check_decode(s: &str) -> Result<Chain> {
+    let bytes = bs58::decode(s)
+        .into_vec()
+        .map_err(|e| Error::InvalidAddress(e.to_string()))?;
+    if bytes.len() != 25 {
+        return Err(Error::InvalidAddress("Bitcoin address must be 25 bytes".into()));
+    }
+    let (payload, checksum) = bytes.split_at(21);
+    if &Sha256::digest(Sha256::digest(payload))[..4] != checksum {
+        return Err(Error::InvalidAddress("Invalid checksum".into()));
+    }
+    match payload[0] {
+        0x00 | 0x05 => Ok(Chain::Bitcoin),
+        0x6f | 0xc4 => Ok(Chain::BitcoinTestnet),
+        version => Err(Error::InvalidAddress(format!("Unknown version byte {:#04x}", version))),
+    }
+}
+
 /// Address type for different blockchain formats
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AddressType {
-    /// BitCell native address (Base58 encoded)
+    /// BitCell native address (Bech32m encoded, `BCL1` prefix)
     BitCell,
     /// Bitcoin P2PKH (Pay to Public Key Hash)
     BitcoinP2PKH,
@@ -46,16 +113,50 @@ impl Address {
 
     /// Generate a BitCell address from a public key
     /// 
-    /// Uses double SHA256 and takes the first 20 bytes as the address.
-    /// This is a simplified scheme for BitCell's native addresses.
+    /// The payload is the 33-byte compressed public key itself: BitCell
+    /// accounts are keyed by public key, so a recipient's key must be
+    /// recoverable from their address.
     pub fn from_public_key_bitcell(public_key: &PublicKey, index: u32) -> Self {
-        let pubkey_bytes = public_key.as_bytes();
-        let hash1 = Sha256::digest(pubkey_bytes);
-        let hash2 = Sha256::digest(hash1);
-        // Take first 20 bytes as address
-        let address_bytes = hash2[..20].to_vec();
-        
-        Self::new(address_bytes, AddressType::BitCell, Chain::BitCell, index)
+        Self::new(public_key.as_bytes().to_vec(), AddressType::BitCell, Chain::BitCell, index)
+    }
+
+    /// Recover the public key a BitCell address pays to
+    ///
+    /// Fails for other chains and for legacy BitCell addresses, which only
+    /// carry a hash of the key.
+    pub fn bitcell_public_key(&self) -> Result<PublicKey> {
+        if self.address_type != AddressType::BitCell {
+            return Err(Error::InvalidAddress("Not a BitCell address".into()));
+        }
+        let bytes: [u8; 33] = self.bytes.as_slice().try_into().map_err(|_| {
+            Error::InvalidAddress(
+                "Legacy BitCell address does not contain a public key; ask the recipient for a BCL1 address".into(),
+            )
+        })?;
+        PublicKey::from_bytes(bytes).map_err(|e| Error::InvalidAddress(e.to_string()))
+    }
+
+    /// Parse a legacy BitCell address (`BC1` + Base58, no checksum)
+    ///
+    /// Migration path for addresses stored before the Bech32m format. The
+    /// regular parsers reject legacy addresses, since they carry no checksum
+    /// and no public key to pay to.
+    pub fn from_legacy_bitcell(s: &str) -> Result<Self> {
+        let encoded = s.strip_prefix(LEGACY_BITCELL_PREFIX)
+            .ok_or_else(|| Error::InvalidAddress("Legacy BitCell address must start with BC1".into()))?;
+        let bytes = bs58::decode(encoded)
+            .into_vec()
+            .map_err(|e| Error::InvalidAddress(e.to_string()))?;
+        if bytes.len() != 20 {
+            return Err(Error::InvalidAddress("Legacy BitCell address must be 20 bytes".into()));
+        }
+        Ok(Self::new(bytes, AddressType::BitCell, Chain::BitCell, 0))
+    }
+
+    /// Whether `s` starts with the BitCell prefix, in either case
+    fn has_bitcell_prefix(s: &str) -> bool {
+        let hrp = format!("{}1", BITCELL_HRP);
+        s.get(..hrp.len()).is_some_and(|p| p.eq_ignore_ascii_case(&hrp))
     }
 
     /// Generate a Bitcoin P2PKH address from a public key
@@ -118,8 +219,8 @@ impl Address {
     pub fn to_string_formatted(&self) -> String {
         match self.address_type {
             AddressType::BitCell => {
-                // Format: BC1 + base58 encoded
-                format!("BC1{}", bs58::encode(&self.bytes).into_string())
+                // Bech32m with HRP "bcl", shown in uppercase (valid Bech32)
+                bech32m_encode(BITCELL, &self.bytes).to_ascii_uppercase()
             }
             AddressType::BitcoinP2PKH => {
                 // Version byte + address + checksum
@@ -146,12 +247,10 @@ impl Address {
     pub fn from_string(s: &str, chain: Chain) -> Result<Self> {
         match chain {
             Chain::BitCell => {
-                if !s.starts_with("BC1") {
-                    return Err(Error::InvalidAddress("BitCell address must start with BC1".into()));
+                let bytes = bech32m_decode(BITCELL, s)?;
+                if bytes.len() != 33 {
+                    return Err(Error::InvalidAddress("BitCell address must be 33 bytes".into()));
                 }
-                let bytes = bs58::decode(&s[3..])
-                    .into_vec()
-                    .map_err(|e| Error::InvalidAddress(e.to_string()))?;
                 Ok(Self::new(bytes, AddressType::BitCell, chain, 0))
             }
             Chain::Bitcoin | Chain::BitcoinTestnet => {
@@ -185,10 +284,55 @@ impl Address {
         }
     }
 
+    /// Check that an address string is a valid address for `chain`
+    ///
+    /// The string is fully decoded: HRP and checksum for Bech32 formats,
+    /// version byte and checksum for Base58Check, and length and hex digits
+    /// for Ethereum. Testnets of the same family are accepted.
+    pub fn check_chain_prefix(s: &str, chain: Chain) -> Result<()> {
+        let s = s.trim();
+        let encoded = Self::decoded_chain(s)?;
+
+        let matches = match chain {
+            Chain::BitCell => encoded == Chain::BitCell,
+            Chain::Bitcoin | Chain::BitcoinTestnet => {
+                matches!(encoded, Chain::Bitcoin | Chain::BitcoinTestnet)
+            }
+            Chain::Ethereum | Chain::EthereumSepolia => encoded == Chain::Ethereum,
+            Chain::Custom(_) => false,
+        };
+        if matches {
+            Ok(())
+        } else {
+            Err(Error::InvalidAddress(format!(
+                "{} address expected, got a {} address",
+                chain.name(),
+                encoded.name()
+            )))
+        }
+    }
+
+    /// Decode an address string and return the chain it belongs to
+    fn decoded_chain(s: &str) -> Result<Chain> {
+        if Self::has_bitcell_prefix(s) {
+            Self::from_string(s, Chain::BitCell)?;
+            return Ok(Chain::BitCell);
+        }
+        if s.starts_with("0x") {
+            Self::from_string(s, Chain::Ethereum)?;
+            return Ok(Chain::Ethereum);
+        }
+        if let Ok(chain) = segwit_decode(s) {
+            return Ok(chain);
+        }
+        base58check_decode(s)
+    }
+
     /// Validate that the address is well-formed
     pub fn is_valid(&self) -> bool {
         match self.address_type {
-            AddressType::BitCell => self.bytes.len() == 20,
+            // 33-byte public key, or a 20-byte hash for legacy addresses
+            AddressType::BitCell => self.bytes.len() == 33 || self.bytes.len() == 20,
             AddressType::BitcoinP2PKH => self.bytes.len() == 20,
             AddressType::BitcoinP2WPKH => self.bytes.len() == 20,
             AddressType::Ethereum => self.bytes.len() == 20,
@@ -196,6 +340,33 @@ impl Address {
     }
 }
 
+impl std::str::FromStr for Address {
+    type Err = Error;
+
+    /// Parse an address, inferring the chain from its format
+    ///
+    /// `BCL1`/`bcl1` is BitCell (checksum validated), `0x` is Ethereum and
+    /// anything else is tried as a Base58Check Bitcoin address. Legacy `BC1`
+    /// addresses go through [`Address::from_legacy_bitcell`] instead.
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if Self::has_bitcell_prefix(s) {
+            Self::from_string(s, Chain::BitCell)
+        } else if s.starts_with("0x") {
+            Self::from_string(s, Chain::Ethereum)
+        } else {
+            let address = Self::from_string(s, Chain::Bitcoin)?;
+            // Version byte was validated by the checksum; pick the network from it
+            let version = bs58::decode(s).into_vec().ok().and_then(|b| b.first().copied());
+            Ok(if version == Some(0x6f) {
+                Self::new(address.bytes, AddressType::BitcoinP2PKH, Chain::BitcoinTestnet, 0)
+            } else {
+                address
+            })
+        }
+    }
+}
+
 impl std::fmt::Display for Address {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.to_string_formatted())
@@ -277,7 +448,8 @@ mod tests {
         assert_eq!(address.chain(), Chain::BitCell);
         assert_eq!(address.address_type(), AddressType::BitCell);
         assert!(address.is_valid());
-        assert!(address.to_string_formatted().starts_with("BC1"));
+        assert!(address.to_string_formatted().starts_with("BCL1"));
+        assert_eq!(address.bitcell_public_key().unwrap(), pk);
     }
 
     #[test]
@@ -356,4 +528,142 @@ mod tests {
         assert_eq!(btc_mainnet.chain(), Chain::Bitcoin);
         assert_eq!(btc_testnet.chain(), Chain::BitcoinTestnet);
     }
+
+    #[test]
+    fn test_bitcell_address_checksum() {
+        let (_, pk) = test_keypair();
+        let address = Address::from_public_key_bitcell(&pk, 0);
+        let formatted = address.to_string_formatted();
+
+        let parsed: Address = formatted.parse().unwrap();
+        assert_eq!(parsed.chain(), Chain::BitCell);
+        assert_eq!(parsed.as_bytes(), address.as_bytes());
+
+        // Lowercase is equally valid; mixed case is not
+        assert!(formatted.to_lowercase().parse::<Address>().is_ok());
+        let mixed = format!("{}{}", &formatted[..10], formatted[10..].to_lowercase());
+        assert!(matches!(mixed.parse::<Address>(), Err(Error::InvalidAddress(_))));
+    }
+
+    #[test]
+    fn test_bitcell_address_typo_detected() {
+        let (_, pk) = test_keypair();
+        let formatted = Address::from_public_key_bitcell(&pk, 0).to_string_formatted();
+
+        // Every single-character substitution after the separator must be rejected
+        for pos in BITCELL_HRP.len() + 1..formatted.len() {
+            let original = formatted.as_bytes()[pos];
+            let replacement = *b"QPZRY9X8GF2TVDW0S3JN54KHCE6MUA7L"
+                .iter()
+                .find(|&&c| c != original)
+                .unwrap();
+            let mut corrupted = formatted.clone().into_bytes();
+            corrupted[pos] = replacement;
+            let corrupted = String::from_utf8(corrupted).unwrap();
+
+            assert!(
+                matches!(Address::from_string(&corrupted, Chain::BitCell), Err(Error::InvalidAddress(_))),
+                "typo at position {} was not detected",
+                pos
+            );
+        }
+    }
+
+    #[test]
+    fn test_chain_prefix_check() {
+        let (_, pk) = test_keypair();
+        let bitcell = Address::from_public_key_bitcell(&pk, 0).to_string_formatted();
+        let bitcoin = Address::from_public_key_bitcoin(&pk, false, 0).to_string_formatted();
+        let ethereum = Address::from_public_key_ethereum(&pk, false, 0).to_string_formatted();
+
+        // Matching chains are accepted, including testnets of the same family
+        assert!(Address::check_chain_prefix(&bitcell, Chain::BitCell).is_ok());
+        assert!(Address::check_chain_prefix(&bitcoin, Chain::BitcoinTestnet).is_ok());
+        assert!(Address::check_chain_prefix(&ethereum, Chain::EthereumSepolia).is_ok());
+
+        // A Bitcoin or Ethereum address is never a BitCell recipient
+        assert!(Address::check_chain_prefix(&bitcoin, Chain::BitCell).is_err());
+        assert!(Address::check_chain_prefix(&ethereum, Chain::BitCell).is_err());
+        assert!(Address::check_chain_prefix(&bitcell, Chain::Ethereum).is_err());
+        assert!(Address::check_chain_prefix(&bitcell, Chain::Custom(7)).is_err());
+
+        // Bitcoin SegWit v0 (Bech32) and v1 (Bech32m) addresses are Bitcoin only
+        let segwit_v0 = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+        let taproot = "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0";
+        for addr in [segwit_v0, taproot, segwit_v0.to_uppercase().as_str()] {
+            assert!(Address::check_chain_prefix(addr, Chain::Bitcoin).is_ok(), "{}", addr);
+            assert!(Address::check_chain_prefix(addr, Chain::BitCell).is_err(), "{}", addr);
+        }
+        let testnet_v0 = "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7";
+        assert!(Address::check_chain_prefix(testnet_v0, Chain::BitcoinTestnet).is_ok());
+
+        // Garbage and corrupted checksums are rejected for every chain
+        let mut corrupted_bitcoin = bitcoin.clone();
+        let last = corrupted_bitcoin.pop().unwrap();
+        corrupted_bitcoin.push(if last == '2' { '3' } else { '2' });
+        let mut corrupted_bitcell = bitcell.clone();
+        let last = corrupted_bitcell.pop().unwrap();
+        corrupted_bitcell.push(if last == 'Q' { 'P' } else { 'Q' });
+        for garbage in [
+            "garbage",
+            "",
+            "bc1qgarbage",
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5",
+            corrupted_bitcoin.as_str(),
+            corrupted_bitcell.as_str(),
+            "0x1234",
+        ] {
+            for chain in [Chain::BitCell, Chain::Bitcoin, Chain::Ethereum] {
+                assert!(
+                    Address::check_chain_prefix(garbage, chain).is_err(),
+                    "{:?} accepted for {:?}",
+                    garbage,
+                    chain
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_bitcell_address_not_bitcoin() {
+        let (_, pk) = test_keypair();
+        let formatted = Address::from_public_key_bitcell(&pk, 0).to_string_formatted();
+
+        // A Bitcoin decoder splits at the last '1' and finds HRP "bcl", not "bc"
+        let lower = formatted.to_lowercase();
+        assert_eq!(lower.rsplit_once('1').map(|(hrp, _)| hrp), Some(BITCELL_HRP));
+        assert!(matches!(bech32m_decode(bech32::hrp::BC, &formatted), Err(Error::InvalidAddress(_))));
+
+        // Bitcoin's "bc1..." is not mistaken for a BitCell address either
+        let segwit = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+        assert!(Address::from_string(segwit, Chain::BitCell).is_err());
+    }
+
+    #[test]
+    fn test_legacy_bitcell_address_migration_only() {
+        let (_, pk) = test_keypair();
+        let hash = Sha256::digest(Sha256::digest(pk.as_bytes()));
+        let legacy = format!("BC1{}", bs58::encode(&hash[..20]).into_string());
+
+        let migrated = Address::from_legacy_bitcell(&legacy).unwrap();
+        assert_eq!(migrated.chain(), Chain::BitCell);
+        assert_eq!(migrated.as_bytes(), &hash[..20]);
+        assert!(migrated.is_valid());
+
+        // The regular parsers never accept a legacy address
+        assert!(legacy.parse::<Address>().is_err());
+        assert!(Address::from_string(&legacy, Chain::BitCell).is_err());
+        assert!(Address::check_chain_prefix(&legacy, Chain::BitCell).is_err());
+
+        // The key is not recoverable from a legacy address
+        assert!(matches!(migrated.bitcell_public_key(), Err(Error::InvalidAddress(_))));
+    }
+
+    #[test]
+    fn test_bech32m_reference_vector() {
+        // BIP-350 test vector: valid Bech32m with an empty payload
+        let hrp = Hrp::parse("a").unwrap();
+        assert_eq!(bech32m_encode(hrp, &[]), "a1lqfn3a");
+        assert!(bech32m_decode(hrp, "a1lqfn3a").unwrap().is_empty());
+    }
 }
@@ -12,7 +12,7 @@
 //! - Private keys never leave the device
 //! - Derivation paths are displayed on device screen
 
-use crate::{Chain, Error, Result, Transaction};
+use crate::{Address, Chain, Error, Result, Transaction};
 use bitcell_crypto::{Hash256, PublicKey, Signature};
 use super::{ConnectionStatus, HardwareWalletDevice, HardwareWalletType};
 
@@ -215,7 +215,9 @@ impl HardwareWalletDevice for LedgerDevice {
         // Derive address from public key based on chain
         let hash = Hash256::hash(pubkey.as_bytes());
         let prefix = match chain {
-            Chain::BitCell => "BC1",
+            Chain::BitCell => {
+                return Ok(Address::from_public_key_bitcell(&pubkey, 0).to_string_formatted());
+            }
             Chain::Bitcoin | Chain::BitcoinTestnet => "bc1",
             Chain::Ethereum | Chain::EthereumSepolia => "0x",
             Chain::Custom(_) => "CUST",
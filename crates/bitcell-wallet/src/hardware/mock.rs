@@ -1,6 +1,6 @@
 //! Mock hardware wallet for testing
 
-use crate::{Chain, Error, Result, Transaction};
+use crate::{Address, Chain, Error, Result, Transaction};
 use bitcell_crypto::{Hash256, PublicKey, Signature};
 use super::{ConnectionStatus, HardwareWalletDevice, HardwareWalletType};
 
@@ -41,7 +41,7 @@ impl HardwareWalletDevice for MockHardwareWallet {
         // Simple address derivation for testing
         let hash = Hash256::hash(pk.as_bytes());
         let prefix = match chain {
-            Chain::BitCell => "BC1",
+            Chain::BitCell => return Ok(Address::from_public_key_bitcell(&pk, 0).to_string_formatted()),
             Chain::Bitcoin | Chain::BitcoinTestnet => "bc1",
             Chain::Ethereum | Chain::EthereumSepolia => "0x",
             Chain::Custom(_) => "CUST",
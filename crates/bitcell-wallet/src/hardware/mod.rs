@@ -260,7 +260,8 @@ mod tests {
         let hw = HardwareWallet::connect(HardwareWalletType::Mock).unwrap();
         let address = hw.get_address(Chain::BitCell).unwrap();
         
-        assert!(address.starts_with("BC1"));
+        assert!(address.starts_with("BCL1"));
+        assert!(address.parse::<crate::Address>().is_ok());
     }
     
     #[test]
@@ -1,10 +1,14 @@
 //! Transaction History
 //!
-//! Provides transaction history tracking and display.
+//! Provides transaction history tracking, persistence (JSON) and display.
 
-use crate::{Chain, transaction::TransactionStatus};
+use crate::{Chain, Error, Result, transaction::TransactionStatus};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
+
+/// History file shared by the wallet CLI and GUI
+pub const DEFAULT_HISTORY_FILE: &str = "bitcell-history.json";
 
 /// Direction of a transaction
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -209,6 +213,56 @@ fn is_leap_year(year: u64) -> bool {
     (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
 }
 
+/// Filter for querying transaction history
+///
+/// Unset fields match everything; time bounds are inclusive Unix timestamps.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    pub chain: Option<Chain>,
+    pub direction: Option<TransactionDirection>,
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+}
+
+impl HistoryFilter {
+    /// Create a filter matching all transactions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only transactions on a chain
+    pub fn chain(mut self, chain: Chain) -> Self {
+        self.chain = Some(chain);
+        self
+    }
+
+    /// Only transactions in a direction
+    pub fn direction(mut self, direction: TransactionDirection) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Only transactions at or after a timestamp
+    pub fn since(mut self, timestamp: u64) -> Self {
+        self.since = Some(timestamp);
+        self
+    }
+
+    /// Only transactions at or before a timestamp
+    pub fn until(mut self, timestamp: u64) -> Self {
+        self.until = Some(timestamp);
+        self
+    }
+
+    /// Check whether a record matches
+    pub fn matches(&self, tx: &TransactionRecord) -> bool {
+        self.chain.is_none_or(|c| tx.chain == c)
+            && self.direction.is_none_or(|d| tx.direction == d)
+            && self.since.is_none_or(|t| tx.timestamp >= t)
+            && self.until.is_none_or(|t| tx.timestamp <= t)
+    }
+}
+
 /// Transaction history manager
 /// 
 /// Note: When deserializing, call `rebuild_index()` to ensure
@@ -249,6 +303,72 @@ impl TransactionHistory {
         self.hash_index.insert(hash, idx);
     }
 
+    /// Record a transaction, replacing any existing record with the same hash
+    ///
+    /// Use this rather than `add` when a transaction may be seen more than
+    /// once (e.g. pending, then confirmed).
+    pub fn record(&mut self, record: TransactionRecord) {
+        match self.hash_index.get(&record.tx_hash) {
+            Some(&idx) => self.transactions[idx] = record,
+            None => self.add(record),
+        }
+    }
+
+    /// Save the history to a JSON file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.transactions)
+            .map_err(|e| Error::Serialization(e.to_string()))?;
+        std::fs::write(path, json).map_err(|e| Error::Io(e.to_string()))
+    }
+
+    /// Load history from a JSON file written by `save`
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path).map_err(|e| Error::Io(e.to_string()))?;
+        let transactions: Vec<TransactionRecord> = serde_json::from_str(&json)
+            .map_err(|e| Error::Serialization(e.to_string()))?;
+
+        let mut history = Self::new();
+        for record in transactions {
+            history.record(record);
+        }
+        Ok(history)
+    }
+
+    /// Record a transaction in the history file at `path`, creating it if needed
+    pub fn record_to_file(path: &Path, record: TransactionRecord) -> Result<()> {
+        let mut history = Self::load_or_default(path)?;
+        history.record(record);
+        history.save(path)
+    }
+
+    /// Load history from a JSON file, or start empty if it does not exist
+    pub fn load_or_default(path: &Path) -> Result<Self> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::new())
+        }
+    }
+
+    /// Get transactions matching a filter, newest first
+    pub fn query(&self, filter: &HistoryFilter) -> Vec<&TransactionRecord> {
+        let mut txs: Vec<_> = self.transactions.iter().filter(|tx| filter.matches(tx)).collect();
+        txs.sort_by_key(|tx| std::cmp::Reverse(tx.timestamp));
+        txs
+    }
+
+    /// Get transactions in a direction
+    pub fn by_direction(&self, direction: TransactionDirection) -> Vec<&TransactionRecord> {
+        self.transactions.iter().filter(|tx| tx.direction == direction).collect()
+    }
+
+    /// Get transactions within an inclusive time range
+    pub fn in_range(&self, since: u64, until: u64) -> Vec<&TransactionRecord> {
+        self.transactions.iter()
+            .filter(|tx| tx.timestamp >= since && tx.timestamp <= until)
+            .collect()
+    }
+
     /// Get transaction by hash
     pub fn get(&self, tx_hash: &str) -> Option<&TransactionRecord> {
         self.hash_index.get(tx_hash).map(|&idx| &self.transactions[idx])
@@ -292,7 +412,7 @@ impl TransactionHistory {
     /// Get transactions sorted by timestamp (newest first)
     pub fn sorted_by_time(&self) -> Vec<&TransactionRecord> {
         let mut txs: Vec<_> = self.transactions.iter().collect();
-        txs.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        txs.sort_by_key(|tx| std::cmp::Reverse(tx.timestamp));
         txs
     }
 
@@ -560,4 +680,73 @@ mod tests {
         
         assert_eq!(record.memo, Some("Payment for services".to_string()));
     }
+
+    #[test]
+    fn test_history_record_replaces() {
+        let mut history = TransactionHistory::new();
+        history.record(create_test_record("tx1", TransactionDirection::Outgoing, 1000));
+
+        let mut confirmed = create_test_record("tx1", TransactionDirection::Outgoing, 1000);
+        confirmed.confirm(42);
+        history.record(confirmed);
+
+        assert_eq!(history.count(), 1);
+        assert_eq!(history.get("tx1").unwrap().block_height, Some(42));
+    }
+
+    #[test]
+    fn test_history_persist_and_filter() {
+        let mut history = TransactionHistory::new();
+        history.record(create_test_record("tx1", TransactionDirection::Outgoing, 1000));
+        history.record(create_test_record("tx2", TransactionDirection::Incoming, 2000));
+
+        let mut eth = create_test_record("tx3", TransactionDirection::Incoming, 3000);
+        eth.chain = Chain::Ethereum;
+        eth.timestamp = 1234567999;
+        history.record(eth.with_memo("from exchange"));
+
+        let path = std::env::temp_dir().join(format!("bitcell_history_test_{}.json", std::process::id()));
+        history.save(&path).unwrap();
+        let loaded = TransactionHistory::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.count(), 3);
+        assert_eq!(loaded.get("tx3").unwrap().memo.as_deref(), Some("from exchange"));
+
+        let bitcell = loaded.query(&HistoryFilter::new().chain(Chain::BitCell));
+        assert_eq!(bitcell.len(), 2);
+        assert!(bitcell.iter().all(|tx| tx.chain == Chain::BitCell));
+
+        let incoming = loaded.query(&HistoryFilter::new().direction(TransactionDirection::Incoming));
+        assert_eq!(incoming.iter().map(|tx| tx.tx_hash.as_str()).collect::<Vec<_>>(), vec!["tx3", "tx2"]);
+
+        let recent = loaded.query(&HistoryFilter::new().since(1234567900));
+        assert_eq!(recent.len(), 1);
+        assert_eq!(loaded.in_range(0, 1234567890).len(), 2);
+        assert_eq!(loaded.by_direction(TransactionDirection::Outgoing).len(), 1);
+    }
+
+    #[test]
+    fn test_history_load_missing_file() {
+        let path = std::env::temp_dir().join("bitcell_history_does_not_exist.json");
+        assert!(TransactionHistory::load(&path).is_err());
+        assert_eq!(TransactionHistory::load_or_default(&path).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_record_to_file() {
+        let path = std::env::temp_dir().join(format!("bitcell_history_record_{}.json", std::process::id()));
+        std::fs::remove_file(&path).ok();
+
+        TransactionHistory::record_to_file(&path, create_test_record("tx1", TransactionDirection::Outgoing, 1000)).unwrap();
+        TransactionHistory::record_to_file(&path, create_test_record("tx2", TransactionDirection::Outgoing, 2000)).unwrap();
+        let mut confirmed = create_test_record("tx1", TransactionDirection::Outgoing, 1000);
+        confirmed.confirm(42);
+        TransactionHistory::record_to_file(&path, confirmed).unwrap();
+
+        let loaded = TransactionHistory::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.count(), 2);
+        assert_eq!(loaded.get("tx1").unwrap().block_height, Some(42));
+    }
 }
@@ -29,10 +29,10 @@ pub use address::{Address, AddressType};
 pub use balance::Balance;
 pub use chain::{Chain, ChainConfig};
 pub use hardware::{HardwareWallet, HardwareWalletType, SigningMethod};
-pub use history::{TransactionRecord, TransactionHistory};
+pub use history::{HistoryFilter, TransactionDirection, TransactionRecord, TransactionHistory, DEFAULT_HISTORY_FILE};
 pub use mnemonic::Mnemonic;
-pub use transaction::{Transaction, TransactionBuilder, SignedTransaction};
-pub use wallet::{Wallet, WalletConfig};
+pub use transaction::{Transaction, TransactionBuilder, SignedTransaction, SignedBitCellTransaction};
+pub use wallet::{DerivationPath, Wallet, WalletConfig};
 
 /// Standard result type for wallet operations
 pub type Result<T> = std::result::Result<T, Error>;
@@ -2,8 +2,11 @@
 //!
 //! Command-line interface for the BitCell wallet.
 
-use bitcell_wallet::{Chain, Mnemonic, Wallet, WalletConfig};
+use bitcell_wallet::{
+    Chain, DEFAULT_HISTORY_FILE, HistoryFilter, Mnemonic, TransactionDirection, TransactionHistory, Wallet, WalletConfig,
+};
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "bitcell-wallet")]
@@ -42,6 +45,27 @@ enum Commands {
         #[arg(short, long)]
         chain: Option<String>,
     },
+    /// Show transaction history
+    History {
+        /// History file (JSON)
+        #[arg(short, long, default_value = DEFAULT_HISTORY_FILE)]
+        file: PathBuf,
+        /// Only show transactions for this chain
+        #[arg(short, long)]
+        chain: Option<String>,
+        /// Only show transactions in this direction (in, out, self)
+        #[arg(short, long)]
+        direction: Option<String>,
+        /// Only show transactions at or after this Unix timestamp
+        #[arg(long)]
+        since: Option<u64>,
+        /// Only show transactions at or before this Unix timestamp
+        #[arg(long)]
+        until: Option<u64>,
+        /// Maximum number of transactions to show
+        #[arg(short, long, default_value_t = 20)]
+        limit: usize,
+    },
     /// Show version information
     Version,
 }
@@ -57,6 +81,15 @@ fn parse_chain(chain: &str) -> Result<Chain, String> {
     }
 }
 
+fn parse_direction(direction: &str) -> Result<TransactionDirection, String> {
+    match direction.to_lowercase().as_str() {
+        "in" | "incoming" => Ok(TransactionDirection::Incoming),
+        "out" | "outgoing" => Ok(TransactionDirection::Outgoing),
+        "self" => Ok(TransactionDirection::SelfTransfer),
+        _ => Err(format!("Unknown direction: {}", direction)),
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
 
@@ -166,6 +199,72 @@ fn main() {
             println!();
             println!("Note: Connect to a node to fetch actual balances.");
         }
+        Commands::History {
+            file,
+            chain,
+            direction,
+            since,
+            until,
+            limit,
+        } => {
+            println!("💰 BitCell Wallet - History");
+            println!("===========================");
+            println!();
+
+            let mut filter = HistoryFilter::new();
+            if let Some(chain_str) = chain {
+                match parse_chain(&chain_str) {
+                    Ok(c) => filter = filter.chain(c),
+                    Err(e) => {
+                        eprintln!("❌ Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            if let Some(direction_str) = direction {
+                match parse_direction(&direction_str) {
+                    Ok(d) => filter = filter.direction(d),
+                    Err(e) => {
+                        eprintln!("❌ Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            if let Some(t) = since {
+                filter = filter.since(t);
+            }
+            if let Some(t) = until {
+                filter = filter.until(t);
+            }
+
+            let history = match TransactionHistory::load_or_default(&file) {
+                Ok(h) => h,
+                Err(e) => {
+                    eprintln!("❌ Error loading {}: {}", file.display(), e);
+                    std::process::exit(1);
+                }
+            };
+
+            let txs = history.query(&filter);
+            if txs.is_empty() {
+                println!("No transactions found.");
+                return;
+            }
+
+            for tx in txs.iter().take(limit) {
+                println!(
+                    "{}  {:<20}  {:?}  {}",
+                    tx.format_date(),
+                    tx.format_amount(),
+                    tx.status,
+                    tx.short_hash()
+                );
+            }
+            if txs.len() > limit {
+                println!();
+                println!("... {} more (use --limit to show more)", txs.len() - limit);
+            }
+        }
         Commands::Version => {
             println!("bitcell-wallet v{}", env!("CARGO_PKG_VERSION"));
             println!("BitCell blockchain wallet");
@@ -144,6 +144,69 @@ impl SignedTransaction {
     }
 }
 
+/// Signed BitCell consensus transaction, ready for broadcast
+///
+/// This is the on-chain format accepted by the node (a bincode-encoded
+/// `bitcell_consensus::Transaction`), as opposed to the chain-agnostic
+/// [`SignedTransaction`] used for wallet bookkeeping.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedBitCellTransaction {
+    /// The signed consensus transaction
+    pub transaction: bitcell_consensus::Transaction,
+}
+
+impl SignedBitCellTransaction {
+    /// Build and sign a transfer offline
+    ///
+    /// The signature covers the consensus signing hash (every field except the
+    /// signature itself); the sender is the public key of `secret_key`.
+    pub fn sign(
+        secret_key: &SecretKey,
+        to: PublicKey,
+        amount: u64,
+        nonce: u64,
+        gas_price: u64,
+        gas_limit: u64,
+    ) -> Self {
+        let mut transaction = bitcell_consensus::Transaction {
+            nonce,
+            from: secret_key.public_key(),
+            to,
+            amount,
+            gas_limit,
+            gas_price,
+            data: Vec::new(),
+            spends: Vec::new(),
+            signature: Signature::from_bytes([0u8; 64]), // Replaced below
+        };
+        transaction.signature = secret_key.sign(transaction.signing_hash().as_bytes());
+        Self { transaction }
+    }
+
+    /// Verify the signature against a public key
+    pub fn verify(&self, public_key: &PublicKey) -> Result<()> {
+        let hash = self.transaction.signing_hash();
+        self.transaction.signature.verify(public_key, hash.as_bytes())
+            .map_err(|_| Error::SigningError("Invalid signature".into()))
+    }
+
+    /// Transaction hash (includes the signature)
+    pub fn hash(&self) -> Hash256 {
+        self.transaction.hash()
+    }
+
+    /// Get the transaction hash as hex string
+    pub fn hash_hex(&self) -> String {
+        hex::encode(self.hash().as_bytes())
+    }
+
+    /// Serialize for `eth_sendRawTransaction`
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        bincode::serialize(&self.transaction)
+            .map_err(|e| Error::Serialization(e.to_string()))
+    }
+}
+
 /// Transaction builder for easier transaction creation
 #[derive(Debug, Clone)]
 pub struct TransactionBuilder {
@@ -1,10 +1,24 @@
 //! Wallet Core
 //!
 //! Main wallet functionality that integrates all components.
+//!
+//! # Key derivation
+//!
+//! Every key is derived deterministically from the master seed and the
+//! BIP44-style path `m/44'/{coin_type}'/0'/0'/{index}`:
+//!
+//! ```text
+//! secret_key = SHA-256(seed || path_string)
+//! ```
+//!
+//! The next unused index per chain is persisted in
+//! [`WalletConfig::next_address_index`], so restoring a wallet from its
+//! mnemonic with the saved config re-derives every address handed out
+//! before, not just the lookahead window.
 
 use crate::{
     Address, Balance, Chain, ChainConfig, Error, Mnemonic, Result,
-    SignedTransaction, Transaction, TransactionBuilder, TransactionHistory,
+    SignedBitCellTransaction, SignedTransaction, Transaction, TransactionBuilder, TransactionHistory,
     address::AddressManager,
     balance::BalanceTracker,
     mnemonic::SeedBytes,
@@ -24,6 +38,27 @@ pub struct WalletConfig {
     pub auto_generate_addresses: bool,
     /// Number of addresses to pre-generate per chain
     pub address_lookahead: u32,
+    /// Next unused derivation index per chain
+    #[serde(default)]
+    pub next_address_index: Vec<(Chain, u32)>,
+}
+
+impl WalletConfig {
+    /// Get the persisted next derivation index for a chain
+    pub fn next_index(&self, chain: Chain) -> u32 {
+        self.next_address_index
+            .iter()
+            .find(|(c, _)| *c == chain)
+            .map_or(0, |(_, index)| *index)
+    }
+
+    /// Record the next derivation index for a chain (never moves backwards)
+    pub fn set_next_index(&mut self, chain: Chain, index: u32) {
+        match self.next_address_index.iter_mut().find(|(c, _)| *c == chain) {
+            Some((_, current)) => *current = (*current).max(index),
+            None => self.next_address_index.push((chain, index)),
+        }
+    }
 }
 
 impl Default for WalletConfig {
@@ -37,6 +72,7 @@ impl Default for WalletConfig {
             ],
             auto_generate_addresses: true,
             address_lookahead: 5,
+            next_address_index: Vec::new(),
         }
     }
 }
@@ -165,6 +201,17 @@ impl Wallet {
                 }
             }
         }
+
+        // Re-derive every address handed out before the wallet was restored
+        let issued: Vec<_> = wallet.config.next_address_index.clone();
+        for (chain, next) in issued {
+            for i in 0..next {
+                if let Err(_e) = wallet.generate_address(chain, i) {
+                    #[cfg(debug_assertions)]
+                    eprintln!("Warning: failed to restore address for chain {:?} at index {}: {}", chain, i, _e);
+                }
+            }
+        }
         
         wallet
     }
@@ -233,25 +280,41 @@ impl Wallet {
             return Ok(&self.derived_keys[&path_str]);
         }
         
+        let secret_key = self.derive_secret_key(path)?;
+        
+        let derived_key = DerivedKey::new(secret_key, path.clone());
+        self.derived_keys.insert(path_str.clone(), derived_key);
+        
+        Ok(&self.derived_keys[&path_str])
+    }
+
+    /// Derive the secret key at a path from the master seed (see module docs)
+    ///
+    /// The key is not added to the wallet's addresses.
+    pub fn derive_secret_key(&self, path: &DerivationPath) -> Result<SecretKey> {
         let seed = self.master_seed.as_ref().ok_or(Error::WalletLocked)?;
         
         // Simplified key derivation using HMAC-like construction
         // For full BIP32 compatibility, use a proper BIP32 library
         let mut derivation_data = Vec::new();
         derivation_data.extend_from_slice(seed.as_bytes());
-        derivation_data.extend_from_slice(path_str.as_bytes());
+        derivation_data.extend_from_slice(path.to_string().as_bytes());
         
         let derived_hash = Hash256::hash(&derivation_data);
-        let secret_key = SecretKey::from_bytes(derived_hash.as_bytes())?;
-        
-        let derived_key = DerivedKey::new(secret_key, path.clone());
-        self.derived_keys.insert(path_str.clone(), derived_key);
-        
-        Ok(&self.derived_keys[&path_str])
+        Ok(SecretKey::from_bytes(derived_hash.as_bytes())?)
     }
 
-    /// Generate a new address for a chain
+    /// Generate the address for a chain at a derivation index
+    ///
+    /// Generating an index that already exists returns the stored address.
     pub fn generate_address(&mut self, chain: Chain, index: u32) -> Result<Address> {
+        if let Some(existing) = self.addresses.all_addresses()
+            .iter()
+            .find(|a| a.chain() == chain && a.index() == index)
+        {
+            return Ok(existing.clone());
+        }
+
         let path = DerivationPath::for_chain(chain, index);
         let key = self.derive_key(&path)?;
         let public_key = &key.public_key;
@@ -266,16 +329,27 @@ impl Wallet {
         };
         
         self.addresses.add_address(address.clone());
+        self.config.set_next_index(chain, self.addresses.next_index(chain));
         
         Ok(address)
     }
 
     /// Get the next address for a chain
     pub fn next_address(&mut self, chain: Chain) -> Result<Address> {
-        let index = self.addresses.next_index(chain);
+        let index = self.addresses.next_index(chain).max(self.config.next_index(chain));
         self.generate_address(chain, index)
     }
 
+    /// Find the wallet's stored copy of an address (matching chain and bytes)
+    ///
+    /// Addresses parsed from strings carry index 0, so this is how the real
+    /// derivation index is recovered.
+    pub fn find_address(&self, address: &Address) -> Option<&Address> {
+        self.addresses.all_addresses()
+            .iter()
+            .find(|a| a.chain() == address.chain() && a.as_bytes() == address.as_bytes())
+    }
+
     /// Get all addresses for a chain
     pub fn get_addresses(&self, chain: Chain) -> Vec<&Address> {
         self.addresses.get_addresses(chain)
@@ -301,6 +375,11 @@ impl Wallet {
         self.balances.update_balance(address, amount);
     }
 
+    /// Set the balance for an address, e.g. after fetching it from a node
+    pub fn set_balance(&mut self, address: &Address, balance: Balance) {
+        self.balances.set_balance(address, balance);
+    }
+
     /// Get nonce for an address
     pub fn get_nonce(&self, address: &Address) -> u64 {
         let key = address.to_string_formatted();
@@ -349,7 +428,9 @@ impl Wallet {
             return Err(Error::WalletLocked);
         }
         
-        let path = DerivationPath::for_chain(from.chain(), from.index());
+        // Prefer the stored index in case `from` was parsed from a string
+        let index = self.find_address(from).map_or(from.index(), |a| a.index());
+        let path = DerivationPath::for_chain(from.chain(), index);
         let key = self.derive_key(&path)?;
         
         let signed = tx.sign(&key.secret_key);
@@ -398,8 +479,33 @@ impl Wallet {
         self.sign_transaction(tx, from)
     }
 
+    /// Build and sign a BitCell consensus transaction offline
+    ///
+    /// The caller supplies the nonce and gas parameters (e.g. fetched over RPC),
+    /// so this works without a node connection. `from` must be a wallet address;
+    /// its key is re-derived from the stored derivation index.
+    pub fn build_signed_transaction(
+        &self,
+        from: &Address,
+        to: &PublicKey,
+        amount: u64,
+        nonce: u64,
+        gas_price: u64,
+        gas_limit: u64,
+    ) -> Result<SignedBitCellTransaction> {
+        if from.chain() != Chain::BitCell {
+            return Err(Error::UnsupportedChain(from.chain().name().to_string()));
+        }
+        let secret_key = self.get_secret_key_for_address(from)?;
+        Ok(SignedBitCellTransaction::sign(&secret_key, *to, amount, nonce, gas_price, gas_limit))
+    }
+
     /// Get the secret key for an address (for advanced use cases like consensus transaction signing)
     /// 
+    /// The key is re-derived from the derivation index stored for this address
+    /// in the wallet, so it does not depend on the index carried by `address`.
+    /// Returns `Error::InvalidAddress` if the address does not belong to the wallet.
+    /// 
     /// This method should be used with caution as it exposes the raw secret key.
     /// Prefer using sign_transaction when possible.
     pub fn get_secret_key_for_address(&self, address: &Address) -> Result<SecretKey> {
@@ -407,20 +513,13 @@ impl Wallet {
             return Err(Error::WalletLocked);
         }
         
-        let path = DerivationPath::for_chain(address.chain(), address.index());
-        
-        // We need to derive the key without caching (since self is immutable)
-        let seed = self.master_seed.as_ref().ok_or(Error::WalletLocked)?;
-        
-        let path_str = path.to_string();
-        let mut derivation_data = Vec::new();
-        derivation_data.extend_from_slice(seed.as_bytes());
-        derivation_data.extend_from_slice(path_str.as_bytes());
-        
-        let derived_hash = Hash256::hash(&derivation_data);
-        let secret_key = SecretKey::from_bytes(derived_hash.as_bytes())?;
+        let stored = self.find_address(address).ok_or_else(|| Error::InvalidAddress(
+            format!("Address {} not found in wallet", address.to_string_formatted())
+        ))?;
         
-        Ok(secret_key)
+        // Derive without caching (since self is immutable)
+        let path = DerivationPath::for_chain(stored.chain(), stored.index());
+        self.derive_secret_key(&path)
     }
 
     /// Get transaction history
@@ -675,6 +774,24 @@ mod tests {
         assert!(total.amount() >= 500_000);
     }
 
+    #[test]
+    fn test_set_balance() {
+        let mut wallet = test_wallet();
+        let first = wallet.next_address(Chain::BitCell).unwrap();
+        let second = wallet.next_address(Chain::BitCell).unwrap();
+
+        wallet.set_balance(&first, Balance::new(300, Chain::BitCell));
+        wallet.set_balance(&second, Balance::new(200, Chain::BitCell));
+        assert_eq!(wallet.get_balance(&first), Balance::new(300, Chain::BitCell));
+        assert_eq!(wallet.get_total_balance(Chain::BitCell).amount(), 500);
+
+        // A refresh replaces the previous value rather than adding to it
+        wallet.set_balance(&first, Balance::new(50, Chain::BitCell));
+        assert_eq!(wallet.get_balance(&first).amount(), 50);
+        assert_eq!(wallet.get_total_balance(Chain::BitCell).amount(), 250);
+        assert_eq!(wallet.get_balance(&second).amount(), 200);
+    }
+
     #[test]
     fn test_export_import() {
         let mut wallet = test_wallet();
@@ -723,4 +840,76 @@ mod tests {
         let result = wallet.sign_transaction(tx, &addr);
         assert!(matches!(result, Err(Error::WalletLocked)));
     }
+
+    #[test]
+    fn test_restore_rederives_issued_addresses() {
+        let mnemonic = Mnemonic::new();
+        let mut wallet = Wallet::from_mnemonic(&mnemonic, "", WalletConfig::default());
+
+        let issued: Vec<Address> = (0..3)
+            .map(|_| wallet.next_address(Chain::BitCell).unwrap())
+            .collect();
+        let keys: Vec<_> = issued.iter()
+            .map(|a| wallet.get_secret_key_for_address(a).unwrap().public_key())
+            .collect();
+        assert_eq!(wallet.config().next_index(Chain::BitCell), 8);
+
+        // Persist the config and restore in a fresh session
+        let saved = serde_json::to_string(wallet.config()).unwrap();
+        drop(wallet);
+        let config: WalletConfig = serde_json::from_str(&saved).unwrap();
+        let mut restored = Wallet::from_mnemonic(&mnemonic, "", config);
+
+        for (address, key) in issued.iter().zip(&keys) {
+            let stored = restored.find_address(address).expect("address restored");
+            assert_eq!(stored, address);
+
+            // Parsed addresses carry index 0; the stored index must be used
+            let parsed = Address::from_string(&address.to_string_formatted(), Chain::BitCell).unwrap();
+            let sk = restored.get_secret_key_for_address(&parsed).unwrap();
+            assert_eq!(sk.public_key(), *key);
+        }
+
+        // New addresses continue after the restored ones
+        assert_eq!(restored.next_address(Chain::BitCell).unwrap().index(), 8);
+    }
+
+    #[test]
+    fn test_secret_key_for_foreign_address() {
+        let wallet = test_wallet();
+        let foreign = Address::from_public_key_bitcell(&SecretKey::generate().public_key(), 0);
+        assert!(matches!(
+            wallet.get_secret_key_for_address(&foreign),
+            Err(Error::InvalidAddress(_))
+        ));
+    }
+
+    #[test]
+    fn test_build_signed_transaction() {
+        let mut wallet = test_wallet();
+        let from = wallet.next_address(Chain::BitCell).unwrap();
+        let sender = wallet.get_secret_key_for_address(&from).unwrap().public_key();
+        let recipient = SecretKey::generate().public_key();
+
+        let signed = wallet
+            .build_signed_transaction(&from, &recipient, 5_000, 3, 1_000, 21_000)
+            .unwrap();
+
+        assert_eq!(signed.transaction.from, sender);
+        assert_eq!(signed.transaction.nonce, 3);
+        assert!(signed.verify(&sender).is_ok());
+        assert!(signed.verify(&recipient).is_err());
+
+        // Round-trips through the wire format with the signature intact
+        let decoded: bitcell_consensus::Transaction =
+            bincode::deserialize(&signed.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded.hash(), signed.hash());
+
+        // Locked wallets cannot sign
+        wallet.lock();
+        assert!(matches!(
+            wallet.build_signed_transaction(&from, &recipient, 5_000, 3, 1_000, 21_000),
+            Err(Error::WalletLocked)
+        ));
+    }
 }
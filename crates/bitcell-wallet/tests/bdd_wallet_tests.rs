@@ -95,6 +95,7 @@ mod wallet_creation_tests {
             ],
             auto_generate_addresses: true,
             address_lookahead: 3,
+            next_address_index: Vec::new(),
         };
 
         // When: A wallet is created with this configuration
@@ -329,7 +330,7 @@ mod address_generation_tests {
         assert_eq!(addr.chain(), Chain::BitCell);
         assert_eq!(addr.address_type(), AddressType::BitCell);
         assert!(addr.is_valid());
-        assert!(addr.to_string_formatted().starts_with("BC1"));
+        assert!(addr.to_string_formatted().starts_with("BCL1"));
     }
 
     /// Scenario: Generating Bitcoin addresses
@@ -437,7 +438,7 @@ mod address_generation_tests {
         let ethereum_addr = wallet.generate_address(Chain::Ethereum, 0).unwrap();
 
         // Then: Each chain should have its own address format
-        assert!(bitcell_addr.to_string_formatted().starts_with("BC1"));
+        assert!(bitcell_addr.to_string_formatted().starts_with("BCL1"));
         // Bitcoin addresses are Base58 encoded
         assert!(!bitcoin_addr.to_string_formatted().starts_with("0x"));
         assert!(ethereum_addr.to_string_formatted().starts_with("0x"));
@@ -68,8 +68,8 @@ fn test_hardware_wallet_address_generation() {
     
     // BitCell address
     let address = hw.get_address(Chain::BitCell).unwrap();
-    assert!(address.starts_with("BC1"));
-    assert_eq!(address.len(), 43); // "BC1" + 40 hex chars
+    assert!(address.starts_with("BCL1"));
+    assert_eq!(address.len(), 63); // "BCL1" + 53 data chars + 6 checksum chars
     
     // Bitcoin address
     let address = hw.get_address(Chain::Bitcoin).unwrap();
@@ -206,7 +206,7 @@ fn test_hardware_wallet_cross_chain_addresses() {
     let eth_addr = hw.get_address(Chain::Ethereum).unwrap();
     
     // All addresses should be valid and different formats
-    assert!(bc_addr.starts_with("BC1"));
+    assert!(bc_addr.starts_with("BCL1"));
     assert!(btc_addr.starts_with("bc1"));
     assert!(eth_addr.starts_with("0x"));
     
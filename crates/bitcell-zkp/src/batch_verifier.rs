@@ -0,0 +1,148 @@
+//! Batch verification of Groth16 proofs
+//!
+//! A block carries many independent battle proofs under the same verifying
+//! key. `BatchVerifier` prepares the key once and checks the proofs either
+//! sequentially or across the rayon thread pool.
+
+use crate::Groth16Proof;
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{prepare_verifying_key, Groth16, PreparedVerifyingKey, VerifyingKey};
+use ark_serialize::CanonicalSerialize;
+use ark_snark::SNARK;
+use rayon::prelude::*;
+use std::time::Instant;
+
+/// Size and timing of one batch verification
+///
+/// Lets the node's monitoring layer weigh batching against verifying
+/// proofs individually.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AggregationStats {
+    /// Number of proofs in the batch
+    pub num_proofs: usize,
+    /// Total compressed size of the batch's proofs
+    pub aggregated_size_bytes: usize,
+    /// Wall-clock time spent verifying the batch
+    pub verify_time_ms: u64,
+}
+
+/// Verifies many proofs against one verifying key
+pub struct BatchVerifier {
+    pvk: PreparedVerifyingKey<Bn254>,
+}
+
+impl BatchVerifier {
+    pub fn new(vk: &VerifyingKey<Bn254>) -> Self {
+        Self {
+            pvk: prepare_verifying_key(vk),
+        }
+    }
+
+    fn verify_one(&self, proof: &Groth16Proof, public_inputs: &[Fr]) -> crate::Result<bool> {
+        crate::check_public_inputs(&self.pvk.vk, public_inputs)?;
+        Groth16::<Bn254>::verify_with_processed_vk(&self.pvk, public_inputs, &proof.proof)
+            .map_err(|_| crate::Error::ProofVerification)
+    }
+
+    /// Verify proofs one after another, stopping at the first failure
+    pub fn verify(&self, proofs: &[(Groth16Proof, Vec<Fr>)]) -> crate::Result<bool> {
+        for (proof, public_inputs) in proofs {
+            if !self.verify_one(proof, public_inputs)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Verify proofs in parallel
+    ///
+    /// Returns `false` as soon as any proof fails; remaining work is
+    /// abandoned. Batches of one proof skip the thread pool.
+    pub fn verify_parallel(&self, proofs: &[(Groth16Proof, Vec<Fr>)]) -> crate::Result<bool> {
+        if proofs.len() < 2 {
+            return self.verify(proofs);
+        }
+
+        let failure = proofs
+            .par_iter()
+            .find_map_any(|(proof, public_inputs)| match self.verify_one(proof, public_inputs) {
+                Ok(true) => None,
+                other => Some(other),
+            });
+
+        failure.unwrap_or(Ok(true))
+    }
+
+    /// Verify proofs in parallel and report the batch's size and timing
+    pub fn verify_with_stats(
+        &self,
+        proofs: &[(Groth16Proof, Vec<Fr>)],
+    ) -> crate::Result<(bool, AggregationStats)> {
+        let aggregated_size_bytes = proofs
+            .iter()
+            .map(|(proof, _)| proof.proof.compressed_size())
+            .sum();
+
+        let start = Instant::now();
+        let valid = self.verify_parallel(proofs)?;
+        let verify_time_ms = start.elapsed().as_millis() as u64;
+
+        Ok((valid, AggregationStats {
+            num_proofs: proofs.len(),
+            aggregated_size_bytes,
+            verify_time_ms,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleBattleCircuit;
+    use ark_ff::One;
+
+    fn proofs(n: usize) -> (VerifyingKey<Bn254>, Vec<(Groth16Proof, Vec<Fr>)>) {
+        let (pk, vk) = SimpleBattleCircuit::setup().expect("Circuit setup should succeed");
+        let batch = (0..n)
+            .map(|i| {
+                let winner = (i % 3) as u8;
+                let circuit = SimpleBattleCircuit::new(Fr::one(), Fr::one(), winner, 100, 200);
+                let proof = circuit.prove(&pk).unwrap();
+                (proof, vec![Fr::one(), Fr::one(), Fr::from(winner)])
+            })
+            .collect();
+        (vk, batch)
+    }
+
+    #[test]
+    fn test_batch_of_valid_proofs() {
+        let (vk, batch) = proofs(6);
+        let verifier = BatchVerifier::new(&vk);
+
+        assert!(verifier.verify(&batch).unwrap());
+        assert!(verifier.verify_parallel(&batch).unwrap());
+    }
+
+    #[test]
+    fn test_batch_with_one_invalid_proof() {
+        let (vk, mut batch) = proofs(6);
+        // Claim a different winner for one proof
+        batch[4].1[2] = Fr::from(0u8);
+        let verifier = BatchVerifier::new(&vk);
+
+        assert!(!verifier.verify(&batch).unwrap());
+        assert!(!verifier.verify_parallel(&batch).unwrap());
+    }
+
+    #[test]
+    fn test_verify_with_stats() {
+        let (vk, batch) = proofs(4);
+        let verifier = BatchVerifier::new(&vk);
+
+        let (valid, stats) = verifier.verify_with_stats(&batch).unwrap();
+        assert!(valid);
+        assert_eq!(stats.num_proofs, 4);
+        assert_eq!(stats.aggregated_size_bytes, 4 * batch[0].0.serialize().unwrap().len());
+        assert!(stats.aggregated_size_bytes > 0);
+    }
+}
@@ -94,6 +94,10 @@ use ark_snark::SNARK;
 use ark_std::rand::thread_rng;
 
 impl BattleCircuit {
+    /// Key cache subdirectory for this circuit; bump the version whenever the
+    /// constraints change so cached keys from older builds are not reused
+    pub const KEY_ID: &'static str = "simple-battle-v1";
+
     /// Setup the circuit and generate proving/verifying keys
     ///
     /// **WARNING:** This method generates keys using insecure randomness and should
@@ -190,6 +194,15 @@ impl BattleCircuit {
         Ok((pk, vk))
     }
 
+    /// Load cached keys from `dir/KEY_ID`, or run [`Self::setup`] and save them there
+    ///
+    /// Like `setup()`, keys generated here are for testing only.
+    pub fn setup_or_load(
+        dir: impl AsRef<std::path::Path>,
+    ) -> crate::Result<(ProvingKey<ark_bn254::Bn254>, VerifyingKey<ark_bn254::Bn254>)> {
+        crate::key_management::setup_or_load(dir, Self::KEY_ID, Self::setup)
+    }
+
     /// Generate a proof for this circuit instance
     pub fn prove(
         &self,
@@ -207,6 +220,7 @@ impl BattleCircuit {
         proof: &crate::Groth16Proof,
         public_inputs: &[Fr],
     ) -> crate::Result<bool> {
+        crate::check_public_inputs(vk, public_inputs)?;
         Groth16::<ark_bn254::Bn254>::verify(vk, &public_inputs, &proof.proof)
             .map_err(|_| crate::Error::ProofVerification)
     }
@@ -244,6 +258,25 @@ mod tests {
         assert!(BattleCircuit::verify(&vk, &proof, &public_inputs).unwrap());
     }
 
+    #[test]
+    fn test_short_public_inputs_are_a_mismatch() {
+        let (pk, vk) = BattleCircuit::setup().expect("Circuit setup should succeed");
+        let proof = BattleCircuit::new(Fr::one(), Fr::one(), 1, 100, 200)
+            .prove(&pk)
+            .unwrap();
+
+        // Winner ID left out
+        let public_inputs = vec![Fr::one(), Fr::one()];
+
+        match BattleCircuit::verify(&vk, &proof, &public_inputs) {
+            Err(crate::Error::PublicInputMismatch { expected, got }) => {
+                assert_eq!(expected, 3);
+                assert_eq!(got, 2);
+            }
+            other => panic!("expected PublicInputMismatch, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_battle_circuit_all_winner_ids() {
         // Test that all valid winner IDs (0, 1, 2) work
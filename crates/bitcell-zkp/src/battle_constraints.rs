@@ -23,6 +23,10 @@ use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisE
 pub const GRID_SIZE: usize = 64; // Reduced from 1024 for practical circuit size
 pub const BATTLE_STEPS: usize = 10; // Reduced from 1000 for practical proving time
 
+/// Version of the battle constraints; bump whenever they change so keys
+/// cached by [`BattleCircuit::setup_or_load`] are regenerated
+pub const CIRCUIT_VERSION: u32 = 1;
+
 /// Battle circuit witness
 #[derive(Clone)]
 pub struct BattleCircuit<F: PrimeField> {
@@ -436,12 +440,20 @@ impl BattleCircuit<Fr> {
     /// **Note on RNG**: Uses `thread_rng()` which is cryptographically secure (ChaCha20-based).
     /// For deterministic testing, consider using a seeded RNG from `ark_std::test_rng()`.
     pub fn setup() -> crate::Result<(ProvingKey<Bn254>, VerifyingKey<Bn254>)> {
+        Self::setup_for_grid(GRID_SIZE)
+    }
+
+    /// Setup the circuit for `grid_size` x `grid_size` grids
+    ///
+    /// Keys only prove and verify battles on grids of that size; smaller
+    /// grids keep tests affordable.
+    pub fn setup_for_grid(grid_size: usize) -> crate::Result<(ProvingKey<Bn254>, VerifyingKey<Bn254>)> {
         let rng = &mut thread_rng();
         
         // Create empty circuit for setup
         let circuit = Self {
-            initial_grid: Some(vec![vec![0u8; GRID_SIZE]; GRID_SIZE]),
-            final_grid: Some(vec![vec![0u8; GRID_SIZE]; GRID_SIZE]),
+            initial_grid: Some(vec![vec![0u8; grid_size]; grid_size]),
+            final_grid: Some(vec![vec![0u8; grid_size]; grid_size]),
             commitment_a: Some(Fr::from(0u64)),
             commitment_b: Some(Fr::from(0u64)),
             winner: Some(0),
@@ -455,6 +467,25 @@ impl BattleCircuit<Fr> {
             .map_err(|e| crate::Error::ProofGeneration(format!("Circuit setup failed: {}", e)))
     }
 
+    /// Key cache subdirectory for keys from [`Self::setup_for_grid`]
+    ///
+    /// Covers everything the keys depend on: grid size, step count and
+    /// constraint version.
+    pub fn key_id(grid_size: usize) -> String {
+        format!("battle-{}x{}-s{}-v{}", grid_size, grid_size, BATTLE_STEPS, CIRCUIT_VERSION)
+    }
+
+    /// Load cached keys from `dir`, or run [`Self::setup`] and save them there
+    ///
+    /// Keys are kept under [`Self::key_id`], so keys for a different grid
+    /// size or constraint version are never loaded. Avoids paying the setup
+    /// cost in every process; see [`crate::key_management::setup_or_load`].
+    pub fn setup_or_load(
+        dir: impl AsRef<std::path::Path>,
+    ) -> crate::Result<(ProvingKey<Bn254>, VerifyingKey<Bn254>)> {
+        crate::key_management::setup_or_load(dir, &Self::key_id(GRID_SIZE), Self::setup)
+    }
+
     /// Generate a proof for this circuit instance
     pub fn prove(
         &self,
@@ -469,29 +500,41 @@ impl BattleCircuit<Fr> {
     /// Verify a proof against public inputs
     ///
     /// Public inputs should be in order:
-    /// 1. Initial grid cells (flattened)
-    /// 2. Final grid cells (flattened)
+    /// 1. Initial grid cells (flattened, 8 little-endian bits per cell)
+    /// 2. Final grid cells (flattened, 8 little-endian bits per cell)
     /// 3. Commitment A
     /// 4. Commitment B
-    /// 5. Winner
+    /// 5. Winner (8 little-endian bits)
+    ///
+    /// Returns `Error::PublicInputMismatch` if the number of inputs is wrong.
     pub fn verify(
         vk: &VerifyingKey<Bn254>,
         proof: &crate::Groth16Proof,
         public_inputs: &[Fr],
     ) -> crate::Result<bool> {
+        crate::check_public_inputs(vk, public_inputs)?;
         Groth16::<Bn254>::verify(vk, public_inputs, &proof.proof)
             .map_err(|e| crate::Error::ProofVerification)
     }
     
     /// Helper to construct public inputs vector from circuit components
+    ///
+    /// `UInt8` inputs are allocated bit by bit, so each byte contributes
+    /// eight inputs.
     pub fn public_inputs(&self) -> Vec<Fr> {
+        fn push_byte(inputs: &mut Vec<Fr>, byte: u8) {
+            for i in 0..8 {
+                inputs.push(Fr::from(((byte >> i) & 1) as u64));
+            }
+        }
+        
         let mut inputs = Vec::new();
         
         // Add initial grid (flattened)
         if let Some(ref grid) = self.initial_grid {
             for row in grid {
                 for &cell in row {
-                    inputs.push(Fr::from(cell as u64));
+                    push_byte(&mut inputs, cell);
                 }
             }
         }
@@ -500,7 +543,7 @@ impl BattleCircuit<Fr> {
         if let Some(ref grid) = self.final_grid {
             for row in grid {
                 for &cell in row {
-                    inputs.push(Fr::from(cell as u64));
+                    push_byte(&mut inputs, cell);
                 }
             }
         }
@@ -513,7 +556,7 @@ impl BattleCircuit<Fr> {
             inputs.push(commitment_b);
         }
         if let Some(winner) = self.winner {
-            inputs.push(Fr::from(winner as u64));
+            push_byte(&mut inputs, winner);
         }
         
         inputs
@@ -526,6 +569,12 @@ mod tests {
     use ark_bn254::Fr;
     use ark_relations::r1cs::ConstraintSystem;
     
+    #[test]
+    fn test_key_id_depends_on_grid_size() {
+        assert_eq!(BattleCircuit::<Fr>::key_id(64), format!("battle-64x64-s{}-v{}", BATTLE_STEPS, CIRCUIT_VERSION));
+        assert_ne!(BattleCircuit::<Fr>::key_id(4), BattleCircuit::<Fr>::key_id(64));
+    }
+    
     #[test]
     fn test_battle_circuit_satisfiable() {
         let cs = ConstraintSystem::<Fr>::new_ref();
@@ -558,8 +607,11 @@ mod tests {
             nonce_b: Some(nonce_b),
         };
 
-        circuit.generate_constraints(cs.clone()).unwrap();
+        circuit.clone().generate_constraints(cs.clone()).unwrap();
         assert!(cs.is_satisfied().unwrap());
+        
+        // One public input per allocated instance variable (minus the constant one)
+        assert_eq!(circuit.public_inputs().len(), cs.num_instance_variables() - 1);
     }
     
     #[test]
@@ -107,6 +107,63 @@ pub fn load_verification_key(
         .map_err(|e| crate::Error::KeyManagement(format!("Failed to deserialize verification key: {}", e)))
 }
 
+/// File name of the proving key inside a key directory
+pub const PROVING_KEY_FILE: &str = "proving_key.bin";
+
+/// File name of the verification key inside a key directory
+pub const VERIFICATION_KEY_FILE: &str = "verification_key.bin";
+
+/// Save a proving/verification key pair into a directory
+///
+/// The directory is created if needed, and the keys are written as
+/// `proving_key.bin` and `verification_key.bin` (the ceremony layout).
+pub fn save_keys(
+    pk: &ProvingKey<ark_bn254::Bn254>,
+    vk: &VerifyingKey<ark_bn254::Bn254>,
+    dir: impl AsRef<Path>,
+) -> crate::Result<()> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)
+        .map_err(|e| crate::Error::KeyManagement(format!("Failed to create key directory: {}", e)))?;
+    save_proving_key(pk, dir.join(PROVING_KEY_FILE))?;
+    save_verification_key(vk, dir.join(VERIFICATION_KEY_FILE))
+}
+
+/// Load a proving/verification key pair saved with [`save_keys`]
+pub fn load_keys(
+    dir: impl AsRef<Path>,
+) -> crate::Result<(ProvingKey<ark_bn254::Bn254>, VerifyingKey<ark_bn254::Bn254>)> {
+    let dir = dir.as_ref();
+    let pk = load_proving_key(dir.join(PROVING_KEY_FILE))?;
+    let vk = load_verification_key(dir.join(VERIFICATION_KEY_FILE))?;
+    Ok((pk, vk))
+}
+
+/// Load cached keys for `circuit_id` from `dir`, or run `setup` and cache them
+///
+/// Keys live in `dir/<circuit_id>`, so one key directory can hold keys for
+/// several circuits. The id must change whenever the circuit's constraints
+/// do (see the circuits' `KEY_ID`s); stale keys are then never loaded for a
+/// changed circuit. Used by the circuits' `setup_or_load` so each process
+/// only pays the setup cost once per key directory.
+pub fn setup_or_load<F>(
+    dir: impl AsRef<Path>,
+    circuit_id: &str,
+    setup: F,
+) -> crate::Result<(ProvingKey<ark_bn254::Bn254>, VerifyingKey<ark_bn254::Bn254>)>
+where
+    F: FnOnce() -> crate::Result<(ProvingKey<ark_bn254::Bn254>, VerifyingKey<ark_bn254::Bn254>)>,
+{
+    let dir = dir.as_ref().join(circuit_id);
+    if dir.join(PROVING_KEY_FILE).exists() && dir.join(VERIFICATION_KEY_FILE).exists() {
+        return load_keys(&dir);
+    }
+    
+    let (pk, vk) = setup()?;
+    save_keys(&pk, &vk, &dir)?;
+    Ok((pk, vk))
+}
+
 /// Compute a SHA-256 hash of a proving key for verification
 ///
 /// This can be used to verify key integrity and ensure all participants
@@ -195,6 +252,44 @@ mod tests {
         std::fs::remove_file(vk_path).ok();
     }
 
+    #[test]
+    fn test_setup_or_load_reuses_cached_keys() {
+        use crate::SimpleBattleCircuit;
+        use ark_bn254::Fr;
+        use ark_ff::One;
+        
+        let key_dir = env::temp_dir().join("test_setup_or_load_keys");
+        std::fs::remove_dir_all(&key_dir).ok();
+        
+        // First call generates and saves under the circuit's id
+        let (pk, vk) = SimpleBattleCircuit::setup_or_load(&key_dir).expect("Setup should succeed");
+        let circuit_dir = key_dir.join(SimpleBattleCircuit::KEY_ID);
+        assert!(circuit_dir.join(PROVING_KEY_FILE).exists());
+        assert!(circuit_dir.join(VERIFICATION_KEY_FILE).exists());
+        
+        // Keys cached for another circuit are not picked up
+        let mut called = false;
+        setup_or_load(&key_dir, "other-circuit-v1", || {
+            called = true;
+            SimpleBattleCircuit::setup()
+        }).expect("Setup should succeed");
+        assert!(called);
+        
+        // Second call loads the same keys from disk
+        let (loaded_pk, loaded_vk) = SimpleBattleCircuit::setup_or_load(&key_dir).expect("Load should succeed");
+        assert_eq!(hash_proving_key(&pk).unwrap(), hash_proving_key(&loaded_pk).unwrap());
+        assert_eq!(hash_verification_key(&vk).unwrap(), hash_verification_key(&loaded_vk).unwrap());
+        
+        // The reloaded keys prove and verify
+        let circuit = SimpleBattleCircuit::new(Fr::one(), Fr::one(), 1, 100, 200);
+        let proof = circuit.prove(&loaded_pk).expect("Proof should succeed");
+        let public_inputs = vec![Fr::one(), Fr::one(), Fr::from(1u8)];
+        assert!(SimpleBattleCircuit::verify(&loaded_vk, &proof, &public_inputs).unwrap());
+        
+        // Cleanup
+        std::fs::remove_dir_all(key_dir).ok();
+    }
+
     #[test]
     fn test_key_hashing() {
         let (pk, vk) = BattleCircuit::setup().expect("Setup should succeed");
@@ -46,6 +46,16 @@
 //! let public_inputs = circuit.public_inputs();
 //! assert!(BattleCircuit::verify(&vk, &proof, &public_inputs).unwrap());
 //! ```
+//!
+//! Callers that only need to store and pass proofs as bytes can use
+//! [`ProofService`] instead, which loads the ceremony keys from a key directory
+//! and hides the ark types:
+//!
+//! ```rust,ignore
+//! let service = ProofService::from_key_dir(&key_dir)?;
+//! let proof_bytes = service.prove_battle(&inputs)?;
+//! assert!(service.verify_battle(&proof_bytes, &inputs.public)?);
+//! ```
 
 pub mod battle_circuit;
 pub mod state_circuit;
@@ -60,6 +70,10 @@ pub mod merkle_gadget;
 pub mod poseidon_merkle;
 // Key management for trusted setup ceremony
 pub mod key_management;
+// Byte-level prove/verify facade
+pub mod proof_service;
+// Parallel verification of many proofs under one key
+pub mod batch_verifier;
 
 // Export simplified circuits for backward compatibility
 pub use battle_circuit::BattleCircuit as SimpleBattleCircuit;
@@ -71,6 +85,11 @@ pub use state_constraints::{StateCircuit, NullifierCircuit};
 
 pub use merkle_gadget::{MerklePathGadget, MERKLE_DEPTH};
 pub use poseidon_merkle::{PoseidonMerkleGadget, POSEIDON_MERKLE_DEPTH};
+pub use batch_verifier::{AggregationStats, BatchVerifier};
+pub use proof_service::{
+    BattleProofInputs, BattlePublicInputs, ProofService, SpendProofInputs, SpendPublicInputs,
+    SpendVerifier,
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -95,10 +114,13 @@ pub enum Error {
     
     #[error("Key management error: {0}")]
     KeyManagement(String),
+    
+    #[error("Public input mismatch: expected {expected} inputs, got {got}")]
+    PublicInputMismatch { expected: usize, got: usize },
 }
 
-use ark_bn254::Bn254;
-use ark_groth16::Proof;
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{Proof, VerifyingKey};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
 /// Wrapper for Groth16 proof
@@ -151,4 +173,17 @@ impl Groth16Proof {
     }
 }
 
-
+/// Check that `public_inputs` has the arity the verifying key expects
+///
+/// Groth16 verification would otherwise fail in a way indistinguishable
+/// from an invalid proof.
+pub(crate) fn check_public_inputs(vk: &VerifyingKey<Bn254>, public_inputs: &[Fr]) -> Result<()> {
+    let expected = vk.gamma_abc_g1.len().saturating_sub(1);
+    if public_inputs.len() != expected {
+        return Err(Error::PublicInputMismatch {
+            expected,
+            got: public_inputs.len(),
+        });
+    }
+    Ok(())
+}
@@ -22,7 +22,9 @@
 //! - Plus MDS and addition constraints
 //! Total: ~400-500 constraints per hash, ~500 per tree level
 
+use ark_bn254::Fr;
 use ark_ff::PrimeField;
+use bitcell_crypto::poseidon_hash_two;
 use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
 use ark_r1cs_std::{
     prelude::*,
@@ -283,14 +285,15 @@ impl<F: PrimeField> PoseidonMerkleGadget<F> {
     }
 }
 
-/// Compute Poseidon hash of two field elements (native, for testing)
-pub fn compute_poseidon_merkle_root<F: PrimeField>(
-    leaf: F,
-    path: &[F],
+/// Compute a Poseidon Merkle root natively (outside the circuit)
+///
+/// Nodes are hashed with [`bitcell_crypto::poseidon_hash_two`], the same
+/// permutation the gadget enforces, so roots computed here verify in-circuit.
+pub fn compute_poseidon_merkle_root(
+    leaf: Fr,
+    path: &[Fr],
     directions: &[bool],
-) -> F {
-    // Note: This only works for ark_bn254::Fr due to the params generation
-    // For generic F, we'd need generic params
+) -> Fr {
     let mut current = leaf;
     
     for (sibling, &is_right) in path.iter().zip(directions.iter()) {
@@ -300,137 +303,15 @@ pub fn compute_poseidon_merkle_root<F: PrimeField>(
             (current, *sibling)
         };
         
-        // Use native Poseidon for Fr
-        // For other fields, fall back to algebraic hash
-        current = poseidon_hash_native(left, right);
+        current = poseidon_hash_two(left, right);
     }
     
     current
 }
 
-/// Native Poseidon hash for testing (same algorithm as gadget)
-fn poseidon_hash_native<F: PrimeField>(left: F, right: F) -> F {
-    use sha2::{Sha256, Digest};
-    
-    let total_rounds = FULL_ROUNDS + PARTIAL_ROUNDS;
-    let total_constants = STATE_WIDTH * total_rounds;
-    
-    // Generate round constants
-    let mut round_constants = Vec::with_capacity(total_constants);
-    let mut counter = 0u64;
-    while round_constants.len() < total_constants {
-        let mut hasher = Sha256::new();
-        hasher.update(b"BitCell_Poseidon_RC");
-        hasher.update(&counter.to_le_bytes());
-        hasher.update(&(STATE_WIDTH as u64).to_le_bytes());
-        let hash = hasher.finalize();
-        
-        let mut bytes = [0u8; 32];
-        bytes[..31].copy_from_slice(&hash[..31]);
-        bytes[31] = 0;
-        
-        if let Some(fe) = F::from_random_bytes(&bytes) {
-            round_constants.push(fe);
-        }
-        counter += 1;
-    }
-    
-    // Generate MDS matrix
-    let t = STATE_WIDTH;
-    let mut mds_matrix = vec![vec![F::zero(); t]; t];
-    let x: Vec<F> = (0..t).map(|i| F::from((i + 1) as u64)).collect();
-    let y: Vec<F> = (0..t).map(|i| F::from((t + i + 1) as u64)).collect();
-    for i in 0..t {
-        for j in 0..t {
-            let sum = x[i] + y[j];
-            mds_matrix[i][j] = sum.inverse().expect(
-                "MDS matrix Cauchy construction guarantees non-zero inverse for distinct x_i, y_j"
-            );
-        }
-    }
-    
-    // Initialize state
-    let mut state = vec![F::zero(), left, right];
-    
-    // Apply permutation
-    let rf = FULL_ROUNDS / 2;
-    let rp = PARTIAL_ROUNDS;
-    let mut round_idx = 0;
-    
-    // First half of full rounds
-    for _ in 0..rf {
-        // Add round constants
-        for i in 0..STATE_WIDTH {
-            state[i] += round_constants[round_idx * STATE_WIDTH + i];
-        }
-        // Full S-box
-        for s in state.iter_mut() {
-            let s2 = s.square();
-            let s4 = s2.square();
-            *s = s4 * *s;
-        }
-        // MDS multiply
-        let mut new_state = vec![F::zero(); STATE_WIDTH];
-        for i in 0..STATE_WIDTH {
-            for j in 0..STATE_WIDTH {
-                new_state[i] += mds_matrix[i][j] * state[j];
-            }
-        }
-        state = new_state;
-        round_idx += 1;
-    }
-    
-    // Partial rounds
-    for _ in 0..rp {
-        // Add round constants
-        for i in 0..STATE_WIDTH {
-            state[i] += round_constants[round_idx * STATE_WIDTH + i];
-        }
-        // Partial S-box (first element only)
-        let s2 = state[0].square();
-        let s4 = s2.square();
-        state[0] = s4 * state[0];
-        // MDS multiply
-        let mut new_state = vec![F::zero(); STATE_WIDTH];
-        for i in 0..STATE_WIDTH {
-            for j in 0..STATE_WIDTH {
-                new_state[i] += mds_matrix[i][j] * state[j];
-            }
-        }
-        state = new_state;
-        round_idx += 1;
-    }
-    
-    // Second half of full rounds
-    for _ in 0..rf {
-        // Add round constants
-        for i in 0..STATE_WIDTH {
-            state[i] += round_constants[round_idx * STATE_WIDTH + i];
-        }
-        // Full S-box
-        for s in state.iter_mut() {
-            let s2 = s.square();
-            let s4 = s2.square();
-            *s = s4 * *s;
-        }
-        // MDS multiply
-        let mut new_state = vec![F::zero(); STATE_WIDTH];
-        for i in 0..STATE_WIDTH {
-            for j in 0..STATE_WIDTH {
-                new_state[i] += mds_matrix[i][j] * state[j];
-            }
-        }
-        state = new_state;
-        round_idx += 1;
-    }
-    
-    state[0]
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ark_bn254::Fr;
     use ark_relations::r1cs::ConstraintSystem;
     
     #[test]
@@ -494,13 +375,42 @@ mod tests {
         assert!(!cs.is_satisfied().unwrap());
     }
     
+    #[test]
+    fn test_gadget_matches_crypto_poseidon_tree() {
+        // Four-leaf tree built entirely with the crypto crate's Poseidon
+        let leaves: Vec<Fr> = (10u64..14).map(Fr::from).collect();
+        let left = poseidon_hash_two(leaves[0], leaves[1]);
+        let right = poseidon_hash_two(leaves[2], leaves[3]);
+        let root = poseidon_hash_two(left, right);
+        
+        // Prove leaf 2: sibling leaf 3 on the right, then `left` on the left
+        let path_values = vec![leaves[3], left];
+        let directions = vec![false, true];
+        assert_eq!(compute_poseidon_merkle_root(leaves[2], &path_values, &directions), root);
+        
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let leaf = FpVar::new_witness(cs.clone(), || Ok(leaves[2])).unwrap();
+        let path: Vec<FpVar<Fr>> = path_values.iter()
+            .map(|v| FpVar::new_witness(cs.clone(), || Ok(*v)).unwrap())
+            .collect();
+        let indices: Vec<Boolean<Fr>> = directions.iter()
+            .map(|d| Boolean::new_witness(cs.clone(), || Ok(*d)).unwrap())
+            .collect();
+        let root_var = FpVar::new_input(cs.clone(), || Ok(root)).unwrap();
+        
+        let gadget = PoseidonMerkleGadget::new(cs.clone(), leaf, path, indices).unwrap();
+        gadget.verify_inclusion(&root_var).unwrap();
+        
+        assert!(cs.is_satisfied().unwrap());
+    }
+    
     #[test]
     fn test_poseidon_native_deterministic() {
         let a = Fr::from(123u64);
         let b = Fr::from(456u64);
         
-        let h1 = poseidon_hash_native(a, b);
-        let h2 = poseidon_hash_native(a, b);
+        let h1 = poseidon_hash_two(a, b);
+        let h2 = poseidon_hash_two(a, b);
         
         assert_eq!(h1, h2);
     }
@@ -510,8 +420,8 @@ mod tests {
         let a = Fr::from(1u64);
         let b = Fr::from(2u64);
         
-        let h1 = poseidon_hash_native(a, b);
-        let h2 = poseidon_hash_native(b, a);
+        let h1 = poseidon_hash_two(a, b);
+        let h2 = poseidon_hash_two(b, a);
         
         assert_ne!(h1, h2);
     }
@@ -0,0 +1,341 @@
+//! Byte-level proving and verification facade
+//!
+//! `ProofService` hides the arkworks circuit and key types behind plain
+//! inputs and serialized proofs, so callers such as the node can store and
+//! pass battle proofs around as `Vec<u8>`. `SpendVerifier` does the same for
+//! the private spend proofs carried by transactions.
+
+use crate::battle_constraints::{BattleCircuit, GRID_SIZE};
+use crate::key_management::{self, PROVING_KEY_FILE, VERIFICATION_KEY_FILE};
+use crate::state_constraints::{native_hash_single, native_merkle_root, StateCircuit, MERKLE_DEPTH};
+use crate::Groth16Proof;
+use ark_bn254::{Bn254, Fr};
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::{ProvingKey, VerifyingKey};
+
+/// Public inputs of a battle proof
+///
+/// Commitments are field elements encoded as 32 little-endian bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BattlePublicInputs {
+    pub initial_grid: Vec<Vec<u8>>,
+    pub final_grid: Vec<Vec<u8>>,
+    pub commitment_a: [u8; 32],
+    pub commitment_b: [u8; 32],
+    /// 0 = A, 1 = B, 2 = tie
+    pub winner: u8,
+}
+
+/// Everything needed to prove a battle: the public inputs plus the
+/// private glider patterns and nonces
+#[derive(Debug, Clone)]
+pub struct BattleProofInputs {
+    pub public: BattlePublicInputs,
+    pub pattern_a: Vec<Vec<u8>>,
+    pub pattern_b: Vec<Vec<u8>>,
+    pub nonce_a: [u8; 32],
+    pub nonce_b: [u8; 32],
+}
+
+impl BattlePublicInputs {
+    fn circuit(&self) -> BattleCircuit<Fr> {
+        BattleCircuit::new(
+            self.initial_grid.clone(),
+            self.final_grid.clone(),
+            Fr::from_le_bytes_mod_order(&self.commitment_a),
+            Fr::from_le_bytes_mod_order(&self.commitment_b),
+            self.winner,
+        )
+    }
+}
+
+impl BattleProofInputs {
+    fn circuit(&self) -> BattleCircuit<Fr> {
+        self.public.circuit().with_witnesses(
+            self.pattern_a.clone(),
+            self.pattern_b.clone(),
+            Fr::from_le_bytes_mod_order(&self.nonce_a),
+            Fr::from_le_bytes_mod_order(&self.nonce_b),
+        )
+    }
+}
+
+/// Proves and verifies battles over serialized proofs
+///
+/// The service never runs a setup itself: keys come from the trusted setup
+/// ceremony, either directly or from a key directory (see
+/// [`ProofService::from_key_dir`]).
+pub struct ProofService {
+    battle_pk: ProvingKey<Bn254>,
+    battle_vk: VerifyingKey<Bn254>,
+}
+
+impl ProofService {
+    /// Use existing battle keys (e.g. from the trusted setup ceremony)
+    pub fn with_battle_keys(pk: ProvingKey<Bn254>, vk: VerifyingKey<Bn254>) -> Self {
+        Self {
+            battle_pk: pk,
+            battle_vk: vk,
+        }
+    }
+
+    /// Use battle keys stored in `dir`
+    ///
+    /// Keys are read from `dir/<key id>` (see [`BattleCircuit::key_id`]), the
+    /// layout written by [`key_management::setup_or_load`]. Missing keys are
+    /// an error rather than a reason to run an insecure local setup.
+    pub fn from_key_dir(dir: impl AsRef<std::path::Path>) -> crate::Result<Self> {
+        let dir = dir.as_ref().join(BattleCircuit::<Fr>::key_id(GRID_SIZE));
+        if !dir.join(PROVING_KEY_FILE).exists() || !dir.join(VERIFICATION_KEY_FILE).exists() {
+            return Err(crate::Error::KeyManagement(format!(
+                "No battle keys in {}",
+                dir.display()
+            )));
+        }
+        let (pk, vk) = key_management::load_keys(&dir)?;
+        Ok(Self::with_battle_keys(pk, vk))
+    }
+
+    /// Prove a battle and return the serialized proof
+    pub fn prove_battle(&self, inputs: &BattleProofInputs) -> crate::Result<Vec<u8>> {
+        inputs.circuit().prove(&self.battle_pk)?.serialize()
+    }
+
+    /// Verify a serialized battle proof against its public inputs
+    pub fn verify_battle(
+        &self,
+        proof_bytes: &[u8],
+        public_inputs: &BattlePublicInputs,
+    ) -> crate::Result<bool> {
+        let proof = Groth16Proof::deserialize(proof_bytes)?;
+        BattleCircuit::verify(&self.battle_vk, &proof, &public_inputs.circuit().public_inputs())
+    }
+}
+
+/// Public inputs of a private spend proof
+///
+/// The proof shows that the spender knows the note behind `nullifier`, a
+/// leaf under `old_root`, and replaced it with the note behind `commitment`
+/// to give `new_root` (see [`StateCircuit`]). Field elements are 32
+/// little-endian bytes and must be canonical, so each nullifier has exactly
+/// one encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpendPublicInputs {
+    pub old_root: [u8; 32],
+    pub new_root: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub commitment: [u8; 32],
+}
+
+/// Everything needed to prove a spend: the note being spent, its Merkle
+/// path and the note replacing it
+#[derive(Debug, Clone)]
+pub struct SpendProofInputs {
+    pub public: SpendPublicInputs,
+    pub leaf: [u8; 32],
+    pub path: Vec<[u8; 32]>,
+    pub indices: Vec<bool>,
+    pub new_leaf: [u8; 32],
+}
+
+/// Decode a canonical little-endian field element
+fn field_element(bytes: &[u8; 32]) -> crate::Result<Fr> {
+    let element = Fr::from_le_bytes_mod_order(bytes);
+    if element.into_bigint().to_bytes_le() != bytes.as_slice() {
+        return Err(crate::Error::Serialization(
+            "non-canonical field element".to_string(),
+        ));
+    }
+    Ok(element)
+}
+
+fn field_bytes(element: Fr) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&element.into_bigint().to_bytes_le());
+    bytes
+}
+
+impl SpendPublicInputs {
+    fn circuit(&self) -> crate::Result<StateCircuit<Fr>> {
+        Ok(StateCircuit::new(
+            field_element(&self.old_root)?,
+            field_element(&self.new_root)?,
+            field_element(&self.nullifier)?,
+            field_element(&self.commitment)?,
+        ))
+    }
+}
+
+impl SpendProofInputs {
+    /// Derive the public inputs of spending `leaf` for `new_leaf`
+    ///
+    /// `path` and `indices` must cover the full tree depth.
+    pub fn new(
+        leaf: [u8; 32],
+        path: Vec<[u8; 32]>,
+        indices: Vec<bool>,
+        new_leaf: [u8; 32],
+    ) -> crate::Result<Self> {
+        if path.len() != MERKLE_DEPTH || indices.len() != MERKLE_DEPTH {
+            return Err(crate::Error::Circuit(format!(
+                "spend path must have depth {}",
+                MERKLE_DEPTH
+            )));
+        }
+        let path_elements = path.iter().map(field_element).collect::<crate::Result<Vec<_>>>()?;
+        let (old, new) = (field_element(&leaf)?, field_element(&new_leaf)?);
+
+        let public = SpendPublicInputs {
+            old_root: field_bytes(native_merkle_root(old, &path_elements, &indices)),
+            new_root: field_bytes(native_merkle_root(new, &path_elements, &indices)),
+            nullifier: field_bytes(native_hash_single(old)),
+            commitment: field_bytes(native_hash_single(new)),
+        };
+        Ok(Self { public, leaf, path, indices, new_leaf })
+    }
+
+    /// Prove the spend and return the serialized proof
+    pub fn prove(&self, pk: &ProvingKey<Bn254>) -> crate::Result<Vec<u8>> {
+        let circuit = self.public.circuit()?.with_witnesses(
+            field_element(&self.leaf)?,
+            self.path.iter().map(field_element).collect::<crate::Result<Vec<_>>>()?,
+            self.indices.clone(),
+            field_element(&self.new_leaf)?,
+        );
+        circuit.prove(pk)?.serialize()
+    }
+}
+
+/// Verifies private spend proofs
+///
+/// As with [`ProofService`], the verifying key comes from the trusted setup
+/// ceremony; the verifier never runs a setup itself.
+pub struct SpendVerifier {
+    vk: VerifyingKey<Bn254>,
+}
+
+impl SpendVerifier {
+    /// Use an existing spend verifying key
+    pub fn new(vk: VerifyingKey<Bn254>) -> Self {
+        Self { vk }
+    }
+
+    /// Verify a serialized spend proof against its public inputs
+    pub fn verify(
+        &self,
+        proof_bytes: &[u8],
+        public_inputs: &SpendPublicInputs,
+    ) -> crate::Result<bool> {
+        let proof = Groth16Proof::deserialize(proof_bytes)?;
+        let circuit = public_inputs.circuit()?;
+        StateCircuit::verify(&self.vk, &proof, &circuit.public_inputs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Grid side for proving tests; full-size setup takes minutes
+    const TEST_GRID_SIZE: usize = 4;
+
+    /// An empty grid stays empty, and all-zero patterns and nonces commit to 0
+    fn empty_battle(grid_size: usize) -> BattleProofInputs {
+        let grid = vec![vec![0u8; grid_size]; grid_size];
+        BattleProofInputs {
+            public: BattlePublicInputs {
+                initial_grid: grid.clone(),
+                final_grid: grid,
+                commitment_a: [0u8; 32],
+                commitment_b: [0u8; 32],
+                winner: 2,
+            },
+            pattern_a: vec![vec![0u8; 3]; 3],
+            pattern_b: vec![vec![0u8; 3]; 3],
+            nonce_a: [0u8; 32],
+            nonce_b: [0u8; 32],
+        }
+    }
+
+    #[test]
+    fn test_public_inputs_match_circuit() {
+        let inputs = empty_battle(GRID_SIZE);
+        assert_eq!(
+            inputs.public.circuit().public_inputs(),
+            inputs.circuit().public_inputs()
+        );
+    }
+
+    #[test]
+    fn test_battle_proof_round_trip() {
+        let (pk, vk) = BattleCircuit::<Fr>::setup_for_grid(TEST_GRID_SIZE).unwrap();
+        let service = ProofService::with_battle_keys(pk, vk);
+        let inputs = empty_battle(TEST_GRID_SIZE);
+
+        let proof_bytes = service.prove_battle(&inputs).expect("Proof generation should succeed");
+        assert!(!proof_bytes.is_empty());
+        assert!(service.verify_battle(&proof_bytes, &inputs.public).unwrap());
+
+        // A different claimed winner must not verify
+        let mut wrong_winner = inputs.public.clone();
+        wrong_winner.winner = 0;
+        assert!(!service.verify_battle(&proof_bytes, &wrong_winner).unwrap_or(false));
+
+        // Truncated bytes fail to deserialize
+        assert!(matches!(
+            service.verify_battle(&proof_bytes[..8], &inputs.public),
+            Err(crate::Error::Serialization(_))
+        ));
+    }
+
+    #[test]
+    fn test_spend_proof_round_trip() {
+        let (pk, vk) = StateCircuit::<Fr>::setup().unwrap();
+        let verifier = SpendVerifier::new(vk);
+        let mut leaf = [0u8; 32];
+        leaf[0] = 100;
+        let inputs = SpendProofInputs::new(
+            leaf,
+            vec![[0u8; 32]; MERKLE_DEPTH],
+            vec![false; MERKLE_DEPTH],
+            [7u8; 32],
+        )
+        .unwrap();
+
+        let proof_bytes = inputs.prove(&pk).unwrap();
+        assert!(verifier.verify(&proof_bytes, &inputs.public).unwrap());
+
+        // The proof does not carry over to another nullifier
+        let mut other = inputs.public.clone();
+        other.nullifier[0] ^= 1;
+        assert!(!verifier.verify(&proof_bytes, &other).unwrap_or(false));
+
+        // A non-canonical encoding of the same nullifier (plus the modulus,
+        // which fits in 256 bits) is rejected
+        let mut aliased = inputs.public.clone();
+        let mut carry = 0u16;
+        for (byte, m) in aliased.nullifier.iter_mut().zip(Fr::MODULUS.to_bytes_le()) {
+            let sum = *byte as u16 + m as u16 + carry;
+            *byte = sum as u8;
+            carry = sum >> 8;
+        }
+        assert_eq!(carry, 0);
+        assert!(matches!(
+            verifier.verify(&proof_bytes, &aliased),
+            Err(crate::Error::Serialization(_))
+        ));
+    }
+
+    #[test]
+    fn test_missing_keys_rejected() {
+        let key_dir = std::env::temp_dir().join("test_proof_service_missing_keys");
+        std::fs::remove_dir_all(&key_dir).ok();
+
+        assert!(matches!(
+            ProofService::from_key_dir(&key_dir),
+            Err(crate::Error::KeyManagement(_))
+        ));
+        // No keys are generated in their place
+        assert!(!key_dir.exists());
+    }
+}
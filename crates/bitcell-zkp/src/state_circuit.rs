@@ -157,6 +157,7 @@ impl StateCircuit {
         proof: &crate::Groth16Proof,
         public_inputs: &[Fr],
     ) -> crate::Result<bool> {
+        crate::check_public_inputs(vk, public_inputs)?;
         Groth16::<ark_bn254::Bn254>::verify(vk, &public_inputs, &proof.proof)
             .map_err(|_| crate::Error::ProofVerification)
     }
@@ -188,6 +188,19 @@ fn hash_pair<F: PrimeField>(
     Ok(result)
 }
 
+/// [`hash_single`] computed outside the circuit
+pub(crate) fn native_hash_single<F: PrimeField>(input: F) -> F {
+    input.square() + input + F::one()
+}
+
+/// [`compute_merkle_root`] computed outside the circuit
+pub(crate) fn native_merkle_root<F: PrimeField>(leaf: F, path: &[F], indices: &[bool]) -> F {
+    path.iter().zip(indices).fold(leaf, |current, (&sibling, &index)| {
+        let (left, right) = if index { (sibling, current) } else { (current, sibling) };
+        left.square() + right.square() + left * right + F::one()
+    })
+}
+
 // Groth16 proof generation and verification for Bn254
 use ark_bn254::{Bn254, Fr};
 use ark_groth16::{Groth16, ProvingKey, VerifyingKey};
@@ -243,6 +256,7 @@ impl StateCircuit<Fr> {
         proof: &crate::Groth16Proof,
         public_inputs: &[Fr],
     ) -> crate::Result<bool> {
+        crate::check_public_inputs(vk, public_inputs)?;
         Groth16::<Bn254>::verify(vk, public_inputs, &proof.proof)
             .map_err(|e| crate::Error::ProofVerification)
     }
@@ -295,6 +309,7 @@ impl NullifierCircuit<Fr> {
         proof: &crate::Groth16Proof,
         public_inputs: &[Fr],
     ) -> crate::Result<bool> {
+        crate::check_public_inputs(vk, public_inputs)?;
         Groth16::<Bn254>::verify(vk, public_inputs, &proof.proof)
             .map_err(|e| crate::Error::ProofVerification)
     }
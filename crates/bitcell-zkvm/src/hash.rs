@@ -0,0 +1,25 @@
+//! Poseidon hashing behind the `Hash` opcode
+//!
+//! Circuits must reproduce this encoding exactly:
+//!
+//! 1. `n` memory cells `m_0 .. m_{n-1}` become the BN254 scalars
+//!    `[n, m_0, .., m_{n-1}]`. Each cell is read as an unsigned integer below
+//!    2^64, so no reduction happens. The length prefix keeps `[x]` and
+//!    `[x, 0]` apart.
+//! 2. The scalars are absorbed by `bitcell_crypto::poseidon_hash_many`
+//!    (t = 3, rate 2, zero initial state, one permutation per pair with an
+//!    odd trailing element absorbed alone), and `state[0]` is the digest.
+//! 3. The digest's canonical integer is split into `DIGEST_WORDS`
+//!    little-endian 64-bit limbs, least significant limb first.
+
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+
+/// Number of 64-bit words in a digest
+pub const DIGEST_WORDS: usize = 4;
+
+/// Hash memory words as the `Hash` opcode does
+pub fn poseidon_words(words: &[u64]) -> [u64; DIGEST_WORDS] {
+    let inputs: Vec<Fr> = words.iter().copied().map(Fr::from).collect();
+    bitcell_crypto::poseidon_hash(&inputs).into_bigint().0
+}
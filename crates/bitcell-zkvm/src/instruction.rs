@@ -26,10 +26,13 @@ pub enum OpCode {
     Gt,     // rd = (rs1 > rs2) ? 1 : 0
     Le,     // rd = (rs1 <= rs2) ? 1 : 0
     Ge,     // rd = (rs1 >= rs2) ? 1 : 0
+    SLt,    // rd = (rs1 as i64 < rs2 as i64) ? 1 : 0
+    SGt,    // rd = (rs1 as i64 > rs2 as i64) ? 1 : 0
     
     // Memory
     Load,   // rd = mem[rs1 + imm]
-    Store,  // mem[rs2 + imm] = rs1
+    Store,  // mem[rd + imm] = rs1
+    LoadImm, // rd = imm
     
     // Control Flow
     Jmp,    // pc = imm
@@ -38,10 +41,13 @@ pub enum OpCode {
     Ret,    // return from subroutine
     
     // Crypto (field-friendly operations)
-    Hash,   // rd = hash(rs1, rs2)
+    Hash,   // mem[rd .. rd + 4] = poseidon(mem[rs1 .. rs1 + rs2]), see `hash`
     
     // System
+    Gas,    // rd = gas remaining after this instruction
     Halt,   // stop execution
+    Revert, // abort execution with revert message imm
+    Log,    // emit log: topic = imm, data = mem[rs1 .. rs1 + rd]
 }
 
 /// Instruction format: 4 fields (opcode, rd, rs1, rs2/imm)
@@ -2,27 +2,89 @@
 //!
 //! Executes ZKVM instructions and generates execution traces for ZK proving.
 
-use crate::{gas, Instruction, Memory, OpCode};
+use crate::{gas, hash, Instruction, Memory, OpCode};
 use serde::{Deserialize, Serialize};
 
 /// Execution trace for ZK proof generation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Records the register file at the start of execution and one `TraceStep`
+/// per executed instruction (`Halt` is not recorded). Applying every step's
+/// register writes to `initial_registers` reproduces the final register file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ExecutionTrace {
+    pub initial_registers: Vec<u64>,
     pub steps: Vec<TraceStep>,
     pub gas_used: u64,
+    /// Gas left under the limit when execution stopped
+    pub gas_remaining: u64,
+    /// Logs emitted by `Log` instructions, in execution order
+    pub logs: Vec<LogEntry>,
+}
+
+impl ExecutionTrace {
+    /// Replay the recorded register writes on top of the initial register file
+    pub fn replay_registers(&self) -> Vec<u64> {
+        let mut registers = self.initial_registers.clone();
+        for step in &self.steps {
+            for write in &step.register_writes {
+                registers[write.reg as usize] = write.after;
+            }
+        }
+        registers
+    }
 }
 
 /// Single step in execution trace
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraceStep {
+    /// Address of the executed instruction
     pub pc: usize,
     pub instruction: Instruction,
-    pub registers_before: Vec<u64>,
-    pub registers_after: Vec<u64>,
-    pub memory_reads: Vec<(u32, u64)>,
-    pub memory_writes: Vec<(u32, u64)>,
+    /// Source registers read by the instruction, with their values
+    pub register_reads: Vec<(u8, u64)>,
+    /// Registers whose value changed
+    pub register_writes: Vec<RegisterWrite>,
+    /// Memory access performed by the instruction, if any
+    pub memory: Option<MemoryAccess>,
+    /// Gas charged for this instruction
+    pub gas_cost: u64,
+}
+
+/// A log emitted by a `Log` instruction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub topic: u64,
+    pub data: Vec<u64>,
 }
 
+/// A register value change recorded in a trace step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisterWrite {
+    pub reg: u8,
+    pub before: u64,
+    pub after: u64,
+}
+
+/// A memory access recorded in a trace step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MemoryAccess {
+    Read { addr: u32, value: u64 },
+    Write { addr: u32, before: u64, after: u64 },
+}
+
+/// Default maximum depth of nested CALLs
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 1024;
+
+/// Default addressable memory (in cells)
+pub const DEFAULT_MEMORY_LIMIT: usize = 1024 * 1024;
+
+/// Smallest memory limit accepted by `Interpreter::with_memory_limit`
+///
+/// Covers the fixed layout used by compiled BCL contracts: built-in variables
+/// and parameters below 0x200, storage from 0x200 and scratch/stack space
+/// from 0x1000.
+pub const MIN_MEMORY_LIMIT: usize = 0x2000;
+
 #[derive(Debug)]
 pub enum InterpreterError {
     OutOfGas,
@@ -30,6 +92,9 @@ pub enum InterpreterError {
     DivisionByZero,
     InvalidJump(usize),
     ProgramTooLarge,
+    StackOverflow,
+    MemoryOutOfBounds { addr: u64 },
+    Reverted(String),
 }
 
 impl std::fmt::Display for InterpreterError {
@@ -40,6 +105,9 @@ impl std::fmt::Display for InterpreterError {
             Self::DivisionByZero => write!(f, "Division by zero"),
             Self::InvalidJump(addr) => write!(f, "Invalid jump to address {}", addr),
             Self::ProgramTooLarge => write!(f, "Program too large"),
+            Self::StackOverflow => write!(f, "Call stack overflow"),
+            Self::MemoryOutOfBounds { addr } => write!(f, "Memory access out of bounds: {}", addr),
+            Self::Reverted(msg) => write!(f, "Execution reverted: {}", msg),
         }
     }
 }
@@ -50,10 +118,13 @@ impl std::error::Error for InterpreterError {}
 pub struct Interpreter {
     registers: [u64; 32],
     memory: Memory,
+    memory_limit: usize,
     pc: usize,
     gas_limit: u64,
     gas_used: u64,
     call_stack: Vec<usize>,
+    max_call_depth: usize,
+    revert_messages: Vec<String>,
     trace: ExecutionTrace,
 }
 
@@ -62,18 +133,49 @@ impl Interpreter {
     pub fn new(gas_limit: u64) -> Self {
         Self {
             registers: [0; 32],
-            memory: Memory::new(1024 * 1024), // 1MB address space
+            memory: Memory::new(DEFAULT_MEMORY_LIMIT as u32),
+            memory_limit: DEFAULT_MEMORY_LIMIT,
             pc: 0,
             gas_limit,
             gas_used: 0,
             call_stack: Vec::new(),
-            trace: ExecutionTrace {
-                steps: Vec::new(),
-                gas_used: 0,
-            },
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            revert_messages: Vec::new(),
+            trace: ExecutionTrace::default(),
         }
     }
     
+    /// Set the addressable memory size in cells (default: 1M)
+    ///
+    /// Limits below `MIN_MEMORY_LIMIT` are raised to it so the compiler's
+    /// storage and stack regions always remain addressable. Any existing
+    /// memory contents are discarded.
+    pub fn with_memory_limit(mut self, limit: usize) -> Self {
+        let limit = limit.clamp(MIN_MEMORY_LIMIT, u32::MAX as usize);
+        self.memory = Memory::new(limit as u32);
+        self.memory_limit = limit;
+        self
+    }
+    
+    /// Get the addressable memory size in cells
+    pub fn memory_limit(&self) -> usize {
+        self.memory_limit
+    }
+    
+    /// Set the maximum depth of nested CALLs (default: 1024)
+    pub fn with_max_call_depth(mut self, depth: usize) -> Self {
+        self.max_call_depth = depth;
+        self
+    }
+    
+    /// Set the revert messages referenced by `Revert` instructions
+    ///
+    /// `Revert` with immediate `i` fails with `revert_messages[i]`.
+    pub fn with_revert_messages(mut self, messages: Vec<String>) -> Self {
+        self.revert_messages = messages;
+        self
+    }
+    
     /// Set register value
     pub fn set_register(&mut self, reg: u8, value: u64) {
         if (reg as usize) < 32 {
@@ -97,21 +199,33 @@ impl Interpreter {
         }
         
         self.pc = 0;
+        self.call_stack.clear();
+        self.trace = ExecutionTrace {
+            initial_registers: self.registers.to_vec(),
+            steps: Vec::new(),
+            gas_used: self.gas_used,
+            gas_remaining: self.gas_remaining(),
+            logs: Vec::new(),
+        };
         
         while self.pc < program.len() {
             let inst = program[self.pc];
             
             // Check gas
-            let gas_cost = self.gas_cost(&inst.opcode);
+            let mut gas_cost = self.gas_cost(&inst.opcode);
             if self.gas_used + gas_cost > self.gas_limit {
                 return Err(InterpreterError::OutOfGas);
             }
             self.gas_used += gas_cost;
             
             // Execute instruction
-            let registers_before = self.registers.clone();
-            let mut memory_reads = Vec::new();
-            let mut memory_writes = Vec::new();
+            let pc = self.pc;
+            let registers_before = self.registers;
+            let register_reads = Self::source_registers(&inst)
+                .into_iter()
+                .map(|reg| (reg, self.get_register(reg)))
+                .collect();
+            let mut memory = None;
             
             match inst.opcode {
                 OpCode::Add => {
@@ -203,20 +317,38 @@ impl Interpreter {
                     self.set_register(inst.rd, if lhs >= rhs { 1 } else { 0 });
                     self.pc += 1;
                 }
+                OpCode::SLt => {
+                    let lhs = self.get_register(inst.rs1) as i64;
+                    let rhs = self.get_register(inst.rs2()) as i64;
+                    self.set_register(inst.rd, if lhs < rhs { 1 } else { 0 });
+                    self.pc += 1;
+                }
+                OpCode::SGt => {
+                    let lhs = self.get_register(inst.rs1) as i64;
+                    let rhs = self.get_register(inst.rs2()) as i64;
+                    self.set_register(inst.rd, if lhs > rhs { 1 } else { 0 });
+                    self.pc += 1;
+                }
                 OpCode::Load => {
-                    let addr = self.get_register(inst.rs1) as u32 + inst.imm();
+                    let addr = self.effective_address(inst.rs1, inst.imm())?;
                     let value = self.memory.load(addr)
                         .map_err(InterpreterError::InvalidMemoryAccess)?;
-                    memory_reads.push((addr, value));
+                    memory = Some(MemoryAccess::Read { addr, value });
                     self.set_register(inst.rd, value);
                     self.pc += 1;
                 }
                 OpCode::Store => {
-                    let addr = self.get_register(inst.rs2()) as u32 + inst.imm();
+                    let addr = self.effective_address(inst.rd, inst.imm())?;
                     let value = self.get_register(inst.rs1);
+                    let before = self.memory.load(addr)
+                        .map_err(InterpreterError::InvalidMemoryAccess)?;
                     self.memory.store(addr, value)
                         .map_err(InterpreterError::InvalidMemoryAccess)?;
-                    memory_writes.push((addr, value));
+                    memory = Some(MemoryAccess::Write { addr, before, after: value });
+                    self.pc += 1;
+                }
+                OpCode::LoadImm => {
+                    self.set_register(inst.rd, inst.imm() as u64);
                     self.pc += 1;
                 }
                 OpCode::Jmp => {
@@ -243,6 +375,10 @@ impl Interpreter {
                     if target >= program.len() {
                         return Err(InterpreterError::InvalidJump(target));
                     }
+                    if self.call_stack.len() >= self.max_call_depth {
+                        return Err(InterpreterError::StackOverflow);
+                    }
+                    // Save the return address (instruction after the CALL)
                     self.call_stack.push(self.pc + 1);
                     self.pc = target;
                 }
@@ -255,30 +391,89 @@ impl Interpreter {
                     }
                 }
                 OpCode::Hash => {
-                    // Simple hash: XOR and rotate
-                    let a = self.get_register(inst.rs1);
-                    let b = self.get_register(inst.rs2());
-                    let hash = (a ^ b).rotate_left(17);
-                    self.set_register(inst.rd, hash);
+                    // Charge per input word before touching memory
+                    let len = self.get_register(inst.rs2());
+                    let word_gas = len.saturating_mul(gas::HASH_WORD);
+                    if self.gas_used.saturating_add(word_gas) > self.gas_limit {
+                        return Err(InterpreterError::OutOfGas);
+                    }
+                    self.gas_used += word_gas;
+                    gas_cost += word_gas;
+                    
+                    let start = self.get_register(inst.rs1);
+                    let end = start.saturating_add(len);
+                    if end > self.memory_limit as u64 {
+                        return Err(InterpreterError::MemoryOutOfBounds { addr: end - 1 });
+                    }
+                    let words = (start..end)
+                        .map(|addr| self.memory.load(addr as u32))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(InterpreterError::InvalidMemoryAccess)?;
+                    
+                    let digest = hash::poseidon_words(&words);
+                    self.effective_address(inst.rd, hash::DIGEST_WORDS as u32 - 1)?;
+                    for (i, word) in digest.into_iter().enumerate() {
+                        let addr = self.effective_address(inst.rd, i as u32)?;
+                        self.memory.store(addr, word)
+                            .map_err(InterpreterError::InvalidMemoryAccess)?;
+                    }
+                    self.pc += 1;
+                }
+                OpCode::Gas => {
+                    self.set_register(inst.rd, self.gas_remaining());
                     self.pc += 1;
                 }
                 OpCode::Halt => {
                     break;
                 }
+                OpCode::Log => {
+                    // Charge per data word before touching memory
+                    let count = inst.rd as u32;
+                    let word_gas = u64::from(count).saturating_mul(gas::LOG_WORD);
+                    if self.gas_used.saturating_add(word_gas) > self.gas_limit {
+                        return Err(InterpreterError::OutOfGas);
+                    }
+                    self.gas_used += word_gas;
+                    gas_cost += word_gas;
+                    
+                    let mut data = Vec::with_capacity(count as usize);
+                    for i in 0..count {
+                        let addr = self.effective_address(inst.rs1, i)?;
+                        let value = self.memory.load(addr)
+                            .map_err(InterpreterError::InvalidMemoryAccess)?;
+                        data.push(value);
+                    }
+                    self.trace.logs.push(LogEntry { topic: inst.imm() as u64, data });
+                    self.pc += 1;
+                }
+                OpCode::Revert => {
+                    let index = inst.imm() as usize;
+                    let message = self.revert_messages.get(index)
+                        .cloned()
+                        .unwrap_or_else(|| format!("revert #{}", index));
+                    return Err(InterpreterError::Reverted(message));
+                }
             }
             
             // Record trace step
+            let register_writes = registers_before.iter()
+                .zip(self.registers.iter())
+                .enumerate()
+                .filter(|(_, (before, after))| before != after)
+                .map(|(reg, (&before, &after))| RegisterWrite { reg: reg as u8, before, after })
+                .collect();
             self.trace.steps.push(TraceStep {
-                pc: self.pc,
+                pc,
                 instruction: inst,
-                registers_before: registers_before.to_vec(),
-                registers_after: self.registers.to_vec(),
-                memory_reads,
-                memory_writes,
+                register_reads,
+                register_writes,
+                memory,
+                gas_cost,
             });
         }
         
         self.trace.gas_used = self.gas_used;
+        self.trace.gas_remaining = self.gas_remaining();
         Ok(())
     }
     
@@ -287,11 +482,61 @@ impl Interpreter {
         &self.trace
     }
     
+    /// Write a memory cell directly, e.g. to provide call data before execution
+    pub fn write_memory(&mut self, addr: u32, value: u64) -> Result<(), InterpreterError> {
+        if addr as usize >= self.memory_limit {
+            return Err(InterpreterError::MemoryOutOfBounds { addr: addr as u64 });
+        }
+        self.memory.store(addr, value)
+            .map_err(InterpreterError::InvalidMemoryAccess)
+    }
+    
+    /// Read a memory cell directly, e.g. to inspect contract storage after execution
+    pub fn read_memory(&self, addr: u32) -> Result<u64, InterpreterError> {
+        if addr as usize >= self.memory_limit {
+            return Err(InterpreterError::MemoryOutOfBounds { addr: addr as u64 });
+        }
+        self.memory.load(addr)
+            .map_err(InterpreterError::InvalidMemoryAccess)
+    }
+    
     /// Get gas used
     pub fn gas_used(&self) -> u64 {
         self.gas_used
     }
     
+    /// Gas left under the limit
+    pub fn gas_remaining(&self) -> u64 {
+        self.gas_limit - self.gas_used
+    }
+    
+    /// Compute `reg + offset` and check it against the memory limit
+    ///
+    /// The sum is computed in 64 bits so large register values cannot wrap
+    /// around into a valid address.
+    fn effective_address(&self, base_reg: u8, offset: u32) -> Result<u32, InterpreterError> {
+        let addr = self.get_register(base_reg).saturating_add(offset as u64);
+        if addr >= self.memory_limit as u64 {
+            return Err(InterpreterError::MemoryOutOfBounds { addr });
+        }
+        Ok(addr as u32)
+    }
+    
+    /// Registers read by an instruction, in operand order
+    fn source_registers(inst: &Instruction) -> Vec<u8> {
+        match inst.opcode {
+            OpCode::Add | OpCode::Sub | OpCode::Mul | OpCode::Div | OpCode::Mod
+            | OpCode::And | OpCode::Or | OpCode::Xor
+            | OpCode::Eq | OpCode::Lt | OpCode::Gt | OpCode::Le | OpCode::Ge
+            | OpCode::SLt | OpCode::SGt => vec![inst.rs1, inst.rs2()],
+            OpCode::Hash => vec![inst.rs1, inst.rs2(), inst.rd],
+            OpCode::Not | OpCode::Load | OpCode::Jz | OpCode::Log => vec![inst.rs1],
+            OpCode::Store => vec![inst.rs1, inst.rd],
+            OpCode::LoadImm | OpCode::Jmp | OpCode::Call | OpCode::Ret
+            | OpCode::Gas | OpCode::Halt | OpCode::Revert => vec![],
+        }
+    }
+    
     fn gas_cost(&self, opcode: &OpCode) -> u64 {
         match opcode {
             OpCode::Add => gas::ADD,
@@ -308,14 +553,19 @@ impl Interpreter {
             OpCode::Gt => gas::GT,
             OpCode::Le => gas::LT,  // Same cost as LT
             OpCode::Ge => gas::GT,  // Same cost as GT
+            OpCode::SLt => gas::SLT,
+            OpCode::SGt => gas::SGT,
             OpCode::Load => gas::LOAD,
             OpCode::Store => gas::STORE,
+            OpCode::LoadImm => gas::LOAD_IMM,
             OpCode::Jmp => gas::JMP,
             OpCode::Jz => gas::JZ,
             OpCode::Call => gas::CALL,
             OpCode::Ret => gas::RET,
             OpCode::Hash => gas::HASH,
-            OpCode::Halt => 0,
+            OpCode::Log => gas::LOG,
+            OpCode::Gas => gas::GAS,
+            OpCode::Halt | OpCode::Revert => 0,
         }
     }
 }
@@ -3,12 +3,16 @@
 //! A RISC-like virtual machine for private smart contract execution.
 //! Designed to be field-friendly for ZK-SNARK constraint generation.
 
+pub mod hash;
 mod instruction;
 mod interpreter;
 mod memory;
 
 pub use instruction::{Instruction, OpCode};
-pub use interpreter::{Interpreter, ExecutionTrace, InterpreterError};
+pub use interpreter::{
+    Interpreter, ExecutionTrace, TraceStep, RegisterWrite, MemoryAccess, LogEntry,
+    InterpreterError, DEFAULT_MAX_CALL_DEPTH, DEFAULT_MEMORY_LIMIT, MIN_MEMORY_LIMIT,
+};
 pub use memory::Memory;
 
 /// Gas costs for each instruction type
@@ -25,13 +29,22 @@ pub mod gas {
     pub const EQ: u64 = 1;
     pub const LT: u64 = 1;
     pub const GT: u64 = 1;
+    pub const SLT: u64 = 1;
+    pub const SGT: u64 = 1;
     pub const LOAD: u64 = 3;
     pub const STORE: u64 = 3;
+    pub const LOAD_IMM: u64 = 1;
     pub const JMP: u64 = 2;
     pub const JZ: u64 = 2;
     pub const CALL: u64 = 5;
     pub const RET: u64 = 3;
     pub const HASH: u64 = 20;
+    /// Added to `HASH` for every input word
+    pub const HASH_WORD: u64 = 2;
+    pub const LOG: u64 = 10;
+    /// Added to `LOG` for every data word
+    pub const LOG_WORD: u64 = 2;
+    pub const GAS: u64 = 1;
 }
 
 #[cfg(test)]
@@ -72,6 +85,208 @@ mod tests {
         assert_eq!(interp.get_register(3), 42);
     }
 
+    #[test]
+    fn test_memory_limit_in_bounds() {
+        let mut interp = Interpreter::new(1000).with_memory_limit(0x4000);
+        interp.set_register(1, 7);
+        
+        let program = vec![
+            Instruction::new(OpCode::Store, 0, 1, 0x3FFF),  // last valid cell
+            Instruction::new(OpCode::Load, 2, 0, 0x3FFF),
+            Instruction::new(OpCode::Halt, 0, 0, 0),
+        ];
+        
+        interp.execute(&program).expect("execution failed");
+        assert_eq!(interp.get_register(2), 7);
+    }
+
+    #[test]
+    fn test_memory_limit_out_of_bounds() {
+        let mut interp = Interpreter::new(1000).with_memory_limit(0x4000);
+        let program = vec![
+            Instruction::new(OpCode::Load, 2, 0, 0x4000),
+        ];
+        let result = interp.execute(&program);
+        assert!(matches!(result, Err(InterpreterError::MemoryOutOfBounds { addr: 0x4000 })));
+        
+        // A huge base register must not wrap around to a valid address
+        let mut interp = Interpreter::new(1000).with_memory_limit(0x4000);
+        interp.set_register(1, 1 << 32);
+        let program = vec![
+            Instruction::new(OpCode::Store, 1, 2, 1),  // mem[r1 + 1] = r2
+        ];
+        let result = interp.execute(&program);
+        assert!(matches!(result, Err(InterpreterError::MemoryOutOfBounds { .. })));
+    }
+
+    #[test]
+    fn test_revert_with_message() {
+        let mut interp = Interpreter::new(1000)
+            .with_revert_messages(vec!["first".to_string(), "second".to_string()]);
+        
+        let program = vec![
+            Instruction::new(OpCode::LoadImm, 1, 0, 5),
+            Instruction::new(OpCode::Revert, 0, 0, 1),
+            Instruction::new(OpCode::LoadImm, 1, 0, 6),
+        ];
+        
+        let result = interp.execute(&program);
+        assert!(matches!(result, Err(InterpreterError::Reverted(ref msg)) if msg == "second"));
+        assert_eq!(interp.get_register(1), 5);
+        
+        // Unknown message index still reverts
+        let mut interp = Interpreter::new(1000);
+        let result = interp.execute(&[Instruction::new(OpCode::Revert, 0, 0, 3)]);
+        assert!(matches!(result, Err(InterpreterError::Reverted(ref msg)) if msg == "revert #3"));
+    }
+
+    #[test]
+    fn test_log_emission() {
+        let mut interp = Interpreter::new(1000);
+        interp.write_memory(0x100, 11).unwrap();
+        interp.write_memory(0x101, 22).unwrap();
+        interp.set_register(4, 0x100);
+        
+        let program = vec![
+            Instruction::new(OpCode::Log, 2, 4, 0xABCD),  // topic 0xABCD, data mem[r4..r4+2]
+            Instruction::new(OpCode::Log, 0, 4, 7),       // topic only
+            Instruction::new(OpCode::Halt, 0, 0, 0),
+        ];
+        
+        interp.execute(&program).expect("execution failed");
+        let logs = &interp.trace().logs;
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0], LogEntry { topic: 0xABCD, data: vec![11, 22] });
+        assert_eq!(logs[1], LogEntry { topic: 7, data: vec![] });
+    }
+
+    #[test]
+    fn test_log_charges_per_data_word() {
+        let mut interp = Interpreter::new(1000);
+        interp.set_register(4, 0x100);
+        
+        let program = vec![
+            Instruction::new(OpCode::Log, 5, 4, 1),
+            Instruction::new(OpCode::Halt, 0, 0, 0),
+        ];
+        interp.execute(&program).expect("execution failed");
+        assert_eq!(interp.gas_used(), gas::LOG + 5 * gas::LOG_WORD);
+        
+        // Running out of gas for the data aborts before anything is logged
+        let mut interp = Interpreter::new(gas::LOG + 4 * gas::LOG_WORD);
+        interp.set_register(4, 0x100);
+        let result = interp.execute(&program);
+        assert!(matches!(result, Err(InterpreterError::OutOfGas)));
+        assert!(interp.trace().logs.is_empty());
+    }
+
+    #[test]
+    fn test_gas_opcode_reads_remaining_gas() {
+        let mut interp = Interpreter::new(1000);
+        
+        let program = vec![
+            Instruction::new(OpCode::Gas, 1, 0, 0),
+            Instruction::new(OpCode::Mul, 3, 4, 5),
+            Instruction::new(OpCode::Gas, 2, 0, 0),
+            Instruction::new(OpCode::Halt, 0, 0, 0),
+        ];
+        
+        interp.execute(&program).expect("execution failed");
+        // GAS reports what is left after its own cost, so the second read
+        // also pays for itself
+        assert_eq!(interp.get_register(1), 1000 - gas::GAS);
+        assert_eq!(interp.get_register(1) - interp.get_register(2) - gas::GAS, gas::MUL);
+        assert_eq!(interp.trace().gas_remaining, interp.get_register(2));
+        assert_eq!(interp.trace().gas_remaining, interp.gas_remaining());
+    }
+
+    #[test]
+    fn test_hash_matches_poseidon() {
+        use ark_bn254::Fr;
+        use ark_ff::PrimeField;
+        
+        let mut interp = Interpreter::new(1000);
+        for (i, word) in [1u64, 2, 3].into_iter().enumerate() {
+            interp.write_memory(0x100 + i as u32, word).unwrap();
+        }
+        interp.set_register(1, 0x100);  // input address
+        interp.set_register(2, 3);      // input length
+        interp.set_register(3, 0x200);  // digest address
+        
+        let program = vec![
+            Instruction::new(OpCode::Hash, 3, 1, 2),
+            Instruction::new(OpCode::Halt, 0, 0, 0),
+        ];
+        interp.execute(&program).expect("execution failed");
+        
+        // Length-prefixed input, digest as little-endian limbs
+        let inputs = [3u64, 1, 2, 3].map(Fr::from);
+        let expected = bitcell_crypto::poseidon_hash_many(&inputs).into_bigint().0;
+        let digest: Vec<u64> = (0..hash::DIGEST_WORDS as u32)
+            .map(|i| interp.read_memory(0x200 + i).unwrap())
+            .collect();
+        assert_eq!(digest, expected);
+        assert_eq!(hash::poseidon_words(&[1, 2, 3]), expected);
+        assert_ne!(hash::poseidon_words(&[1, 2, 3, 0]), expected);
+        assert_eq!(interp.gas_used(), gas::HASH + 3 * gas::HASH_WORD);
+    }
+
+    #[test]
+    fn test_load_immediate() {
+        let mut interp = Interpreter::new(1000);
+        
+        let program = vec![
+            Instruction::new(OpCode::LoadImm, 1, 0, 0xFFFF_FFFF),
+            Instruction::new(OpCode::LoadImm, 2, 0, 7),
+            Instruction::new(OpCode::Store, 0, 2, 0x40),  // mem[0x40] = r2
+            Instruction::new(OpCode::Halt, 0, 0, 0),
+        ];
+        
+        interp.execute(&program).expect("execution failed");
+        assert_eq!(interp.get_register(1), 0xFFFF_FFFF);
+        assert_eq!(interp.read_memory(0x40).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_store_base_register_is_rd() {
+        let mut interp = Interpreter::new(1000);
+        
+        let program = vec![
+            Instruction::new(OpCode::LoadImm, 1, 0, 0x40),
+            Instruction::new(OpCode::LoadImm, 2, 0, 11),
+            Instruction::new(OpCode::Store, 1, 2, 8),  // mem[r1 + 8] = r2
+            Instruction::new(OpCode::Halt, 0, 0, 0),
+        ];
+        
+        interp.execute(&program).expect("execution failed");
+        assert_eq!(interp.read_memory(0x48).unwrap(), 11);
+        assert_eq!(interp.read_memory(0x40).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_direct_memory_access() {
+        let mut interp = Interpreter::new(1000).with_memory_limit(0x4000);
+        interp.write_memory(0x10, 99).unwrap();
+        
+        let program = vec![
+            Instruction::new(OpCode::Load, 1, 0, 0x10),
+            Instruction::new(OpCode::Halt, 0, 0, 0),
+        ];
+        
+        interp.execute(&program).expect("execution failed");
+        assert_eq!(interp.get_register(1), 99);
+        assert!(matches!(
+            interp.write_memory(0x4000, 1),
+            Err(InterpreterError::MemoryOutOfBounds { addr: 0x4000 })
+        ));
+    }
+
+    #[test]
+    fn test_memory_limit_minimum() {
+        let interp = Interpreter::new(1000).with_memory_limit(16);
+        assert_eq!(interp.memory_limit(), MIN_MEMORY_LIMIT);
+    }
+
     #[test]
     fn test_conditional_jump() {
         let mut interp = Interpreter::new(1000);
@@ -93,6 +308,99 @@ mod tests {
         assert_eq!(interp.get_register(0), 5);
     }
 
+    #[test]
+    fn test_signed_comparison() {
+        let mut interp = Interpreter::new(1000);
+        
+        interp.set_register(1, (-5i64) as u64);
+        interp.set_register(2, 3);
+        
+        let program = vec![
+            Instruction::new(OpCode::SLt, 3, 1, 2),  // r3 = -5 < 3
+            Instruction::new(OpCode::SGt, 4, 2, 1),  // r4 = 3 > -5
+            Instruction::new(OpCode::SGt, 5, 1, 2),  // r5 = -5 > 3
+            Instruction::new(OpCode::Lt, 6, 1, 2),   // r6 = unsigned(-5) < 3
+            Instruction::new(OpCode::Halt, 0, 0, 0),
+        ];
+        
+        interp.execute(&program).expect("execution failed");
+        assert_eq!(interp.get_register(3), 1);
+        assert_eq!(interp.get_register(4), 1);
+        assert_eq!(interp.get_register(5), 0);
+        // Unsigned comparison treats -5 as a huge value
+        assert_eq!(interp.get_register(6), 0);
+    }
+
+    #[test]
+    fn test_nested_call_returns() {
+        let mut interp = Interpreter::new(1000);
+        interp.set_register(1, 1);
+        
+        let program = vec![
+            Instruction::new(OpCode::Call, 0, 0, 3),     // call A
+            Instruction::new(OpCode::Add, 10, 10, 1),    // back in main
+            Instruction::new(OpCode::Halt, 0, 0, 0),
+            Instruction::new(OpCode::Call, 0, 0, 6),     // A: call B
+            Instruction::new(OpCode::Add, 11, 11, 1),    // A: after B returns
+            Instruction::new(OpCode::Ret, 0, 0, 0),
+            Instruction::new(OpCode::Add, 12, 12, 1),    // B
+            Instruction::new(OpCode::Ret, 0, 0, 0),
+        ];
+        
+        interp.execute(&program).expect("execution failed");
+        assert_eq!(interp.get_register(10), 1);
+        assert_eq!(interp.get_register(11), 1);
+        assert_eq!(interp.get_register(12), 1);
+    }
+
+    #[test]
+    fn test_call_stack_overflow() {
+        let mut interp = Interpreter::new(100_000).with_max_call_depth(16);
+        
+        // Unbounded recursion: the function calls itself
+        let program = vec![
+            Instruction::new(OpCode::Call, 0, 0, 0),
+        ];
+        
+        let result = interp.execute(&program);
+        assert!(matches!(result, Err(InterpreterError::StackOverflow)));
+    }
+
+    #[test]
+    fn test_execution_trace() {
+        let mut interp = Interpreter::new(1000);
+        interp.set_register(1, 7);
+        interp.set_register(2, 5);
+        
+        let program = vec![
+            Instruction::new(OpCode::Add, 3, 1, 2),      // r3 = 12
+            Instruction::new(OpCode::Store, 0, 3, 200),  // mem[200] = r3
+            Instruction::new(OpCode::Load, 4, 0, 200),   // r4 = mem[200]
+            Instruction::new(OpCode::Mul, 5, 4, 2),      // r5 = 60
+            Instruction::new(OpCode::Halt, 0, 0, 0),
+        ];
+        
+        interp.execute(&program).expect("execution failed");
+        let trace = interp.trace();
+        
+        // Halt is not recorded
+        assert_eq!(trace.steps.len(), 4);
+        assert_eq!(trace.steps.iter().map(|s| s.gas_cost).sum::<u64>(), trace.gas_used);
+        
+        let add = &trace.steps[0];
+        assert_eq!(add.pc, 0);
+        assert_eq!(add.register_reads, vec![(1, 7), (2, 5)]);
+        assert_eq!(add.register_writes, vec![RegisterWrite { reg: 3, before: 0, after: 12 }]);
+        assert_eq!(trace.steps[1].memory, Some(MemoryAccess::Write { addr: 200, before: 0, after: 12 }));
+        assert_eq!(trace.steps[2].memory, Some(MemoryAccess::Read { addr: 200, value: 12 }));
+        
+        // Replaying the trace reproduces the final register file
+        let replayed = trace.replay_registers();
+        for reg in 0..32u8 {
+            assert_eq!(replayed[reg as usize], interp.get_register(reg));
+        }
+    }
+
     #[test]
     fn test_gas_metering() {
         let mut interp = Interpreter::new(10); // Only 10 gas
@@ -78,6 +78,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("  CAPTCHA required: {} (WARNING: not implemented - must be false)", faucet_config.require_captcha);
 
     let console = AdminConsole::new(addr)
+        .expect("Failed to initialize admin console")
         .with_faucet(faucet_config)
         .expect("Failed to initialize faucet");
 
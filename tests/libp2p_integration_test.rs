@@ -19,6 +19,7 @@ fn create_test_block(height: u64, num_txs: usize) -> Block {
             gas_limit: 21000,
             gas_price: 1,
             data: vec![],
+            spends: vec![],
             signature: Signature::from(vec![0; 64]),
         };
         transactions.push(tx);
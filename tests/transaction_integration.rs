@@ -65,6 +65,7 @@ fn test_wallet_to_consensus_transaction_conversion() {
         gas_limit,
         gas_price,
         data: Vec::new(),
+        spends: Vec::new(),
         signature: bitcell_crypto::Signature::from_bytes(&[0u8; 64]).unwrap(), // Placeholder
     };
     
@@ -104,6 +105,7 @@ fn test_transaction_serialization() {
         gas_limit,
         gas_price,
         data: Vec::new(),
+        spends: Vec::new(),
         signature: bitcell_crypto::Signature::from_bytes(&[0u8; 64]).unwrap(), // Placeholder
     };
     
@@ -156,6 +158,7 @@ fn test_transaction_hash_deterministic() {
         gas_limit: 21000,
         gas_price: 1000,
         data: vec![1, 2, 3],
+        spends: vec![],
         signature: signature.clone(),
     };
     
@@ -167,6 +170,7 @@ fn test_transaction_hash_deterministic() {
         gas_limit: 21000,
         gas_price: 1000,
         data: vec![1, 2, 3],
+        spends: vec![],
         signature: signature.clone(),
     };
     
@@ -189,6 +193,7 @@ fn test_transaction_hash_unique() {
         gas_limit: 21000,
         gas_price: 1000,
         data: Vec::new(),
+        spends: Vec::new(),
         signature: signature.clone(),
     };
     
@@ -200,6 +205,7 @@ fn test_transaction_hash_unique() {
         gas_limit: 21000,
         gas_price: 1000,
         data: Vec::new(),
+        spends: Vec::new(),
         signature: signature.clone(),
     };
     